@@ -0,0 +1,273 @@
+//! AMF0 encoding
+//!
+//! AMF0 is the format of the script data messages in FLV files, and of the
+//! arguments that `LocalConnection` passes between movies, so values can be
+//! written as well as read.
+
+use std::convert::TryInto;
+
+pub type Error = Box<dyn std::error::Error>;
+
+/// How deeply objects and arrays may be nested before we give up on the
+/// data, so that malicious input can't overflow the stack.
+const MAX_DEPTH: usize = 64;
+
+/// An AMF0 value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScriptValue {
+    Number(f64),
+    Boolean(bool),
+    String(String),
+    Object(Vec<(String, ScriptValue)>),
+    StrictArray(Vec<ScriptValue>),
+
+    /// A date, in milliseconds since the Unix epoch.
+    Date(f64),
+    Null,
+    Undefined,
+}
+
+impl ScriptValue {
+    /// Look up a property of an object value.
+    pub fn get(&self, name: &str) -> Option<&ScriptValue> {
+        match self {
+            ScriptValue::Object(properties) => properties
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            ScriptValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+}
+
+/// Read a sequence of AMF0 values, such as the arguments of a
+/// `LocalConnection` message.
+pub fn read_amf0_values(data: &[u8]) -> Result<Vec<ScriptValue>, Error> {
+    let mut reader = Amf0Reader::new(data);
+    let mut values = vec![];
+    while !reader.data.is_empty() {
+        values.push(reader.read_value()?);
+    }
+    Ok(values)
+}
+
+/// Write a sequence of AMF0 values, which `read_amf0_values` reads back.
+pub fn write_amf0_values(values: &[ScriptValue]) -> Vec<u8> {
+    let mut data = vec![];
+    for value in values {
+        write_amf0_value(&mut data, value);
+    }
+    data
+}
+
+fn write_amf0_string(data: &mut Vec<u8>, s: &str) {
+    // Property names and short strings carry a 16-bit length, so anything
+    // longer is cut short.
+    let bytes = &s.as_bytes()[..s.len().min(u16::MAX as usize)];
+    data.extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+    data.extend_from_slice(bytes);
+}
+
+fn write_amf0_value(data: &mut Vec<u8>, value: &ScriptValue) {
+    match value {
+        ScriptValue::Number(n) => {
+            data.push(0x00);
+            data.extend_from_slice(&n.to_be_bytes());
+        }
+        ScriptValue::Boolean(b) => data.extend_from_slice(&[0x01, *b as u8]),
+        ScriptValue::String(s) if s.len() > u16::MAX as usize => {
+            data.push(0x0c);
+            data.extend_from_slice(&(s.len() as u32).to_be_bytes());
+            data.extend_from_slice(s.as_bytes());
+        }
+        ScriptValue::String(s) => {
+            data.push(0x02);
+            write_amf0_string(data, s);
+        }
+        ScriptValue::Object(properties) => {
+            data.push(0x03);
+            for (name, value) in properties {
+                write_amf0_string(data, name);
+                write_amf0_value(data, value);
+            }
+            data.extend_from_slice(&[0, 0, 0x09]);
+        }
+        ScriptValue::StrictArray(values) => {
+            data.push(0x0a);
+            data.extend_from_slice(&(values.len() as u32).to_be_bytes());
+            for value in values {
+                write_amf0_value(data, value);
+            }
+        }
+        ScriptValue::Date(time) => {
+            data.push(0x0b);
+            data.extend_from_slice(&time.to_be_bytes());
+            data.extend_from_slice(&[0, 0]);
+        }
+        ScriptValue::Null => data.push(0x05),
+        ScriptValue::Undefined => data.push(0x06),
+    }
+}
+
+/// A reader for the subset of AMF0 used by FLV script data and
+/// `LocalConnection`.
+pub struct Amf0Reader<'a> {
+    data: &'a [u8],
+
+    /// How many objects and arrays enclose the value being read.
+    depth: usize,
+}
+
+impl<'a> Amf0Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, depth: 0 }
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() < len {
+            return Err("Unexpected end of AMF0 data".into());
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Error> {
+        Ok(u16::from_be_bytes(self.read_bytes(2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_be_bytes(self.read_bytes(4)?.try_into().unwrap()))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, Error> {
+        Ok(f64::from_be_bytes(self.read_bytes(8)?.try_into().unwrap()))
+    }
+
+    fn read_string(&mut self, len: usize) -> Result<String, Error> {
+        Ok(String::from_utf8_lossy(self.read_bytes(len)?).into_owned())
+    }
+
+    /// Read object properties up to the object end marker.
+    fn read_properties(&mut self) -> Result<Vec<(String, ScriptValue)>, Error> {
+        let mut properties = vec![];
+        loop {
+            let len = self.read_u16()?.into();
+            if len == 0 && self.data.first() == Some(&0x09) {
+                self.read_u8()?;
+                return Ok(properties);
+            }
+            let name = self.read_string(len)?;
+            properties.push((name, self.read_value()?));
+        }
+    }
+
+    /// Read a value that contains other values, such as an object.
+    fn read_nested<T>(
+        &mut self,
+        read: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        if self.depth >= MAX_DEPTH {
+            return Err("AMF0 data is nested too deeply".into());
+        }
+        self.depth += 1;
+        let result = read(self);
+        self.depth -= 1;
+        result
+    }
+
+    pub fn read_value(&mut self) -> Result<ScriptValue, Error> {
+        Ok(match self.read_u8()? {
+            0x00 => ScriptValue::Number(self.read_f64()?),
+            0x01 => ScriptValue::Boolean(self.read_u8()? != 0),
+            0x02 => {
+                let len = self.read_u16()?.into();
+                ScriptValue::String(self.read_string(len)?)
+            }
+            0x03 => ScriptValue::Object(self.read_nested(Self::read_properties)?),
+            0x05 => ScriptValue::Null,
+            0x06 => ScriptValue::Undefined,
+            0x08 => {
+                // The count of an ECMA array is only a hint.
+                self.read_u32()?;
+                ScriptValue::Object(self.read_nested(Self::read_properties)?)
+            }
+            0x0a => {
+                let len = self.read_u32()?;
+                ScriptValue::StrictArray(self.read_nested(|reader| {
+                    let mut values = vec![];
+                    for _ in 0..len {
+                        values.push(reader.read_value()?);
+                    }
+                    Ok(values)
+                })?)
+            }
+            0x0b => {
+                let time = self.read_f64()?;
+                // Time zone, which is always ignored.
+                self.read_u16()?;
+                ScriptValue::Date(time)
+            }
+            0x0c => {
+                let len = self.read_u32()? as usize;
+                ScriptValue::String(self.read_string(len)?)
+            }
+            marker => return Err(format!("Unsupported AMF0 type {}", marker).into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let values = vec![
+            ScriptValue::String("hello".to_string()),
+            ScriptValue::Number(-1.5),
+            ScriptValue::Object(vec![
+                ("a".to_string(), ScriptValue::Boolean(true)),
+                (
+                    "b".to_string(),
+                    ScriptValue::StrictArray(vec![ScriptValue::Null, ScriptValue::Undefined]),
+                ),
+            ]),
+            ScriptValue::Date(1_000_000.0),
+            ScriptValue::String("x".repeat(70_000)),
+        ];
+
+        assert_eq!(
+            read_amf0_values(&write_amf0_values(&values)).unwrap(),
+            values
+        );
+    }
+
+    #[test]
+    fn depth_limit() {
+        let nested = |depth| {
+            let mut data = vec![];
+            for _ in 0..depth {
+                data.extend_from_slice(&[0x0a, 0, 0, 0, 1]);
+            }
+            data.push(0x05);
+            data
+        };
+
+        assert!(read_amf0_values(&nested(MAX_DEPTH)).is_ok());
+        assert!(read_amf0_values(&nested(MAX_DEPTH + 1)).is_err());
+        // Objects that each hold the next one in a property, with no end in sight.
+        assert!(read_amf0_values(&b"\x03\x00\x01a".repeat(100_000)).is_err());
+    }
+}
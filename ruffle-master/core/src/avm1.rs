@@ -11,7 +11,7 @@ use crate::tag_utils::SwfSlice;
 
 #[cfg(test)]
 #[macro_use]
-mod test_utils;
+pub(crate) mod test_utils;
 
 pub mod activation;
 mod callable_value;
@@ -58,9 +58,18 @@ macro_rules! avm_debug {
 macro_rules! avm_warn {
     ($activation: ident, $($arg:tt)*) => (
         if cfg!(feature = "avm_debug") {
-            log::warn!("{} -- in {}", format!($($arg)*), $activation.id)
+            log::warn!(
+                "{} -- in {}{}",
+                format!($($arg)*),
+                $activation.id,
+                $activation.context.log_context_description()
+            )
         } else {
-            log::warn!($($arg)*)
+            log::warn!(
+                "{}{}",
+                format!($($arg)*),
+                $activation.context.log_context_description()
+            )
         }
     )
 }
@@ -69,9 +78,18 @@ macro_rules! avm_warn {
 macro_rules! avm_error {
     ($activation: ident, $($arg:tt)*) => (
         if cfg!(feature = "avm_debug") {
-            log::error!("{} -- in {}", format!($($arg)*), $activation.id)
+            log::error!(
+                "{} -- in {}{}",
+                format!($($arg)*),
+                $activation.id,
+                $activation.context.log_context_description()
+            )
         } else {
-            log::error!($($arg)*)
+            log::error!(
+                "{}{}",
+                format!($($arg)*),
+                $activation.context.log_context_description()
+            )
         }
     )
 }
@@ -113,6 +131,10 @@ pub struct Avm1<'gc> {
     /// is raised. This defaults to 256 but can be changed per movie.
     max_recursion_depth: u16,
 
+    /// Whether calls to unimplemented builtins throw an `Error` in the movie,
+    /// rather than only logging a warning.
+    strict_mode: bool,
+
     /// Whether a Mouse listener has been registered.
     /// Used to prevent scrolling on web.
     has_mouse_listener: bool,
@@ -141,6 +163,7 @@ impl<'gc> Avm1<'gc> {
             ],
             halted: false,
             max_recursion_depth: 255,
+            strict_mode: false,
             has_mouse_listener: false,
 
             #[cfg(feature = "avm_debug")]
@@ -423,6 +446,14 @@ impl<'gc> Avm1<'gc> {
         self.max_recursion_depth = max_recursion_depth
     }
 
+    pub fn strict_mode(&self) -> bool {
+        self.strict_mode
+    }
+
+    pub fn set_strict_mode(&mut self, strict_mode: bool) {
+        self.strict_mode = strict_mode
+    }
+
     pub fn broadcaster_functions(&self) -> BroadcasterFunctions<'gc> {
         self.broadcaster_functions
     }
@@ -450,9 +481,13 @@ impl<'gc> Avm1<'gc> {
 pub fn root_error_handler<'gc>(activation: &mut Activation<'_, 'gc, '_>, error: Error<'gc>) {
     if let Error::ThrownValue(error) = &error {
         let message = error
-            .coerce_to_string(activation)
+            .to_trace_string(activation)
             .unwrap_or_else(|_| "undefined".into());
         activation.context.log.avm_trace(&message);
+        let stack_trace = activation.id.stack_trace();
+        activation
+            .context
+            .report_script_error(message.to_string(), stack_trace);
     } else {
         log::error!("{}", error);
     }
@@ -7,11 +7,14 @@ use crate::avm1::scope::Scope;
 use crate::avm1::{
     fscommand, globals, scope, skip_actions, start_drag, AvmString, ScriptObject, Value,
 };
-use crate::backend::navigator::{NavigationMethod, RequestOptions};
-use crate::context::UpdateContext;
+use crate::backend::navigator::NavigationMethod;
+use crate::config::StageQuality;
+use crate::context::{LogContext, UpdateContext};
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer};
-use crate::ecma_conversions::f64_to_wrapping_u32;
+use crate::ecma_conversions::f64_to_wrapping_i32;
+use crate::net::{self, Request};
 use crate::tag_utils::SwfSlice;
+use crate::telemetry::AvmVersion;
 use crate::vminterface::Instantiator;
 use crate::{avm_error, avm_warn};
 use gc_arena::{Gc, GcCell, MutationContext};
@@ -25,7 +28,6 @@ use std::fmt;
 use swf::avm1::read::Reader;
 use swf::avm1::types::{Action, CatchVar, Function, TryBlock};
 use swf::SwfStr;
-use url::form_urlencoded;
 
 macro_rules! avm_debug {
     ($avm: expr, $($arg:tt)*) => (
@@ -168,6 +170,19 @@ impl<'a> ActivationIdentifier<'a> {
     pub fn depth(&self) -> u16 {
         self.depth
     }
+
+    /// The names of this activation and of every activation above it,
+    /// outermost first.
+    pub fn stack_trace(&self) -> Vec<String> {
+        let mut stack_trace = vec![];
+        let mut frame = Some(self);
+        while let Some(id) = frame {
+            stack_trace.push(id.name.to_string());
+            frame = id.parent;
+        }
+        stack_trace.reverse();
+        stack_trace
+    }
 }
 
 unsafe impl<'gc> gc_arena::Collect for ActivationIdentifier<'gc> {
@@ -246,7 +261,7 @@ impl Drop for Activation<'_, '_, '_> {
 impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     #[allow(clippy::too_many_arguments)]
     pub fn from_action(
-        context: UpdateContext<'a, 'gc, 'gc_context>,
+        mut context: UpdateContext<'a, 'gc, 'gc_context>,
         id: ActivationIdentifier<'a>,
         swf_version: u8,
         scope: GcCell<'gc, Scope<'gc>>,
@@ -257,6 +272,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         arguments: Option<Object<'gc>>,
     ) -> Self {
         avm_debug!(context.avm1, "START {}", id);
+        context.log_context = LogContext::for_clip(base_clip);
         Self {
             context,
             id,
@@ -304,7 +320,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     /// This is used by tests and by callback methods (`onEnterFrame`) to create a base
     /// activation frame with access to the global context.
     pub fn from_nothing(
-        context: UpdateContext<'a, 'gc, 'gc_context>,
+        mut context: UpdateContext<'a, 'gc, 'gc_context>,
         id: ActivationIdentifier<'a>,
         swf_version: u8,
         globals: Object<'gc>,
@@ -317,6 +333,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         );
         let empty_constant_pool = GcCell::allocate(context.gc_context, Vec::new());
         avm_debug!(context.avm1, "START {}", id);
+        context.log_context = LogContext::for_clip(base_clip);
 
         Self {
             context,
@@ -762,31 +779,20 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let target = self.target_clip_or_root()?;
 
         // The parameter can be a frame # or a path to a movie clip with a frame number.
-        let mut call_frame = None;
-        if let Value::Number(frame) = arg {
+        let call_frame = if let Value::Number(frame) = arg {
             // Frame # on the current clip.
-            if let Some(target) = target.as_movie_clip() {
-                call_frame = Some((target, f64_to_wrapping_u32(frame)));
-            }
+            target
+                .as_movie_clip()
+                .map(|target| (target, f64_to_wrapping_i32(frame)))
         } else {
             // An optional path to a movieclip and a frame #/label, such as "/clip:framelabel".
             let frame_path = arg.coerce_to_string(self)?;
-            if let Some((clip, frame)) = self.resolve_variable_path(target, &frame_path)? {
-                if let Some(clip) = clip.as_display_object().and_then(|o| o.as_movie_clip()) {
-                    if let Ok(frame) = frame.parse().map(f64_to_wrapping_u32) {
-                        // First try to parse as a frame number.
-                        call_frame = Some((clip, frame));
-                    } else if let Some(frame) = clip.frame_label_to_number(&frame) {
-                        // Otherwise, it's a frame label.
-                        call_frame = Some((clip, frame.into()));
-                    }
-                }
-            }
+            self.resolve_frame_path(target, &frame_path)?
         };
 
         if let Some((clip, frame)) = call_frame {
-            if frame <= u32::from(u16::MAX) {
-                for action in clip.actions_on_frame(&mut self.context, frame as u16) {
+            if let Ok(frame) = u16::try_from(frame) {
+                for action in clip.actions_on_frame(&mut self.context, frame) {
                     let _ = self.run_child_frame_for_action(
                         "[Frame Call]",
                         clip.into(),
@@ -1235,7 +1241,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         if target.starts_with("_level") && target.len() > 6 {
             match target[6..].parse::<u32>() {
                 Ok(level_id) => {
-                    let fetch = self.context.navigator.fetch(&url, RequestOptions::get());
+                    let fetch = net::fetch(&self.context, Request::get(url.to_string()));
                     let level = self.resolve_level(level_id);
 
                     if url.is_empty() {
@@ -1313,11 +1319,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                     .unwrap()
                     .object()
                     .coerce_to_object(self);
-                let (url, opts) = self.locals_into_request_options(
-                    Cow::Borrowed(&url),
+                let request = self.locals_into_request(
+                    url.to_string(),
                     NavigationMethod::from_send_vars_method(swf_method),
                 );
-                let fetch = self.context.navigator.fetch(&url, opts);
+                let fetch = net::fetch_data(&self.context, request);
                 let process = self.context.load_manager.load_form_into_object(
                     self.context.player.clone().unwrap(),
                     target_obj,
@@ -1330,23 +1336,24 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return Ok(FrameControl::Continue);
         } else if is_target_sprite {
             if let Some(clip_target) = clip_target {
-                let (url, opts) = self.locals_into_request_options(
-                    Cow::Borrowed(&url),
+                let request = self.locals_into_request(
+                    url.to_string(),
                     NavigationMethod::from_send_vars_method(swf_method),
                 );
 
-                if url.is_empty() {
+                if request.url().is_empty() {
                     //Blank URL on movie loads = unload!
                     if let Some(mut mc) = clip_target.as_movie_clip() {
                         mc.replace_with_movie(self.context.gc_context, None)
                     }
                 } else {
-                    let fetch = self.context.navigator.fetch(&url, opts);
+                    let url = request.url().to_string();
+                    let fetch = net::fetch(&self.context, request);
                     let process = self.context.load_manager.load_movie_into_clip(
                         self.context.player.clone().unwrap(),
                         clip_target,
                         fetch,
-                        url.to_string(),
+                        url,
                         None,
                     );
                     self.context.navigator.spawn_future(process);
@@ -1357,7 +1364,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             // target of `_level#` indicates a `loadMovieNum` call.
             match window_target[6..].parse::<u32>() {
                 Ok(level_id) => {
-                    let fetch = self.context.navigator.fetch(&url, RequestOptions::get());
+                    let fetch = net::fetch(&self.context, Request::get(url.to_string()));
                     let level = self.resolve_level(level_id);
 
                     let process = self.context.load_manager.load_movie_into_clip(
@@ -1414,9 +1421,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
         // Version 4+ gotoAndPlay/gotoAndStop
         // Param can either be a frame number or a frame label.
+        // The frame is popped even if there's nothing to direct.
+        let frame = self.context.avm1.pop();
         if let Some(clip) = self.target_clip() {
             if let Some(clip) = clip.as_movie_clip() {
-                let frame = self.context.avm1.pop();
                 let _ = globals::movie_clip::goto_frame(
                     clip,
                     self,
@@ -1926,45 +1934,45 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             new_target_clip = None;
         }
 
-        self.set_target_clip(new_target_clip);
-
-        let scope = self.scope_cell();
-        let clip_obj = self.target_clip_or_root()?.object().coerce_to_object(self);
-
-        self.set_scope(Scope::new_target_scope(
-            scope,
-            clip_obj,
-            self.context.gc_context,
-        ));
-        Ok(FrameControl::Continue)
+        self.retarget(new_target_clip)
     }
 
     fn action_set_target2(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let target = self.context.avm1.pop();
         match target {
-            Value::String(target) => {
-                return self.action_set_target(&target);
-            }
+            Value::String(target) => self.action_set_target(&target),
             Value::Undefined => {
                 // Reset
                 let base_clip = self.base_clip();
-                self.set_target_clip(Some(base_clip));
+                self.retarget(Some(base_clip))
             }
-            Value::Object(o) => {
-                if let Some(clip) = o.as_display_object() {
-                    // Movieclips can be targeted directly
-                    self.set_target_clip(Some(clip));
-                } else {
-                    // Other objects get coerced to string
+            Value::Object(o) => match o.as_display_object() {
+                // Movieclips can be targeted directly, unless they've been
+                // removed, in which case they're looked up by their path.
+                Some(clip) if !clip.removed() => self.retarget(Some(clip)),
+                // Other objects get coerced to string
+                _ => {
                     let target = target.coerce_to_string(self)?;
-                    return self.action_set_target(&target);
+                    self.action_set_target(&target)
                 }
-            }
+            },
             _ => {
                 let target = target.coerce_to_string(self)?;
-                return self.action_set_target(&target);
+                self.action_set_target(&target)
             }
-        };
+        }
+    }
+
+    /// Directs the timeline actions that follow at a new target clip, as done
+    /// by `SetTarget` and `tellTarget`.
+    ///
+    /// With no target, timeline actions silently do nothing, while variables
+    /// are looked up on the root.
+    fn retarget(
+        &mut self,
+        target: Option<DisplayObject<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error<'gc>> {
+        self.set_target_clip(target);
 
         let scope = self.scope_cell();
         let clip_obj = self.target_clip_or_root()?.object().coerce_to_object(self);
@@ -2146,7 +2154,11 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn toggle_quality(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
-        // TODO(Herschel): Noop for now? Could chang anti-aliasing on render backend.
+        // Flips between high and low quality, like `toggleHighQuality()`.
+        *self.context.quality = match *self.context.quality {
+            StageQuality::Low => StageQuality::High,
+            _ => StageQuality::Low,
+        };
         Ok(FrameControl::Continue)
     }
 
@@ -2179,13 +2191,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn action_trace(&mut self) -> Result<FrameControl<'gc>, Error<'gc>> {
         let val = self.context.avm1.pop();
-        // trace always prints "undefined" even though SWF6 and below normally
-        // coerce undefined to "".
-        let out = if val == Value::Undefined {
-            "undefined".into()
-        } else {
-            val.coerce_to_string(self)?
-        };
+        let out = val.to_trace_string(self)?;
         self.context.log.avm_trace(&out);
         Ok(FrameControl::Continue)
     }
@@ -2200,12 +2206,17 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn action_wait_for_frame(
         &mut self,
-        _frame: u16,
+        frame: u16,
         num_actions_to_skip: u8,
         r: &mut Reader<'_>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        // TODO(Herschel): Always true for now.
-        let loaded = true;
+        // The frame is 0-based, not 1-based. The frames of a missing target
+        // never load.
+        let loaded = self
+            .target_clip()
+            .and_then(|clip| clip.as_movie_clip())
+            .map(|clip| frame < clip.frames_loaded())
+            .unwrap_or(false);
         if !loaded {
             // Note that the offset is given in # of actions, NOT in bytes.
             // Read the actions and toss them away.
@@ -2219,9 +2230,23 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         num_actions_to_skip: u8,
         r: &mut Reader<'_>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        // TODO(Herschel): Always true for now.
-        let _frame_num = self.context.avm1.pop().coerce_to_f64(self)? as u16;
-        let loaded = true;
+        // Param can either be a frame number or a frame path. The frames of a
+        // missing target never load.
+        let frame = self.context.avm1.pop();
+        let loaded = if let Some(target) = self.target_clip() {
+            let frame = match frame {
+                Value::String(path) => self.resolve_frame_path(target, &path)?,
+                frame => {
+                    let frame = f64_to_wrapping_i32(frame.coerce_to_f64(self)?);
+                    target.as_movie_clip().map(|clip| (clip, frame))
+                }
+            };
+            frame
+                .map(|(clip, frame)| frame <= i32::from(clip.frames_loaded()))
+                .unwrap_or(false)
+        } else {
+            false
+        };
         if !loaded {
             // Note that the offset is given in # of actions, NOT in bytes.
             // Read the actions and toss them away.
@@ -2379,40 +2404,20 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         form_values
     }
 
-    /// Construct request options for a fetch operation that may sends object properties as
+    /// Construct a request for a fetch operation that may send object properties as
     /// form data in the request body or URL.
-    pub fn object_into_request_options<'c>(
+    pub fn object_into_request(
         &mut self,
         object: Object<'gc>,
-        url: Cow<'c, str>,
+        url: String,
         method: Option<NavigationMethod>,
-    ) -> (Cow<'c, str>, RequestOptions) {
+    ) -> Request {
         match method {
             Some(method) => {
                 let vars = self.object_into_form_values(object);
-                let qstring = form_urlencoded::Serializer::new(String::new())
-                    .extend_pairs(vars.iter())
-                    .finish();
-
-                match method {
-                    NavigationMethod::Get if url.find('?').is_none() => (
-                        Cow::Owned(format!("{}?{}", url, qstring)),
-                        RequestOptions::get(),
-                    ),
-                    NavigationMethod::Get => (
-                        Cow::Owned(format!("{}&{}", url, qstring)),
-                        RequestOptions::get(),
-                    ),
-                    NavigationMethod::Post => (
-                        url,
-                        RequestOptions::post(Some((
-                            qstring.as_bytes().to_owned(),
-                            "application/x-www-form-urlencoded".to_string(),
-                        ))),
-                    ),
-                }
+                Request::with_form_values(url, &vars, Some(method))
             }
-            None => (url, RequestOptions::get()),
+            None => Request::get(url),
         }
     }
 
@@ -2428,16 +2433,38 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.object_into_form_values(locals)
     }
 
-    /// Construct request options for a fetch operation that may send locals as
+    /// Construct a request for a fetch operation that may send locals as
     /// form data in the request body or URL.
-    pub fn locals_into_request_options<'c>(
+    pub fn locals_into_request(
         &mut self,
-        url: Cow<'c, str>,
+        url: String,
         method: Option<NavigationMethod>,
-    ) -> (Cow<'c, str>, RequestOptions) {
+    ) -> Request {
         let scope = self.scope_cell();
         let locals = scope.read().locals_cell();
-        self.object_into_request_options(locals, url, method)
+        self.object_into_request(locals, url, method)
+    }
+
+    /// Report the use of a builtin that Ruffle does not implement yet.
+    ///
+    /// This logs a warning naming the builtin and reports it to the telemetry
+    /// sink. In strict mode, it also throws an `Error` into the movie, which
+    /// the movie is free to catch.
+    pub fn unimplemented(&mut self, name: &str) -> Result<(), Error<'gc>> {
+        avm_warn!(self, "{}: Unimplemented", name);
+        self.context.telemetry.unimplemented(AvmVersion::Avm1, name);
+
+        if self.context.avm1.strict_mode() {
+            let message = AvmString::new(
+                self.context.gc_context,
+                format!("{} is not implemented", name),
+            );
+            let constructor = self.context.avm1.prototypes.error_constructor;
+            let error = constructor.construct(self, &[message.into()])?;
+            return Err(Error::ThrownValue(error));
+        }
+
+        Ok(())
     }
 
     /// Resolves a target value to a display object, relative to a starting display object.
@@ -2651,6 +2678,54 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         }
     }
 
+    /// Resolves a frame path, such as `/clip:12` or `_parent.clip:label`, to
+    /// the movie clip it directs and the number of the frame it names.
+    ///
+    /// The clip on the left of the right-most : or . is first looked up from
+    /// `start`, so that code in a `tellTarget` block directs the clips around
+    /// its target, and then on the scope chain like a variable path. A path
+    /// with no clip names a frame of `start`. Returns `None` if the clip or
+    /// the frame label doesn't exist.
+    pub fn resolve_frame_path(
+        &mut self,
+        start: DisplayObject<'gc>,
+        path: &str,
+    ) -> Result<Option<(MovieClip<'gc>, i32)>, Error<'gc>> {
+        let (clip, frame) = match path.rfind(|c| c == ':' || c == '.') {
+            Some(index) => {
+                let root = start.avm1_root(&self.context)?;
+                let start_object = start.object().coerce_to_object(self);
+                let clip =
+                    match self.resolve_target_path(root, start_object, &path[..index], true)? {
+                        Some(clip) => Some(clip),
+                        None => self
+                            .resolve_variable_path(start, path)?
+                            .map(|(clip, _)| clip),
+                    };
+                (
+                    clip.and_then(|clip| clip.as_display_object()),
+                    &path[index + 1..],
+                )
+            }
+            None => (Some(start), path),
+        };
+
+        let clip = match clip.and_then(|clip| clip.as_movie_clip()) {
+            Some(clip) => clip,
+            None => return Ok(None),
+        };
+
+        if let Ok(frame) = frame.parse().map(f64_to_wrapping_i32) {
+            // First try to parse as a frame number.
+            Ok(Some((clip, frame)))
+        } else {
+            // Otherwise, it's a frame label.
+            Ok(clip
+                .frame_label_to_number(frame)
+                .map(|frame| (clip, i32::from(frame))))
+        }
+    }
+
     /// Gets the value referenced by a target path string.
     ///
     /// This can be a raw variable name, a slash path, a dot path, or weird combination thereof.
@@ -34,14 +34,18 @@ pub mod gradient_bevel_filter;
 pub mod gradient_glow_filter;
 mod key;
 mod load_vars;
+mod local_connection;
 mod math;
 mod matrix;
 pub(crate) mod mouse;
 pub(crate) mod movie_clip;
 mod movie_clip_loader;
+mod net_connection;
+mod net_stream;
 pub(crate) mod number;
 mod object;
 mod point;
+mod print_job;
 mod rectangle;
 mod selection;
 pub(crate) mod shared_object;
@@ -57,6 +61,7 @@ mod text_format;
 mod transform;
 mod video;
 mod xml;
+mod xml_socket;
 
 pub fn random<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -464,6 +469,7 @@ pub struct SystemPrototypes<'gc> {
     pub text_format: Object<'gc>,
     pub array: Object<'gc>,
     pub array_constructor: Object<'gc>,
+    pub error_constructor: Object<'gc>,
     pub xml_node: Object<'gc>,
     pub string: Object<'gc>,
     pub number: Object<'gc>,
@@ -546,6 +552,12 @@ pub fn create_globals<'gc>(
         xml::create_xmlnode_proto(gc_context, object_proto, function_proto);
 
     let xml_proto: Object<'gc> = xml::create_xml_proto(gc_context, xmlnode_proto, function_proto);
+    let xml_socket_proto: Object<'gc> =
+        xml_socket::create_proto(gc_context, object_proto, function_proto);
+    let local_connection_proto: Object<'gc> =
+        local_connection::create_proto(gc_context, object_proto, function_proto);
+    let print_job_proto: Object<'gc> =
+        print_job::create_proto(gc_context, object_proto, function_proto);
 
     let string_proto: Object<'gc> = string::create_proto(gc_context, object_proto, function_proto);
     let number_proto: Object<'gc> = number::create_proto(gc_context, object_proto, function_proto);
@@ -586,6 +598,10 @@ pub fn create_globals<'gc>(
     let date_proto: Object<'gc> = date::create_proto(gc_context, object_proto, function_proto);
 
     let video_proto: Object<'gc> = video::create_proto(gc_context, object_proto, function_proto);
+    let net_connection_proto: Object<'gc> =
+        net_connection::create_proto(gc_context, object_proto, function_proto);
+    let net_stream_proto: Object<'gc> =
+        net_stream::create_proto(gc_context, object_proto, function_proto);
 
     //TODO: These need to be constructors and should also set `.prototype` on each one
     let object = object::create_object_object(gc_context, object_proto, function_proto);
@@ -673,6 +689,27 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         xml_proto,
     );
+    let xml_socket = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(xml_socket::constructor),
+        constructor_to_fn!(xml_socket::constructor),
+        Some(function_proto),
+        xml_socket_proto,
+    );
+    let local_connection = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(local_connection::constructor),
+        constructor_to_fn!(local_connection::constructor),
+        Some(function_proto),
+        local_connection_proto,
+    );
+    let print_job = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(print_job::constructor),
+        constructor_to_fn!(print_job::constructor),
+        Some(function_proto),
+        print_job_proto,
+    );
     let string = string::create_string_object(gc_context, string_proto, Some(function_proto));
     let number = number::create_number_object(gc_context, number_proto, Some(function_proto));
     let boolean = boolean::create_boolean_object(gc_context, boolean_proto, Some(function_proto));
@@ -709,6 +746,20 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         video_proto,
     );
+    let net_connection = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(net_connection::constructor),
+        constructor_to_fn!(net_connection::constructor),
+        Some(function_proto),
+        net_connection_proto,
+    );
+    let net_stream = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(net_stream::constructor),
+        constructor_to_fn!(net_stream::constructor),
+        Some(function_proto),
+        net_stream_proto,
+    );
 
     flash.define_value(gc_context, "geom", geom.into(), Attribute::empty());
     flash.define_value(gc_context, "filters", filters.into(), Attribute::empty());
@@ -959,6 +1010,18 @@ pub fn create_globals<'gc>(
         movie_clip_loader.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "NetConnection",
+        net_connection.into(),
+        Attribute::DONT_ENUM,
+    );
+    globals.define_value(
+        gc_context,
+        "NetStream",
+        net_stream.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(gc_context, "Sound", sound.into(), Attribute::DONT_ENUM);
     globals.define_value(
         gc_context,
@@ -974,6 +1037,24 @@ pub fn create_globals<'gc>(
     );
     globals.define_value(gc_context, "XMLNode", xmlnode.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "XML", xml.into(), Attribute::DONT_ENUM);
+    globals.define_value(
+        gc_context,
+        "XMLSocket",
+        xml_socket.into(),
+        Attribute::DONT_ENUM,
+    );
+    globals.define_value(
+        gc_context,
+        "LocalConnection",
+        local_connection.into(),
+        Attribute::DONT_ENUM,
+    );
+    globals.define_value(
+        gc_context,
+        "PrintJob",
+        print_job.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(gc_context, "String", string.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "Number", number.into(), Attribute::DONT_ENUM);
     globals.define_value(gc_context, "Boolean", boolean.into(), Attribute::DONT_ENUM);
@@ -1224,6 +1305,7 @@ pub fn create_globals<'gc>(
             text_format: text_format_proto,
             array: array_proto,
             array_constructor: array,
+            error_constructor: error,
             xml_node: xmlnode_proto,
             string: string_proto,
             number: number_proto,
@@ -2,13 +2,20 @@
 
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
-use crate::avm1::object::bitmap_data::{BitmapDataObject, ChannelOptions, Color};
+use crate::avm1::globals::bitmap_filter;
+use crate::avm1::globals::color_transform::object_to_color_transform;
+use crate::avm1::globals::matrix::object_to_matrix;
+use crate::avm1::object::bitmap_data::BitmapDataObject;
 use crate::avm1::property::Attribute;
-use crate::avm1::{activation::Activation, object::bitmap_data::BitmapData};
-use crate::avm1::{Object, TObject, Value};
+use crate::avm1::{activation::Activation, Object, TObject, Value};
+use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, Color, ThresholdOperation};
+use crate::bitmap::rasterizer::{blend_mode_from_name, Rasterizer};
 use crate::character::Character;
+use crate::color_transform::ColorTransform;
 use crate::display_object::TDisplayObject;
+use crate::transform::Transform;
 use gc_arena::{GcCell, MutationContext};
+use swf::{BlendMode, Matrix};
 
 pub fn constructor<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -466,22 +473,164 @@ pub fn noise<'gc>(
 }
 
 pub fn apply_filter<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    log::warn!("BitmapData.applyFilter - not yet implemented");
+    if let Some(bitmap_data) = this.as_bitmap_data_object() {
+        if !bitmap_data.disposed() {
+            let source_bitmap = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let source_rect = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let src_min_x = source_rect
+                .get("x", activation)?
+                .coerce_to_i32(activation)?;
+            let src_min_y = source_rect
+                .get("y", activation)?
+                .coerce_to_i32(activation)?;
+            let src_width = source_rect
+                .get("width", activation)?
+                .coerce_to_i32(activation)?;
+            let src_height = source_rect
+                .get("height", activation)?
+                .coerce_to_i32(activation)?;
+
+            let dest_point = args
+                .get(2)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let dest_x = dest_point.get("x", activation)?.coerce_to_i32(activation)?;
+            let dest_y = dest_point.get("y", activation)?.coerce_to_i32(activation)?;
+
+            let filter = args
+                .get(3)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+            let filter = match bitmap_filter::object_to_filter(filter) {
+                Some(filter) => filter,
+                None => {
+                    activation.unimplemented("BitmapData.applyFilter")?;
+                    return Ok((-1).into());
+                }
+            };
+
+            if let Some(src_bitmap) = source_bitmap.as_bitmap_data_object() {
+                if !src_bitmap.disposed() {
+                    // dealing with object aliasing the same way as `copyPixels`
+                    let src_bitmap_clone: BitmapData;
+                    let src_bitmap_data_cell = src_bitmap.bitmap_data();
+                    let src_bitmap_gc_ref;
+                    let source_bitmap_ref =
+                        if GcCell::ptr_eq(src_bitmap.bitmap_data(), bitmap_data.bitmap_data()) {
+                            src_bitmap_clone = src_bitmap_data_cell.read().clone();
+                            &src_bitmap_clone
+                        } else {
+                            src_bitmap_gc_ref = src_bitmap_data_cell.read();
+                            &src_bitmap_gc_ref
+                        };
+
+                    bitmap_data
+                        .bitmap_data()
+                        .write(activation.context.gc_context)
+                        .apply_filter(
+                            source_bitmap_ref,
+                            (src_min_x, src_min_y, src_width, src_height),
+                            (dest_x, dest_y),
+                            &filter,
+                        );
+
+                    return Ok(0.into());
+                }
+            }
+        }
+    }
+
     Ok((-1).into())
 }
 
 pub fn draw<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.as_bitmap_data_object() {
         if !bitmap_data.disposed() {
-            log::warn!("BitmapData.draw - not yet implemented");
+            let source = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let matrix = match args.get(1) {
+                Some(Value::Object(matrix)) => object_to_matrix(*matrix, activation)?,
+                _ => Matrix::identity(),
+            };
+
+            let color_transform = match args.get(2) {
+                Some(Value::Object(color_transform))
+                    if color_transform.as_color_transform_object().is_some() =>
+                {
+                    object_to_color_transform(*color_transform, activation)?
+                }
+                _ => ColorTransform::default(),
+            };
+
+            let blend_mode = match args.get(3) {
+                None | Some(Value::Undefined) | Some(Value::Null) => BlendMode::Normal,
+                Some(blend_mode) => {
+                    let blend_mode = blend_mode.coerce_to_string(activation)?;
+                    blend_mode_from_name(&blend_mode).unwrap_or(BlendMode::Normal)
+                }
+            };
+
+            let clip = match args.get(4) {
+                Some(Value::Object(rectangle)) => {
+                    let x = rectangle.get("x", activation)?.coerce_to_i32(activation)?;
+                    let y = rectangle.get("y", activation)?.coerce_to_i32(activation)?;
+                    let width = rectangle
+                        .get("width", activation)?
+                        .coerce_to_i32(activation)?;
+                    let height = rectangle
+                        .get("height", activation)?
+                        .coerce_to_i32(activation)?;
+                    Some((x, y, width, height))
+                }
+                _ => None,
+            };
+
+            let smoothing = args
+                .get(5)
+                .unwrap_or(&Value::Bool(false))
+                .as_bool(activation.current_swf_version());
+
+            // Rasterize into a copy, as the source may read from the pixels
+            // being drawn into.
+            let mut target = bitmap_data.bitmap_data().read().clone();
+            let transform = Transform {
+                matrix,
+                color_transform,
+            };
+            let mut rasterizer = Rasterizer::new(&mut target, &transform, clip, blend_mode);
+            if let Some(source_bitmap) = source.as_bitmap_data_object() {
+                if !source_bitmap.disposed() {
+                    rasterizer.draw_bitmap(&source_bitmap.bitmap_data().read(), smoothing);
+                }
+            } else if let Some(source_object) = source.as_display_object() {
+                source_object.rasterize_self(activation.context.renderer, &mut rasterizer);
+            }
+
+            let bitmap_data = bitmap_data.bitmap_data();
+            let mut bitmap_data = bitmap_data.write(activation.context.gc_context);
+            bitmap_data.set_pixels(target.pixels().to_vec());
+            bitmap_data.set_dirty(true);
+
             return Ok(Value::Undefined);
         }
     }
@@ -490,13 +639,13 @@ pub fn draw<'gc>(
 }
 
 pub fn generate_filter_rect<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.as_bitmap_data_object() {
         if !bitmap_data.disposed() {
-            log::warn!("BitmapData.generateFilterRect - not yet implemented");
+            activation.unimplemented("BitmapData.generateFilterRect")?;
             return Ok(Value::Undefined);
         }
     }
@@ -535,11 +684,12 @@ pub fn color_transform<'gc>(
             let min_y = y.max(0) as u32;
             let end_y = (y + height) as u32;
 
-            if let Some(color_transform) = color_transform.as_color_transform_object() {
+            if color_transform.as_color_transform_object().is_some() {
+                let color_transform = object_to_color_transform(color_transform, activation)?;
                 bitmap_data
                     .bitmap_data()
                     .write(activation.context.gc_context)
-                    .color_transform(min_x, min_y, end_x, end_y, color_transform);
+                    .color_transform(min_x, min_y, end_x, end_y, &color_transform);
             }
 
             return Ok(Value::Undefined);
@@ -656,13 +806,13 @@ pub fn perlin_noise<'gc>(
 }
 
 pub fn hit_test<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.as_bitmap_data_object() {
         if !bitmap_data.disposed() {
-            log::warn!("BitmapData.hitTest - not yet implemented");
+            activation.unimplemented("BitmapData.hitTest")?;
             return Ok(Value::Undefined);
         }
     }
@@ -800,13 +950,13 @@ pub fn copy_pixels<'gc>(
 }
 
 pub fn merge<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.as_bitmap_data_object() {
         if !bitmap_data.disposed() {
-            log::warn!("BitmapData.merge - not yet implemented");
+            activation.unimplemented("BitmapData.merge")?;
             return Ok(Value::Undefined);
         }
     }
@@ -905,13 +1055,13 @@ pub fn palette_map<'gc>(
 }
 
 pub fn pixel_dissolve<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.as_bitmap_data_object() {
         if !bitmap_data.disposed() {
-            log::warn!("BitmapData.pixelDissolve - not yet implemented");
+            activation.unimplemented("BitmapData.pixelDissolve")?;
             return Ok(Value::Undefined);
         }
     }
@@ -948,14 +1098,170 @@ pub fn scroll<'gc>(
 }
 
 pub fn threshold<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(bitmap_data) = this.as_bitmap_data_object() {
         if !bitmap_data.disposed() {
-            log::warn!("BitmapData.threshold - not yet implemented");
-            return Ok(Value::Undefined);
+            let source_bitmap = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let source_rect = args
+                .get(1)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let src_min_x = source_rect
+                .get("x", activation)?
+                .coerce_to_i32(activation)?;
+            let src_min_y = source_rect
+                .get("y", activation)?
+                .coerce_to_i32(activation)?;
+            let src_width = source_rect
+                .get("width", activation)?
+                .coerce_to_i32(activation)?;
+            let src_height = source_rect
+                .get("height", activation)?
+                .coerce_to_i32(activation)?;
+
+            let dest_point = args
+                .get(2)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let dest_x = dest_point.get("x", activation)?.coerce_to_i32(activation)?;
+            let dest_y = dest_point.get("y", activation)?.coerce_to_i32(activation)?;
+
+            let operation = args
+                .get(3)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_string(activation)?;
+
+            let threshold = args
+                .get(4)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_u32(activation)?;
+
+            let color = args
+                .get(5)
+                .unwrap_or(&Value::Number(0.0))
+                .coerce_to_i32(activation)?;
+
+            let mask = args
+                .get(6)
+                .unwrap_or(&Value::Number(0xFFFFFFFF_u32 as f64))
+                .coerce_to_u32(activation)?;
+
+            let copy_source = args
+                .get(7)
+                .unwrap_or(&Value::Bool(false))
+                .as_bool(activation.current_swf_version());
+
+            let operation = match ThresholdOperation::from_name(&operation) {
+                Some(operation) => operation,
+                // Invalid operations make the whole call fail.
+                None => return Ok(0.into()),
+            };
+
+            if let Some(src_bitmap) = source_bitmap.as_bitmap_data_object() {
+                if !src_bitmap.disposed() {
+                    // dealing with object aliasing...
+                    let src_bitmap_data_cell = src_bitmap.bitmap_data();
+                    let read;
+                    let source: Option<&BitmapData> =
+                        if GcCell::ptr_eq(src_bitmap_data_cell, bitmap_data.bitmap_data()) {
+                            None
+                        } else {
+                            read = src_bitmap_data_cell.read();
+                            Some(&read)
+                        };
+
+                    let modified_count = bitmap_data
+                        .bitmap_data()
+                        .write(activation.context.gc_context)
+                        .threshold(
+                            source,
+                            (src_min_x, src_min_y, src_width, src_height),
+                            (dest_x, dest_y),
+                            operation,
+                            threshold,
+                            color.into(),
+                            mask,
+                            copy_source,
+                        );
+
+                    return Ok(modified_count.into());
+                }
+            }
+
+            return Ok(0.into());
+        }
+    }
+
+    Ok((-1).into())
+}
+
+pub fn compare<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(bitmap_data) = this.as_bitmap_data_object() {
+        if !bitmap_data.disposed() {
+            let other = args
+                .get(0)
+                .unwrap_or(&Value::Undefined)
+                .coerce_to_object(activation);
+
+            let other = match other.as_bitmap_data_object() {
+                Some(other) if !other.disposed() => other,
+                _ => return Ok((-2).into()),
+            };
+
+            let difference = {
+                let bitmap_data = bitmap_data.bitmap_data();
+                let bitmap_data = bitmap_data.read();
+                let other = other.bitmap_data();
+                let other = other.read();
+
+                if bitmap_data.width() != other.width() {
+                    return Ok((-3).into());
+                }
+                if bitmap_data.height() != other.height() {
+                    return Ok((-4).into());
+                }
+
+                bitmap_data.compare(&other)
+            };
+
+            if let Some(difference) = difference {
+                let proto = activation.context.avm1.prototypes.bitmap_data_constructor;
+                let new_bitmap_data = proto.construct(
+                    activation,
+                    &[
+                        difference.width().into(),
+                        difference.height().into(),
+                        true.into(),
+                        0.into(),
+                    ],
+                )?;
+                let new_bitmap_data_object = new_bitmap_data
+                    .coerce_to_object(activation)
+                    .as_bitmap_data_object()
+                    .unwrap();
+
+                new_bitmap_data_object
+                    .bitmap_data()
+                    .write(activation.context.gc_context)
+                    .set_pixels(difference.pixels().to_vec());
+
+                return Ok(new_bitmap_data);
+            }
+
+            return Ok(0.into());
         }
     }
 
@@ -1177,6 +1483,13 @@ pub fn create_proto<'gc>(
         Attribute::empty(),
         Some(fn_proto),
     );
+    object.force_set_function(
+        "compare",
+        compare,
+        gc_context,
+        Attribute::empty(),
+        Some(fn_proto),
+    );
 
     bitmap_data_object.into()
 }
@@ -1202,7 +1515,10 @@ pub fn load_bitmap<'gc>(
         .and_then(|l| l.character_by_export_name(name.as_str()));
 
     if let Some(Character::Bitmap(bitmap_object)) = character {
-        if let Some(bitmap) = renderer.get_bitmap_pixels(bitmap_object.bitmap_handle()) {
+        if let Some(bitmap) = bitmap_object
+            .bitmap_handle()
+            .and_then(|handle| renderer.get_bitmap_pixels(handle))
+        {
             let proto = activation.context.avm1.prototypes.bitmap_data_constructor;
             let new_bitmap =
                 proto.construct(activation, &[bitmap.width.into(), bitmap.height.into()])?;
@@ -2,9 +2,11 @@
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
+use crate::avm1::object::bevel_filter::BevelFilterType;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Object, ScriptObject, TObject, Value};
 use gc_arena::MutationContext;
+use swf::Filter;
 
 pub fn constructor<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -268,6 +270,200 @@ pub fn clone<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Create an AVM1 filter object from a filter applied to a display object.
+///
+/// Filters that have no AVM1 equivalent yet are returned as `None`.
+pub fn filter_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    filter: &Filter,
+) -> Result<Option<Object<'gc>>, Error<'gc>> {
+    let gc_context = activation.context.gc_context;
+    let object = match filter {
+        Filter::BlurFilter(filter) => {
+            let constructor = activation.context.avm1.prototypes.blur_filter_constructor;
+            let object = constructor
+                .construct(activation, &[])?
+                .coerce_to_object(activation);
+            if let Some(blur) = object.as_blur_filter_object() {
+                blur.set_blur_x(gc_context, filter.blur_x);
+                blur.set_blur_y(gc_context, filter.blur_y);
+                blur.set_quality(gc_context, filter.num_passes.into());
+            }
+            object
+        }
+        Filter::GlowFilter(filter) => {
+            let constructor = activation.context.avm1.prototypes.glow_filter_constructor;
+            let object = constructor
+                .construct(activation, &[])?
+                .coerce_to_object(activation);
+            if let Some(glow) = object.as_glow_filter_object() {
+                glow.set_color(gc_context, color_to_rgb(&filter.color) as i32);
+                glow.set_alpha(gc_context, color_to_alpha(&filter.color));
+                glow.set_blur_x(gc_context, filter.blur_x);
+                glow.set_blur_y(gc_context, filter.blur_y);
+                glow.set_strength(gc_context, filter.strength.into());
+                glow.set_inner(gc_context, filter.is_inner);
+                glow.set_knockout(gc_context, filter.is_knockout);
+                glow.set_quality(gc_context, filter.num_passes.into());
+            }
+            object
+        }
+        Filter::DropShadowFilter(filter) => {
+            let constructor = activation
+                .context
+                .avm1
+                .prototypes
+                .drop_shadow_filter_constructor;
+            let object = constructor
+                .construct(activation, &[])?
+                .coerce_to_object(activation);
+            if let Some(shadow) = object.as_drop_shadow_filter_object() {
+                shadow.set_distance(gc_context, filter.distance);
+                shadow.set_angle(gc_context, filter.angle.to_degrees());
+                shadow.set_color(gc_context, color_to_rgb(&filter.color));
+                shadow.set_alpha(gc_context, color_to_alpha(&filter.color));
+                shadow.set_blur_x(gc_context, filter.blur_x);
+                shadow.set_blur_y(gc_context, filter.blur_y);
+                shadow.set_strength(gc_context, filter.strength.into());
+                shadow.set_inner(gc_context, filter.is_inner);
+                shadow.set_knockout(gc_context, filter.is_knockout);
+                shadow.set_quality(gc_context, filter.num_passes.into());
+            }
+            object
+        }
+        Filter::BevelFilter(filter) => {
+            let constructor = activation.context.avm1.prototypes.bevel_filter_constructor;
+            let object = constructor
+                .construct(activation, &[])?
+                .coerce_to_object(activation);
+            if let Some(bevel) = object.as_bevel_filter_object() {
+                bevel.set_distance(gc_context, filter.distance);
+                bevel.set_angle(gc_context, filter.angle.to_degrees());
+                bevel.set_highlight_color(gc_context, color_to_rgb(&filter.highlight_color));
+                bevel.set_highlight_alpha(gc_context, color_to_alpha(&filter.highlight_color));
+                bevel.set_shadow_color(gc_context, color_to_rgb(&filter.shadow_color));
+                bevel.set_shadow_alpha(gc_context, color_to_alpha(&filter.shadow_color));
+                bevel.set_blur_x(gc_context, filter.blur_x);
+                bevel.set_blur_y(gc_context, filter.blur_y);
+                bevel.set_strength(gc_context, filter.strength.into());
+                bevel.set_quality(gc_context, filter.num_passes.into());
+                bevel.set_knockout(gc_context, filter.is_knockout);
+                let type_ = if filter.is_on_top {
+                    BevelFilterType::Full
+                } else if filter.is_inner {
+                    BevelFilterType::Inner
+                } else {
+                    BevelFilterType::Outer
+                };
+                bevel.set_type(gc_context, type_);
+            }
+            object
+        }
+        Filter::ColorMatrixFilter(filter) => {
+            let constructor = activation
+                .context
+                .avm1
+                .prototypes
+                .color_matrix_filter_constructor;
+            let object = constructor
+                .construct(activation, &[])?
+                .coerce_to_object(activation);
+            if let Some(color_matrix) = object.as_color_matrix_filter_object() {
+                color_matrix.set_matrix(gc_context, filter.matrix);
+            }
+            object
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(object))
+}
+
+/// Read the filter described by an AVM1 filter object, so that it can be
+/// applied to a display object.
+///
+/// Objects that are not filters, or filters that can't be rendered yet, are
+/// returned as `None`.
+pub fn object_to_filter(object: Object<'_>) -> Option<Filter> {
+    if let Some(blur) = object.as_blur_filter_object() {
+        return Some(Filter::BlurFilter(Box::new(swf::BlurFilter {
+            blur_x: blur.blur_x(),
+            blur_y: blur.blur_y(),
+            num_passes: quality_to_passes(blur.quality()),
+        })));
+    }
+
+    if let Some(glow) = object.as_glow_filter_object() {
+        return Some(Filter::GlowFilter(Box::new(swf::GlowFilter {
+            color: rgb_to_color(glow.color() as u32, glow.alpha()),
+            blur_x: glow.blur_x(),
+            blur_y: glow.blur_y(),
+            strength: glow.strength() as f32,
+            is_inner: glow.inner(),
+            is_knockout: glow.knockout(),
+            num_passes: quality_to_passes(glow.quality()),
+        })));
+    }
+
+    // `hideObject` can't be represented by a SWF filter, and is ignored.
+    if let Some(shadow) = object.as_drop_shadow_filter_object() {
+        return Some(Filter::DropShadowFilter(Box::new(swf::DropShadowFilter {
+            color: rgb_to_color(shadow.color(), shadow.alpha()),
+            blur_x: shadow.blur_x(),
+            blur_y: shadow.blur_y(),
+            angle: shadow.angle().to_radians(),
+            distance: shadow.distance(),
+            strength: shadow.strength() as f32,
+            is_inner: shadow.inner(),
+            is_knockout: shadow.knockout(),
+            num_passes: quality_to_passes(shadow.quality()),
+        })));
+    }
+
+    if let Some(bevel) = object.as_bevel_filter_object() {
+        let type_ = bevel.get_type();
+        return Some(Filter::BevelFilter(Box::new(swf::BevelFilter {
+            shadow_color: rgb_to_color(bevel.shadow_color(), bevel.shadow_alpha()),
+            highlight_color: rgb_to_color(bevel.highlight_color(), bevel.highlight_alpha()),
+            blur_x: bevel.blur_x(),
+            blur_y: bevel.blur_y(),
+            angle: bevel.angle().to_radians(),
+            distance: bevel.distance(),
+            strength: bevel.strength() as f32,
+            is_inner: matches!(type_, BevelFilterType::Inner),
+            is_knockout: bevel.knockout(),
+            is_on_top: matches!(type_, BevelFilterType::Full),
+            num_passes: quality_to_passes(bevel.quality()),
+        })));
+    }
+
+    if let Some(color_matrix) = object.as_color_matrix_filter_object() {
+        return Some(Filter::ColorMatrixFilter(Box::new(
+            swf::ColorMatrixFilter {
+                matrix: color_matrix.matrix(),
+            },
+        )));
+    }
+
+    None
+}
+
+fn color_to_rgb(color: &swf::Color) -> u32 {
+    (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b)
+}
+
+fn color_to_alpha(color: &swf::Color) -> f64 {
+    f64::from(color.a) / 255.0
+}
+
+fn rgb_to_color(rgb: u32, alpha: f64) -> swf::Color {
+    swf::Color::from_rgb(rgb, (alpha.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+fn quality_to_passes(quality: i32) -> u8 {
+    quality.clamp(0, 15) as u8
+}
+
 pub fn create_proto<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Object<'gc>,
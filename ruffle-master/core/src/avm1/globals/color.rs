@@ -95,11 +95,9 @@ fn get_rgb<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(target) = target(activation, this)? {
-        let color_transform = target.color_transform();
-        let r = ((color_transform.r_add * 255.0) as i32) << 16;
-        let g = ((color_transform.g_add * 255.0) as i32) << 8;
-        let b = (color_transform.b_add * 255.0) as i32;
-        Ok((r | g | b).into())
+        let [r, g, b, _] = target.color_transform().additions();
+        let rgb = (i32::from(r) << 16) | (i32::from(g) << 8) | i32::from(b);
+        Ok(rgb.into())
     } else {
         Ok(Value::Undefined)
     }
@@ -112,18 +110,23 @@ fn get_transform<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     if let Some(target) = target(activation, this)? {
         let color_transform = target.color_transform();
+        let [ra, ga, ba, aa] = color_transform.multipliers();
+        let [rb, gb, bb, ab] = color_transform.additions();
         let out = ScriptObject::object(
             activation.context.gc_context,
             Some(activation.context.avm1.prototypes.object),
         );
-        out.set("ra", (color_transform.r_mult * 100.0).into(), activation)?;
-        out.set("ga", (color_transform.g_mult * 100.0).into(), activation)?;
-        out.set("ba", (color_transform.b_mult * 100.0).into(), activation)?;
-        out.set("aa", (color_transform.a_mult * 100.0).into(), activation)?;
-        out.set("rb", (color_transform.r_add * 255.0).into(), activation)?;
-        out.set("gb", (color_transform.g_add * 255.0).into(), activation)?;
-        out.set("bb", (color_transform.b_add * 255.0).into(), activation)?;
-        out.set("ab", (color_transform.a_add * 255.0).into(), activation)?;
+        // The multipliers are read back from their fixed-point values, so
+        // `ra: 33` comes back as `32.8125`, as in Flash.
+        let percent = |mult: i16| f64::from(mult) * 100.0 / 256.0;
+        out.set("ra", percent(ra).into(), activation)?;
+        out.set("ga", percent(ga).into(), activation)?;
+        out.set("ba", percent(ba).into(), activation)?;
+        out.set("aa", percent(aa).into(), activation)?;
+        out.set("rb", rb.into(), activation)?;
+        out.set("gb", gb.into(), activation)?;
+        out.set("bb", bb.into(), activation)?;
+        out.set("ab", ab.into(), activation)?;
         Ok(out.into())
     } else {
         Ok(Value::Undefined)
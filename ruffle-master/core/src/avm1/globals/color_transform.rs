@@ -298,6 +298,9 @@ fn to_string<'gc>(
     )))
 }
 
+/// The other transform is applied first, followed by this one, in the same
+/// order as `ColorTransform::concat` combines the transforms of nested clips.
+/// Unlike those, the values here are kept as numbers and don't saturate.
 fn concat<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
 
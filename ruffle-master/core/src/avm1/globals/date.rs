@@ -4,7 +4,9 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::date_object::DateObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, TObject, Value};
-use chrono::{DateTime, Datelike, Duration, FixedOffset, LocalResult, TimeZone, Timelike, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, FixedOffset, LocalResult, NaiveDate, TimeZone, Timelike, Utc,
+};
 use gc_arena::{Collect, MutationContext};
 use num_traits::ToPrimitive;
 
@@ -16,7 +18,7 @@ macro_rules! implement_local_getters {
                 |activation: &mut Activation<'_, 'gc, '_>, this, _args| -> Result<Value<'gc>, Error<'gc>> {
                     if let Some(this) = this.as_date_object() {
                         if let Some(date) = this.date_time() {
-                            let local = date.with_timezone(&activation.context.locale.get_timezone());
+                            let local = activation.context.locale.to_local_date_time(date);
                             Ok($fn(&local).into())
                         } else {
                             Ok(f64::NAN.into())
@@ -93,6 +95,17 @@ impl YearType {
     }
 }
 
+/// Which calendar a `DateAdjustment` reads and writes date fields in.
+#[derive(Clone, Copy, Collect)]
+#[collect(require_static)]
+enum DateTimezone {
+    /// Fields are in UTC.
+    Utc,
+
+    /// Fields are in local time, as defined by the `LocaleBackend`.
+    Local,
+}
+
 #[derive(Collect)]
 #[collect(no_drop)]
 struct DateAdjustment<
@@ -100,11 +113,10 @@ struct DateAdjustment<
     'activation_a: 'builder,
     'gc: 'activation_a,
     'gc_context: 'activation_a,
-    T: TimeZone + 'builder,
 > {
     activation: &'builder mut Activation<'activation_a, 'gc, 'gc_context>,
     year_type: YearType,
-    timezone: &'builder T,
+    timezone: DateTimezone,
     year: Option<Option<f64>>,
     month: Option<Option<f64>>,
     day: Option<Option<f64>>,
@@ -115,12 +127,12 @@ struct DateAdjustment<
     ignore_next: bool,
 }
 
-impl<'builder, 'activation_a, 'gc, 'gc_context, T: TimeZone>
-    DateAdjustment<'builder, 'activation_a, 'gc, 'gc_context, T>
+impl<'builder, 'activation_a, 'gc, 'gc_context>
+    DateAdjustment<'builder, 'activation_a, 'gc, 'gc_context>
 {
     fn new(
         activation: &'builder mut Activation<'activation_a, 'gc, 'gc_context>,
-        timezone: &'builder T,
+        timezone: DateTimezone,
     ) -> Self {
         Self {
             activation,
@@ -496,42 +508,47 @@ impl<'builder, 'activation_a, 'gc, 'gc_context, T: TimeZone>
     }
 
     fn calculate(&mut self, current: DateObject<'gc>) -> Option<DateTime<Utc>> {
-        if let Some(current) = current.date_time().map(|v| v.with_timezone(self.timezone)) {
-            let month_rem = self
-                .month
-                .flatten()
-                .map(|v| v as i64)
-                .unwrap_or_default()
-                .div_euclid(12);
-            let month =
-                self.check_mapped_value(self.month, |v| v.rem_euclid(12), current.month0())?;
-            let year = self
-                .check_mapped_value(self.year, |v| self.year_type.adjust(v), current.year())?
-                .wrapping_add(month_rem) as i32;
-            let day = self.check_value(self.day, current.day())?;
-            let hour = self.check_value(self.hour, current.hour())?;
-            let minute = self.check_value(self.minute, current.minute())?;
-            let second = self.check_value(self.second, current.second())?;
-            let millisecond =
-                self.check_value(self.millisecond, current.timestamp_subsec_millis())?;
-
-            let duration = Duration::days(day - 1)
-                + Duration::hours(hour)
-                + Duration::minutes(minute)
-                + Duration::seconds(second)
-                + Duration::milliseconds(millisecond);
-
-            if let LocalResult::Single(Some(result)) = current
-                .timezone()
-                .ymd_opt(year, (month + 1) as u32, 1)
-                .and_hms_opt(0, 0, 0)
-                .map(|date| date.checked_add_signed(duration))
-            {
-                return Some(result.with_timezone(&Utc));
-            }
+        let current = current.date_time()?;
+        let current = match self.timezone {
+            DateTimezone::Utc => current.naive_utc(),
+            DateTimezone::Local => self
+                .activation
+                .context
+                .locale
+                .to_local_date_time(current)
+                .naive_local(),
+        };
+
+        let month_rem = self
+            .month
+            .flatten()
+            .map(|v| v as i64)
+            .unwrap_or_default()
+            .div_euclid(12);
+        let month = self.check_mapped_value(self.month, |v| v.rem_euclid(12), current.month0())?;
+        let year = self
+            .check_mapped_value(self.year, |v| self.year_type.adjust(v), current.year())?
+            .wrapping_add(month_rem) as i32;
+        let day = self.check_value(self.day, current.day())?;
+        let hour = self.check_value(self.hour, current.hour())?;
+        let minute = self.check_value(self.minute, current.minute())?;
+        let second = self.check_value(self.second, current.second())?;
+        let millisecond = self.check_value(self.millisecond, current.timestamp_subsec_millis())?;
+
+        let duration = Duration::days(day - 1)
+            + Duration::hours(hour)
+            + Duration::minutes(minute)
+            + Duration::seconds(second)
+            + Duration::milliseconds(millisecond);
+
+        let result = NaiveDate::from_ymd_opt(year, (month + 1) as u32, 1)?
+            .and_hms(0, 0, 0)
+            .checked_add_signed(duration)?;
+
+        match self.timezone {
+            DateTimezone::Utc => Some(DateTime::from_utc(result, Utc)),
+            DateTimezone::Local => self.activation.context.locale.from_local_date_time(result),
         }
-
-        None
     }
 
     fn apply(&mut self, object: DateObject<'gc>) -> f64 {
@@ -559,15 +576,14 @@ fn constructor<'gc>(
     let timestamp = args.get(0).unwrap_or(&Value::Undefined);
     if timestamp != &Value::Undefined {
         if args.len() > 1 {
-            let timezone = activation.context.locale.get_timezone();
-
             // We need a starting value to adjust from.
-            this.set_date_time(
-                activation.context.gc_context,
-                Some(timezone.ymd(0, 1, 1).and_hms(0, 0, 0).into()),
-            );
+            let start = activation
+                .context
+                .locale
+                .from_local_date_time(NaiveDate::from_ymd(0, 1, 1).and_hms(0, 0, 0));
+            this.set_date_time(activation.context.gc_context, start);
 
-            DateAdjustment::new(activation, &timezone)
+            DateAdjustment::new(activation, DateTimezone::Local)
                 .year_opt(args.get(0))?
                 .month_opt(args.get(1))?
                 .day_opt(args.get(2))?
@@ -575,6 +591,8 @@ fn constructor<'gc>(
                 .minute_opt(args.get(4))?
                 .second_opt(args.get(5))?
                 .millisecond_opt(args.get(6))?
+                // Unlike `setYear`, Flash moves negative years to the 1900s
+                // too.
                 .adjust_year(|year| if year < 100 { year + 1900 } else { year })
                 .apply(this);
         } else {
@@ -615,7 +633,7 @@ fn create_utc<'gc>(
         Some(Utc.ymd(0, 1, 1).and_hms(0, 0, 0)),
     );
 
-    let timestamp = DateAdjustment::new(activation, &Utc)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Utc)
         .year(args.get(0))?
         .month(args.get(1))?
         .day_opt(args.get(2))?
@@ -623,6 +641,7 @@ fn create_utc<'gc>(
         .minute_opt(args.get(4))?
         .second_opt(args.get(5))?
         .millisecond_opt(args.get(6))?
+        // Unlike `setYear`, Flash moves negative years to the 1900s too.
         .adjust_year(|year| if year < 100 { year + 1900 } else { year })
         .apply(date);
 
@@ -637,7 +656,7 @@ fn to_string<'gc>(
     let date = this.date_time();
 
     if let Some(date) = date {
-        let local = date.with_timezone(&activation.context.locale.get_timezone());
+        let local = activation.context.locale.to_local_date_time(date);
         Ok(AvmString::new(
             activation.context.gc_context,
             local.format("%a %b %-d %T GMT%z %-Y").to_string(),
@@ -654,7 +673,7 @@ fn get_timezone_offset<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     let date = if let Some(date) = this.date_time() {
-        date.with_timezone(&activation.context.locale.get_timezone())
+        activation.context.locale.to_local_date_time(date)
     } else {
         return Ok(f64::NAN.into());
     };
@@ -673,8 +692,7 @@ fn set_date<'gc>(
         this.set_date_time(activation.context.gc_context, None);
         Ok(f64::NAN.into())
     } else {
-        let timezone = activation.context.locale.get_timezone();
-        let timestamp = DateAdjustment::new(activation, &timezone)
+        let timestamp = DateAdjustment::new(activation, DateTimezone::Local)
             .day(args.get(0))?
             .apply(this);
         Ok(timestamp.into())
@@ -690,7 +708,7 @@ fn set_utc_date<'gc>(
         this.set_date_time(activation.context.gc_context, None);
         Ok(f64::NAN.into())
     } else {
-        let timestamp = DateAdjustment::new(activation, &Utc)
+        let timestamp = DateAdjustment::new(activation, DateTimezone::Utc)
             .day(args.get(0))?
             .apply(this);
         Ok(timestamp.into())
@@ -702,11 +720,10 @@ fn set_year<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Local)
         .year(args.get(0))?
         .adjust_year(|year| {
-            if year >= 0 && year < 100 {
+            if (0..100).contains(&year) {
                 year + 1900
             } else {
                 year
@@ -721,8 +738,7 @@ fn set_hours<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Local)
         .hour(args.get(0))?
         .apply(this);
     Ok(timestamp.into())
@@ -733,7 +749,7 @@ fn set_utc_hours<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timestamp = DateAdjustment::new(activation, &Utc)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Utc)
         .hour(args.get(0))?
         .minute_opt(args.get(1))?
         .second_opt(args.get(2))?
@@ -747,8 +763,7 @@ fn set_milliseconds<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Local)
         .millisecond(args.get(0))?
         .apply(this);
     Ok(timestamp.into())
@@ -759,7 +774,7 @@ fn set_utc_milliseconds<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timestamp = DateAdjustment::new(activation, &Utc)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Utc)
         .millisecond(args.get(0))?
         .apply(this);
     Ok(timestamp.into())
@@ -770,8 +785,7 @@ fn set_minutes<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Local)
         .minute_or(args.get(0), -2147483648.0)?
         .apply(this);
     Ok(timestamp.into())
@@ -782,7 +796,7 @@ fn set_utc_minutes<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timestamp = DateAdjustment::new(activation, &Utc)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Utc)
         .minute_or(args.get(0), -2147483648.0)?
         .second_opt(args.get(1))?
         .millisecond_opt(args.get(2))?
@@ -795,8 +809,7 @@ fn set_month<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Local)
         .month_or(args.get(0), 0.0)?
         .day_opt(args.get(1))?
         .apply(this);
@@ -808,7 +821,7 @@ fn set_utc_month<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timestamp = DateAdjustment::new(activation, &Utc)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Utc)
         .month_or(args.get(0), 0.0)?
         .day_opt(args.get(1))?
         .apply(this);
@@ -820,8 +833,7 @@ fn set_seconds<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Local)
         .second(args.get(0))?
         .apply(this);
     Ok(timestamp.into())
@@ -832,7 +844,7 @@ fn set_utc_seconds<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timestamp = DateAdjustment::new(activation, &Utc)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Utc)
         .second(args.get(0))?
         .millisecond_opt(args.get(1))?
         .apply(this);
@@ -864,8 +876,7 @@ fn set_full_year<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timezone = activation.context.locale.get_timezone();
-    let timestamp = DateAdjustment::new(activation, &timezone)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Local)
         .year(args.get(0))?
         .month_opt(args.get(1))?
         .day_opt(args.get(2))?
@@ -878,7 +889,7 @@ fn set_utc_full_year<'gc>(
     this: DateObject<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let timestamp = DateAdjustment::new(activation, &Utc)
+    let timestamp = DateAdjustment::new(activation, DateTimezone::Utc)
         .year(args.get(0))?
         .month_opt(args.get(1))?
         .day_opt(args.get(2))?
@@ -970,3 +981,28 @@ pub fn create_proto<'gc>(
 
     date.into()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        create_date_object(
+            activation.context.gc_context,
+            activation.context.avm1.prototypes().date,
+            Some(activation.context.avm1.prototypes().function),
+        )
+    }
+
+    // Every year below 100 is moved to the 1900s, as the recorded output of
+    // the `avm1/date/UTC` test shows for negative years.
+    test_method!(test_utc_years_below_100, "UTC", setup,
+        [8] => {
+            [0, 0] => -2208988800000.0,
+            [99, 0] => 915148800000.0,
+            [100, 0] => -59011459200000.0,
+            [-1, 0] => -2240524800000.0,
+            [-199, 0] => -8488800000000.0
+        }
+    );
+}
@@ -1,14 +1,13 @@
 //! AVM1 LoadVars object
-//! TODO: bytesLoaded, bytesTotal, contentType, addRequestHeader
+//! TODO: contentType, addRequestHeader
 
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
-use crate::avm_warn;
-use crate::backend::navigator::{NavigationMethod, RequestOptions};
+use crate::backend::navigator::NavigationMethod;
+use crate::net::{self, Request};
 use gc_arena::MutationContext;
-use std::borrow::Cow;
 
 /// Implements `LoadVars`
 pub fn constructor<'gc>(
@@ -122,7 +121,7 @@ fn add_request_header<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "LoadVars.addRequestHeader: Unimplemented");
+    activation.unimplemented("LoadVars.addRequestHeader")?;
     Ok(Value::Undefined)
 }
 
@@ -320,15 +319,15 @@ fn spawn_load_var_fetch<'gc>(
     url: &AvmString,
     send_object: Option<(Object<'gc>, NavigationMethod)>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let (url, request_options) = if let Some((send_object, method)) = send_object {
+    let request = if let Some((send_object, method)) = send_object {
         // Send properties from `send_object`.
-        activation.object_into_request_options(send_object, Cow::Borrowed(&url), Some(method))
+        activation.object_into_request(send_object, url.to_string(), Some(method))
     } else {
         // Not sending any parameters.
-        (Cow::Borrowed(url.as_str()), RequestOptions::get())
+        Request::get(url.to_string())
     };
 
-    let fetch = activation.context.navigator.fetch(&url, request_options);
+    let fetch = net::fetch_data(&activation.context, request);
     let process = activation.context.load_manager.load_form_into_load_vars(
         activation.context.player.clone().unwrap(),
         loader_object,
@@ -347,6 +346,17 @@ fn spawn_load_var_fetch<'gc>(
         loader_object.set("_bytesLoaded", 0.into(), activation)?;
     }
 
+    if !loader_object.has_property(activation, "_bytesTotal") {
+        loader_object.define_value(
+            activation.context.gc_context,
+            "_bytesTotal",
+            Value::Undefined,
+            Attribute::DONT_DELETE | Attribute::DONT_ENUM,
+        );
+    } else {
+        loader_object.set("_bytesTotal", Value::Undefined, activation)?;
+    }
+
     if !loader_object.has_property(activation, "loaded") {
         loader_object.define_value(
             activation.context.gc_context,
@@ -0,0 +1,184 @@
+//! AVM1 LocalConnection object
+
+use crate::amf::ScriptValue;
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
+use crate::local_connection::{
+    domain as movie_domain, LocalConnectionManager, LocalConnectionObject,
+};
+use gc_arena::MutationContext;
+
+/// How deeply nested objects may be before the rest of them is left out of a
+/// message, which keeps objects that contain themselves from being sent
+/// forever.
+const MAX_DEPTH: usize = 64;
+
+/// Implements `LocalConnection`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "connect",
+        connect,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "close",
+        close,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "domain",
+        domain,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0) {
+        Some(Value::String(name)) => name.to_string(),
+        _ => return Ok(false.into()),
+    };
+
+    Ok(LocalConnectionManager::connect(
+        &mut activation.context,
+        LocalConnectionObject::Avm1(this),
+        &name,
+    )
+    .into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (name, method) = match (args.get(0), args.get(1)) {
+        (Some(Value::String(name)), Some(Value::String(method))) => {
+            (name.to_string(), method.to_string())
+        }
+        _ => return Ok(false.into()),
+    };
+    let args: Vec<_> = args[2..]
+        .iter()
+        .map(|arg| to_script_value(activation, arg, 0))
+        .collect();
+
+    LocalConnectionManager::send(
+        &mut activation.context,
+        LocalConnectionObject::Avm1(this),
+        &name,
+        &method,
+        &args,
+    );
+
+    Ok(true.into())
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    LocalConnectionManager::close(&mut activation.context, LocalConnectionObject::Avm1(this));
+
+    Ok(Value::Undefined)
+}
+
+fn domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let domain = movie_domain(activation.context.swf);
+
+    Ok(AvmString::new(activation.context.gc_context, domain).into())
+}
+
+/// Convert an argument of `send` into an AMF0 value. Functions can't be sent,
+/// and become `undefined`.
+fn to_script_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: &Value<'gc>,
+    depth: usize,
+) -> ScriptValue {
+    match value {
+        Value::Undefined => ScriptValue::Undefined,
+        Value::Null => ScriptValue::Null,
+        Value::Bool(b) => ScriptValue::Boolean(*b),
+        Value::Number(n) => ScriptValue::Number(*n),
+        Value::String(s) => ScriptValue::String(s.to_string()),
+        Value::Object(_) if depth >= MAX_DEPTH => ScriptValue::Undefined,
+        Value::Object(object) => {
+            if object.as_executable().is_some() {
+                return ScriptValue::Undefined;
+            }
+            if let Some(date) = object.as_date_object() {
+                return match date.date_time() {
+                    Some(date) => ScriptValue::Date(date.timestamp_millis() as f64),
+                    None => ScriptValue::Date(f64::NAN),
+                };
+            }
+
+            let array_proto = activation.context.avm1.prototypes.array;
+            if object
+                .is_instance_of(activation, *object, array_proto)
+                .unwrap_or_default()
+            {
+                let elements: Vec<_> = (0..object.length())
+                    .map(|i| object.array_element(i))
+                    .collect();
+                let elements = elements
+                    .iter()
+                    .map(|element| to_script_value(activation, element, depth + 1))
+                    .collect();
+                return ScriptValue::StrictArray(elements);
+            }
+
+            let mut properties = vec![];
+            for key in object.get_keys(activation) {
+                if let Ok(property) = object.get(&key, activation) {
+                    let property = to_script_value(activation, &property, depth + 1);
+                    properties.push((key, property));
+                }
+            }
+            ScriptValue::Object(properties)
+        }
+    }
+}
@@ -66,8 +66,24 @@ pub fn gradient_object_to_matrix<'gc>(
             Twips::from_pixels(tx),
             Twips::from_pixels(ty),
         ))
+    } else if object.has_property(activation, "g") && !object.has_property(activation, "tx") {
+        // Flash 6 style 3x3 matrix, `{a, b, c, d, e, f, g, h, i}`, stretching a gradient
+        // that is one pixel across. The third column is ignored.
+        let a = object.get("a", activation)?.coerce_to_f64(activation)?;
+        let b = object.get("b", activation)?.coerce_to_f64(activation)?;
+        let d = object.get("d", activation)?.coerce_to_f64(activation)?;
+        let e = object.get("e", activation)?.coerce_to_f64(activation)?;
+        let g = object.get("g", activation)?.coerce_to_f64(activation)?;
+        let h = object.get("h", activation)?.coerce_to_f64(activation)?;
+        Ok(Matrix {
+            a: (a / 1638.4) as f32,
+            b: (b / 1638.4) as f32,
+            c: (d / 1638.4) as f32,
+            d: (e / 1638.4) as f32,
+            tx: Twips::from_pixels(g),
+            ty: Twips::from_pixels(h),
+        })
     } else {
-        // TODO: You can apparently pass a 3x3 matrix here. Did anybody actually? How does it work?
         object_to_matrix(object, activation)
     }
 }
@@ -3,26 +3,30 @@
 use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::globals::bitmap_filter;
 use crate::avm1::globals::display_object::{self, AVM_DEPTH_BIAS, AVM_MAX_DEPTH};
-use crate::avm1::globals::matrix::gradient_object_to_matrix;
+use crate::avm1::globals::matrix::{gradient_object_to_matrix, object_to_matrix};
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
 use crate::avm_error;
 use crate::avm_warn;
 use crate::backend::navigator::NavigationMethod;
+use crate::bitmap::rasterizer::{blend_mode_from_name, blend_mode_name};
+use crate::character::Character;
 use crate::display_object::{
-    Bitmap, DisplayObject, EditText, MovieClip, TDisplayObject, TDisplayObjectContainer,
+    Bitmap, DisplayObject, EditText, HitTestOptions, MovieClip, TDisplayObject,
+    TDisplayObjectContainer,
 };
 use crate::ecma_conversions::f64_to_wrapping_i32;
+use crate::net;
 use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
 use crate::tag_utils::SwfSlice;
 use crate::vminterface::Instantiator;
 use gc_arena::MutationContext;
-use std::borrow::Cow;
 use swf::{
-    FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread, LineCapStyle,
-    LineJoinStyle, LineStyle, Twips,
+    BlendMode, FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Twips,
 };
 
 /// Implements `MovieClip`
@@ -133,7 +137,7 @@ pub fn hit_test<'gc>(
                 .avm1_root(&activation.context)?
                 .local_to_global((Twips::from_pixels(x), Twips::from_pixels(y)));
             let ret = if shape {
-                movie_clip.hit_test_shape(&mut activation.context, point)
+                movie_clip.hit_test_shape(&mut activation.context, point, HitTestOptions::empty())
             } else {
                 movie_clip.hit_test_bounds(point)
             };
@@ -195,11 +199,13 @@ pub fn create_proto<'gc>(
         "unloadMovie" => unload_movie,
         "beginFill" => begin_fill,
         "beginGradientFill" => begin_gradient_fill,
+        "beginBitmapFill" => begin_bitmap_fill,
         "moveTo" => move_to,
         "lineTo" => line_to,
         "curveTo" => curve_to,
         "endFill" => end_fill,
         "lineStyle" => line_style,
+        "lineGradientStyle" => line_gradient_style,
         "clear" => clear,
         "attachBitmap" => attach_bitmap
     );
@@ -215,6 +221,10 @@ pub fn create_proto<'gc>(
     with_movie_clip_props!(
         object, gc_context, fn_proto,
         "transform" => [transform, set_transform],
+        "filters" => [filters, set_filters],
+        "blendMode" => [blend_mode, set_blend_mode],
+        "scale9Grid" => [scale_9_grid, set_scale_9_grid],
+        "cacheAsBitmap" => [cache_as_bitmap, set_cache_as_bitmap],
         "enabled" => [enabled, set_enabled],
         "focusEnabled" => [focus_enabled, set_focus_enabled],
         "_lockroot" => [lock_root, set_lock_root],
@@ -284,7 +294,8 @@ fn line_style<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    if let Some(width) = args.get(0) {
+    // An undefined thickness removes the line style, just like passing nothing.
+    if let Some(width) = args.get(0).filter(|v| !matches!(v, Value::Undefined)) {
         let width = Twips::from_pixels(width.coerce_to_f64(activation)?.min(255.0).max(0.0));
         let color = if let Some(rgb) = args.get(1) {
             let rgb = rgb.coerce_to_u32(activation)?;
@@ -387,99 +398,231 @@ fn begin_fill<'gc>(
     Ok(Value::Undefined)
 }
 
-fn begin_gradient_fill<'gc>(
-    movie_clip: MovieClip<'gc>,
+/// Build a gradient fill from the arguments to `beginGradientFill` or
+/// `lineGradientStyle`, warning and returning `None` if they're unusable.
+fn gradient_fill_from_args<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error<'gc>> {
-    if let (Some(method), Some(colors), Some(alphas), Some(ratios), Some(matrix)) = (
+    method_name: &str,
+) -> Result<Option<FillStyle>, Error<'gc>> {
+    let (method, colors, alphas, ratios, matrix) = match (
         args.get(0),
         args.get(1),
         args.get(2),
         args.get(3),
         args.get(4),
     ) {
-        let method = method.coerce_to_string(activation)?;
-        let colors = colors.coerce_to_object(activation).array();
-        let alphas = alphas.coerce_to_object(activation).array();
-        let ratios = ratios.coerce_to_object(activation).array();
-        let matrix_object = matrix.coerce_to_object(activation);
-        if colors.len() != alphas.len() || colors.len() != ratios.len() {
+        (Some(method), Some(colors), Some(alphas), Some(ratios), Some(matrix)) => {
+            (method, colors, alphas, ratios, matrix)
+        }
+        _ => return Ok(None),
+    };
+    let method = method.coerce_to_string(activation)?;
+    let colors = colors.coerce_to_object(activation).array();
+    let alphas = alphas.coerce_to_object(activation).array();
+    let ratios = ratios.coerce_to_object(activation).array();
+    let matrix_object = matrix.coerce_to_object(activation);
+    if colors.len() != alphas.len() || colors.len() != ratios.len() {
+        avm_warn!(
+            activation,
+            "{}() received different sized arrays for colors, alphas and ratios",
+            method_name
+        );
+        return Ok(None);
+    }
+    let mut records = Vec::with_capacity(colors.len());
+    for i in 0..colors.len() {
+        let ratio = ratios[i].coerce_to_f64(activation)?.min(255.0).max(0.0);
+        let rgb = colors[i].coerce_to_u32(activation)?;
+        let alpha = alphas[i].coerce_to_f64(activation)?.min(100.0).max(0.0);
+        records.push(GradientRecord {
+            ratio: ratio as u8,
+            color: Color::from_rgb(rgb, (alpha / 100.0 * 255.0) as u8),
+        });
+    }
+    let matrix = gradient_object_to_matrix(matrix_object, activation)?;
+    let spread = match args
+        .get(5)
+        .and_then(|v| v.coerce_to_string(activation).ok())
+        .as_deref()
+    {
+        Some("reflect") => GradientSpread::Reflect,
+        Some("repeat") => GradientSpread::Repeat,
+        _ => GradientSpread::Pad,
+    };
+    let interpolation = match args
+        .get(6)
+        .and_then(|v| v.coerce_to_string(activation).ok())
+        .as_deref()
+    {
+        Some("linearRGB") => GradientInterpolation::LinearRgb,
+        _ => GradientInterpolation::Rgb,
+    };
+
+    let gradient = Gradient {
+        matrix,
+        spread,
+        interpolation,
+        records,
+    };
+    let style = match method.as_ref() {
+        "linear" => FillStyle::LinearGradient(gradient),
+        "radial" => {
+            if let Some(focal_point) = args.get(7) {
+                FillStyle::FocalGradient {
+                    gradient,
+                    focal_point: focal_point.coerce_to_f64(activation)?.min(1.0).max(-1.0) as f32,
+                }
+            } else {
+                FillStyle::RadialGradient(gradient)
+            }
+        }
+        other => {
             avm_warn!(
                 activation,
-                "beginGradientFill() received different sized arrays for colors, alphas and ratios"
+                "{}() received invalid fill type {:?}",
+                method_name,
+                other
             );
-            return Ok(Value::Undefined);
-        }
-        let mut records = Vec::with_capacity(colors.len());
-        for i in 0..colors.len() {
-            let ratio = ratios[i].coerce_to_f64(activation)?.min(255.0).max(0.0);
-            let rgb = colors[i].coerce_to_u32(activation)?;
-            let alpha = alphas[i].coerce_to_f64(activation)?.min(100.0).max(0.0);
-            records.push(GradientRecord {
-                ratio: ratio as u8,
-                color: Color::from_rgb(rgb, (alpha / 100.0 * 255.0) as u8),
-            });
+            return Ok(None);
         }
-        let matrix = gradient_object_to_matrix(matrix_object, activation)?;
-        let spread = match args
-            .get(5)
-            .and_then(|v| v.coerce_to_string(activation).ok())
-            .as_deref()
-        {
-            Some("reflect") => GradientSpread::Reflect,
-            Some("repeat") => GradientSpread::Repeat,
-            _ => GradientSpread::Pad,
-        };
-        let interpolation = match args
-            .get(6)
-            .and_then(|v| v.coerce_to_string(activation).ok())
-            .as_deref()
-        {
-            Some("linearRGB") => GradientInterpolation::LinearRgb,
-            _ => GradientInterpolation::Rgb,
-        };
+    };
+    Ok(Some(style))
+}
 
-        let gradient = Gradient {
-            matrix,
-            spread,
-            interpolation,
-            records,
-        };
-        let style = match method.as_ref() {
-            "linear" => FillStyle::LinearGradient(gradient),
-            "radial" => {
-                if let Some(focal_point) = args.get(7) {
-                    FillStyle::FocalGradient {
-                        gradient,
-                        focal_point: focal_point.coerce_to_f64(activation)? as f32,
-                    }
-                } else {
-                    FillStyle::RadialGradient(gradient)
-                }
-            }
-            other => {
-                avm_warn!(
-                    activation,
-                    "beginGradientFill() received invalid fill type {:?}",
-                    other
-                );
-                return Ok(Value::Undefined);
-            }
-        };
+fn begin_gradient_fill<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if args.len() < 5 {
         movie_clip
             .as_drawing(activation.context.gc_context)
             .unwrap()
-            .set_fill_style(Some(style));
-    } else {
+            .set_fill_style(None);
+    } else if let Some(style) = gradient_fill_from_args(activation, args, "beginGradientFill")? {
         movie_clip
             .as_drawing(activation.context.gc_context)
             .unwrap()
-            .set_fill_style(None);
+            .set_fill_style(Some(style));
+    }
+    Ok(Value::Undefined)
+}
+
+fn line_gradient_style<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(style) = gradient_fill_from_args(activation, args, "lineGradientStyle")? {
+        let mut drawing = movie_clip
+            .as_drawing(activation.context.gc_context)
+            .unwrap();
+        if let Some(mut line_style) = drawing.line_style().cloned() {
+            line_style.fill_style = Some(style);
+            drawing.set_line_style(Some(line_style));
+        }
     }
     Ok(Value::Undefined)
 }
 
+fn begin_bitmap_fill<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bitmap_data = match args
+        .get(0)
+        .and_then(|v| v.coerce_to_object(activation).as_bitmap_data_object())
+    {
+        Some(bitmap_data) => bitmap_data.bitmap_data(),
+        None => {
+            movie_clip
+                .as_drawing(activation.context.gc_context)
+                .unwrap()
+                .set_fill_style(None);
+            return Ok(Value::Undefined);
+        }
+    };
+    let matrix = match args.get(1) {
+        Some(Value::Object(matrix)) => object_to_matrix(*matrix, activation)?,
+        _ => Matrix::identity(),
+    };
+    let is_repeating = args
+        .get(2)
+        .map_or(true, |v| v.as_bool(activation.current_swf_version()));
+    let is_smoothed = args
+        .get(3)
+        .map_or(false, |v| v.as_bool(activation.current_swf_version()));
+
+    let (handle, width, height) = {
+        let mut write = bitmap_data.write(activation.context.gc_context);
+        let handle = write.bitmap_handle(activation.context.renderer);
+        (handle, write.width(), write.height())
+    };
+    let handle = match handle {
+        Some(handle) => handle,
+        None => return Ok(Value::Undefined),
+    };
+
+    // Bitmap fills refer to their bitmap by character ID, so the bitmap has to
+    // be in the library of the movie that draws it.
+    let movie = match movie_clip.movie() {
+        Some(movie) => movie,
+        None => return Ok(Value::Undefined),
+    };
+    let library = activation
+        .context
+        .library
+        .library_for_movie_mut(movie.clone());
+    let id = match library.bitmap_id_by_handle(handle) {
+        Some(id) => id,
+        None => {
+            let id = match library.unused_character_id() {
+                Some(id) => id,
+                None => {
+                    avm_warn!(activation, "beginBitmapFill() ran out of character IDs");
+                    return Ok(Value::Undefined);
+                }
+            };
+            let bitmap = Bitmap::new_with_bitmap_data(
+                &mut activation.context,
+                id,
+                handle,
+                width as u16,
+                height as u16,
+                Some(bitmap_data),
+                is_smoothed,
+            );
+            activation
+                .context
+                .library
+                .library_for_movie_mut(movie)
+                .register_character(id, Character::Bitmap(bitmap));
+            id
+        }
+    };
+
+    // Bitmap fill matrices map from bitmap pixels to twips.
+    let matrix = Matrix {
+        a: matrix.a * 20.0,
+        b: matrix.b * 20.0,
+        c: matrix.c * 20.0,
+        d: matrix.d * 20.0,
+        ..matrix
+    };
+    movie_clip
+        .as_drawing(activation.context.gc_context)
+        .unwrap()
+        .set_fill_style(Some(FillStyle::Bitmap {
+            id,
+            matrix,
+            is_smoothed,
+            is_repeating,
+        }));
+    Ok(Value::Undefined)
+}
+
 fn move_to<'gc>(
     movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
@@ -929,19 +1072,7 @@ pub fn goto_frame<'gc>(
             // Coerce to string and search for a frame label.
             // This can direct other clips than the one this method was called on!
             let frame_path = val.coerce_to_string(activation)?;
-            if let Some((clip, frame)) =
-                activation.resolve_variable_path(movie_clip.into(), &frame_path)?
-            {
-                if let Some(clip) = clip.as_display_object().and_then(|o| o.as_movie_clip()) {
-                    if let Ok(frame) = frame.parse().map(f64_to_wrapping_i32) {
-                        // First try to parse as a frame number.
-                        call_frame = Some((clip, frame));
-                    } else if let Some(frame) = clip.frame_label_to_number(&frame) {
-                        // Otherwise, it's a frame label.
-                        call_frame = Some((clip, frame as i32));
-                    }
-                }
-            }
+            call_frame = activation.resolve_frame_path(movie_clip.into(), &frame_path)?;
         }
     }
 
@@ -950,7 +1081,9 @@ pub fn goto_frame<'gc>(
         frame = frame.wrapping_add(i32::from(scene_offset));
         frame = frame.saturating_add(1);
         if frame > 0 {
-            clip.goto_frame(&mut activation.context, frame as u16, stop);
+            // Gotoing past the last frame of the clip stops on it.
+            let frame = frame.min(i32::from(u16::MAX)) as u16;
+            clip.goto_frame(&mut activation.context, frame, stop);
         }
     }
     Ok(Value::Undefined)
@@ -1134,6 +1267,26 @@ fn get_bounds<'gc>(
     movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
     args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    bounds_in_target(movie_clip, activation, args, movie_clip.bounds())
+}
+
+fn get_rect<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let bounds = movie_clip.edge_bounds_with_transform(&Default::default());
+    bounds_in_target(movie_clip, activation, args, bounds)
+}
+
+/// Transform `bounds`, in the coordinate space of `movie_clip`, into the
+/// coordinate space of the target passed to `getBounds` or `getRect`.
+fn bounds_in_target<'gc>(
+    movie_clip: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    bounds: BoundingBox,
 ) -> Result<Value<'gc>, Error<'gc>> {
     let target = match args.get(0) {
         Some(Value::String(s)) if s.is_empty() => None,
@@ -1150,7 +1303,6 @@ fn get_bounds<'gc>(
     };
 
     if let Some(target) = target {
-        let bounds = movie_clip.bounds();
         let out_bounds = if DisplayObject::ptr_eq(movie_clip.into(), target) {
             // Getting the clips bounds in its own coordinate space; no AABB transform needed.
             bounds
@@ -1179,16 +1331,6 @@ fn get_bounds<'gc>(
     }
 }
 
-fn get_rect<'gc>(
-    movie_clip: MovieClip<'gc>,
-    activation: &mut Activation<'_, 'gc, '_>,
-    args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error<'gc>> {
-    // TODO: This should get the bounds ignoring strokes. Always equal to or smaller than getBounds.
-    // Just defer to getBounds for now. Will have to store edge_bounds vs. shape_bounds in Graphic.
-    get_bounds(movie_clip, activation, args)
-}
-
 #[allow(unused_must_use)] //can't use errors yet
 pub fn get_url<'gc>(
     _movie_clip: MovieClip<'gc>,
@@ -1271,13 +1413,14 @@ fn load_movie<'gc>(
     let url = url_val.coerce_to_string(activation)?;
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
-    let (url, opts) = activation.locals_into_request_options(Cow::Borrowed(&url), method);
-    let fetch = activation.context.navigator.fetch(&url, opts);
+    let request = activation.locals_into_request(url.to_string(), method);
+    let url = request.url().to_string();
+    let fetch = net::fetch(&activation.context, request);
     let process = activation.context.load_manager.load_movie_into_clip(
         activation.context.player.clone().unwrap(),
         DisplayObject::MovieClip(target),
         fetch,
-        url.to_string(),
+        url,
         None,
     );
 
@@ -1295,8 +1438,8 @@ fn load_variables<'gc>(
     let url = url_val.coerce_to_string(activation)?;
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
-    let (url, opts) = activation.locals_into_request_options(Cow::Borrowed(&url), method);
-    let fetch = activation.context.navigator.fetch(&url, opts);
+    let request = activation.locals_into_request(url.to_string(), method);
+    let fetch = net::fetch_data(&activation.context, request);
     let target = target.object().coerce_to_object(activation);
     let process = activation.context.load_manager.load_form_into_object(
         activation.context.player.clone().unwrap(),
@@ -1339,6 +1482,150 @@ fn set_transform<'gc>(
     Ok(())
 }
 
+fn filters<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let array = ScriptObject::array(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes.array),
+    );
+
+    let filters = this.filters().to_vec();
+    for filter in &filters {
+        if let Some(object) = bitmap_filter::filter_to_object(activation, filter)? {
+            let length = array.length();
+            array.set_array_element(length, object.into(), activation.context.gc_context);
+        }
+    }
+
+    Ok(array.into())
+}
+
+fn set_filters<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let mut filters = Vec::new();
+    if let Value::Object(array) = value {
+        for item in array.array() {
+            if let Value::Object(object) = item {
+                filters.extend(bitmap_filter::object_to_filter(object));
+            }
+        }
+    }
+
+    this.set_filters(activation.context.gc_context, filters);
+    Ok(())
+}
+
+fn blend_mode<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(blend_mode_name(this.blend_mode()).into())
+}
+
+/// Blend modes can be set either by name or by their position in the list of
+/// blend modes, starting from 1.
+fn set_blend_mode<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let blend_mode = match value {
+        Value::Number(index) => match index as i32 {
+            1 => Some(BlendMode::Normal),
+            2 => Some(BlendMode::Layer),
+            3 => Some(BlendMode::Multiply),
+            4 => Some(BlendMode::Screen),
+            5 => Some(BlendMode::Lighten),
+            6 => Some(BlendMode::Darken),
+            7 => Some(BlendMode::Difference),
+            8 => Some(BlendMode::Add),
+            9 => Some(BlendMode::Subtract),
+            10 => Some(BlendMode::Invert),
+            11 => Some(BlendMode::Alpha),
+            12 => Some(BlendMode::Erase),
+            13 => Some(BlendMode::Overlay),
+            14 => Some(BlendMode::HardLight),
+            _ => None,
+        },
+        Value::String(name) => blend_mode_from_name(&name),
+        _ => None,
+    };
+
+    if let Some(blend_mode) = blend_mode {
+        this.set_blend_mode(activation.context.gc_context, blend_mode);
+    }
+    Ok(())
+}
+
+fn cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.is_bitmap_cached().into())
+}
+
+fn set_cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let cache_as_bitmap = value.as_bool(activation.current_swf_version());
+    this.set_is_bitmap_cached(activation.context.gc_context, cache_as_bitmap);
+    Ok(())
+}
+
+fn scale_9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    let grid = this.scaling_grid();
+    if grid.x_max <= grid.x_min || grid.y_max <= grid.y_min {
+        return Ok(Value::Undefined);
+    }
+
+    let proto = activation.context.avm1.prototypes.rectangle_constructor;
+    let rect = proto.construct(
+        activation,
+        &[
+            grid.x_min.to_pixels().into(),
+            grid.y_min.to_pixels().into(),
+            (grid.x_max - grid.x_min).to_pixels().into(),
+            (grid.y_max - grid.y_min).to_pixels().into(),
+        ],
+    )?;
+    Ok(rect)
+}
+
+/// Setting the grid to anything but a rectangle removes it.
+fn set_scale_9_grid<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let grid = match value {
+        Value::Object(rect) => {
+            let x = rect.get("x", activation)?.coerce_to_f64(activation)?;
+            let y = rect.get("y", activation)?.coerce_to_f64(activation)?;
+            let width = rect.get("width", activation)?.coerce_to_f64(activation)?;
+            let height = rect.get("height", activation)?.coerce_to_f64(activation)?;
+            swf::Rectangle {
+                x_min: Twips::from_pixels(x),
+                y_min: Twips::from_pixels(y),
+                x_max: Twips::from_pixels(x + width),
+                y_max: Twips::from_pixels(y + height),
+            }
+        }
+        _ => Default::default(),
+    };
+    this.set_scaling_grid(activation.context.gc_context, grid);
+    Ok(())
+}
+
 fn enabled<'gc>(
     this: MovieClip<'gc>,
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -7,8 +7,8 @@ use crate::avm1::object::script_object::ScriptObject;
 use crate::avm1::object::TObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Object, Value};
-use crate::backend::navigator::RequestOptions;
 use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::net::{self, Request};
 use gc_arena::MutationContext;
 
 pub fn constructor<'gc>(
@@ -45,10 +45,7 @@ pub fn load_clip<'gc>(
             .as_display_object()
             .and_then(|dobj| dobj.as_movie_clip())
         {
-            let fetch = activation
-                .context
-                .navigator
-                .fetch(&url, RequestOptions::get());
+            let fetch = net::fetch(&activation.context, Request::get(url.to_string()));
             let process = activation.context.load_manager.load_movie_into_clip(
                 activation.context.player.clone().unwrap(),
                 DisplayObject::MovieClip(movieclip),
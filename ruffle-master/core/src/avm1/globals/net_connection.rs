@@ -0,0 +1,93 @@
+//! AVM1 NetConnection object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
+use crate::avm_warn;
+use gc_arena::MutationContext;
+
+/// Implements `NetConnection`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+/// Implements `NetConnection.connect`.
+///
+/// Only `null` connections, which are used for progressive downloads, are
+/// supported. Connections to streaming servers always fail.
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let uri = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let is_null = match uri {
+        Value::Null | Value::Undefined => true,
+        Value::String(ref uri) => uri.eq_ignore_ascii_case("null"),
+        _ => false,
+    };
+
+    this.set("uri", uri, activation)?;
+    this.set("isConnected", is_null.into(), activation)?;
+
+    if !is_null {
+        avm_warn!(
+            activation,
+            "NetConnection.connect: streaming servers are not supported"
+        );
+        let info = ScriptObject::object(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes().object),
+        );
+        info.define_value(
+            activation.context.gc_context,
+            "code",
+            AvmString::new(
+                activation.context.gc_context,
+                "NetConnection.Connect.Failed",
+            )
+            .into(),
+            Attribute::empty(),
+        );
+        info.define_value(
+            activation.context.gc_context,
+            "level",
+            AvmString::new(activation.context.gc_context, "error").into(),
+            Attribute::empty(),
+        );
+        if let Value::Object(_) = this.get("onStatus", activation)? {
+            this.call_method("onStatus", &[info.into()], activation)?;
+        }
+    }
+
+    Ok(is_null.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "connect",
+        connect,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+    object.define_value(
+        gc_context,
+        "isConnected",
+        false.into(),
+        Attribute::DONT_ENUM,
+    );
+
+    object.into()
+}
@@ -0,0 +1,249 @@
+//! AVM1 NetStream object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::function::{Executable, FunctionObject};
+use crate::avm1::object::net_stream_object::NetStreamObject;
+use crate::avm1::property::Attribute;
+use crate::avm1::{Object, TObject, Value};
+use crate::avm_warn;
+use crate::streams::{StreamHandle, StreamManager};
+use gc_arena::MutationContext;
+
+/// Implements `NetStream`
+pub fn constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // The NetConnection argument only matters for streaming servers, which we
+    // don't support; progressive downloads are fetched directly.
+    if let Some(net_stream) = this.as_net_stream_object() {
+        let stream = activation.context.stream_manager.create_stream();
+        activation
+            .context
+            .stream_manager
+            .set_avm1_object(stream, this);
+        net_stream.set_stream(activation.context.gc_context, Some(stream));
+    } else {
+        log::error!("Tried to construct a NetStream on a non-NetStreamObject");
+    }
+
+    Ok(this.into())
+}
+
+/// The stream controlled by a `NetStream` object.
+fn stream_handle(this: Object<'_>) -> Option<StreamHandle> {
+    this.as_net_stream_object()
+        .and_then(|net_stream| net_stream.stream())
+}
+
+fn play<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(url) => url.coerce_to_string(activation)?.to_string(),
+        None => return Ok(Value::Undefined),
+    };
+
+    if let Some(stream) = stream_handle(this) {
+        StreamManager::play(&mut activation.context, stream, url);
+    } else {
+        avm_warn!(activation, "NetStream.play: this is not a NetStream");
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn pause<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let paused = match args.get(0) {
+        None | Some(Value::Undefined) => None,
+        Some(paused) => Some(paused.as_bool(activation.current_swf_version())),
+    };
+
+    if let Some(stream) = stream_handle(this) {
+        StreamManager::pause(&mut activation.context, stream, paused);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn seek<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let offset = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+
+    if let Some(stream) = stream_handle(this) {
+        StreamManager::seek(&mut activation.context, stream, offset);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(stream) = stream_handle(this) {
+        StreamManager::close(&mut activation.context, stream);
+    }
+
+    Ok(Value::Undefined)
+}
+
+fn set_buffer_time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Streams are always fully downloaded before they play, so the buffer
+    // time is only recorded for scripts that read it back.
+    let buffer_time = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_f64(activation)?;
+    this.define_value(
+        activation.context.gc_context,
+        "bufferTime",
+        buffer_time.into(),
+        Attribute::DONT_DELETE | Attribute::READ_ONLY,
+    );
+
+    Ok(Value::Undefined)
+}
+
+fn time<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(stream_handle(this)
+        .and_then(|stream| activation.context.stream_manager.get(stream))
+        .map_or(0.0, |stream| stream.time())
+        .into())
+}
+
+fn bytes_loaded<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(stream_handle(this)
+        .and_then(|stream| activation.context.stream_manager.get(stream))
+        .map_or(0, |stream| stream.bytes_loaded())
+        .into())
+}
+
+fn bytes_total<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(stream_handle(this)
+        .and_then(|stream| activation.context.stream_manager.get(stream))
+        .map_or(0, |stream| stream.bytes_total())
+        .into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let net_stream = NetStreamObject::empty_net_stream(gc_context, Some(proto));
+    let mut object = net_stream.as_script_object().unwrap();
+
+    object.force_set_function(
+        "play",
+        play,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "pause",
+        pause,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "seek",
+        seek,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "close",
+        close,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "setBufferTime",
+        set_buffer_time,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.add_property(
+        gc_context,
+        "time",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(time),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+    );
+    object.add_property(
+        gc_context,
+        "bytesLoaded",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(bytes_loaded),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+    );
+    object.add_property(
+        gc_context,
+        "bytesTotal",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(bytes_total),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+    );
+
+    object.define_value(
+        gc_context,
+        "bufferTime",
+        0.1.into(),
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+    );
+
+    net_stream.into()
+}
@@ -0,0 +1,195 @@
+//! AVM1 PrintJob object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::backend::print::{PageOrientation, PageSettings};
+use crate::bounding_box::BoundingBox;
+use crate::print_job;
+use gc_arena::MutationContext;
+use swf::Twips;
+
+/// Implements `PrintJob`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "start",
+        start,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "addPage",
+        add_page,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
+
+fn start<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let settings = match print_job::start(&mut activation.context) {
+        Some(settings) => settings,
+        None => return Ok(false.into()),
+    };
+
+    // The paper that was picked can be read back, but not changed.
+    let gc_context = activation.context.gc_context;
+    let attributes = Attribute::DONT_DELETE | Attribute::READ_ONLY;
+    this.define_value(
+        gc_context,
+        "paperWidth",
+        settings.paper_width.into(),
+        attributes,
+    );
+    this.define_value(
+        gc_context,
+        "paperHeight",
+        settings.paper_height.into(),
+        attributes,
+    );
+    this.define_value(
+        gc_context,
+        "pageWidth",
+        settings.page_width.into(),
+        attributes,
+    );
+    this.define_value(
+        gc_context,
+        "pageHeight",
+        settings.page_height.into(),
+        attributes,
+    );
+    this.define_value(
+        gc_context,
+        "orientation",
+        settings.orientation.as_str().into(),
+        attributes,
+    );
+
+    Ok(true.into())
+}
+
+/// The paper of a job that was started, read back from its properties.
+fn page_settings<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<Option<PageSettings>, Error<'gc>> {
+    if !this.has_own_property(activation, "pageWidth") {
+        return Ok(None);
+    }
+
+    let mut size = |name| -> Result<u32, Error<'gc>> {
+        Ok(this.get(name, activation)?.coerce_to_u32(activation)?)
+    };
+    let page_width = size("pageWidth")?;
+    let page_height = size("pageHeight")?;
+    let paper_width = size("paperWidth")?;
+    let paper_height = size("paperHeight")?;
+    let orientation = match this
+        .get("orientation", activation)?
+        .coerce_to_string(activation)?
+        .as_str()
+    {
+        "landscape" => PageOrientation::Landscape,
+        _ => PageOrientation::Portrait,
+    };
+
+    Ok(Some(PageSettings {
+        page_width,
+        page_height,
+        paper_width,
+        paper_height,
+        orientation,
+    }))
+}
+
+fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let settings = match page_settings(activation, this)? {
+        Some(settings) => settings,
+        None => return Ok(false.into()),
+    };
+
+    // The target is a level number, or a clip or its path.
+    let target = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+        Value::Number(level) => activation.context.levels.get(&(level as u32)).copied(),
+        target => {
+            let start_clip = activation.target_clip_or_root()?;
+            activation.resolve_target_display_object(start_clip, target, false)?
+        }
+    };
+    let target = match target {
+        Some(target) => target,
+        None => return Ok(false.into()),
+    };
+
+    let area = match args.get(1) {
+        Some(Value::Object(area)) => {
+            let mut coordinate = |name| -> Result<Twips, Error<'gc>> {
+                Ok(Twips::from_pixels(
+                    area.get(name, activation)?.coerce_to_f64(activation)?,
+                ))
+            };
+            Some(BoundingBox {
+                x_min: coordinate("xMin")?,
+                y_min: coordinate("yMin")?,
+                x_max: coordinate("xMax")?,
+                y_max: coordinate("yMax")?,
+                valid: true,
+            })
+        }
+        _ => None,
+    };
+
+    if let Some(Value::Number(_)) = args.get(3) {
+        log::warn!(
+            "PrintJob.addPage: printing a frame other than the current one is not supported"
+        );
+    }
+
+    Ok(print_job::add_page(&mut activation.context, target, area, &settings).into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    print_job::send(&mut activation.context);
+
+    Ok(Value::Undefined)
+}
@@ -3,7 +3,6 @@ use crate::avm1::error::Error;
 use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, TObject, Value};
-use crate::avm_warn;
 use crate::display_object::TDisplayObject;
 use gc_arena::MutationContext;
 
@@ -16,7 +15,7 @@ pub fn delete_all<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.deleteAll() not implemented");
+    activation.unimplemented("SharedObject.deleteAll")?;
     Ok(Value::Undefined)
 }
 
@@ -25,7 +24,7 @@ pub fn get_disk_usage<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.getDiskUsage() not implemented");
+    activation.unimplemented("SharedObject.getDiskUsage")?;
     Ok(Value::Undefined)
 }
 
@@ -295,7 +294,7 @@ pub fn get_remote<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.getRemote() not implemented");
+    activation.unimplemented("SharedObject.getRemote")?;
     Ok(Value::Undefined)
 }
 
@@ -304,7 +303,7 @@ pub fn get_max_size<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.getMaxSize() not implemented");
+    activation.unimplemented("SharedObject.getMaxSize")?;
     Ok(Value::Undefined)
 }
 
@@ -313,7 +312,7 @@ pub fn add_listener<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.addListener() not implemented");
+    activation.unimplemented("SharedObject.addListener")?;
     Ok(Value::Undefined)
 }
 
@@ -322,7 +321,7 @@ pub fn remove_listener<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.removeListener() not implemented");
+    activation.unimplemented("SharedObject.removeListener")?;
     Ok(Value::Undefined)
 }
 
@@ -423,7 +422,7 @@ pub fn close<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.close() not implemented");
+    activation.unimplemented("SharedObject.close")?;
     Ok(Value::Undefined)
 }
 
@@ -432,7 +431,7 @@ pub fn connect<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.connect() not implemented");
+    activation.unimplemented("SharedObject.connect")?;
     Ok(Value::Undefined)
 }
 
@@ -461,7 +460,7 @@ pub fn get_size<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.getSize() not implemented");
+    activation.unimplemented("SharedObject.getSize")?;
     Ok(Value::Undefined)
 }
 
@@ -470,7 +469,7 @@ pub fn send<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.send() not implemented");
+    activation.unimplemented("SharedObject.send")?;
     Ok(Value::Undefined)
 }
 
@@ -479,7 +478,7 @@ pub fn set_fps<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.setFps() not implemented");
+    activation.unimplemented("SharedObject.setFps")?;
     Ok(Value::Undefined)
 }
 
@@ -488,7 +487,7 @@ pub fn on_status<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.onStatus() not implemented");
+    activation.unimplemented("SharedObject.onStatus")?;
     Ok(Value::Undefined)
 }
 
@@ -497,7 +496,7 @@ pub fn on_sync<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.onSync() not implemented");
+    activation.unimplemented("SharedObject.onSync")?;
     Ok(Value::Undefined)
 }
 
@@ -245,7 +245,7 @@ fn get_bytes_loaded<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.current_swf_version() >= 6 {
-        avm_warn!(activation, "Sound.getBytesLoaded: Unimplemented");
+        activation.unimplemented("Sound.getBytesLoaded")?;
         Ok(1.into())
     } else {
         Ok(Value::Undefined)
@@ -258,7 +258,7 @@ fn get_bytes_total<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.current_swf_version() >= 6 {
-        avm_warn!(activation, "Sound.getBytesTotal: Unimplemented");
+        activation.unimplemented("Sound.getBytesTotal")?;
         Ok(1.into())
     } else {
         Ok(Value::Undefined)
@@ -337,7 +337,7 @@ fn id3<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.current_swf_version() >= 6 {
-        avm_warn!(activation, "Sound.id3: Unimplemented");
+        activation.unimplemented("Sound.id3")?;
     }
     Ok(Value::Undefined)
 }
@@ -348,7 +348,7 @@ fn load_sound<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.current_swf_version() >= 6 {
-        avm_warn!(activation, "Sound.loadSound: Unimplemented");
+        activation.unimplemented("Sound.loadSound")?;
     }
     Ok(Value::Undefined)
 }
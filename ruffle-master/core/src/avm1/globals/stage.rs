@@ -7,7 +7,6 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::globals::as_broadcaster::BroadcasterFunctions;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Object, ScriptObject, TObject, Value};
-use crate::avm_warn;
 use gc_arena::MutationContext;
 
 pub fn create_stage_object<'gc>(
@@ -109,7 +108,7 @@ fn align<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Stage.align: unimplemented");
+    activation.unimplemented("Stage.align")?;
     Ok("".into())
 }
 
@@ -118,7 +117,7 @@ fn set_align<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Stage.align: unimplemented");
+    activation.unimplemented("Stage.align")?;
     Ok(Value::Undefined)
 }
 
@@ -135,7 +134,7 @@ fn scale_mode<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Stage.scaleMode: unimplemented");
+    activation.unimplemented("Stage.scaleMode")?;
     Ok("noScale".into())
 }
 
@@ -144,7 +143,7 @@ fn set_scale_mode<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Stage.scaleMode: unimplemented");
+    activation.unimplemented("Stage.scaleMode")?;
     Ok(Value::Undefined)
 }
 
@@ -153,7 +152,7 @@ fn show_menu<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Stage.showMenu: unimplemented");
+    activation.unimplemented("Stage.showMenu")?;
     Ok(true.into())
 }
 
@@ -162,7 +161,7 @@ fn set_show_menu<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Stage.showMenu: unimplemented");
+    activation.unimplemented("Stage.showMenu")?;
     Ok(Value::Undefined)
 }
 
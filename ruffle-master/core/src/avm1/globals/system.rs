@@ -4,7 +4,6 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::Object;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Avm1, ScriptObject, TObject, Value};
-use crate::avm_warn;
 use bitflags::bitflags;
 use core::fmt;
 use gc_arena::MutationContext;
@@ -30,25 +29,6 @@ impl fmt::Display for CpuArchitecture {
     }
 }
 
-/// Available type of sandbox for a given SWF
-pub enum SandboxType {
-    Remote,
-    LocalWithFile,
-    LocalWithNetwork,
-    LocalTrusted,
-}
-
-impl fmt::Display for SandboxType {
-    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt.write_str(match self {
-            SandboxType::Remote => "remote",
-            SandboxType::LocalWithFile => "localWithFile",
-            SandboxType::LocalWithNetwork => "localWithNetwork",
-            SandboxType::LocalTrusted => "localTrusted",
-        })
-    }
-}
-
 /// The available host operating systems
 pub enum OperatingSystem {
     WindowsXp,
@@ -266,8 +246,6 @@ pub struct SystemProperties {
     pub manufacturer: Manufacturer,
     /// The os of the host
     pub os: OperatingSystem,
-    /// The type of the player sandbox
-    pub sandbox_type: SandboxType,
     /// The cpu architecture of the platform
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
@@ -393,7 +371,6 @@ impl Default for SystemProperties {
             dpi: 1_f32,
             manufacturer: Manufacturer::Linux,
             os: OperatingSystem::Linux,
-            sandbox_type: SandboxType::LocalTrusted,
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
         }
@@ -411,7 +388,7 @@ pub fn set_clipboard<'gc>(
         .coerce_to_string(activation)?
         .to_string();
 
-    activation.context.ui.set_clipboard_content(new_content);
+    activation.context.clipboard.set_contents(new_content);
 
     Ok(Value::Undefined)
 }
@@ -431,11 +408,7 @@ pub fn show_settings<'gc>(
 
     let panel = SettingsPanel::try_from(panel_pos as u8).unwrap_or(SettingsPanel::Privacy);
 
-    avm_warn!(
-        activation,
-        "System.showSettings({:?}) not not implemented",
-        panel
-    );
+    activation.unimplemented(&format!("System.showSettings({:?})", panel))?;
     Ok(Value::Undefined)
 }
 
@@ -492,7 +465,7 @@ pub fn on_status<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "System.onStatus() not implemented");
+    activation.unimplemented("System.onStatus")?;
     Ok(Value::Undefined)
 }
 
@@ -4,40 +4,43 @@ use crate::avm1::function::{Executable, FunctionObject};
 use crate::avm1::object::Object;
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, ScriptObject, TObject, Value};
-use crate::avm_warn;
+use crate::net;
 use gc_arena::MutationContext;
 use std::convert::Into;
 
 fn allow_domain<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "System.security.allowDomain() not implemented");
+    for domain in args {
+        let domain = domain.coerce_to_string(activation)?;
+        activation.context.security.allow_domain(&domain, false);
+    }
     Ok(Value::Undefined)
 }
 
 fn allow_insecure_domain<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.allowInsecureDomain() not implemented"
-    );
+    for domain in args {
+        let domain = domain.coerce_to_string(activation)?;
+        activation.context.security.allow_domain(&domain, true);
+    }
     Ok(Value::Undefined)
 }
 
 fn load_policy_file<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.allowInsecureDomain() not implemented"
-    );
+    if let Some(url) = args.get(0) {
+        let url = url.coerce_to_string(activation)?;
+        net::load_policy_file(&mut activation.context, &url);
+    }
     Ok(Value::Undefined)
 }
 
@@ -46,7 +49,7 @@ fn escape_domain<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "System.security.escapeDomain() not implemented");
+    activation.unimplemented("System.security.escapeDomain")?;
     Ok(Value::Undefined)
 }
 
@@ -57,7 +60,7 @@ fn get_sandbox_type<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     Ok(AvmString::new(
         activation.context.gc_context,
-        activation.context.system.sandbox_type.to_string(),
+        activation.context.security.sandbox_type().to_string(),
     )
     .into())
 }
@@ -67,10 +70,7 @@ fn get_choose_local_swf_path<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.chooseLocalSwfPath() not implemented"
-    );
+    activation.unimplemented("System.security.chooseLocalSwfPath")?;
     Ok(Value::Undefined)
 }
 
@@ -79,10 +79,7 @@ fn policy_file_resolver<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.chooseLocalSwfPath() not implemented"
-    );
+    activation.unimplemented("System.security.policyFileResolver")?;
     Ok(Value::Undefined)
 }
 
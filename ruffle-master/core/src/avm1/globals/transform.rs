@@ -110,14 +110,10 @@ fn concatenated_color_transform<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     clip: MovieClip<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    // Walk through parents to get combined color transform.
-    let mut color_transform = *clip.color_transform();
-    let mut node = clip.parent();
-    while let Some(display_object) = node {
-        color_transform = *display_object.color_transform() * color_transform;
-        node = display_object.parent();
-    }
-    let color_transform = color_transform::color_transform_to_object(color_transform, activation)?;
+    let color_transform = color_transform::color_transform_to_object(
+        clip.local_to_global_color_transform(),
+        activation,
+    )?;
     Ok(color_transform)
 }
 
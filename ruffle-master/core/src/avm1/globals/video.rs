@@ -4,8 +4,11 @@ use crate::avm1::activation::Activation;
 use crate::avm1::error::Error;
 use crate::avm1::globals::display_object;
 use crate::avm1::object::Object;
+use crate::avm1::property::Attribute;
 use crate::avm1::value::Value;
-use crate::avm1::ScriptObject;
+use crate::avm1::{ScriptObject, TObject};
+use crate::avm_warn;
+use crate::display_object::TDisplayObject;
 use gc_arena::MutationContext;
 
 /// Implements `Video`
@@ -17,14 +20,73 @@ pub fn constructor<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Video.attachVideo`
+fn attach_video<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let video = match this.as_display_object().and_then(|o| o.as_video()) {
+        Some(video) => video,
+        None => return Ok(Value::Undefined),
+    };
+
+    let source = args.get(0).unwrap_or(&Value::Undefined);
+    let stream = match source {
+        Value::Null | Value::Undefined => None,
+        Value::Object(object) => match object.as_net_stream_object() {
+            Some(net_stream) => net_stream.stream(),
+            None => {
+                avm_warn!(
+                    activation,
+                    "Video.attachVideo: only NetStreams are supported"
+                );
+                return Ok(Value::Undefined);
+            }
+        },
+        _ => return Ok(Value::Undefined),
+    };
+    video.attach_net_stream(activation.context.gc_context, stream);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Video.clear`
+fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(video) = this.as_display_object().and_then(|o| o.as_video()) {
+        video.clear(activation.context.gc_context);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn create_proto<'gc>(
     gc_context: MutationContext<'gc, '_>,
     proto: Object<'gc>,
     fn_proto: Object<'gc>,
 ) -> Object<'gc> {
-    let object = ScriptObject::object(gc_context, Some(proto));
+    let mut object = ScriptObject::object(gc_context, Some(proto));
 
     display_object::define_display_object_proto(gc_context, object, fn_proto);
 
+    object.force_set_function(
+        "attachVideo",
+        attach_video,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+    object.force_set_function(
+        "clear",
+        clear,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
     object.into()
 }
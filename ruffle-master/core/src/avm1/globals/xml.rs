@@ -8,7 +8,7 @@ use crate::avm1::object::xml_object::XmlObject;
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, TObject, Value};
 use crate::avm_warn;
-use crate::backend::navigator::RequestOptions;
+use crate::net::{self, Request};
 use crate::xml;
 use crate::xml::{XmlDocument, XmlNode};
 use gc_arena::MutationContext;
@@ -82,6 +82,7 @@ pub fn xmlnode_append_child<'gc>(
         args.get(0)
             .and_then(|n| n.coerce_to_object(activation).as_xml_node()),
     ) {
+        // Flash ignores nodes that already belong to a tree.
         if let Ok(None) = child_xmlnode.parent() {
             let position = xmlnode.children_len();
             if let Err(e) =
@@ -939,10 +940,7 @@ pub fn xml_load<'gc>(
 
         this.set("loaded", false.into(), activation)?;
 
-        let fetch = activation
-            .context
-            .navigator
-            .fetch(&url, RequestOptions::get());
+        let fetch = net::fetch_data(&activation.context, Request::get(url.to_string()));
         let target_clip = activation.target_clip_or_root()?;
         let process = activation.context.load_manager.load_xml_into_node(
             activation.context.player.clone().unwrap(),
@@ -959,6 +957,96 @@ pub fn xml_load<'gc>(
     }
 }
 
+/// Build the POST request used by `send` and `sendAndLoad`.
+///
+/// The body is the serialized document, tagged with the object's
+/// `contentType`.
+fn xml_post_request<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    node: XmlNode<'gc>,
+    url: String,
+) -> Result<Request, Error<'gc>> {
+    let body = node
+        .into_string(&mut is_as2_compatible)
+        .unwrap_or_else(|e| {
+            avm_warn!(activation, "XML serialization failed: {}", e);
+            "".to_string()
+        });
+    let content_type = match this.get("contentType", activation)? {
+        Value::Undefined | Value::Null => "application/x-www-form-urlencoded".to_string(),
+        v => v.coerce_to_string(activation)?.to_string(),
+    };
+
+    Ok(Request::post(url, Some((body.into_bytes(), content_type))))
+}
+
+pub fn xml_send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(Value::Undefined) | Some(Value::Null) | None => return Ok(false.into()),
+        Some(url) => url.coerce_to_string(activation)?,
+    };
+
+    if let Some(node) = this.as_xml_node() {
+        if let Some(window) = args.get(1) {
+            if !matches!(window, Value::Undefined) {
+                avm_warn!(
+                    activation,
+                    "XML.send: Displaying the response in a window is not supported"
+                );
+            }
+        }
+
+        let request = xml_post_request(activation, this, node, url.to_string())?;
+        net::spawn_fetch(&mut activation.context, request, |_context, response| {
+            response.map(|_| ())
+        });
+
+        Ok(true.into())
+    } else {
+        Ok(false.into())
+    }
+}
+
+pub fn xml_send_and_load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let url = match args.get(0) {
+        Some(Value::Undefined) | Some(Value::Null) | None => return Ok(false.into()),
+        Some(url) => url.coerce_to_string(activation)?,
+    };
+    let target = match args.get(1) {
+        Some(&Value::Object(o)) => o,
+        _ => return Ok(false.into()),
+    };
+
+    if let (Some(node), Some(target_node)) = (this.as_xml_node(), target.as_xml_node()) {
+        target.set("loaded", false.into(), activation)?;
+
+        let request = xml_post_request(activation, this, node, url.to_string())?;
+        let fetch = net::fetch_data(&activation.context, request);
+        let target_clip = activation.target_clip_or_root()?;
+        let process = activation.context.load_manager.load_xml_into_node(
+            activation.context.player.clone().unwrap(),
+            target_node,
+            target_clip,
+            fetch,
+        );
+
+        activation.context.navigator.spawn_future(process);
+
+        Ok(true.into())
+    } else {
+        Ok(false.into())
+    }
+}
+
 pub fn xml_on_data<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -1093,6 +1181,12 @@ pub fn create_xml_proto<'gc>(
         Attribute::READ_ONLY,
     );
     xml_proto.define_value(gc_context, "ignoreWhite", false.into(), Attribute::empty());
+    xml_proto.define_value(
+        gc_context,
+        "contentType",
+        "application/x-www-form-urlencoded".into(),
+        Attribute::empty(),
+    );
     xml_proto.add_property(
         gc_context,
         "xmlDecl",
@@ -1157,6 +1251,20 @@ pub fn create_xml_proto<'gc>(
         Attribute::empty(),
         Some(fn_proto),
     );
+    xml_proto.as_script_object().unwrap().force_set_function(
+        "send",
+        xml_send,
+        gc_context,
+        Attribute::empty(),
+        Some(fn_proto),
+    );
+    xml_proto.as_script_object().unwrap().force_set_function(
+        "sendAndLoad",
+        xml_send_and_load,
+        gc_context,
+        Attribute::empty(),
+        Some(fn_proto),
+    );
     xml_proto.as_script_object().unwrap().force_set_function(
         "onData",
         xml_on_data,
@@ -0,0 +1,146 @@
+//! AVM1 XMLSocket object
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::socket::{SocketManager, SocketObject};
+use gc_arena::MutationContext;
+use url::Url;
+
+/// Implements `XMLSocket`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "connect",
+        connect,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "close",
+        close,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "onData",
+        on_data,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
+
+fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // A null host means the host that the movie was loaded from.
+    let host = match args.get(0) {
+        None | Some(Value::Undefined) | Some(Value::Null) => activation
+            .context
+            .swf
+            .url()
+            .and_then(|url| Url::parse(url).ok())
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or_else(|| "localhost".to_string()),
+        Some(host) => host.coerce_to_string(activation)?.to_string(),
+    };
+    let port = args
+        .get(1)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_i32(activation)?;
+    if port <= 0 || port > 65535 {
+        return Ok(false.into());
+    }
+
+    Ok(SocketManager::connect(
+        &mut activation.context,
+        SocketObject::Avm1XmlSocket(this),
+        &host,
+        port as u16,
+    )
+    .into())
+}
+
+fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let message = match args.get(0) {
+        Some(message) => message.coerce_to_string(activation)?,
+        None => return Ok(Value::Undefined),
+    };
+    let mut data = message.as_bytes().to_vec();
+    data.push(0);
+
+    SocketManager::send(
+        &mut activation.context,
+        SocketObject::Avm1XmlSocket(this),
+        &data,
+    );
+
+    Ok(Value::Undefined)
+}
+
+fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    SocketManager::close(&mut activation.context, SocketObject::Avm1XmlSocket(this));
+
+    Ok(Value::Undefined)
+}
+
+fn on_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    // Default implementation parses the message and forwards it to onXML.
+    let src = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let xml_constructor = activation
+        .context
+        .avm1
+        .global_object_cell()
+        .get("XML", activation)?
+        .coerce_to_object(activation);
+    let xml = xml_constructor.construct(activation, &[src])?;
+
+    if let Value::Object(_) = this.get("onXML", activation)? {
+        this.call_method("onXML", &[xml], activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
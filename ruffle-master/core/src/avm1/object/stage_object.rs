@@ -6,7 +6,7 @@ use crate::avm1::function::Executable;
 use crate::avm1::object::search_prototype;
 use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, ObjectPtr, ScriptObject, TDisplayObject, TObject, Value};
-use crate::avm_warn;
+use crate::config::StageQuality;
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, EditText, MovieClip, TDisplayObjectContainer};
 use crate::property_map::PropertyMap;
@@ -836,7 +836,7 @@ fn drop_target<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _droptarget");
+    activation.unimplemented("_droptarget")?;
     Ok("".into())
 }
 
@@ -856,16 +856,25 @@ fn high_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _highquality");
-    Ok(1.into())
+    let high_quality = match *activation.context.quality {
+        StageQuality::Low => 0,
+        StageQuality::Medium | StageQuality::High => 1,
+        StageQuality::Best => 2,
+    };
+    Ok(high_quality.into())
 }
 
 fn set_high_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _highquality");
+    let quality = match val.coerce_to_i32(activation)? {
+        0 => StageQuality::Low,
+        1 => StageQuality::High,
+        _ => StageQuality::Best,
+    };
+    *activation.context.quality = quality;
     Ok(())
 }
 
@@ -873,7 +882,7 @@ fn focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
+    activation.unimplemented("_focusrect")?;
     Ok(Value::Null)
 }
 
@@ -882,7 +891,7 @@ fn set_focus_rect<'gc>(
     _this: DisplayObject<'gc>,
     _val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
+    activation.unimplemented("_focusrect")?;
     Ok(())
 }
 
@@ -890,7 +899,7 @@ fn sound_buf_time<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _soundbuftime");
+    activation.unimplemented("_soundbuftime")?;
     Ok(5.into())
 }
 
@@ -899,7 +908,7 @@ fn set_sound_buf_time<'gc>(
     _this: DisplayObject<'gc>,
     _val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _soundbuftime");
+    activation.unimplemented("_soundbuftime")?;
     Ok(())
 }
 
@@ -907,16 +916,19 @@ fn quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _quality");
-    Ok("HIGH".into())
+    Ok(activation.context.quality.as_str().into())
 }
 
 fn set_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _quality");
+    // Unknown qualities are ignored.
+    let name = val.coerce_to_string(activation)?;
+    if let Some(quality) = StageQuality::from_name(&name) {
+        *activation.context.quality = quality;
+    }
     Ok(())
 }
 
@@ -75,12 +75,14 @@ impl<'gc> TObject<'gc> for XmlAttributesObject<'gc> {
         value: Value<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<(), Error<'gc>> {
+        // Attributes are stored on the node itself, so that changes are
+        // visible through every attributes object of the node.
         self.node().set_attribute_value(
             activation.context.gc_context,
             &XmlName::from_str(name),
             &value.coerce_to_string(activation)?,
         );
-        self.base().set(name, value, activation)
+        Ok(())
     }
     fn call(
         &self,
@@ -4,20 +4,33 @@ use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::{Avm1, Object, Timers, UpdateContext};
 use crate::avm2::Avm2;
 use crate::backend::audio::{AudioManager, NullAudioBackend};
+use crate::backend::clipboard::MemoryClipboardBackend;
+use crate::backend::local_connection::InProcessLocalConnectionBackend;
 use crate::backend::locale::NullLocaleBackend;
 use crate::backend::log::NullLogBackend;
 use crate::backend::navigator::NullNavigatorBackend;
+use crate::backend::print::NullPrintBackend;
 use crate::backend::render::NullRenderer;
+use crate::backend::socket::NullSocketBackend;
 use crate::backend::storage::MemoryStorageBackend;
-use crate::backend::ui::NullUiBackend;
+use crate::backend::ui::{NullUiBackend, StageOrientation};
 use crate::backend::video::NullVideoBackend;
-use crate::context::ActionQueue;
+use crate::config::{StageAlign, StageQuality, StageScaleMode};
+use crate::context::{ActionQueue, LogContext};
 use crate::display_object::{MovieClip, TDisplayObject};
+use crate::events::KeyboardState;
+use crate::executor::Executor;
 use crate::focus_tracker::FocusTracker;
+use crate::i18n::EnglishMessageCatalog;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnectionManager;
 use crate::prelude::*;
+use crate::security::SecuritySandbox;
+use crate::socket::SocketManager;
+use crate::streams::StreamManager;
 use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::telemetry::Telemetry;
 use crate::vminterface::Instantiator;
 use gc_arena::{rootless_arena, MutationContext};
 use instant::Instant;
@@ -53,21 +66,35 @@ where
             rng: &mut SmallRng::from_seed([0u8; 32]),
             audio: &mut NullAudioBackend::new(),
             ui: &mut NullUiBackend::new(),
+            clipboard: &mut MemoryClipboardBackend::new(),
             action_queue: &mut ActionQueue::new(),
             background_color: &mut None,
+            frame_rate: &mut 12.0,
+            quality: &mut StageQuality::default(),
+            stage_scale_mode: &mut StageScaleMode::default(),
+            stage_align: &mut StageAlign::default(),
+            orientation: &mut StageOrientation::default(),
+            text_rendering: Default::default(),
             library: &mut Library::empty(gc_context),
             navigator: &mut NullNavigatorBackend::new(),
             renderer: &mut NullRenderer::new(),
             locale: &mut NullLocaleBackend::new(),
             log: &mut NullLogBackend::new(),
             video: &mut NullVideoBackend::new(),
+            socket: &mut NullSocketBackend::new(),
+            local_connection: &mut InProcessLocalConnectionBackend::default(),
+            print: &mut NullPrintBackend::new(),
             mouse_hovered_object: None,
             mouse_position: &(Twips::zero(), Twips::zero()),
+            keyboard: &KeyboardState::default(),
             drag_object: &mut None,
             stage_size: (Twips::from_pixels(550.0), Twips::from_pixels(400.0)),
             player: None,
             load_manager: &mut LoadManager::new(),
             system: &mut SystemProperties::default(),
+            security: &mut SecuritySandbox::new(),
+            messages: &EnglishMessageCatalog,
+            telemetry: &mut Telemetry::new(),
             instance_counter: &mut 0,
             storage: &mut MemoryStorageBackend::default(),
             shared_objects: &mut HashMap::new(),
@@ -82,6 +109,12 @@ where
             focus_tracker: FocusTracker::new(gc_context),
             times_get_time_called: 0,
             time_offset: &mut 0,
+            script_errors_suppressed: &mut false,
+            executor: &mut Executor::new(),
+            log_context: LogContext::default(),
+            stream_manager: &mut StreamManager::new(),
+            socket_manager: &mut SocketManager::new(),
+            local_connection_manager: &mut LocalConnectionManager::new(),
             audio_manager: &mut AudioManager::new(),
         };
         root.post_instantiation(&mut context, root, None, Instantiator::Movie, false);
@@ -23,3 +23,25 @@ fn locals_into_form_values() {
         Ok(())
     });
 }
+
+#[test]
+fn unimplemented_throws_in_strict_mode() {
+    with_avm(19, |activation, _this| -> Result<(), Error> {
+        assert!(activation.unimplemented("Test.lenient").is_ok());
+
+        activation.context.avm1.set_strict_mode(true);
+        match activation.unimplemented("Test.strict") {
+            Err(Error::ThrownValue(error)) => {
+                let error = error.coerce_to_object(activation);
+                assert_eq!(
+                    error.get("message", activation)?,
+                    "Test.strict is not implemented".into()
+                );
+                assert_eq!(error.get("name", activation)?, "Error".into());
+            }
+            _ => panic!("Expected a thrown Error"),
+        }
+
+        Ok(())
+    });
+}
@@ -111,11 +111,17 @@ impl PartialEq for Value<'_> {
 }
 
 impl<'gc> Value<'gc> {
+    /// Coerce this value to a number the way the untyped SWF4 actions
+    /// (`Add`, `Equals`, `Less` and friends) do.
+    ///
+    /// Unlike `coerce_to_f64`, this never produces `NaN` from a non-number:
+    /// strings are read with `string_to_f64_v1`, and anything else that isn't
+    /// `true` is `0`.
     pub fn into_number_v1(self) -> f64 {
         match self {
             Value::Bool(true) => 1.0,
             Value::Number(v) => v,
-            Value::String(v) => v.parse().unwrap_or(0.0),
+            Value::String(v) => string_to_f64_v1(&v),
             _ => 0.0,
         }
     }
@@ -438,6 +444,21 @@ impl<'gc> Value<'gc> {
         })
     }
 
+    /// Coerce a value to a string the way `trace` prints it.
+    ///
+    /// This is `coerce_to_string`, except that `undefined` is always printed
+    /// as "undefined", even though SWF6 and below normally coerce it to "".
+    pub fn to_trace_string(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<AvmString<'gc>, Error<'gc>> {
+        if *self == Value::Undefined {
+            Ok("undefined".into())
+        } else {
+            self.coerce_to_string(activation)
+        }
+    }
+
     pub fn as_bool(&self, swf_version: u8) -> bool {
         match self {
             Value::Bool(v) => *v,
@@ -445,9 +466,11 @@ impl<'gc> Value<'gc> {
             Value::String(v) => {
                 if swf_version >= 7 {
                     !v.is_empty()
+                } else if swf_version < 5 {
+                    // SWF4 conditions read strings the same way SWF4 arithmetic does.
+                    string_to_f64_v1(v) != 0.0
                 } else {
-                    let num = v.parse().unwrap_or(0.0);
-                    num != 0.0
+                    !is_word_number(v) && v.parse().unwrap_or(0.0) != 0.0
                 }
             }
             Value::Object(_) => true,
@@ -486,6 +509,60 @@ impl<'gc> Value<'gc> {
     }
 }
 
+/// Converts a string to a number the way the SWF4 actions do, which is much
+/// like C's `atof`: leading whitespace is skipped and the longest decimal
+/// number at the start of the string is used, ignoring whatever follows.
+/// A string that doesn't start with a number is `0`.
+fn string_to_f64_v1(s: &str) -> f64 {
+    let s = s.trim_start();
+    let bytes = s.as_bytes();
+    let mut end = 0;
+    let skip_digits = |mut i: usize| {
+        while bytes.get(i).map_or(false, u8::is_ascii_digit) {
+            i += 1;
+        }
+        i
+    };
+
+    if let Some(b'+') | Some(b'-') = bytes.first() {
+        end += 1;
+    }
+    let int_end = skip_digits(end);
+    let mut has_digits = int_end > end;
+    end = int_end;
+    if bytes.get(end) == Some(&b'.') {
+        let frac_end = skip_digits(end + 1);
+        has_digits |= frac_end > end + 1;
+        end = frac_end;
+    }
+    if !has_digits {
+        return 0.0;
+    }
+
+    // An exponent only counts if it has digits.
+    if let Some(b'e') | Some(b'E') = bytes.get(end) {
+        let mut exp_start = end + 1;
+        if let Some(b'+') | Some(b'-') = bytes.get(exp_start) {
+            exp_start += 1;
+        }
+        let exp_end = skip_digits(exp_start);
+        if exp_end > exp_start {
+            end = exp_end;
+        }
+    }
+
+    s[..end].parse().unwrap_or(0.0)
+}
+
+/// Whether a string is one of the spelled-out numbers that Rust's float
+/// parsing accepts but Flash doesn't, such as `"inf"` or `"NaN"`.
+fn is_word_number(s: &str) -> bool {
+    let s = s.trim_start_matches(|c| c == '+' || c == '-');
+    s.eq_ignore_ascii_case("inf")
+        || s.eq_ignore_ascii_case("infinity")
+        || s.eq_ignore_ascii_case("nan")
+}
+
 #[cfg(test)]
 mod test {
     use crate::avm1::activation::Activation;
@@ -595,6 +672,51 @@ mod test {
         });
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn to_number_v1() {
+        with_avm(4, |activation, _this| -> Result<(), Error> {
+            let mc = activation.context.gc_context;
+            let string = |s: &str| Value::String(AvmString::new(mc, s));
+
+            assert_eq!(string("12").into_number_v1(), 12.0);
+            assert_eq!(string(" \t-1.5e2xyz").into_number_v1(), -150.0);
+            assert_eq!(string("3.").into_number_v1(), 3.0);
+            assert_eq!(string(".5").into_number_v1(), 0.5);
+            assert_eq!(string("2e").into_number_v1(), 2.0);
+            assert_eq!(string("abc").into_number_v1(), 0.0);
+            assert_eq!(string("").into_number_v1(), 0.0);
+            assert_eq!(string("-").into_number_v1(), 0.0);
+            assert_eq!(string("inf").into_number_v1(), 0.0);
+            assert_eq!(string("NaN").into_number_v1(), 0.0);
+            assert_eq!(Value::Undefined.into_number_v1(), 0.0);
+            assert_eq!(Value::Bool(true).into_number_v1(), 1.0);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn as_bool_string() {
+        with_avm(4, |activation, _this| -> Result<(), Error> {
+            let mc = activation.context.gc_context;
+            let string = |s: &str| Value::String(AvmString::new(mc, s));
+
+            assert!(string("1abc").as_bool(4));
+            assert!(!string("1abc").as_bool(6));
+            assert!(string("1abc").as_bool(7));
+            assert!(!string("0").as_bool(4));
+            assert!(!string("0").as_bool(6));
+            assert!(string("0").as_bool(7));
+            assert!(!string("inf").as_bool(4));
+            assert!(!string("inf").as_bool(6));
+            assert!(!string("NaN").as_bool(6));
+            assert!(string("2").as_bool(6));
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn abstract_lt_num() {
         with_avm(8, |activation, _this| -> Result<(), Error> {
@@ -772,5 +894,9 @@ mod test {
         assert_eq!(f64_to_string(-1e-5), "-0.00001");
         assert_eq!(f64_to_string(0.999e-5), "9.99e-6");
         assert_eq!(f64_to_string(-0.999e-5), "-9.99e-6");
+        assert_eq!(f64_to_string(0.1 + 0.2), "0.3");
+        assert_eq!(f64_to_string(1.0 / 3.0), "0.333333333333333");
+        assert_eq!(f64_to_string(999999999999999.9), "1e+15");
+        assert_eq!(f64_to_string(123456789012345680.0), "1.23456789012346e+17");
     }
 }
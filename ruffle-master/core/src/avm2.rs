@@ -1,11 +1,22 @@
 //! ActionScript Virtual Machine 2 (AS3) support
 
-use crate::avm2::globals::SystemPrototypes;
+use crate::amf::ScriptValue;
+use crate::avm2::globals::{
+    FileSession, GameInputDevice, ShownContextMenu, SystemPrototypes, UrlStreamLoad,
+};
 use crate::avm2::method::Method;
 use crate::avm2::object::EventObject;
 use crate::avm2::script::{Script, TranslationUnit};
 use crate::avm2::string::AvmString;
+use crate::avm2::traits::TraitKind;
+use crate::backend::audio::SoundInstanceHandle;
+use crate::backend::gamepad::GamepadState;
+use crate::backend::navigator::Chunk;
+use crate::backend::ui::{ContextMenuItem, DialogFile, MouseCursor, StageOrientation};
 use crate::context::UpdateContext;
+use crate::events::KeyCode;
+use crate::executor::TaskHandle;
+use crate::loader::Error as LoaderError;
 use crate::tag_utils::SwfSlice;
 use gc_arena::{Collect, MutationContext};
 use std::collections::HashMap;
@@ -45,13 +56,16 @@ mod value;
 
 pub use crate::avm2::activation::Activation;
 pub use crate::avm2::array::ArrayStorage;
+pub use crate::avm2::class::ClassMetadata;
 pub use crate::avm2::domain::Domain;
 pub use crate::avm2::events::Event;
 pub use crate::avm2::names::{Namespace, QName};
-pub use crate::avm2::object::{ArrayObject, Object, ScriptObject, StageObject, TObject};
+pub use crate::avm2::object::{
+    ArrayObject, Object, ScriptObject, SoundChannelObject, StageObject, TObject,
+};
 pub use crate::avm2::value::Value;
 
-const BROADCAST_WHITELIST: [&str; 3] = ["enterFrame", "exitFrame", "frameConstructed"];
+const BROADCAST_WHITELIST: [&str; 4] = ["enterFrame", "exitFrame", "frameConstructed", "render"];
 
 /// Boxed error alias.
 ///
@@ -82,6 +96,51 @@ pub struct Avm2<'gc> {
     /// collector does not support weak references.
     broadcast_list: HashMap<AvmString<'gc>, Vec<Object<'gc>>>,
 
+    /// The `Stage` object shared by every display object on the stage.
+    ///
+    /// This is created the first time a script asks for it.
+    stage: Option<Object<'gc>>,
+
+    /// Whether `Stage.invalidate` was called since the last `render` event.
+    stage_invalidated: bool,
+
+    /// Whether the last frame ended without broadcasting `exitFrame`, which
+    /// is then sent when the next frame starts.
+    exit_frame_pending: bool,
+
+    /// The cursor set through `Mouse.cursor`, which overrides the one the
+    /// player would show for the hovered object.
+    #[collect(require_static)]
+    mouse_cursor: Option<MouseCursor>,
+
+    /// Every `GameInput` that has been constructed, which are all told when a
+    /// gamepad is connected or disconnected.
+    ///
+    /// TODO: These should be weak object pointers, like the broadcast list.
+    game_inputs: Vec<Object<'gc>>,
+
+    /// The gamepads that are connected, in the order `GameInput` lists them.
+    game_input_devices: Vec<GameInputDevice<'gc>>,
+
+    /// The context menu that the UI is currently showing, if it was built
+    /// from a `ContextMenu`.
+    shown_context_menu: Option<ShownContextMenu<'gc>>,
+
+    /// The `FileReference` that is waiting for the user to pick a file, or
+    /// for a download to finish.
+    file_session: Option<FileSession<'gc>>,
+
+    /// The `FileReference`s whose contents will be handed over at the next
+    /// frame, in the order `load` was called.
+    file_loads: Vec<Object<'gc>>,
+
+    /// The `URLStream`s that are waiting for a response.
+    url_stream_loads: Vec<UrlStreamLoad<'gc>>,
+
+    /// Whether calls to unimplemented builtins stop the running script,
+    /// rather than only logging a warning.
+    strict_mode: bool,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -96,6 +155,17 @@ impl<'gc> Avm2<'gc> {
             globals,
             system_prototypes: None,
             broadcast_list: HashMap::new(),
+            stage: None,
+            stage_invalidated: false,
+            exit_frame_pending: false,
+            mouse_cursor: None,
+            game_inputs: Vec::new(),
+            game_input_devices: Vec::new(),
+            shown_context_menu: None,
+            file_session: None,
+            file_loads: Vec::new(),
+            url_stream_loads: Vec::new(),
+            strict_mode: false,
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
@@ -115,6 +185,42 @@ impl<'gc> Avm2<'gc> {
         self.system_prototypes.as_ref().unwrap()
     }
 
+    /// The `Stage` object, if a script has asked for it yet.
+    pub fn stage_object(&self) -> Option<Object<'gc>> {
+        self.stage
+    }
+
+    pub fn set_stage_object(&mut self, stage: Object<'gc>) {
+        self.stage = Some(stage);
+    }
+
+    /// Ask for a `render` event to be broadcast before the stage is next
+    /// drawn.
+    pub fn invalidate_stage(&mut self) {
+        self.stage_invalidated = true;
+    }
+
+    /// Whether a `render` event was asked for.
+    pub fn is_stage_invalidated(&self) -> bool {
+        self.stage_invalidated
+    }
+
+    /// Whether a `render` event was asked for, clearing the request.
+    pub fn take_stage_invalidated(&mut self) -> bool {
+        std::mem::take(&mut self.stage_invalidated)
+    }
+
+    /// Hold the current frame's `exitFrame` broadcast until the next frame
+    /// starts.
+    pub fn defer_exit_frame(&mut self) {
+        self.exit_frame_pending = true;
+    }
+
+    /// Whether an `exitFrame` broadcast is still owed, clearing it.
+    pub fn take_exit_frame_pending(&mut self) -> bool {
+        std::mem::take(&mut self.exit_frame_pending)
+    }
+
     /// Run a script's initializer method.
     pub fn run_script_initializer(
         script: Script<'gc>,
@@ -151,6 +257,250 @@ impl<'gc> Avm2<'gc> {
         dispatch_event(&mut activation, target, event_object)
     }
 
+    /// Request more sample data for a dynamic sound by dispatching a
+    /// `sampleData` event to the `Sound` that is playing on `channel`.
+    pub fn request_sample_data(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        instance: SoundInstanceHandle,
+        channel: SoundChannelObject<'gc>,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::request_sample_data(&mut activation, instance, channel)
+    }
+
+    /// Dispatch a `TextEvent` with the given type and text to `target`.
+    pub fn dispatch_text_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        event_type: &'static str,
+        text: &str,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::dispatch_text_event(&mut activation, target, event_type, text)
+    }
+
+    /// Dispatch a `MouseEvent` with the given type to `target`, at the current
+    /// mouse position.
+    pub fn dispatch_mouse_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        event_type: &'static str,
+        related: Option<Object<'gc>>,
+        button_down: bool,
+        delta: i32,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::dispatch_mouse_event(
+            &mut activation,
+            target,
+            event_type,
+            related,
+            button_down,
+            delta,
+        )
+    }
+
+    /// Dispatch a `KeyboardEvent` with the given type to `target`.
+    pub fn dispatch_keyboard_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        event_type: &'static str,
+        key_code: KeyCode,
+        char_code: u32,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::dispatch_keyboard_event(&mut activation, target, event_type, key_code, char_code)
+    }
+
+    /// Dispatch an `ioError` event with the given text to `target`.
+    pub fn dispatch_io_error_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        text: &str,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::dispatch_io_error_event(&mut activation, target, text)
+    }
+
+    /// Dispatch a `data` event carrying `data` to `target`.
+    pub fn dispatch_data_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        data: &str,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::dispatch_data_event(&mut activation, target, data)
+    }
+
+    /// Dispatch a `status` event with the given code and level to `target`.
+    pub fn dispatch_status_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        code: Option<&str>,
+        level: &str,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::dispatch_status_event(&mut activation, target, code, level)
+    }
+
+    /// Call the method named by a message sent to a `LocalConnection` on the
+    /// connection's client.
+    pub fn receive_local_connection_message(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        sender_domain: &str,
+        method: &str,
+        args: &[ScriptValue],
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::receive_local_connection_message(
+            &mut activation,
+            target,
+            sender_domain,
+            method,
+            args,
+        )
+    }
+
+    /// Pass data received on a connection to its `Socket`, and dispatch a
+    /// `socketData` event to it.
+    pub fn receive_socket_data(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        socket: Object<'gc>,
+        data: &[u8],
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::receive_socket_data(&mut activation, socket, data)
+    }
+
+    /// Dispatch a `StageOrientationEvent` with the given type to `target`.
+    ///
+    /// The `bool` parameter reads true if the event was cancelled.
+    pub fn dispatch_stage_orientation_event(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        target: Object<'gc>,
+        event_type: &'static str,
+        before_orientation: StageOrientation,
+        after_orientation: StageOrientation,
+    ) -> Result<bool, Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::dispatch_stage_orientation_event(
+            &mut activation,
+            target,
+            event_type,
+            before_orientation,
+            after_orientation,
+        )
+    }
+
+    /// Update the `GameInputDevice`s to match the gamepads that are currently
+    /// connected, dispatching events for any changes.
+    pub fn update_game_input(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        gamepads: &[GamepadState],
+    ) -> Result<(), Error> {
+        if gamepads.is_empty() && context.avm2.game_input_devices.is_empty() {
+            return Ok(());
+        }
+
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::update_game_input(&mut activation, gamepads)
+    }
+
+    /// Get the items of the context menu for a right-click on the first of
+    /// `targets`, which is followed by its ancestors.
+    ///
+    /// The items are remembered until `run_context_menu_item` is called.
+    pub fn prepare_context_menu(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        targets: &[Object<'gc>],
+    ) -> Result<Vec<ContextMenuItem>, Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::prepare_context_menu(&mut activation, targets)
+    }
+
+    /// Tell a `ContextMenuItem` in the last prepared context menu that the
+    /// user selected it.
+    pub fn run_context_menu_item(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        index: usize,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::run_context_menu_item(&mut activation, index)
+    }
+
+    /// Tell the `FileReference` that opened the last file dialog which file
+    /// the user picked, or `None` if they cancelled.
+    pub fn finish_file_dialog(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        file: Option<DialogFile>,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::finish_file_dialog(&mut activation, file)
+    }
+
+    /// Ask the UI where to save the file that a `FileReference` downloaded.
+    pub fn finish_file_download(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        response: Result<Vec<u8>, LoaderError>,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::finish_file_download(&mut activation, response)
+    }
+
+    /// Hand the contents of a file to the next `FileReference` that called
+    /// `load`.
+    pub fn finish_file_load(context: &mut UpdateContext<'_, 'gc, '_>) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::finish_file_load(&mut activation)
+    }
+
+    /// Deliver a chunk of the response fetched by `task` to the `URLStream`
+    /// that is waiting for it, or `None` once the response has ended.
+    pub fn receive_url_stream_chunk(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        task: TaskHandle,
+        chunk: Option<Result<Chunk, LoaderError>>,
+    ) -> Result<(), Error> {
+        let mut activation = Activation::from_nothing(context.reborrow());
+
+        globals::receive_url_stream_chunk(&mut activation, task, chunk)
+    }
+
+    pub fn strict_mode(&self) -> bool {
+        self.strict_mode
+    }
+
+    pub fn set_strict_mode(&mut self, strict_mode: bool) {
+        self.strict_mode = strict_mode
+    }
+
+    /// The cursor that `Mouse.cursor` has forced, if any.
+    pub fn mouse_cursor(&self) -> Option<MouseCursor> {
+        self.mouse_cursor
+    }
+
+    /// Force the cursor to `cursor`, or let the player pick it when `None`.
+    pub fn set_mouse_cursor(&mut self, cursor: Option<MouseCursor>) {
+        self.mouse_cursor = cursor;
+    }
+
     /// Add an object to the broadcast list.
     ///
     /// Each broadcastable event contains it's own broadcast list. You must
@@ -269,6 +619,31 @@ impl<'gc> Avm2<'gc> {
         self.globals
     }
 
+    /// Look up the metadata of a class defined in `domain`, and of its
+    /// traits, such as the `[Inject]` or `[Bindable]` tags that frameworks
+    /// wire themselves up with.
+    ///
+    /// Returns `None` if the domain defines no class with that name.
+    pub fn class_metadata(
+        domain: Domain<'gc>,
+        name: QName<'gc>,
+    ) -> Result<Option<ClassMetadata<'gc>>, Error> {
+        let (name, script) = match domain.get_defining_script(&name.into())? {
+            Some(definition) => definition,
+            None => return Ok(None),
+        };
+
+        for script_trait in script.traits()?.iter() {
+            if let TraitKind::Class { class, .. } = script_trait.kind() {
+                if script_trait.name() == &name {
+                    return Ok(Some(class.read().all_metadata()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Push a value onto the operand stack.
     fn push(&mut self, value: impl Into<Value<'gc>>) {
         let value = value.into();
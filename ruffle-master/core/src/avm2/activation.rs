@@ -10,9 +10,11 @@ use crate::avm2::object::{Object, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::{value, Avm2, Error};
 use crate::context::UpdateContext;
+use crate::telemetry::AvmVersion;
 use gc_arena::{Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
 use swf::avm2::read::Reader;
@@ -98,6 +100,13 @@ pub struct Activation<'a, 'gc: 'a, 'gc_context: 'a> {
     /// A `scope` of `None` indicates that the scope stack is empty.
     scope: Option<GcCell<'gc, Scope<'gc>>>,
 
+    /// The scope stack this activation started with, such as the scope that
+    /// a closure captured when it was created.
+    ///
+    /// Scopes pushed on top of it belong to this activation, and are the
+    /// ones that `getscopeobject` indexes, starting from the bottom.
+    outer_scope: Option<GcCell<'gc, Scope<'gc>>>,
+
     /// The base prototype of `this`.
     ///
     /// This will not be available if this is not a method call.
@@ -126,6 +135,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return_value: None,
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope: None,
+            outer_scope: None,
             base_proto: None,
             context,
         }
@@ -165,6 +175,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return_value: None,
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
+            outer_scope: scope,
             base_proto: None,
             context,
         })
@@ -213,6 +224,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return_value: None,
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
+            outer_scope: scope,
             base_proto,
             context,
         };
@@ -286,6 +298,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             return_value: None,
             local_scope: ScriptObject::bare_object(context.gc_context),
             scope,
+            outer_scope: scope,
             base_proto,
             context,
         })
@@ -339,6 +352,22 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         function.call(Some(receiver), &args, self, Some(base_proto))
     }
 
+    /// Report the use of a builtin that Ruffle does not implement yet.
+    ///
+    /// This logs a warning naming the builtin and reports it to the telemetry
+    /// sink. In strict mode, it also returns an error, which stops the
+    /// running script.
+    pub fn unimplemented(&mut self, name: &str) -> Result<(), Error> {
+        log::warn!("{}: Unimplemented", name);
+        self.context.telemetry.unimplemented(AvmVersion::Avm2, name);
+
+        if self.context.avm2.strict_mode() {
+            return Err(format!("{} is not implemented", name).into());
+        }
+
+        Ok(())
+    }
+
     /// Attempts to lock the activation frame for execution.
     ///
     /// If this frame is already executing, that is an error condition.
@@ -537,7 +566,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         if let Ok(Some(op)) = op {
             avm_debug!(self.avm2(), "Opcode: {:?}", op);
 
-            let result = match op {
+            match op {
                 Op::PushByte { value } => self.op_push_byte(value),
                 Op::PushDouble { value } => self.op_push_double(method, value),
                 Op::PushFalse => self.op_push_false(),
@@ -597,7 +626,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                     self.op_construct_prop(method, index, num_args)
                 }
                 Op::ConstructSuper { num_args } => self.op_construct_super(num_args),
-                Op::NewActivation => self.op_new_activation(),
+                Op::NewActivation => self.op_new_activation(method),
                 Op::NewObject { num_args } => self.op_new_object(num_args),
                 Op::NewFunction { index } => self.op_new_function(method, index),
                 Op::NewClass { index } => self.op_new_class(method, index),
@@ -679,13 +708,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::DebugLine { line_num } => self.op_debug_line(line_num),
                 Op::TypeOf => self.op_type_of(),
                 _ => self.unknown_op(op),
-            };
-
-            if let Err(e) = result {
-                log::error!("AVM2 error: {}", e);
-                return Err(e);
             }
-            result
         } else if let Ok(None) = op {
             log::error!("Unknown opcode!");
             Err("Unknown opcode!".into())
@@ -848,11 +871,32 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         arg_count: u32,
     ) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
-        let receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        receiver.ensure_class_initialized(self)?;
+        let method_trait = receiver.get_method_trait(index.0);
+        let base_proto = match &method_trait {
+            Some(method_trait) => receiver.get_base_proto(method_trait.name())?,
+            None => None,
+        }
+        .or_else(|| receiver.proto());
+
+        // Traits are installed lazily, so the method table may not have this
+        // method yet even though the receiver's class declares it. Every
+        // trait with the same name is installed at once, so that a getter
+        // doesn't hide its setter.
+        if receiver.get_method(index.0).is_none() {
+            if let Some(method_trait) = &method_trait {
+                if !receiver.has_instantiated_property(method_trait.name()) {
+                    for abc_trait in receiver.get_trait(method_trait.name())? {
+                        receiver.install_trait(self, abc_trait, receiver)?;
+                    }
+                }
+            }
+        }
+
         let function: Result<Object<'gc>, Error> = receiver
             .get_method(index.0)
             .ok_or_else(|| format!("Object method {} does not exist", index.0).into());
-        let base_proto = receiver.proto();
         let value = function?.call(Some(receiver), &args, self, base_proto)?;
 
         self.context.avm2.push(value);
@@ -869,6 +913,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        receiver.ensure_class_initialized(self)?;
         let name: Result<QName, Error> = receiver
             .resolve_multiname(&multiname)?
             .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
@@ -893,6 +938,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        receiver.ensure_class_initialized(self)?;
         let name: Result<QName, Error> = receiver
             .resolve_multiname(&multiname)?
             .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
@@ -915,6 +961,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let args = self.context.avm2.pop_args(arg_count);
         let multiname = self.pool_multiname(method, index)?;
         let mut receiver = self.context.avm2.pop().coerce_to_object(self)?;
+        receiver.ensure_class_initialized(self)?;
         let name: Result<QName, Error> = receiver
             .resolve_multiname(&multiname)?
             .ok_or_else(|| format!("Could not find method {:?}", multiname.local_name()).into());
@@ -938,6 +985,22 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let args = self.context.avm2.pop_args(arg_count);
         let receiver = self.context.avm2.pop().coerce_to_object(self)?;
         let method = self.table_method(method, index, self.context.gc_context)?;
+
+        // Like `callmethod`, the method runs with the prototype of the class
+        // that declares it as its base, so that `super` inside it refers to
+        // that class's superclass even when called on an instance of a
+        // subclass.
+        let mut base_proto = receiver.proto();
+        while let Some(proto) = base_proto {
+            if let Some(class) = proto.as_class() {
+                if class.read().declares_instance_method(&method) {
+                    break;
+                }
+            }
+            base_proto = proto.proto();
+        }
+        let base_proto = base_proto.or_else(|| receiver.proto());
+
         let scope = self.scope(); //TODO: Is this correct?
         let function = FunctionObject::from_method(
             self.context.gc_context,
@@ -946,7 +1009,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             self.context.avm2.prototypes().function,
             None,
         );
-        let value = function.call(Some(receiver), &args, self, receiver.proto())?;
+        let value = function.call(Some(receiver), &args, self, base_proto)?;
 
         self.context.avm2.push(value);
 
@@ -1032,6 +1095,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     ) -> Result<FrameControl<'gc>, Error> {
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
+        object.ensure_class_initialized(self)?;
 
         let name: Result<QName, Error> = object.resolve_multiname(&multiname)?.ok_or_else(|| {
             format!("Could not resolve property {:?}", multiname.local_name()).into()
@@ -1063,6 +1127,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value = self.context.avm2.pop();
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
+        object.ensure_class_initialized(self)?;
 
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.set_property(object, &name, value, self)?;
@@ -1087,6 +1152,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value = self.context.avm2.pop();
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
+        object.ensure_class_initialized(self)?;
 
         if let Some(name) = object.resolve_multiname(&multiname)? {
             object.init_property(object, &name, value, self)?;
@@ -1226,22 +1292,31 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_get_scope_object(&mut self, mut index: u8) -> Result<FrameControl<'gc>, Error> {
+    fn op_get_scope_object(&mut self, index: u8) -> Result<FrameControl<'gc>, Error> {
+        // The index counts up from the first scope this activation pushed,
+        // not down from the innermost one, so that it refers to the same
+        // object (usually `this` or the activation object) however many
+        // `with` or `catch` scopes are pushed on top of it.
+        let mut local_scopes = Vec::new();
         let mut scope = self.scope();
-
-        while index > 0 {
-            if let Some(child_scope) = scope {
-                scope = child_scope.read().parent_cell();
+        while let Some(child_scope) = scope {
+            if let Some(outer_scope) = self.outer_scope {
+                if GcCell::ptr_eq(child_scope, outer_scope) {
+                    break;
+                }
             }
 
-            index -= 1;
+            local_scopes.push(child_scope);
+            scope = child_scope.read().parent_cell();
         }
 
-        self.context.avm2.push(
-            scope
-                .map(|s| s.read().locals().clone().into())
-                .unwrap_or(Value::Undefined),
-        );
+        let scope = local_scopes
+            .iter()
+            .rev()
+            .nth(index as usize)
+            .ok_or_else(|| format!("Scope object index {} out of range", index))?;
+        let value: Value<'gc> = scope.read().locals().clone().into();
+        self.context.avm2.push(value);
 
         Ok(FrameControl::Continue)
     }
@@ -1300,14 +1375,15 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let multiname = self.pool_multiname_static(method, index, self.context.gc_context)?;
         avm_debug!(self.avm2(), "Resolving {:?}", multiname);
         let found: Result<Value<'gc>, Error> = if let Some(scope) = self.scope() {
-            scope
-                .write(self.context.gc_context)
-                .resolve(&multiname, self)?
+            scope.read().resolve(&multiname, self)?
         } else {
             None
         }
         .ok_or_else(|| format!("Property does not exist: {:?}", multiname).into());
         let result: Value<'gc> = found?;
+        if let Value::Object(class) = &result {
+            class.ensure_class_initialized(self)?;
+        }
 
         self.context.avm2.push(result);
 
@@ -1317,6 +1393,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_get_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
         let object = self.context.avm2.pop().coerce_to_object(self)?;
         let value = object.get_slot(index)?;
+        if let Value::Object(class) = &value {
+            class.ensure_class_initialized(self)?;
+        }
 
         self.context.avm2.push(value);
 
@@ -1324,8 +1403,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     fn op_set_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
-        let object = self.context.avm2.pop().coerce_to_object(self)?;
         let value = self.context.avm2.pop();
+        let object = self.context.avm2.pop().coerce_to_object(self)?;
 
         object.set_slot(index, value, self.context.gc_context)?;
 
@@ -1334,6 +1413,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
     fn op_get_global_slot(&mut self, index: u32) -> Result<FrameControl<'gc>, Error> {
         let value = self.scope.unwrap().read().globals().get_slot(index)?;
+        if let Value::Object(class) = &value {
+            class.ensure_class_initialized(self)?;
+        }
 
         self.context.avm2.push(value);
 
@@ -1355,6 +1437,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     fn op_construct(&mut self, arg_count: u32) -> Result<FrameControl<'gc>, Error> {
         let args = self.context.avm2.pop_args(arg_count);
         let mut ctor = self.context.avm2.pop().coerce_to_object(self)?;
+        ctor.ensure_class_initialized(self)?;
 
         let proto = ctor
             .get_property(ctor, &QName::new(Namespace::public(), "prototype"), self)?
@@ -1385,6 +1468,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let mut ctor = source
             .get_property(source, &ctor_name?, self)?
             .coerce_to_object(self)?;
+        ctor.ensure_class_initialized(self)?;
         let proto = ctor
             .get_property(ctor, &QName::new(Namespace::public(), "prototype"), self)?
             .coerce_to_object(self)?;
@@ -1406,10 +1490,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
-    fn op_new_activation(&mut self) -> Result<FrameControl<'gc>, Error> {
-        self.context
-            .avm2
-            .push(ScriptObject::bare_object(self.context.gc_context));
+    /// Create the activation object, which holds the variables that the
+    /// method body declares as traits, such as ones captured by closures.
+    fn op_new_activation(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let mut activation_object = ScriptObject::bare_object(self.context.gc_context);
+        if let Some(body) = method.body() {
+            for abc_trait in body.traits.iter() {
+                let loaded_trait = Trait::from_abc_trait(
+                    method.translation_unit(),
+                    abc_trait,
+                    self.context.avm2,
+                    self.context.gc_context,
+                )?;
+                activation_object.install_trait(self, loaded_trait, activation_object)?;
+            }
+        }
+
+        self.context.avm2.push(activation_object);
 
         Ok(FrameControl::Continue)
     }
@@ -1443,6 +1543,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         index: Index<AbcMethod>,
     ) -> Result<FrameControl<'gc>, Error> {
         let method_entry = self.table_method(method, index, self.context.gc_context)?;
+
+        // Scope chains are immutable, so holding onto the current one captures
+        // it as it is right now. Scopes pushed or popped after this point, such
+        // as on the next iteration of a loop, don't affect this closure.
         let scope = self.scope();
 
         let mut new_fn = FunctionObject::from_method(
@@ -1484,10 +1588,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let class_entry = self.table_class(method, index)?;
         let scope = self.scope();
 
-        let (new_class, class_init) =
-            FunctionObject::from_class(self, class_entry, base_class, scope)?;
-
-        class_init.call(Some(new_class), &[], self, None)?;
+        let new_class = FunctionObject::from_class(self, class_entry, base_class, scope)?;
+        new_class.ensure_class_initialized(self)?;
 
         self.context.avm2.push(new_class);
 
@@ -1,14 +1,16 @@
 //! AVM2 classes
 
-use crate::avm2::method::Method;
+use crate::avm2::method::{BytecodeMethod, Method};
 use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::property_map::PropertyMap;
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::string::AvmString;
-use crate::avm2::traits::{Trait, TraitKind};
+use crate::avm2::traits::{Metadata, Trait, TraitKind};
 use crate::avm2::{Avm2, Error};
 use crate::collect::CollectWrapper;
 use bitflags::bitflags;
 use gc_arena::{Collect, GcCell, MutationContext};
+use std::collections::HashMap;
 use swf::avm2::types::{Class as AbcClass, Instance as AbcInstance};
 
 bitflags! {
@@ -56,7 +58,15 @@ pub struct Class<'gc> {
     /// These are accessed as normal instance properties; they should not be
     /// present on prototypes, but instead should shadow any prototype
     /// properties that would match.
-    instance_traits: Vec<Trait<'gc>>,
+    instance_traits: TraitTable<'gc>,
+
+    /// The instance traits of this class and all of its superclasses, with
+    /// overridden traits replaced by their overrides.
+    ///
+    /// This is built by `link_instance_traits` once the superclass is known.
+    /// Until then, only this class's own instance traits are visible on its
+    /// instances.
+    linked_instance_traits: Option<TraitTable<'gc>>,
 
     /// The class initializer for this class.
     ///
@@ -66,25 +76,151 @@ pub struct Class<'gc> {
     /// Static traits for a given class.
     ///
     /// These are accessed as constructor properties.
-    class_traits: Vec<Trait<'gc>>,
+    class_traits: TraitTable<'gc>,
 
     /// Whether or not this `Class` has loaded its traits or not.
     traits_loaded: bool,
+
+    /// The metadata the compiler attached to the trait that defines this
+    /// class.
+    metadata: Vec<Metadata<'gc>>,
 }
 
-/// Find traits in a list of traits matching a name.
-///
-/// This function also enforces final/override bits on the traits, and will
-/// raise `VerifyError`s as needed.
+/// The metadata of a class and of its traits, as written in its source.
 ///
-/// TODO: This is an O(n^2) algorithm, it sucks.
-fn do_trait_lookup<'gc>(
-    name: &QName<'gc>,
-    known_traits: &mut Vec<Trait<'gc>>,
-    all_traits: &[Trait<'gc>],
-) -> Result<(), Error> {
-    for trait_entry in all_traits {
-        if name == trait_entry.name() {
+/// Only traits that have metadata are listed.
+#[derive(Clone, Debug)]
+pub struct ClassMetadata<'gc> {
+    /// The metadata of the class itself.
+    pub class: Vec<Metadata<'gc>>,
+
+    /// The metadata of each instance trait.
+    pub instance_traits: Vec<(QName<'gc>, Vec<Metadata<'gc>>)>,
+
+    /// The metadata of each class (static) trait.
+    pub class_traits: Vec<(QName<'gc>, Vec<Metadata<'gc>>)>,
+}
+
+/// A list of traits, indexed by name and by dispatch id.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(no_drop)]
+struct TraitTable<'gc> {
+    /// The traits, in the order they were defined.
+    traits: Vec<Trait<'gc>>,
+
+    /// The positions in `traits` of the traits with each dispatch id.
+    methods: HashMap<u32, usize>,
+
+    /// The positions in `traits` of the traits with each name.
+    names: PropertyMap<'gc, Vec<usize>>,
+
+    /// The positions in `traits` of the traits implementing each interface
+    /// method, keyed by the name the interface declared it with.
+    ///
+    /// Interface methods usually live in a namespace of their own, while the
+    /// classes implementing them declare them as public. Code calling a method
+    /// through an interface-typed reference uses the interface's name.
+    interface_names: PropertyMap<'gc, Vec<usize>>,
+}
+
+impl<'gc> TraitTable<'gc> {
+    /// Add a trait to the end of the table.
+    fn define(&mut self, my_trait: Trait<'gc>) {
+        let index = self.traits.len();
+
+        self.index_method(index, &my_trait);
+        self.names
+            .entry(my_trait.name().clone())
+            .or_insert_with(Vec::new)
+            .push(index);
+        self.traits.push(my_trait);
+    }
+
+    /// Add a trait to the table, replacing the trait it overrides.
+    ///
+    /// Getters and setters of the same name are kept side by side, and each
+    /// only replaces a previous accessor of its own kind.
+    ///
+    /// Attempting to override a final trait, overlapping an existing trait
+    /// without being marked as an override, or overriding a trait of another
+    /// kind raises a `VerifyError`.
+    fn define_override(&mut self, my_trait: Trait<'gc>) -> Result<(), Error> {
+        let mut overridden = None;
+
+        for index in self.names.get(my_trait.name()).into_iter().flatten() {
+            let known_trait = &self.traits[*index];
+            match (my_trait.kind(), known_trait.kind()) {
+                (TraitKind::Getter { .. }, TraitKind::Setter { .. }) => continue,
+                (TraitKind::Setter { .. }, TraitKind::Getter { .. }) => continue,
+                (TraitKind::Getter { .. }, TraitKind::Getter { .. })
+                | (TraitKind::Setter { .. }, TraitKind::Setter { .. })
+                | (TraitKind::Method { .. }, TraitKind::Method { .. }) => {}
+                _ => return Err("VerifyError: Illegal override".into()),
+            };
+
+            if known_trait.is_final() {
+                return Err("Attempting to override a final definition".into());
+            }
+
+            if !my_trait.is_override() {
+                return Err("Definition override is not marked as override".into());
+            }
+
+            overridden = Some(*index);
+        }
+
+        if let Some(index) = overridden {
+            self.index_method(index, &my_trait);
+            self.traits[index] = my_trait;
+        } else {
+            self.define(my_trait);
+        }
+
+        Ok(())
+    }
+
+    /// Make the public traits with the same local name as an interface's
+    /// trait reachable under the interface's name for it.
+    ///
+    /// Nothing is bound if the table already has a trait of that name, or if
+    /// the interface method is not implemented.
+    fn bind_interface_trait(&mut self, name: &QName<'gc>) {
+        if self.has(name) {
+            return;
+        }
+
+        let public_name = QName::new(Namespace::public(), name.local_name());
+        if let Some(indices) = self.names.get(&public_name) {
+            let indices = indices.clone();
+            self.interface_names.insert(name.clone(), indices);
+        }
+    }
+
+    /// Record the position of a trait in the method table, if it has a
+    /// dispatch id.
+    fn index_method(&mut self, index: usize, my_trait: &Trait<'gc>) {
+        if let Some(disp_id) = my_trait.disp_id() {
+            self.methods.insert(disp_id, index);
+        }
+    }
+
+    /// Find the trait with the given dispatch id.
+    fn method(&self, disp_id: u32) -> Option<&Trait<'gc>> {
+        let index = *self.methods.get(&disp_id)?;
+        self.traits.get(index)
+    }
+
+    /// Append the traits matching a name to a list of known traits.
+    ///
+    /// This also enforces final/override bits against the traits already in
+    /// the list, and will raise `VerifyError`s as needed.
+    fn lookup(&self, name: &QName<'gc>, known_traits: &mut Vec<Trait<'gc>>) -> Result<(), Error> {
+        let indices = self
+            .names
+            .get(name)
+            .or_else(|| self.interface_names.get(name));
+        for index in indices.into_iter().flatten() {
+            let trait_entry = &self.traits[*index];
             for known_trait in known_traits.iter() {
                 match (&trait_entry.kind(), &known_trait.kind()) {
                     (TraitKind::Getter { .. }, TraitKind::Setter { .. }) => continue,
@@ -101,11 +237,30 @@ fn do_trait_lookup<'gc>(
                 }
             }
 
-            known_traits.push(trait_entry.clone());
+            // Traits bound to an interface name are installed under it.
+            let mut trait_entry = trait_entry.clone();
+            trait_entry.set_name(name.clone());
+            known_traits.push(trait_entry);
         }
+
+        Ok(())
+    }
+
+    /// Determine if any trait in the table has the given name.
+    fn has(&self, name: &QName<'gc>) -> bool {
+        self.names.contains_key(name) || self.interface_names.contains_key(name)
     }
 
-    Ok(())
+    /// Find the namespace of the first trait with a given local name.
+    fn resolve_any(&self, local_name: AvmString<'gc>) -> Option<Namespace<'gc>> {
+        for trait_entry in self.traits.iter() {
+            if local_name == trait_entry.name().local_name() {
+                return Some(trait_entry.name().namespace().clone());
+            }
+        }
+
+        None
+    }
 }
 
 impl<'gc> Class<'gc> {
@@ -133,10 +288,12 @@ impl<'gc> Class<'gc> {
                 protected_namespace: None,
                 interfaces: Vec::new(),
                 instance_init,
-                instance_traits: Vec::new(),
+                instance_traits: TraitTable::default(),
+                linked_instance_traits: None,
                 class_init,
-                class_traits: Vec::new(),
+                class_traits: TraitTable::default(),
                 traits_loaded: true,
+                metadata: Vec::new(),
             },
         )
     }
@@ -217,10 +374,12 @@ impl<'gc> Class<'gc> {
                 protected_namespace,
                 interfaces,
                 instance_init,
-                instance_traits: Vec::new(),
+                instance_traits: TraitTable::default(),
+                linked_instance_traits: None,
                 class_init,
-                class_traits: Vec::new(),
+                class_traits: TraitTable::default(),
                 traits_loaded: false,
+                metadata: Vec::new(),
             },
         ))
     }
@@ -258,13 +417,11 @@ impl<'gc> Class<'gc> {
         let abc_instance = abc_instance?;
 
         for abc_trait in abc_instance.traits.iter() {
-            self.instance_traits
-                .push(Trait::from_abc_trait(unit, &abc_trait, avm2, mc)?);
+            self.define_instance_trait(Trait::from_abc_trait(unit, &abc_trait, avm2, mc)?);
         }
 
         for abc_trait in abc_class.traits.iter() {
-            self.class_traits
-                .push(Trait::from_abc_trait(unit, &abc_trait, avm2, mc)?);
+            self.define_class_trait(Trait::from_abc_trait(unit, &abc_trait, avm2, mc)?);
         }
 
         Ok(())
@@ -283,16 +440,17 @@ impl<'gc> Class<'gc> {
     /// Class traits will be accessible as properties on the class constructor
     /// function.
     pub fn define_class_trait(&mut self, my_trait: Trait<'gc>) {
-        self.class_traits.push(my_trait);
+        self.class_traits.define(my_trait);
+    }
+
+    /// Find the class trait with the given dispatch id.
+    pub fn class_method(&self, disp_id: u32) -> Option<&Trait<'gc>> {
+        self.class_traits.method(disp_id)
     }
 
     /// Given a name, append class traits matching the name to a list of known
     /// traits.
     ///
-    /// This function adds its result onto the list of known traits, with the
-    /// caveat that duplicate entries will be replaced (if allowed). As such, this
-    /// function should be run on the class hierarchy from top to bottom.
-    ///
     /// If a given trait has an invalid name, attempts to override a final trait,
     /// or overlaps an existing trait without being an override, then this function
     /// returns an error.
@@ -301,18 +459,12 @@ impl<'gc> Class<'gc> {
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
     ) -> Result<(), Error> {
-        do_trait_lookup(name, known_traits, &self.class_traits)
+        self.class_traits.lookup(name, known_traits)
     }
 
     /// Determines if this class provides a given trait on itself.
     pub fn has_class_trait(&self, name: &QName<'gc>) -> bool {
-        for trait_entry in self.class_traits.iter() {
-            if name == trait_entry.name() {
-                return true;
-            }
-        }
-
-        false
+        self.class_traits.has(name)
     }
 
     /// Look for a class trait with a given local name, and return its
@@ -321,13 +473,7 @@ impl<'gc> Class<'gc> {
     /// TODO: Matching multiple namespaces with the same local name is at least
     /// claimed by the AVM2 specification to be a `VerifyError`.
     pub fn resolve_any_class_trait(&self, local_name: AvmString<'gc>) -> Option<Namespace<'gc>> {
-        for trait_entry in self.class_traits.iter() {
-            if local_name == trait_entry.name().local_name() {
-                return Some(trait_entry.name().namespace().clone());
-            }
-        }
-
-        None
+        self.class_traits.resolve_any(local_name)
     }
 
     /// Define a trait on instances of the class.
@@ -335,16 +481,21 @@ impl<'gc> Class<'gc> {
     /// Instance traits will be accessible as properties on instances of the
     /// class. They will not be accessible on the class prototype, and any
     /// properties defined on the prototype will be shadowed by these traits.
+    ///
+    /// Traits defined after the class has been linked are not visible on its
+    /// instances until it is linked again.
     pub fn define_instance_trait(&mut self, my_trait: Trait<'gc>) {
-        self.instance_traits.push(my_trait);
+        self.instance_traits.define(my_trait);
     }
 
-    /// Given a name, append instance traits matching the name to a list of
-    /// known traits.
-    ///
-    /// This function adds its result onto the list of known traits, with the
-    /// caveat that duplicate entries will be replaced (if allowed). As such, this
-    /// function should be run on the class hierarchy from top to bottom.
+    /// Find the instance trait declared by this class with the given
+    /// dispatch id.
+    pub fn instance_method(&self, disp_id: u32) -> Option<&Trait<'gc>> {
+        self.instance_traits.method(disp_id)
+    }
+
+    /// Given a name, append instance traits declared by this class matching
+    /// the name to a list of known traits.
     ///
     /// If a given trait has an invalid name, attempts to override a final trait,
     /// or overlaps an existing trait without being an override, then this function
@@ -354,33 +505,136 @@ impl<'gc> Class<'gc> {
         name: &QName<'gc>,
         known_traits: &mut Vec<Trait<'gc>>,
     ) -> Result<(), Error> {
-        do_trait_lookup(name, known_traits, &self.instance_traits)
+        self.instance_traits.lookup(name, known_traits)
     }
 
     /// Determines if this class provides a given trait on its instances.
+    ///
+    /// Traits inherited from superclasses are not considered.
     pub fn has_instance_trait(&self, name: &QName<'gc>) -> bool {
-        for trait_entry in self.instance_traits.iter() {
-            if name == trait_entry.name() {
-                return true;
-            }
-        }
-
-        false
+        self.instance_traits.has(name)
     }
 
-    /// Look for an instance trait with a given local name, and return its
-    /// namespace.
+    /// Look for an instance trait declared by this class with a given local
+    /// name, and return its namespace.
     ///
     /// TODO: Matching multiple namespaces with the same local name is at least
     /// claimed by the AVM2 specification to be a `VerifyError`.
     pub fn resolve_any_instance_trait(&self, local_name: AvmString<'gc>) -> Option<Namespace<'gc>> {
-        for trait_entry in self.instance_traits.iter() {
-            if local_name == trait_entry.name().local_name() {
-                return Some(trait_entry.name().namespace().clone());
+        self.instance_traits.resolve_any(local_name)
+    }
+
+    /// Link this class to its superclass and interfaces, flattening the
+    /// instance traits of the whole class hierarchy into a single table.
+    ///
+    /// The superclass and interfaces must already have been linked. Overrides
+    /// replace the traits they override, so each name resolves to at most one
+    /// method, slot, or pair of accessors. Overrides of final traits, and
+    /// overlapping traits not marked as overrides, are rejected here rather
+    /// than when the trait is first used.
+    ///
+    /// Each method of the interfaces is bound to the public trait of the same
+    /// local name, so that calls made through the interface's name reach the
+    /// class's implementation. Interfaces instead inherit the methods of the
+    /// interfaces they extend.
+    pub fn link_instance_traits(
+        &mut self,
+        superclass: Option<GcCell<'gc, Class<'gc>>>,
+        interfaces: &[GcCell<'gc, Class<'gc>>],
+    ) -> Result<(), Error> {
+        let mut linked = superclass
+            .map(|superclass| superclass.read().linked_traits().clone())
+            .unwrap_or_default();
+
+        for my_trait in self.instance_traits.traits.iter() {
+            linked.define_override(my_trait.clone())?;
+        }
+
+        for interface in interfaces {
+            for interface_trait in interface.read().linked_traits().traits.iter() {
+                if !self.is_interface() {
+                    linked.bind_interface_trait(interface_trait.name());
+                } else if !linked.has(interface_trait.name()) {
+                    linked.define(interface_trait.clone());
+                }
             }
         }
 
-        None
+        self.linked_instance_traits = Some(linked);
+
+        Ok(())
+    }
+
+    /// The flattened instance traits of this class, or only the ones it
+    /// declares itself if it has not been linked yet.
+    fn linked_traits(&self) -> &TraitTable<'gc> {
+        self.linked_instance_traits
+            .as_ref()
+            .unwrap_or(&self.instance_traits)
+    }
+
+    /// Find the instance trait with the given dispatch id, including traits
+    /// inherited from superclasses.
+    pub fn linked_instance_method(&self, disp_id: u32) -> Option<&Trait<'gc>> {
+        self.linked_traits().method(disp_id)
+    }
+
+    /// Given a name, append the instance traits of this class and its
+    /// superclasses matching the name to a list of known traits.
+    ///
+    /// Overrides have already been resolved when the class was linked, so
+    /// this yields at most a getter and a setter, or a single other trait.
+    pub fn lookup_linked_instance_traits(
+        &self,
+        name: &QName<'gc>,
+        known_traits: &mut Vec<Trait<'gc>>,
+    ) -> Result<(), Error> {
+        self.linked_traits().lookup(name, known_traits)
+    }
+
+    /// Determines if instances of this class have a given trait, including
+    /// traits inherited from superclasses.
+    pub fn has_linked_instance_trait(&self, name: &QName<'gc>) -> bool {
+        self.linked_traits().has(name)
+    }
+
+    /// Look for an instance trait of this class or its superclasses with a
+    /// given local name, and return its namespace.
+    pub fn resolve_any_linked_instance_trait(
+        &self,
+        local_name: AvmString<'gc>,
+    ) -> Option<Namespace<'gc>> {
+        self.linked_traits().resolve_any(local_name)
+    }
+
+    /// Get all traits defined on instances of this class.
+    ///
+    /// This does not include traits inherited from superclasses.
+    pub fn instance_traits(&self) -> &[Trait<'gc>] {
+        &self.instance_traits.traits
+    }
+
+    /// Whether one of the instance methods, getters or setters declared by
+    /// this class is `method`.
+    pub fn declares_instance_method(&self, method: &BytecodeMethod<'gc>) -> bool {
+        self.instance_traits
+            .traits
+            .iter()
+            .any(|my_trait| match my_trait.kind() {
+                TraitKind::Method {
+                    method: Method::Entry(bm),
+                    ..
+                }
+                | TraitKind::Getter {
+                    method: Method::Entry(bm),
+                    ..
+                }
+                | TraitKind::Setter {
+                    method: Method::Entry(bm),
+                    ..
+                } => bm.is_same_method(method),
+                _ => false,
+            })
     }
 
     /// Get this class's instance initializer.
@@ -401,6 +655,47 @@ impl<'gc> Class<'gc> {
         self.interfaces.push(iface)
     }
 
+    /// Get all traits defined on the class itself.
+    pub fn class_traits(&self) -> &[Trait<'gc>] {
+        &self.class_traits.traits
+    }
+
+    /// Get the metadata of the trait that defines this class.
+    pub fn metadata(&self) -> &[Metadata<'gc>] {
+        &self.metadata
+    }
+
+    pub fn set_metadata(&mut self, metadata: Vec<Metadata<'gc>>) {
+        self.metadata = metadata;
+    }
+
+    /// Collect the metadata of this class and of all of its traits.
+    pub fn all_metadata(&self) -> ClassMetadata<'gc> {
+        let of_traits = |traits: &[Trait<'gc>]| {
+            traits
+                .iter()
+                .filter(|t| !t.metadata().is_empty())
+                .map(|t| (t.name().clone(), t.metadata().to_vec()))
+                .collect()
+        };
+
+        ClassMetadata {
+            class: self.metadata.clone(),
+            instance_traits: of_traits(&self.instance_traits.traits),
+            class_traits: of_traits(&self.class_traits.traits),
+        }
+    }
+
+    /// Determine if this class is final (cannot be subclassed)
+    pub fn is_final(&self) -> bool {
+        self.attributes.0.contains(ClassAttributes::FINAL)
+    }
+
+    /// Determine if this class is an interface
+    pub fn is_interface(&self) -> bool {
+        self.attributes.0.contains(ClassAttributes::INTERFACE)
+    }
+
     /// Determine if this class is sealed (no dynamic properties)
     pub fn is_sealed(&self) -> bool {
         self.attributes.0.contains(ClassAttributes::SEALED)
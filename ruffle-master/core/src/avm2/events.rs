@@ -6,7 +6,7 @@ use crate::avm2::object::{Object, TObject};
 use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::display_object::TDisplayObject;
+use crate::display_object::{DisplayObject, TDisplayObject};
 use gc_arena::Collect;
 use std::collections::{BTreeMap, HashMap};
 use std::hash::{Hash, Hasher};
@@ -452,6 +452,21 @@ pub fn dispatch_event<'gc>(
         parent = parent_of(par);
     }
 
+    // Events on the display list continue on to the stage.
+    let root = ancestor_list.last().copied().unwrap_or(target);
+    let root_is_level = root.as_display_object().map_or(false, |root| {
+        activation
+            .context
+            .levels
+            .values()
+            .any(|level| DisplayObject::ptr_eq(*level, root))
+    });
+    if let Some(stage) = activation.context.avm2.stage_object() {
+        if root_is_level && !Object::ptr_eq(root, stage) {
+            ancestor_list.push(stage);
+        }
+    }
+
     let mut evtmut = event.as_event_mut(activation.context.gc_context).unwrap();
 
     evtmut.set_phase(EventPhase::Capturing);
@@ -6,12 +6,14 @@ use crate::avm2::domain::Domain;
 use crate::avm2::method::NativeMethod;
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::{
-    implicit_deriver, ArrayObject, ByteArrayObject, DomainObject, FunctionObject, NamespaceObject,
-    Object, PrimitiveObject, ScriptObject, StageObject, TObject, XmlObject,
+    implicit_deriver, ArrayObject, BitmapDataObject, ByteArrayObject, DomainObject, FunctionObject,
+    NamespaceObject, Object, PrimitiveObject, ScriptObject, SoundChannelObject, SoundObject,
+    StageObject, StyleSheetObject, TObject, XmlObject,
 };
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
 use gc_arena::{Collect, GcCell, MutationContext};
@@ -27,12 +29,31 @@ mod math;
 mod namespace;
 mod number;
 mod object;
+mod qname;
 mod regexp;
 mod string;
 mod r#uint;
 mod xml;
 mod xml_list;
 
+pub use flash::events::dataevent::dispatch_data_event;
+pub use flash::events::ioerrorevent::dispatch_io_error_event;
+pub use flash::events::keyboardevent::dispatch_keyboard_event;
+pub use flash::events::mouseevent::dispatch_mouse_event;
+pub use flash::events::stageorientationevent::dispatch_stage_orientation_event;
+pub use flash::events::statusevent::dispatch_status_event;
+pub use flash::events::textevent::dispatch_text_event;
+pub use flash::media::sound::request_sample_data;
+pub use flash::net::filereference::{
+    finish_file_dialog, finish_file_download, finish_file_load, FileSession,
+};
+pub use flash::net::localconnection::receive_local_connection_message;
+pub use flash::net::socket::receive_socket_data;
+pub use flash::net::urlstream::{receive_url_stream_chunk, UrlStreamLoad};
+pub use flash::ui::contextmenu::{prepare_context_menu, run_context_menu_item, ShownContextMenu};
+pub use flash::ui::gameinput::update_game_input;
+pub use flash::ui::gameinputdevice::GameInputDevice;
+
 const NS_RUFFLE_INTERNAL: &str = "https://ruffle.rs/AS3/impl/";
 
 fn trace<'gc>(
@@ -92,21 +113,65 @@ pub struct SystemPrototypes<'gc> {
     pub int: Object<'gc>,
     pub uint: Object<'gc>,
     pub namespace: Object<'gc>,
+    pub qname: Object<'gc>,
     pub array: Object<'gc>,
     pub movieclip: Object<'gc>,
     pub framelabel: Object<'gc>,
     pub scene: Object<'gc>,
     pub application_domain: Object<'gc>,
+    pub security_domain: Object<'gc>,
     pub event: Object<'gc>,
     pub video: Object<'gc>,
     pub xml: Object<'gc>,
     pub xml_list: Object<'gc>,
     pub display_object: Object<'gc>,
+    pub stage: Object<'gc>,
     pub shape: Object<'gc>,
+    pub simple_button: Object<'gc>,
+    pub morph_shape: Object<'gc>,
+    pub static_text: Object<'gc>,
     pub point: Object<'gc>,
+    pub rectangle: Object<'gc>,
+    pub matrix: Object<'gc>,
+    pub color_transform: Object<'gc>,
+    pub transform: Object<'gc>,
     pub textfield: Object<'gc>,
     pub textformat: Object<'gc>,
     pub graphics: Object<'gc>,
+    pub graphics_path: Object<'gc>,
+    pub graphics_solid_fill: Object<'gc>,
+    pub graphics_gradient_fill: Object<'gc>,
+    pub graphics_bitmap_fill: Object<'gc>,
+    pub graphics_end_fill: Object<'gc>,
+    pub graphics_stroke: Object<'gc>,
+    pub graphics_triangle_path: Object<'gc>,
+    pub bitmap: Object<'gc>,
+    pub bitmap_data: Object<'gc>,
+    pub sound: Object<'gc>,
+    pub sound_channel: Object<'gc>,
+    pub sound_transform: Object<'gc>,
+    pub sample_data_event: Object<'gc>,
+    pub text_event: Object<'gc>,
+    pub mouse_event: Object<'gc>,
+    pub keyboard_event: Object<'gc>,
+    pub game_input_event: Object<'gc>,
+    pub progress_event: Object<'gc>,
+    pub io_error_event: Object<'gc>,
+    pub data_event: Object<'gc>,
+    pub status_event: Object<'gc>,
+    pub game_input_device: Object<'gc>,
+    pub game_input_control: Object<'gc>,
+    pub context_menu: Object<'gc>,
+    pub context_menu_item: Object<'gc>,
+    pub context_menu_built_in_items: Object<'gc>,
+    pub context_menu_event: Object<'gc>,
+    pub stage_orientation_event: Object<'gc>,
+    pub bytearray: Object<'gc>,
+    pub microphone: Object<'gc>,
+    pub blur_filter: Object<'gc>,
+    pub glow_filter: Object<'gc>,
+    pub drop_shadow_filter: Object<'gc>,
+    pub bevel_filter: Object<'gc>,
 }
 
 impl<'gc> SystemPrototypes<'gc> {
@@ -134,21 +199,65 @@ impl<'gc> SystemPrototypes<'gc> {
             int: empty,
             uint: empty,
             namespace: empty,
+            qname: empty,
             array: empty,
             movieclip: empty,
             framelabel: empty,
             scene: empty,
             application_domain: empty,
+            security_domain: empty,
             event: empty,
             video: empty,
             xml: empty,
             xml_list: empty,
             display_object: empty,
+            stage: empty,
             shape: empty,
+            simple_button: empty,
+            morph_shape: empty,
+            static_text: empty,
             point: empty,
+            rectangle: empty,
+            matrix: empty,
+            color_transform: empty,
+            transform: empty,
             textfield: empty,
             textformat: empty,
             graphics: empty,
+            graphics_path: empty,
+            graphics_solid_fill: empty,
+            graphics_gradient_fill: empty,
+            graphics_bitmap_fill: empty,
+            graphics_end_fill: empty,
+            graphics_stroke: empty,
+            graphics_triangle_path: empty,
+            bitmap: empty,
+            bitmap_data: empty,
+            sound: empty,
+            sound_channel: empty,
+            sound_transform: empty,
+            sample_data_event: empty,
+            text_event: empty,
+            mouse_event: empty,
+            keyboard_event: empty,
+            game_input_event: empty,
+            progress_event: empty,
+            io_error_event: empty,
+            data_event: empty,
+            status_event: empty,
+            game_input_device: empty,
+            game_input_control: empty,
+            context_menu: empty,
+            context_menu_item: empty,
+            context_menu_built_in_items: empty,
+            context_menu_event: empty,
+            stage_orientation_event: empty,
+            bytearray: empty,
+            microphone: empty,
+            blur_filter: empty,
+            glow_filter: empty,
+            drop_shadow_filter: empty,
+            bevel_filter: empty,
         }
     }
 }
@@ -220,8 +329,9 @@ where
     let mut global = script.init().1;
     let global_scope = Scope::push_scope(global.get_scope(), global, activation.context.gc_context);
 
-    let class_read = class_def.read();
-    let super_class = if let Some(sc_name) = class_read.super_class_name() {
+    let class_name = class_def.read().name().clone();
+    let super_class_name = class_def.read().super_class_name().clone();
+    let super_class = if let Some(sc_name) = &super_class_name {
         let super_name = global
             .resolve_multiname(sc_name)?
             .unwrap_or_else(|| QName::dynamic_name("Object"));
@@ -238,7 +348,7 @@ where
         None
     };
 
-    let (mut constr, _cinit) = FunctionObject::from_class_with_deriver(
+    let mut constr = FunctionObject::from_class_with_deriver(
         activation,
         class_def,
         super_class,
@@ -247,15 +357,11 @@ where
     )?;
     global.install_const(
         activation.context.gc_context,
-        class_read.name().clone(),
+        class_name.clone(),
         0,
         constr.into(),
     );
-    domain.export_definition(
-        class_read.name().clone(),
-        script,
-        activation.context.gc_context,
-    )?;
+    domain.export_definition(class_name, script, activation.context.gc_context)?;
 
     constr
         .get_property(
@@ -302,6 +408,24 @@ fn xml_deriver<'gc>(
     XmlObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
+fn bitmapdata_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error> {
+    BitmapDataObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn stylesheet_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error> {
+    StyleSheetObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
 fn bytearray_deriver<'gc>(
     base_proto: Object<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
@@ -311,6 +435,24 @@ fn bytearray_deriver<'gc>(
     ByteArrayObject::derive(base_proto, activation.context.gc_context, class, scope)
 }
 
+fn sound_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error> {
+    SoundObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
+fn soundchannel_deriver<'gc>(
+    base_proto: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    class: GcCell<'gc, Class<'gc>>,
+    scope: Option<GcCell<'gc, Scope<'gc>>>,
+) -> Result<Object<'gc>, Error> {
+    SoundChannelObject::derive(base_proto, activation.context.gc_context, class, scope)
+}
+
 fn stage_deriver<'gc>(
     base_proto: Object<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
@@ -358,6 +500,41 @@ fn constant<'gc>(
     Ok(())
 }
 
+/// A property of a builtin class that is kept in a slot, as its name, type
+/// name and default value.
+pub type SlotProperty<'gc> = (&'static str, &'static str, Value<'gc>);
+
+/// Define the properties of a builtin class as slots.
+pub fn define_properties<'gc>(class: &mut Class<'gc>, properties: &[SlotProperty<'gc>]) {
+    for (name, type_name, default) in properties {
+        class.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), *type_name).into(),
+            Some(default.clone()),
+        ));
+    }
+}
+
+/// Set the properties of a new object from its constructor arguments, which
+/// are given in the same order as its properties.
+pub fn init_properties<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    args: &[Value<'gc>],
+    properties: &[SlotProperty<'gc>],
+) -> Result<(), Error> {
+    for ((name, _, _), value) in properties.iter().zip(args) {
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), *name),
+            value.clone(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
 /// Initialize the player global domain.
 ///
 /// This should be called only once, to construct the global scope of the
@@ -514,129 +691,120 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
-
-    // package `flash.system`
     activation
         .context
         .avm2
         .system_prototypes
         .as_mut()
         .unwrap()
-        .application_domain = class(
-        activation,
-        flash::system::application_domain::create_class(mc),
-        appdomain_deriver,
-        domain,
-        script,
-    )?;
-    class(
+        .qname = class(
         activation,
-        flash::system::system::create_class(mc),
+        qname::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
 
-    // package `flash.events`
+    // package `flash.system`
     activation
         .context
         .avm2
         .system_prototypes
         .as_mut()
         .unwrap()
-        .event = class(
+        .application_domain = class(
         activation,
-        flash::events::event::create_class(mc),
-        flash::events::event::event_deriver,
+        flash::system::application_domain::create_class(mc),
+        appdomain_deriver,
         domain,
         script,
     )?;
     class(
         activation,
-        flash::events::ieventdispatcher::create_interface(mc),
+        flash::system::system::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
     class(
         activation,
-        flash::events::eventdispatcher::create_class(mc),
+        flash::system::security::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
-    // package `flash.utils`
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .security_domain = class(
         activation,
-        flash::utils::bytearray::create_class(mc),
-        bytearray_deriver,
+        flash::system::security_domain::create_class(mc),
+        implicit_deriver,
         domain,
         script,
     )?;
-
     class(
         activation,
-        flash::utils::endian::create_class(mc),
+        flash::system::loader_context::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
 
-    function(
-        mc,
-        "flash.utils",
-        "getTimer",
-        flash::utils::get_timer,
-        fn_proto,
-        domain,
-        script,
-    )?;
-
-    // package `flash.display`
+    // package `flash.events`
     activation
         .context
         .avm2
         .system_prototypes
         .as_mut()
         .unwrap()
-        .display_object = class(
+        .event = class(
         activation,
-        flash::display::displayobject::create_class(mc),
-        stage_deriver,
+        flash::events::event::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
-    activation
-        .context
-        .avm2
-        .system_prototypes
-        .as_mut()
-        .unwrap()
-        .shape = class(
+    class(
         activation,
-        flash::display::shape::create_class(mc),
+        flash::events::ieventdispatcher::create_interface(mc),
         implicit_deriver,
         domain,
         script,
     )?;
     class(
         activation,
-        flash::display::interactiveobject::create_class(mc),
+        flash::events::eventdispatcher::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .sample_data_event = class(
         activation,
-        flash::display::displayobjectcontainer::create_class(mc),
-        implicit_deriver,
+        flash::events::sampledataevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .text_event = class(
         activation,
-        flash::display::sprite::create_class(mc),
-        implicit_deriver,
+        flash::events::textevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
@@ -646,10 +814,10 @@ pub fn load_player_globals<'gc>(
         .system_prototypes
         .as_mut()
         .unwrap()
-        .movieclip = class(
+        .context_menu_event = class(
         activation,
-        flash::display::movieclip::create_class(mc),
-        implicit_deriver,
+        flash::events::contextmenuevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
@@ -659,10 +827,10 @@ pub fn load_player_globals<'gc>(
         .system_prototypes
         .as_mut()
         .unwrap()
-        .framelabel = class(
+        .stage_orientation_event = class(
         activation,
-        flash::display::framelabel::create_class(mc),
-        implicit_deriver,
+        flash::events::stageorientationevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
@@ -672,10 +840,10 @@ pub fn load_player_globals<'gc>(
         .system_prototypes
         .as_mut()
         .unwrap()
-        .scene = class(
+        .mouse_event = class(
         activation,
-        flash::display::scene::create_class(mc),
-        implicit_deriver,
+        flash::events::mouseevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
@@ -685,109 +853,1027 @@ pub fn load_player_globals<'gc>(
         .system_prototypes
         .as_mut()
         .unwrap()
-        .graphics = class(
+        .keyboard_event = class(
         activation,
-        flash::display::graphics::create_class(mc),
-        stage_deriver,
+        flash::events::keyboardevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .game_input_event = class(
         activation,
-        flash::display::jointstyle::create_class(mc),
-        implicit_deriver,
+        flash::events::gameinputevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
-    class(
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .progress_event = class(
         activation,
-        flash::display::linescalemode::create_class(mc),
-        implicit_deriver,
+        flash::events::progressevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
     class(
         activation,
-        flash::display::capsstyle::create_class(mc),
-        implicit_deriver,
+        flash::events::errorevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
-
-    // package `flash.geom`
     activation
         .context
         .avm2
         .system_prototypes
         .as_mut()
         .unwrap()
-        .point = class(
+        .io_error_event = class(
         activation,
-        flash::geom::point::create_class(mc),
-        implicit_deriver,
+        flash::events::ioerrorevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
-
-    // package `flash.media`
     activation
         .context
         .avm2
         .system_prototypes
         .as_mut()
         .unwrap()
-        .video = class(
+        .data_event = class(
         activation,
-        flash::media::video::create_class(mc),
-        implicit_deriver,
+        flash::events::dataevent::create_class(mc),
+        flash::events::event::event_deriver,
         domain,
         script,
     )?;
-
-    // package `flash.text`
     activation
         .context
         .avm2
         .system_prototypes
         .as_mut()
         .unwrap()
-        .textfield = class(
+        .status_event = class(
         activation,
-        flash::text::textfield::create_class(mc),
+        flash::events::statusevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    // package `flash.external`
+    class(
+        activation,
+        flash::external::externalinterface::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
+
+    // package `flash.utils`
     activation
         .context
         .avm2
         .system_prototypes
         .as_mut()
         .unwrap()
-        .textformat = class(
+        .bytearray = class(
         activation,
-        flash::text::textformat::create_class(mc),
-        implicit_deriver,
+        flash::utils::bytearray::create_class(mc),
+        bytearray_deriver,
         domain,
         script,
     )?;
+
     class(
         activation,
-        flash::text::textfieldautosize::create_class(mc),
+        flash::utils::endian::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
+
+    function(
+        mc,
+        "flash.utils",
+        "getTimer",
+        flash::utils::get_timer,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "getQualifiedClassName",
+        flash::utils::get_qualified_class_name,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "getQualifiedSuperclassName",
+        flash::utils::get_qualified_superclass_name,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "getDefinitionByName",
+        flash::utils::get_definition_by_name,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "describeType",
+        flash::utils::describe_type,
+        fn_proto,
+        domain,
+        script,
+    )?;
+
+    // package `flash.sampler`
     class(
         activation,
-        flash::text::textformatalign::create_class(mc),
+        flash::sampler::sample::create_class(mc),
         implicit_deriver,
         domain,
         script,
     )?;
     class(
         activation,
-        flash::text::textfieldtype::create_class(mc),
+        flash::sampler::newobjectsample::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.sampler",
+        "getSize",
+        flash::sampler::get_size,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.sampler",
+        "getMemberNames",
+        flash::sampler::get_member_names,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.sampler",
+        "getSamples",
+        flash::sampler::get_samples,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.sampler",
+        "getSampleCount",
+        flash::sampler::get_sample_count,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.sampler",
+        "startSampling",
+        flash::sampler::sampling_noop,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.sampler",
+        "stopSampling",
+        flash::sampler::sampling_noop,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.sampler",
+        "pauseSampling",
+        flash::sampler::sampling_noop,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.sampler",
+        "clearSamples",
+        flash::sampler::sampling_noop,
+        fn_proto,
+        domain,
+        script,
+    )?;
+
+    // package `flash.display`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .display_object = class(
+        activation,
+        flash::display::displayobject::create_class(mc),
+        stage_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .shape = class(
+        activation,
+        flash::display::shape::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .morph_shape = class(
+        activation,
+        flash::display::morphshape::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::interactiveobject::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .simple_button = class(
+        activation,
+        flash::display::simplebutton::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::displayobjectcontainer::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::sprite::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .stage = class(
+        activation,
+        flash::display::stage::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .movieclip = class(
+        activation,
+        flash::display::movieclip::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .framelabel = class(
+        activation,
+        flash::display::framelabel::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .scene = class(
+        activation,
+        flash::display::scene::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics = class(
+        activation,
+        flash::display::graphics::create_class(mc),
+        stage_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::igraphicsdata::create_interface(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::igraphicsfill::create_interface(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::igraphicspath::create_interface(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::igraphicsstroke::create_interface(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics_path = class(
+        activation,
+        flash::display::graphicspath::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics_solid_fill = class(
+        activation,
+        flash::display::graphicssolidfill::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics_gradient_fill = class(
+        activation,
+        flash::display::graphicsgradientfill::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics_bitmap_fill = class(
+        activation,
+        flash::display::graphicsbitmapfill::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics_end_fill = class(
+        activation,
+        flash::display::graphicsendfill::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics_stroke = class(
+        activation,
+        flash::display::graphicsstroke::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .graphics_triangle_path = class(
+        activation,
+        flash::display::graphicstrianglepath::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicspathcommand::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::graphicspathwinding::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .bitmap = class(
+        activation,
+        flash::display::bitmap::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .bitmap_data = class(
+        activation,
+        flash::display::bitmapdata::create_class(mc),
+        bitmapdata_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::jointstyle::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::linescalemode::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::capsstyle::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::stagequality::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::display::stageorientation::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.filters`
+    class(
+        activation,
+        flash::filters::bitmapfilter::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::filters::bitmapfilterquality::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::filters::bitmapfiltertype::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .bevel_filter = class(
+        activation,
+        flash::filters::bevelfilter::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .blur_filter = class(
+        activation,
+        flash::filters::blurfilter::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .drop_shadow_filter = class(
+        activation,
+        flash::filters::dropshadowfilter::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .glow_filter = class(
+        activation,
+        flash::filters::glowfilter::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.geom`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .point = class(
+        activation,
+        flash::geom::point::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .rectangle = class(
+        activation,
+        flash::geom::rectangle::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .matrix = class(
+        activation,
+        flash::geom::matrix::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .color_transform = class(
+        activation,
+        flash::geom::colortransform::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .transform = class(
+        activation,
+        flash::geom::transform::create_class(mc),
+        stage_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.media`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .video = class(
+        activation,
+        flash::media::video::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .microphone = class(
+        activation,
+        flash::media::microphone::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .sound = class(
+        activation,
+        flash::media::sound::create_class(mc),
+        sound_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .sound_channel = class(
+        activation,
+        flash::media::soundchannel::create_class(mc),
+        soundchannel_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .sound_transform = class(
+        activation,
+        flash::media::soundtransform::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.net`
+    class(
+        activation,
+        flash::net::filefilter::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::filereference::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::localconnection::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::socket::create_class(mc),
+        bytearray_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::urlstream::create_class(mc),
+        bytearray_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::xmlsocket::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.printing`
+    class(
+        activation,
+        flash::printing::printjob::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::printing::printjoboptions::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::printing::printjoborientation::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.text`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .textfield = class(
+        activation,
+        flash::text::textfield::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .static_text = class(
+        activation,
+        flash::text::statictext::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .textformat = class(
+        activation,
+        flash::text::textformat::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::textfieldautosize::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::textformatalign::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::textfieldtype::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::text::stylesheet::create_class(mc),
+        stylesheet_deriver,
+        domain,
+        script,
+    )?;
+
+    // package `flash.ui`
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .context_menu = class(
+        activation,
+        flash::ui::contextmenu::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .context_menu_built_in_items = class(
+        activation,
+        flash::ui::contextmenubuiltinitems::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .context_menu_item = class(
+        activation,
+        flash::ui::contextmenuitem::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::ui::gameinput::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .game_input_control = class(
+        activation,
+        flash::ui::gameinputcontrol::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .game_input_device = class(
+        activation,
+        flash::ui::gameinputdevice::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::ui::keyboard::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::ui::keylocation::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::ui::mouse::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::ui::mousecursor::create_class(mc),
         implicit_deriver,
         domain,
         script,
@@ -2,8 +2,14 @@
 
 pub mod display;
 pub mod events;
+pub mod external;
+pub mod filters;
 pub mod geom;
 pub mod media;
+pub mod net;
+pub mod printing;
+pub mod sampler;
 pub mod system;
 pub mod text;
+pub mod ui;
 pub mod utils;
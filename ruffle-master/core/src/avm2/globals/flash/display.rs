@@ -1,14 +1,34 @@
 //! `flash.display` namespace
 
+pub mod bitmap;
+pub mod bitmapdata;
 pub mod capsstyle;
 pub mod displayobject;
 pub mod displayobjectcontainer;
 pub mod framelabel;
 pub mod graphics;
+pub mod graphicsbitmapfill;
+pub mod graphicsendfill;
+pub mod graphicsgradientfill;
+pub mod graphicspath;
+pub mod graphicspathcommand;
+pub mod graphicspathwinding;
+pub mod graphicssolidfill;
+pub mod graphicsstroke;
+pub mod graphicstrianglepath;
+pub mod igraphicsdata;
+pub mod igraphicsfill;
+pub mod igraphicspath;
+pub mod igraphicsstroke;
 pub mod interactiveobject;
 pub mod jointstyle;
 pub mod linescalemode;
+pub mod morphshape;
 pub mod movieclip;
 pub mod scene;
 pub mod shape;
+pub mod simplebutton;
 pub mod sprite;
+pub mod stage;
+pub mod stageorientation;
+pub mod stagequality;
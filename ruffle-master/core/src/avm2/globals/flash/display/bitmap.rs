@@ -0,0 +1,187 @@
+//! `flash.display.Bitmap` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{Bitmap, TDisplayObject};
+use crate::tag_utils::SwfMovie;
+use crate::vminterface::AvmType;
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// Implements `flash.display.Bitmap`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let library = activation.context.library.library_for_movie_mut(movie);
+            library.force_avm_type(AvmType::Avm2);
+
+            // TODO: Implement pixel snapping
+            let smoothing = args
+                .get(2)
+                .cloned()
+                .unwrap_or(Value::Bool(false))
+                .coerce_to_boolean();
+            let new_do = Bitmap::new_with_avm2(&mut activation.context, this, smoothing);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+
+            let bitmap_data = args.get(0).cloned().unwrap_or(Value::Null);
+            set_bitmap_data(activation, Some(this), &[bitmap_data])?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Bitmap`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Bitmap.bitmapData`'s getter.
+pub fn bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        // The `BitmapData` object itself is kept in a hidden property, as the
+        // display object only holds on to its pixels.
+        return match this.get_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "bitmapData"),
+            activation,
+        )? {
+            Value::Undefined => Ok(Value::Null),
+            bitmap_data => Ok(bitmap_data),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Bitmap.bitmapData`'s setter.
+pub fn set_bitmap_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if let Some(bitmap) = this.as_display_object().and_then(|dobj| dobj.as_bitmap()) {
+            let (bitmap_data, pixels) = match args.get(0).cloned().unwrap_or(Value::Null) {
+                Value::Undefined | Value::Null => (Value::Null, None),
+                value => {
+                    let object = value.coerce_to_object(activation)?;
+                    let pixels = object
+                        .as_bitmap_data()
+                        .ok_or("TypeError: Value is not a BitmapData")?;
+                    (object.into(), Some(pixels))
+                }
+            };
+
+            bitmap.set_bitmap_data(&mut activation.context, pixels);
+            this.set_property(
+                this,
+                &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "bitmapData"),
+                bitmap_data,
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Bitmap.smoothing`'s getter.
+pub fn smoothing<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_bitmap())
+    {
+        return Ok(bitmap.smoothing().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Bitmap.smoothing`'s setter.
+pub fn set_smoothing<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_bitmap())
+    {
+        let smoothing = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        bitmap.set_smoothing(activation.context.gc_context, smoothing);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Bitmap`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Bitmap"),
+        Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bitmapData"),
+        Method::from_builtin(bitmap_data),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "bitmapData"),
+        Method::from_builtin(set_bitmap_data),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "smoothing"),
+        Method::from_builtin(smoothing),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "smoothing"),
+        Method::from_builtin(set_smoothing),
+    ));
+
+    // Slot for the `BitmapData` object this bitmap shows.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "bitmapData"),
+        QName::new(Namespace::package("flash.display"), "BitmapData").into(),
+        None,
+    ));
+
+    class
+}
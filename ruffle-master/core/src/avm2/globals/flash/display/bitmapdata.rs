@@ -0,0 +1,1151 @@
+//! `flash.display.BitmapData` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::globals::flash::filters::bitmapfilter::object_to_filter;
+use crate::avm2::globals::flash::geom::colortransform::object_to_color_transform;
+use crate::avm2::globals::flash::geom::matrix::object_to_matrix;
+use crate::avm2::globals::flash::geom::point::coords;
+use crate::avm2::globals::flash::geom::rectangle::{create_rectangle, rect};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::bitmap::bitmap_data::{BitmapData, ChannelOptions, ThresholdOperation};
+use crate::bitmap::rasterizer::{blend_mode_from_name, Rasterizer};
+use crate::color_transform::ColorTransform;
+use crate::display_object::TDisplayObject;
+use crate::transform::Transform;
+use gc_arena::{GcCell, MutationContext};
+use swf::{BlendMode, Matrix};
+
+/// The largest width or height a `BitmapData` can have.
+const MAX_SIZE: u32 = 8191;
+
+/// The largest number of pixels a `BitmapData` can have.
+const MAX_PIXELS: u32 = 16_777_215;
+
+/// Implements `flash.display.BitmapData`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        // Instances of classes bound to a bitmap symbol already have its
+        // pixels, and ignore the requested size.
+        if this.as_bitmap_data().is_some() {
+            return Ok(Value::Undefined);
+        }
+
+        let width = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let height = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        let transparent = args
+            .get(2)
+            .cloned()
+            .unwrap_or(Value::Bool(true))
+            .coerce_to_boolean();
+        let fill_color = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| 0xFFFFFFFFu32.into())
+            .coerce_to_u32(activation)?;
+
+        if width <= 0
+            || height <= 0
+            || width as u32 > MAX_SIZE
+            || height as u32 > MAX_SIZE
+            || width as u32 * height as u32 > MAX_PIXELS
+        {
+            return Err("ArgumentError: Error #2015: Invalid BitmapData.".into());
+        }
+
+        let mut bitmap_data = BitmapData::default();
+        bitmap_data.init_pixels(width as u32, height as u32, fill_color as i32, transparent);
+        this.init_bitmap_data(
+            activation.context.gc_context,
+            GcCell::allocate(activation.context.gc_context, bitmap_data),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.BitmapData`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Get the pixels of a `BitmapData` object.
+///
+/// This fails if the object has been disposed of, as Flash does.
+fn pixels<'gc>(object: Object<'gc>) -> Result<GcCell<'gc, BitmapData>, Error> {
+    object
+        .as_bitmap_data()
+        .filter(|bitmap_data| bitmap_data.read().width() > 0)
+        .ok_or_else(|| "ArgumentError: Error #2015: Invalid BitmapData.".into())
+}
+
+/// Read an argument as an object.
+fn object_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<Object<'gc>, Error> {
+    args.get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)
+}
+
+/// Read an argument as an object, treating a missing or `null` one as `None`.
+fn optional_object_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<Option<Object<'gc>>, Error> {
+    match args.get(index) {
+        None | Some(Value::Undefined) | Some(Value::Null) => Ok(None),
+        Some(value) => Ok(Some(value.coerce_to_object(activation)?)),
+    }
+}
+
+/// Read an argument as an integer.
+fn i32_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<i32, Error> {
+    args.get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)
+}
+
+/// Read an argument as an alpha threshold, falling back to a default if it
+/// was not given.
+fn threshold_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    default: u8,
+) -> Result<u8, Error> {
+    match args.get(index) {
+        Some(value) => Ok(value.coerce_to_u32(activation)?.min(255) as u8),
+        None => Ok(default),
+    }
+}
+
+/// Read an argument as a `Rectangle`, rounded to whole pixels.
+fn rect_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<(i32, i32, i32, i32), Error> {
+    let object = object_arg(activation, args, index)?;
+    let (x, y, width, height) = rect(object, activation)?;
+
+    Ok((x as i32, y as i32, width as i32, height as i32))
+}
+
+/// Read an argument as a `Point`, rounded to whole pixels.
+fn point_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<(i32, i32), Error> {
+    let mut object = object_arg(activation, args, index)?;
+    let (x, y) = coords(&mut object, activation)?;
+
+    Ok((x as i32, y as i32))
+}
+
+/// Modify the pixels of one `BitmapData` while reading those of another.
+///
+/// Both may be the same bitmap, in which case the source is a copy of the
+/// pixels taken beforehand.
+fn with_source<'gc, R>(
+    mc: MutationContext<'gc, '_>,
+    target: GcCell<'gc, BitmapData>,
+    source: GcCell<'gc, BitmapData>,
+    f: impl FnOnce(&mut BitmapData, &BitmapData) -> R,
+) -> R {
+    if GcCell::ptr_eq(target, source) {
+        let source = source.read().clone();
+        f(&mut *target.write(mc), &source)
+    } else {
+        f(&mut *target.write(mc), &*source.read())
+    }
+}
+
+/// Modify the pixels of one `BitmapData` while reading those of another, in
+/// place.
+///
+/// Unlike `with_source`, no copy is taken if both are the same bitmap;
+/// instead, the source is given as `None`.
+fn with_source_in_place<'gc, R>(
+    mc: MutationContext<'gc, '_>,
+    target: GcCell<'gc, BitmapData>,
+    source: GcCell<'gc, BitmapData>,
+    f: impl FnOnce(&mut BitmapData, Option<&BitmapData>) -> R,
+) -> R {
+    if GcCell::ptr_eq(target, source) {
+        f(&mut *target.write(mc), None)
+    } else {
+        f(&mut *target.write(mc), Some(&*source.read()))
+    }
+}
+
+/// Read an argument as a boolean, falling back to a default if it was not
+/// given.
+fn bool_arg(args: &[Value<'_>], index: usize, default: bool) -> bool {
+    args.get(index)
+        .map(|value| value.coerce_to_boolean())
+        .unwrap_or(default)
+}
+
+/// Read an argument as an unsigned integer, falling back to a default if it
+/// was not given.
+fn u32_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    default: u32,
+) -> Result<u32, Error> {
+    match args.get(index) {
+        Some(value) => value.coerce_to_u32(activation),
+        None => Ok(default),
+    }
+}
+
+/// Read an argument as an `Array`, treating a missing or `null` one as
+/// `None`.
+fn array_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<Option<Vec<Value<'gc>>>, Error> {
+    let array = match optional_object_arg(activation, args, index)? {
+        Some(array) => array,
+        None => return Ok(None),
+    };
+    let values = array.as_array_storage().map(|array| {
+        array
+            .iter()
+            .map(|value| value.unwrap_or(Value::Undefined))
+            .collect()
+    });
+
+    Ok(values)
+}
+
+/// Implements `BitmapData.width`'s getter.
+pub fn width<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(pixels(this)?.read().width().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.height`'s getter.
+pub fn height<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(pixels(this)?.read().height().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.transparent`'s getter.
+pub fn transparent<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(pixels(this)?.read().transparency().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.rect`'s getter.
+pub fn get_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let (width, height) = {
+            let read = bitmap_data.read();
+            (read.width(), read.height())
+        };
+
+        return create_rectangle(activation, (0.0, 0.0, width.into(), height.into()));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.getPixel`.
+pub fn get_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let x = i32_arg(activation, args, 0)?;
+        let y = i32_arg(activation, args, 1)?;
+
+        return Ok((bitmap_data.read().get_pixel(x, y) as u32).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.getPixel32`.
+pub fn get_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let x = i32_arg(activation, args, 0)?;
+        let y = i32_arg(activation, args, 1)?;
+        let color: u32 = bitmap_data.read().get_pixel32(x, y).into();
+
+        return Ok(color.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.setPixel`.
+pub fn set_pixel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let x = i32_arg(activation, args, 0)?;
+        let y = i32_arg(activation, args, 1)?;
+        let color = i32_arg(activation, args, 2)?;
+
+        let mut write = bitmap_data.write(activation.context.gc_context);
+        if write.is_point_in_bounds(x, y) {
+            write.set_pixel(x as u32, y as u32, color.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.setPixel32`.
+pub fn set_pixel32<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let x = i32_arg(activation, args, 0)?;
+        let y = i32_arg(activation, args, 1)?;
+        let color = i32_arg(activation, args, 2)?;
+
+        bitmap_data
+            .write(activation.context.gc_context)
+            .set_pixel32(x, y, color.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.fillRect`.
+pub fn fill_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let (x, y, width, height) = rect_arg(activation, args, 0)?;
+        let color = i32_arg(activation, args, 1)?;
+
+        // Clip the rectangle to the bitmap, as filling is done in unsigned
+        // coordinates.
+        let mut write = bitmap_data.write(activation.context.gc_context);
+        let min_x = x.max(0);
+        let min_y = y.max(0);
+        let end_x = x.saturating_add(width).min(write.width() as i32);
+        let end_y = y.saturating_add(height).min(write.height() as i32);
+        if end_x > min_x && end_y > min_y {
+            write.fill_rect(
+                min_x as u32,
+                min_y as u32,
+                (end_x - min_x) as u32,
+                (end_y - min_y) as u32,
+                color.into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.copyPixels`.
+pub fn copy_pixels<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let source = pixels(object_arg(activation, args, 0)?)?;
+        let source_rect = rect_arg(activation, args, 1)?;
+        let dest_point = point_arg(activation, args, 2)?;
+        let alpha_bitmap = match optional_object_arg(activation, args, 3)? {
+            Some(alpha_bitmap) => Some(pixels(alpha_bitmap)?),
+            None => None,
+        };
+        let alpha_point = match optional_object_arg(activation, args, 4)? {
+            Some(mut alpha_point) => {
+                let (x, y) = coords(&mut alpha_point, activation)?;
+                (x as i32, y as i32)
+            }
+            None => (0, 0),
+        };
+        let merge_alpha = args
+            .get(5)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        // Clip the source rectangle to the source bitmap, moving the
+        // destination and alpha points along with it, so that huge rectangles
+        // don't walk over pixels that can never be copied.
+        let (x, y, width, height) = source_rect;
+        let (source_width, source_height) = {
+            let source = source.read();
+            (source.width() as i32, source.height() as i32)
+        };
+        let (target_width, target_height) = {
+            let target = bitmap_data.read();
+            (target.width() as i32, target.height() as i32)
+        };
+        let min_x = x.max(0);
+        let min_y = y.max(0);
+        let end_x = x.saturating_add(width).min(source_width);
+        let end_y = y.saturating_add(height).min(source_height);
+        if end_x <= min_x || end_y <= min_y {
+            return Ok(Value::Undefined);
+        }
+        let (dx, dy) = (min_x.saturating_sub(x), min_y.saturating_sub(y));
+        let source_rect = (min_x, min_y, end_x - min_x, end_y - min_y);
+        let dest_point = (
+            dest_point.0.saturating_add(dx),
+            dest_point.1.saturating_add(dy),
+        );
+        if dest_point.0 >= target_width
+            || dest_point.1 >= target_height
+            || dest_point.0.saturating_add(source_rect.2) <= 0
+            || dest_point.1.saturating_add(source_rect.3) <= 0
+        {
+            return Ok(Value::Undefined);
+        }
+        let alpha_point = (
+            alpha_point.0.saturating_add(dx),
+            alpha_point.1.saturating_add(dy),
+        );
+
+        // The alpha bitmap is rarely used, so it's always copied rather than
+        // checked for aliasing.
+        let alpha_bitmap = alpha_bitmap.map(|alpha_bitmap| alpha_bitmap.read().clone());
+
+        with_source(
+            activation.context.gc_context,
+            bitmap_data,
+            source,
+            |bitmap_data, source| {
+                bitmap_data.copy_pixels(
+                    source,
+                    source_rect,
+                    dest_point,
+                    alpha_bitmap
+                        .as_ref()
+                        .map(|alpha_bitmap| (alpha_bitmap, alpha_point, merge_alpha)),
+                )
+            },
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.draw`.
+///
+/// Display objects are drawn with the software rasterizer, which does not
+/// support masks or text.
+pub fn draw<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let source = object_arg(activation, args, 0)?;
+        let matrix = match optional_object_arg(activation, args, 1)? {
+            Some(matrix) => object_to_matrix(matrix, activation)?,
+            None => Matrix::identity(),
+        };
+        let color_transform = match optional_object_arg(activation, args, 2)? {
+            Some(color_transform) => object_to_color_transform(color_transform, activation)?,
+            None => ColorTransform::default(),
+        };
+        let blend_mode = match args.get(3) {
+            None | Some(Value::Undefined) | Some(Value::Null) => BlendMode::Normal,
+            Some(blend_mode) => {
+                let blend_mode = blend_mode.coerce_to_string(activation)?;
+                blend_mode_from_name(&blend_mode).unwrap_or(BlendMode::Normal)
+            }
+        };
+        let clip = match optional_object_arg(activation, args, 4)? {
+            Some(clip) => {
+                let (x, y, width, height) = rect(clip, activation)?;
+                Some((x as i32, y as i32, width as i32, height as i32))
+            }
+            None => None,
+        };
+        // Only bitmap data is smoothed, as display objects have their own
+        // smoothing settings.
+        let smoothing = args
+            .get(5)
+            .cloned()
+            .unwrap_or(Value::Bool(false))
+            .coerce_to_boolean();
+
+        let source_pixels = match source.as_bitmap_data() {
+            Some(_) => Some(pixels(source)?),
+            None => None,
+        };
+        let source_object = source.as_display_object();
+        if source_pixels.is_none() && source_object.is_none() {
+            return Err(
+                "TypeError: Error #1034: Type Coercion failed: cannot convert to IBitmapDrawable."
+                    .into(),
+            );
+        }
+
+        // Rasterize into a copy, as the source may read from the pixels
+        // being drawn into.
+        let mut target = bitmap_data.read().clone();
+        let transform = Transform {
+            matrix,
+            color_transform,
+        };
+        let mut rasterizer = Rasterizer::new(&mut target, &transform, clip, blend_mode);
+        if let Some(source_pixels) = source_pixels {
+            rasterizer.draw_bitmap(&source_pixels.read(), smoothing);
+        } else if let Some(source_object) = source_object {
+            source_object.rasterize_self(activation.context.renderer, &mut rasterizer);
+        }
+
+        let mut bitmap_data = bitmap_data.write(activation.context.gc_context);
+        bitmap_data.set_pixels(target.pixels().to_vec());
+        bitmap_data.set_dirty(true);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.colorTransform`.
+pub fn color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let (x, y, width, height) = rect_arg(activation, args, 0)?;
+        let color_transform = object_arg(activation, args, 1)?;
+        let color_transform = object_to_color_transform(color_transform, activation)?;
+
+        bitmap_data
+            .write(activation.context.gc_context)
+            .color_transform(
+                x.max(0) as u32,
+                y.max(0) as u32,
+                x.saturating_add(width).max(0) as u32,
+                y.saturating_add(height).max(0) as u32,
+                &color_transform,
+            );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.applyFilter`.
+///
+/// Filters that can't be rendered yet leave the bitmap unchanged.
+pub fn apply_filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let source = pixels(object_arg(activation, args, 0)?)?;
+        let source_rect = rect_arg(activation, args, 1)?;
+        let dest_point = point_arg(activation, args, 2)?;
+        let filter = object_arg(activation, args, 3)?;
+
+        match object_to_filter(activation, filter)? {
+            Some(filter) => with_source(
+                activation.context.gc_context,
+                bitmap_data,
+                source,
+                |bitmap_data, source| {
+                    bitmap_data.apply_filter(source, source_rect, dest_point, &filter)
+                },
+            ),
+            None => activation.unimplemented("BitmapData.applyFilter")?,
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.hitTest`.
+pub fn hit_test<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let (first_x, first_y) = point_arg(activation, args, 0)?;
+        let first_threshold = threshold_arg(activation, args, 1, 0)?;
+        let second = object_arg(activation, args, 2)?;
+
+        if second.as_bitmap_data().is_some() {
+            let second_bitmap = pixels(second)?;
+            let (second_x, second_y) = match optional_object_arg(activation, args, 3)? {
+                Some(mut point) => {
+                    let (x, y) = coords(&mut point, activation)?;
+                    (x as i32, y as i32)
+                }
+                None => (0, 0),
+            };
+            let second_threshold = threshold_arg(activation, args, 4, 1)?;
+
+            return Ok(bitmap_data
+                .read()
+                .hit_test_bitmap(
+                    first_threshold,
+                    &second_bitmap.read(),
+                    (second_x - first_x, second_y - first_y),
+                    second_threshold,
+                )
+                .into());
+        }
+
+        let is_rectangle = second.has_property(&QName::new(Namespace::public(), "width"))?;
+        if is_rectangle {
+            let (x, y, width, height) = rect(second, activation)?;
+            return Ok(bitmap_data
+                .read()
+                .hit_test_rectangle(
+                    first_threshold,
+                    (
+                        x as i32 - first_x,
+                        y as i32 - first_y,
+                        width as i32,
+                        height as i32,
+                    ),
+                )
+                .into());
+        }
+
+        let mut point = second;
+        let (x, y) = coords(&mut point, activation)?;
+        return Ok(bitmap_data
+            .read()
+            .hit_test_point(first_threshold, (x as i32 - first_x, y as i32 - first_y))
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.noise`.
+pub fn noise<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let seed = i32_arg(activation, args, 0)?;
+        let low = u32_arg(activation, args, 1, 0)? as u8;
+        let high = u32_arg(activation, args, 2, 255)? as u8;
+        let channel_options = u32_arg(activation, args, 3, ChannelOptions::rgb().0)?;
+        let gray_scale = bool_arg(args, 4, false);
+
+        bitmap_data.write(activation.context.gc_context).noise(
+            seed,
+            low,
+            high.max(low),
+            channel_options.into(),
+            gray_scale,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.perlinNoise`.
+pub fn perlin_noise<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let base_x = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let base_y = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+        let num_octaves = u32_arg(activation, args, 2, 0)? as usize;
+        let seed = i32_arg(activation, args, 3)?;
+        let stitch = bool_arg(args, 4, false);
+        let fractal_noise = bool_arg(args, 5, false);
+        let channel_options = u32_arg(activation, args, 6, ChannelOptions::rgb().0)?;
+        let gray_scale = bool_arg(args, 7, false);
+        let offsets = array_arg(activation, args, 8)?.unwrap_or_default();
+
+        let mut octave_offsets = Vec::with_capacity(num_octaves);
+        for i in 0..num_octaves {
+            octave_offsets.push(match offsets.get(i) {
+                Some(Value::Object(point)) => coords(&mut point.clone(), activation)?,
+                _ => (0.0, 0.0),
+            });
+        }
+
+        bitmap_data
+            .write(activation.context.gc_context)
+            .perlin_noise(
+                (base_x, base_y),
+                num_octaves,
+                seed.into(),
+                stitch,
+                fractal_noise,
+                channel_options as u8,
+                gray_scale,
+                octave_offsets,
+            );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.paletteMap`.
+pub fn palette_map<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let source = object_arg(activation, args, 0)?;
+        let source = pixels(source)?;
+        let source_rect = rect_arg(activation, args, 1)?;
+        let dest_point = point_arg(activation, args, 2)?;
+
+        let mut get_channel = |index: usize, shift: usize| -> Result<[u32; 256], Error> {
+            let values = array_arg(activation, args, index)?;
+            let mut array = [0_u32; 256];
+            for (i, item) in array.iter_mut().enumerate() {
+                *item = match &values {
+                    Some(values) => values
+                        .get(i)
+                        .cloned()
+                        .unwrap_or(Value::Undefined)
+                        .coerce_to_u32(activation)?,
+                    // Channels without an array are copied unchanged.
+                    None => (i << shift) as u32,
+                };
+            }
+            Ok(array)
+        };
+
+        let red_array = get_channel(3, 16)?;
+        let green_array = get_channel(4, 8)?;
+        let blue_array = get_channel(5, 0)?;
+        let alpha_array = get_channel(6, 24)?;
+
+        with_source_in_place(
+            activation.context.gc_context,
+            bitmap_data,
+            source,
+            |bitmap_data, source| {
+                bitmap_data.palette_map(
+                    source,
+                    source_rect,
+                    dest_point,
+                    (red_array, green_array, blue_array, alpha_array),
+                )
+            },
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.threshold`.
+pub fn threshold<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let source = object_arg(activation, args, 0)?;
+        let source = pixels(source)?;
+        let source_rect = rect_arg(activation, args, 1)?;
+        let dest_point = point_arg(activation, args, 2)?;
+        let operation = args
+            .get(3)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let operation = ThresholdOperation::from_name(&operation)
+            .ok_or("ArgumentError: Error #2005: Parameter 3 is of the incorrect type.")?;
+        let threshold = u32_arg(activation, args, 4, 0)?;
+        let color = u32_arg(activation, args, 5, 0)?;
+        let mask = u32_arg(activation, args, 6, 0xFFFFFFFF)?;
+        let copy_source = bool_arg(args, 7, false);
+
+        let modified_count = with_source_in_place(
+            activation.context.gc_context,
+            bitmap_data,
+            source,
+            |bitmap_data, source| {
+                bitmap_data.threshold(
+                    source,
+                    source_rect,
+                    dest_point,
+                    operation,
+                    threshold,
+                    (color as i32).into(),
+                    mask,
+                    copy_source,
+                )
+            },
+        );
+
+        return Ok(modified_count.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.getColorBoundsRect`.
+pub fn get_color_bounds_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let mask = u32_arg(activation, args, 0, 0)?;
+        let color = u32_arg(activation, args, 1, 0)?;
+        let find_color = bool_arg(args, 2, true);
+
+        let (x, y, width, height) =
+            bitmap_data
+                .read()
+                .color_bounds_rect(find_color, mask as i32, color as i32);
+
+        return create_rectangle(
+            activation,
+            (x.into(), y.into(), width.into(), height.into()),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.compare`.
+///
+/// This returns `0` if both bitmaps are identical, a negative number if their
+/// sizes differ, or otherwise a new `BitmapData` of their differences.
+pub fn compare<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let other = object_arg(activation, args, 0)?;
+        let other = pixels(other)?;
+
+        let difference = {
+            let read = bitmap_data.read();
+            let other = other.read();
+            if read.width() != other.width() {
+                return Ok((-3).into());
+            }
+            if read.height() != other.height() {
+                return Ok((-4).into());
+            }
+
+            read.compare(&other)
+        };
+
+        if let Some(difference) = difference {
+            let proto = activation.context.avm2.prototypes().bitmap_data;
+            let new_bitmap_data = proto.construct(activation, &[])?;
+            new_bitmap_data.init_bitmap_data(
+                activation.context.gc_context,
+                GcCell::allocate(activation.context.gc_context, difference),
+            );
+
+            return Ok(new_bitmap_data.into());
+        }
+
+        return Ok(0.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.scroll`.
+pub fn scroll<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let x = i32_arg(activation, args, 0)?;
+        let y = i32_arg(activation, args, 1)?;
+
+        bitmap_data
+            .write(activation.context.gc_context)
+            .scroll(x, y);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.lock`.
+///
+/// Changed pixels are only uploaded to the renderer once per frame anyway,
+/// so there is nothing to defer.
+pub fn lock<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.unlock`.
+pub fn unlock<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let bitmap_data = pixels(this)?;
+        let proto = activation.context.avm2.prototypes().bitmap_data;
+        let new_bitmap_data = proto.construct(activation, &[])?;
+
+        let read = bitmap_data.read();
+        let mut copy = BitmapData::default();
+        copy.init_pixels(read.width(), read.height(), 0, read.transparency());
+        copy.set_pixels(read.pixels().to_vec());
+        drop(read);
+
+        new_bitmap_data.init_bitmap_data(
+            activation.context.gc_context,
+            GcCell::allocate(activation.context.gc_context, copy),
+        );
+
+        return Ok(new_bitmap_data.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `BitmapData.dispose`.
+pub fn dispose<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(bitmap_data) = this.and_then(|this| this.as_bitmap_data()) {
+        bitmap_data.write(activation.context.gc_context).dispose();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `BitmapData`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "BitmapData"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "width"),
+        Method::from_builtin(width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "height"),
+        Method::from_builtin(height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "transparent"),
+        Method::from_builtin(transparent),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "rect"),
+        Method::from_builtin(get_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getPixel"),
+        Method::from_builtin(get_pixel),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getPixel32"),
+        Method::from_builtin(get_pixel32),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setPixel"),
+        Method::from_builtin(set_pixel),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setPixel32"),
+        Method::from_builtin(set_pixel32),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "fillRect"),
+        Method::from_builtin(fill_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "copyPixels"),
+        Method::from_builtin(copy_pixels),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "draw"),
+        Method::from_builtin(draw),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "colorTransform"),
+        Method::from_builtin(color_transform),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "applyFilter"),
+        Method::from_builtin(apply_filter),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "hitTest"),
+        Method::from_builtin(hit_test),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "noise"),
+        Method::from_builtin(noise),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "perlinNoise"),
+        Method::from_builtin(perlin_noise),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "paletteMap"),
+        Method::from_builtin(palette_map),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "threshold"),
+        Method::from_builtin(threshold),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getColorBoundsRect"),
+        Method::from_builtin(get_color_bounds_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "compare"),
+        Method::from_builtin(compare),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "scroll"),
+        Method::from_builtin(scroll),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "lock"),
+        Method::from_builtin(lock),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "unlock"),
+        Method::from_builtin(unlock),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "dispose"),
+        Method::from_builtin(dispose),
+    ));
+
+    class
+}
@@ -1,15 +1,22 @@
 //! `flash.display.DisplayObject` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::globals::flash::display::stage::stage_object;
+use crate::avm2::globals::flash::filters::bitmapfilter::{filter_to_object, object_to_filter};
+use crate::avm2::globals::flash::geom::point::{coords, create_point};
+use crate::avm2::globals::flash::geom::rectangle::{create_rectangle, rect};
+use crate::avm2::globals::flash::geom::transform::create_transform;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{ArrayObject, Object, TObject};
 use crate::avm2::string::AvmString;
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::display_object::TDisplayObject;
+use crate::bitmap::rasterizer::{blend_mode_from_name, blend_mode_name};
+use crate::display_object::{HitTestOptions, TDisplayObject};
 use crate::types::{Degrees, Percent};
 use gc_arena::{GcCell, MutationContext};
 use swf::Twips;
@@ -376,6 +383,23 @@ pub fn root<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `stage`'s getter.
+pub fn stage<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if dobj.is_on_stage(&activation.context) {
+            return Ok(stage_object(activation)?.into());
+        }
+
+        return Ok(Value::Null);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `visible`'s getter.
 pub fn visible<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -464,7 +488,9 @@ pub fn hit_test_point<'gc>(
             .coerce_to_boolean();
 
         if shape_flag {
-            return Ok(dobj.hit_test_shape(&mut activation.context, (x, y)).into());
+            return Ok(dobj
+                .hit_test_shape(&mut activation.context, (x, y), HitTestOptions::empty())
+                .into());
         } else {
             return Ok(dobj.hit_test_bounds((x, y)).into());
         }
@@ -494,6 +520,379 @@ pub fn hit_test_object<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `getBounds`.
+pub fn get_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    bounds_in_target(activation, this, args, false)
+}
+
+/// Implements `getRect`.
+pub fn get_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    bounds_in_target(activation, this, args, true)
+}
+
+/// Create the `Rectangle` returned by `getBounds`, or by `getRect` when
+/// `edges_only` leaves out the width of strokes.
+fn bounds_in_target<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    edges_only: bool,
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let target = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => {
+                return Err(
+                    "TypeError: Error #2007: Parameter targetCoordinateSpace must be non-null."
+                        .into(),
+                )
+            }
+            target => target.coerce_to_object(activation)?.as_display_object(),
+        };
+
+        if let Some(target) = target {
+            let matrix = target.global_to_local_matrix() * dobj.local_to_global_matrix();
+            let bounds = if edges_only {
+                dobj.edge_bounds_with_transform(&matrix)
+            } else {
+                dobj.bounds_with_transform(&matrix)
+            };
+            let (x, y) = if bounds.valid {
+                (bounds.x_min.to_pixels(), bounds.y_min.to_pixels())
+            } else {
+                (0.0, 0.0)
+            };
+
+            return create_rectangle(
+                activation,
+                (
+                    x,
+                    y,
+                    bounds.width().to_pixels(),
+                    bounds.height().to_pixels(),
+                ),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read the coordinates of the `Point` passed to `localToGlobal` or
+/// `globalToLocal`, in twips.
+fn point_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(Twips, Twips), Error> {
+    let mut point = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Null | Value::Undefined => {
+            return Err("TypeError: Error #2007: Parameter point must be non-null.".into())
+        }
+        point => point.coerce_to_object(activation)?,
+    };
+    let (x, y) = coords(&mut point, activation)?;
+
+    Ok((Twips::from_pixels(x), Twips::from_pixels(y)))
+}
+
+/// Implements `localToGlobal`.
+pub fn local_to_global<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let (x, y) = dobj.local_to_global(point_arg(activation, args)?);
+
+        return create_point(activation, (x.to_pixels(), y.to_pixels()));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `globalToLocal`.
+pub fn global_to_local<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let (x, y) = dobj.global_to_local(point_arg(activation, args)?);
+
+        return create_point(activation, (x.to_pixels(), y.to_pixels()));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `transform`'s getter.
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if this.as_display_object().is_some() {
+            return Ok(create_transform(activation, this)?.into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `transform`'s setter.
+///
+/// This copies the matrix and color transform of another display object's
+/// `Transform` onto this one.
+pub fn set_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        if let Some(source) = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?
+            .as_display_object()
+        {
+            let matrix = *source.matrix();
+            let color_transform = *source.color_transform();
+            dobj.set_matrix(activation.context.gc_context, &matrix);
+            dobj.set_color_transform(activation.context.gc_context, &color_transform);
+            dobj.set_transformed_by_script(activation.context.gc_context, true);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `filters`'s getter.
+pub fn filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let filters = dobj.filters().to_vec();
+        let mut objects = Vec::with_capacity(filters.len());
+        for filter in &filters {
+            if let Some(object) = filter_to_object(activation, filter)? {
+                objects.push(Some(object.into()));
+            }
+        }
+
+        return Ok(ArrayObject::from_array(
+            ArrayStorage::from_storage(objects),
+            activation.context.avm2.prototypes().array,
+            activation.context.gc_context,
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `filters`'s setter.
+///
+/// Anything in the array that isn't a supported filter is ignored.
+pub fn set_filters<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let values: Vec<Value<'gc>> = match args.get(0) {
+            Some(Value::Object(array)) => array
+                .as_array_storage()
+                .map(|array| {
+                    array
+                        .iter()
+                        .map(|value| value.unwrap_or(Value::Undefined))
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+
+        let mut filters = Vec::with_capacity(values.len());
+        for value in values {
+            if let Value::Object(object) = value {
+                filters.extend(object_to_filter(activation, object)?);
+            }
+        }
+
+        dobj.set_filters(activation.context.gc_context, filters);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `blendMode`'s getter.
+pub fn blend_mode<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(blend_mode_name(dobj.blend_mode()).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `blendMode`'s setter.
+pub fn set_blend_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let blend_mode = blend_mode_from_name(&name).ok_or(
+            "ArgumentError: Error #2008: Parameter blendMode must be one of the accepted values.",
+        )?;
+
+        dobj.set_blend_mode(activation.context.gc_context, blend_mode);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `cacheAsBitmap`'s getter.
+pub fn cache_as_bitmap<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.is_bitmap_cached().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `cacheAsBitmap`'s setter.
+pub fn set_cache_as_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let cache_as_bitmap = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+        dobj.set_is_bitmap_cached(activation.context.gc_context, cache_as_bitmap);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `mask`'s getter.
+pub fn mask<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj
+            .masker()
+            .map(|masker| masker.object2())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `mask`'s setter.
+pub fn set_mask<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let mask = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => None,
+            value => value.coerce_to_object(activation)?.as_display_object(),
+        };
+
+        let context = &mut activation.context;
+        dobj.set_clip_depth(context.gc_context, 0);
+        dobj.set_masker(context.gc_context, mask, true);
+        if let Some(mask) = mask {
+            mask.set_maskee(context.gc_context, Some(dobj), true);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scale9Grid`'s getter.
+pub fn scale_9_grid<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let grid = dobj.scaling_grid();
+        if grid.x_max <= grid.x_min || grid.y_max <= grid.y_min {
+            return Ok(Value::Null);
+        }
+
+        return create_rectangle(
+            activation,
+            (
+                grid.x_min.to_pixels(),
+                grid.y_min.to_pixels(),
+                (grid.x_max - grid.x_min).to_pixels(),
+                (grid.y_max - grid.y_min).to_pixels(),
+            ),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `scale9Grid`'s setter.
+pub fn set_scale_9_grid<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let grid = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => Default::default(),
+            value => {
+                let (x, y, width, height) = rect(value.coerce_to_object(activation)?, activation)?;
+                swf::Rectangle {
+                    x_min: Twips::from_pixels(x),
+                    y_min: Twips::from_pixels(y),
+                    x_max: Twips::from_pixels(x + width),
+                    y_max: Twips::from_pixels(y + height),
+                }
+            }
+        };
+
+        dobj.set_scaling_grid(activation.context.gc_context, grid);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `DisplayObject`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -586,6 +985,10 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "root"),
         Method::from_builtin(root),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stage"),
+        Method::from_builtin(stage),
+    ));
     write.define_instance_trait(Trait::from_getter(
         QName::new(Namespace::public(), "visible"),
         Method::from_builtin(visible),
@@ -610,6 +1013,70 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "hitTestObject"),
         Method::from_builtin(hit_test_object),
     ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getBounds"),
+        Method::from_builtin(get_bounds),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getRect"),
+        Method::from_builtin(get_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "localToGlobal"),
+        Method::from_builtin(local_to_global),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "globalToLocal"),
+        Method::from_builtin(global_to_local),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "transform"),
+        Method::from_builtin(transform),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "transform"),
+        Method::from_builtin(set_transform),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "filters"),
+        Method::from_builtin(filters),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "filters"),
+        Method::from_builtin(set_filters),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "blendMode"),
+        Method::from_builtin(blend_mode),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "blendMode"),
+        Method::from_builtin(set_blend_mode),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "cacheAsBitmap"),
+        Method::from_builtin(cache_as_bitmap),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "cacheAsBitmap"),
+        Method::from_builtin(set_cache_as_bitmap),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "mask"),
+        Method::from_builtin(mask),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "mask"),
+        Method::from_builtin(set_mask),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "scale9Grid"),
+        Method::from_builtin(scale_9_grid),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "scale9Grid"),
+        Method::from_builtin(set_scale_9_grid),
+    ));
 
     class
 }
@@ -1,10 +1,11 @@
 //! `flash.display.DisplayObjectContainer` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{ArrayObject, Object, TObject};
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
@@ -253,6 +254,38 @@ pub fn remove_child<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `DisplayObjectContainer.mouseChildren`'s getter
+pub fn mouse_children<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.mouse_children().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `DisplayObjectContainer.mouseChildren`'s setter
+pub fn set_mouse_children<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let mouse_children = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        dobj.set_mouse_children(activation.context.gc_context, mouse_children);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `DisplayObjectContainer.numChildren`
 pub fn num_children<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -554,21 +587,35 @@ pub fn stop_all_movie_clips<'gc>(
 }
 
 /// Stubs `DisplayObjectContainer.getObjectsUnderPoint`
+///
+/// Outside of strict mode, no objects are ever found.
 pub fn get_objects_under_point<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("DisplayObjectContainer.getObjectsUnderPoint not yet implemented".into())
+    activation.unimplemented("DisplayObjectContainer.getObjectsUnderPoint")?;
+
+    let array_proto = activation.context.avm2.prototypes().array;
+    Ok(ArrayObject::from_array(
+        ArrayStorage::new(0),
+        array_proto,
+        activation.context.gc_context,
+    )
+    .into())
 }
 
 /// Stubs `DisplayObjectContainer.areInaccessibleObjectsUnderPoint`
+///
+/// Outside of strict mode, every object is considered accessible.
 pub fn are_inaccessible_objects_under_point<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Err("DisplayObjectContainer.areInaccessibleObjectsUnderPoint not yet implemented".into())
+    activation.unimplemented("DisplayObjectContainer.areInaccessibleObjectsUnderPoint")?;
+
+    Ok(false.into())
 }
 
 /// Construct `DisplayObjectContainer`'s class.
@@ -598,6 +645,14 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "numChildren"),
         Method::from_builtin(num_children),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "mouseChildren"),
+        Method::from_builtin(mouse_children),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "mouseChildren"),
+        Method::from_builtin(set_mouse_children),
+    ));
     write.define_instance_trait(Trait::from_method(
         QName::new(Namespace::public(), "addChild"),
         Method::from_builtin(add_child),
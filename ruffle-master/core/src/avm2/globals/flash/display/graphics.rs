@@ -1,17 +1,29 @@
 //! `flash.display.Graphics` builtin/prototype
 
 use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::display::graphicspathcommand::{
+    CUBIC_CURVE_TO, CURVE_TO, LINE_TO, MOVE_TO, WIDE_LINE_TO, WIDE_MOVE_TO,
+};
+use crate::avm2::globals::flash::display::igraphicsdata::{display_object_to_objects, draw_object};
+use crate::avm2::globals::flash::geom::matrix::object_to_matrix;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{ArrayObject, Object, TObject};
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
-use crate::display_object::TDisplayObject;
-use crate::shape_utils::DrawCommand;
+use crate::character::Character;
+use crate::display_object::{Bitmap, TDisplayObject};
+use crate::drawing::Drawing;
+use crate::shape_utils::{DrawCommand, WindingRule};
 use gc_arena::{GcCell, MutationContext};
-use swf::{Color, FillStyle, LineCapStyle, LineJoinStyle, LineStyle, Twips};
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
+use swf::{
+    Color, FillStyle, Gradient, GradientInterpolation, GradientRecord, GradientSpread,
+    LineCapStyle, LineJoinStyle, LineStyle, Matrix, Twips,
+};
 
 /// Implements `flash.display.Graphics`'s instance constructor.
 pub fn instance_init<'gc>(
@@ -352,6 +364,715 @@ pub fn draw_rect<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Coerce an optional numeric argument, falling back to `default` if it
+/// wasn't given.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    default: f64,
+) -> Result<f64, Error> {
+    args.get(index)
+        .cloned()
+        .unwrap_or_else(|| default.into())
+        .coerce_to_number(activation)
+}
+
+/// Read the elements of an array argument. Holes and a `null` argument are
+/// read as `undefined` and an empty list respectively.
+fn array_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<Vec<Value<'gc>>, Error> {
+    let value = args.get(index).cloned().unwrap_or(Value::Null);
+    if matches!(value, Value::Null | Value::Undefined) {
+        return Ok(Vec::new());
+    }
+
+    let object = value.coerce_to_object(activation)?;
+    let array = object.as_array_storage();
+    Ok(array
+        .map(|array| {
+            array
+                .iter()
+                .map(|value| value.unwrap_or(Value::Undefined))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Read an array argument as a list of numbers.
+fn number_array_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<Vec<f64>, Error> {
+    array_arg(activation, args, index)?
+        .into_iter()
+        .map(|value| value.coerce_to_number(activation))
+        .collect()
+}
+
+/// Draw part of an ellipse around `center`, as a series of quadratic curves
+/// each spanning 45 degrees. The drawing cursor must already be at the start
+/// of the arc.
+fn draw_arc(
+    draw: &mut Drawing,
+    center: (f64, f64),
+    radius: (f64, f64),
+    start_angle: f64,
+    segments: usize,
+) {
+    // The control point of a curve lies where the tangents at its ends meet.
+    let control_scale = 1.0 / (FRAC_PI_4 / 2.0).cos();
+
+    for i in 0..segments {
+        let control_angle = start_angle + FRAC_PI_4 * (i as f64 + 0.5);
+        let end_angle = start_angle + FRAC_PI_4 * (i as f64 + 1.0);
+        draw.draw_command(DrawCommand::CurveTo {
+            x1: Twips::from_pixels(center.0 + radius.0 * control_scale * control_angle.cos()),
+            y1: Twips::from_pixels(center.1 + radius.1 * control_scale * control_angle.sin()),
+            x2: Twips::from_pixels(center.0 + radius.0 * end_angle.cos()),
+            y2: Twips::from_pixels(center.1 + radius.1 * end_angle.sin()),
+        });
+    }
+}
+
+/// Draw a closed ellipse that fits the given box.
+fn draw_ellipse_in_box(draw: &mut Drawing, x: f64, y: f64, width: f64, height: f64) {
+    let radius = (width / 2.0, height / 2.0);
+    let center = (x + radius.0, y + radius.1);
+
+    draw.draw_command(DrawCommand::MoveTo {
+        x: Twips::from_pixels(center.0 + radius.0),
+        y: Twips::from_pixels(center.1),
+    });
+    draw_arc(draw, center, radius, 0.0, 8);
+}
+
+/// Draw a cubic Bézier curve from the drawing cursor, approximated with
+/// quadratic curves.
+fn draw_cubic_curve(
+    draw: &mut Drawing,
+    start: (f64, f64),
+    control1: (f64, f64),
+    control2: (f64, f64),
+    end: (f64, f64),
+) {
+    const SEGMENTS: usize = 4;
+
+    let point = |t: f64| {
+        let u = 1.0 - t;
+        let coord = |p0: f64, p1: f64, p2: f64, p3: f64| {
+            u * u * u * p0 + 3.0 * u * u * t * p1 + 3.0 * u * t * t * p2 + t * t * t * p3
+        };
+        (
+            coord(start.0, control1.0, control2.0, end.0),
+            coord(start.1, control1.1, control2.1, end.1),
+        )
+    };
+    let tangent = |t: f64| {
+        let u = 1.0 - t;
+        let coord = |p0: f64, p1: f64, p2: f64, p3: f64| {
+            3.0 * u * u * (p1 - p0) + 6.0 * u * t * (p2 - p1) + 3.0 * t * t * (p3 - p2)
+        };
+        (
+            coord(start.0, control1.0, control2.0, end.0),
+            coord(start.1, control1.1, control2.1, end.1),
+        )
+    };
+
+    let step = 1.0 / SEGMENTS as f64;
+    for i in 0..SEGMENTS {
+        let t0 = i as f64 * step;
+        let t1 = t0 + step;
+        let (p0, p3) = (point(t0), point(t1));
+        let (d0, d3) = (tangent(t0), tangent(t1));
+
+        // Each piece is itself a cubic curve; use the quadratic curve that
+        // best matches it, whose control point is the average of the two
+        // cubic control points pushed out along the tangents.
+        let c1 = (p0.0 + d0.0 * step / 3.0, p0.1 + d0.1 * step / 3.0);
+        let c2 = (p3.0 - d3.0 * step / 3.0, p3.1 - d3.1 * step / 3.0);
+        let control = (
+            (3.0 * (c1.0 + c2.0) - p0.0 - p3.0) / 4.0,
+            (3.0 * (c1.1 + c2.1) - p0.1 - p3.1) / 4.0,
+        );
+
+        draw.draw_command(DrawCommand::CurveTo {
+            x1: Twips::from_pixels(control.0),
+            y1: Twips::from_pixels(control.1),
+            x2: Twips::from_pixels(p3.0),
+            y2: Twips::from_pixels(p3.1),
+        });
+    }
+}
+
+/// Implements `Graphics.cubicCurveTo`.
+pub fn cubic_curve_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let control1 = (
+            number_arg(activation, args, 0, f64::NAN)?,
+            number_arg(activation, args, 1, f64::NAN)?,
+        );
+        let control2 = (
+            number_arg(activation, args, 2, f64::NAN)?,
+            number_arg(activation, args, 3, f64::NAN)?,
+        );
+        let end = (
+            number_arg(activation, args, 4, f64::NAN)?,
+            number_arg(activation, args, 5, f64::NAN)?,
+        );
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            let (x, y) = draw.cursor();
+            draw_cubic_curve(
+                &mut draw,
+                (x.to_pixels(), y.to_pixels()),
+                control1,
+                control2,
+                end,
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawCircle`.
+pub fn draw_circle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let x = number_arg(activation, args, 0, f64::NAN)?;
+        let y = number_arg(activation, args, 1, f64::NAN)?;
+        let radius = number_arg(activation, args, 2, f64::NAN)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw_ellipse_in_box(
+                &mut draw,
+                x - radius,
+                y - radius,
+                radius * 2.0,
+                radius * 2.0,
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawEllipse`.
+pub fn draw_ellipse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let x = number_arg(activation, args, 0, f64::NAN)?;
+        let y = number_arg(activation, args, 1, f64::NAN)?;
+        let width = number_arg(activation, args, 2, f64::NAN)?;
+        let height = number_arg(activation, args, 3, f64::NAN)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw_ellipse_in_box(&mut draw, x, y, width, height);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawRoundRect`.
+pub fn draw_round_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let x = number_arg(activation, args, 0, f64::NAN)?;
+        let y = number_arg(activation, args, 1, f64::NAN)?;
+        let width = number_arg(activation, args, 2, f64::NAN)?;
+        let height = number_arg(activation, args, 3, f64::NAN)?;
+        let ellipse_width = number_arg(activation, args, 4, f64::NAN)?;
+        let ellipse_height = match args.get(5) {
+            Some(Value::Undefined) | None => ellipse_width,
+            Some(value) => value.coerce_to_number(activation)?,
+        };
+
+        let radius_x = (ellipse_width / 2.0).min(width / 2.0).max(0.0);
+        let radius_y = (ellipse_height / 2.0).min(height / 2.0).max(0.0);
+        let (left, top, right, bottom) = (x, y, x + width, y + height);
+        let to_twips = |x: f64, y: f64| (Twips::from_pixels(x), Twips::from_pixels(y));
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            if radius_x > 0.0 && radius_y > 0.0 {
+                let radius = (radius_x, radius_y);
+                let (x, y) = to_twips(right, bottom - radius_y);
+                draw.draw_command(DrawCommand::MoveTo { x, y });
+                draw_arc(
+                    &mut draw,
+                    (right - radius_x, bottom - radius_y),
+                    radius,
+                    0.0,
+                    2,
+                );
+                let (x, y) = to_twips(left + radius_x, bottom);
+                draw.draw_command(DrawCommand::LineTo { x, y });
+                draw_arc(
+                    &mut draw,
+                    (left + radius_x, bottom - radius_y),
+                    radius,
+                    FRAC_PI_2,
+                    2,
+                );
+                let (x, y) = to_twips(left, top + radius_y);
+                draw.draw_command(DrawCommand::LineTo { x, y });
+                draw_arc(&mut draw, (left + radius_x, top + radius_y), radius, PI, 2);
+                let (x, y) = to_twips(right - radius_x, top);
+                draw.draw_command(DrawCommand::LineTo { x, y });
+                draw_arc(
+                    &mut draw,
+                    (right - radius_x, top + radius_y),
+                    radius,
+                    PI + FRAC_PI_2,
+                    2,
+                );
+                let (x, y) = to_twips(right, bottom - radius_y);
+                draw.draw_command(DrawCommand::LineTo { x, y });
+            } else {
+                let (x, y) = to_twips(left, top);
+                draw.draw_command(DrawCommand::MoveTo { x, y });
+                for &(x, y) in &[(right, top), (right, bottom), (left, bottom), (left, top)] {
+                    let (x, y) = to_twips(x, y);
+                    draw.draw_command(DrawCommand::LineTo { x, y });
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Build a gradient fill from the arguments shared by `beginGradientFill`
+/// and `lineGradientStyle`.
+fn gradient_fill_from_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<FillStyle, Error> {
+    let fill_type = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let colors = array_arg(activation, args, 1)?;
+    let alphas = number_array_arg(activation, args, 2)?;
+    let ratios = number_array_arg(activation, args, 3)?;
+    let matrix = match args.get(4).cloned().unwrap_or(Value::Null) {
+        Value::Null | Value::Undefined => Matrix::identity(),
+        matrix => object_to_matrix(matrix.coerce_to_object(activation)?, activation)?,
+    };
+    let spread = match &*args
+        .get(5)
+        .cloned()
+        .unwrap_or_else(|| "pad".into())
+        .coerce_to_string(activation)?
+    {
+        "reflect" => GradientSpread::Reflect,
+        "repeat" => GradientSpread::Repeat,
+        _ => GradientSpread::Pad,
+    };
+    let interpolation = match &*args
+        .get(6)
+        .cloned()
+        .unwrap_or_else(|| "rgb".into())
+        .coerce_to_string(activation)?
+    {
+        "linearRGB" => GradientInterpolation::LinearRgb,
+        _ => GradientInterpolation::Rgb,
+    };
+    let focal_point = number_arg(activation, args, 7, 0.0)?;
+
+    // Flash quietly ignores any colors that are missing an alpha or a ratio.
+    let mut records = Vec::with_capacity(colors.len());
+    for ((color, alpha), ratio) in colors.into_iter().zip(alphas).zip(ratios) {
+        let rgb = color.coerce_to_u32(activation)?;
+        records.push(GradientRecord {
+            ratio: ratio.min(255.0).max(0.0) as u8,
+            color: color_from_args(rgb, alpha.min(1.0).max(0.0)),
+        });
+    }
+
+    let gradient = Gradient {
+        matrix,
+        spread,
+        interpolation,
+        records,
+    };
+
+    match &*fill_type {
+        "linear" => Ok(FillStyle::LinearGradient(gradient)),
+        "radial" if focal_point != 0.0 => Ok(FillStyle::FocalGradient {
+            gradient,
+            focal_point: focal_point.min(1.0).max(-1.0) as f32,
+        }),
+        "radial" => Ok(FillStyle::RadialGradient(gradient)),
+        _ => Err(
+            "ArgumentError: Error #2008: Parameter type must be one of the accepted values.".into(),
+        ),
+    }
+}
+
+/// Implements `Graphics.beginGradientFill`.
+pub fn begin_gradient_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let fill_style = gradient_fill_from_args(activation, args)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_fill_style(Some(fill_style));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.lineGradientStyle`.
+///
+/// This replaces the color of the current line style, so it does nothing
+/// unless `lineStyle` was called first.
+pub fn line_gradient_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let fill_style = gradient_fill_from_args(activation, args)?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            if let Some(mut line_style) = draw.line_style().cloned() {
+                line_style.fill_style = Some(fill_style);
+                draw.set_line_style(Some(line_style));
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.beginBitmapFill`.
+pub fn begin_bitmap_fill<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let bitmap_data = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?
+            .as_bitmap_data()
+            .ok_or("TypeError: Error #1034: Parameter bitmap must be a BitmapData.")?;
+        let matrix = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => Matrix::identity(),
+            matrix => object_to_matrix(matrix.coerce_to_object(activation)?, activation)?,
+        };
+        let is_repeating = args
+            .get(2)
+            .cloned()
+            .unwrap_or_else(|| true.into())
+            .coerce_to_boolean();
+        let is_smoothed = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+
+        let movie = match this.movie() {
+            Some(movie) => movie,
+            None => return Ok(Value::Undefined),
+        };
+        let (handle, width, height) = {
+            let mut write = bitmap_data.write(activation.context.gc_context);
+            let handle = write.bitmap_handle(activation.context.renderer);
+            (handle, write.width(), write.height())
+        };
+        let handle = match handle {
+            Some(handle) => handle,
+            None => return Ok(Value::Undefined),
+        };
+
+        // Bitmap fills refer to their bitmap by character ID, so the bitmap
+        // has to be in the library of the movie that draws it.
+        let library = activation
+            .context
+            .library
+            .library_for_movie_mut(movie.clone());
+        let id = match library.bitmap_id_by_handle(handle) {
+            Some(id) => id,
+            None => {
+                let id = library
+                    .unused_character_id()
+                    .ok_or("Error: Out of character IDs for bitmap fills")?;
+                let bitmap = Bitmap::new_with_bitmap_data(
+                    &mut activation.context,
+                    id,
+                    handle,
+                    width as u16,
+                    height as u16,
+                    Some(bitmap_data),
+                    is_smoothed,
+                );
+                activation
+                    .context
+                    .library
+                    .library_for_movie_mut(movie)
+                    .register_character(id, Character::Bitmap(bitmap));
+                id
+            }
+        };
+
+        // Bitmap fill matrices map from bitmap pixels to twips.
+        let matrix = Matrix {
+            a: matrix.a * 20.0,
+            b: matrix.b * 20.0,
+            c: matrix.c * 20.0,
+            d: matrix.d * 20.0,
+            ..matrix
+        };
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_fill_style(Some(FillStyle::Bitmap {
+                id,
+                matrix,
+                is_smoothed,
+                is_repeating,
+            }));
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Convert a `GraphicsPathWinding` name into a winding rule.
+fn winding_rule_from_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    winding: Value<'gc>,
+) -> Result<WindingRule, Error> {
+    match &*winding.coerce_to_string(activation)? {
+        "evenOdd" => Ok(WindingRule::EvenOdd),
+        "nonZero" => Ok(WindingRule::NonZero),
+        _ => Err(
+            "ArgumentError: Error #2008: Parameter winding must be one of the accepted values."
+                .into(),
+        ),
+    }
+}
+
+/// Implements `Graphics.drawPath`.
+pub fn draw_path<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let commands = array_arg(activation, args, 0)?
+            .into_iter()
+            .map(|command| command.coerce_to_i32(activation))
+            .collect::<Result<Vec<_>, _>>()?;
+        let data = number_array_arg(activation, args, 1)?;
+        let winding = winding_rule_from_arg(
+            activation,
+            args.get(2).cloned().unwrap_or_else(|| "evenOdd".into()),
+        )?;
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            draw.set_winding_rule(winding);
+
+            let to_twips = |x: f64, y: f64| (Twips::from_pixels(x), Twips::from_pixels(y));
+            let mut data = data.as_slice();
+            for command in commands {
+                // Each `GraphicsPathCommand` takes a fixed number of values;
+                // the path ends early if the data runs out.
+                let data_len = match command {
+                    MOVE_TO | LINE_TO => 2,
+                    CURVE_TO | WIDE_MOVE_TO | WIDE_LINE_TO => 4,
+                    CUBIC_CURVE_TO => 6,
+                    _ => 0,
+                };
+                if data.len() < data_len {
+                    break;
+                }
+                let (values, rest) = data.split_at(data_len);
+                data = rest;
+
+                match (command, values) {
+                    (MOVE_TO, &[x, y]) | (WIDE_MOVE_TO, &[_, _, x, y]) => {
+                        let (x, y) = to_twips(x, y);
+                        draw.draw_command(DrawCommand::MoveTo { x, y });
+                    }
+                    (LINE_TO, &[x, y]) | (WIDE_LINE_TO, &[_, _, x, y]) => {
+                        let (x, y) = to_twips(x, y);
+                        draw.draw_command(DrawCommand::LineTo { x, y });
+                    }
+                    (CURVE_TO, &[x1, y1, x2, y2]) => {
+                        let (x1, y1) = to_twips(x1, y1);
+                        let (x2, y2) = to_twips(x2, y2);
+                        draw.draw_command(DrawCommand::CurveTo { x1, y1, x2, y2 });
+                    }
+                    (CUBIC_CURVE_TO, &[x1, y1, x2, y2, x, y]) => {
+                        let (start_x, start_y) = draw.cursor();
+                        draw_cubic_curve(
+                            &mut draw,
+                            (start_x.to_pixels(), start_y.to_pixels()),
+                            (x1, y1),
+                            (x2, y2),
+                            (x, y),
+                        );
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.drawTriangles`.
+///
+/// Each triangle is drawn as a closed outline with the current fill and
+/// line styles. Texture coordinates and culling are not supported yet.
+pub fn draw_triangles<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let vertices = number_array_arg(activation, args, 0)?;
+        let vertices: Vec<_> = vertices
+            .chunks_exact(2)
+            .map(|vertex| (Twips::from_pixels(vertex[0]), Twips::from_pixels(vertex[1])))
+            .collect();
+        let indices = match args.get(1) {
+            Some(Value::Null) | Some(Value::Undefined) | None => (0..vertices.len()).collect(),
+            Some(_) => number_array_arg(activation, args, 1)?
+                .into_iter()
+                .map(|index| index as usize)
+                .collect::<Vec<_>>(),
+        };
+
+        if let Some(mut draw) = this.as_drawing(activation.context.gc_context) {
+            for triangle in indices.chunks_exact(3) {
+                let points: Option<Vec<_>> =
+                    triangle.iter().map(|&i| vertices.get(i).copied()).collect();
+                let points = match points {
+                    Some(points) => points,
+                    None => {
+                        return Err(
+                            "RangeError: Error #2004: One of the parameters is invalid.".into()
+                        )
+                    }
+                };
+
+                let (x, y) = points[0];
+                draw.draw_command(DrawCommand::MoveTo { x, y });
+                for &(x, y) in points[1..].iter().chain(&points[..1]) {
+                    draw.draw_command(DrawCommand::LineTo { x, y });
+                }
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let source = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?
+            .as_display_object()
+            .ok_or("TypeError: Error #1034: Parameter sourceGraphics must be a Graphics.")?;
+
+        // Copy the source out first, in case it's the same drawing.
+        let source = source
+            .as_drawing(activation.context.gc_context)
+            .map(|draw| draw.clone());
+
+        if let (Some(source), Some(mut draw)) =
+            (source, this.as_drawing(activation.context.gc_context))
+        {
+            draw.copy_from(&source);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Graphics.readGraphicsData`.
+///
+/// The graphics data is returned as an `Array`, as there is no `Vector`
+/// class yet. The drawings of child display objects follow, unless `recurse`
+/// is `false`.
+pub fn read_graphics_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut objects = Vec::new();
+
+    if let Some(this) = this.and_then(|t| t.as_display_object()) {
+        let recurse = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Bool(true))
+            .coerce_to_boolean();
+        objects = display_object_to_objects(activation, this, recurse)?;
+    }
+
+    Ok(ArrayObject::from_array(
+        ArrayStorage::from_args(&objects),
+        activation.context.avm2.prototypes().array,
+        activation.context.gc_context,
+    )
+    .into())
+}
+
+/// Implements `Graphics.drawGraphicsData`.
+pub fn draw_graphics_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        for value in array_arg(activation, args, 0)? {
+            if let Value::Object(object) = value {
+                draw_object(activation, this, object)?;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Graphics`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -398,6 +1119,54 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "drawRect"),
         Method::from_builtin(draw_rect),
     ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "beginGradientFill"),
+        Method::from_builtin(begin_gradient_fill),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "beginBitmapFill"),
+        Method::from_builtin(begin_bitmap_fill),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "lineGradientStyle"),
+        Method::from_builtin(line_gradient_style),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "cubicCurveTo"),
+        Method::from_builtin(cubic_curve_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "drawCircle"),
+        Method::from_builtin(draw_circle),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "drawEllipse"),
+        Method::from_builtin(draw_ellipse),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "drawRoundRect"),
+        Method::from_builtin(draw_round_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "drawPath"),
+        Method::from_builtin(draw_path),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "drawTriangles"),
+        Method::from_builtin(draw_triangles),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "copyFrom"),
+        Method::from_builtin(copy_from),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "readGraphicsData"),
+        Method::from_builtin(read_graphics_data),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "drawGraphicsData"),
+        Method::from_builtin(draw_graphics_data),
+    ));
 
     class
 }
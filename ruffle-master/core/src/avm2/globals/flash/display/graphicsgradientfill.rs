@@ -0,0 +1,71 @@
+//! `flash.display.GraphicsGradientFill` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::{define_properties, init_properties, SlotProperty};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// `GraphicsGradientFill`'s properties, in the order of its constructor's parameters,
+/// with their default values.
+pub fn properties<'gc>() -> [SlotProperty<'gc>; 8] {
+    [
+        ("type", "String", "linear".into()),
+        ("colors", "Array", Value::Null),
+        ("alphas", "Array", Value::Null),
+        ("ratios", "Array", Value::Null),
+        ("matrix", "Object", Value::Null),
+        ("spreadMethod", "String", "pad".into()),
+        ("interpolationMethod", "String", "rgb".into()),
+        ("focalPointRatio", "Number", 0.0.into()),
+    ]
+}
+
+/// Implements `flash.display.GraphicsGradientFill`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+        init_properties(activation, this, args, &properties())?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.GraphicsGradientFill`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `GraphicsGradientFill`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "GraphicsGradientFill"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.implements(QName::new(Namespace::package("flash.display"), "IGraphicsFill").into());
+    write.implements(QName::new(Namespace::package("flash.display"), "IGraphicsData").into());
+
+    define_properties(&mut write, &properties());
+
+    class
+}
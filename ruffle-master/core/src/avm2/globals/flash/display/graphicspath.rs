@@ -0,0 +1,212 @@
+//! `flash.display.GraphicsPath` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::display::graphicspathcommand::{
+    CUBIC_CURVE_TO, CURVE_TO, LINE_TO, MOVE_TO, WIDE_LINE_TO, WIDE_MOVE_TO,
+};
+use crate::avm2::globals::{define_properties, init_properties, SlotProperty};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// `GraphicsPath`'s properties, in the order of its constructor's parameters,
+/// with their default values.
+pub fn properties<'gc>() -> [SlotProperty<'gc>; 3] {
+    [
+        ("commands", "Array", Value::Null),
+        ("data", "Array", Value::Null),
+        ("winding", "String", "evenOdd".into()),
+    ]
+}
+
+/// Implements `flash.display.GraphicsPath`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+        init_properties(activation, this, args, &properties())?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.GraphicsPath`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Append values to one of a path's arrays, creating the array if the path
+/// doesn't have one yet.
+fn push_values<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    name: &'static str,
+    values: &[Value<'gc>],
+) -> Result<(), Error> {
+    let name = QName::new(Namespace::public(), name);
+    let array = match this.get_property(this, &name, activation)? {
+        Value::Null | Value::Undefined => {
+            let array = ArrayObject::from_array(
+                ArrayStorage::new(0),
+                activation.context.avm2.prototypes().array,
+                activation.context.gc_context,
+            );
+            this.set_property(this, &name, array.into(), activation)?;
+            array
+        }
+        array => array.coerce_to_object(activation)?,
+    };
+
+    if let Some(mut array) = array.as_array_storage_mut(activation.context.gc_context) {
+        for value in values {
+            array.push(value.clone());
+        }
+    }
+
+    Ok(())
+}
+
+/// Append a command to a path, along with the points it takes from `args`.
+///
+/// Wide commands are padded with an unused point, as Flash does.
+fn push_command<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    command: i32,
+    args: &[Value<'gc>],
+    arg_count: usize,
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut data = Vec::with_capacity(arg_count + 2);
+        if command == WIDE_MOVE_TO || command == WIDE_LINE_TO {
+            data.extend_from_slice(&[0.0.into(), 0.0.into()]);
+        }
+        for i in 0..arg_count {
+            let value = args
+                .get(i)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_number(activation)?;
+            data.push(value.into());
+        }
+
+        push_values(activation, this, "commands", &[command.into()])?;
+        push_values(activation, this, "data", &data)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `GraphicsPath.cubicCurveTo`.
+pub fn cubic_curve_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    push_command(activation, this, CUBIC_CURVE_TO, args, 6)
+}
+
+/// Implements `GraphicsPath.curveTo`.
+pub fn curve_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    push_command(activation, this, CURVE_TO, args, 4)
+}
+
+/// Implements `GraphicsPath.lineTo`.
+pub fn line_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    push_command(activation, this, LINE_TO, args, 2)
+}
+
+/// Implements `GraphicsPath.moveTo`.
+pub fn move_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    push_command(activation, this, MOVE_TO, args, 2)
+}
+
+/// Implements `GraphicsPath.wideLineTo`.
+pub fn wide_line_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    push_command(activation, this, WIDE_LINE_TO, args, 2)
+}
+
+/// Implements `GraphicsPath.wideMoveTo`.
+pub fn wide_move_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    push_command(activation, this, WIDE_MOVE_TO, args, 2)
+}
+
+/// Construct `GraphicsPath`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "GraphicsPath"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.implements(QName::new(Namespace::package("flash.display"), "IGraphicsPath").into());
+    write.implements(QName::new(Namespace::package("flash.display"), "IGraphicsData").into());
+
+    define_properties(&mut write, &properties());
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "cubicCurveTo"),
+        Method::from_builtin(cubic_curve_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "curveTo"),
+        Method::from_builtin(curve_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "lineTo"),
+        Method::from_builtin(line_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "moveTo"),
+        Method::from_builtin(move_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "wideLineTo"),
+        Method::from_builtin(wide_line_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "wideMoveTo"),
+        Method::from_builtin(wide_move_to),
+    ));
+
+    class
+}
@@ -0,0 +1,107 @@
+//! `flash.display.GraphicsPathCommand` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Draws nothing.
+pub const NO_OP: i32 = 0;
+
+/// Moves the drawing cursor to a point.
+pub const MOVE_TO: i32 = 1;
+
+/// Draws a line to a point.
+pub const LINE_TO: i32 = 2;
+
+/// Draws a quadratic curve through a control point to an anchor point.
+pub const CURVE_TO: i32 = 3;
+
+/// Like `MOVE_TO`, but with an unused point before the destination.
+pub const WIDE_MOVE_TO: i32 = 4;
+
+/// Like `LINE_TO`, but with an unused point before the destination.
+pub const WIDE_LINE_TO: i32 = 5;
+
+/// Draws a cubic curve through two control points to an anchor point.
+pub const CUBIC_CURVE_TO: i32 = 6;
+
+/// Implements `flash.display.GraphicsPathCommand`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.GraphicsPathCommand`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `GraphicsPathCommand`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "GraphicsPathCommand"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "NO_OP"),
+        QName::new(Namespace::public(), "int").into(),
+        Some(NO_OP.into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "MOVE_TO"),
+        QName::new(Namespace::public(), "int").into(),
+        Some(MOVE_TO.into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "LINE_TO"),
+        QName::new(Namespace::public(), "int").into(),
+        Some(LINE_TO.into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "CURVE_TO"),
+        QName::new(Namespace::public(), "int").into(),
+        Some(CURVE_TO.into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "WIDE_MOVE_TO"),
+        QName::new(Namespace::public(), "int").into(),
+        Some(WIDE_MOVE_TO.into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "WIDE_LINE_TO"),
+        QName::new(Namespace::public(), "int").into(),
+        Some(WIDE_LINE_TO.into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "CUBIC_CURVE_TO"),
+        QName::new(Namespace::public(), "int").into(),
+        Some(CUBIC_CURVE_TO.into()),
+    ));
+
+    class
+}
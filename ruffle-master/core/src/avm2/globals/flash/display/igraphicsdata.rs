@@ -0,0 +1,519 @@
+//! `flash.display.IGraphicsData` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::display::graphicspathcommand::{CURVE_TO, LINE_TO, MOVE_TO};
+use crate::avm2::globals::flash::display::{
+    graphics, graphicsbitmapfill, graphicsendfill, graphicsgradientfill, graphicspath,
+    graphicssolidfill, graphicsstroke, graphicstrianglepath,
+};
+use crate::avm2::globals::flash::geom::matrix::matrix_to_object;
+use crate::avm2::globals::SlotProperty;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{DisplayObject, TDisplayObject, TDisplayObjectContainer};
+use crate::drawing::Drawing;
+use crate::shape_utils::{DrawCommand, DrawPath, WindingRule};
+use crate::tag_utils::SwfMovie;
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+use swf::{
+    Color, FillStyle, Gradient, GradientInterpolation, GradientSpread, LineCapStyle, LineJoinStyle,
+    LineStyle, Matrix,
+};
+
+/// Emulates attempts to execute bodiless methods.
+pub fn bodiless_method<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Err("Cannot execute non-native method without body".into())
+}
+
+/// Implements the class constructor of the graphics data interfaces.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read the properties of a graphics data object, in the same order as its
+/// constructor's parameters.
+///
+/// Each graphics data class takes its properties in the same order as the
+/// `Graphics` method that draws it, so they can be passed straight on.
+fn get_properties<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    properties: &[SlotProperty<'gc>],
+) -> Result<Vec<Value<'gc>>, Error> {
+    properties
+        .iter()
+        .map(|(name, _, _)| {
+            object.get_property(object, &QName::new(Namespace::public(), *name), activation)
+        })
+        .collect()
+}
+
+/// Construct a new graphics data object.
+fn construct<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    proto: Object<'gc>,
+    instance_init: NativeMethod<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let object = proto.construct(activation, &[])?;
+    instance_init(activation, Some(object), args)?;
+
+    Ok(object.into())
+}
+
+/// Construct a new `Array` of the given values.
+fn array<'gc>(activation: &mut Activation<'_, 'gc, '_>, values: &[Value<'gc>]) -> Value<'gc> {
+    ArrayObject::from_array(
+        ArrayStorage::from_args(values),
+        activation.context.avm2.prototypes().array,
+        activation.context.gc_context,
+    )
+    .into()
+}
+
+fn color_to_rgb(color: &Color) -> u32 {
+    (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b)
+}
+
+fn color_to_alpha(color: &Color) -> f64 {
+    f64::from(color.a) / 255.0
+}
+
+/// Construct a new `GraphicsGradientFill` from a gradient, drawn with
+/// `matrix`.
+fn gradient_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    fill_type: &'static str,
+    gradient: &Gradient,
+    focal_point: f32,
+    matrix: Matrix,
+) -> Result<Value<'gc>, Error> {
+    let colors: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| color_to_rgb(&record.color).into())
+        .collect();
+    let alphas: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| color_to_alpha(&record.color).into())
+        .collect();
+    let ratios: Vec<Value<'gc>> = gradient
+        .records
+        .iter()
+        .map(|record| i32::from(record.ratio).into())
+        .collect();
+    let spread = match gradient.spread {
+        GradientSpread::Pad => "pad",
+        GradientSpread::Reflect => "reflect",
+        GradientSpread::Repeat => "repeat",
+    };
+    let interpolation = match gradient.interpolation {
+        GradientInterpolation::Rgb => "rgb",
+        GradientInterpolation::LinearRgb => "linearRGB",
+    };
+
+    let args = [
+        fill_type.into(),
+        array(activation, &colors),
+        array(activation, &alphas),
+        array(activation, &ratios),
+        matrix_to_object(matrix * gradient.matrix, activation)?.into(),
+        spread.into(),
+        interpolation.into(),
+        f64::from(focal_point).into(),
+    ];
+
+    let proto = activation.context.avm2.prototypes().graphics_gradient_fill;
+    construct(
+        activation,
+        proto,
+        graphicsgradientfill::instance_init,
+        &args,
+    )
+}
+
+/// Construct a new graphics fill object from a fill style, drawn with
+/// `matrix`.
+fn fill_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    fill_style: &FillStyle,
+    matrix: Matrix,
+    movie: Option<Arc<SwfMovie>>,
+) -> Result<Value<'gc>, Error> {
+    match fill_style {
+        FillStyle::Color(color) => {
+            let proto = activation.context.avm2.prototypes().graphics_solid_fill;
+            construct(
+                activation,
+                proto,
+                graphicssolidfill::instance_init,
+                &[color_to_rgb(color).into(), color_to_alpha(color).into()],
+            )
+        }
+        FillStyle::LinearGradient(gradient) => {
+            gradient_to_object(activation, "linear", gradient, 0.0, matrix)
+        }
+        FillStyle::RadialGradient(gradient) => {
+            gradient_to_object(activation, "radial", gradient, 0.0, matrix)
+        }
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => gradient_to_object(activation, "radial", gradient, *focal_point, matrix),
+        FillStyle::Bitmap {
+            id,
+            matrix: bitmap_matrix,
+            is_smoothed,
+            is_repeating,
+        } => {
+            // Bitmaps that came from the movie itself have no `BitmapData`.
+            let bitmap_data = movie
+                .and_then(|movie| activation.context.library.library_for_movie(movie))
+                .and_then(|library| library.get_bitmap(*id))
+                .and_then(|bitmap| bitmap.bitmap_data());
+            let bitmap_data = match bitmap_data {
+                Some(bitmap_data) => {
+                    let proto = activation.context.avm2.prototypes().bitmap_data;
+                    let object = proto.construct(activation, &[])?;
+                    object.init_bitmap_data(activation.context.gc_context, bitmap_data);
+                    object.into()
+                }
+                None => Value::Null,
+            };
+
+            // Bitmap fill matrices map from bitmap pixels to twips.
+            let matrix = matrix * *bitmap_matrix;
+            let matrix = Matrix {
+                a: matrix.a / 20.0,
+                b: matrix.b / 20.0,
+                c: matrix.c / 20.0,
+                d: matrix.d / 20.0,
+                ..matrix
+            };
+
+            let args = [
+                bitmap_data,
+                matrix_to_object(matrix, activation)?.into(),
+                (*is_repeating).into(),
+                (*is_smoothed).into(),
+            ];
+
+            let proto = activation.context.avm2.prototypes().graphics_bitmap_fill;
+            construct(activation, proto, graphicsbitmapfill::instance_init, &args)
+        }
+    }
+}
+
+/// Construct a new `GraphicsStroke` from a line style, drawn with `matrix`.
+fn line_style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    line_style: &LineStyle,
+    matrix: Matrix,
+    movie: Option<Arc<SwfMovie>>,
+) -> Result<Value<'gc>, Error> {
+    let scale_mode = match (line_style.allow_scale_x, line_style.allow_scale_y) {
+        (true, true) => "normal",
+        (false, false) => "none",
+        (true, false) => "horizontal",
+        (false, true) => "vertical",
+    };
+    let caps = match line_style.start_cap {
+        LineCapStyle::None => "none",
+        LineCapStyle::Round => "round",
+        LineCapStyle::Square => "square",
+    };
+    let (joints, miter_limit) = match line_style.join_style {
+        LineJoinStyle::Round => ("round", 3.0),
+        LineJoinStyle::Bevel => ("bevel", 3.0),
+        LineJoinStyle::Miter(miter_limit) => ("miter", f64::from(miter_limit)),
+    };
+    let fill = match &line_style.fill_style {
+        Some(fill_style) => fill_style_to_object(activation, fill_style, matrix, movie)?,
+        None => fill_style_to_object(
+            activation,
+            &FillStyle::Color(line_style.color.clone()),
+            matrix,
+            movie,
+        )?,
+    };
+
+    let args = [
+        line_style.width.to_pixels().into(),
+        line_style.is_pixel_hinted.into(),
+        scale_mode.into(),
+        caps.into(),
+        joints.into(),
+        miter_limit.into(),
+        fill,
+    ];
+
+    let proto = activation.context.avm2.prototypes().graphics_stroke;
+    construct(activation, proto, graphicsstroke::instance_init, &args)
+}
+
+/// Construct a new `GraphicsPath` from a list of drawing commands, with their
+/// points transformed by `matrix`.
+fn commands_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    commands: &[DrawCommand],
+    matrix: Matrix,
+    winding: WindingRule,
+) -> Result<Value<'gc>, Error> {
+    let mut path_commands: Vec<Value<'gc>> = Vec::with_capacity(commands.len());
+    let mut data: Vec<Value<'gc>> = Vec::with_capacity(commands.len() * 2);
+
+    for command in commands {
+        match *command {
+            DrawCommand::MoveTo { x, y } => {
+                let (x, y) = matrix * (x, y);
+                path_commands.push(MOVE_TO.into());
+                data.extend_from_slice(&[x.to_pixels().into(), y.to_pixels().into()]);
+            }
+            DrawCommand::LineTo { x, y } => {
+                let (x, y) = matrix * (x, y);
+                path_commands.push(LINE_TO.into());
+                data.extend_from_slice(&[x.to_pixels().into(), y.to_pixels().into()]);
+            }
+            DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                let (x1, y1) = matrix * (x1, y1);
+                let (x2, y2) = matrix * (x2, y2);
+                path_commands.push(CURVE_TO.into());
+                data.extend_from_slice(&[
+                    x1.to_pixels().into(),
+                    y1.to_pixels().into(),
+                    x2.to_pixels().into(),
+                    y2.to_pixels().into(),
+                ]);
+            }
+        }
+    }
+
+    let winding = match winding {
+        WindingRule::EvenOdd => "evenOdd",
+        WindingRule::NonZero => "nonZero",
+    };
+    let args = [
+        array(activation, &path_commands),
+        array(activation, &data),
+        winding.into(),
+    ];
+
+    let proto = activation.context.avm2.prototypes().graphics_path;
+    construct(activation, proto, graphicspath::instance_init, &args)
+}
+
+/// Describe the drawing of a display object as a list of graphics data
+/// objects, as returned by `Graphics.readGraphicsData`.
+///
+/// If `recurse` is set, the drawings of its children follow, in the order
+/// they're rendered and in the coordinate space of `object`. Shapes placed
+/// on a timeline have no drawing, and are skipped.
+///
+/// Each fill is given as its fill, its path and a `GraphicsEndFill`. Each
+/// line is given as its stroke and its path, and a final empty stroke stops
+/// lines from carrying on into anything drawn after them.
+pub fn display_object_to_objects<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: DisplayObject<'gc>,
+    recurse: bool,
+) -> Result<Vec<Value<'gc>>, Error> {
+    let mut objects = Vec::new();
+    let has_lines = push_drawings(
+        activation,
+        object,
+        Matrix::identity(),
+        recurse,
+        &mut objects,
+    )?;
+
+    if has_lines {
+        let proto = activation.context.avm2.prototypes().graphics_stroke;
+        objects.push(construct(
+            activation,
+            proto,
+            graphicsstroke::instance_init,
+            &[],
+        )?);
+    }
+
+    Ok(objects)
+}
+
+/// Append the graphics data objects describing the drawing of a display
+/// object, and those of its children if `recurse` is set, with their
+/// coordinates transformed by `matrix`.
+///
+/// Returns whether any lines were described.
+fn push_drawings<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: DisplayObject<'gc>,
+    matrix: Matrix,
+    recurse: bool,
+    objects: &mut Vec<Value<'gc>>,
+) -> Result<bool, Error> {
+    let mut has_lines = false;
+    let movie = object.movie();
+    let drawing = object.drawing().map(|drawing| drawing.clone());
+
+    for path in drawing.iter().flat_map(Drawing::paths) {
+        match path {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding,
+            } => {
+                objects.push(fill_style_to_object(
+                    activation,
+                    style,
+                    matrix,
+                    movie.clone(),
+                )?);
+                objects.push(commands_to_object(activation, &commands, matrix, winding)?);
+                let proto = activation.context.avm2.prototypes().graphics_end_fill;
+                objects.push(construct(
+                    activation,
+                    proto,
+                    graphicsendfill::instance_init,
+                    &[],
+                )?);
+            }
+            DrawPath::Stroke {
+                style, commands, ..
+            } => {
+                objects.push(line_style_to_object(
+                    activation,
+                    style,
+                    matrix,
+                    movie.clone(),
+                )?);
+                objects.push(commands_to_object(
+                    activation,
+                    &commands,
+                    matrix,
+                    WindingRule::EvenOdd,
+                )?);
+                has_lines = true;
+            }
+        }
+    }
+
+    if let Some(container) = object.as_container().filter(|_| recurse) {
+        for child in container.iter_render_list() {
+            let child_matrix = matrix * *child.matrix();
+            has_lines |= push_drawings(activation, child, child_matrix, recurse, objects)?;
+        }
+    }
+
+    Ok(has_lines)
+}
+
+/// Draw a graphics data object into a `Graphics` object, as done by
+/// `Graphics.drawGraphicsData`.
+///
+/// Objects that are not graphics data, or that can't be drawn yet, are
+/// ignored.
+pub fn draw_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    graphics: Object<'gc>,
+    object: Object<'gc>,
+) -> Result<(), Error> {
+    let prototypes = activation.context.avm2.prototypes().clone();
+
+    if object.has_prototype_in_chain(prototypes.graphics_solid_fill, false)? {
+        let args = get_properties(activation, object, &graphicssolidfill::properties())?;
+        graphics::begin_fill(activation, Some(graphics), &args)?;
+    } else if object.has_prototype_in_chain(prototypes.graphics_gradient_fill, false)? {
+        let args = get_properties(activation, object, &graphicsgradientfill::properties())?;
+        graphics::begin_gradient_fill(activation, Some(graphics), &args)?;
+    } else if object.has_prototype_in_chain(prototypes.graphics_bitmap_fill, false)? {
+        let args = get_properties(activation, object, &graphicsbitmapfill::properties())?;
+        graphics::begin_bitmap_fill(activation, Some(graphics), &args)?;
+    } else if object.has_prototype_in_chain(prototypes.graphics_end_fill, false)? {
+        graphics::end_fill(activation, Some(graphics), &[])?;
+    } else if object.has_prototype_in_chain(prototypes.graphics_path, false)? {
+        let args = get_properties(activation, object, &graphicspath::properties())?;
+        graphics::draw_path(activation, Some(graphics), &args)?;
+    } else if object.has_prototype_in_chain(prototypes.graphics_triangle_path, false)? {
+        let args = get_properties(activation, object, &graphicstrianglepath::properties())?;
+        graphics::draw_triangles(activation, Some(graphics), &args)?;
+    } else if object.has_prototype_in_chain(prototypes.graphics_stroke, false)? {
+        let args = get_properties(activation, object, &graphicsstroke::properties())?;
+        draw_stroke(activation, graphics, &args)?;
+    }
+
+    Ok(())
+}
+
+/// Set the line style of a `Graphics` object from the properties of a
+/// `GraphicsStroke`.
+///
+/// Strokes with a bitmap fill are drawn in their fallback color, as bitmap
+/// lines are not supported yet.
+fn draw_stroke<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    graphics: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<(), Error> {
+    let prototypes = activation.context.avm2.prototypes().clone();
+    let arg = |index: usize| args.get(index).cloned().unwrap_or(Value::Undefined);
+    let fill = match arg(6) {
+        Value::Object(fill) => Some(fill),
+        _ => None,
+    };
+
+    let mut color = 0.into();
+    let mut alpha = 1.0.into();
+    if let Some(fill) = fill {
+        if fill.has_prototype_in_chain(prototypes.graphics_solid_fill, false)? {
+            let fill_args = get_properties(activation, fill, &graphicssolidfill::properties())?;
+            color = fill_args[0].clone();
+            alpha = fill_args[1].clone();
+        }
+    }
+    graphics::line_style(
+        activation,
+        Some(graphics),
+        &[arg(0), color, alpha, arg(1), arg(2), arg(3), arg(4), arg(5)],
+    )?;
+
+    if let Some(fill) = fill {
+        if fill.has_prototype_in_chain(prototypes.graphics_gradient_fill, false)? {
+            let fill_args = get_properties(activation, fill, &graphicsgradientfill::properties())?;
+            graphics::line_gradient_style(activation, Some(graphics), &fill_args)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Construct `IGraphicsData`'s class.
+pub fn create_interface<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "IGraphicsData"),
+        None,
+        Method::from_builtin(bodiless_method),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::INTERFACE);
+
+    class
+}
@@ -0,0 +1,24 @@
+//! `flash.display.IGraphicsStroke` builtin
+
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::display::igraphicsdata::{bodiless_method, class_init};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use gc_arena::{GcCell, MutationContext};
+
+/// Construct `IGraphicsStroke`'s class.
+pub fn create_interface<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "IGraphicsStroke"),
+        None,
+        Method::from_builtin(bodiless_method),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::INTERFACE);
+
+    class
+}
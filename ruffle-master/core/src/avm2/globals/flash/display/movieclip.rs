@@ -14,6 +14,7 @@ use crate::avm2::Error;
 use crate::display_object::{MovieClip, Scene, TDisplayObject};
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use gc_arena::{GcCell, MutationContext};
+use std::convert::TryFrom;
 use std::sync::Arc;
 
 /// Implements `flash.display.MovieClip`'s instance constructor.
@@ -57,6 +58,9 @@ pub fn class_init<'gc>(
 
 /// Implements `addFrameScript`, an undocumented method of `MovieClip` used to
 /// specify what methods of a clip's class run on which frames.
+///
+/// It takes pairs of zero-based frame indices and functions. Each frame has at
+/// most one script: adding another replaces it, and adding `null` removes it.
 pub fn add_frame_script<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -67,10 +71,19 @@ pub fn add_frame_script<'gc>(
         .and_then(|dobj| dobj.as_movie_clip())
     {
         for (frame_id, callable) in args.chunks_exact(2).map(|s| (s[0].clone(), s[1].clone())) {
-            let frame_id = frame_id.coerce_to_u32(activation)? as u16 + 1;
-            let callable = callable.coerce_to_object(activation)?;
-
-            mc.register_frame_script(frame_id, callable, &mut activation.context);
+            let frame_id = frame_id.coerce_to_u32(activation)?;
+            let callable = match callable {
+                Value::Null | Value::Undefined => None,
+                callable => Some(callable.coerce_to_object(activation)?),
+            };
+
+            // Frames past the last one a timeline can have never run.
+            if let Some(frame_id) = u16::try_from(frame_id)
+                .ok()
+                .and_then(|frame_id| frame_id.checked_add(1))
+            {
+                mc.register_frame_script(frame_id, callable, &mut activation.context);
+            }
         }
     } else {
         log::error!("Attempted to add frame scripts to non-MovieClip this!");
@@ -168,13 +181,21 @@ fn labels_for_scene<'gc>(
     let frame_label_proto = activation.context.avm2.prototypes().framelabel;
 
     for (name, frame) in mc.labels_in_range(*scene_start, scene_start + scene_length) {
-        let name: Value<'gc> = AvmString::new(activation.context.gc_context, name).into();
+        // Labels keep their identity, so that listeners can be added to them.
+        if let Some(frame_label) = mc.avm2_frame_label(&name) {
+            frame_labels.push(Some(frame_label.into()));
+            continue;
+        }
+
+        let name_value: Value<'gc> =
+            AvmString::new(activation.context.gc_context, name.clone()).into();
         let local_frame = frame - scene_start + 1;
-        let args = [name, local_frame.into()];
+        let args = [name_value, local_frame.into()];
         let frame_label = frame_label_proto.construct(activation, &args)?;
 
         framelabel::instance_init(activation, Some(frame_label), &args)?;
 
+        mc.set_avm2_frame_label(activation.context.gc_context, name, frame_label);
         frame_labels.push(Some(frame_label.into()));
     }
 
@@ -26,10 +26,13 @@ pub fn instance_init<'gc>(
 
         if this.as_display_object().is_none() {
             let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
-            let library = activation.context.library.library_for_movie_mut(movie);
+            let library = activation
+                .context
+                .library
+                .library_for_movie_mut(movie.clone());
             library.force_avm_type(AvmType::Avm2);
 
-            let new_do = Graphic::new_with_avm2(&mut activation.context, this);
+            let new_do = Graphic::new_with_avm2(&mut activation.context, this, movie);
 
             this.init_display_object(activation.context.gc_context, new_do.into());
         }
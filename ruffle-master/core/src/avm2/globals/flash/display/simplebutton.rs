@@ -0,0 +1,333 @@
+//! `flash.display.SimpleButton` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::{Avm2ButtonStates, Button, DisplayObject, TDisplayObject};
+use crate::tag_utils::SwfMovie;
+use crate::vminterface::AvmType;
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// Implements `flash.display.SimpleButton`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let library = activation
+                .context
+                .library
+                .library_for_movie_mut(movie.clone());
+            library.force_avm_type(AvmType::Avm2);
+
+            let new_do = Button::empty_avm2(movie, this, activation.context.gc_context);
+            this.init_display_object(activation.context.gc_context, new_do.into());
+
+            let states = Avm2ButtonStates {
+                up: state_arg(activation, args, 0)?,
+                over: state_arg(activation, args, 1)?,
+                down: state_arg(activation, args, 2)?,
+                hit_test: state_arg(activation, args, 3)?,
+            };
+            new_do.set_avm2_states(&mut activation.context, states);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.SimpleButton`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Coerce an argument to the display object of a button state.
+///
+/// A missing or null argument leaves the state empty.
+fn state_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<Option<DisplayObject<'gc>>, Error> {
+    match args.get(index).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => Ok(None),
+        state => match state.coerce_to_object(activation)?.as_display_object() {
+            Some(state) => Ok(Some(state)),
+            None => Err("TypeError: Error #1034: Type Coercion failed: cannot convert value to flash.display.DisplayObject.".into()),
+        },
+    }
+}
+
+/// Get the display object of one state of a button, as an AVM2 value.
+fn get_state<'gc>(
+    this: Option<Object<'gc>>,
+    state: fn(&Avm2ButtonStates<'gc>) -> Option<DisplayObject<'gc>>,
+) -> Value<'gc> {
+    if let Some(states) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_button())
+        .and_then(|button| button.avm2_states())
+    {
+        return state(&states)
+            .map(|state| state.object2())
+            .unwrap_or(Value::Null);
+    }
+
+    Value::Undefined
+}
+
+/// Replace the display object of one state of a button.
+fn set_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    state: for<'a> fn(&'a mut Avm2ButtonStates<'gc>) -> &'a mut Option<DisplayObject<'gc>>,
+) -> Result<Value<'gc>, Error> {
+    if let Some(button) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_button())
+    {
+        let mut states = button.avm2_states().unwrap_or_default();
+        *state(&mut states) = state_arg(activation, args, 0)?;
+        button.set_avm2_states(&mut activation.context, states);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `upState`'s getter.
+pub fn up_state<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(get_state(this, |states| states.up))
+}
+
+/// Implements `upState`'s setter.
+pub fn set_up_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    set_state(activation, this, args, |states| &mut states.up)
+}
+
+/// Implements `overState`'s getter.
+pub fn over_state<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(get_state(this, |states| states.over))
+}
+
+/// Implements `overState`'s setter.
+pub fn set_over_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    set_state(activation, this, args, |states| &mut states.over)
+}
+
+/// Implements `downState`'s getter.
+pub fn down_state<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(get_state(this, |states| states.down))
+}
+
+/// Implements `downState`'s setter.
+pub fn set_down_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    set_state(activation, this, args, |states| &mut states.down)
+}
+
+/// Implements `hitTestState`'s getter.
+pub fn hit_test_state<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(get_state(this, |states| states.hit_test))
+}
+
+/// Implements `hitTestState`'s setter.
+pub fn set_hit_test_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    set_state(activation, this, args, |states| &mut states.hit_test)
+}
+
+/// Implements `enabled`'s getter.
+pub fn enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(button) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_button())
+    {
+        return Ok(button.enabled().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `enabled`'s setter.
+pub fn set_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(button) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_button())
+    {
+        let enabled = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        button.set_enabled(&mut activation.context, enabled);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `trackAsMenu`'s getter.
+pub fn track_as_menu<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(button) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_button())
+    {
+        return Ok(button.track_as_menu().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `trackAsMenu`'s setter.
+pub fn set_track_as_menu<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(button) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_button())
+    {
+        let track_as_menu = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        button.set_track_as_menu(&mut activation.context, track_as_menu);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `useHandCursor`'s getter.
+pub fn use_hand_cursor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(button) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_button())
+    {
+        return Ok(button.use_hand_cursor().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `useHandCursor`'s setter.
+pub fn set_use_hand_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(button) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_button())
+    {
+        let use_hand_cursor = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        button.set_use_hand_cursor(&mut activation.context, use_hand_cursor);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `SimpleButton`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "SimpleButton"),
+        Some(QName::new(Namespace::package("flash.display"), "InteractiveObject").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    let accessors: &[(&str, NativeMethod<'gc>, NativeMethod<'gc>)] = &[
+        ("upState", up_state, set_up_state),
+        ("overState", over_state, set_over_state),
+        ("downState", down_state, set_down_state),
+        ("hitTestState", hit_test_state, set_hit_test_state),
+        ("enabled", enabled, set_enabled),
+        ("trackAsMenu", track_as_menu, set_track_as_menu),
+        ("useHandCursor", use_hand_cursor, set_use_hand_cursor),
+    ];
+
+    for (name, getter, setter) in accessors {
+        write.define_instance_trait(Trait::from_getter(
+            QName::new(Namespace::public(), *name),
+            Method::from_builtin(*getter),
+        ));
+        write.define_instance_trait(Trait::from_setter(
+            QName::new(Namespace::public(), *name),
+            Method::from_builtin(*setter),
+        ));
+    }
+
+    class
+}
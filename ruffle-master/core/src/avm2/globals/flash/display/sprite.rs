@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::rectangle::rect;
 use crate::avm2::globals::NS_RUFFLE_INTERNAL;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Namespace, QName};
@@ -9,7 +10,11 @@ use crate::avm2::object::{Object, StageObject, TObject};
 use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::bounding_box::BoundingBox;
+use crate::display_object::TDisplayObject;
+use crate::player::DragObject;
 use gc_arena::{GcCell, MutationContext};
+use swf::Twips;
 
 /// Implements `flash.display.Sprite`'s instance constructor.
 pub fn instance_init<'gc>(
@@ -71,6 +76,206 @@ pub fn graphics<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `startDrag`.
+pub fn start_drag<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(display_object) = this.and_then(|this| this.as_display_object()) {
+        let lock_center = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+
+        let offset = if lock_center {
+            // The object's origin point is locked to the mouse.
+            Default::default()
+        } else {
+            // The object moves relative to current mouse position.
+            // Calculate the offset from the mouse to the object in world space.
+            let obj_pos = display_object.local_to_global(Default::default());
+            (
+                obj_pos.0 - activation.context.mouse_position.0,
+                obj_pos.1 - activation.context.mouse_position.1,
+            )
+        };
+
+        let constraint = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => Default::default(),
+            bounds => {
+                let bounds = bounds.coerce_to_object(activation)?;
+                let (x, y, width, height) = rect(bounds, activation)?;
+
+                // A negative size extends the bounds up or left instead.
+                let (x_min, x_max) = if width < 0.0 {
+                    (x + width, x)
+                } else {
+                    (x, x + width)
+                };
+                let (y_min, y_max) = if height < 0.0 {
+                    (y + height, y)
+                } else {
+                    (y, y + height)
+                };
+
+                BoundingBox {
+                    valid: true,
+                    x_min: Twips::from_pixels(x_min),
+                    y_min: Twips::from_pixels(y_min),
+                    x_max: Twips::from_pixels(x_max),
+                    y_max: Twips::from_pixels(y_max),
+                }
+            }
+        };
+
+        *activation.context.drag_object = Some(DragObject {
+            display_object,
+            offset,
+            constraint,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stopDrag`.
+pub fn stop_drag<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    *activation.context.drag_object = None;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `buttonMode`'s getter.
+pub fn button_mode<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        return Ok(mc.button_mode().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `buttonMode`'s setter.
+pub fn set_button_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        let button_mode = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        mc.set_button_mode(&mut activation.context, button_mode);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `useHandCursor`'s getter.
+pub fn use_hand_cursor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        return Ok(mc.use_hand_cursor().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `useHandCursor`'s setter.
+pub fn set_use_hand_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        let use_hand_cursor = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        mc.set_use_hand_cursor(&mut activation.context, use_hand_cursor);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `hitArea`'s getter.
+pub fn hit_area<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        return Ok(mc
+            .hit_area()
+            .map(|hit_area| hit_area.object2())
+            .unwrap_or(Value::Null));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `hitArea`'s setter.
+pub fn set_hit_area<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mc) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_movie_clip())
+    {
+        let hit_area = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            hit_area => {
+                let hit_area = hit_area
+                    .coerce_to_object(activation)?
+                    .as_display_object()
+                    .filter(|dobj| dobj.as_movie_clip().is_some())
+                    .ok_or(
+                        "TypeError: Error #1034: Type Coercion failed: cannot convert to Sprite.",
+                    )?;
+                Some(hit_area)
+            }
+        };
+
+        mc.set_hit_area(&mut activation.context, hit_area);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Sprite`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
@@ -96,6 +301,40 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         Method::from_builtin(graphics),
     ));
 
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "startDrag"),
+        Method::from_builtin(start_drag),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "stopDrag"),
+        Method::from_builtin(stop_drag),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "buttonMode"),
+        Method::from_builtin(button_mode),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "buttonMode"),
+        Method::from_builtin(set_button_mode),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "useHandCursor"),
+        Method::from_builtin(use_hand_cursor),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "useHandCursor"),
+        Method::from_builtin(set_use_hand_cursor),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "hitArea"),
+        Method::from_builtin(hit_area),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "hitArea"),
+        Method::from_builtin(set_hit_area),
+    ));
+
     write.define_instance_trait(Trait::from_slot(
         QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "graphics"),
         QName::new(Namespace::package("flash.display"), "Graphics").into(),
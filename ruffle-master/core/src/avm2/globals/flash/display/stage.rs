@@ -0,0 +1,437 @@
+//! `flash.display.Stage` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::events::stageorientationevent::dispatch_stage_orientation_event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::StageOrientation;
+use crate::config::{StageAlign, StageQuality, StageScaleMode};
+use gc_arena::{GcCell, MutationContext};
+use swf::Color;
+
+/// Implements `flash.display.Stage`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Stage`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Get the `Stage` object that every display object on the stage shares,
+/// creating it the first time it is asked for.
+pub fn stage_object<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<Object<'gc>, Error> {
+    if let Some(stage) = activation.context.avm2.stage_object() {
+        return Ok(stage);
+    }
+
+    let proto = activation.context.avm2.prototypes().stage;
+    let stage = proto.construct(activation, &[])?;
+    instance_init(activation, Some(stage), &[])?;
+    activation.context.avm2.set_stage_object(stage);
+
+    Ok(stage)
+}
+
+/// Implements `Stage.color`'s getter.
+pub fn color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let color = activation
+        .context
+        .background_color
+        .as_ref()
+        .map(|color| color.to_rgb())
+        .unwrap_or(0xFFFFFF);
+
+    Ok(color.into())
+}
+
+/// Implements `Stage.color`'s setter.
+pub fn set_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let color = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    *activation.context.background_color = Some(Color::from_rgb(color, 255));
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Stage.frameRate`'s getter.
+pub fn frame_rate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok((*activation.context.frame_rate).into())
+}
+
+/// Implements `Stage.frameRate`'s setter.
+///
+/// The player runs its frames at the new rate from the next tick on.
+pub fn set_frame_rate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let frame_rate = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?;
+
+    // Flash Player ignores rates that aren't numbers, and clamps the rest to
+    // the rates it can run at.
+    if !frame_rate.is_nan() {
+        let frame_rate = frame_rate.max(0.01).min(1000.0);
+        *activation.context.frame_rate = frame_rate;
+        activation.context.audio.set_frame_rate(frame_rate);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Stage.quality`'s getter.
+pub fn quality<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.quality.as_str().into())
+}
+
+/// Implements `Stage.quality`'s setter.
+pub fn set_quality<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    match StageQuality::from_name(&name) {
+        Some(quality) => *activation.context.quality = quality,
+        None => return Err(format!("Invalid stage quality: {}", name).into()),
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Stage.scaleMode`'s getter.
+pub fn scale_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.stage_scale_mode.as_str().into())
+}
+
+/// Implements `Stage.scaleMode`'s setter.
+///
+/// The player lays the stage out again once the script is done running.
+pub fn set_scale_mode<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    match StageScaleMode::from_name(&name) {
+        Some(scale_mode) => *activation.context.stage_scale_mode = scale_mode,
+        None => return Err(format!("Invalid stage scale mode: {}", name).into()),
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Stage.align`'s getter.
+pub fn align<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = activation.context.stage_align.to_name();
+
+    Ok(AvmString::new(activation.context.gc_context, name).into())
+}
+
+/// Implements `Stage.align`'s setter.
+pub fn set_align<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    *activation.context.stage_align = StageAlign::from_name(&name);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Stage.stageWidth`'s getter.
+pub fn stage_width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.stage_size.0.to_pixels().into())
+}
+
+/// Implements `Stage.stageHeight`'s getter.
+pub fn stage_height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.stage_size.1.to_pixels().into())
+}
+
+/// Implements `Stage.displayState`'s getter.
+pub fn display_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if activation.context.ui.is_fullscreen() {
+        Ok("fullScreen".into())
+    } else {
+        Ok("normal".into())
+    }
+}
+
+/// Implements `Stage.displayState`'s setter.
+///
+/// Entering fullscreen is up to the UI, which may refuse it.
+pub fn set_display_state<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    let is_full = match name.to_ascii_lowercase().as_str() {
+        "normal" => false,
+        "fullscreen" | "fullscreeninteractive" => true,
+        _ => return Err(format!("Invalid stage display state: {}", name).into()),
+    };
+
+    if is_full != activation.context.ui.is_fullscreen()
+        && !activation.context.ui.set_fullscreen(is_full)
+    {
+        return Err("SecurityError: Fullscreen is not allowed".into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Stage.orientation` and `Stage.deviceOrientation`.
+pub fn orientation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.orientation.as_str().into())
+}
+
+/// Implements `Stage.supportsOrientationChange`.
+pub fn supports_orientation_change<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.ui.supports_orientation_change().into())
+}
+
+/// Implements `Stage.setOrientation`.
+///
+/// If the UI rotates the stage, an `orientationChange` event is dispatched
+/// to it straight away.
+pub fn set_orientation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let after = match StageOrientation::from_name(&name) {
+        Some(StageOrientation::Unknown) | None => {
+            return Err(format!("Invalid stage orientation: {}", name).into())
+        }
+        Some(orientation) => orientation,
+    };
+
+    let before = *activation.context.orientation;
+    if after != before && activation.context.ui.set_orientation(after) {
+        *activation.context.orientation = after;
+
+        if let Some(this) = this {
+            dispatch_stage_orientation_event(activation, this, "orientationChange", before, after)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Stage.invalidate`.
+///
+/// Display objects listening for `render` will hear it at the end of the
+/// current frame, before the stage is drawn.
+pub fn invalidate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    activation.context.avm2.invalidate_stage();
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Stage`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Stage"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "color"),
+        Method::from_builtin(color),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "color"),
+        Method::from_builtin(set_color),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "frameRate"),
+        Method::from_builtin(frame_rate),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "frameRate"),
+        Method::from_builtin(set_frame_rate),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "quality"),
+        Method::from_builtin(quality),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "quality"),
+        Method::from_builtin(set_quality),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "scaleMode"),
+        Method::from_builtin(scale_mode),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "scaleMode"),
+        Method::from_builtin(set_scale_mode),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "align"),
+        Method::from_builtin(align),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "align"),
+        Method::from_builtin(set_align),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageWidth"),
+        Method::from_builtin(stage_width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageHeight"),
+        Method::from_builtin(stage_height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "displayState"),
+        Method::from_builtin(display_state),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "displayState"),
+        Method::from_builtin(set_display_state),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "orientation"),
+        Method::from_builtin(orientation),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "deviceOrientation"),
+        Method::from_builtin(orientation),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setOrientation"),
+        Method::from_builtin(set_orientation),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "invalidate"),
+        Method::from_builtin(invalidate),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "supportsOrientationChange"),
+        Method::from_builtin(supports_orientation_change),
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "fullScreenSourceRect"),
+        Multiname::from(QName::new(Namespace::package("flash.geom"), "Rectangle")),
+        None,
+    ));
+
+    class
+}
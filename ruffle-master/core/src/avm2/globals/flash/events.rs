@@ -1,5 +1,17 @@
 //! `flash.events` namespace
 
+pub mod contextmenuevent;
+pub mod dataevent;
+pub mod errorevent;
 pub mod event;
 pub mod eventdispatcher;
+pub mod gameinputevent;
 pub mod ieventdispatcher;
+pub mod ioerrorevent;
+pub mod keyboardevent;
+pub mod mouseevent;
+pub mod progressevent;
+pub mod sampledataevent;
+pub mod stageorientationevent;
+pub mod statusevent;
+pub mod textevent;
@@ -0,0 +1,125 @@
+//! `flash.events.ContextMenuEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::dispatch_event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.ContextMenuEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, args.get(0..3).unwrap_or(args))?;
+
+        let mouse_target = args.get(3).cloned().unwrap_or(Value::Null);
+        let context_menu_owner = args.get(4).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "mouseTarget"),
+            mouse_target,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "contextMenuOwner"),
+            context_menu_owner,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.ContextMenuEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Dispatch a `ContextMenuEvent` of the given type to a menu or menu item.
+///
+/// `mouse_target` is the object that was right-clicked, and
+/// `context_menu_owner` is the object whose `contextMenu` is being shown,
+/// which is either the mouse target or one of its ancestors.
+pub fn dispatch_context_menu_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+    mouse_target: Object<'gc>,
+    context_menu_owner: Object<'gc>,
+) -> Result<(), Error> {
+    let event_proto = activation.context.avm2.prototypes().context_menu_event;
+    let event = event_proto.construct(activation, &[])?;
+    instance_init(
+        activation,
+        Some(event),
+        &[
+            event_type.into(),
+            false.into(),
+            false.into(),
+            mouse_target.into(),
+            context_menu_owner.into(),
+        ],
+    )?;
+
+    dispatch_event(activation, target, event)?;
+
+    Ok(())
+}
+
+/// Construct `ContextMenuEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "ContextMenuEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("MENU_ITEM_SELECT", "menuItemSelect"),
+        ("MENU_SELECT", "menuSelect"),
+    ];
+
+    for (name, value) in CONSTANTS {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "String").into(),
+            Some((*value).into()),
+        ));
+    }
+
+    const SLOTS: &[(&str, &str, &str)] = &[
+        ("mouseTarget", "flash.display", "InteractiveObject"),
+        ("contextMenuOwner", "flash.display", "InteractiveObject"),
+        ("isMouseTargetInaccessible", "", "Boolean"),
+    ];
+
+    for (name, type_package, type_name) in SLOTS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::package(*type_package), *type_name)),
+            None,
+        ));
+    }
+
+    class
+}
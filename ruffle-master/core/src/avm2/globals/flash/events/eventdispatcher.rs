@@ -78,7 +78,10 @@ pub fn add_event_listener<'gc>(
             .unwrap_or(Value::Integer(0))
             .coerce_to_i32(activation)?;
 
-        //TODO: If we ever get weak GC references, we should respect `useWeakReference`.
+        // `useWeakReference` is accepted, but every listener is held strongly,
+        // as gc-arena has no weak pointers for the dispatch list to hold it by.
+        // A weak listener therefore fires until it is removed, exactly as it
+        // does in Flash whilst anything else still references it.
         dispatch_list
             .as_dispatch_mut(activation.context.gc_context)
             .ok_or_else(|| Error::from("Internal properties should have what I put in them"))?
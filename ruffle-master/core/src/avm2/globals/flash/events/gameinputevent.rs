@@ -0,0 +1,103 @@
+//! `flash.events.GameInputEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::dispatch_event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.GameInputEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, args.get(0..3).unwrap_or(args))?;
+
+        let device = args.get(3).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "device"),
+            device,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.GameInputEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Dispatch a `GameInputEvent` about `device` to a target.
+pub fn dispatch_game_input_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+    device: Object<'gc>,
+) -> Result<(), Error> {
+    let event_proto = activation.context.avm2.prototypes().game_input_event;
+    let event = event_proto.construct(activation, &[])?;
+    instance_init(
+        activation,
+        Some(event),
+        &[event_type.into(), false.into(), false.into(), device.into()],
+    )?;
+
+    dispatch_event(activation, target, event)?;
+
+    Ok(())
+}
+
+/// Construct `GameInputEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "GameInputEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("DEVICE_ADDED", "deviceAdded"),
+        ("DEVICE_REMOVED", "deviceRemoved"),
+        ("DEVICE_UNUSABLE", "deviceUnusable"),
+    ];
+
+    for (name, value) in CONSTANTS {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "String").into(),
+            Some((*value).into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "device"),
+        Multiname::from(QName::new(
+            Namespace::package("flash.ui"),
+            "GameInputDevice",
+        )),
+        None,
+    ));
+
+    class
+}
@@ -0,0 +1,191 @@
+//! `flash.events.KeyboardEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::dispatch_event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::events::KeyCode;
+use gc_arena::{GcCell, MutationContext};
+
+/// The `KeyLocation` of keys on the numeric keypad.
+const KEY_LOCATION_NUM_PAD: u32 = 3;
+
+/// Implements `flash.events.KeyboardEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        // Like mouse events, keyboard events bubble unless told otherwise.
+        let event_type = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let bubbles = args.get(1).cloned().unwrap_or_else(|| true.into());
+        let cancelable = args.get(2).cloned().unwrap_or_else(|| false.into());
+        activation.super_init(this, &[event_type, bubbles, cancelable])?;
+
+        for (index, name) in ["charCode", "keyCode", "keyLocation"].iter().enumerate() {
+            let value = args
+                .get(3 + index)
+                .cloned()
+                .unwrap_or_else(|| 0.into())
+                .coerce_to_u32(activation)?;
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+
+        for (index, name) in ["ctrlKey", "altKey", "shiftKey"].iter().enumerate() {
+            let value = args
+                .get(6 + index)
+                .cloned()
+                .unwrap_or_else(|| false.into())
+                .coerce_to_boolean();
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.KeyboardEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `updateAfterEvent`.
+///
+/// We always render at the end of the frame, so there is nothing to do here.
+pub fn update_after_event<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Dispatch a `KeyboardEvent` of the given type to a target.
+///
+/// `char_code` is the character the key typed, or zero if it didn't type
+/// one. The modifier keys are read from the keyboard state shared with AVM1.
+pub fn dispatch_keyboard_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+    key_code: KeyCode,
+    char_code: u32,
+) -> Result<(), Error> {
+    let key_location = match key_code {
+        KeyCode::Numpad0
+        | KeyCode::Numpad1
+        | KeyCode::Numpad2
+        | KeyCode::Numpad3
+        | KeyCode::Numpad4
+        | KeyCode::Numpad5
+        | KeyCode::Numpad6
+        | KeyCode::Numpad7
+        | KeyCode::Numpad8
+        | KeyCode::Numpad9
+        | KeyCode::Multiply
+        | KeyCode::Plus
+        | KeyCode::NumpadMinus
+        | KeyCode::NumpadPeriod
+        | KeyCode::NumpadSlash => KEY_LOCATION_NUM_PAD,
+        _ => 0,
+    };
+
+    let keyboard = activation.context.keyboard;
+    let ctrl_key = keyboard.is_key_down(KeyCode::Control);
+    let alt_key = keyboard.is_key_down(KeyCode::Alt);
+    let shift_key = keyboard.is_key_down(KeyCode::Shift);
+
+    let event_proto = activation.context.avm2.prototypes().keyboard_event;
+    let event = event_proto.construct(activation, &[])?;
+    instance_init(
+        activation,
+        Some(event),
+        &[
+            event_type.into(),
+            true.into(),
+            false.into(),
+            char_code.into(),
+            u32::from(u8::from(key_code)).into(),
+            key_location.into(),
+            ctrl_key.into(),
+            alt_key.into(),
+            shift_key.into(),
+        ],
+    )?;
+
+    dispatch_event(activation, target, event)?;
+
+    Ok(())
+}
+
+/// Construct `KeyboardEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "KeyboardEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "KEY_DOWN"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("keyDown".into()),
+    ));
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "KEY_UP"),
+        QName::new(Namespace::public(), "String").into(),
+        Some("keyUp".into()),
+    ));
+
+    const SLOTS: &[(&str, &str)] = &[
+        ("charCode", "uint"),
+        ("keyCode", "uint"),
+        ("keyLocation", "uint"),
+        ("ctrlKey", "Boolean"),
+        ("altKey", "Boolean"),
+        ("shiftKey", "Boolean"),
+    ];
+
+    for (name, type_name) in SLOTS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::public(), *type_name)),
+            None,
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "updateAfterEvent"),
+        Method::from_builtin(update_after_event),
+    ));
+
+    class
+}
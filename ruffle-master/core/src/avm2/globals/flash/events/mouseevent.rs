@@ -0,0 +1,291 @@
+//! `flash.events.MouseEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::dispatch_event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
+use crate::events::KeyCode;
+use gc_arena::{GcCell, MutationContext};
+use swf::Twips;
+
+/// Implements `flash.events.MouseEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        // Unlike most events, mouse events bubble unless told otherwise.
+        let event_type = args.get(0).cloned().unwrap_or(Value::Undefined);
+        let bubbles = args.get(1).cloned().unwrap_or_else(|| true.into());
+        let cancelable = args.get(2).cloned().unwrap_or_else(|| false.into());
+        activation.super_init(this, &[event_type, bubbles, cancelable])?;
+
+        let local_x = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| f64::NAN.into())
+            .coerce_to_number(activation)?;
+        let local_y = args
+            .get(4)
+            .cloned()
+            .unwrap_or_else(|| f64::NAN.into())
+            .coerce_to_number(activation)?;
+        let related_object = args.get(5).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "localX"),
+            local_x.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "localY"),
+            local_y.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "relatedObject"),
+            related_object,
+            activation,
+        )?;
+
+        for (index, name) in ["ctrlKey", "altKey", "shiftKey", "buttonDown"]
+            .iter()
+            .enumerate()
+        {
+            let value = args
+                .get(6 + index)
+                .cloned()
+                .unwrap_or_else(|| false.into())
+                .coerce_to_boolean();
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+
+        let delta = args
+            .get(10)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "delta"),
+            delta.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.MouseEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Convert the event's local position into stage coordinates, using the
+/// display object it was dispatched to.
+fn stage_position<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<(f64, f64), Error> {
+    let local_x = this
+        .get_property(this, &QName::new(Namespace::public(), "localX"), activation)?
+        .coerce_to_number(activation)?;
+    let local_y = this
+        .get_property(this, &QName::new(Namespace::public(), "localY"), activation)?
+        .coerce_to_number(activation)?;
+
+    let target = this
+        .as_event()
+        .and_then(|event| event.target())
+        .and_then(|target| target.as_display_object());
+
+    if let Some(target) = target {
+        let (stage_x, stage_y) =
+            target.local_to_global((Twips::from_pixels(local_x), Twips::from_pixels(local_y)));
+
+        return Ok((stage_x.to_pixels(), stage_y.to_pixels()));
+    }
+
+    Ok((local_x, local_y))
+}
+
+/// Implements `stageX`.
+pub fn stage_x<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(stage_position(activation, this)?.0.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `stageY`.
+pub fn stage_y<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(stage_position(activation, this)?.1.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `updateAfterEvent`.
+///
+/// We always render at the end of the frame, so there is nothing to do here.
+pub fn update_after_event<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Dispatch a `MouseEvent` of the given type to a target, at the current
+/// mouse position.
+///
+/// `related` is the object the mouse moved from or to, for the `mouseOver`,
+/// `mouseOut`, `rollOver` and `rollOut` events.
+pub fn dispatch_mouse_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+    related: Option<Object<'gc>>,
+    button_down: bool,
+    delta: i32,
+) -> Result<(), Error> {
+    let mouse_position = *activation.context.mouse_position;
+    let (local_x, local_y) = match target.as_display_object() {
+        Some(dobj) => dobj.global_to_local(mouse_position),
+        None => mouse_position,
+    };
+
+    // `rollOver` and `rollOut` are sent to each object the mouse entered or
+    // left, so they don't need to bubble.
+    let bubbles = !matches!(event_type, "rollOver" | "rollOut");
+
+    let keyboard = activation.context.keyboard;
+    let ctrl_key = keyboard.is_key_down(KeyCode::Control);
+    let alt_key = keyboard.is_key_down(KeyCode::Alt);
+    let shift_key = keyboard.is_key_down(KeyCode::Shift);
+
+    let event_proto = activation.context.avm2.prototypes().mouse_event;
+    let event = event_proto.construct(activation, &[])?;
+    instance_init(
+        activation,
+        Some(event),
+        &[
+            event_type.into(),
+            bubbles.into(),
+            false.into(),
+            local_x.to_pixels().into(),
+            local_y.to_pixels().into(),
+            related.map(Value::from).unwrap_or(Value::Null),
+            ctrl_key.into(),
+            alt_key.into(),
+            shift_key.into(),
+            button_down.into(),
+            delta.into(),
+        ],
+    )?;
+
+    dispatch_event(activation, target, event)?;
+
+    Ok(())
+}
+
+/// Construct `MouseEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "MouseEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("CLICK", "click"),
+        ("DOUBLE_CLICK", "doubleClick"),
+        ("MOUSE_DOWN", "mouseDown"),
+        ("MOUSE_MOVE", "mouseMove"),
+        ("MOUSE_OUT", "mouseOut"),
+        ("MOUSE_OVER", "mouseOver"),
+        ("MOUSE_UP", "mouseUp"),
+        ("MOUSE_WHEEL", "mouseWheel"),
+        ("ROLL_OUT", "rollOut"),
+        ("ROLL_OVER", "rollOver"),
+    ];
+
+    for (name, value) in CONSTANTS {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "String").into(),
+            Some((*value).into()),
+        ));
+    }
+
+    const SLOTS: &[(&str, &str, &str)] = &[
+        ("localX", "", "Number"),
+        ("localY", "", "Number"),
+        ("relatedObject", "flash.display", "InteractiveObject"),
+        ("ctrlKey", "", "Boolean"),
+        ("altKey", "", "Boolean"),
+        ("shiftKey", "", "Boolean"),
+        ("buttonDown", "", "Boolean"),
+        ("delta", "", "int"),
+    ];
+
+    for (name, type_package, type_name) in SLOTS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::package(*type_package), *type_name)),
+            None,
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageX"),
+        Method::from_builtin(stage_x),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "stageY"),
+        Method::from_builtin(stage_y),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "updateAfterEvent"),
+        Method::from_builtin(update_after_event),
+    ));
+
+    class
+}
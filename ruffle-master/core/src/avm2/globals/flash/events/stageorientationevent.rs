@@ -0,0 +1,117 @@
+//! `flash.events.StageOrientationEvent` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::dispatch_event;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::StageOrientation;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.events.StageOrientationEvent`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, args.get(0..3).unwrap_or(args))?;
+
+        let before_orientation = args.get(3).cloned().unwrap_or(Value::Null);
+        let after_orientation = args.get(4).cloned().unwrap_or(Value::Null);
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "beforeOrientation"),
+            before_orientation,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "afterOrientation"),
+            after_orientation,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.events.StageOrientationEvent`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Dispatch a `StageOrientationEvent` of the given type to the stage.
+///
+/// `orientationChanging` events can be cancelled to keep the stage from
+/// rotating; the returned `bool` reads true if that happened.
+pub fn dispatch_stage_orientation_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+    before_orientation: StageOrientation,
+    after_orientation: StageOrientation,
+) -> Result<bool, Error> {
+    let event_proto = activation.context.avm2.prototypes().stage_orientation_event;
+    let event = event_proto.construct(activation, &[])?;
+    instance_init(
+        activation,
+        Some(event),
+        &[
+            event_type.into(),
+            false.into(),
+            (event_type == "orientationChanging").into(),
+            before_orientation.as_str().into(),
+            after_orientation.as_str().into(),
+        ],
+    )?;
+
+    dispatch_event(activation, target, event)
+}
+
+/// Construct `StageOrientationEvent`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.events"), "StageOrientationEvent"),
+        Some(QName::new(Namespace::package("flash.events"), "Event").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("ORIENTATION_CHANGE", "orientationChange"),
+        ("ORIENTATION_CHANGING", "orientationChanging"),
+    ];
+
+    for (name, value) in CONSTANTS {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "String").into(),
+            Some((*value).into()),
+        ));
+    }
+
+    for name in &["beforeOrientation", "afterOrientation"] {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::public(), "String")),
+            None,
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,9 @@
+//! `flash.filters` namespace
+
+pub mod bevelfilter;
+pub mod bitmapfilter;
+pub mod bitmapfilterquality;
+pub mod bitmapfiltertype;
+pub mod blurfilter;
+pub mod dropshadowfilter;
+pub mod glowfilter;
@@ -0,0 +1,254 @@
+//! `flash.filters.BitmapFilter` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::filters::{bevelfilter, blurfilter, dropshadowfilter, glowfilter};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use swf::Filter;
+
+/// Implements `flash.filters.BitmapFilter`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.filters.BitmapFilter`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read a property off of a filter.
+fn get<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    this.get_property(this, &QName::new(Namespace::public(), name), activation)
+}
+
+/// Read a numeric property off of a filter.
+fn get_number<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    name: &'static str,
+) -> Result<f64, Error> {
+    get(activation, this, name)?.coerce_to_number(activation)
+}
+
+/// Read a color and its alpha off of a filter.
+fn get_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    color: &'static str,
+    alpha: &'static str,
+) -> Result<swf::Color, Error> {
+    let rgb = get(activation, this, color)?.coerce_to_u32(activation)?;
+    let alpha = get_number(activation, this, alpha)?;
+    Ok(swf::Color::from_rgb(
+        rgb,
+        (alpha.clamp(0.0, 1.0) * 255.0).round() as u8,
+    ))
+}
+
+/// Read the quality of a filter, as a number of blur passes.
+fn get_passes<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<u8, Error> {
+    Ok(get(activation, this, "quality")?
+        .coerce_to_i32(activation)?
+        .clamp(0, 15) as u8)
+}
+
+fn color_to_rgb(color: &swf::Color) -> u32 {
+    (u32::from(color.r) << 16) | (u32::from(color.g) << 8) | u32::from(color.b)
+}
+
+fn color_to_alpha(color: &swf::Color) -> f64 {
+    f64::from(color.a) / 255.0
+}
+
+/// Construct a new filter object from a filter applied to a display object.
+///
+/// Filters that have no class yet are returned as `None`.
+pub fn filter_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    filter: &Filter,
+) -> Result<Option<Object<'gc>>, Error> {
+    let prototypes = activation.context.avm2.prototypes();
+    let (proto, instance_init, args): (_, NativeMethod<'gc>, Vec<Value<'gc>>) = match filter {
+        Filter::BlurFilter(filter) => (
+            prototypes.blur_filter,
+            blurfilter::instance_init,
+            vec![
+                filter.blur_x.into(),
+                filter.blur_y.into(),
+                i32::from(filter.num_passes).into(),
+            ],
+        ),
+        Filter::GlowFilter(filter) => (
+            prototypes.glow_filter,
+            glowfilter::instance_init,
+            vec![
+                color_to_rgb(&filter.color).into(),
+                color_to_alpha(&filter.color).into(),
+                filter.blur_x.into(),
+                filter.blur_y.into(),
+                f64::from(filter.strength).into(),
+                i32::from(filter.num_passes).into(),
+                filter.is_inner.into(),
+                filter.is_knockout.into(),
+            ],
+        ),
+        Filter::DropShadowFilter(filter) => (
+            prototypes.drop_shadow_filter,
+            dropshadowfilter::instance_init,
+            vec![
+                filter.distance.into(),
+                filter.angle.to_degrees().into(),
+                color_to_rgb(&filter.color).into(),
+                color_to_alpha(&filter.color).into(),
+                filter.blur_x.into(),
+                filter.blur_y.into(),
+                f64::from(filter.strength).into(),
+                i32::from(filter.num_passes).into(),
+                filter.is_inner.into(),
+                filter.is_knockout.into(),
+            ],
+        ),
+        Filter::BevelFilter(filter) => {
+            let type_ = if filter.is_on_top {
+                "full"
+            } else if filter.is_inner {
+                "inner"
+            } else {
+                "outer"
+            };
+            (
+                prototypes.bevel_filter,
+                bevelfilter::instance_init,
+                vec![
+                    filter.distance.into(),
+                    filter.angle.to_degrees().into(),
+                    color_to_rgb(&filter.highlight_color).into(),
+                    color_to_alpha(&filter.highlight_color).into(),
+                    color_to_rgb(&filter.shadow_color).into(),
+                    color_to_alpha(&filter.shadow_color).into(),
+                    filter.blur_x.into(),
+                    filter.blur_y.into(),
+                    f64::from(filter.strength).into(),
+                    i32::from(filter.num_passes).into(),
+                    type_.into(),
+                    filter.is_knockout.into(),
+                ],
+            )
+        }
+        _ => return Ok(None),
+    };
+
+    let object = proto.construct(activation, &[])?;
+    instance_init(activation, Some(object), &args)?;
+
+    Ok(Some(object))
+}
+
+/// Read the filter described by a filter object, so that it can be applied
+/// to a display object.
+///
+/// Objects that are not filters, or filters that can't be rendered yet, are
+/// returned as `None`.
+pub fn object_to_filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<Option<Filter>, Error> {
+    let prototypes = activation.context.avm2.prototypes().clone();
+
+    if object.has_prototype_in_chain(prototypes.blur_filter, false)? {
+        return Ok(Some(Filter::BlurFilter(Box::new(swf::BlurFilter {
+            blur_x: get_number(activation, object, "blurX")?,
+            blur_y: get_number(activation, object, "blurY")?,
+            num_passes: get_passes(activation, object)?,
+        }))));
+    }
+
+    if object.has_prototype_in_chain(prototypes.glow_filter, false)? {
+        return Ok(Some(Filter::GlowFilter(Box::new(swf::GlowFilter {
+            color: get_color(activation, object, "color", "alpha")?,
+            blur_x: get_number(activation, object, "blurX")?,
+            blur_y: get_number(activation, object, "blurY")?,
+            strength: get_number(activation, object, "strength")? as f32,
+            is_inner: get(activation, object, "inner")?.coerce_to_boolean(),
+            is_knockout: get(activation, object, "knockout")?.coerce_to_boolean(),
+            num_passes: get_passes(activation, object)?,
+        }))));
+    }
+
+    // `hideObject` can't be represented by a SWF filter, and is ignored.
+    if object.has_prototype_in_chain(prototypes.drop_shadow_filter, false)? {
+        return Ok(Some(Filter::DropShadowFilter(Box::new(
+            swf::DropShadowFilter {
+                color: get_color(activation, object, "color", "alpha")?,
+                blur_x: get_number(activation, object, "blurX")?,
+                blur_y: get_number(activation, object, "blurY")?,
+                angle: get_number(activation, object, "angle")?.to_radians(),
+                distance: get_number(activation, object, "distance")?,
+                strength: get_number(activation, object, "strength")? as f32,
+                is_inner: get(activation, object, "inner")?.coerce_to_boolean(),
+                is_knockout: get(activation, object, "knockout")?.coerce_to_boolean(),
+                num_passes: get_passes(activation, object)?,
+            },
+        ))));
+    }
+
+    if object.has_prototype_in_chain(prototypes.bevel_filter, false)? {
+        let type_ = get(activation, object, "type")?.coerce_to_string(activation)?;
+        return Ok(Some(Filter::BevelFilter(Box::new(swf::BevelFilter {
+            shadow_color: get_color(activation, object, "shadowColor", "shadowAlpha")?,
+            highlight_color: get_color(activation, object, "highlightColor", "highlightAlpha")?,
+            blur_x: get_number(activation, object, "blurX")?,
+            blur_y: get_number(activation, object, "blurY")?,
+            angle: get_number(activation, object, "angle")?.to_radians(),
+            distance: get_number(activation, object, "distance")?,
+            strength: get_number(activation, object, "strength")? as f32,
+            is_inner: &*type_ == "inner",
+            is_knockout: get(activation, object, "knockout")?.coerce_to_boolean(),
+            is_on_top: &*type_ == "full",
+            num_passes: get_passes(activation, object)?,
+        }))));
+    }
+
+    Ok(None)
+}
+
+/// Construct `BitmapFilter`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.filters"), "BitmapFilter"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    class
+}
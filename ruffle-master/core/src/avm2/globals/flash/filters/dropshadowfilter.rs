@@ -0,0 +1,71 @@
+//! `flash.filters.DropShadowFilter` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::{define_properties, init_properties, SlotProperty};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// `DropShadowFilter`'s properties, in the order of its constructor's parameters,
+/// with their default values.
+fn properties<'gc>() -> [SlotProperty<'gc>; 11] {
+    [
+        ("distance", "Number", 4.0.into()),
+        ("angle", "Number", 45.0.into()),
+        ("color", "uint", 0_u32.into()),
+        ("alpha", "Number", 1.0.into()),
+        ("blurX", "Number", 4.0.into()),
+        ("blurY", "Number", 4.0.into()),
+        ("strength", "Number", 1.0.into()),
+        ("quality", "int", 1.into()),
+        ("inner", "Boolean", false.into()),
+        ("knockout", "Boolean", false.into()),
+        ("hideObject", "Boolean", false.into()),
+    ]
+}
+
+/// Implements `flash.filters.DropShadowFilter`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+        init_properties(activation, this, args, &properties())?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.filters.DropShadowFilter`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `DropShadowFilter`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.filters"), "DropShadowFilter"),
+        Some(QName::new(Namespace::package("flash.filters"), "BitmapFilter").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    define_properties(&mut write, &properties());
+
+    class
+}
@@ -0,0 +1,288 @@
+//! `flash.geom.ColorTransform` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::color_transform::ColorTransform;
+use gc_arena::{GcCell, MutationContext};
+
+/// The properties of a `ColorTransform`, in constructor order, along with
+/// their default values.
+const PROPERTIES: [(&str, f64); 8] = [
+    ("redMultiplier", 1.0),
+    ("greenMultiplier", 1.0),
+    ("blueMultiplier", 1.0),
+    ("alphaMultiplier", 1.0),
+    ("redOffset", 0.0),
+    ("greenOffset", 0.0),
+    ("blueOffset", 0.0),
+    ("alphaOffset", 0.0),
+];
+
+/// Implements `flash.geom.ColorTransform`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let mut values = [0.0; 8];
+        for (index, (value, (_, default))) in values.iter_mut().zip(&PROPERTIES).enumerate() {
+            *value = match args.get(index) {
+                Some(arg) => arg.coerce_to_number(activation)?,
+                None => *default,
+            };
+        }
+        set_values(this, activation, values)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.ColorTransform`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read the multipliers and offsets of a `ColorTransform` object, in
+/// constructor order.
+fn values<'gc>(
+    mut this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<[f64; 8], Error> {
+    let mut values = [0.0; 8];
+    for (value, (name, _)) in values.iter_mut().zip(&PROPERTIES) {
+        *value = this
+            .get_property(this, &QName::new(Namespace::public(), *name), activation)?
+            .coerce_to_number(activation)?;
+    }
+
+    Ok(values)
+}
+
+/// Overwrite the multipliers and offsets of a `ColorTransform` object.
+fn set_values<'gc>(
+    mut this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    values: [f64; 8],
+) -> Result<(), Error> {
+    for (value, (name, _)) in values.iter().zip(&PROPERTIES) {
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), *name),
+            (*value).into(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Convert a `ColorTransform` object into a display object color transform.
+pub fn object_to_color_transform<'gc>(
+    object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<ColorTransform, Error> {
+    let [r_mult, g_mult, b_mult, a_mult, r_add, g_add, b_add, a_add] = values(object, activation)?;
+
+    Ok(ColorTransform {
+        r_mult: r_mult as f32,
+        g_mult: g_mult as f32,
+        b_mult: b_mult as f32,
+        a_mult: a_mult as f32,
+        r_add: r_add as f32 / 255.0,
+        g_add: g_add as f32 / 255.0,
+        b_add: b_add as f32 / 255.0,
+        a_add: a_add as f32 / 255.0,
+    })
+}
+
+/// Construct a new `ColorTransform` object from a display object color
+/// transform.
+pub fn color_transform_to_object<'gc>(
+    color_transform: &ColorTransform,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let proto = activation.context.avm2.prototypes().color_transform;
+    let args = [
+        color_transform.r_mult.into(),
+        color_transform.g_mult.into(),
+        color_transform.b_mult.into(),
+        color_transform.a_mult.into(),
+        (color_transform.r_add * 255.0).into(),
+        (color_transform.g_add * 255.0).into(),
+        (color_transform.b_add * 255.0).into(),
+        (color_transform.a_add * 255.0).into(),
+    ];
+    let object = proto.construct(activation, &args)?;
+    instance_init(activation, Some(object), &args)?;
+
+    Ok(object)
+}
+
+/// Implements `ColorTransform.color`'s getter.
+pub fn color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let [_, _, _, _, red, green, blue, _] = values(this, activation)?;
+        let rgb = ((red as u32 & 0xFF) << 16) | ((green as u32 & 0xFF) << 8) | (blue as u32 & 0xFF);
+
+        return Ok(rgb.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ColorTransform.color`'s setter.
+///
+/// This replaces the color channels with a solid color, leaving alpha alone.
+pub fn set_color<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let rgb = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_u32(activation)?;
+        let [_, _, _, alpha_multiplier, _, _, _, alpha_offset] = values(this, activation)?;
+
+        set_values(
+            this,
+            activation,
+            [
+                0.0,
+                0.0,
+                0.0,
+                alpha_multiplier,
+                f64::from((rgb >> 16) & 0xFF),
+                f64::from((rgb >> 8) & 0xFF),
+                f64::from(rgb & 0xFF),
+                alpha_offset,
+            ],
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ColorTransform.concat`.
+///
+/// The second transform is applied first, followed by this one, in the same
+/// order as `ColorTransform::concat` combines the transforms of nested clips.
+/// Unlike those, the values here are kept as numbers and don't saturate.
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let other = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let ours = values(this, activation)?;
+        let theirs = values(other, activation)?;
+
+        set_values(
+            this,
+            activation,
+            [
+                ours[0] * theirs[0],
+                ours[1] * theirs[1],
+                ours[2] * theirs[2],
+                ours[3] * theirs[3],
+                theirs[4] * ours[0] + ours[4],
+                theirs[5] * ours[1] + ours[5],
+                theirs[6] * ours[2] + ours[6],
+                theirs[7] * ours[3] + ours[7],
+            ],
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ColorTransform.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let values = values(this, activation)?;
+        let mut parts = Vec::with_capacity(values.len());
+        for ((name, _), value) in PROPERTIES.iter().zip(&values) {
+            let value = Value::Number(*value).coerce_to_string(activation)?;
+            parts.push(format!("{}={}", name, value));
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("({})", parts.join(", ")),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ColorTransform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "ColorTransform"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    for (name, default) in &PROPERTIES {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::public(), "Number")),
+            Some((*default).into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "color"),
+        Method::from_builtin(color),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "color"),
+        Method::from_builtin(set_color),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "concat"),
+        Method::from_builtin(concat),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
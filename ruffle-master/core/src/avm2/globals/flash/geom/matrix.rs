@@ -0,0 +1,497 @@
+//! `flash.geom.Matrix` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::point::{coords, create_point};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use swf::{Matrix, Twips};
+
+/// Implements `flash.geom.Matrix`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+        set_to(activation, Some(this), args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Matrix`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read a numeric argument, falling back to a default if it was not given.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+    default: f64,
+) -> Result<f64, Error> {
+    match args.get(index) {
+        Some(value) => value.coerce_to_number(activation),
+        None => Ok(default),
+    }
+}
+
+/// Convert a `Matrix` object into a display object matrix.
+pub fn object_to_matrix<'gc>(
+    mut object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Matrix, Error> {
+    let mut get = |name: &'static str| {
+        object
+            .get_property(object, &QName::new(Namespace::public(), name), activation)?
+            .coerce_to_number(activation)
+    };
+
+    Ok(Matrix {
+        a: get("a")? as f32,
+        b: get("b")? as f32,
+        c: get("c")? as f32,
+        d: get("d")? as f32,
+        tx: Twips::from_pixels(get("tx")?),
+        ty: Twips::from_pixels(get("ty")?),
+    })
+}
+
+/// Overwrite the properties of a `Matrix` object with a display object
+/// matrix.
+fn apply_matrix_to_object<'gc>(
+    matrix: Matrix,
+    mut object: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<(), Error> {
+    for (name, value) in &[
+        ("a", f64::from(matrix.a)),
+        ("b", f64::from(matrix.b)),
+        ("c", f64::from(matrix.c)),
+        ("d", f64::from(matrix.d)),
+        ("tx", matrix.tx.to_pixels()),
+        ("ty", matrix.ty.to_pixels()),
+    ] {
+        object.set_property(
+            object,
+            &QName::new(Namespace::public(), *name),
+            (*value).into(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Construct a new `Matrix` object from a display object matrix.
+pub fn matrix_to_object<'gc>(
+    matrix: Matrix,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Object<'gc>, Error> {
+    let proto = activation.context.avm2.prototypes().matrix;
+    let object = proto.construct(activation, &[])?;
+    instance_init(activation, Some(object), &[])?;
+    apply_matrix_to_object(matrix, object, activation)?;
+
+    Ok(object)
+}
+
+/// Implements `Matrix.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let matrix = object_to_matrix(this, activation)?;
+        return Ok(matrix_to_object(matrix, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.concat`.
+pub fn concat<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let other = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let matrix = object_to_matrix(this, activation)?;
+        let other = object_to_matrix(other, activation)?;
+        apply_matrix_to_object(other * matrix, this, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let other = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let matrix = object_to_matrix(other, activation)?;
+        apply_matrix_to_object(matrix, this, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.createBox`.
+pub fn create_box<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let scale_x = number_arg(activation, args, 0, f64::NAN)?;
+        let scale_y = number_arg(activation, args, 1, f64::NAN)?;
+        let rotation = number_arg(activation, args, 2, 0.0)?;
+        let tx = number_arg(activation, args, 3, 0.0)?;
+        let ty = number_arg(activation, args, 4, 0.0)?;
+
+        let matrix = Matrix::create_box(
+            scale_x as f32,
+            scale_y as f32,
+            rotation as f32,
+            Twips::from_pixels(tx),
+            Twips::from_pixels(ty),
+        );
+        apply_matrix_to_object(matrix, this, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.createGradientBox`.
+pub fn create_gradient_box<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let width = number_arg(activation, args, 0, f64::NAN)?;
+        let height = number_arg(activation, args, 1, f64::NAN)?;
+        let rotation = number_arg(activation, args, 2, 0.0)?;
+        let tx = number_arg(activation, args, 3, 0.0)?;
+        let ty = number_arg(activation, args, 4, 0.0)?;
+
+        let matrix = Matrix::create_gradient_box(
+            width as f32,
+            height as f32,
+            rotation as f32,
+            Twips::from_pixels(tx),
+            Twips::from_pixels(ty),
+        );
+        apply_matrix_to_object(matrix, this, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.deltaTransformPoint`.
+pub fn delta_transform_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let matrix = object_to_matrix(this, activation)?;
+        let mut point = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let (x, y) = coords(&mut point, activation)?;
+
+        return create_point(
+            activation,
+            (
+                x * f64::from(matrix.a) + y * f64::from(matrix.c),
+                x * f64::from(matrix.b) + y * f64::from(matrix.d),
+            ),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.identity`.
+pub fn identity<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        apply_matrix_to_object(Matrix::identity(), this, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.invert`.
+pub fn invert<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut matrix = object_to_matrix(this, activation)?;
+        matrix.invert();
+        apply_matrix_to_object(matrix, this, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.rotate`.
+pub fn rotate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let angle = number_arg(activation, args, 0, f64::NAN)?;
+        let matrix = object_to_matrix(this, activation)?;
+        apply_matrix_to_object(Matrix::rotate(angle as f32) * matrix, this, activation)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.scale`.
+pub fn scale<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let scale_x = number_arg(activation, args, 0, f64::NAN)?;
+        let scale_y = number_arg(activation, args, 1, f64::NAN)?;
+        let matrix = object_to_matrix(this, activation)?;
+        apply_matrix_to_object(
+            Matrix::scale(scale_x as f32, scale_y as f32) * matrix,
+            this,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.setTo`.
+pub fn set_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        for (index, (name, default)) in [
+            ("a", 1.0),
+            ("b", 0.0),
+            ("c", 0.0),
+            ("d", 1.0),
+            ("tx", 0.0),
+            ("ty", 0.0),
+        ]
+        .iter()
+        .enumerate()
+        {
+            let value = number_arg(activation, args, index, *default)?;
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let mut parts = Vec::with_capacity(6);
+        for name in &["a", "b", "c", "d", "tx", "ty"] {
+            let value = this
+                .get_property(this, &QName::new(Namespace::public(), *name), activation)?
+                .coerce_to_string(activation)?;
+            parts.push(format!("{}={}", name, value));
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("({})", parts.join(", ")),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.transformPoint`.
+pub fn transform_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let matrix = object_to_matrix(this, activation)?;
+        let mut point = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let (x, y) = coords(&mut point, activation)?;
+
+        return create_point(
+            activation,
+            (
+                x * f64::from(matrix.a) + y * f64::from(matrix.c) + matrix.tx.to_pixels(),
+                x * f64::from(matrix.b) + y * f64::from(matrix.d) + matrix.ty.to_pixels(),
+            ),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Matrix.translate`.
+pub fn translate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let dx = number_arg(activation, args, 0, f64::NAN)?;
+        let dy = number_arg(activation, args, 1, f64::NAN)?;
+        let matrix = object_to_matrix(this, activation)?;
+        apply_matrix_to_object(
+            Matrix::translate(Twips::from_pixels(dx), Twips::from_pixels(dy)) * matrix,
+            this,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Matrix`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Matrix"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    for (name, default) in &[
+        ("a", 1.0),
+        ("b", 0.0),
+        ("c", 0.0),
+        ("d", 1.0),
+        ("tx", 0.0),
+        ("ty", 0.0),
+    ] {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::public(), "Number")),
+            Some((*default).into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "concat"),
+        Method::from_builtin(concat),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "copyFrom"),
+        Method::from_builtin(copy_from),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "createBox"),
+        Method::from_builtin(create_box),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "createGradientBox"),
+        Method::from_builtin(create_gradient_box),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "deltaTransformPoint"),
+        Method::from_builtin(delta_transform_point),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "identity"),
+        Method::from_builtin(identity),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "invert"),
+        Method::from_builtin(invert),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "rotate"),
+        Method::from_builtin(rotate),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "scale"),
+        Method::from_builtin(scale),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setTo"),
+        Method::from_builtin(set_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "transformPoint"),
+        Method::from_builtin(transform_point),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "translate"),
+        Method::from_builtin(translate),
+    ));
+
+    class
+}
@@ -7,7 +7,8 @@ use crate::avm2::traits::Trait;
 use crate::avm2::{Activation, Error, Namespace, Object, QName, TObject, Value};
 use gc_arena::{GcCell, MutationContext};
 
-fn create_point<'gc>(
+/// Construct a new `Point` object with the given coordinates.
+pub fn create_point<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     coords: (f64, f64),
 ) -> Result<Value<'gc>, Error> {
@@ -29,7 +30,8 @@ pub fn instance_init<'gc>(
     Ok(Value::Undefined)
 }
 
-fn coords<'gc>(
+/// Read the coordinates of a `Point` object.
+pub fn coords<'gc>(
     this: &mut Object<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
 ) -> Result<(f64, f64), Error> {
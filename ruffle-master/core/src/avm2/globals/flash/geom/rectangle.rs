@@ -0,0 +1,798 @@
+//! `flash.geom.Rectangle` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::point::{coords, create_point};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The position and size of a rectangle, as `(x, y, width, height)`.
+pub type Rect = (f64, f64, f64, f64);
+
+/// Implements `flash.geom.Rectangle`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+        set_to(activation, Some(this), args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Rectangle`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a new `Rectangle` object.
+pub fn create_rectangle<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    rect: Rect,
+) -> Result<Value<'gc>, Error> {
+    let proto = activation.context.avm2.prototypes().rectangle;
+    let args = [
+        Value::Number(rect.0),
+        Value::Number(rect.1),
+        Value::Number(rect.2),
+        Value::Number(rect.3),
+    ];
+    let new_rect = proto.construct(activation, &args)?;
+    instance_init(activation, Some(new_rect), &args)?;
+
+    Ok(new_rect.into())
+}
+
+/// Read the position and size of a `Rectangle` object.
+pub fn rect<'gc>(
+    mut this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Rect, Error> {
+    let mut get = |name: &'static str| {
+        this.get_property(this, &QName::new(Namespace::public(), name), activation)?
+            .coerce_to_number(activation)
+    };
+
+    Ok((get("x")?, get("y")?, get("width")?, get("height")?))
+}
+
+/// Overwrite the position and size of a `Rectangle` object.
+fn set_rect<'gc>(
+    mut this: Object<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    rect: Rect,
+) -> Result<(), Error> {
+    for (name, value) in &[
+        ("x", rect.0),
+        ("y", rect.1),
+        ("width", rect.2),
+        ("height", rect.3),
+    ] {
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), *name),
+            (*value).into(),
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Read the first argument as a `Rectangle`.
+fn rect_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<Rect, Error> {
+    let other = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    rect(other, activation)
+}
+
+/// Read the first argument as a `Point`.
+fn point_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+) -> Result<(f64, f64), Error> {
+    let mut point = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    coords(&mut point, activation)
+}
+
+/// Read a numeric argument, treating a missing one as `undefined`.
+fn number_arg<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    index: usize,
+) -> Result<f64, Error> {
+    args.get(index)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)
+}
+
+/// Whether a rectangle has no area.
+fn is_empty_rect(rect: Rect) -> bool {
+    // Written this way so that NaN sizes also count as empty.
+    !(rect.2 > 0.0 && rect.3 > 0.0)
+}
+
+/// Implements `Rectangle.left`'s getter.
+pub fn left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, _, _, _) = rect(this, activation)?;
+        return Ok(x.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.left`'s setter.
+pub fn set_left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let new_left = number_arg(activation, args, 0)?;
+        let (x, y, width, height) = rect(this, activation)?;
+        set_rect(
+            this,
+            activation,
+            (new_left, y, width + x - new_left, height),
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.top`'s getter.
+pub fn top<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (_, y, _, _) = rect(this, activation)?;
+        return Ok(y.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.top`'s setter.
+pub fn set_top<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let new_top = number_arg(activation, args, 0)?;
+        let (x, y, width, height) = rect(this, activation)?;
+        set_rect(this, activation, (x, new_top, width, height + y - new_top))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.right`'s getter.
+pub fn right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, _, width, _) = rect(this, activation)?;
+        return Ok((x + width).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.right`'s setter.
+pub fn set_right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let new_right = number_arg(activation, args, 0)?;
+        let (x, y, _, height) = rect(this, activation)?;
+        set_rect(this, activation, (x, y, new_right - x, height))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.bottom`'s getter.
+pub fn bottom<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (_, y, _, height) = rect(this, activation)?;
+        return Ok((y + height).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.bottom`'s setter.
+pub fn set_bottom<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let new_bottom = number_arg(activation, args, 0)?;
+        let (x, y, width, _) = rect(this, activation)?;
+        set_rect(this, activation, (x, y, width, new_bottom - y))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.topLeft`'s getter.
+pub fn top_left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y, _, _) = rect(this, activation)?;
+        return create_point(activation, (x, y));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.topLeft`'s setter.
+pub fn set_top_left<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (new_x, new_y) = point_arg(activation, args)?;
+        let (x, y, width, height) = rect(this, activation)?;
+        set_rect(
+            this,
+            activation,
+            (new_x, new_y, width + x - new_x, height + y - new_y),
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.bottomRight`'s getter.
+pub fn bottom_right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y, width, height) = rect(this, activation)?;
+        return create_point(activation, (x + width, y + height));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.bottomRight`'s setter.
+pub fn set_bottom_right<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (right, bottom) = point_arg(activation, args)?;
+        let (x, y, _, _) = rect(this, activation)?;
+        set_rect(this, activation, (x, y, right - x, bottom - y))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.size`'s getter.
+pub fn size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (_, _, width, height) = rect(this, activation)?;
+        return create_point(activation, (width, height));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.size`'s setter.
+pub fn set_size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (width, height) = point_arg(activation, args)?;
+        let (x, y, _, _) = rect(this, activation)?;
+        set_rect(this, activation, (x, y, width, height))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let rect = rect(this, activation)?;
+        return create_rectangle(activation, rect);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.contains`.
+pub fn contains<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let point_x = number_arg(activation, args, 0)?;
+        let point_y = number_arg(activation, args, 1)?;
+        let (x, y, width, height) = rect(this, activation)?;
+
+        return Ok(
+            (point_x >= x && point_x < x + width && point_y >= y && point_y < y + height).into(),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.containsPoint`.
+pub fn contains_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (point_x, point_y) = point_arg(activation, args)?;
+        return contains(activation, Some(this), &[point_x.into(), point_y.into()]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.containsRect`.
+pub fn contains_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (other_x, other_y, other_width, other_height) = rect_arg(activation, args)?;
+        let (x, y, width, height) = rect(this, activation)?;
+
+        return Ok((other_x >= x
+            && other_y >= y
+            && other_x + other_width <= x + width
+            && other_y + other_height <= y + height)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.copyFrom`.
+pub fn copy_from<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let other = rect_arg(activation, args)?;
+        set_rect(this, activation, other)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.equals`.
+#[allow(clippy::float_cmp)]
+pub fn equals<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let other = rect_arg(activation, args)?;
+        let ours = rect(this, activation)?;
+
+        return Ok((ours == other).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.inflate`.
+pub fn inflate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let dx = number_arg(activation, args, 0)?;
+        let dy = number_arg(activation, args, 1)?;
+        let (x, y, width, height) = rect(this, activation)?;
+        set_rect(
+            this,
+            activation,
+            (x - dx, y - dy, width + 2.0 * dx, height + 2.0 * dy),
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.inflatePoint`.
+pub fn inflate_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (dx, dy) = point_arg(activation, args)?;
+        inflate(activation, Some(this), &[dx.into(), dy.into()])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.intersection`.
+pub fn intersection<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (other_x, other_y, other_width, other_height) = rect_arg(activation, args)?;
+        let (x, y, width, height) = rect(this, activation)?;
+
+        let left = x.max(other_x);
+        let top = y.max(other_y);
+        let right = (x + width).min(other_x + other_width);
+        let bottom = (y + height).min(other_y + other_height);
+
+        let result = (left, top, right - left, bottom - top);
+        if is_empty_rect(result) {
+            return create_rectangle(activation, (0.0, 0.0, 0.0, 0.0));
+        }
+
+        return create_rectangle(activation, result);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.intersects`.
+pub fn intersects<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (other_x, other_y, other_width, other_height) = rect_arg(activation, args)?;
+        let (x, y, width, height) = rect(this, activation)?;
+
+        return Ok((x < other_x + other_width
+            && other_x < x + width
+            && y < other_y + other_height
+            && other_y < y + height)
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.isEmpty`.
+pub fn is_empty<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let rect = rect(this, activation)?;
+        return Ok(is_empty_rect(rect).into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.offset`.
+pub fn offset<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let dx = number_arg(activation, args, 0)?;
+        let dy = number_arg(activation, args, 1)?;
+        let (x, y, width, height) = rect(this, activation)?;
+        set_rect(this, activation, (x + dx, y + dy, width, height))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.offsetPoint`.
+pub fn offset_point<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (dx, dy) = point_arg(activation, args)?;
+        offset(activation, Some(this), &[dx.into(), dy.into()])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.setEmpty`.
+pub fn set_empty<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        set_rect(this, activation, (0.0, 0.0, 0.0, 0.0))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.setTo`.
+pub fn set_to<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut rect = [0.0; 4];
+        for (index, value) in rect.iter_mut().enumerate() {
+            if let Some(arg) = args.get(index) {
+                *value = arg.coerce_to_number(activation)?;
+            }
+        }
+
+        set_rect(this, activation, (rect[0], rect[1], rect[2], rect[3]))?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.toString`.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let (x, y, width, height) = rect(this, activation)?;
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("(x={}, y={}, w={}, h={})", x, y, width, height),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Rectangle.union`.
+pub fn union<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let other = rect_arg(activation, args)?;
+        let ours = rect(this, activation)?;
+
+        if is_empty_rect(ours) {
+            return create_rectangle(activation, other);
+        } else if is_empty_rect(other) {
+            return create_rectangle(activation, ours);
+        }
+
+        let left = ours.0.min(other.0);
+        let top = ours.1.min(other.1);
+        let right = (ours.0 + ours.2).max(other.0 + other.2);
+        let bottom = (ours.1 + ours.3).max(other.1 + other.3);
+
+        return create_rectangle(activation, (left, top, right - left, bottom - top));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Rectangle`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Rectangle"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    for name in &["x", "y", "width", "height"] {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::public(), "Number")),
+            Some(0.0.into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "left"),
+        Method::from_builtin(left),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "left"),
+        Method::from_builtin(set_left),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "top"),
+        Method::from_builtin(top),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "top"),
+        Method::from_builtin(set_top),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "right"),
+        Method::from_builtin(right),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "right"),
+        Method::from_builtin(set_right),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bottom"),
+        Method::from_builtin(bottom),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "bottom"),
+        Method::from_builtin(set_bottom),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "topLeft"),
+        Method::from_builtin(top_left),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "topLeft"),
+        Method::from_builtin(set_top_left),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bottomRight"),
+        Method::from_builtin(bottom_right),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "bottomRight"),
+        Method::from_builtin(set_bottom_right),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "size"),
+        Method::from_builtin(size),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "size"),
+        Method::from_builtin(set_size),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "contains"),
+        Method::from_builtin(contains),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "containsPoint"),
+        Method::from_builtin(contains_point),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "containsRect"),
+        Method::from_builtin(contains_rect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "copyFrom"),
+        Method::from_builtin(copy_from),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "equals"),
+        Method::from_builtin(equals),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "inflate"),
+        Method::from_builtin(inflate),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "inflatePoint"),
+        Method::from_builtin(inflate_point),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "intersection"),
+        Method::from_builtin(intersection),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "intersects"),
+        Method::from_builtin(intersects),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "isEmpty"),
+        Method::from_builtin(is_empty),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "offset"),
+        Method::from_builtin(offset),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "offsetPoint"),
+        Method::from_builtin(offset_point),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setEmpty"),
+        Method::from_builtin(set_empty),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setTo"),
+        Method::from_builtin(set_to),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "union"),
+        Method::from_builtin(union),
+    ));
+
+    class
+}
@@ -0,0 +1,227 @@
+//! `flash.geom.Transform` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::colortransform::{
+    color_transform_to_object, object_to_color_transform,
+};
+use crate::avm2::globals::flash::geom::matrix::{matrix_to_object, object_to_matrix};
+use crate::avm2::globals::flash::geom::rectangle::create_rectangle;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::TDisplayObject;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.geom.Transform`'s instance constructor.
+///
+/// A `Transform` is bound to the display object it was constructed with, and
+/// reads and writes that object's transformation directly.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        let display_object = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?
+            .as_display_object()
+            .ok_or("Transform must be constructed with a display object")?;
+        this.init_display_object(activation.context.gc_context, display_object);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.geom.Transform`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a new `Transform` object bound to a display object.
+pub fn create_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    display_object: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    let proto = activation.context.avm2.prototypes().transform;
+    let args = [display_object.into()];
+    let transform = proto.construct(activation, &args)?;
+    instance_init(activation, Some(transform), &args)?;
+
+    Ok(transform)
+}
+
+/// Implements `Transform.matrix`'s getter.
+pub fn matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let matrix = *dobj.matrix();
+        return Ok(matrix_to_object(matrix, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Transform.matrix`'s setter.
+pub fn set_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        // A null matrix is only meaningful for 3D transforms, which we don't
+        // support.
+        if let Some(Value::Object(matrix)) = args.get(0) {
+            let matrix = object_to_matrix(*matrix, activation)?;
+            dobj.set_matrix(activation.context.gc_context, &matrix);
+            dobj.set_transformed_by_script(activation.context.gc_context, true);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Transform.colorTransform`'s getter.
+pub fn color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let color_transform = *dobj.color_transform();
+        return Ok(color_transform_to_object(&color_transform, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Transform.colorTransform`'s setter.
+pub fn set_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let color_transform = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let color_transform = object_to_color_transform(color_transform, activation)?;
+        dobj.set_color_transform(activation.context.gc_context, &color_transform);
+        dobj.set_transformed_by_script(activation.context.gc_context, true);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Transform.concatenatedMatrix`'s getter.
+pub fn concatenated_matrix<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let matrix = dobj.local_to_global_matrix();
+        return Ok(matrix_to_object(matrix, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Transform.concatenatedColorTransform`'s getter.
+pub fn concatenated_color_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let color_transform = dobj.local_to_global_color_transform();
+        return Ok(color_transform_to_object(&color_transform, activation)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Transform.pixelBounds`'s getter.
+pub fn pixel_bounds<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let bounds = dobj.world_bounds();
+        return create_rectangle(
+            activation,
+            (
+                bounds.x_min.to_pixels(),
+                bounds.y_min.to_pixels(),
+                bounds.width().to_pixels(),
+                bounds.height().to_pixels(),
+            ),
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Transform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.geom"), "Transform"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "matrix"),
+        Method::from_builtin(matrix),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "matrix"),
+        Method::from_builtin(set_matrix),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "colorTransform"),
+        Method::from_builtin(color_transform),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "colorTransform"),
+        Method::from_builtin(set_color_transform),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "concatenatedMatrix"),
+        Method::from_builtin(concatenated_matrix),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "concatenatedColorTransform"),
+        Method::from_builtin(concatenated_color_transform),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "pixelBounds"),
+        Method::from_builtin(pixel_bounds),
+    ));
+
+    class
+}
@@ -1,3 +1,7 @@
 //! `flash.media` namespace
 
+pub mod microphone;
+pub mod sound;
+pub mod soundchannel;
+pub mod soundtransform;
 pub mod video;
@@ -0,0 +1,400 @@
+//! `flash.media.Microphone` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The gain at which captured audio is reported unchanged.
+const DEFAULT_GAIN: f64 = 50.0;
+
+/// Implements `flash.media.Microphone`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        for (name, value) in &[
+            ("gain", DEFAULT_GAIN),
+            ("rate", 8.0),
+            ("silenceLevel", 10.0),
+            ("silenceTimeout", 2000.0),
+            ("index", 0.0),
+        ] {
+            this.set_property(
+                this,
+                &QName::new(Namespace::Private("ruffle".into()), *name),
+                (*value).into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.media.Microphone`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read one of the microphone's private properties.
+fn get_private<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), name),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Write one of the microphone's private properties.
+fn set_private<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error> {
+    if let Some(mut this) = this {
+        this.set_property(
+            this,
+            &QName::new(Namespace::Private("ruffle".into()), name),
+            value,
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Implements `Microphone.getMicrophone`.
+pub fn get_microphone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let num_devices = activation.context.audio.capture_device_names().len();
+    let index = args
+        .get(0)
+        .cloned()
+        .unwrap_or_else(|| (-1).into())
+        .coerce_to_i32(activation)?;
+    let index = if index < 0 { 0 } else { index as usize };
+
+    if index >= num_devices {
+        return Ok(Value::Null);
+    }
+
+    let proto = activation.context.avm2.prototypes().microphone;
+    let microphone = proto.construct(activation, &[])?;
+    instance_init(activation, Some(microphone), &[])?;
+    set_private(activation, Some(microphone), "index", (index as u32).into())?;
+
+    Ok(microphone.into())
+}
+
+/// Implements `Microphone.names`.
+pub fn names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let names: Vec<Option<Value<'gc>>> = activation
+        .context
+        .audio
+        .capture_device_names()
+        .into_iter()
+        .map(|name| Some(AvmString::new(activation.context.gc_context, name).into()))
+        .collect();
+
+    Ok(ArrayObject::from_array(
+        ArrayStorage::from_storage(names),
+        activation.context.avm2.prototypes().array,
+        activation.context.gc_context,
+    )
+    .into())
+}
+
+/// Implements `Microphone.isSupported`.
+pub fn is_supported<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok((!activation.context.audio.capture_device_names().is_empty()).into())
+}
+
+/// Implements `Microphone.activityLevel`.
+pub fn activity_level<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let gain = get_private(activation, this, "gain")?.coerce_to_number(activation)?;
+
+    match activation.context.audio_manager.capture_activity_level() {
+        Some(level) => Ok((level * gain / DEFAULT_GAIN).min(100.0).max(0.0).into()),
+        None => Ok((-1.0).into()),
+    }
+}
+
+/// Implements `Microphone.gain`'s getter.
+pub fn gain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "gain")
+}
+
+/// Implements `Microphone.gain`'s setter.
+pub fn set_gain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let gain = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?
+        .min(100.0)
+        .max(0.0);
+    set_private(activation, this, "gain", gain.into())?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.rate`'s getter.
+pub fn rate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "rate")
+}
+
+/// Implements `Microphone.rate`'s setter.
+pub fn set_rate<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let rate = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    set_private(activation, this, "rate", rate.into())?;
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.silenceLevel`.
+pub fn silence_level<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "silenceLevel")
+}
+
+/// Implements `Microphone.silenceTimeout`.
+pub fn silence_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "silenceTimeout")
+}
+
+/// Implements `Microphone.setSilenceLevel`.
+pub fn set_silence_level<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let level = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_number(activation)?
+        .min(100.0)
+        .max(0.0);
+    set_private(activation, this, "silenceLevel", level.into())?;
+
+    if let Some(timeout) = args.get(1) {
+        let timeout = timeout.coerce_to_i32(activation)?;
+        if timeout >= 0 {
+            set_private(activation, this, "silenceTimeout", timeout.into())?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Microphone.index`.
+pub fn index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    get_private(activation, this, "index")
+}
+
+/// Implements `Microphone.name`.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let index = get_private(activation, this, "index")?.coerce_to_u32(activation)?;
+    let name = activation
+        .context
+        .audio
+        .capture_device_names()
+        .into_iter()
+        .nth(index as usize)
+        .unwrap_or_default();
+
+    Ok(AvmString::new(activation.context.gc_context, name).into())
+}
+
+/// Implements `Microphone.muted`.
+pub fn muted<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `Microphone.setLoopBack`.
+///
+/// Captured audio is not played back, but capture must be running for
+/// `activityLevel` to report anything.
+pub fn set_loop_back<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let state = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Bool(true))
+        .coerce_to_boolean();
+
+    let context = &mut activation.context;
+    if state {
+        if let Err(e) = context.audio_manager.start_capture(context.audio) {
+            log::warn!(
+                "Microphone.setLoopBack: Couldn't start audio capture: {}",
+                e
+            );
+        }
+    } else {
+        context.audio_manager.stop_capture(context.audio);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Microphone`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.media"), "Microphone"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getMicrophone"),
+        Method::from_builtin(get_microphone),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "names"),
+        Method::from_builtin(names),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isSupported"),
+        Method::from_builtin(is_supported),
+    ));
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "activityLevel"),
+        Method::from_builtin(activity_level),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "gain"),
+        Method::from_builtin(gain),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "gain"),
+        Method::from_builtin(set_gain),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "rate"),
+        Method::from_builtin(rate),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "rate"),
+        Method::from_builtin(set_rate),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "silenceLevel"),
+        Method::from_builtin(silence_level),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "silenceTimeout"),
+        Method::from_builtin(silence_timeout),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setSilenceLevel"),
+        Method::from_builtin(set_silence_level),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "index"),
+        Method::from_builtin(index),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "name"),
+        Method::from_builtin(name),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "muted"),
+        Method::from_builtin(muted),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setLoopBack"),
+        Method::from_builtin(set_loop_back),
+    ));
+
+    class
+}
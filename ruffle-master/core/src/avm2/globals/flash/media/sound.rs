@@ -0,0 +1,286 @@
+//! `flash.media.Sound` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::events::dispatch_event;
+use crate::avm2::globals::flash::events::sampledataevent;
+use crate::avm2::globals::flash::media::soundchannel;
+use crate::avm2::globals::flash::media::soundtransform::object_to_sound_transform;
+use crate::avm2::globals::flash::utils::bytearray;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, SoundChannelObject, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::audio::SoundInstanceHandle;
+use gc_arena::{GcCell, MutationContext};
+use swf::{SoundEvent, SoundInfo};
+
+/// Implements `flash.media.Sound`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.media.Sound`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Sound.length`.
+pub fn length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(sound) = this.and_then(|this| this.as_sound()) {
+        if let Some(duration) = activation.context.audio.get_sound_duration(sound) {
+            return Ok(f64::from(duration).into());
+        }
+    }
+
+    Ok(0.0.into())
+}
+
+/// Implements `Sound.play`.
+pub fn play<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let start_time = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.0.into())
+            .coerce_to_number(activation)?;
+        let loops = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+        let sound_transform = match args.get(2) {
+            Some(Value::Object(object)) => Some(object_to_sound_transform(activation, *object)?),
+            _ => None,
+        };
+
+        let proto = activation.context.avm2.prototypes().sound_channel;
+        let channel_object = proto.construct(activation, &[])?;
+        soundchannel::instance_init(activation, Some(channel_object), &[])?;
+        let channel = match channel_object.as_sound_channel() {
+            Some(channel) => channel,
+            None => return Ok(Value::Null),
+        };
+
+        let mc = activation.context.gc_context;
+        if let Some(sound_transform) = sound_transform {
+            channel.set_sound_transform(mc, sound_transform);
+        }
+
+        if let Some(sound) = this.as_sound() {
+            // TODO: Handle loops > u16::MAX.
+            let num_loops = loops.max(1).min(i32::from(u16::MAX)) as u16;
+            let sound_info = SoundInfo {
+                event: SoundEvent::Start,
+                in_sample: if start_time > 0.0 {
+                    Some((start_time / 1000.0 * 44100.0) as u32)
+                } else {
+                    None
+                },
+                out_sample: None,
+                num_loops,
+                envelope: None,
+            };
+
+            if let Some(instance) = activation
+                .context
+                .start_sound(sound, &sound_info, None, None)
+            {
+                channel.set_instance(mc, Some(instance));
+                activation
+                    .context
+                    .attach_avm2_sound_channel(instance, channel);
+
+                return Ok(channel_object.into());
+            }
+        } else {
+            // A `Sound` with no sound data generates its samples at runtime
+            // through `sampleData` events.
+            channel.set_sample_data_source(mc, Some(this));
+
+            if let Some(instance) = activation.context.start_dynamic_sound(channel) {
+                channel.set_instance(mc, Some(instance));
+                request_sample_data(activation, instance, channel)?;
+
+                return Ok(channel_object.into());
+            }
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Sound.extract`.
+pub fn extract<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        if let Some(sound) = this.as_sound() {
+            let target = args
+                .get(0)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_object(activation)?;
+            let length = args
+                .get(1)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_u32(activation)?;
+            let start_position = args
+                .get(2)
+                .cloned()
+                .unwrap_or_else(|| (-1.0).into())
+                .coerce_to_number(activation)?;
+
+            let extract_position =
+                QName::new(Namespace::Private("ruffle".into()), "extract_position");
+            let start_position = if start_position < 0.0 {
+                this.get_property(this, &extract_position, activation)?
+                    .coerce_to_u32(activation)?
+            } else {
+                start_position as u32
+            };
+
+            let samples = activation
+                .context
+                .audio
+                .extract_samples(sound, start_position, length)
+                .unwrap_or_default();
+
+            if let Some(mut bytearray) = target.as_bytearray_mut(activation.context.gc_context) {
+                for [left, right] in &samples {
+                    bytearray.write_float(*left);
+                    bytearray.write_float(*right);
+                }
+            }
+
+            let num_samples = samples.len() as u32;
+            this.set_property(
+                this,
+                &extract_position,
+                start_position.saturating_add(num_samples).into(),
+                activation,
+            )?;
+
+            return Ok(num_samples.into());
+        }
+    }
+
+    Ok(0.into())
+}
+
+/// The most sample frames that a single `sampleData` event may provide.
+const MAX_SAMPLE_DATA_SAMPLES: usize = 8192;
+
+/// The fewest sample frames that a `sampleData` event may provide without
+/// ending the sound.
+const MIN_SAMPLE_DATA_SAMPLES: usize = 2048;
+
+/// Dispatch a `sampleData` event to the `Sound` playing on a channel, and
+/// queue the samples written by its listeners onto the sound instance.
+pub fn request_sample_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    instance: SoundInstanceHandle,
+    channel: SoundChannelObject<'gc>,
+) -> Result<(), Error> {
+    let source = match channel.sample_data_source() {
+        Some(source) => source,
+        None => return Ok(()),
+    };
+    let position = channel.sample_data_position();
+
+    let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+    let data = bytearray_proto.construct(activation, &[])?;
+    bytearray::instance_init(activation, Some(data), &[])?;
+
+    let event_proto = activation.context.avm2.prototypes().sample_data_event;
+    let event = event_proto.construct(activation, &[])?;
+    sampledataevent::instance_init(
+        activation,
+        Some(event),
+        &[
+            "sampleData".into(),
+            false.into(),
+            false.into(),
+            position.into(),
+            data.into(),
+        ],
+    )?;
+
+    dispatch_event(activation, source, event)?;
+
+    let mut samples = Vec::new();
+    if let Some(mut bytearray) = data.as_bytearray_mut(activation.context.gc_context) {
+        bytearray.set_position(0);
+        while samples.len() < MAX_SAMPLE_DATA_SAMPLES {
+            match (bytearray.read_float(), bytearray.read_float()) {
+                (Ok(left), Ok(right)) => samples.push([left, right]),
+                _ => break,
+            }
+        }
+    }
+
+    activation.context.audio.queue_samples(instance, &samples);
+    channel.set_sample_data_position(
+        activation.context.gc_context,
+        position + samples.len() as f64,
+    );
+
+    if samples.len() < MIN_SAMPLE_DATA_SAMPLES {
+        activation.context.finish_dynamic_sound(instance);
+    }
+
+    Ok(())
+}
+
+/// Construct `Sound`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.media"), "Sound"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "length"),
+        Method::from_builtin(length),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "extract"),
+        Method::from_builtin(extract),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "play"),
+        Method::from_builtin(play),
+    ));
+
+    class
+}
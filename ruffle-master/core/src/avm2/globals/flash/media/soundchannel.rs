@@ -0,0 +1,137 @@
+//! `flash.media.SoundChannel` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::media::soundtransform::{
+    object_to_sound_transform, sound_transform_to_object,
+};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.media.SoundChannel`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.media.SoundChannel`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `SoundChannel.position`.
+pub fn position<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(channel) = this.and_then(|this| this.as_sound_channel()) {
+        return Ok(channel.position().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SoundChannel.soundTransform`'s getter.
+pub fn sound_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(channel) = this.and_then(|this| this.as_sound_channel()) {
+        let transform = channel.sound_transform();
+        return Ok(sound_transform_to_object(activation, &transform)?.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SoundChannel.soundTransform`'s setter.
+pub fn set_sound_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(channel) = this.and_then(|this| this.as_sound_channel()) {
+        let object = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let transform = object_to_sound_transform(activation, object)?;
+
+        channel.set_sound_transform(activation.context.gc_context, transform);
+        activation
+            .context
+            .audio_manager
+            .set_sound_transforms_dirty();
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SoundChannel.stop`.
+pub fn stop<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(channel) = this.and_then(|this| this.as_sound_channel()) {
+        if let Some(instance) = channel.instance() {
+            activation.context.stop_sound(instance);
+            channel.set_instance(activation.context.gc_context, None);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `SoundChannel`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.media"), "SoundChannel"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "position"),
+        Method::from_builtin(position),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "soundTransform"),
+        Method::from_builtin(sound_transform),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "soundTransform"),
+        Method::from_builtin(set_sound_transform),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "stop"),
+        Method::from_builtin(stop),
+    ));
+
+    class
+}
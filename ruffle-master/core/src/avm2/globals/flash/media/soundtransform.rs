@@ -0,0 +1,206 @@
+//! `flash.media.SoundTransform` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::display_object::SoundTransform;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.media.SoundTransform`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let volume = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 1.0.into())
+            .coerce_to_number(activation)?;
+        let pan = args
+            .get(1)
+            .cloned()
+            .unwrap_or_else(|| 0.0.into())
+            .coerce_to_number(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "volume"),
+            volume.into(),
+            activation,
+        )?;
+        set_pan(activation, Some(this), &[pan.into()])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.media.SoundTransform`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read a numeric property off of a `SoundTransform`.
+fn get_number<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    name: &'static str,
+) -> Result<f64, Error> {
+    this.get_property(this, &QName::new(Namespace::public(), name), activation)?
+        .coerce_to_number(activation)
+}
+
+/// Implements `SoundTransform.pan`'s getter.
+pub fn pan<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let left_to_left = get_number(activation, this, "leftToLeft")?;
+        let right_to_right = get_number(activation, this, "rightToRight")?;
+
+        if left_to_left != 1.0 {
+            return Ok((1.0 - left_to_left.abs()).into());
+        } else {
+            return Ok((right_to_right.abs() - 1.0).into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `SoundTransform.pan`'s setter.
+pub fn set_pan<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let pan = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_number(activation)?;
+
+        let (left_to_left, right_to_right) = if pan >= 0.0 {
+            (1.0 - pan, 1.0)
+        } else {
+            (1.0, 1.0 + pan)
+        };
+
+        for (name, value) in &[
+            ("leftToLeft", left_to_left),
+            ("leftToRight", 0.0),
+            ("rightToLeft", 0.0),
+            ("rightToRight", right_to_right),
+        ] {
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                (*value).into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Convert a `SoundTransform` object into a display object sound transform.
+pub fn object_to_sound_transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+) -> Result<SoundTransform, Error> {
+    let to_volume = |value: f64| (value * f64::from(SoundTransform::MAX_VOLUME)) as i32;
+
+    Ok(SoundTransform {
+        volume: to_volume(get_number(activation, object, "volume")?),
+        left_to_left: to_volume(get_number(activation, object, "leftToLeft")?),
+        left_to_right: to_volume(get_number(activation, object, "leftToRight")?),
+        right_to_left: to_volume(get_number(activation, object, "rightToLeft")?),
+        right_to_right: to_volume(get_number(activation, object, "rightToRight")?),
+    })
+}
+
+/// Construct a new `SoundTransform` object from a display object sound
+/// transform.
+pub fn sound_transform_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    transform: &SoundTransform,
+) -> Result<Object<'gc>, Error> {
+    let proto = activation.context.avm2.prototypes().sound_transform;
+    let mut object = proto.construct(activation, &[])?;
+    instance_init(activation, Some(object), &[])?;
+
+    let from_volume = |value: i32| f64::from(value) / f64::from(SoundTransform::MAX_VOLUME);
+
+    for (name, value) in &[
+        ("volume", transform.volume),
+        ("leftToLeft", transform.left_to_left),
+        ("leftToRight", transform.left_to_right),
+        ("rightToLeft", transform.right_to_left),
+        ("rightToRight", transform.right_to_right),
+    ] {
+        object.set_property(
+            object,
+            &QName::new(Namespace::public(), *name),
+            from_volume(*value).into(),
+            activation,
+        )?;
+    }
+
+    Ok(object)
+}
+
+/// Construct `SoundTransform`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.media"), "SoundTransform"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    for (name, default) in &[
+        ("volume", 1.0),
+        ("leftToLeft", 1.0),
+        ("leftToRight", 0.0),
+        ("rightToLeft", 0.0),
+        ("rightToRight", 1.0),
+    ] {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::public(), "Number")),
+            Some((*default).into()),
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "pan"),
+        Method::from_builtin(pan),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "pan"),
+        Method::from_builtin(set_pan),
+    ));
+
+    class
+}
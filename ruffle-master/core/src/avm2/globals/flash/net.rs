@@ -0,0 +1,8 @@
+//! `flash.net` namespace
+
+pub mod filefilter;
+pub mod filereference;
+pub mod localconnection;
+pub mod socket;
+pub mod urlstream;
+pub mod xmlsocket;
@@ -0,0 +1,134 @@
+//! `flash.net.FileFilter` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::FileFilter;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.FileFilter`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let description = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let extension = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let mac_type = match args.get(2).cloned().unwrap_or(Value::Null) {
+            Value::Null | Value::Undefined => Value::Null,
+            mac_type => mac_type.coerce_to_string(activation)?.into(),
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "description"),
+            description.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "extension"),
+            extension.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "macType"),
+            mac_type,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.FileFilter`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read a `FileFilter` for a file dialog. `extension` lists the patterns of
+/// the filter separated by semicolons, as in `*.jpg;*.png`.
+pub fn to_file_filter<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut filter: Object<'gc>,
+) -> Result<FileFilter, Error> {
+    let description = filter
+        .get_property(
+            filter,
+            &QName::new(Namespace::public(), "description"),
+            activation,
+        )?
+        .coerce_to_string(activation)?;
+    let extension = filter
+        .get_property(
+            filter,
+            &QName::new(Namespace::public(), "extension"),
+            activation,
+        )?
+        .coerce_to_string(activation)?;
+
+    Ok(FileFilter {
+        description: description.to_string(),
+        patterns: extension
+            .as_str()
+            .split(';')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .map(str::to_string)
+            .collect(),
+    })
+}
+
+/// Construct `FileFilter`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "FileFilter"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "description"),
+        Multiname::from(QName::new(Namespace::public(), "String")),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "extension"),
+        Multiname::from(QName::new(Namespace::public(), "String")),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "macType"),
+        Multiname::from(QName::new(Namespace::public(), "String")),
+        None,
+    ));
+
+    class
+}
@@ -0,0 +1,571 @@
+//! `flash.net.FileReference` builtin/prototype
+//!
+//! Only one `FileReference` at a time may browse for, save or download a
+//! file. AVM2 remembers which one is waiting until the UI passes the user's
+//! choice to `Player::finish_file_dialog`. Picked files are read whole by the
+//! UI, and `load` hands their contents to the movie as a `ByteArray`.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::globals::flash::events::ioerrorevent::dispatch_io_error_event;
+use crate::avm2::globals::flash::events::progressevent::dispatch_progress_event;
+use crate::avm2::globals::flash::net::filefilter::to_file_filter;
+use crate::avm2::globals::flash::utils::bytearray;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use crate::backend::ui::DialogFile;
+use crate::executor::TaskHandle;
+use crate::loader::Error as LoaderError;
+use crate::net::{self, Request};
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// A `FileReference` that is waiting for the user or for a download.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct FileSession<'gc> {
+    target: Object<'gc>,
+    kind: FileSessionKind,
+}
+
+#[derive(Clone, Collect)]
+#[collect(require_static)]
+enum FileSessionKind {
+    /// The UI is showing an open file dialog.
+    Open,
+
+    /// The UI is showing a save file dialog.
+    Save,
+
+    /// A file is being downloaded, after which the UI will be asked where to
+    /// save it.
+    Download {
+        task: TaskHandle,
+        default_name: String,
+    },
+}
+
+/// Implements `flash.net.FileReference`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.FileReference`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read one of the private slots of a `FileReference`.
+fn get_slot<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), name),
+        activation,
+    )
+}
+
+/// Write one of the private slots of a `FileReference`.
+fn set_slot<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error> {
+    this.set_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), name),
+        value,
+        activation,
+    )
+}
+
+/// The `ByteArray` that holds the contents of the file that was picked.
+fn contents<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    match get_slot(activation, this, "contents")? {
+        Value::Object(contents) => Ok(contents),
+        _ => Err("IllegalOperationError: Error #2037: Functions called in incorrect sequence, or earlier call was unsuccessful.".into()),
+    }
+}
+
+/// Construct a `ByteArray` holding `data`.
+fn new_bytearray<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    data: &[u8],
+) -> Result<Object<'gc>, Error> {
+    let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+    let bytearray = bytearray_proto.construct(activation, &[])?;
+    bytearray::instance_init(activation, Some(bytearray), &[])?;
+    if let Some(mut storage) = bytearray.as_bytearray_mut(activation.context.gc_context) {
+        storage.write_bytes_at(data, 0);
+    }
+
+    Ok(bytearray)
+}
+
+/// Dispatch a plain `Event` of the given type to a `FileReference`.
+fn dispatch_file_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+) -> Result<(), Error> {
+    let event_proto = activation.context.avm2.prototypes().event;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(event_proto),
+        Event::new(event_type),
+    );
+    dispatch_event(activation, target, event)?;
+
+    Ok(())
+}
+
+/// Fail if another `FileReference` is already waiting on a file.
+fn check_no_session(activation: &mut Activation<'_, '_, '_>) -> Result<(), Error> {
+    if activation.context.avm2.file_session.is_some() {
+        return Err("IllegalOperationError: Error #2041: Only one file browsing session may be performed at a time.".into());
+    }
+
+    Ok(())
+}
+
+/// Implements `FileReference.browse`.
+pub fn browse<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        check_no_session(activation)?;
+
+        let mut filters = Vec::new();
+        if let Some(Value::Object(type_filter)) = args.get(0) {
+            let type_filter: Vec<Value<'gc>> = type_filter
+                .as_array_storage()
+                .map(|array| array.iter().flatten().collect())
+                .unwrap_or_default();
+            for filter in type_filter {
+                match filter {
+                    Value::Object(filter) => filters.push(to_file_filter(activation, filter)?),
+                    _ => {
+                        return Err(
+                            "ArgumentError: Error #2004: One of the parameters is invalid.".into(),
+                        )
+                    }
+                }
+            }
+        }
+
+        if !activation.context.ui.display_open_file_dialog(&filters) {
+            return Ok(false.into());
+        }
+
+        activation.context.avm2.file_session = Some(FileSession {
+            target: this,
+            kind: FileSessionKind::Open,
+        });
+        return Ok(true.into());
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `FileReference.save`.
+pub fn save<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        check_no_session(activation)?;
+
+        let data = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => {
+                return Err("ArgumentError: Error #2007: Parameter data must be non-null.".into())
+            }
+            Value::Object(object) if object.as_bytearray().is_some() => object
+                .as_bytearray()
+                .map(|bytearray| bytearray.bytes().clone())
+                .unwrap_or_default(),
+            data => data
+                .coerce_to_string(activation)?
+                .as_str()
+                .as_bytes()
+                .to_vec(),
+        };
+        let default_name = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => String::new(),
+            name => name.coerce_to_string(activation)?.to_string(),
+        };
+
+        if !activation
+            .context
+            .ui
+            .display_save_file_dialog(&default_name, &data)
+        {
+            log::warn!("FileReference.save: the UI can't show file dialogs");
+            return Ok(Value::Undefined);
+        }
+
+        activation.context.avm2.file_session = Some(FileSession {
+            target: this,
+            kind: FileSessionKind::Save,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.download`.
+///
+/// Flash asks where to save the file before downloading it, but the UI needs
+/// the data to save it, so here the file is downloaded first.
+pub fn download<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        check_no_session(activation)?;
+
+        let mut request = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Object(request) => request,
+            _ => return Err("TypeError: Error #2007: Parameter request must be non-null.".into()),
+        };
+        let url = request
+            .get_property(request, &QName::new(Namespace::public(), "url"), activation)?
+            .coerce_to_string(activation)?
+            .to_string();
+        let default_name = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => url
+                .split(|c| c == '?' || c == '#')
+                .next()
+                .and_then(|path| path.rsplit('/').next())
+                .unwrap_or_default()
+                .to_string(),
+            name => name.coerce_to_string(activation)?.to_string(),
+        };
+
+        let task = net::spawn_fetch(
+            &mut activation.context,
+            Request::get(url),
+            |context, response| {
+                if let Err(e) = Avm2::finish_file_download(context, response) {
+                    log::error!("Encountered AVM2 error when finishing a download: {}", e);
+                }
+                Ok(())
+            },
+        );
+        activation.context.avm2.file_session = Some(FileSession {
+            target: this,
+            kind: FileSessionKind::Download { task, default_name },
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.cancel`.
+pub fn cancel<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(FileSession {
+            target,
+            kind: FileSessionKind::Download { task, .. },
+        }) = activation.context.avm2.file_session
+        {
+            if Object::ptr_eq(target, this) {
+                activation.context.executor.cancel(task);
+                activation.context.avm2.file_session = None;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.load`.
+///
+/// The contents are handed over at the next frame, like any other load, so
+/// that listeners added after calling `load` still hear about it.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        contents(activation, this)?;
+
+        activation.context.avm2.file_loads.push(this);
+        activation.context.executor.spawn_with(
+            std::future::ready(Ok::<_, LoaderError>(())),
+            |context, _| {
+                if let Err(e) = Avm2::finish_file_load(context) {
+                    log::error!("Encountered AVM2 error when loading a file: {}", e);
+                }
+                Ok(())
+            },
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.name`.
+pub fn name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        contents(activation, this)?;
+        return get_slot(activation, this, "name");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.size`.
+pub fn size<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let contents = contents(activation, this)?;
+        return bytearray::length(activation, Some(contents), &[]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.type`, which is the file's extension.
+pub fn file_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        contents(activation, this)?;
+        let name = get_slot(activation, this, "name")?.coerce_to_string(activation)?;
+        return Ok(match name.as_str().rfind('.') {
+            Some(index) => {
+                AvmString::new(activation.context.gc_context, &name.as_str()[index..]).into()
+            }
+            None => Value::Null,
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `FileReference.data`, which is `null` until the file is
+/// loaded.
+pub fn data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return match get_slot(activation, this, "data")? {
+            Value::Object(data) => Ok(data.into()),
+            _ => Ok(Value::Null),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Tell the `FileReference` that opened the last file dialog what the user
+/// picked, or that they cancelled.
+pub fn finish_file_dialog<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    file: Option<DialogFile>,
+) -> Result<(), Error> {
+    let session = match activation.context.avm2.file_session.take() {
+        Some(session) => session,
+        None => return Ok(()),
+    };
+    if let FileSessionKind::Download { .. } = session.kind {
+        // The download hasn't finished, so no dialog is open yet.
+        activation.context.avm2.file_session = Some(session);
+        return Ok(());
+    }
+    let target = session.target;
+
+    let file = match file {
+        Some(file) => file,
+        None => return dispatch_file_event(activation, target, "cancel"),
+    };
+
+    let name = AvmString::new(activation.context.gc_context, file.name);
+    set_slot(activation, target, "name", name.into())?;
+    let contents = new_bytearray(activation, &file.data)?;
+    set_slot(activation, target, "contents", contents.into())?;
+    set_slot(activation, target, "data", Value::Null)?;
+
+    dispatch_file_event(activation, target, "select")?;
+    if let FileSessionKind::Save = session.kind {
+        dispatch_file_event(activation, target, "complete")?;
+    }
+
+    Ok(())
+}
+
+/// Ask the UI where to save a file that finished downloading.
+pub fn finish_file_download<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    response: Result<Vec<u8>, LoaderError>,
+) -> Result<(), Error> {
+    let (target, default_name) = match activation.context.avm2.file_session.take() {
+        Some(FileSession {
+            target,
+            kind: FileSessionKind::Download { default_name, .. },
+        }) => (target, default_name),
+        session => {
+            activation.context.avm2.file_session = session;
+            return Ok(());
+        }
+    };
+
+    match response {
+        Ok(data) => {
+            if activation
+                .context
+                .ui
+                .display_save_file_dialog(&default_name, &data)
+            {
+                activation.context.avm2.file_session = Some(FileSession {
+                    target,
+                    kind: FileSessionKind::Save,
+                });
+            } else {
+                log::warn!("FileReference.download: the UI can't show file dialogs");
+            }
+            Ok(())
+        }
+        Err(_) => dispatch_io_error_event(activation, target, "Error #2038: File I/O Error."),
+    }
+}
+
+/// Hand the contents of a file to the `FileReference` that asked to load it
+/// first.
+pub fn finish_file_load<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<(), Error> {
+    if activation.context.avm2.file_loads.is_empty() {
+        return Ok(());
+    }
+    let target = activation.context.avm2.file_loads.remove(0);
+
+    let contents = contents(activation, target)?;
+    let data = match contents.as_bytearray() {
+        Some(bytearray) => bytearray.bytes().clone(),
+        None => Vec::new(),
+    };
+    let len = data.len();
+    let data = new_bytearray(activation, &data)?;
+    set_slot(activation, target, "data", data.into())?;
+
+    dispatch_file_event(activation, target, "open")?;
+    dispatch_progress_event(activation, target, "progress", len, len)?;
+    dispatch_file_event(activation, target, "complete")
+}
+
+/// Construct `FileReference`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "FileReference"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "browse"),
+        Method::from_builtin(browse),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "save"),
+        Method::from_builtin(save),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "download"),
+        Method::from_builtin(download),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "cancel"),
+        Method::from_builtin(cancel),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "load"),
+        Method::from_builtin(load),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "name"),
+        Method::from_builtin(name),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "size"),
+        Method::from_builtin(size),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "type"),
+        Method::from_builtin(file_type),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "data"),
+        Method::from_builtin(data),
+    ));
+
+    // The name of the file that was picked.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "name"),
+        QName::new(Namespace::public(), "String").into(),
+        None,
+    ));
+    // The whole file, which is read as soon as it is picked.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "contents"),
+        QName::new(Namespace::package("flash.utils"), "ByteArray").into(),
+        None,
+    ));
+    // The contents that `load` handed to the movie.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "data"),
+        QName::new(Namespace::package("flash.utils"), "ByteArray").into(),
+        None,
+    ));
+
+    class
+}
@@ -0,0 +1,456 @@
+//! `flash.net.LocalConnection` builtin/prototype
+
+use crate::amf::ScriptValue;
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, ScriptObject, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::local_connection::{
+    domain as movie_domain, LocalConnectionManager, LocalConnectionObject,
+};
+use gc_arena::{GcCell, MutationContext};
+
+/// How deeply nested objects may be before the rest of them is left out of a
+/// message, which keeps objects that contain themselves from being sent
+/// forever.
+const MAX_DEPTH: usize = 64;
+
+/// Implements `flash.net.LocalConnection`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let array_proto = activation.context.avm2.prototypes().array;
+        let allowed_domains = ArrayObject::from_array(
+            ArrayStorage::new(0),
+            array_proto,
+            activation.context.gc_context,
+        );
+        this.init_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "allowedDomains"),
+            allowed_domains.into(),
+            activation,
+        )?;
+        this.init_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "client"),
+            this.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.LocalConnection`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// The array of domains that a connection accepts messages from, besides its
+/// own.
+fn allowed_domains<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "allowedDomains"),
+        activation,
+    )?
+    .coerce_to_object(activation)
+}
+
+/// Implements `LocalConnection.connect`.
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if name.as_str().contains(':') {
+            return Err("ArgumentError: Error #2077: The connection name is not valid.".into());
+        }
+
+        if !LocalConnectionManager::connect(
+            &mut activation.context,
+            LocalConnectionObject::Avm2(this),
+            name.as_str(),
+        ) {
+            return Err(
+                "ArgumentError: Error #2082: Connect failed because the object is already connected."
+                    .into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.send`.
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let method = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let args = args.get(2..).unwrap_or(&[]);
+        let args = args
+            .iter()
+            .map(|arg| to_script_value(activation, arg, 0))
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        LocalConnectionManager::send(
+            &mut activation.context,
+            LocalConnectionObject::Avm2(this),
+            name.as_str(),
+            method.as_str(),
+            &args,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if !LocalConnectionManager::close(
+            &mut activation.context,
+            LocalConnectionObject::Avm2(this),
+        ) {
+            return Err(
+                "ArgumentError: Error #2083: Close failed because the object is not connected."
+                    .into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.allowDomain` and `allowInsecureDomain`.
+pub fn allow_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let allowed_domains = allowed_domains(activation, this)?;
+        for domain in args {
+            let domain = domain.coerce_to_string(activation)?;
+            if let Some(mut allowed_domains) =
+                allowed_domains.as_array_storage_mut(activation.context.gc_context)
+            {
+                allowed_domains.push(domain.into());
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.domain`.
+pub fn domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let domain = movie_domain(activation.context.swf);
+
+    Ok(AvmString::new(activation.context.gc_context, domain).into())
+}
+
+/// Implements `LocalConnection.client`'s getter.
+pub fn client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "client"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.client`'s setter.
+pub fn set_client<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let client = match args.get(0) {
+            Some(Value::Object(client)) => *client,
+            _ => return Err("TypeError: Error #2004: One of the parameters is invalid.".into()),
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "client"),
+            client.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `LocalConnection.isSupported`.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Call the method named by a message on a connection's client.
+///
+/// Messages from other domains are only received if the connection allowed
+/// the sender's domain.
+pub fn receive_local_connection_message<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    sender_domain: &str,
+    method: &str,
+    args: &[ScriptValue],
+) -> Result<(), Error> {
+    if sender_domain != movie_domain(activation.context.swf) {
+        let allowed_domains = allowed_domains(activation, target)?;
+        let is_allowed = match allowed_domains.as_array_storage() {
+            Some(allowed_domains) => allowed_domains.iter().any(|domain| match domain {
+                Some(Value::String(domain)) => {
+                    domain.as_str() == "*" || domain.as_str().eq_ignore_ascii_case(sender_domain)
+                }
+                _ => false,
+            }),
+            None => false,
+        };
+        if !is_allowed {
+            log::warn!("LocalConnection: ignored a message from {}", sender_domain);
+            return Ok(());
+        }
+    }
+
+    let mut client = client(activation, Some(target), &[])?.coerce_to_object(activation)?;
+    let method_name = QName::new(
+        Namespace::public(),
+        AvmString::new(activation.context.gc_context, method),
+    );
+    let handler = match client.get_property(client, &method_name, activation)? {
+        Value::Object(handler) => handler,
+        _ => {
+            log::warn!("LocalConnection: no method {} to receive a message", method);
+            return Ok(());
+        }
+    };
+
+    let args: Vec<_> = args
+        .iter()
+        .map(|arg| from_script_value(activation, arg))
+        .collect();
+    handler.call(Some(client), &args, activation, None)?;
+
+    Ok(())
+}
+
+/// Convert an argument of `send` into an AMF0 value. Functions can't be sent,
+/// and become `undefined`.
+fn to_script_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: &Value<'gc>,
+    depth: usize,
+) -> Result<ScriptValue, Error> {
+    Ok(match value {
+        Value::Undefined => ScriptValue::Undefined,
+        Value::Null => ScriptValue::Null,
+        Value::Bool(b) => ScriptValue::Boolean(*b),
+        Value::Number(n) => ScriptValue::Number(*n),
+        Value::Unsigned(n) => ScriptValue::Number((*n).into()),
+        Value::Integer(n) => ScriptValue::Number((*n).into()),
+        Value::String(s) => ScriptValue::String(s.to_string()),
+        Value::Object(_) if depth >= MAX_DEPTH => ScriptValue::Undefined,
+        Value::Object(object) => {
+            if object.as_executable().is_some() {
+                return Ok(ScriptValue::Undefined);
+            }
+
+            let elements: Option<Vec<_>> = object.as_array_storage().map(|array| {
+                array
+                    .iter()
+                    .map(|v| v.unwrap_or(Value::Undefined))
+                    .collect()
+            });
+            if let Some(elements) = elements {
+                let elements = elements
+                    .iter()
+                    .map(|element| to_script_value(activation, element, depth + 1))
+                    .collect::<Result<_, Error>>()?;
+                return Ok(ScriptValue::StrictArray(elements));
+            }
+
+            let mut object = *object;
+            let mut properties = vec![];
+            let mut index = 1;
+            while let Some(name) = object.get_enumerant_name(index) {
+                let property = object.get_property(object, &name, activation)?;
+                let property = to_script_value(activation, &property, depth + 1)?;
+                properties.push((name.local_name().to_string(), property));
+                index += 1;
+            }
+            ScriptValue::Object(properties)
+        }
+    })
+}
+
+/// Convert an AMF0 value into an AVM2 value. Dates become the number of
+/// milliseconds since the Unix epoch.
+fn from_script_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: &ScriptValue,
+) -> Value<'gc> {
+    let gc_context = activation.context.gc_context;
+    match value {
+        ScriptValue::Number(n) | ScriptValue::Date(n) => (*n).into(),
+        ScriptValue::Boolean(b) => (*b).into(),
+        ScriptValue::String(s) => AvmString::new(gc_context, s.clone()).into(),
+        ScriptValue::Object(properties) => {
+            let object_proto = activation.context.avm2.prototypes().object;
+            let mut object = ScriptObject::object(gc_context, object_proto);
+            for (name, value) in properties {
+                let value = from_script_value(activation, value);
+                let name = QName::new(
+                    Namespace::public(),
+                    AvmString::new(activation.context.gc_context, name.clone()),
+                );
+                let _ = object.set_property(object, &name, value, activation);
+            }
+            object.into()
+        }
+        ScriptValue::StrictArray(values) => {
+            let values: Vec<_> = values
+                .iter()
+                .map(|value| from_script_value(activation, value))
+                .collect();
+            let array_proto = activation.context.avm2.prototypes().array;
+            ArrayObject::from_array(
+                ArrayStorage::from_args(&values),
+                array_proto,
+                activation.context.gc_context,
+            )
+            .into()
+        }
+        ScriptValue::Null => Value::Null,
+        ScriptValue::Undefined => Value::Undefined,
+    }
+}
+
+/// Construct `LocalConnection`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "LocalConnection"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isSupported"),
+        Method::from_builtin(is_supported),
+    ));
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "connect"),
+        Method::from_builtin(connect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "send"),
+        Method::from_builtin(send),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "close"),
+        Method::from_builtin(close),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "allowDomain"),
+        Method::from_builtin(allow_domain),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "allowInsecureDomain"),
+        Method::from_builtin(allow_domain),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "domain"),
+        Method::from_builtin(domain),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "client"),
+        Method::from_builtin(client),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "client"),
+        Method::from_builtin(set_client),
+    ));
+
+    // Slots for the domains that messages are accepted from, and the object
+    // whose methods messages call.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "allowedDomains"),
+        QName::new(Namespace::public(), "Array").into(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "client"),
+        QName::new(Namespace::public(), "Object").into(),
+        None,
+    ));
+
+    class
+}
@@ -0,0 +1,335 @@
+//! `flash.net.Socket` builtin/prototype
+//!
+//! A `Socket` is a `ByteArray` underneath, which holds the data received so
+//! far, so that it can share `ByteArray`'s methods for reading. Data written
+//! to the socket goes into a second `ByteArray` until it is flushed.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::events::progressevent::dispatch_progress_event;
+use crate::avm2::globals::flash::utils::bytearray;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::socket::{SocketManager, SocketObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.Socket`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+        let output = bytearray_proto.construct(activation, &[])?;
+        bytearray::instance_init(activation, Some(output), &[])?;
+        this.init_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "output"),
+            output.into(),
+            activation,
+        )?;
+
+        if let Some(Value::Object(_)) | Some(Value::String(_)) = args.get(0) {
+            connect(activation, Some(this), args)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.Socket`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// The `ByteArray` that holds the data written to a socket.
+fn output<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Object<'gc>, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "output"),
+        activation,
+    )?
+    .coerce_to_object(activation)
+}
+
+/// Implements `Socket.connect`.
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let host = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let port = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        if port <= 0 || port > 65535 {
+            return Err("SecurityError: Error #2003: Invalid socket port number specified.".into());
+        }
+
+        if !SocketManager::connect(
+            &mut activation.context,
+            SocketObject::Avm2Socket(this),
+            &host,
+            port as u16,
+        ) {
+            return Err(
+                "SecurityError: Error #2048: Security sandbox violation: cannot connect to socket."
+                    .into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        SocketManager::close(&mut activation.context, SocketObject::Avm2Socket(this));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.connected`.
+pub fn connected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(activation
+            .context
+            .socket_manager
+            .is_connected(SocketObject::Avm2Socket(this))
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.flush`.
+pub fn flush<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let output = output(activation, this)?;
+        let data = match output.as_bytearray_mut(activation.context.gc_context) {
+            Some(mut bytearray) => {
+                let data = bytearray.bytes().clone();
+                bytearray.clear();
+                data
+            }
+            None => return Ok(Value::Undefined),
+        };
+
+        if !SocketManager::send(
+            &mut activation.context,
+            SocketObject::Avm2Socket(this),
+            &data,
+        ) {
+            return Err("IOError: Error #2002: Operation attempted on invalid socket.".into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.bytesPending`.
+pub fn bytes_pending<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let output = output(activation, this)?;
+        return bytearray::length(activation, Some(output), &[]);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Socket.endian`'s setter, which applies to data read and
+/// written alike.
+pub fn set_endian<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        bytearray::set_endian(activation, Some(this), args)?;
+        let output = output(activation, this)?;
+        bytearray::set_endian(activation, Some(output), args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Write to a socket's output with one of `ByteArray`'s methods.
+fn write_output<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+    method: NativeMethod<'gc>,
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let output = output(activation, this)?;
+        method(activation, Some(output), args)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Append data received on a socket to it, and dispatch a `socketData` event.
+pub fn receive_socket_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    data: &[u8],
+) -> Result<(), Error> {
+    if let Some(mut bytearray) = target.as_bytearray_mut(activation.context.gc_context) {
+        // Drop data that was already read, so that the buffer doesn't grow
+        // for as long as the connection is open.
+        if bytearray.position() >= bytearray.bytes().len() {
+            bytearray.clear();
+        }
+        let len = bytearray.bytes().len();
+        bytearray.write_bytes_at(data, len);
+    }
+
+    dispatch_progress_event(activation, target, "socketData", data.len(), 0)
+}
+
+/// Construct `Socket`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "Socket"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "connect"),
+        Method::from_builtin(connect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "close"),
+        Method::from_builtin(close),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "flush"),
+        Method::from_builtin(flush),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "connected"),
+        Method::from_builtin(connected),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesAvailable"),
+        Method::from_builtin(bytearray::bytes_available),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesPending"),
+        Method::from_builtin(bytes_pending),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "endian"),
+        Method::from_builtin(bytearray::endian),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "endian"),
+        Method::from_builtin(set_endian),
+    ));
+
+    let read_methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("readBoolean", bytearray::read_boolean),
+        ("readByte", bytearray::read_byte),
+        ("readBytes", bytearray::read_bytes),
+        ("readDouble", bytearray::read_double),
+        ("readFloat", bytearray::read_float),
+        ("readInt", bytearray::read_int),
+        ("readMultiByte", bytearray::read_multibyte),
+        ("readShort", bytearray::read_short),
+        ("readUnsignedByte", bytearray::read_unsigned_byte),
+        ("readUnsignedInt", bytearray::read_unsigned_int),
+        ("readUnsignedShort", bytearray::read_unsigned_short),
+        ("readUTF", bytearray::read_utf),
+        ("readUTFBytes", bytearray::read_utf_bytes),
+    ];
+
+    for (name, method) in read_methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public(), *name),
+            Method::from_builtin(*method),
+        ));
+    }
+
+    macro_rules! write_method {
+        ($($name:expr => $f:expr),*) => {{
+            $(
+                write.define_instance_trait(Trait::from_method(
+                    QName::new(Namespace::public(), $name),
+                    Method::from_builtin(|activation, this, args| {
+                        write_output(activation, this, args, $f)
+                    }),
+                ));
+            )*
+        }};
+    }
+
+    write_method!(
+        "writeBoolean" => bytearray::write_boolean,
+        "writeByte" => bytearray::write_byte,
+        "writeBytes" => bytearray::write_bytes,
+        "writeDouble" => bytearray::write_double,
+        "writeFloat" => bytearray::write_float,
+        "writeInt" => bytearray::write_int,
+        "writeMultiByte" => bytearray::write_multibyte,
+        "writeShort" => bytearray::write_short,
+        "writeUnsignedInt" => bytearray::write_unsigned_int,
+        "writeUTF" => bytearray::write_utf,
+        "writeUTFBytes" => bytearray::write_utf_bytes
+    );
+
+    // Slot for the `ByteArray` that holds data until it is flushed.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "output"),
+        QName::new(Namespace::package("flash.utils"), "ByteArray").into(),
+        None,
+    ));
+
+    class
+}
@@ -0,0 +1,355 @@
+//! `flash.net.URLStream` builtin/prototype
+//!
+//! Like `Socket`, a `URLStream` is a `ByteArray` underneath, which holds the
+//! data received so far so that it can share `ByteArray`'s methods for
+//! reading. Data is appended as it is delivered, with a `progress` event for
+//! each chunk, and can be read while the rest of the download is in flight.
+//!
+//! How large the chunks are is up to the navigator; one that can't stream
+//! responses hands each one over whole, as a single chunk.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::globals::flash::events::ioerrorevent::dispatch_io_error_event;
+use crate::avm2::globals::flash::events::progressevent::dispatch_progress_event;
+use crate::avm2::globals::flash::utils::bytearray;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use crate::backend::navigator::Chunk;
+use crate::executor::TaskHandle;
+use crate::loader::Error as LoaderError;
+use crate::net::{self, Request};
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// A `URLStream` that is waiting for its response.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct UrlStreamLoad<'gc> {
+    target: Object<'gc>,
+
+    /// The task fetching the response.
+    #[collect(require_static)]
+    task: TaskHandle,
+
+    /// Whether the `open` event was dispatched, which happens when the first
+    /// chunk of the response arrives.
+    opened: bool,
+}
+
+/// Implements `flash.net.URLStream`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.URLStream`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Stop waiting for the response to a `URLStream`'s load, if it has one.
+///
+/// Returns `false` if the stream wasn't loading.
+fn cancel_load<'gc>(activation: &mut Activation<'_, 'gc, '_>, this: Object<'gc>) -> bool {
+    let loads = &mut activation.context.avm2.url_stream_loads;
+    match loads
+        .iter()
+        .position(|load| Object::ptr_eq(load.target, this))
+    {
+        Some(index) => {
+            let load = loads.remove(index);
+            activation.context.executor.cancel(load.task);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Build the request described by a `URLRequest`.
+///
+/// POST requests send the request's `data`, as the bytes of a `ByteArray` or
+/// as a string.
+fn to_request<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut request: Object<'gc>,
+) -> Result<Request, Error> {
+    let url = request
+        .get_property(request, &QName::new(Namespace::public(), "url"), activation)?
+        .coerce_to_string(activation)?
+        .to_string();
+    let method = request
+        .get_property(
+            request,
+            &QName::new(Namespace::public(), "method"),
+            activation,
+        )?
+        .coerce_to_string(activation)?;
+    if !method.eq_ignore_ascii_case("POST") {
+        return Ok(Request::get(url));
+    }
+
+    let body = match request.get_property(
+        request,
+        &QName::new(Namespace::public(), "data"),
+        activation,
+    )? {
+        Value::Undefined | Value::Null => None,
+        Value::Object(data) if data.as_bytearray().is_some() => data
+            .as_bytearray()
+            .map(|bytearray| bytearray.bytes().clone()),
+        data => Some(data.coerce_to_string(activation)?.to_string().into_bytes()),
+    };
+    let content_type = match request.get_property(
+        request,
+        &QName::new(Namespace::public(), "contentType"),
+        activation,
+    )? {
+        Value::Undefined | Value::Null => "application/x-www-form-urlencoded".to_string(),
+        content_type => content_type.coerce_to_string(activation)?.to_string(),
+    };
+
+    Ok(Request::post(url, body.map(|body| (body, content_type))))
+}
+
+/// Implements `URLStream.load`.
+///
+/// Any load that is already in progress is abandoned, and the data it
+/// received is discarded.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let request = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Object(request) => to_request(activation, request)?,
+            _ => return Err("TypeError: Error #2007: Parameter request must be non-null.".into()),
+        };
+
+        cancel_load(activation, this);
+        if let Some(mut bytearray) = this.as_bytearray_mut(activation.context.gc_context) {
+            bytearray.clear();
+        }
+
+        let chunks = net::fetch_data_chunked(&activation.context, request);
+        let handle = activation.context.executor.next_handle();
+        let task = activation
+            .context
+            .executor
+            .spawn_stream(chunks, move |context, chunk| {
+                if let Err(e) = Avm2::receive_url_stream_chunk(context, handle, chunk) {
+                    log::error!("Encountered AVM2 error when loading a URLStream: {}", e);
+                }
+                Ok(())
+            });
+        activation
+            .context
+            .avm2
+            .url_stream_loads
+            .push(UrlStreamLoad {
+                target: this,
+                task,
+                opened: false,
+            });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if !cancel_load(activation, this) {
+            return Err(
+                "IOError: Error #2029: This URLStream object does not have a stream opened.".into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLStream.connected`.
+pub fn connected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(activation
+            .context
+            .avm2
+            .url_stream_loads
+            .iter()
+            .any(|load| Object::ptr_eq(load.target, this))
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Dispatch a plain `Event` of the given type to a `URLStream`.
+fn dispatch_stream_event<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+) -> Result<(), Error> {
+    let event_proto = activation.context.avm2.prototypes().event;
+    let event = EventObject::from_event(
+        activation.context.gc_context,
+        Some(event_proto),
+        Event::new(event_type),
+    );
+    dispatch_event(activation, target, event)?;
+
+    Ok(())
+}
+
+/// Append a chunk of a download to a `URLStream`, and dispatch a `progress`
+/// event for it.
+fn receive_url_stream_data<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    data: &[u8],
+    bytes_total: usize,
+) -> Result<(), Error> {
+    let bytes_loaded = match target.as_bytearray_mut(activation.context.gc_context) {
+        Some(mut bytearray) => {
+            let len = bytearray.bytes().len();
+            bytearray.write_bytes_at(data, len);
+            len + data.len()
+        }
+        None => return Ok(()),
+    };
+
+    dispatch_progress_event(activation, target, "progress", bytes_loaded, bytes_total)
+}
+
+/// Deliver a chunk of the response to a `URLStream`'s load, or finish the
+/// load once the response has ended (`None`) or failed.
+pub fn receive_url_stream_chunk<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    task: TaskHandle,
+    chunk: Option<Result<Chunk, LoaderError>>,
+) -> Result<(), Error> {
+    let loads = &mut activation.context.avm2.url_stream_loads;
+    let index = match loads.iter().position(|load| load.task == task) {
+        Some(index) => index,
+        None => return Ok(()),
+    };
+    let target = loads[index].target;
+    let opened = std::mem::replace(&mut loads[index].opened, true);
+    if matches!(chunk, None | Some(Err(_))) {
+        loads.remove(index);
+    }
+
+    match chunk {
+        Some(Ok(chunk)) => {
+            if !opened {
+                dispatch_stream_event(activation, target, "open")?;
+            }
+            receive_url_stream_data(
+                activation,
+                target,
+                &chunk.data,
+                chunk.total_len.unwrap_or(0),
+            )
+        }
+        None => {
+            if !opened {
+                dispatch_stream_event(activation, target, "open")?;
+            }
+            dispatch_stream_event(activation, target, "complete")
+        }
+        Some(Err(e)) => dispatch_io_error_event(
+            activation,
+            target,
+            &format!("Error #2032: Stream Error. {}", e),
+        ),
+    }
+}
+
+/// Construct `URLStream`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "URLStream"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "load"),
+        Method::from_builtin(load),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "close"),
+        Method::from_builtin(close),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "connected"),
+        Method::from_builtin(connected),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "bytesAvailable"),
+        Method::from_builtin(bytearray::bytes_available),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "endian"),
+        Method::from_builtin(bytearray::endian),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "endian"),
+        Method::from_builtin(bytearray::set_endian),
+    ));
+
+    let read_methods: &[(&str, NativeMethod<'gc>)] = &[
+        ("readBoolean", bytearray::read_boolean),
+        ("readByte", bytearray::read_byte),
+        ("readBytes", bytearray::read_bytes),
+        ("readDouble", bytearray::read_double),
+        ("readFloat", bytearray::read_float),
+        ("readInt", bytearray::read_int),
+        ("readMultiByte", bytearray::read_multibyte),
+        ("readShort", bytearray::read_short),
+        ("readUnsignedByte", bytearray::read_unsigned_byte),
+        ("readUnsignedInt", bytearray::read_unsigned_int),
+        ("readUnsignedShort", bytearray::read_unsigned_short),
+        ("readUTF", bytearray::read_utf),
+        ("readUTFBytes", bytearray::read_utf_bytes),
+    ];
+
+    for (name, method) in read_methods {
+        write.define_instance_trait(Trait::from_method(
+            QName::new(Namespace::public(), *name),
+            Method::from_builtin(*method),
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,166 @@
+//! `flash.net.XMLSocket` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::socket::{SocketManager, SocketObject};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.XMLSocket`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+
+        if let Some(Value::Object(_)) | Some(Value::String(_)) = args.get(0) {
+            connect(activation, Some(this), args)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.XMLSocket`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLSocket.connect`.
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let host = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let port = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_i32(activation)?;
+        if port <= 0 || port > 65535 {
+            return Err("SecurityError: Error #2003: Invalid socket port number specified.".into());
+        }
+
+        if !SocketManager::connect(
+            &mut activation.context,
+            SocketObject::Avm2XmlSocket(this),
+            &host,
+            port as u16,
+        ) {
+            return Err(
+                "SecurityError: Error #2048: Security sandbox violation: cannot connect to socket."
+                    .into(),
+            );
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLSocket.send`.
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let message = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let mut data = message.as_str().as_bytes().to_vec();
+        data.push(0);
+
+        if !SocketManager::send(
+            &mut activation.context,
+            SocketObject::Avm2XmlSocket(this),
+            &data,
+        ) {
+            return Err("IOError: Error #2002: Operation attempted on invalid socket.".into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLSocket.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        SocketManager::close(&mut activation.context, SocketObject::Avm2XmlSocket(this));
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `XMLSocket.connected`.
+pub fn connected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return Ok(activation
+            .context
+            .socket_manager
+            .is_connected(SocketObject::Avm2XmlSocket(this))
+            .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `XMLSocket`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "XMLSocket"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "connect"),
+        Method::from_builtin(connect),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "send"),
+        Method::from_builtin(send),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "close"),
+        Method::from_builtin(close),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "connected"),
+        Method::from_builtin(connected),
+    ));
+
+    class
+}
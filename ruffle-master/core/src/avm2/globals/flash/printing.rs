@@ -0,0 +1,5 @@
+//! `flash.printing` namespace
+
+pub mod printjob;
+pub mod printjoboptions;
+pub mod printjoborientation;
@@ -0,0 +1,360 @@
+//! `flash.printing.PrintJob` builtin/prototype
+//!
+//! The paper picked when a job is started is kept in private slots, and read
+//! back from them when pages are added.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::geom::rectangle::rect;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::print::{PageOrientation, PageSettings};
+use crate::bounding_box::BoundingBox;
+use crate::print_job;
+use gc_arena::{GcCell, MutationContext};
+use swf::Twips;
+
+/// Implements `flash.printing.PrintJob`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.printing.PrintJob`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read one of the private slots of a `PrintJob`.
+fn get_slot<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    name: &'static str,
+) -> Result<Value<'gc>, Error> {
+    this.get_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), name),
+        activation,
+    )
+}
+
+/// Write one of the private slots of a `PrintJob`.
+fn set_slot<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+    name: &'static str,
+    value: Value<'gc>,
+) -> Result<(), Error> {
+    this.set_property(
+        this,
+        &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), name),
+        value,
+        activation,
+    )
+}
+
+/// The paper of a job that was started, or `None` if it wasn't.
+fn page_settings<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+) -> Result<Option<PageSettings>, Error> {
+    if !get_slot(activation, this, "started")?.coerce_to_boolean() {
+        return Ok(None);
+    }
+
+    let mut size = |name| -> Result<u32, Error> {
+        get_slot(activation, this, name)?.coerce_to_u32(activation)
+    };
+    let page_width = size("pageWidth")?;
+    let page_height = size("pageHeight")?;
+    let paper_width = size("paperWidth")?;
+    let paper_height = size("paperHeight")?;
+    let orientation = match get_slot(activation, this, "orientation")?
+        .coerce_to_string(activation)?
+        .as_str()
+    {
+        "landscape" => PageOrientation::Landscape,
+        _ => PageOrientation::Portrait,
+    };
+
+    Ok(Some(PageSettings {
+        page_width,
+        page_height,
+        paper_width,
+        paper_height,
+        orientation,
+    }))
+}
+
+/// Implements `PrintJob.start`.
+pub fn start<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let settings = match print_job::start(&mut activation.context) {
+            Some(settings) => settings,
+            None => return Ok(false.into()),
+        };
+
+        set_slot(activation, this, "pageWidth", settings.page_width.into())?;
+        set_slot(activation, this, "pageHeight", settings.page_height.into())?;
+        set_slot(activation, this, "paperWidth", settings.paper_width.into())?;
+        set_slot(
+            activation,
+            this,
+            "paperHeight",
+            settings.paper_height.into(),
+        )?;
+        set_slot(
+            activation,
+            this,
+            "orientation",
+            settings.orientation.as_str().into(),
+        )?;
+        set_slot(activation, this, "started", true.into())?;
+
+        return Ok(true.into());
+    }
+
+    Ok(false.into())
+}
+
+/// Implements `PrintJob.addPage`.
+pub fn add_page<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let settings = match page_settings(activation, this)? {
+            Some(settings) => settings,
+            None => {
+                return Err(
+                    "Error: Error #2057: The page could not be added to the print job.".into(),
+                )
+            }
+        };
+
+        let sprite = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Object(sprite) => sprite.as_display_object(),
+            _ => None,
+        };
+        let sprite = match sprite {
+            Some(sprite) => sprite,
+            None => return Err("TypeError: Error #2007: Parameter sprite must be non-null.".into()),
+        };
+
+        let area = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Object(print_area) => {
+                let (x, y, width, height) = rect(print_area, activation)?;
+                Some(BoundingBox {
+                    x_min: Twips::from_pixels(x),
+                    y_min: Twips::from_pixels(y),
+                    x_max: Twips::from_pixels(x + width),
+                    y_max: Twips::from_pixels(y + height),
+                    valid: true,
+                })
+            }
+            _ => None,
+        };
+
+        let frame_num = args
+            .get(3)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+        if frame_num > 0 {
+            log::warn!(
+                "PrintJob.addPage: printing a frame other than the current one is not supported"
+            );
+        }
+
+        if !print_job::add_page(&mut activation.context, sprite, area, &settings) {
+            return Err("Error: Error #2057: The page could not be added to the print job.".into());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.send`.
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if get_slot(activation, this, "started")?.coerce_to_boolean() {
+            print_job::send(&mut activation.context);
+            set_slot(activation, this, "started", false.into())?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.pageWidth`.
+pub fn page_width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_slot(activation, this, "pageWidth");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.pageHeight`.
+pub fn page_height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_slot(activation, this, "pageHeight");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.paperWidth`.
+pub fn paper_width<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_slot(activation, this, "paperWidth");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.paperHeight`.
+pub fn paper_height<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_slot(activation, this, "paperHeight");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.orientation`.
+pub fn orientation<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        return get_slot(activation, this, "orientation");
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `PrintJob.isSupported`.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Construct `PrintJob`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.printing"), "PrintJob"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isSupported"),
+        Method::from_builtin(is_supported),
+    ));
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "start"),
+        Method::from_builtin(start),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "addPage"),
+        Method::from_builtin(add_page),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "send"),
+        Method::from_builtin(send),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "pageWidth"),
+        Method::from_builtin(page_width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "pageHeight"),
+        Method::from_builtin(page_height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "paperWidth"),
+        Method::from_builtin(paper_width),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "paperHeight"),
+        Method::from_builtin(paper_height),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "orientation"),
+        Method::from_builtin(orientation),
+    ));
+
+    // Whether `start` picked the paper of a job that hasn't been sent yet.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "started"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        Some(false.into()),
+    ));
+    for name in &["pageWidth", "pageHeight", "paperWidth", "paperHeight"] {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::private(NS_RUFFLE_INTERNAL), *name),
+            QName::new(Namespace::public(), "int").into(),
+            Some(0.into()),
+        ));
+    }
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "orientation"),
+        QName::new(Namespace::public(), "String").into(),
+        None,
+    ));
+
+    class
+}
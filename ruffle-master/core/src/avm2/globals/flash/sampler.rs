@@ -0,0 +1,145 @@
+//! `flash.sampler` namespace
+//!
+//! Ruffle does not implement the memory sampler of the Flash debug player.
+//! Sampling can be started and stopped, but no samples are ever recorded, so
+//! `Sample` and `NewObjectSample` are never instantiated by the player.
+//!
+//! Our garbage collector does not keep per-object allocation statistics
+//! either, so `getSize` adds up the sizes of the structures that the collector
+//! allocates for the value instead. It does not include allocator overhead or
+//! anything the value shares with other objects, such as its class.
+
+use crate::avm2::globals::qname::create_qname;
+use crate::avm2::object::ScriptObjectData;
+use crate::avm2::property::Property;
+use crate::avm2::{Activation, ArrayObject, ArrayStorage, Error, Object, QName, TObject, Value};
+use std::mem::size_of;
+
+pub mod newobjectsample;
+pub mod sample;
+
+/// The size of a value, which is stored inline wherever it is held.
+const VALUE_SIZE: u64 = size_of::<Value<'_>>() as u64;
+
+/// The size of an object's own storage, before any of its properties.
+const OBJECT_SIZE: u64 = size_of::<ScriptObjectData<'_>>() as u64;
+
+/// The size of a single named property of an object.
+const PROPERTY_SIZE: u64 = (size_of::<QName<'_>>() + size_of::<Property<'_>>()) as u64;
+
+/// Add up the sizes of the structures that the garbage collector allocates
+/// for an AVM2 value.
+///
+/// The arithmetic saturates, as arrays and byte arrays can be large enough
+/// for the product to overflow.
+fn estimate_size(value: &Value<'_>) -> u64 {
+    match value {
+        Value::String(s) => VALUE_SIZE.saturating_add(s.len() as u64),
+        Value::Object(object) => {
+            let mut size = OBJECT_SIZE;
+
+            if let Some(array) = object.as_array_storage() {
+                size = size.saturating_add((array.length() as u64).saturating_mul(VALUE_SIZE));
+            }
+
+            if let Some(bytearray) = object.as_bytearray() {
+                size = size.saturating_add(bytearray.bytes().len() as u64);
+            }
+
+            let mut index = 1;
+            while object.get_enumerant_name(index).is_some() {
+                size = size.saturating_add(PROPERTY_SIZE);
+                index += 1;
+            }
+
+            if let Some(class) = object.as_proto_class() {
+                let traits = class.read().instance_traits().len() as u64;
+                size = size.saturating_add(traits.saturating_mul(PROPERTY_SIZE));
+            }
+
+            size
+        }
+        _ => VALUE_SIZE,
+    }
+}
+
+/// Implements `flash.sampler.getSize`
+///
+/// The result is an estimate built from the sizes of the value's structures,
+/// see `estimate_size`.
+pub fn get_size<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+
+    Ok((estimate_size(&value) as f64).into())
+}
+
+/// Implements `flash.sampler.getMemberNames`
+///
+/// This returns an array of `QName`s for all instance traits and enumerable
+/// properties of the object. Flash returns an opaque iterable object instead,
+/// but both can be walked with `for each`.
+pub fn get_member_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut names = ArrayStorage::new(0);
+
+    if let Some(Value::Object(object)) = args.get(0) {
+        if let Some(class) = object.as_proto_class() {
+            for instance_trait in class.read().instance_traits() {
+                names.push(create_qname(activation, instance_trait.name())?);
+            }
+        }
+
+        let mut index = 1;
+        while let Some(name) = object.get_enumerant_name(index) {
+            names.push(create_qname(activation, &name)?);
+            index += 1;
+        }
+    }
+
+    Ok(ArrayObject::from_array(
+        names,
+        activation.context.avm2.prototypes().array,
+        activation.context.gc_context,
+    )
+    .into())
+}
+
+/// Implements `flash.sampler.getSamples`
+pub fn get_samples<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(ArrayObject::from_array(
+        ArrayStorage::new(0),
+        activation.context.avm2.prototypes().array,
+        activation.context.gc_context,
+    )
+    .into())
+}
+
+/// Implements `flash.sampler.getSampleCount`
+pub fn get_sample_count<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(0.into())
+}
+
+/// Implements `flash.sampler.startSampling`, `stopSampling`,
+/// `pauseSampling` and `clearSamples`, none of which do anything.
+pub fn sampling_noop<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
@@ -0,0 +1,71 @@
+//! `flash.sampler.NewObjectSample` builtin
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.sampler.NewObjectSample`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.sampler.NewObjectSample`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `NewObjectSample`'s class.
+///
+/// Like `Sample`, this is never instantiated, as no samples are recorded.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.sampler"), "NewObjectSample"),
+        Some(QName::new(Namespace::package("flash.sampler"), "Sample").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.define_instance_trait(Trait::from_const(
+        QName::new(Namespace::public(), "id"),
+        Multiname::from(QName::new(Namespace::public(), "Number")),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_const(
+        QName::new(Namespace::public(), "type"),
+        Multiname::from(QName::new(Namespace::public(), "Class")),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_const(
+        QName::new(Namespace::public(), "object"),
+        Multiname::any(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_const(
+        QName::new(Namespace::public(), "size"),
+        Multiname::from(QName::new(Namespace::public(), "Number")),
+        None,
+    ));
+
+    class
+}
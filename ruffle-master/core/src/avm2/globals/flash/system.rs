@@ -2,4 +2,7 @@
 #![allow(clippy::module_inception)]
 
 pub mod application_domain;
+pub mod loader_context;
+pub mod security;
+pub mod security_domain;
 pub mod system;
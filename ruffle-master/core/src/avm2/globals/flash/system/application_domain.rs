@@ -84,7 +84,7 @@ pub fn get_definition<'gc>(
             .cloned()
             .unwrap_or_else(|| "".into())
             .coerce_to_string(activation)?;
-        let qname = QName::new(Namespace::public(), local_name);
+        let qname = QName::from_qualified_name(&local_name, activation.context.gc_context);
 
         let (qname, mut defined_script) = appdomain
             .get_defining_script(&qname.into())?
@@ -110,7 +110,7 @@ pub fn has_definition<'gc>(
             .cloned()
             .unwrap_or_else(|| "".into())
             .coerce_to_string(activation)?;
-        let qname = QName::new(Namespace::public(), local_name);
+        let qname = QName::from_qualified_name(&local_name, activation.context.gc_context);
 
         return Ok(appdomain.has_definition(qname).into());
     }
@@ -134,15 +134,15 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "currentDomain"),
         Method::from_builtin(current_domain),
     ));
-    write.define_class_trait(Trait::from_getter(
+    write.define_instance_trait(Trait::from_getter(
         QName::new(Namespace::public(), "parentDomain"),
         Method::from_builtin(parent_domain),
     ));
-    write.define_class_trait(Trait::from_method(
+    write.define_instance_trait(Trait::from_method(
         QName::new(Namespace::public(), "getDefinition"),
         Method::from_builtin(get_definition),
     ));
-    write.define_class_trait(Trait::from_method(
+    write.define_instance_trait(Trait::from_method(
         QName::new(Namespace::public(), "hasDefinition"),
         Method::from_builtin(has_definition),
     ));
@@ -0,0 +1,103 @@
+//! `flash.system.LoaderContext` class
+//!
+//! A `LoaderContext` only holds options. Loading a movie with them goes
+//! through `LoadManager::load_movie_into_clip_with_options`, but there is no
+//! AS3 `Loader` to call it yet.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.LoaderContext`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let check_policy_file = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| false.into())
+            .coerce_to_boolean();
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "checkPolicyFile"),
+            check_policy_file.into(),
+            activation,
+        )?;
+
+        for (index, name) in ["applicationDomain", "securityDomain"].iter().enumerate() {
+            let value = match args.get(index + 1).cloned() {
+                Some(Value::Object(domain)) => domain.into(),
+                _ => Value::Null,
+            };
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value,
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.LoaderContext`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `LoaderContext`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "LoaderContext"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    // Whether to load the site's policy file before loading the content.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "checkPolicyFile"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        Some(false.into()),
+    ));
+    // The domain to define the loaded movie's classes in. If null, the movie
+    // gets a new domain of its own.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "applicationDomain"),
+        QName::new(Namespace::package("flash.system"), "ApplicationDomain").into(),
+        Some(Value::Null),
+    ));
+    // There is only one security domain, so this has no effect.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "securityDomain"),
+        QName::new(Namespace::package("flash.system"), "SecurityDomain").into(),
+        Some(Value::Null),
+    ));
+    // Whether content with code, such as movies, may be loaded.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "allowCodeImport"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        Some(true.into()),
+    ));
+
+    class
+}
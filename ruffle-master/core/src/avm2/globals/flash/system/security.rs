@@ -0,0 +1,153 @@
+//! `flash.system.Security` class
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::net;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.system.Security`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.system.Security`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Security.sandboxType`.
+pub fn sandbox_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(activation.context.security.sandbox_type().as_str().into())
+}
+
+/// Implements `Security.allowDomain`.
+pub fn allow_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    for domain in args {
+        let domain = domain.coerce_to_string(activation)?;
+        activation.context.security.allow_domain(&domain, false);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Security.allowInsecureDomain`.
+pub fn allow_insecure_domain<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    for domain in args {
+        let domain = domain.coerce_to_string(activation)?;
+        activation.context.security.allow_domain(&domain, true);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Security.loadPolicyFile`.
+pub fn load_policy_file<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let url = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    net::load_policy_file(&mut activation.context, &url);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Security.showSettings`.
+///
+/// There is no settings panel, so this does nothing.
+pub fn show_settings<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct `Security`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.system"), "Security"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    const CONSTANTS: &[(&str, &str)] = &[
+        ("APPLICATION", "application"),
+        ("LOCAL_TRUSTED", "localTrusted"),
+        ("LOCAL_WITH_FILE", "localWithFile"),
+        ("LOCAL_WITH_NETWORK", "localWithNetwork"),
+        ("REMOTE", "remote"),
+    ];
+
+    for (name, value) in CONSTANTS {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "String").into(),
+            Some((*value).into()),
+        ));
+    }
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "sandboxType"),
+        Method::from_builtin(sandbox_type),
+    ));
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "allowDomain"),
+        Method::from_builtin(allow_domain),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "allowInsecureDomain"),
+        Method::from_builtin(allow_insecure_domain),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "loadPolicyFile"),
+        Method::from_builtin(load_policy_file),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "showSettings"),
+        Method::from_builtin(show_settings),
+    ));
+
+    class
+}
@@ -1,5 +1,7 @@
 //! `flash.text` namespace
 
+pub mod statictext;
+pub mod stylesheet;
 pub mod textfield;
 pub mod textfieldautosize;
 pub mod textfieldtype;
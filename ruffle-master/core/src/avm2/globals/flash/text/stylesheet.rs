@@ -0,0 +1,250 @@
+//! `flash.text.StyleSheet` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::array::build_array;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, ScriptObject, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{AvmString, Error};
+use crate::html::{Style, StyleSheet};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.text.StyleSheet`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.text.StyleSheet`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Read the properties of a style object into a `Style`.
+fn style_from_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+) -> Result<Style, Error> {
+    let mut style = Style::new();
+
+    let mut index = 1;
+    while let Some(name) = object.get_enumerant_name(index) {
+        let value = object
+            .get_property(object, &name, activation)?
+            .coerce_to_string(activation)?;
+        style.insert(name.local_name().to_string(), value.to_string());
+        index += 1;
+    }
+
+    Ok(style)
+}
+
+/// Create a style object with the properties of a `Style`.
+fn style_to_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    style: &Style,
+) -> Result<Object<'gc>, Error> {
+    let mut object = ScriptObject::object(
+        activation.context.gc_context,
+        activation.context.avm2.prototypes().object,
+    );
+
+    for (name, value) in style {
+        object.set_property(
+            object,
+            &QName::new(
+                Namespace::public(),
+                AvmString::new(activation.context.gc_context, name.as_str()),
+            ),
+            AvmString::new(activation.context.gc_context, value.as_str()).into(),
+            activation,
+        )?;
+    }
+
+    Ok(object)
+}
+
+/// Implements `StyleSheet.parseCSS`.
+pub fn parse_css<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let css = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        if let Some(mut style_sheet) = this.as_style_sheet_mut(activation.context.gc_context) {
+            if !style_sheet.parse_css(&css) {
+                log::warn!("StyleSheet.parseCSS: Could not parse CSS");
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.getStyle`.
+pub fn get_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+
+        let style = this
+            .as_style_sheet()
+            .and_then(|style_sheet| style_sheet.style(&name).cloned());
+        if let Some(style) = style {
+            return Ok(style_to_object(activation, &style)?.into());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `StyleSheet.setStyle`.
+pub fn set_style<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let name = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let style = match args.get(1).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            value => {
+                let object = value.coerce_to_object(activation)?;
+                Some(style_from_object(activation, object)?)
+            }
+        };
+
+        if let Some(mut style_sheet) = this.as_style_sheet_mut(activation.context.gc_context) {
+            style_sheet.set_style(&name, style);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.styleNames`'s getter.
+pub fn style_names<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mut names = ArrayStorage::new(0);
+
+    if let Some(this) = this {
+        if let Some(style_sheet) = this.as_style_sheet() {
+            for name in style_sheet.style_names() {
+                names.push(AvmString::new(activation.context.gc_context, name).into());
+            }
+        }
+    }
+
+    build_array(activation, names)
+}
+
+/// Implements `StyleSheet.clear`.
+pub fn clear<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut style_sheet) = this.as_style_sheet_mut(activation.context.gc_context) {
+            style_sheet.clear();
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `StyleSheet.transform`.
+pub fn transform<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let style = match args.get(0).cloned().unwrap_or(Value::Null) {
+        Value::Undefined | Value::Null => return Ok(Value::Null),
+        value => {
+            let object = value.coerce_to_object(activation)?;
+            style_from_object(activation, object)?
+        }
+    };
+
+    Ok(StyleSheet::transform(&style)
+        .as_avm2_object(activation)?
+        .into())
+}
+
+/// Construct `StyleSheet`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.text"), "StyleSheet"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "parseCSS"),
+        Method::from_builtin(parse_css),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getStyle"),
+        Method::from_builtin(get_style),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "setStyle"),
+        Method::from_builtin(set_style),
+    ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "styleNames"),
+        Method::from_builtin(style_names),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clear"),
+        Method::from_builtin(clear),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "transform"),
+        Method::from_builtin(transform),
+    ));
+
+    class
+}
@@ -355,6 +355,41 @@ pub fn set_html_text<'gc>(
     Ok(Value::Undefined)
 }
 
+pub fn style_sheet<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        return Ok(this.style_sheet().map_or(Value::Null, Value::Object));
+    }
+
+    Ok(Value::Undefined)
+}
+
+pub fn set_style_sheet<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|this| this.as_edit_text())
+    {
+        let style_sheet = match args.get(0).cloned().unwrap_or(Value::Null) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_object(activation)?),
+        };
+
+        this.set_style_sheet(&mut activation.context, style_sheet);
+    }
+
+    Ok(Value::Undefined)
+}
+
 pub fn length<'gc>(
     _activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
@@ -930,6 +965,14 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         QName::new(Namespace::public(), "htmlText"),
         Method::from_builtin(set_html_text),
     ));
+    write.define_instance_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "styleSheet"),
+        Method::from_builtin(style_sheet),
+    ));
+    write.define_instance_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "styleSheet"),
+        Method::from_builtin(set_style_sheet),
+    ));
     write.define_instance_trait(Trait::from_getter(
         QName::new(Namespace::public(), "length"),
         Method::from_builtin(length),
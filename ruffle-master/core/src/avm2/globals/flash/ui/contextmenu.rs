@@ -0,0 +1,339 @@
+//! `flash.ui.ContextMenu` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::array::ArrayStorage;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::flash::events::contextmenuevent::dispatch_context_menu_event;
+use crate::avm2::globals::flash::ui::contextmenubuiltinitems::{self, ITEMS};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{ArrayObject, Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::ContextMenuItem;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// The context menu that is currently being shown by the UI.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct ShownContextMenu<'gc> {
+    /// The `ContextMenuItem`s that were shown, in the same order as the items
+    /// given to the UI.
+    items: Vec<Object<'gc>>,
+
+    /// The object that was right-clicked.
+    mouse_target: Object<'gc>,
+
+    /// The object whose `contextMenu` was shown.
+    owner: Object<'gc>,
+}
+
+/// Implements `flash.ui.ContextMenu`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let built_in_items_proto = activation
+            .context
+            .avm2
+            .prototypes()
+            .context_menu_built_in_items;
+        let built_in_items = built_in_items_proto.construct(activation, &[])?;
+        contextmenubuiltinitems::instance_init(activation, Some(built_in_items), &[])?;
+
+        let custom_items = ArrayObject::from_array(
+            ArrayStorage::new(0),
+            activation.context.avm2.prototypes().array,
+            activation.context.gc_context,
+        );
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "builtInItems"),
+            built_in_items.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "customItems"),
+            custom_items.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.ContextMenu`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenu.isSupported`.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Implements `ContextMenu.hideBuiltInItems`.
+pub fn hide_built_in_items<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let built_in_items = this.get_property(
+            this,
+            &QName::new(Namespace::public(), "builtInItems"),
+            activation,
+        )?;
+
+        if let Value::Object(mut built_in_items) = built_in_items {
+            for name in ITEMS {
+                built_in_items.set_property(
+                    built_in_items,
+                    &QName::new(Namespace::public(), *name),
+                    false.into(),
+                    activation,
+                )?;
+            }
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenu.clone`.
+///
+/// The copy shares its `ContextMenuItem`s with the original, but has its own
+/// `customItems` array and `builtInItems`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let menu_proto = activation.context.avm2.prototypes().context_menu;
+        let mut copy = menu_proto.construct(activation, &[])?;
+        instance_init(activation, Some(copy), &[])?;
+
+        let built_in_name = QName::new(Namespace::public(), "builtInItems");
+        let built_in_items = this.get_property(this, &built_in_name, activation)?;
+        let copy_built_in_items = copy.get_property(copy, &built_in_name, activation)?;
+        if let (Value::Object(mut built_in_items), Value::Object(mut copy_built_in_items)) =
+            (built_in_items, copy_built_in_items)
+        {
+            for name in ITEMS {
+                let name = QName::new(Namespace::public(), *name);
+                let value = built_in_items.get_property(built_in_items, &name, activation)?;
+                copy_built_in_items.set_property(copy_built_in_items, &name, value, activation)?;
+            }
+        }
+
+        let custom_items_name = QName::new(Namespace::public(), "customItems");
+        let custom_items = this.get_property(this, &custom_items_name, activation)?;
+        if let Value::Object(custom_items) = custom_items {
+            if let Some(array) = custom_items.as_array_storage() {
+                let custom_items_copy = ArrayObject::from_array(
+                    array.clone(),
+                    activation.context.avm2.prototypes().array,
+                    activation.context.gc_context,
+                );
+                drop(array);
+
+                copy.set_property(
+                    copy,
+                    &custom_items_name,
+                    custom_items_copy.into(),
+                    activation,
+                )?;
+            }
+        }
+
+        return Ok(copy.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Read a property of a `ContextMenuItem` as a boolean.
+fn item_flag<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut item: Object<'gc>,
+    name: &'static str,
+) -> Result<bool, Error> {
+    Ok(item
+        .get_property(item, &QName::new(Namespace::public(), name), activation)?
+        .coerce_to_boolean())
+}
+
+/// Find the context menu to show for a right-click, and get the items that
+/// the UI should show for it.
+///
+/// `targets` is the object that was right-clicked, followed by each of its
+/// ancestors. The nearest one with a `contextMenu` owns the menu, which is
+/// sent a `menuSelect` event before its `customItems` are read. An empty list
+/// is returned if none of them have a menu.
+pub fn prepare_context_menu<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    targets: &[Object<'gc>],
+) -> Result<Vec<ContextMenuItem>, Error> {
+    activation.context.avm2.shown_context_menu = None;
+
+    let mut owner_and_menu = None;
+    for target in targets {
+        let mut target = *target;
+        let menu = target.get_property(
+            target,
+            &QName::new(Namespace::public(), "contextMenu"),
+            activation,
+        )?;
+
+        if let Value::Object(menu) = menu {
+            owner_and_menu = Some((target, menu));
+            break;
+        }
+    }
+
+    let (owner, mut menu) = match owner_and_menu {
+        Some(owner_and_menu) => owner_and_menu,
+        None => return Ok(Vec::new()),
+    };
+    let mouse_target = targets[0];
+
+    dispatch_context_menu_event(activation, menu, "menuSelect", mouse_target, owner)?;
+
+    let custom_items = menu.get_property(
+        menu,
+        &QName::new(Namespace::public(), "customItems"),
+        activation,
+    )?;
+    let custom_items: Vec<Value<'gc>> = match custom_items {
+        Value::Object(custom_items) => custom_items
+            .as_array_storage()
+            .map(|array| array.iter().flatten().collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    let mut items = Vec::new();
+    let mut objects = Vec::new();
+    for item in custom_items {
+        let mut item = match item {
+            Value::Object(item) => item,
+            _ => continue,
+        };
+
+        if !item_flag(activation, item, "visible")? {
+            continue;
+        }
+
+        let caption = item
+            .get_property(
+                item,
+                &QName::new(Namespace::public(), "caption"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+
+        items.push(ContextMenuItem {
+            caption: caption.to_string(),
+            enabled: item_flag(activation, item, "enabled")?,
+            separator_before: item_flag(activation, item, "separatorBefore")?,
+        });
+        objects.push(item);
+    }
+
+    if !objects.is_empty() {
+        activation.context.avm2.shown_context_menu = Some(ShownContextMenu {
+            items: objects,
+            mouse_target,
+            owner,
+        });
+    }
+
+    Ok(items)
+}
+
+/// Dispatch `menuItemSelect` to the item the user picked from the context
+/// menu that was last shown.
+pub fn run_context_menu_item<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    index: usize,
+) -> Result<(), Error> {
+    let shown = match activation.context.avm2.shown_context_menu.take() {
+        Some(shown) => shown,
+        None => return Ok(()),
+    };
+
+    if let Some(item) = shown.items.get(index) {
+        if item_flag(activation, *item, "enabled")? {
+            dispatch_context_menu_event(
+                activation,
+                *item,
+                "menuItemSelect",
+                shown.mouse_target,
+                shown.owner,
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Construct `ContextMenu`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "ContextMenu"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isSupported"),
+        Method::from_builtin(is_supported),
+    ));
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "builtInItems"),
+        Multiname::from(QName::new(
+            Namespace::package("flash.ui"),
+            "ContextMenuBuiltInItems",
+        )),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "customItems"),
+        Multiname::from(QName::new(Namespace::public(), "Array")),
+        None,
+    ));
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "hideBuiltInItems"),
+        Method::from_builtin(hide_built_in_items),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+
+    class
+}
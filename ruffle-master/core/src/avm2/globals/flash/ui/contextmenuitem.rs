@@ -0,0 +1,135 @@
+//! `flash.ui.ContextMenuItem` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The properties of a `ContextMenuItem`, in the order that the constructor
+/// takes them, along with their defaults.
+const PROPERTIES: &[(&str, bool)] = &[
+    ("separatorBefore", false),
+    ("enabled", true),
+    ("visible", true),
+];
+
+/// Implements `flash.ui.ContextMenuItem`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let caption = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| "".into())
+            .coerce_to_string(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "caption"),
+            caption.into(),
+            activation,
+        )?;
+
+        for (index, (name, default)) in PROPERTIES.iter().enumerate() {
+            let value = args
+                .get(1 + index)
+                .cloned()
+                .unwrap_or_else(|| (*default).into())
+                .coerce_to_boolean();
+
+            this.set_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                value.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.ContextMenuItem`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `ContextMenuItem.clone`.
+pub fn clone<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let mut args = vec![this.get_property(
+            this,
+            &QName::new(Namespace::public(), "caption"),
+            activation,
+        )?];
+        for (name, _default) in PROPERTIES {
+            args.push(this.get_property(
+                this,
+                &QName::new(Namespace::public(), *name),
+                activation,
+            )?);
+        }
+
+        let item_proto = activation.context.avm2.prototypes().context_menu_item;
+        let item = item_proto.construct(activation, &[])?;
+        instance_init(activation, Some(item), &args)?;
+
+        return Ok(item.into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `ContextMenuItem`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "ContextMenuItem"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "caption"),
+        Multiname::from(QName::new(Namespace::public(), "String")),
+        None,
+    ));
+
+    for (name, _default) in PROPERTIES {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::public(), "Boolean")),
+            None,
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "clone"),
+        Method::from_builtin(clone),
+    ));
+
+    class
+}
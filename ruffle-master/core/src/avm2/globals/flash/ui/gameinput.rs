@@ -0,0 +1,271 @@
+//! `flash.ui.GameInput` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::{dispatch_event, Event};
+use crate::avm2::globals::flash::events::gameinputevent::dispatch_game_input_event;
+use crate::avm2::globals::flash::ui::gameinputcontrol::create_control;
+use crate::avm2::globals::flash::ui::gameinputdevice::{self, GameInputDevice};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{EventObject, Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::gamepad::GamepadState;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.ui.GameInput`'s instance constructor.
+///
+/// Every `GameInput` is told when a device is added or removed, so they are
+/// kept for the rest of the movie.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+        activation.context.avm2.game_inputs.push(this);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.GameInput`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `GameInput.isSupported`.
+pub fn is_supported<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Implements `GameInput.numDevices`.
+pub fn num_devices<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok((activation.context.avm2.game_input_devices.len() as u32).into())
+}
+
+/// Implements `GameInput.getDeviceAt`.
+pub fn get_device_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let index = args
+        .get(0)
+        .cloned()
+        .unwrap_or_else(|| 0.into())
+        .coerce_to_i32(activation)?;
+
+    let devices = &activation.context.avm2.game_input_devices;
+    match devices.get(index as usize) {
+        Some(device) if index >= 0 => Ok(device.object.into()),
+        _ => Err("RangeError: The supplied index is out of bounds.".into()),
+    }
+}
+
+/// Construct the `GameInputDevice` and controls for a newly connected
+/// gamepad.
+fn create_device<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    gamepad: &GamepadState,
+) -> Result<GameInputDevice<'gc>, Error> {
+    let device_proto = activation.context.avm2.prototypes().game_input_device;
+    let mut object = device_proto.construct(activation, &[])?;
+    gameinputdevice::instance_init(activation, Some(object), &[])?;
+
+    let mc = activation.context.gc_context;
+    let num_controls = gamepad.axes.len() + gamepad.buttons.len();
+    let properties: [(&str, Value<'gc>); 5] = [
+        ("id", AvmString::new(mc, gamepad.id.clone()).into()),
+        ("name", AvmString::new(mc, gamepad.name.clone()).into()),
+        ("numControls", (num_controls as i32).into()),
+        ("enabled", false.into()),
+        ("sampleInterval", 0.into()),
+    ];
+
+    for (name, value) in properties.iter() {
+        object.set_property(
+            object,
+            &QName::new(Namespace::public(), *name),
+            value.clone(),
+            activation,
+        )?;
+    }
+
+    let mut controls = Vec::with_capacity(num_controls);
+    for (index, value) in gamepad.axes.iter().enumerate() {
+        let id = AvmString::new(activation.context.gc_context, format!("AXIS_{}", index));
+        controls.push(create_control(
+            activation,
+            object,
+            id.into(),
+            -1.0,
+            1.0,
+            *value,
+        )?);
+    }
+    for (index, value) in gamepad.buttons.iter().enumerate() {
+        let id = AvmString::new(activation.context.gc_context, format!("BUTTON_{}", index));
+        controls.push(create_control(
+            activation,
+            object,
+            id.into(),
+            0.0,
+            1.0,
+            *value,
+        )?);
+    }
+
+    Ok(GameInputDevice {
+        id: gamepad.id.clone(),
+        object,
+        controls,
+    })
+}
+
+/// Update the controls of a device, dispatching `change` events for the ones
+/// that moved if the device has been enabled.
+fn update_controls<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    device: &GameInputDevice<'gc>,
+    gamepad: &GamepadState,
+) -> Result<(), Error> {
+    let mut object = device.object;
+    let enabled = object
+        .get_property(
+            object,
+            &QName::new(Namespace::public(), "enabled"),
+            activation,
+        )?
+        .coerce_to_boolean();
+
+    let values = gamepad.axes.iter().chain(gamepad.buttons.iter());
+    for (control, value) in device.controls.iter().zip(values) {
+        let mut control = *control;
+        let value_name = QName::new(Namespace::public(), "value");
+        let old_value = control
+            .get_property(control, &value_name, activation)?
+            .coerce_to_number(activation)?;
+
+        if old_value == *value {
+            continue;
+        }
+
+        control.set_property(control, &value_name, (*value).into(), activation)?;
+
+        if enabled {
+            let event_proto = activation.context.avm2.prototypes().event;
+            let event = EventObject::from_event(
+                activation.context.gc_context,
+                Some(event_proto),
+                Event::new("change"),
+            );
+            dispatch_event(activation, control, event)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Tell every `GameInput` that a device was added or removed.
+fn notify_game_inputs<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    event_type: &'static str,
+    device: Object<'gc>,
+) -> Result<(), Error> {
+    let game_inputs = activation.context.avm2.game_inputs.clone();
+    for game_input in game_inputs {
+        dispatch_game_input_event(activation, game_input, event_type, device)?;
+    }
+
+    Ok(())
+}
+
+/// Bring the `GameInputDevice`s in line with the gamepads that are currently
+/// connected.
+pub fn update_game_input<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    gamepads: &[GamepadState],
+) -> Result<(), Error> {
+    let mut removed = Vec::new();
+    activation.context.avm2.game_input_devices.retain(|device| {
+        let connected = gamepads.iter().any(|gamepad| gamepad.id == device.id);
+        if !connected {
+            removed.push(device.object);
+        }
+
+        connected
+    });
+
+    for device in removed {
+        notify_game_inputs(activation, "deviceRemoved", device)?;
+    }
+
+    for gamepad in gamepads {
+        let known = activation
+            .context
+            .avm2
+            .game_input_devices
+            .iter()
+            .find(|device| device.id == gamepad.id)
+            .cloned();
+
+        match known {
+            Some(device) => update_controls(activation, &device, gamepad)?,
+            None => {
+                let device = create_device(activation, gamepad)?;
+                let object = device.object;
+                activation.context.avm2.game_input_devices.push(device);
+                notify_game_inputs(activation, "deviceAdded", object)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Construct `GameInput`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "GameInput"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "isSupported"),
+        Method::from_builtin(is_supported),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "numDevices"),
+        Method::from_builtin(num_devices),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getDeviceAt"),
+        Method::from_builtin(get_device_at),
+    ));
+
+    class
+}
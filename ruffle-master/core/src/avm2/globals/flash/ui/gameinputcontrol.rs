@@ -0,0 +1,99 @@
+//! `flash.ui.GameInputControl` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.ui.GameInputControl`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.GameInputControl`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Construct a `GameInputControl` for one of the axes or buttons of `device`.
+pub fn create_control<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    device: Object<'gc>,
+    id: Value<'gc>,
+    min_value: f64,
+    max_value: f64,
+    value: f64,
+) -> Result<Object<'gc>, Error> {
+    let control_proto = activation.context.avm2.prototypes().game_input_control;
+    let mut control = control_proto.construct(activation, &[])?;
+    instance_init(activation, Some(control), &[])?;
+
+    let properties = [
+        ("device", device.into()),
+        ("id", id),
+        ("minValue", min_value.into()),
+        ("maxValue", max_value.into()),
+        ("value", value.into()),
+    ];
+
+    for (name, value) in properties.iter() {
+        control.set_property(
+            control,
+            &QName::new(Namespace::public(), *name),
+            value.clone(),
+            activation,
+        )?;
+    }
+
+    Ok(control)
+}
+
+/// Construct `GameInputControl`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "GameInputControl"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const SLOTS: &[(&str, &str, &str)] = &[
+        ("device", "flash.ui", "GameInputDevice"),
+        ("id", "", "String"),
+        ("minValue", "", "Number"),
+        ("maxValue", "", "Number"),
+        ("value", "", "Number"),
+    ];
+
+    for (name, type_package, type_name) in SLOTS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::package(*type_package), *type_name)),
+            None,
+        ));
+    }
+
+    class
+}
@@ -0,0 +1,155 @@
+//! `flash.ui.GameInputDevice` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// A gamepad that has been handed to ActionScript as a `GameInputDevice`.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct GameInputDevice<'gc> {
+    /// The identifier the gamepad backend gave this gamepad.
+    #[collect(require_static)]
+    pub id: String,
+
+    /// The `GameInputDevice` object.
+    pub object: Object<'gc>,
+
+    /// The `GameInputControl` of each axis, followed by each button.
+    pub controls: Vec<Object<'gc>>,
+}
+
+/// Implements `flash.ui.GameInputDevice`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.GameInputDevice`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `GameInputDevice.getControlAt`.
+pub fn get_control_at<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let index = args
+            .get(0)
+            .cloned()
+            .unwrap_or_else(|| 0.into())
+            .coerce_to_i32(activation)?;
+
+        let control = activation
+            .context
+            .avm2
+            .game_input_devices
+            .iter()
+            .find(|device| Object::ptr_eq(device.object, this))
+            .and_then(|device| device.controls.get(index as usize))
+            .copied();
+
+        return match control {
+            Some(control) if index >= 0 => Ok(control.into()),
+            _ => Err("RangeError: The supplied index is out of bounds.".into()),
+        };
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `GameInputDevice.startCachingSamples` and `stopCachingSamples`.
+///
+/// Controls are only sampled once per frame, so there is nothing to cache.
+pub fn caching_samples<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `GameInputDevice.getCachedSamples`.
+pub fn get_cached_samples<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(0.into())
+}
+
+/// Construct `GameInputDevice`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "GameInputDevice"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_const(
+        QName::new(Namespace::public(), "MAX_BUFFER_SIZE"),
+        QName::new(Namespace::public(), "int").into(),
+        Some(32000.into()),
+    ));
+
+    const SLOTS: &[(&str, &str)] = &[
+        ("id", "String"),
+        ("name", "String"),
+        ("numControls", "int"),
+        ("enabled", "Boolean"),
+        ("sampleInterval", "int"),
+    ];
+
+    for (name, type_name) in SLOTS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), *name),
+            Multiname::from(QName::new(Namespace::public(), *type_name)),
+            None,
+        ));
+    }
+
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getControlAt"),
+        Method::from_builtin(get_control_at),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "startCachingSamples"),
+        Method::from_builtin(caching_samples),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "stopCachingSamples"),
+        Method::from_builtin(caching_samples),
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "getCachedSamples"),
+        Method::from_builtin(get_cached_samples),
+    ));
+
+    class
+}
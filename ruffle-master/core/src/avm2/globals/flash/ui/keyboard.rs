@@ -0,0 +1,220 @@
+//! `flash.ui.Keyboard` class
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// The key codes that `Keyboard` has constants for.
+const KEY_CODES: &[(&str, u32)] = &[
+    ("A", 65),
+    ("B", 66),
+    ("C", 67),
+    ("D", 68),
+    ("E", 69),
+    ("F", 70),
+    ("G", 71),
+    ("H", 72),
+    ("I", 73),
+    ("J", 74),
+    ("K", 75),
+    ("L", 76),
+    ("M", 77),
+    ("N", 78),
+    ("O", 79),
+    ("P", 80),
+    ("Q", 81),
+    ("R", 82),
+    ("S", 83),
+    ("T", 84),
+    ("U", 85),
+    ("V", 86),
+    ("W", 87),
+    ("X", 88),
+    ("Y", 89),
+    ("Z", 90),
+    ("NUMBER_0", 48),
+    ("NUMBER_1", 49),
+    ("NUMBER_2", 50),
+    ("NUMBER_3", 51),
+    ("NUMBER_4", 52),
+    ("NUMBER_5", 53),
+    ("NUMBER_6", 54),
+    ("NUMBER_7", 55),
+    ("NUMBER_8", 56),
+    ("NUMBER_9", 57),
+    ("NUMPAD_0", 96),
+    ("NUMPAD_1", 97),
+    ("NUMPAD_2", 98),
+    ("NUMPAD_3", 99),
+    ("NUMPAD_4", 100),
+    ("NUMPAD_5", 101),
+    ("NUMPAD_6", 102),
+    ("NUMPAD_7", 103),
+    ("NUMPAD_8", 104),
+    ("NUMPAD_9", 105),
+    ("F1", 112),
+    ("F2", 113),
+    ("F3", 114),
+    ("F4", 115),
+    ("F5", 116),
+    ("F6", 117),
+    ("F7", 118),
+    ("F8", 119),
+    ("F9", 120),
+    ("F10", 121),
+    ("F11", 122),
+    ("F12", 123),
+    ("F13", 124),
+    ("F14", 125),
+    ("F15", 126),
+    ("BACKSPACE", 8),
+    ("TAB", 9),
+    ("ENTER", 13),
+    ("COMMAND", 15),
+    ("SHIFT", 16),
+    ("CONTROL", 17),
+    ("ALTERNATE", 18),
+    ("CAPS_LOCK", 20),
+    ("NUMPAD", 21),
+    ("ESCAPE", 27),
+    ("SPACE", 32),
+    ("PAGE_UP", 33),
+    ("PAGE_DOWN", 34),
+    ("END", 35),
+    ("HOME", 36),
+    ("LEFT", 37),
+    ("UP", 38),
+    ("RIGHT", 39),
+    ("DOWN", 40),
+    ("INSERT", 45),
+    ("DELETE", 46),
+    ("NUMPAD_MULTIPLY", 106),
+    ("NUMPAD_ADD", 107),
+    ("NUMPAD_ENTER", 108),
+    ("NUMPAD_SUBTRACT", 109),
+    ("NUMPAD_DECIMAL", 110),
+    ("NUMPAD_DIVIDE", 111),
+    ("SEMICOLON", 186),
+    ("EQUAL", 187),
+    ("COMMA", 188),
+    ("MINUS", 189),
+    ("PERIOD", 190),
+    ("SLASH", 191),
+    ("BACKQUOTE", 192),
+    ("LEFTBRACKET", 219),
+    ("BACKSLASH", 220),
+    ("RIGHTBRACKET", 221),
+    ("QUOTE", 222),
+];
+
+/// Implements `flash.ui.Keyboard`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.Keyboard`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Keyboard.capsLock` and `Keyboard.numLock`.
+///
+/// We aren't told the state of the lock keys, only when they are pressed.
+pub fn lock_state<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `Keyboard.hasVirtualKeyboard`.
+pub fn has_virtual_keyboard<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Implements `Keyboard.physicalKeyboardType`.
+pub fn physical_keyboard_type<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok("alphanumeric".into())
+}
+
+/// Implements `Keyboard.isAccessible`.
+pub fn is_accessible<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(false.into())
+}
+
+/// Construct `Keyboard`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "Keyboard"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    for (name, key_code) in KEY_CODES {
+        write.define_class_trait(Trait::from_const(
+            QName::new(Namespace::public(), *name),
+            QName::new(Namespace::public(), "uint").into(),
+            Some((*key_code).into()),
+        ));
+    }
+
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "capsLock"),
+        Method::from_builtin(lock_state),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "numLock"),
+        Method::from_builtin(lock_state),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "hasVirtualKeyboard"),
+        Method::from_builtin(has_virtual_keyboard),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "physicalKeyboardType"),
+        Method::from_builtin(physical_keyboard_type),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "isAccessible"),
+        Method::from_builtin(is_accessible),
+    ));
+
+    class
+}
@@ -0,0 +1,149 @@
+//! `flash.ui.Mouse` class
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::Object;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::ui::MouseCursor;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.ui.Mouse`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        activation.super_init(this, &[])?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.ui.Mouse`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.hide`.
+pub fn hide<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    activation.context.ui.set_mouse_visible(false);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.show`.
+pub fn show<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    activation.context.ui.set_mouse_visible(true);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.cursor`'s getter.
+pub fn cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let cursor = match activation.context.avm2.mouse_cursor() {
+        None => "auto",
+        Some(MouseCursor::Arrow) => "arrow",
+        Some(MouseCursor::Hand) => "button",
+        Some(MouseCursor::Grab) => "hand",
+        Some(MouseCursor::IBeam) => "ibeam",
+    };
+
+    Ok(cursor.into())
+}
+
+/// Implements `Mouse.cursor`'s setter.
+///
+/// The cursor itself is changed by the player the next time it checks what
+/// the mouse is hovering over.
+pub fn set_cursor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+
+    let cursor = match name.as_str() {
+        "auto" => None,
+        "arrow" => Some(MouseCursor::Arrow),
+        "button" => Some(MouseCursor::Hand),
+        "hand" => Some(MouseCursor::Grab),
+        "ibeam" => Some(MouseCursor::IBeam),
+        _ => return Err(format!("Invalid mouse cursor: {}", name).into()),
+    };
+
+    activation.context.avm2.set_mouse_cursor(cursor);
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Mouse.supportsCursor`.
+pub fn supports_cursor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(true.into())
+}
+
+/// Construct `Mouse`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.ui"), "Mouse"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::FINAL | ClassAttributes::SEALED);
+
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "hide"),
+        Method::from_builtin(hide),
+    ));
+    write.define_class_trait(Trait::from_method(
+        QName::new(Namespace::public(), "show"),
+        Method::from_builtin(show),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "cursor"),
+        Method::from_builtin(cursor),
+    ));
+    write.define_class_trait(Trait::from_setter(
+        QName::new(Namespace::public(), "cursor"),
+        Method::from_builtin(set_cursor),
+    ));
+    write.define_class_trait(Trait::from_getter(
+        QName::new(Namespace::public(), "supportsCursor"),
+        Method::from_builtin(supports_cursor),
+    ));
+
+    class
+}
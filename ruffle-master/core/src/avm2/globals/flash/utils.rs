@@ -1,6 +1,15 @@
 //! `flash.utils` namespace
 
-use crate::avm2::{Activation, Error, Object, Value};
+use crate::avm2::class::Class;
+use crate::avm2::globals::xml;
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::{Metadata, Trait, TraitKind};
+use crate::avm2::{Activation, Error, Object, TObject, Value};
+use gc_arena::{GcCell, MutationContext};
+use std::fmt::Write;
+use swf::avm2::types::Index;
 
 pub mod bytearray;
 pub mod endian;
@@ -13,3 +22,531 @@ pub fn get_timer<'gc>(
 ) -> Result<Value<'gc>, Error> {
     Ok((activation.context.navigator.time_since_launch().as_millis() as u32).into())
 }
+
+/// The object that reflection describes a value with: the value itself if it
+/// is an object, or the prototype of its type if it is a primitive.
+///
+/// Yields `None` for `null` and `undefined`.
+fn reflected_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: &Value<'gc>,
+) -> Option<Object<'gc>> {
+    let prototypes = activation.context.avm2.prototypes();
+
+    match value {
+        Value::Undefined | Value::Null => None,
+        Value::Bool(_) => Some(prototypes.boolean),
+        Value::Integer(_) | Value::Unsigned(_) => Some(prototypes.int),
+        Value::Number(n)
+            if n.fract() == 0.0 && *n >= f64::from(i32::MIN) && *n <= f64::from(i32::MAX) =>
+        {
+            Some(prototypes.int)
+        }
+        Value::Number(_) => Some(prototypes.number),
+        Value::String(_) => Some(prototypes.string),
+        Value::Object(object) => Some(*object),
+    }
+}
+
+/// Whether or not an object is a class, rather than an instance or a
+/// prototype.
+fn is_class_object(object: Object<'_>) -> bool {
+    object.as_class().is_some() && object.as_executable().is_some()
+}
+
+/// The classes an object is an instance of, starting with its own class and
+/// ending with `Object`.
+fn class_chain<'gc>(object: Object<'gc>) -> Vec<GcCell<'gc, Class<'gc>>> {
+    let mut chain: Vec<GcCell<'gc, Class<'gc>>> = Vec::new();
+    let mut proto = Some(object);
+
+    while let Some(object) = proto {
+        if let Some(class) = object.as_class() {
+            if !chain.iter().any(|c| GcCell::ptr_eq(*c, class)) {
+                chain.push(class);
+            }
+        }
+
+        proto = object.proto();
+    }
+
+    chain
+}
+
+/// The fully qualified name of a type, or `*` for the any type.
+fn type_name(multiname: &Multiname<'_>) -> String {
+    match (multiname.namespace_set().next(), multiname.local_name()) {
+        (Some(ns), Some(name)) => QName::new(ns.clone(), name).to_qualified_name(),
+        (None, Some(name)) => name.to_string(),
+        (_, None) => "*".to_string(),
+    }
+}
+
+/// Implements `flash.utils.getQualifiedClassName`
+pub fn get_qualified_class_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let name = match reflected_object(activation, &value) {
+        Some(object) => match class_chain(object).first() {
+            Some(class) => class.read().name().to_qualified_name(),
+            None => "Object".to_string(),
+        },
+        None if value == Value::Null => "null".to_string(),
+        None => "void".to_string(),
+    };
+
+    Ok(AvmString::new(activation.context.gc_context, name).into())
+}
+
+/// Implements `flash.utils.getQualifiedSuperclassName`
+pub fn get_qualified_superclass_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let super_name = reflected_object(activation, &value)
+        .and_then(|object| class_chain(object).first().cloned())
+        .and_then(|class| class.read().super_class_name().clone());
+
+    match super_name {
+        Some(super_name) => {
+            Ok(AvmString::new(activation.context.gc_context, type_name(&super_name)).into())
+        }
+        None => Ok(Value::Null),
+    }
+}
+
+/// Implements `flash.utils.getDefinitionByName`
+pub fn get_definition_by_name<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let name = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_string(activation)?;
+    let qname = QName::from_qualified_name(&name, activation.context.gc_context);
+
+    let domain = activation
+        .scope()
+        .map(|s| s.read().globals())
+        .and_then(|g| g.as_application_domain())
+        .unwrap_or_else(|| activation.context.avm2.global_domain());
+
+    match domain.get_defining_script(&qname.into())? {
+        Some((qname, mut script)) => {
+            let mut globals = script.globals(&mut activation.context)?;
+            globals.get_property(globals, &qname, activation)
+        }
+        None => Err(format!(
+            "ReferenceError: Error #1065: Variable {} is not defined.",
+            name
+        )
+        .into()),
+    }
+}
+
+/// Escape a string for use as an XML attribute value.
+fn escape_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// The parameter types, whether each parameter is optional, and the return
+/// type of a method.
+///
+/// Native methods don't declare their signature, so they take no parameters
+/// and return anything.
+fn method_signature<'gc>(
+    method: &Method<'gc>,
+    mc: MutationContext<'gc, '_>,
+) -> Result<(Vec<(String, bool)>, String), Error> {
+    let bytecode = match method {
+        Method::Native(_) => return Ok((Vec::new(), "*".to_string())),
+        Method::Entry(bytecode) => bytecode,
+    };
+
+    let txunit = bytecode.translation_unit();
+    let abc_method = bytecode.method();
+    let resolve = |index| -> Result<String, Error> {
+        if index == 0 {
+            Ok("*".to_string())
+        } else {
+            Ok(type_name(&Multiname::from_abc_multiname_static(
+                txunit,
+                Index::new(index),
+                mc,
+            )?))
+        }
+    };
+
+    let mut params = Vec::with_capacity(abc_method.params.len());
+    for param in abc_method.params.iter() {
+        params.push((resolve(param.kind.0)?, param.default_value.is_some()));
+    }
+
+    Ok((params, resolve(abc_method.return_type.0)?))
+}
+
+/// Write the metadata of a trait or class as `<metadata>` elements.
+fn describe_metadata(xml: &mut String, metadata: &[Metadata<'_>], indent: &str) {
+    for entry in metadata {
+        let name = escape_attribute(&entry.name);
+        if entry.items.is_empty() {
+            let _ = writeln!(xml, "{}<metadata name=\"{}\"/>", indent, name);
+            continue;
+        }
+
+        let _ = writeln!(xml, "{}<metadata name=\"{}\">", indent, name);
+        for (key, value) in entry.items.iter() {
+            let _ = writeln!(
+                xml,
+                "{}  <arg key=\"{}\" value=\"{}\"/>",
+                indent,
+                escape_attribute(key),
+                escape_attribute(value)
+            );
+        }
+        let _ = writeln!(xml, "{}</metadata>", indent);
+    }
+}
+
+/// A getter, setter, or both, being described.
+struct Accessor<'a, 'gc> {
+    name: String,
+    readable: bool,
+    writable: bool,
+    type_name: String,
+    declared_by: String,
+    metadata: &'a [Metadata<'gc>],
+}
+
+/// Write the public traits of a class and its superclasses as `<accessor>`,
+/// `<method>`, `<variable>` and `<constant>` elements.
+///
+/// `chain` lists each class's name and traits, starting with the class being
+/// described. Traits overridden by a subclass are only described once, as
+/// declared by that subclass.
+fn describe_traits<'gc>(
+    xml: &mut String,
+    chain: &[(String, &[Trait<'gc>])],
+    indent: &str,
+    mc: MutationContext<'gc, '_>,
+) -> Result<(), Error> {
+    let mut seen: Vec<String> = Vec::new();
+    let mut accessors: Vec<Accessor<'_, 'gc>> = Vec::new();
+
+    for (declared_by, traits) in chain {
+        let declared_by = escape_attribute(declared_by);
+
+        for my_trait in traits.iter() {
+            if !my_trait.name().namespace().is_public() {
+                continue;
+            }
+
+            let name = escape_attribute(&my_trait.name().local_name());
+            match my_trait.kind() {
+                TraitKind::Getter { method, .. } | TraitKind::Setter { method, .. } => {
+                    let is_getter = matches!(my_trait.kind(), TraitKind::Getter { .. });
+                    let (params, return_type) = method_signature(method, mc)?;
+                    let accessor_type = if is_getter {
+                        return_type
+                    } else {
+                        params
+                            .into_iter()
+                            .next()
+                            .map(|(param_type, _)| param_type)
+                            .unwrap_or_else(|| "*".to_string())
+                    };
+
+                    let index = match accessors.iter().position(|a| a.name == name) {
+                        Some(index) => index,
+                        None => {
+                            accessors.push(Accessor {
+                                name: name.clone(),
+                                readable: false,
+                                writable: false,
+                                type_name: accessor_type,
+                                declared_by: declared_by.clone(),
+                                metadata: my_trait.metadata(),
+                            });
+                            accessors.len() - 1
+                        }
+                    };
+
+                    if is_getter {
+                        accessors[index].readable = true;
+                    } else {
+                        accessors[index].writable = true;
+                    }
+                }
+                TraitKind::Method { method, .. } => {
+                    if seen.contains(&name) {
+                        continue;
+                    }
+
+                    let (params, return_type) = method_signature(method, mc)?;
+                    let _ = write!(
+                        xml,
+                        "{}<method name=\"{}\" declaredBy=\"{}\" returnType=\"{}\"",
+                        indent,
+                        name,
+                        declared_by,
+                        escape_attribute(&return_type)
+                    );
+                    if params.is_empty() && my_trait.metadata().is_empty() {
+                        xml.push_str("/>\n");
+                    } else {
+                        xml.push_str(">\n");
+                        for (index, (param_type, optional)) in params.iter().enumerate() {
+                            let _ = writeln!(
+                                xml,
+                                "{}  <parameter index=\"{}\" type=\"{}\" optional=\"{}\"/>",
+                                indent,
+                                index + 1,
+                                escape_attribute(param_type),
+                                optional
+                            );
+                        }
+                        describe_metadata(xml, my_trait.metadata(), &format!("{}  ", indent));
+                        let _ = writeln!(xml, "{}</method>", indent);
+                    }
+
+                    seen.push(name);
+                }
+                TraitKind::Slot { type_name: t, .. } | TraitKind::Const { type_name: t, .. } => {
+                    if seen.contains(&name) {
+                        continue;
+                    }
+
+                    let element = if matches!(my_trait.kind(), TraitKind::Const { .. }) {
+                        "constant"
+                    } else {
+                        "variable"
+                    };
+                    let _ = write!(
+                        xml,
+                        "{}<{} name=\"{}\" type=\"{}\"",
+                        indent,
+                        element,
+                        name,
+                        escape_attribute(&type_name(t))
+                    );
+                    if my_trait.metadata().is_empty() {
+                        xml.push_str("/>\n");
+                    } else {
+                        xml.push_str(">\n");
+                        describe_metadata(xml, my_trait.metadata(), &format!("{}  ", indent));
+                        let _ = writeln!(xml, "{}</{}>", indent, element);
+                    }
+
+                    seen.push(name);
+                }
+                TraitKind::Class { .. } | TraitKind::Function { .. } => {}
+            }
+        }
+    }
+
+    for accessor in accessors {
+        let access = match (accessor.readable, accessor.writable) {
+            (true, true) => "readwrite",
+            (true, false) => "readonly",
+            _ => "writeonly",
+        };
+        let _ = write!(
+            xml,
+            "{}<accessor name=\"{}\" access=\"{}\" type=\"{}\" declaredBy=\"{}\"",
+            indent,
+            accessor.name,
+            access,
+            escape_attribute(&accessor.type_name),
+            accessor.declared_by
+        );
+        if accessor.metadata.is_empty() {
+            xml.push_str("/>\n");
+        } else {
+            xml.push_str(">\n");
+            describe_metadata(xml, accessor.metadata, &format!("{}  ", indent));
+            let _ = writeln!(xml, "{}</accessor>", indent);
+        }
+    }
+
+    Ok(())
+}
+
+/// Write the parts of a description that are shared between instances and
+/// the `<factory>` of a class: bases, interfaces, constructor, traits and the
+/// class metadata.
+fn describe_instance<'gc>(
+    xml: &mut String,
+    chain: &[GcCell<'gc, Class<'gc>>],
+    indent: &str,
+    mc: MutationContext<'gc, '_>,
+) -> Result<(), Error> {
+    let classes: Vec<_> = chain.iter().map(|class| class.read()).collect();
+
+    for class in classes.iter().skip(1) {
+        let _ = writeln!(
+            xml,
+            "{}<extendsClass type=\"{}\"/>",
+            indent,
+            escape_attribute(&class.name().to_qualified_name())
+        );
+    }
+
+    let mut interfaces: Vec<String> = Vec::new();
+    for class in classes.iter() {
+        for interface in class.interfaces() {
+            let interface = type_name(interface);
+            if !interfaces.contains(&interface) {
+                let _ = writeln!(
+                    xml,
+                    "{}<implementsInterface type=\"{}\"/>",
+                    indent,
+                    escape_attribute(&interface)
+                );
+                interfaces.push(interface);
+            }
+        }
+    }
+
+    if let Some(class) = classes.first() {
+        let (params, _) = method_signature(&class.instance_init(), mc)?;
+        if !params.is_empty() {
+            let _ = writeln!(xml, "{}<constructor>", indent);
+            for (index, (param_type, optional)) in params.iter().enumerate() {
+                let _ = writeln!(
+                    xml,
+                    "{}  <parameter index=\"{}\" type=\"{}\" optional=\"{}\"/>",
+                    indent,
+                    index + 1,
+                    escape_attribute(param_type),
+                    optional
+                );
+            }
+            let _ = writeln!(xml, "{}</constructor>", indent);
+        }
+    }
+
+    let traits: Vec<_> = classes
+        .iter()
+        .map(|class| (class.name().to_qualified_name(), class.instance_traits()))
+        .collect();
+    describe_traits(xml, &traits, indent, mc)?;
+
+    if let Some(class) = classes.first() {
+        describe_metadata(xml, class.metadata(), indent);
+    }
+
+    Ok(())
+}
+
+/// Implements `flash.utils.describeType`
+///
+/// Only public traits are described. Methods implemented natively by Ruffle
+/// don't declare their parameters, and are described as taking none.
+pub fn describe_type<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let mc = activation.context.gc_context;
+    let value = args.get(0).cloned().unwrap_or(Value::Undefined);
+    let mut xml = String::new();
+
+    match reflected_object(activation, &value) {
+        None => {
+            let name = if value == Value::Null { "null" } else { "void" };
+            let _ = writeln!(
+                xml,
+                "<type name=\"{}\" isDynamic=\"false\" isFinal=\"true\" isStatic=\"false\"/>",
+                name
+            );
+        }
+        Some(mut object) if is_class_object(object) => {
+            let class = object.as_class().unwrap();
+            let name = class.read().name().to_qualified_name();
+            let prototype = object
+                .get_property(
+                    object,
+                    &QName::new(Namespace::public(), "prototype"),
+                    activation,
+                )?
+                .coerce_to_object(activation)?;
+            let chain = class_chain(prototype);
+
+            let _ = writeln!(
+                xml,
+                "<type name=\"{}\" base=\"Class\" isDynamic=\"true\" isFinal=\"true\" isStatic=\"true\">",
+                escape_attribute(&name)
+            );
+            xml.push_str("  <extendsClass type=\"Class\"/>\n");
+            xml.push_str("  <extendsClass type=\"Object\"/>\n");
+            xml.push_str(
+                "  <accessor name=\"prototype\" access=\"readonly\" type=\"*\" declaredBy=\"Class\"/>\n",
+            );
+            {
+                let read = class.read();
+                describe_traits(&mut xml, &[(name.clone(), read.class_traits())], "  ", mc)?;
+            }
+            let _ = writeln!(xml, "  <factory type=\"{}\">", escape_attribute(&name));
+            describe_instance(&mut xml, &chain, "    ", mc)?;
+            xml.push_str("  </factory>\n");
+            xml.push_str("</type>\n");
+        }
+        Some(object) => {
+            let chain = class_chain(object);
+            let (name, base, is_dynamic, is_final) = match chain.first() {
+                Some(class) => {
+                    let read = class.read();
+                    (
+                        read.name().to_qualified_name(),
+                        read.super_class_name().as_ref().map(type_name),
+                        !read.is_sealed(),
+                        read.is_final(),
+                    )
+                }
+                None => ("Object".to_string(), None, true, false),
+            };
+
+            let _ = write!(xml, "<type name=\"{}\"", escape_attribute(&name));
+            if let Some(base) = base {
+                let _ = write!(xml, " base=\"{}\"", escape_attribute(&base));
+            }
+            let _ = writeln!(
+                xml,
+                " isDynamic=\"{}\" isFinal=\"{}\" isStatic=\"false\">",
+                is_dynamic, is_final
+            );
+            describe_instance(&mut xml, &chain, "  ", mc)?;
+            xml.push_str("</type>\n");
+        }
+    }
+
+    let xml_proto = activation.context.avm2.prototypes().xml;
+    let description = xml_proto.construct(activation, &[])?;
+    xml::instance_init(
+        activation,
+        Some(description),
+        &[AvmString::new(mc, xml.trim_end().to_string()).into()],
+    )?;
+
+    Ok(description.into())
+}
@@ -0,0 +1,145 @@
+//! `QName` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::Method;
+use crate::avm2::names::{Multiname, Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+
+/// Construct a new `QName` object describing the given name.
+pub fn create_qname<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    name: &QName<'gc>,
+) -> Result<Value<'gc>, Error> {
+    let proto = activation.context.avm2.prototypes().qname;
+    let args = [
+        Value::String(name.namespace().as_uri()),
+        Value::String(name.local_name()),
+    ];
+    let new_qname = proto.construct(activation, &args)?;
+    instance_init(activation, Some(new_qname), &args)?;
+
+    Ok(new_qname.into())
+}
+
+/// Implements `QName`'s instance initializer.
+///
+/// This accepts either a lone local name, or a namespace URI followed by the
+/// local name. A `null` URI stands for any namespace.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let (uri, local_name) = match args {
+            [] => (Value::String("".into()), Value::String("".into())),
+            [local_name] => (Value::String("".into()), local_name.clone()),
+            [uri, local_name, ..] => (uri.clone(), local_name.clone()),
+        };
+
+        let uri = match uri {
+            Value::Null => Value::Null,
+            uri => uri.coerce_to_string(activation)?.into(),
+        };
+        let local_name = local_name.coerce_to_string(activation)?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "uri"),
+            uri,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "localName"),
+            local_name.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `QName`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `QName.toString`
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let uri = this.get_property(this, &QName::new(Namespace::public(), "uri"), activation)?;
+        let local_name = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public(), "localName"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+
+        return Ok(match uri {
+            Value::Null => {
+                AvmString::new(activation.context.gc_context, format!("*::{}", local_name)).into()
+            }
+            uri => {
+                let uri = uri.coerce_to_string(activation)?;
+                if uri.is_empty() {
+                    local_name.into()
+                } else {
+                    AvmString::new(
+                        activation.context.gc_context,
+                        format!("{}::{}", uri, local_name),
+                    )
+                    .into()
+                }
+            }
+        });
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `QName`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public(), "QName"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+    write.set_attributes(ClassAttributes::SEALED | ClassAttributes::FINAL);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "localName"),
+        Multiname::from(QName::new(Namespace::public(), "String")),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "uri"),
+        Multiname::any(),
+        None,
+    ));
+    write.define_instance_trait(Trait::from_method(
+        QName::new(Namespace::public(), "toString"),
+        Method::from_builtin(to_string),
+    ));
+
+    class
+}
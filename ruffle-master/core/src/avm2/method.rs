@@ -97,6 +97,12 @@ impl<'gc> BytecodeMethod<'gc> {
         self.txunit
     }
 
+    /// Whether this and `other` were loaded from the same method of the same
+    /// ABC file.
+    pub fn is_same_method(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.abc.0, &other.abc.0) && self.abc_method == other.abc_method
+    }
+
     /// Get a reference to the ABC method entry this refers to.
     pub fn method(&self) -> &AbcMethod {
         &self.abc.0.methods.get(self.abc_method as usize).unwrap()
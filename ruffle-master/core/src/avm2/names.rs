@@ -190,6 +190,42 @@ impl<'gc> QName<'gc> {
         }
     }
 
+    /// Parse a fully qualified name, as given to `getDefinitionByName`.
+    ///
+    /// The package may be separated from the local name by two colons, as in
+    /// `flash.display::Sprite`, or by a dot, as in `flash.display.Sprite`.
+    pub fn from_qualified_name(name: &str, mc: MutationContext<'gc, '_>) -> Self {
+        if let Some(separator) = name.rfind("::") {
+            return Self {
+                ns: Namespace::Package(AvmString::new(mc, name[..separator].to_string())),
+                name: AvmString::new(mc, name[separator + 2..].to_string()),
+            };
+        }
+
+        match name.rfind('.') {
+            Some(separator) => Self {
+                ns: Namespace::Package(AvmString::new(mc, name[..separator].to_string())),
+                name: AvmString::new(mc, name[separator + 1..].to_string()),
+            },
+            None => Self {
+                ns: Namespace::public(),
+                name: AvmString::new(mc, name.to_string()),
+            },
+        }
+    }
+
+    /// The fully qualified name, as returned by `getQualifiedClassName`:
+    /// `flash.display::Sprite`, or just the local name for names in the
+    /// public package.
+    pub fn to_qualified_name(&self) -> String {
+        let uri = self.ns.as_uri();
+        if uri.is_empty() {
+            self.name.to_string()
+        } else {
+            format!("{}::{}", uri, self.name)
+        }
+    }
+
     pub fn local_name(&self) -> AvmString<'gc> {
         self.name
     }
@@ -14,13 +14,17 @@ use crate::avm2::string::AvmString;
 use crate::avm2::traits::{Trait, TraitKind};
 use crate::avm2::value::{Hint, Value};
 use crate::avm2::Error;
+use crate::backend::audio::SoundHandle;
+use crate::bitmap::bitmap_data::BitmapData;
 use crate::display_object::DisplayObject;
+use crate::html::StyleSheet;
 use gc_arena::{Collect, GcCell, MutationContext};
 use ruffle_macros::enum_trait_object;
 use std::cell::{Ref, RefMut};
 use std::fmt::Debug;
 
 mod array_object;
+mod bitmapdata_object;
 mod bytearray_object;
 mod custom_object;
 mod dispatch_object;
@@ -31,10 +35,14 @@ mod namespace_object;
 mod primitive_object;
 mod regexp_object;
 mod script_object;
+mod sound_object;
+mod soundchannel_object;
 mod stage_object;
+mod stylesheet_object;
 mod xml_object;
 
 pub use crate::avm2::object::array_object::ArrayObject;
+pub use crate::avm2::object::bitmapdata_object::BitmapDataObject;
 pub use crate::avm2::object::bytearray_object::ByteArrayObject;
 pub use crate::avm2::object::dispatch_object::DispatchObject;
 pub use crate::avm2::object::domain_object::DomainObject;
@@ -43,8 +51,11 @@ pub use crate::avm2::object::function_object::{implicit_deriver, FunctionObject}
 pub use crate::avm2::object::namespace_object::NamespaceObject;
 pub use crate::avm2::object::primitive_object::PrimitiveObject;
 pub use crate::avm2::object::regexp_object::RegExpObject;
-pub use crate::avm2::object::script_object::ScriptObject;
+pub use crate::avm2::object::script_object::{ScriptObject, ScriptObjectData};
+pub use crate::avm2::object::sound_object::SoundObject;
+pub use crate::avm2::object::soundchannel_object::SoundChannelObject;
 pub use crate::avm2::object::stage_object::StageObject;
+pub use crate::avm2::object::stylesheet_object::StyleSheetObject;
 pub use crate::avm2::object::xml_object::XmlObject;
 
 /// Represents an object that can be directly interacted with by the AVM2
@@ -64,7 +75,11 @@ pub use crate::avm2::object::xml_object::XmlObject;
         DispatchObject(DispatchObject<'gc>),
         XmlObject(XmlObject<'gc>),
         RegExpObject(RegExpObject<'gc>),
-        ByteArrayObject(ByteArrayObject<'gc>)
+        ByteArrayObject(ByteArrayObject<'gc>),
+        SoundObject(SoundObject<'gc>),
+        SoundChannelObject(SoundChannelObject<'gc>),
+        BitmapDataObject(BitmapDataObject<'gc>),
+        StyleSheetObject(StyleSheetObject<'gc>)
     }
 )]
 pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy {
@@ -225,6 +240,20 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     /// Retrieve a method by its index.
     fn get_method(self, id: u32) -> Option<Object<'gc>>;
 
+    /// Retrieves the trait of a method by its dispatch id.
+    ///
+    /// Instances use the flattened traits of their class, so that methods
+    /// overridden by a subclass resolve to the override. This is used to
+    /// install methods that `get_method` doesn't know about yet.
+    fn get_method_trait(self, id: u32) -> Option<Trait<'gc>>;
+
+    /// Retrieves the trait of a method that this object provides by its
+    /// dispatch id.
+    ///
+    /// Like `get_provided_trait`, this yields traits for class constructors
+    /// and prototypes, but not instances.
+    fn get_provided_method_trait(self, id: u32) -> Option<Trait<'gc>>;
+
     /// Retrieves a trait entry by name.
     ///
     /// This function returns `None` if no such trait exists, or the object
@@ -558,15 +587,16 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
                 Ok(function.into())
             }
             TraitKind::Class { slot_id, class } => {
-                let class_read = class.read();
-                let super_class = if let Some(sc_name) = class_read.super_class_name() {
+                let class_name = class.read().name().clone();
+                let super_class_name = class.read().super_class_name().clone();
+                let super_class = if let Some(sc_name) = &super_class_name {
                     let super_name = self
                         .resolve_multiname(sc_name)?
                         .unwrap_or_else(|| QName::dynamic_name("Object"));
 
                     let super_class = if let Some(scope) = scope {
                         scope
-                            .write(activation.context.gc_context)
+                            .read()
                             .resolve(&super_name.clone().into(), activation)?
                     } else {
                         None
@@ -586,11 +616,11 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
                     None
                 };
 
-                let (class_object, _cinit) =
+                let class_object =
                     FunctionObject::from_class(activation, *class, super_class, scope)?;
                 self.install_const(
                     activation.context.gc_context,
-                    class_read.name().clone(),
+                    class_name,
                     *slot_id,
                     class_object.into(),
                 );
@@ -828,6 +858,23 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
         class
     }
 
+    /// Run this object's static class initializer, if it is a class that
+    /// hasn't been initialized yet.
+    ///
+    /// Classes created by `newclass` are initialized right away, and any
+    /// other class is initialized the first time bytecode references it: when
+    /// it is looked up by `getlex` or read out of a slot, constructed, or when
+    /// one of its static properties or methods is read, written or called.
+    /// The superclass is initialized first. A class that is referenced again
+    /// while its initializer is still running is not initialized a second
+    /// time.
+    fn ensure_class_initialized(
+        self,
+        _activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Get this object's `Executable`, if it has one.
     fn as_executable(&self) -> Option<Executable<'gc>> {
         None
@@ -909,6 +956,46 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     fn as_regexp_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<RegExp<'gc>>> {
         None
     }
+
+    /// Unwrap this object's sound handle.
+    fn as_sound(&self) -> Option<SoundHandle> {
+        None
+    }
+
+    /// Associate the object with a particular sound handle.
+    ///
+    /// This does nothing if the object is not a sound.
+    fn set_sound(&self, _mc: MutationContext<'gc, '_>, _sound: SoundHandle) {}
+
+    /// Unwrap this object as a sound channel.
+    fn as_sound_channel(&self) -> Option<SoundChannelObject<'gc>> {
+        None
+    }
+
+    /// Unwrap this object's bitmap data.
+    fn as_bitmap_data(&self) -> Option<GcCell<'gc, BitmapData>> {
+        None
+    }
+
+    /// Associate the object with a particular bitmap data.
+    ///
+    /// This does nothing if the object is not a bitmap data.
+    fn init_bitmap_data(
+        &self,
+        _mc: MutationContext<'gc, '_>,
+        _bitmap_data: GcCell<'gc, BitmapData>,
+    ) {
+    }
+
+    /// Unwrap this object as a style sheet.
+    fn as_style_sheet(&self) -> Option<Ref<StyleSheet>> {
+        None
+    }
+
+    /// Unwrap this object as a mutable style sheet.
+    fn as_style_sheet_mut(&self, _mc: MutationContext<'gc, '_>) -> Option<RefMut<StyleSheet>> {
+        None
+    }
 }
 
 pub enum ObjectPtr {}
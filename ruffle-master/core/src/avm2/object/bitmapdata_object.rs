@@ -0,0 +1,124 @@
+//! Object representation for BitmapData
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::bitmap::bitmap_data::BitmapData;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An object that owns the pixels of a `BitmapData`.
+#[derive(Clone, Collect, Debug, Copy)]
+#[collect(no_drop)]
+pub struct BitmapDataObject<'gc>(GcCell<'gc, BitmapDataObjectData<'gc>>);
+
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct BitmapDataObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The pixels of this bitmap, shared with any `Bitmap` display objects
+    /// that show it.
+    ///
+    /// This is `None` until the `BitmapData` constructor has run, unless the
+    /// object's class is bound to a bitmap symbol.
+    bitmap_data: Option<GcCell<'gc, BitmapData>>,
+}
+
+impl<'gc> BitmapDataObject<'gc> {
+    /// Create a new bitmap data object with no pixels.
+    pub fn empty(mc: MutationContext<'gc, '_>, base_proto: Option<Object<'gc>>) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        BitmapDataObject(GcCell::allocate(
+            mc,
+            BitmapDataObjectData {
+                base,
+                bitmap_data: None,
+            },
+        ))
+        .into()
+    }
+
+    /// Instantiate a bitmap data subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(BitmapDataObject(GcCell::allocate(
+            mc,
+            BitmapDataObjectData {
+                base,
+                bitmap_data: None,
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for BitmapDataObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::BitmapDataObject(*self);
+        let object = BitmapDataObject::empty(activation.context.gc_context, Some(this));
+
+        // Classes bound to a bitmap symbol hold its pixels on their prototype,
+        // and each instance gets its own copy of them.
+        if let Some(bitmap_data) = self.0.read().bitmap_data {
+            let read = bitmap_data.read();
+            let mut copy = BitmapData::default();
+            copy.init_pixels(read.width(), read.height(), 0, read.transparency());
+            copy.set_pixels(read.pixels().to_vec());
+            object.init_bitmap_data(
+                activation.context.gc_context,
+                GcCell::allocate(activation.context.gc_context, copy),
+            );
+        }
+
+        Ok(object)
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::BitmapDataObject(*self);
+
+        Self::derive(this, activation.context.gc_context, class, scope)
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Object::from(*self).into())
+    }
+
+    fn as_bitmap_data(&self) -> Option<GcCell<'gc, BitmapData>> {
+        self.0.read().bitmap_data
+    }
+
+    fn init_bitmap_data(&self, mc: MutationContext<'gc, '_>, bitmap_data: GcCell<'gc, BitmapData>) {
+        self.0.write(mc).bitmap_data = Some(bitmap_data);
+    }
+}
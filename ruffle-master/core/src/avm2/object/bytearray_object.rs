@@ -201,10 +201,18 @@ impl<'gc> TObject<'gc> for ByteArrayObject<'gc> {
         _args: &[Value<'gc>],
     ) -> Result<Object<'gc>, Error> {
         let this: Object<'gc> = Object::ByteArrayObject(*self);
-        Ok(ByteArrayObject::construct(
-            activation.context.gc_context,
-            Some(this),
-        ))
+        let object = ByteArrayObject::construct(activation.context.gc_context, Some(this));
+
+        // Classes bound to a binary data symbol hold its data on their
+        // prototype, and each instance gets its own copy of it.
+        let data = self.0.read().storage.bytes().clone();
+        if !data.is_empty() {
+            if let Some(mut storage) = object.as_bytearray_mut(activation.context.gc_context) {
+                storage.write_bytes_at(&data, 0);
+            }
+        }
+
+        Ok(object)
     }
 
     fn derive(
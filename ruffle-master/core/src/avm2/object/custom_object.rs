@@ -120,6 +120,14 @@ macro_rules! impl_avm2_custom_object {
             self.0.read().$field.get_method(id)
         }
 
+        fn get_method_trait(self, id: u32) -> Option<Trait<'gc>> {
+            self.0.read().$field.get_method_trait(id)
+        }
+
+        fn get_provided_method_trait(self, id: u32) -> Option<Trait<'gc>> {
+            self.0.read().$field.get_provided_method_trait(id)
+        }
+
         fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
             self.0.read().$field.get_trait(name)
         }
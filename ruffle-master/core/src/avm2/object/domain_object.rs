@@ -76,17 +76,14 @@ impl<'gc> TObject<'gc> for DomainObject<'gc> {
         args: &[Value<'gc>],
     ) -> Result<Object<'gc>, Error> {
         let this: Object<'gc> = Object::DomainObject(*self);
-        let parent_domain = if let Some(parent_domain) = args
-            .get(0)
-            .cloned()
-            .unwrap_or(Value::Undefined)
-            .coerce_to_object(activation)?
-            .as_application_domain()
-        {
-            parent_domain
-        } else {
-            activation.context.avm2.global_domain()
-        };
+        // `new ApplicationDomain()` makes a new child of the global domain.
+        let parent_domain = match args.get(0).cloned().unwrap_or(Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            parent_domain => parent_domain
+                .coerce_to_object(activation)?
+                .as_application_domain(),
+        }
+        .unwrap_or_else(|| activation.context.avm2.global_domain());
 
         Ok(DomainObject::from_domain(
             activation.context.gc_context,
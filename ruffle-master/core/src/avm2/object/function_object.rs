@@ -5,7 +5,9 @@ use crate::avm2::class::Class;
 use crate::avm2::function::Executable;
 use crate::avm2::method::{Method, NativeMethod};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::script_object::{ScriptObject, ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::script_object::{
+    instance_class, ScriptObject, ScriptObjectClass, ScriptObjectData,
+};
 use crate::avm2::object::{Object, ObjectPtr, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::string::AvmString;
@@ -28,6 +30,34 @@ pub struct FunctionObjectData<'gc> {
 
     /// Executable code
     exec: Option<Executable<'gc>>,
+
+    /// The static initializer of the class this function constructs, if any.
+    class_init: Option<Object<'gc>>,
+
+    /// The class this class extends, which is initialized before it.
+    superclass: Option<Object<'gc>>,
+
+    /// Whether or not `class_init` has been run yet.
+    init_state: ClassInitState,
+}
+
+/// How far along a class is in running its static initializer.
+#[derive(Collect, Debug, Clone, Copy, PartialEq, Eq)]
+#[collect(require_static)]
+enum ClassInitState {
+    /// The static initializer has not been run yet.
+    Uninitialized,
+
+    /// The static initializer is currently running.
+    ///
+    /// References to the class from within its own initializer (or from the
+    /// initializer of a class it references in turn) don't run it again, and
+    /// see the statics that have been set so far. This matches Flash Player,
+    /// and is what keeps circular static references from recursing forever.
+    InProgress,
+
+    /// The static initializer has finished, or the class doesn't have one.
+    Initialized,
 }
 
 pub fn implicit_deriver<'gc>(
@@ -42,9 +72,9 @@ pub fn implicit_deriver<'gc>(
 impl<'gc> FunctionObject<'gc> {
     /// Construct a class.
     ///
-    /// This function returns both the class itself, and the static class
-    /// initializer method that you should call before interacting with the
-    /// class. The latter should be called using the former as a receiver.
+    /// The class's static initializer is not run here. `newclass` runs it
+    /// straight away, and classes created any other way run it the first time
+    /// they are referenced, see `TObject::ensure_class_initialized`.
     ///
     /// `base_class` is allowed to be `None`, corresponding to a `null` value
     /// in the VM. This corresponds to no base class, and in practice appears
@@ -55,7 +85,7 @@ impl<'gc> FunctionObject<'gc> {
         class: GcCell<'gc, Class<'gc>>,
         base_class: Option<Object<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<(Object<'gc>, Object<'gc>), Error> {
+    ) -> Result<Object<'gc>, Error> {
         FunctionObject::from_class_with_deriver(
             activation,
             class,
@@ -79,7 +109,7 @@ impl<'gc> FunctionObject<'gc> {
         base_class: Option<Object<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
         derive: DERIVE,
-    ) -> Result<(Object<'gc>, Object<'gc>), Error>
+    ) -> Result<Object<'gc>, Error>
     where
         DERIVE: FnOnce(
             Object<'gc>,
@@ -88,7 +118,6 @@ impl<'gc> FunctionObject<'gc> {
             Option<GcCell<'gc, Scope<'gc>>>,
         ) -> Result<Object<'gc>, Error>,
     {
-        let class_read = class.read();
         let class_proto = if let Some(mut base_class) = base_class {
             let super_proto: Result<_, Error> = base_class
                 .get_property(
@@ -100,7 +129,8 @@ impl<'gc> FunctionObject<'gc> {
                 .map_err(|_| {
                     format!(
                         "Could not resolve superclass prototype {:?}",
-                        class_read
+                        class
+                            .read()
                             .super_class_name()
                             .as_ref()
                             .map(|p| p.local_name())
@@ -114,7 +144,7 @@ impl<'gc> FunctionObject<'gc> {
             ScriptObject::bare_object(activation.context.gc_context)
         };
 
-        FunctionObject::from_class_and_proto(activation, class, class_proto, scope)
+        FunctionObject::from_class_and_proto(activation, class, class_proto, base_class, scope)
     }
 
     /// Construct a class with a custom object type as its prototype.
@@ -122,15 +152,15 @@ impl<'gc> FunctionObject<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         class: GcCell<'gc, Class<'gc>>,
         mut class_proto: Object<'gc>,
+        superclass: Option<Object<'gc>>,
         scope: Option<GcCell<'gc, Scope<'gc>>>,
-    ) -> Result<(Object<'gc>, Object<'gc>), Error> {
+    ) -> Result<Object<'gc>, Error> {
         let mut interfaces = Vec::new();
+        let mut interface_classes = Vec::new();
         let interface_names = class.read().interfaces().to_vec();
         for interface_name in interface_names {
             let interface = if let Some(scope) = scope {
-                scope
-                    .write(activation.context.gc_context)
-                    .resolve(&interface_name, activation)?
+                scope.read().resolve(&interface_name, activation)?
             } else {
                 None
             };
@@ -149,8 +179,13 @@ impl<'gc> FunctionObject<'gc> {
                 .coerce_to_object(activation)?;
 
             interfaces.push(iface_proto);
+            interface_classes.extend(interface.as_class());
         }
 
+        class
+            .write(activation.context.gc_context)
+            .link_instance_traits(instance_class(class_proto.proto()), &interface_classes)?;
+
         if !interfaces.is_empty() {
             class_proto.set_interfaces(activation.context.gc_context, interfaces);
         }
@@ -160,6 +195,13 @@ impl<'gc> FunctionObject<'gc> {
 
         let class_read = class.read();
         let initializer = class_read.instance_init();
+        let class_initializer = FunctionObject::from_method(
+            activation.context.gc_context,
+            class_read.class_init(),
+            scope,
+            class_constr_proto,
+            None,
+        );
 
         let mut constr: Object<'gc> = FunctionObject(GcCell::allocate(
             activation.context.gc_context,
@@ -174,6 +216,9 @@ impl<'gc> FunctionObject<'gc> {
                     None,
                     activation.context.gc_context,
                 )),
+                class_init: Some(class_initializer),
+                superclass,
+                init_state: ClassInitState::Uninitialized,
             },
         ))
         .into();
@@ -189,16 +234,7 @@ impl<'gc> FunctionObject<'gc> {
             constr.into(),
         )?;
 
-        let class_initializer = class_read.class_init();
-        let class_constr = FunctionObject::from_method(
-            activation.context.gc_context,
-            class_initializer,
-            scope,
-            class_constr_proto,
-            None,
-        );
-
-        Ok((constr, class_constr))
+        Ok(constr)
     }
 
     /// Construct a function from an ABC method and the current closure scope.
@@ -219,6 +255,9 @@ impl<'gc> FunctionObject<'gc> {
             FunctionObjectData {
                 base: ScriptObjectData::base_new(Some(fn_proto), ScriptObjectClass::NoClass),
                 exec,
+                class_init: None,
+                superclass: None,
+                init_state: ClassInitState::Initialized,
             },
         ))
         .into()
@@ -235,6 +274,9 @@ impl<'gc> FunctionObject<'gc> {
             FunctionObjectData {
                 base: ScriptObjectData::base_new(Some(fn_proto), ScriptObjectClass::NoClass),
                 exec: Some(Executable::from_method(nf.into(), None, None, mc)),
+                class_init: None,
+                superclass: None,
+                init_state: ClassInitState::Initialized,
             },
         ))
         .into()
@@ -248,6 +290,12 @@ impl<'gc> FunctionObject<'gc> {
         fn_proto: Object<'gc>,
     ) -> Result<Object<'gc>, Error> {
         let scope = prototype.get_scope();
+        if let Some(class) = prototype.as_class() {
+            class
+                .write(mc)
+                .link_instance_traits(instance_class(prototype.proto()), &[])?;
+        }
+
         let class = prototype
             .as_class()
             .map(|c| ScriptObjectClass::ClassConstructor(c, scope))
@@ -257,6 +305,9 @@ impl<'gc> FunctionObject<'gc> {
             FunctionObjectData {
                 base: ScriptObjectData::base_new(Some(fn_proto), class),
                 exec: Some(Executable::from_method(constr.into(), None, None, mc)),
+                class_init: None,
+                superclass: None,
+                init_state: ClassInitState::Initialized,
             },
         ))
         .into();
@@ -314,6 +365,38 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
         }
     }
 
+    fn ensure_class_initialized(
+        self,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error> {
+        let (class_init, superclass) = {
+            let read = self.0.read();
+            if read.init_state != ClassInitState::Uninitialized {
+                return Ok(());
+            }
+
+            (read.class_init, read.superclass)
+        };
+
+        self.0.write(activation.context.gc_context).init_state = ClassInitState::InProgress;
+
+        let result = superclass
+            .map_or(Ok(()), |superclass| {
+                superclass.ensure_class_initialized(activation)
+            })
+            .and_then(|()| match class_init {
+                Some(class_init) => class_init.call(Some(self.into()), &[], activation, None),
+                None => Ok(Value::Undefined),
+            });
+
+        // Static initializers only ever run once, even if they throw.
+        self.0.write(activation.context.gc_context).init_state = ClassInitState::Initialized;
+
+        result?;
+
+        Ok(())
+    }
+
     fn construct(
         &self,
         activation: &mut Activation<'_, 'gc, '_>,
@@ -324,7 +407,13 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
 
         Ok(FunctionObject(GcCell::allocate(
             activation.context.gc_context,
-            FunctionObjectData { base, exec: None },
+            FunctionObjectData {
+                base,
+                exec: None,
+                class_init: None,
+                superclass: None,
+                init_state: ClassInitState::Initialized,
+            },
         ))
         .into())
     }
@@ -343,7 +432,13 @@ impl<'gc> TObject<'gc> for FunctionObject<'gc> {
 
         Ok(FunctionObject(GcCell::allocate(
             activation.context.gc_context,
-            FunctionObjectData { base, exec: None },
+            FunctionObjectData {
+                base,
+                exec: None,
+                class_init: None,
+                superclass: None,
+                init_state: ClassInitState::Initialized,
+            },
         ))
         .into())
     }
@@ -43,6 +43,24 @@ pub enum ScriptObjectClass<'gc> {
     NoClass,
 }
 
+/// Find the class whose instance traits are inherited by objects with the
+/// given prototype.
+///
+/// This is the class of the nearest instance prototype in the chain. Since
+/// that class was linked against its superclasses, the prototypes past it
+/// need not be searched.
+pub fn instance_class<'gc>(mut proto: Option<Object<'gc>>) -> Option<GcCell<'gc, Class<'gc>>> {
+    while let Some(p) = proto {
+        if let Some(class) = p.as_class() {
+            return Some(class);
+        }
+
+        proto = p.proto();
+    }
+
+    None
+}
+
 /// Base data common to all `TObject` implementations.
 ///
 /// Host implementations of `TObject` should embed `ScriptObjectData` and
@@ -57,8 +75,11 @@ pub struct ScriptObjectData<'gc> {
     /// Slots stored on this object.
     slots: Vec<Slot<'gc>>,
 
-    /// Methods stored on this object.
-    methods: Vec<Option<Object<'gc>>>,
+    /// Methods stored on this object, keyed by dispatch id.
+    ///
+    /// Dispatch ids come straight from the ABC file, so they are not used as
+    /// indices into a table that would have to be as large as the biggest id.
+    methods: HashMap<u32, Object<'gc>>,
 
     /// Implicit prototype of this script object.
     proto: Option<Object<'gc>>,
@@ -160,6 +181,14 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         self.0.read().get_method(id)
     }
 
+    fn get_method_trait(self, id: u32) -> Option<Trait<'gc>> {
+        self.0.read().get_method_trait(id)
+    }
+
+    fn get_provided_method_trait(self, id: u32) -> Option<Trait<'gc>> {
+        self.0.read().get_provided_method_trait(id)
+    }
+
     fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
         self.0.read().get_trait(name)
     }
@@ -412,7 +441,7 @@ impl<'gc> ScriptObjectData<'gc> {
         ScriptObjectData {
             values: HashMap::new(),
             slots: Vec::new(),
-            methods: Vec::new(),
+            methods: HashMap::new(),
             proto,
             class: trait_source,
             enumerants: Vec::new(),
@@ -553,7 +582,35 @@ impl<'gc> ScriptObjectData<'gc> {
 
     /// Retrieve a method from the method table.
     pub fn get_method(&self, id: u32) -> Option<Object<'gc>> {
-        self.methods.get(id as usize).and_then(|v| *v)
+        self.methods.get(&id).copied()
+    }
+
+    /// Retrieve the trait of a method by its dispatch id.
+    pub fn get_method_trait(&self, id: u32) -> Option<Trait<'gc>> {
+        match &self.class {
+            //Class constructors have local traits only.
+            ScriptObjectClass::ClassConstructor(..) => self.get_provided_method_trait(id),
+
+            //Prototypes do not have traits available locally.
+            ScriptObjectClass::InstancePrototype(..) => None,
+
+            //Instances use the flattened traits of their class, in which
+            //overridden methods have already been replaced.
+            ScriptObjectClass::NoClass => instance_class(self.proto())
+                .and_then(|class| class.read().linked_instance_method(id).cloned()),
+        }
+    }
+
+    pub fn get_provided_method_trait(&self, id: u32) -> Option<Trait<'gc>> {
+        match &self.class {
+            ScriptObjectClass::ClassConstructor(class, ..) => {
+                class.read().class_method(id).cloned()
+            }
+            ScriptObjectClass::InstancePrototype(class, ..) => {
+                class.read().instance_method(id).cloned()
+            }
+            ScriptObjectClass::NoClass => None,
+        }
     }
 
     pub fn get_trait(&self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
@@ -570,20 +627,14 @@ impl<'gc> ScriptObjectData<'gc> {
             //traits instead.
             ScriptObjectClass::InstancePrototype(..) => Ok(Vec::new()),
 
-            //Instances walk the prototype chain to build a list of known
-            //traits provided by the classes attached to those prototypes.
+            //Instances use the flattened traits of their class, which already
+            //include everything inherited from its superclasses.
             ScriptObjectClass::NoClass => {
                 let mut known_traits = Vec::new();
-                let mut chain = Vec::new();
-                let mut proto = self.proto();
-
-                while let Some(p) = proto {
-                    chain.push(p);
-                    proto = p.proto();
-                }
-
-                for proto in chain.iter().rev() {
-                    proto.get_provided_trait(name, &mut known_traits)?;
+                if let Some(class) = instance_class(self.proto()) {
+                    class
+                        .read()
+                        .lookup_linked_instance_traits(name, &mut known_traits)?;
                 }
 
                 Ok(known_traits)
@@ -616,21 +667,10 @@ impl<'gc> ScriptObjectData<'gc> {
             //through them to find traits (see `provides_trait`)
             ScriptObjectClass::InstancePrototype(..) => Ok(false),
 
-            //Instances walk the prototype chain to build a list of known
-            //traits provided by the classes attached to those prototypes.
-            ScriptObjectClass::NoClass => {
-                let mut proto = self.proto();
-
-                while let Some(p) = proto {
-                    if p.provides_trait(name)? {
-                        return Ok(true);
-                    }
-
-                    proto = p.proto();
-                }
-
-                Ok(false)
-            }
+            //Instances use the flattened traits of their class.
+            ScriptObjectClass::NoClass => Ok(instance_class(self.proto())
+                .map(|class| class.read().has_linked_instance_trait(name))
+                .unwrap_or(false)),
         }
     }
 
@@ -682,6 +722,12 @@ impl<'gc> ScriptObjectData<'gc> {
         &self,
         local_name: AvmString<'gc>,
     ) -> Result<Option<Namespace<'gc>>, Error> {
+        //Prototypes resolve names against the flattened traits of their
+        //class, which already cover the rest of the prototype chain.
+        if let ScriptObjectClass::InstancePrototype(class, ..) = &self.class {
+            return Ok(class.read().resolve_any_linked_instance_trait(local_name));
+        }
+
         if let Some(proto) = self.proto {
             let proto_trait_name = proto.resolve_any_trait(local_name)?;
             if let Some(ns) = proto_trait_name {
@@ -693,10 +739,7 @@ impl<'gc> ScriptObjectData<'gc> {
             ScriptObjectClass::ClassConstructor(class, ..) => {
                 Ok(class.read().resolve_any_class_trait(local_name))
             }
-            ScriptObjectClass::InstancePrototype(class, ..) => {
-                Ok(class.read().resolve_any_instance_trait(local_name))
-            }
-            ScriptObjectClass::NoClass => Ok(None),
+            ScriptObjectClass::InstancePrototype(..) | ScriptObjectClass::NoClass => Ok(None),
         }
     }
 
@@ -781,12 +824,7 @@ impl<'gc> ScriptObjectData<'gc> {
     /// Install a method into the object.
     pub fn install_method(&mut self, name: QName<'gc>, disp_id: u32, function: Object<'gc>) {
         if disp_id > 0 {
-            if self.methods.len() <= disp_id as usize {
-                self.methods
-                    .resize_with(disp_id as usize + 1, Default::default);
-            }
-
-            *self.methods.get_mut(disp_id as usize).unwrap() = Some(function);
+            self.methods.insert(disp_id, function);
         }
 
         self.values.insert(name, Property::new_method(function));
@@ -808,12 +846,7 @@ impl<'gc> ScriptObjectData<'gc> {
             .ok_or_else(|| Error::from("Attempted to install getter without a valid method"))?;
 
         if disp_id > 0 {
-            if self.methods.len() <= disp_id as usize {
-                self.methods
-                    .resize_with(disp_id as usize + 1, Default::default);
-            }
-
-            *self.methods.get_mut(disp_id as usize).unwrap() = Some(function);
+            self.methods.insert(disp_id, function);
         }
 
         if !self.values.contains_key(&name) {
@@ -842,12 +875,7 @@ impl<'gc> ScriptObjectData<'gc> {
             .ok_or_else(|| Error::from("Attempted to install setter without a valid method"))?;
 
         if disp_id > 0 {
-            if self.methods.len() <= disp_id as usize {
-                self.methods
-                    .resize_with(disp_id as usize + 1, Default::default);
-            }
-
-            *self.methods.get_mut(disp_id as usize).unwrap() = Some(function);
+            self.methods.insert(disp_id, function);
         }
 
         if !self.values.contains_key(&name) {
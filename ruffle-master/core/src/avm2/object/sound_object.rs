@@ -0,0 +1,105 @@
+//! Object representation for sounds
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::audio::SoundHandle;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An object that holds a sound definition.
+///
+/// Instances of a class linked to a `DefineSound` character via `SymbolClass`
+/// inherit the sound handle of the class prototype.
+#[derive(Clone, Collect, Debug, Copy)]
+#[collect(no_drop)]
+pub struct SoundObject<'gc>(GcCell<'gc, SoundObjectData<'gc>>);
+
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct SoundObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The sound this object holds.
+    #[collect(require_static)]
+    sound: Option<SoundHandle>,
+}
+
+impl<'gc> SoundObject<'gc> {
+    /// Convert a bare sound into it's object representation.
+    pub fn from_sound(
+        mc: MutationContext<'gc, '_>,
+        base_proto: Option<Object<'gc>>,
+        sound: Option<SoundHandle>,
+    ) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        SoundObject(GcCell::allocate(mc, SoundObjectData { base, sound })).into()
+    }
+
+    /// Instantiate a sound subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(SoundObject(GcCell::allocate(mc, SoundObjectData { base, sound: None })).into())
+    }
+}
+
+impl<'gc> TObject<'gc> for SoundObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::SoundObject(*self);
+        let sound = self.0.read().sound;
+
+        Ok(SoundObject::from_sound(
+            activation.context.gc_context,
+            Some(this),
+            sound,
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::SoundObject(*self);
+
+        Self::derive(this, activation.context.gc_context, class, scope)
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Object::from(*self).into())
+    }
+
+    fn as_sound(&self) -> Option<SoundHandle> {
+        self.0.read().sound
+    }
+
+    fn set_sound(&self, mc: MutationContext<'gc, '_>, sound: SoundHandle) {
+        self.0.write(mc).sound = Some(sound);
+    }
+}
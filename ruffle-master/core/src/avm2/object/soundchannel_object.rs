@@ -0,0 +1,174 @@
+//! Object representation for sound channels
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::audio::SoundInstanceHandle;
+use crate::display_object::SoundTransform;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+
+/// An object that represents a single playing sound instance.
+#[derive(Clone, Collect, Debug, Copy)]
+#[collect(no_drop)]
+pub struct SoundChannelObject<'gc>(GcCell<'gc, SoundChannelObjectData<'gc>>);
+
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct SoundChannelObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The sound instance this channel controls, if it is still playing.
+    #[collect(require_static)]
+    sound_instance: Option<SoundInstanceHandle>,
+
+    /// The last known playback position of the sound, in milliseconds.
+    position: f64,
+
+    /// The sound transform applied to this channel.
+    #[collect(require_static)]
+    sound_transform: SoundTransform,
+
+    /// The `Sound` object that generates samples for this channel via
+    /// `sampleData` events, if this channel is playing a dynamic sound.
+    sample_data_source: Option<Object<'gc>>,
+
+    /// The number of sample frames that have been requested from the sample
+    /// data source so far.
+    sample_data_position: f64,
+}
+
+impl<'gc> SoundChannelObject<'gc> {
+    /// Create a new, silent sound channel.
+    pub fn empty(mc: MutationContext<'gc, '_>, base_proto: Option<Object<'gc>>) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        SoundChannelObject(GcCell::allocate(
+            mc,
+            SoundChannelObjectData {
+                base,
+                sound_instance: None,
+                position: 0.0,
+                sound_transform: SoundTransform::default(),
+                sample_data_source: None,
+                sample_data_position: 0.0,
+            },
+        ))
+        .into()
+    }
+
+    /// Instantiate a sound channel subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(SoundChannelObject(GcCell::allocate(
+            mc,
+            SoundChannelObjectData {
+                base,
+                sound_instance: None,
+                position: 0.0,
+                sound_transform: SoundTransform::default(),
+                sample_data_source: None,
+                sample_data_position: 0.0,
+            },
+        ))
+        .into())
+    }
+
+    /// The sound instance this channel controls, if it is still playing.
+    pub fn instance(self) -> Option<SoundInstanceHandle> {
+        self.0.read().sound_instance
+    }
+
+    pub fn set_instance(self, mc: MutationContext<'gc, '_>, instance: Option<SoundInstanceHandle>) {
+        self.0.write(mc).sound_instance = instance;
+    }
+
+    /// The last known playback position of the sound, in milliseconds.
+    pub fn position(self) -> f64 {
+        self.0.read().position
+    }
+
+    pub fn set_position(self, mc: MutationContext<'gc, '_>, position: f64) {
+        self.0.write(mc).position = position;
+    }
+
+    pub fn sound_transform(self) -> SoundTransform {
+        self.0.read().sound_transform.clone()
+    }
+
+    pub fn set_sound_transform(self, mc: MutationContext<'gc, '_>, transform: SoundTransform) {
+        self.0.write(mc).sound_transform = transform;
+    }
+
+    /// The `Sound` object that generates samples for this channel, if any.
+    pub fn sample_data_source(self) -> Option<Object<'gc>> {
+        self.0.read().sample_data_source
+    }
+
+    pub fn set_sample_data_source(self, mc: MutationContext<'gc, '_>, source: Option<Object<'gc>>) {
+        self.0.write(mc).sample_data_source = source;
+    }
+
+    /// The number of sample frames requested from the sample data source.
+    pub fn sample_data_position(self) -> f64 {
+        self.0.read().sample_data_position
+    }
+
+    pub fn set_sample_data_position(self, mc: MutationContext<'gc, '_>, position: f64) {
+        self.0.write(mc).sample_data_position = position;
+    }
+}
+
+impl<'gc> TObject<'gc> for SoundChannelObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::SoundChannelObject(*self);
+
+        Ok(SoundChannelObject::empty(
+            activation.context.gc_context,
+            Some(this),
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::SoundChannelObject(*self);
+
+        Self::derive(this, activation.context.gc_context, class, scope)
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Object::from(*self).into())
+    }
+
+    fn as_sound_channel(&self) -> Option<SoundChannelObject<'gc>> {
+        Some(*self)
+    }
+}
@@ -161,6 +161,14 @@ impl<'gc> TObject<'gc> for StageObject<'gc> {
         self.0.read().base.get_method(id)
     }
 
+    fn get_method_trait(self, id: u32) -> Option<Trait<'gc>> {
+        self.0.read().base.get_method_trait(id)
+    }
+
+    fn get_provided_method_trait(self, id: u32) -> Option<Trait<'gc>> {
+        self.0.read().base.get_provided_method_trait(id)
+    }
+
     fn get_trait(self, name: &QName<'gc>) -> Result<Vec<Trait<'gc>>, Error> {
         self.0.read().base.get_trait(name)
     }
@@ -0,0 +1,110 @@
+//! Object representation for StyleSheet
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::script_object::{ScriptObjectClass, ScriptObjectData};
+use crate::avm2::object::{Object, ObjectPtr, TObject};
+use crate::avm2::scope::Scope;
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::html::StyleSheet;
+use crate::{impl_avm2_custom_object, impl_avm2_custom_object_properties};
+use gc_arena::{Collect, GcCell, MutationContext};
+use std::cell::{Ref, RefMut};
+
+/// An object that holds the CSS styles of a `StyleSheet`.
+#[derive(Clone, Collect, Debug, Copy)]
+#[collect(no_drop)]
+pub struct StyleSheetObject<'gc>(GcCell<'gc, StyleSheetObjectData<'gc>>);
+
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub struct StyleSheetObjectData<'gc> {
+    /// Base script object
+    base: ScriptObjectData<'gc>,
+
+    /// The styles of this style sheet.
+    style_sheet: StyleSheet,
+}
+
+impl<'gc> StyleSheetObject<'gc> {
+    /// Create a new style sheet object with no styles.
+    pub fn empty(mc: MutationContext<'gc, '_>, base_proto: Option<Object<'gc>>) -> Object<'gc> {
+        let base = ScriptObjectData::base_new(base_proto, ScriptObjectClass::NoClass);
+
+        StyleSheetObject(GcCell::allocate(
+            mc,
+            StyleSheetObjectData {
+                base,
+                style_sheet: StyleSheet::new(),
+            },
+        ))
+        .into()
+    }
+
+    /// Instantiate a style sheet subclass.
+    pub fn derive(
+        base_proto: Object<'gc>,
+        mc: MutationContext<'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let base = ScriptObjectData::base_new(
+            Some(base_proto),
+            ScriptObjectClass::InstancePrototype(class, scope),
+        );
+
+        Ok(StyleSheetObject(GcCell::allocate(
+            mc,
+            StyleSheetObjectData {
+                base,
+                style_sheet: StyleSheet::new(),
+            },
+        ))
+        .into())
+    }
+}
+
+impl<'gc> TObject<'gc> for StyleSheetObject<'gc> {
+    impl_avm2_custom_object!(base);
+    impl_avm2_custom_object_properties!(base);
+
+    fn construct(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        _args: &[Value<'gc>],
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::StyleSheetObject(*self);
+
+        Ok(StyleSheetObject::empty(
+            activation.context.gc_context,
+            Some(this),
+        ))
+    }
+
+    fn derive(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        class: GcCell<'gc, Class<'gc>>,
+        scope: Option<GcCell<'gc, Scope<'gc>>>,
+    ) -> Result<Object<'gc>, Error> {
+        let this: Object<'gc> = Object::StyleSheetObject(*self);
+
+        Self::derive(this, activation.context.gc_context, class, scope)
+    }
+
+    fn value_of(&self, _mc: MutationContext<'gc, '_>) -> Result<Value<'gc>, Error> {
+        Ok(Object::from(*self).into())
+    }
+
+    fn as_style_sheet(&self) -> Option<Ref<StyleSheet>> {
+        Some(Ref::map(self.0.read(), |d| &d.style_sheet))
+    }
+
+    fn as_style_sheet_mut(&self, mc: MutationContext<'gc, '_>) -> Option<RefMut<StyleSheet>> {
+        Some(RefMut::map(self.0.write(mc), |d| &mut d.style_sheet))
+    }
+}
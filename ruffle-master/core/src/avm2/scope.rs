@@ -22,6 +22,11 @@ pub enum ScopeClass {
 }
 
 /// Represents a scope chain for an AVM2 activation.
+///
+/// A scope is never modified after it is created: pushing or popping a scope
+/// produces a new chain that shares its tail with the old one. This means that
+/// holding onto a scope chain snapshots it, which is how closures capture the
+/// scope stack they were created with.
 #[derive(Debug, Collect)]
 #[collect(no_drop)]
 pub struct Scope<'gc> {
@@ -87,11 +92,6 @@ impl<'gc> Scope<'gc> {
         }
     }
 
-    /// Returns a reference to the current local scope object for mutation.
-    pub fn locals_mut(&mut self) -> &mut Object<'gc> {
-        &mut self.values
-    }
-
     /// Returns a reference to the parent scope object.
     pub fn parent(&self) -> Option<Ref<Scope<'gc>>> {
         match self.parent {
@@ -138,24 +138,19 @@ impl<'gc> Scope<'gc> {
     /// This function yields `None` if no such scope exists to provide the
     /// property's value.
     pub fn resolve(
-        &mut self,
+        &self,
         name: &Multiname<'gc>,
         activation: &mut Activation<'_, 'gc, '_>,
     ) -> Result<Option<Value<'gc>>, Error> {
         if let Some(qname) = self.locals().resolve_multiname(name)? {
             if self.locals().has_property(&qname)? {
-                return Ok(Some(self.values.get_property(
-                    self.values,
-                    &qname,
-                    activation,
-                )?));
+                let mut values = self.values;
+                return Ok(Some(values.get_property(values, &qname, activation)?));
             }
         }
 
-        if let Some(parent) = self.parent {
-            return parent
-                .write(activation.context.gc_context)
-                .resolve(name, activation);
+        if let Some(parent) = self.parent() {
+            return parent.resolve(name, activation);
         }
 
         if let Some(domain) = self.locals().as_application_domain() {
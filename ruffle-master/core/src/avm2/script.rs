@@ -7,7 +7,7 @@ use crate::avm2::method::{BytecodeMethod, Method};
 use crate::avm2::object::{DomainObject, Object, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::string::AvmString;
-use crate::avm2::traits::Trait;
+use crate::avm2::traits::{Trait, TraitKind};
 use crate::avm2::value::Value;
 use crate::avm2::{Avm2, Error};
 use crate::collect::CollectWrapper;
@@ -358,6 +358,20 @@ impl<'gc> Script<'gc> {
             drop(write);
 
             for trait_entry in self.traits()?.iter() {
+                // Classes are created by the script initializer's `newclass`,
+                // which then stores them here. Until it does, the class is
+                // null, including to a class initializer that circularly
+                // references it.
+                if let TraitKind::Class { slot_id, .. } = trait_entry.kind() {
+                    globals.install_const(
+                        null_activation.context.gc_context,
+                        trait_entry.name().clone(),
+                        *slot_id,
+                        Value::Null,
+                    );
+                    continue;
+                }
+
                 globals.install_foreign_trait(
                     &mut null_activation,
                     trait_entry.clone(),
@@ -4,6 +4,7 @@ use crate::avm2::class::Class;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Multiname, QName};
 use crate::avm2::script::TranslationUnit;
+use crate::avm2::string::AvmString;
 use crate::avm2::value::{abc_default_value, Value};
 use crate::avm2::{Avm2, Error};
 use crate::collect::CollectWrapper;
@@ -45,6 +46,25 @@ pub struct Trait<'gc> {
 
     /// The kind of trait in use.
     kind: TraitKind<'gc>,
+
+    /// The metadata the compiler attached to this trait.
+    metadata: Vec<Metadata<'gc>>,
+}
+
+/// A piece of metadata attached to a trait, such as `[Inject]` or
+/// `[Event(name="change", type="flash.events.Event")]`.
+///
+/// Compilers keep only the metadata they are told to, and it has no effect on
+/// the trait; it can only be read back with `describeType`, or from Rust with
+/// `Avm2::class_metadata`.
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+pub struct Metadata<'gc> {
+    pub name: AvmString<'gc>,
+
+    /// The arguments of the metadata, as key and value pairs. Arguments that
+    /// were given without a key have an empty one.
+    pub items: Vec<(AvmString<'gc>, AvmString<'gc>)>,
 }
 
 fn trait_attribs_from_abc_traits(abc_trait: &AbcTrait) -> CollectWrapper<TraitAttributes> {
@@ -104,6 +124,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Class { slot_id: 0, class },
         }
     }
@@ -112,6 +133,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Method { disp_id: 0, method },
         }
     }
@@ -120,6 +142,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Getter { disp_id: 0, method },
         }
     }
@@ -128,6 +151,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Setter { disp_id: 0, method },
         }
     }
@@ -136,6 +160,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Function {
                 slot_id: 0,
                 function,
@@ -151,6 +176,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Slot {
                 slot_id: 0,
                 type_name,
@@ -167,6 +193,7 @@ impl<'gc> Trait<'gc> {
         Trait {
             name,
             attributes: CollectWrapper(TraitAttributes::empty()),
+            metadata: Vec::new(),
             kind: TraitKind::Slot {
                 slot_id: 0,
                 type_name,
@@ -184,7 +211,7 @@ impl<'gc> Trait<'gc> {
     ) -> Result<Self, Error> {
         let name = QName::from_abc_multiname(unit, abc_trait.name.clone(), mc)?;
 
-        Ok(match &abc_trait.kind {
+        let mut loaded = match &abc_trait.kind {
             AbcTraitKind::Slot {
                 slot_id,
                 type_name,
@@ -192,6 +219,7 @@ impl<'gc> Trait<'gc> {
             } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Slot {
                     slot_id: *slot_id,
                     type_name: if type_name.0 == 0 {
@@ -209,6 +237,7 @@ impl<'gc> Trait<'gc> {
             AbcTraitKind::Method { disp_id, method } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Method {
                     disp_id: *disp_id,
                     method: unit.load_method(method.0, mc)?,
@@ -217,6 +246,7 @@ impl<'gc> Trait<'gc> {
             AbcTraitKind::Getter { disp_id, method } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Getter {
                     disp_id: *disp_id,
                     method: unit.load_method(method.0, mc)?,
@@ -225,6 +255,7 @@ impl<'gc> Trait<'gc> {
             AbcTraitKind::Setter { disp_id, method } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Setter {
                     disp_id: *disp_id,
                     method: unit.load_method(method.0, mc)?,
@@ -233,6 +264,7 @@ impl<'gc> Trait<'gc> {
             AbcTraitKind::Class { slot_id, class } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Class {
                     slot_id: *slot_id,
                     class: unit.load_class(class.0, avm2, mc)?,
@@ -241,6 +273,7 @@ impl<'gc> Trait<'gc> {
             AbcTraitKind::Function { slot_id, function } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Function {
                     slot_id: *slot_id,
                     function: unit.load_method(function.0, mc)?,
@@ -253,6 +286,7 @@ impl<'gc> Trait<'gc> {
             } => Trait {
                 name,
                 attributes: trait_attribs_from_abc_traits(abc_trait),
+                metadata: Vec::new(),
                 kind: TraitKind::Const {
                     slot_id: *slot_id,
                     type_name: if type_name.0 == 0 {
@@ -267,7 +301,33 @@ impl<'gc> Trait<'gc> {
                     },
                 },
             },
-        })
+        };
+
+        let abc = unit.abc();
+        for index in &abc_trait.metadata {
+            let abc_metadata = abc
+                .metadata
+                .get(index.0 as usize)
+                .ok_or_else(|| format!("Unknown metadata {}", index.0))?;
+            let mut items = Vec::with_capacity(abc_metadata.items.len());
+            for item in &abc_metadata.items {
+                items.push((
+                    unit.pool_string(item.key.0, mc)?,
+                    unit.pool_string(item.value.0, mc)?,
+                ));
+            }
+
+            loaded.metadata.push(Metadata {
+                name: unit.pool_string(abc_metadata.name.0, mc)?,
+                items,
+            });
+        }
+
+        if let TraitKind::Class { class, .. } = &loaded.kind {
+            class.write(mc).set_metadata(loaded.metadata.clone());
+        }
+
+        Ok(loaded)
     }
 
     pub fn name(&self) -> &QName<'gc> {
@@ -278,6 +338,29 @@ impl<'gc> Trait<'gc> {
         &self.kind
     }
 
+    /// The dispatch id of a method, getter or setter trait, if it was given
+    /// one.
+    ///
+    /// Compilers give overriding methods the same dispatch id as the method
+    /// they override, so that `callmethod` can call them by index.
+    pub fn disp_id(&self) -> Option<u32> {
+        match self.kind {
+            TraitKind::Method { disp_id, .. }
+            | TraitKind::Getter { disp_id, .. }
+            | TraitKind::Setter { disp_id, .. }
+                if disp_id > 0 =>
+            {
+                Some(disp_id)
+            }
+            _ => None,
+        }
+    }
+
+    /// The metadata the compiler attached to this trait.
+    pub fn metadata(&self) -> &[Metadata<'gc>] {
+        &self.metadata
+    }
+
     pub fn is_final(&self) -> bool {
         self.attributes.0.contains(TraitAttributes::FINAL)
     }
@@ -286,6 +369,14 @@ impl<'gc> Trait<'gc> {
         self.attributes.0.contains(TraitAttributes::OVERRIDE)
     }
 
+    /// Rename this trait.
+    ///
+    /// This is used to install a class's implementation of an interface method
+    /// under the name the interface gave it.
+    pub fn set_name(&mut self, name: QName<'gc>) {
+        self.name = name;
+    }
+
     pub fn set_attributes(&mut self, attribs: TraitAttributes) {
         self.attributes.0 = attribs;
     }
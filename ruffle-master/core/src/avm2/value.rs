@@ -7,8 +7,9 @@ use crate::avm2::object::{NamespaceObject, Object, PrimitiveObject, TObject};
 use crate::avm2::script::TranslationUnit;
 use crate::avm2::string::AvmString;
 use crate::avm2::{Avm2, Error};
-use crate::ecma_conversions::{f64_to_wrapping_i32, f64_to_wrapping_u32};
+use crate::ecma_conversions::{f64_to_ecma_string, f64_to_wrapping_i32, f64_to_wrapping_u32};
 use gc_arena::{Collect, MutationContext};
+use std::borrow::Cow;
 use std::cell::Ref;
 use swf::avm2::types::{DefaultValue as AbcDefaultValue, Index};
 
@@ -444,35 +445,13 @@ impl<'gc> Value<'gc> {
         Ok(f64_to_wrapping_i32(self.coerce_to_number(activation)?))
     }
 
-    /// Minimum number of digits after which numbers are formatted as
-    /// exponential strings.
-    const MIN_DIGITS: f64 = -6.0;
-
-    /// Maximum number of digits before numbers are formatted as exponential
-    /// strings.
-    const MAX_DIGITS: f64 = 21.0;
-
-    /// Maximum number of significant digits renderable within coerced numbers.
-    ///
-    /// Any precision beyond this point will be discarded and replaced with
-    /// zeroes (for whole parts) or not rendered (for decimal parts).
-    const MAX_PRECISION: f64 = 15.0;
-
     /// Coerce the value to a String.
     ///
     /// This function returns the resulting String directly; or a TypeError if
     /// the value is an `Object` that cannot be converted to a primitive value.
     ///
-    /// String conversions generally occur according to ECMA-262 3rd Edition's
-    /// ToString algorithm. The conversion of numbers to strings appears to be
-    /// somewhat underspecified; there are several formatting modes which
-    /// change at specific digit count cutoffs, but the spec allows
-    /// implementations to limit how much precision is displayed on coerced
-    /// numbers, even if that precision would result in rounding the whole part
-    /// of the number. (This is confusingly expressed in ECMA-262.)
-    ///
-    /// TODO: The cutoffs change based on SWF/ABC version. Targeting FP10.3 in
-    /// Animate CC 2020 significantly reduces them (towards zero).
+    /// String conversions occur according to ECMA-262 3rd Edition's ToString
+    /// algorithm, which is also how `trace` prints values.
     pub fn coerce_to_string<'a>(
         &'a self,
         activation: &mut Activation<'_, 'gc, '_>,
@@ -482,35 +461,10 @@ impl<'gc> Value<'gc> {
             Value::Null => "null".into(),
             Value::Bool(true) => "true".into(),
             Value::Bool(false) => "false".into(),
-            Value::Number(n) if n.is_nan() => "NaN".into(),
-            Value::Number(n) if *n == 0.0 => "0".into(),
-            Value::Number(n) if *n < 0.0 => AvmString::new(
-                activation.context.gc_context,
-                format!("-{}", Value::Number(-n).coerce_to_string(activation)?),
-            ),
-            Value::Number(n) if n.is_infinite() => "Infinity".into(),
-            Value::Number(n) => {
-                let digits = n.log10().floor();
-
-                // TODO: This needs to limit precision in the resulting decimal
-                // output, not in binary.
-                let precision = (n * 10.0_f64.powf(Self::MAX_PRECISION - digits)).floor()
-                    / 10.0_f64.powf(Self::MAX_PRECISION - digits);
-
-                if digits < Self::MIN_DIGITS || digits >= Self::MAX_DIGITS {
-                    AvmString::new(
-                        activation.context.gc_context,
-                        format!(
-                            "{}e{}{}",
-                            precision / 10.0_f64.powf(digits),
-                            if digits < 0.0 { "-" } else { "+" },
-                            digits.abs()
-                        ),
-                    )
-                } else {
-                    AvmString::new(activation.context.gc_context, format!("{}", n))
-                }
-            }
+            Value::Number(n) => match f64_to_ecma_string(*n) {
+                Cow::Borrowed(s) => s.into(),
+                Cow::Owned(s) => AvmString::new(activation.context.gc_context, s),
+            },
             Value::Unsigned(u) => AvmString::new(activation.context.gc_context, format!("{}", u)),
             Value::Integer(i) => AvmString::new(activation.context.gc_context, format!("{}", i)),
             Value::String(s) => *s,
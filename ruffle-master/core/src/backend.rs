@@ -1,8 +1,13 @@
 pub mod audio;
+pub mod clipboard;
+pub mod gamepad;
+pub mod local_connection;
 pub mod locale;
 pub mod log;
 pub mod navigator;
+pub mod print;
 pub mod render;
+pub mod socket;
 pub mod storage;
 pub mod ui;
 pub mod video;
@@ -1,5 +1,6 @@
 use crate::{
     avm1::SoundObject,
+    avm2::SoundChannelObject,
     display_object::{
         self, DisplayObject, MovieClip, SoundTransform as DisplayObjectSoundTransform,
         TDisplayObject,
@@ -8,6 +9,7 @@ use crate::{
 use downcast_rs::Downcast;
 use gc_arena::Collect;
 use generational_arena::{Arena, Index};
+use std::sync::{Arc, Mutex};
 
 pub mod decoders;
 pub mod swf {
@@ -21,6 +23,13 @@ pub type SoundHandle = Index;
 pub type SoundInstanceHandle = Index;
 pub type PreloadStreamHandle = u32;
 
+/// A callback that an audio backend invokes with the activity level of
+/// captured audio, from `0.0` (silence) to `100.0` (loudest).
+///
+/// This may be called from a different thread than the one that started the
+/// capture.
+pub type CaptureActivityCallback = Box<dyn FnMut(f64) + Send>;
+
 type Error = Box<dyn std::error::Error>;
 
 pub trait AudioBackend: Downcast {
@@ -28,6 +37,10 @@ pub trait AudioBackend: Downcast {
     fn pause(&mut self);
     fn register_sound(&mut self, swf_sound: &swf::Sound) -> Result<SoundHandle, Error>;
 
+    /// Discard a sound that will no longer be played, freeing its data.
+    /// Instances of the sound that are still playing are unaffected.
+    fn unregister_sound(&mut self, sound: SoundHandle);
+
     /// Used by the web backend to pre-decode sound streams.
     /// Returns the sound handle to be used to add data to the stream.
     /// Other backends return `None`.
@@ -92,6 +105,59 @@ pub trait AudioBackend: Downcast {
     /// Set the volume transform for a sound instance.
     fn set_sound_transform(&mut self, instance: SoundInstanceHandle, transform: SoundTransform);
 
+    /// Starts playing a dynamic sound, whose sample data is generated at
+    /// runtime and submitted to the backend via `queue_samples`.
+    ///
+    /// This is used to implement the `sampleData` event of AVM2 `Sound`s.
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, Error> {
+        Err("Dynamic sounds are not supported by this audio backend".into())
+    }
+
+    /// Appends sample frames to the end of a dynamic sound.
+    /// Samples are stereo, with a sample rate of 44.1KHz.
+    fn queue_samples(&mut self, _instance: SoundInstanceHandle, _samples: &[[f32; 2]]) {}
+
+    /// Marks that no more samples will be queued for a dynamic sound.
+    /// The sound will stop once all of its queued samples have played.
+    fn finish_dynamic_sound(&mut self, _instance: SoundInstanceHandle) {}
+
+    /// Get the number of queued sample frames of a dynamic sound that have
+    /// not yet been played.
+    /// Returns `None` if the instance is not a playing dynamic sound.
+    fn queued_sample_count(&self, _instance: SoundInstanceHandle) -> Option<usize> {
+        None
+    }
+
+    /// Get the names of the available audio capture devices.
+    fn capture_device_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Starts capturing audio from the default capture device, such as a
+    /// microphone.
+    ///
+    /// `on_activity` is called with the activity level of each block of
+    /// captured audio until `stop_capture` is called.
+    fn start_capture(&mut self, _on_activity: CaptureActivityCallback) -> Result<(), Error> {
+        Err("Audio capture is not supported by this audio backend".into())
+    }
+
+    /// Stops capturing audio.
+    fn stop_capture(&mut self) {}
+
+    /// Decodes part of a sound into stereo, 44.1KHz sample frames.
+    /// This is used to implement `Sound.extract`.
+    /// Returns `None` if the sound is not registered, or if the backend does
+    /// not support extracting sound data.
+    fn extract_samples(
+        &mut self,
+        _sound: SoundHandle,
+        _start_sample_frame: u32,
+        _num_sample_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        None
+    }
+
     // TODO: Eventually remove this/move it to library.
     fn is_loading_complete(&self) -> bool {
         true
@@ -128,6 +194,10 @@ impl AudioBackend for NullAudioBackend {
         Ok(self.sounds.insert(()))
     }
 
+    fn unregister_sound(&mut self, sound: SoundHandle) {
+        self.sounds.remove(sound);
+    }
+
     fn start_sound(
         &mut self,
         _sound: SoundHandle,
@@ -176,17 +246,27 @@ pub struct AudioManager<'gc> {
 
     /// Whether a sound transform has been changed.
     transforms_dirty: bool,
+
+    /// The most recent activity level of captured audio, if audio is being
+    /// captured.
+    #[collect(require_static)]
+    capture_activity: Option<Arc<Mutex<f64>>>,
 }
 
 impl<'gc> AudioManager<'gc> {
     /// The maximum number of sound instances that can play at once.
     pub const MAX_SOUNDS: usize = 32;
 
+    /// The number of queued sample frames below which a dynamic sound will
+    /// request more sample data.
+    pub const MIN_QUEUED_SAMPLES: usize = 4096;
+
     pub fn new() -> Self {
         Self {
             sounds: Vec::with_capacity(Self::MAX_SOUNDS),
             global_sound_transform: Default::default(),
             transforms_dirty: false,
+            capture_activity: None,
         }
     }
 
@@ -197,7 +277,9 @@ impl<'gc> AudioManager<'gc> {
         gc_context: gc_arena::MutationContext<'gc, '_>,
         action_queue: &mut crate::context::ActionQueue<'gc>,
         root: DisplayObject<'gc>,
-    ) {
+    ) -> Vec<SoundChannelObject<'gc>> {
+        let mut completed_channels = vec![];
+
         // Update the position of sounds, and remove any completed sounds.
         self.sounds.retain(|sound| {
             if let Some(pos) = audio.get_sound_position(sound.instance) {
@@ -205,6 +287,9 @@ impl<'gc> AudioManager<'gc> {
                 if let Some(avm1_object) = sound.avm1_object {
                     avm1_object.set_position(gc_context, pos);
                 }
+                if let Some(avm2_object) = sound.avm2_object {
+                    avm2_object.set_position(gc_context, f64::from(pos));
+                }
                 true
             } else {
                 // Sound ended; fire end event.
@@ -219,12 +304,18 @@ impl<'gc> AudioManager<'gc> {
                         false,
                     );
                 }
+                if let Some(object) = sound.avm2_object {
+                    object.set_instance(gc_context, None);
+                    completed_channels.push(object);
+                }
                 false
             }
         });
 
         // Update sound transforms, if dirty.
         self.update_sound_transforms(audio);
+
+        completed_channels
     }
 
     pub fn start_sound(
@@ -242,6 +333,33 @@ impl<'gc> AudioManager<'gc> {
                 instance: handle,
                 display_object,
                 avm1_object,
+                avm2_object: None,
+                accepts_samples: false,
+            };
+            audio.set_sound_transform(handle, self.transform_for_sound(&instance));
+            self.sounds.push(instance);
+            Some(handle)
+        } else {
+            None
+        }
+    }
+
+    /// Starts a dynamic sound that requests its sample data from an AVM2
+    /// `Sound` via `sampleData` events.
+    pub fn start_dynamic_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        avm2_object: SoundChannelObject<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        if self.sounds.len() < Self::MAX_SOUNDS {
+            let handle = audio.start_dynamic_sound().ok()?;
+            let instance = SoundInstance {
+                sound: None,
+                instance: handle,
+                display_object: None,
+                avm1_object: None,
+                avm2_object: Some(avm2_object),
+                accepts_samples: true,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -251,6 +369,62 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Returns all dynamic sounds that are running low on sample data.
+    pub fn sounds_needing_samples(
+        &self,
+        audio: &dyn AudioBackend,
+    ) -> Vec<(SoundInstanceHandle, SoundChannelObject<'gc>)> {
+        self.sounds
+            .iter()
+            .filter(|sound| sound.accepts_samples)
+            .filter(|sound| {
+                audio
+                    .queued_sample_count(sound.instance)
+                    .map(|count| count < Self::MIN_QUEUED_SAMPLES)
+                    .unwrap_or(false)
+            })
+            .filter_map(|sound| Some((sound.instance, sound.avm2_object?)))
+            .collect()
+    }
+
+    /// Stops requesting sample data for a dynamic sound.
+    /// The sound will complete once its queued samples have played.
+    pub fn finish_dynamic_sound(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        instance: SoundInstanceHandle,
+    ) {
+        if let Some(sound) = self
+            .sounds
+            .iter_mut()
+            .find(|other| other.instance == instance)
+        {
+            sound.accepts_samples = false;
+            audio.finish_dynamic_sound(instance);
+        }
+    }
+
+    /// Associates an AVM2 `SoundChannel` with a playing sound instance.
+    ///
+    /// The channel will have its position updated as the sound plays, and
+    /// its sound transform will be applied to the sound.
+    pub fn attach_avm2_sound_channel(
+        &mut self,
+        audio: &mut dyn AudioBackend,
+        instance: SoundInstanceHandle,
+        avm2_object: SoundChannelObject<'gc>,
+    ) {
+        if let Some(i) = self
+            .sounds
+            .iter()
+            .position(|other| other.instance == instance)
+        {
+            self.sounds[i].avm2_object = Some(avm2_object);
+            let transform = self.transform_for_sound(&self.sounds[i]);
+            audio.set_sound_transform(instance, transform);
+        }
+    }
+
     pub fn stop_sound(&mut self, audio: &mut dyn AudioBackend, instance: SoundInstanceHandle) {
         if let Some(i) = self
             .sounds
@@ -317,6 +491,8 @@ impl<'gc> AudioManager<'gc> {
                 instance: handle,
                 display_object: Some(movie_clip.into()),
                 avm1_object: None,
+                avm2_object: None,
+                accepts_samples: false,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -326,6 +502,34 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Starts capturing audio, if audio is not already being captured.
+    pub fn start_capture(&mut self, audio: &mut dyn AudioBackend) -> Result<(), Error> {
+        if self.capture_activity.is_none() {
+            let activity = Arc::new(Mutex::new(0.0));
+            let callback_activity = Arc::clone(&activity);
+            audio.start_capture(Box::new(move |level| {
+                *callback_activity.lock().unwrap() = level;
+            }))?;
+            self.capture_activity = Some(activity);
+        }
+
+        Ok(())
+    }
+
+    pub fn stop_capture(&mut self, audio: &mut dyn AudioBackend) {
+        if self.capture_activity.take().is_some() {
+            audio.stop_capture();
+        }
+    }
+
+    /// The activity level of captured audio, from `0.0` to `100.0`.
+    /// Returns `None` if audio is not being captured.
+    pub fn capture_activity_level(&self) -> Option<f64> {
+        self.capture_activity
+            .as_ref()
+            .map(|activity| *activity.lock().unwrap())
+    }
+
     pub fn global_sound_transform(&self) -> &DisplayObjectSoundTransform {
         &self.global_sound_transform
     }
@@ -340,7 +544,10 @@ impl<'gc> AudioManager<'gc> {
     }
 
     fn transform_for_sound(&self, sound: &SoundInstance<'gc>) -> SoundTransform {
-        let mut transform = DisplayObjectSoundTransform::default();
+        let mut transform = sound
+            .avm2_object
+            .map(|channel| channel.sound_transform())
+            .unwrap_or_default();
         let mut parent = sound.display_object;
         while let Some(display_object) = parent {
             transform.concat(&display_object.sound_transform());
@@ -390,6 +597,12 @@ pub struct SoundInstance<'gc> {
 
     /// The AVM1 `Sound` object associated with this sound, if any.
     pub avm1_object: Option<SoundObject<'gc>>,
+
+    /// The AVM2 `SoundChannel` object associated with this sound, if any.
+    pub avm2_object: Option<SoundChannelObject<'gc>>,
+
+    /// Whether this is a dynamic sound that is still requesting sample data.
+    accepts_samples: bool,
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
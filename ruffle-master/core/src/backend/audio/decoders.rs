@@ -8,7 +8,7 @@ mod pcm;
 
 pub use adpcm::AdpcmDecoder;
 #[cfg(any(feature = "puremp3", feature = "minimp3"))]
-pub use mp3::Mp3Decoder;
+pub use mp3::{Mp3Decoder, Mp3FrameIndex, Mp3SeekPoint};
 pub use nellymoser::NellymoserDecoder;
 pub use pcm::PcmDecoder;
 
@@ -91,17 +91,27 @@ pub trait StreamDecoder: Decoder {}
 struct StandardStreamDecoder {
     /// The underlying decoder. The decoder will get its data from a `StreamTagReader`.
     decoder: Box<dyn Decoder + Send>,
+
+    /// The number of sample frames left to skip before the stream is heard.
+    skip_sample_frames: u32,
 }
 
 impl StandardStreamDecoder {
     /// Constructs a new `StandardStreamDecoder.
     /// `swf_data` should be the tag data of the MovieClip that contains the stream.
-    fn new(format: &SoundFormat, swf_data: SwfSlice) -> Result<Self, Error> {
+    fn new(
+        format: &SoundFormat,
+        swf_data: SwfSlice,
+        skip_sample_frames: u32,
+    ) -> Result<Self, Error> {
         // Create a tag reader to get the audio data from SoundStreamBlock tags.
         let tag_reader = StreamTagReader::new(format.compression, swf_data);
         // Wrap the tag reader in the decoder.
         let decoder = make_decoder(format, tag_reader)?;
-        Ok(Self { decoder })
+        Ok(Self {
+            decoder,
+            skip_sample_frames,
+        })
     }
 }
 
@@ -119,6 +129,10 @@ impl Iterator for StandardStreamDecoder {
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
+        while self.skip_sample_frames > 0 {
+            self.skip_sample_frames -= 1;
+            self.decoder.next()?;
+        }
         self.decoder.next()
     }
 }
@@ -186,20 +200,59 @@ impl Iterator for AdpcmStreamDecoder {
     }
 }
 
-/// Makes a `StreamDecoder` for the given stream. `swf_data` should be the MovieClip's tag data.
+/// Makes a `StreamDecoder` for the given stream. `swf_data` should be the MovieClip's tag data,
+/// starting at `clip_frame`, the frame that the stream starts playing from.
 /// Generally this will return a `StandardStreamDecoder`, except for ADPCM streams.
 pub fn make_stream_decoder(
-    format: &swf::SoundFormat,
+    stream_info: &swf::SoundStreamHead,
+    clip_frame: u16,
     swf_data: SwfSlice,
 ) -> Result<Box<dyn Decoder + Send>, Error> {
-    let decoder: Box<dyn Decoder + Send> = if format.compression == AudioCompression::Adpcm {
-        Box::new(AdpcmStreamDecoder::new(format, swf_data))
-    } else {
-        Box::new(StandardStreamDecoder::new(format, swf_data)?)
+    let format = &stream_info.stream_format;
+    let decoder: Box<dyn Decoder + Send> = match format.compression {
+        AudioCompression::Adpcm => Box::new(AdpcmStreamDecoder::new(format, swf_data)),
+        AudioCompression::Mp3 => {
+            let skip_sample_frames =
+                mp3_stream_skip_sample_frames(stream_info, clip_frame, &swf_data);
+            Box::new(StandardStreamDecoder::new(
+                format,
+                swf_data,
+                skip_sample_frames,
+            )?)
+        }
+        _ => Box::new(StandardStreamDecoder::new(format, swf_data, 0)?),
     };
     Ok(decoder)
 }
 
+/// The number of sample frames to skip at the start of an MP3 stream.
+///
+/// A stream played from its first frame skips the latency of the MP3 encoder, given by the
+/// `SoundStreamHead` tag. A stream played from a later frame instead skips the seek samples of
+/// the `SoundStreamBlock` it starts at, which belong to the previous frame.
+/// SWF19 p.184, p.188
+fn mp3_stream_skip_sample_frames(
+    stream_info: &swf::SoundStreamHead,
+    clip_frame: u16,
+    swf_data: &SwfSlice,
+) -> u32 {
+    let skip = if clip_frame <= 1 {
+        stream_info.latency_seek
+    } else {
+        // Read the first block without stripping its MP3 header.
+        StreamTagReader::new(AudioCompression::Uncompressed, swf_data.clone())
+            .next()
+            .and_then(|block| {
+                block
+                    .as_ref()
+                    .get(2..4)
+                    .map(|seek_samples| i16::from_le_bytes([seek_samples[0], seek_samples[1]]))
+            })
+            .unwrap_or(0)
+    };
+    skip.max(0) as u32
+}
+
 /// Adds seeking ability to decoders where the underline stream is `std::io::Seek`.
 pub trait SeekableDecoder: Decoder {
     /// Resets the decoder to the beginning of the stream.
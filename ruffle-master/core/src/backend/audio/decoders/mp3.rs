@@ -1,6 +1,212 @@
 use super::{Decoder, SeekableDecoder};
 use std::io::{Cursor, Read};
 
+/// The largest number of frames to search backwards for a seek point.
+const MAX_SEEK_PREROLL_FRAMES: usize = 32;
+
+/// A frame in an MP3 bitstream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Mp3FrameInfo {
+    /// The byte offset of the frame header.
+    byte_offset: u64,
+
+    /// The index of the first sample frame decoded from this frame.
+    sample_offset: u32,
+
+    /// How many bytes of main data this frame borrows from earlier frames via
+    /// the bit reservoir.
+    main_data_begin: u16,
+}
+
+/// Where to restart decoding in order to seek to a sample frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mp3SeekPoint {
+    /// The byte offset to restart decoding from.
+    pub byte_offset: u64,
+
+    /// The number of decoded sample frames to discard after restarting.
+    pub skip_sample_frames: u32,
+}
+
+/// An index of the frames in an MP3 bitstream, built by scanning the frame
+/// headers without decoding any audio.
+///
+/// The header `sample_count` in `DefineSound` is unreliable for VBR files,
+/// so the index is used to find the true length of a sound and to seek
+/// to an exact sample.
+#[derive(Debug, Clone, Default)]
+pub struct Mp3FrameIndex {
+    frames: Vec<Mp3FrameInfo>,
+    num_sample_frames: u32,
+}
+
+impl Mp3FrameIndex {
+    /// Builds the frame index for the given MP3 data.
+    pub fn new(data: &[u8]) -> Self {
+        let mut frames = vec![];
+        let mut sample_offset = 0u32;
+        let mut pos = id3v2_tag_len(data);
+        while pos + 4 <= data.len() {
+            match Mp3FrameHeader::parse(&data[pos..]) {
+                Some(header) if pos + header.frame_len <= data.len() => {
+                    frames.push(Mp3FrameInfo {
+                        byte_offset: pos as u64,
+                        sample_offset,
+                        main_data_begin: header.main_data_begin,
+                    });
+                    sample_offset = sample_offset.saturating_add(header.samples_per_frame);
+                    pos += header.frame_len;
+                }
+                Some(_) => break,
+                // Not a frame header; resync on the next byte.
+                None => pos += 1,
+            }
+        }
+
+        Self {
+            frames,
+            num_sample_frames: sample_offset,
+        }
+    }
+
+    /// The number of frames in the stream.
+    pub fn num_frames(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// The total number of sample frames that decoding the stream produces.
+    pub fn num_sample_frames(&self) -> u32 {
+        self.num_sample_frames
+    }
+
+    /// Returns the number of sample frames in a sound, excluding the encoder
+    /// delay.
+    ///
+    /// The count declared in the SWF is exact for well-formed files, so it is
+    /// preferred unless it disagrees with the frame index by more than a
+    /// frame's worth of samples, as happens with some VBR encoders.
+    pub fn sound_sample_frames(&self, declared_sample_frames: u32, skip_sample_frames: u16) -> u32 {
+        if self.frames.is_empty() {
+            return declared_sample_frames;
+        }
+
+        let indexed = self
+            .num_sample_frames
+            .saturating_sub(skip_sample_frames.into());
+        let max_frame_samples = self
+            .frames
+            .windows(2)
+            .map(|w| w[1].sample_offset - w[0].sample_offset)
+            .max()
+            .unwrap_or(self.num_sample_frames);
+        if declared_sample_frames <= indexed && indexed - declared_sample_frames < max_frame_samples
+        {
+            declared_sample_frames
+        } else {
+            indexed
+        }
+    }
+
+    /// Finds where to restart decoding to land on `sample_frame`.
+    ///
+    /// Decoding must begin on a frame that doesn't borrow from the bit
+    /// reservoir, and at least one frame early so that the decoder's
+    /// overlapping transform is primed. Returns `None` if there is no such
+    /// frame close enough to the target.
+    pub fn seek_point(&self, sample_frame: u32) -> Option<Mp3SeekPoint> {
+        let target = self
+            .frames
+            .partition_point(|frame| frame.sample_offset <= sample_frame)
+            .checked_sub(1)?;
+        let earliest = target.saturating_sub(MAX_SEEK_PREROLL_FRAMES);
+        let start = (earliest..target)
+            .rev()
+            .find(|&i| self.frames[i].main_data_begin == 0)?;
+        let start = &self.frames[start];
+        Some(Mp3SeekPoint {
+            byte_offset: start.byte_offset,
+            skip_sample_frames: sample_frame - start.sample_offset,
+        })
+    }
+}
+
+/// The fields of an MPEG audio Layer III frame header that are needed to index it.
+struct Mp3FrameHeader {
+    frame_len: usize,
+    samples_per_frame: u32,
+    main_data_begin: u16,
+}
+
+impl Mp3FrameHeader {
+    fn parse(data: &[u8]) -> Option<Self> {
+        const MPEG1_BITRATES: [u32; 15] = [
+            0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+        ];
+        const MPEG2_BITRATES: [u32; 15] =
+            [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+        const SAMPLE_RATES: [u32; 3] = [44100, 48000, 32000];
+
+        if data.len() < 4 || data[0] != 0xff || data[1] & 0xe0 != 0xe0 {
+            return None;
+        }
+
+        // 0 = MPEG 2.5, 1 = reserved, 2 = MPEG 2, 3 = MPEG 1
+        let version = (data[1] >> 3) & 0b11;
+        // Only Layer III is valid in SWF files.
+        let layer = (data[1] >> 1) & 0b11;
+        let has_crc = data[1] & 0b1 == 0;
+        let bitrate_index = usize::from(data[2] >> 4);
+        let sample_rate_index = usize::from((data[2] >> 2) & 0b11);
+        let padding = u32::from((data[2] >> 1) & 0b1);
+        if version == 1 || layer != 1 || bitrate_index == 0 || bitrate_index == 15 {
+            return None;
+        }
+        let is_mpeg1 = version == 3;
+
+        let bitrate = 1000
+            * if is_mpeg1 {
+                MPEG1_BITRATES[bitrate_index]
+            } else {
+                MPEG2_BITRATES[bitrate_index]
+            };
+        let sample_rate = *SAMPLE_RATES.get(sample_rate_index)?
+            >> match version {
+                3 => 0,
+                2 => 1,
+                _ => 2,
+            };
+        let (samples_per_frame, slot_factor) = if is_mpeg1 { (1152, 144) } else { (576, 72) };
+        let frame_len = (slot_factor * bitrate / sample_rate + padding) as usize;
+
+        let side_info = data.get(if has_crc { 6 } else { 4 }..)?;
+        let main_data_begin = if is_mpeg1 {
+            (u16::from(*side_info.first()?) << 1) | u16::from(*side_info.get(1)? >> 7)
+        } else {
+            u16::from(*side_info.first()?)
+        };
+
+        Some(Self {
+            frame_len,
+            samples_per_frame,
+            main_data_begin,
+        })
+    }
+}
+
+/// Returns the length of the ID3v2 tag at the start of `data`, if any.
+fn id3v2_tag_len(data: &[u8]) -> usize {
+    if data.len() >= 10 && &data[0..3] == b"ID3" {
+        // The tag size is a 28-bit "synchsafe" integer.
+        let size = data[6..10]
+            .iter()
+            .fold(0usize, |size, &b| (size << 7) | usize::from(b & 0x7f));
+        let footer_len = if data[5] & 0x10 != 0 { 10 } else { 0 };
+        10 + size + footer_len
+    } else {
+        0
+    }
+}
+
 #[cfg(feature = "minimp3")]
 pub struct Mp3Decoder<R: Read> {
     decoder: minimp3::Decoder<R>,
@@ -9,6 +215,7 @@ pub struct Mp3Decoder<R: Read> {
     cur_frame: minimp3::Frame,
     cur_sample: usize,
     num_samples: usize,
+    frame_index: Option<Mp3FrameIndex>,
 }
 
 #[cfg(feature = "minimp3")]
@@ -27,6 +234,7 @@ impl<R: Read> Mp3Decoder<R> {
             },
             cur_sample: 0,
             num_samples: 0,
+            frame_index: None,
         }
     }
 
@@ -69,15 +277,33 @@ impl<R: Read> Iterator for Mp3Decoder<R> {
 }
 
 #[cfg(feature = "minimp3")]
-impl<R: AsRef<[u8]> + Default> SeekableDecoder for Mp3Decoder<Cursor<R>> {
-    #[inline]
-    fn reset(&mut self) {
+impl<R: AsRef<[u8]> + Default> Mp3Decoder<Cursor<R>> {
+    fn data(&mut self) -> &[u8] {
+        self.decoder.reader_mut().get_ref().as_ref()
+    }
+
+    /// Recreates the decoder so that it begins decoding at the given byte offset.
+    fn restart_at(&mut self, byte_offset: u64) {
         // TODO: This is funky.
         // I want to reset the `BitStream` and `Cursor` to their initial positions,
         // but have to work around the borrowing rules of Rust.
         let mut cursor = std::mem::take(self.decoder.reader_mut());
-        cursor.set_position(0);
+        cursor.set_position(byte_offset);
+        let frame_index = self.frame_index.take();
         *self = Mp3Decoder::new(self.num_channels, self.sample_rate, cursor);
+        self.frame_index = frame_index;
+    }
+}
+
+#[cfg(feature = "minimp3")]
+impl<R: AsRef<[u8]> + Default> SeekableDecoder for Mp3Decoder<Cursor<R>> {
+    #[inline]
+    fn reset(&mut self) {
+        self.restart_at(0);
+    }
+
+    fn seek_to_sample_frame(&mut self, frame: u32) {
+        self.seek_with_frame_index(frame);
     }
 }
 
@@ -89,6 +315,7 @@ pub struct Mp3Decoder<R: Read> {
     cur_frame: puremp3::Frame,
     cur_sample: usize,
     cur_channel: usize,
+    frame_index: Option<Mp3FrameIndex>,
 }
 
 #[cfg(all(feature = "puremp3", not(feature = "minimp3")))]
@@ -101,6 +328,7 @@ impl<R: Read> Mp3Decoder<R> {
             cur_frame: unsafe { std::mem::MaybeUninit::zeroed().assume_init() },
             cur_sample: 0,
             cur_channel: 0,
+            frame_index: None,
         }
     }
 
@@ -115,6 +343,39 @@ impl<R: Read> Mp3Decoder<R> {
     }
 }
 
+impl<R: AsRef<[u8]> + Default> Mp3Decoder<Cursor<R>>
+where
+    Self: SeekableDecoder,
+{
+    /// Seeks using the frame index, building it on first use.
+    ///
+    /// Decoding restarts at a nearby frame that does not depend on the bit
+    /// reservoir, and the samples before `frame` are discarded.
+    fn seek_with_frame_index(&mut self, frame: u32) {
+        if self.frame_index.is_none() {
+            let frame_index = Mp3FrameIndex::new(self.data());
+            self.frame_index = Some(frame_index);
+        }
+
+        let seek_point = self
+            .frame_index
+            .as_ref()
+            .and_then(|index| index.seek_point(frame));
+        let skip_sample_frames = if let Some(seek_point) = seek_point {
+            self.restart_at(seek_point.byte_offset);
+            seek_point.skip_sample_frames
+        } else {
+            // No suitable seek point; decode from the start of the stream.
+            self.reset();
+            frame
+        };
+
+        for _ in 0..skip_sample_frames {
+            self.next();
+        }
+    }
+}
+
 impl<R: Read> Decoder for Mp3Decoder<R> {
     #[inline]
     fn num_channels(&self) -> u8 {
@@ -158,14 +419,95 @@ impl<R: Read> Iterator for Mp3Decoder<R> {
 }
 
 #[cfg(all(feature = "puremp3", not(feature = "minimp3")))]
-impl<R: AsRef<[u8]> + Default> SeekableDecoder for Mp3Decoder<Cursor<R>> {
-    #[inline]
-    fn reset(&mut self) {
+impl<R: AsRef<[u8]> + Default> Mp3Decoder<Cursor<R>> {
+    fn data(&mut self) -> &[u8] {
+        self.decoder.get_mut().get_ref().as_ref()
+    }
+
+    /// Recreates the decoder so that it begins decoding at the given byte offset.
+    fn restart_at(&mut self, byte_offset: u64) {
         // TODO: This is funky.
         // I want to reset the `BitStream` and `Cursor` to their initial positions,
         // but have to work around the borrowing rules of Rust.
         let mut cursor = std::mem::replace(self.decoder.get_mut(), Default::default());
-        cursor.set_position(0);
+        cursor.set_position(byte_offset);
+        let frame_index = self.frame_index.take();
         *self = Mp3Decoder::new(self.num_channels, self.sample_rate, cursor);
+        self.frame_index = frame_index;
+    }
+}
+
+#[cfg(all(feature = "puremp3", not(feature = "minimp3")))]
+impl<R: AsRef<[u8]> + Default> SeekableDecoder for Mp3Decoder<Cursor<R>> {
+    #[inline]
+    fn reset(&mut self) {
+        self.restart_at(0);
+    }
+
+    fn seek_to_sample_frame(&mut self, frame: u32) {
+        self.seek_with_frame_index(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// MPEG 1 Layer III, 128 kbps, 44.1 kHz, no CRC, no padding.
+    const HEADER: [u8; 4] = [0xff, 0xfb, 0x90, 0x00];
+    const FRAME_LEN: usize = 417;
+
+    /// Builds a stream of empty frames with the given `main_data_begin` values.
+    fn make_stream(main_data_begins: &[u16]) -> Vec<u8> {
+        let mut data = vec![];
+        for &main_data_begin in main_data_begins {
+            let mut frame = vec![0; FRAME_LEN];
+            frame[0..4].copy_from_slice(&HEADER);
+            frame[4] = (main_data_begin >> 1) as u8;
+            frame[5] = ((main_data_begin & 1) << 7) as u8;
+            data.extend_from_slice(&frame);
+        }
+        data
+    }
+
+    #[test]
+    fn frame_index_length() {
+        let data = make_stream(&[0; 10]);
+        let index = Mp3FrameIndex::new(&data);
+        assert_eq!(index.num_frames(), 10);
+        assert_eq!(index.num_sample_frames(), 11520);
+
+        // A declared count within a frame of the indexed length is trusted.
+        assert_eq!(index.sound_sample_frames(11000, 0), 11000);
+        // A wildly wrong count (as written by some VBR encoders) is not.
+        assert_eq!(index.sound_sample_frames(2000, 576), 11520 - 576);
+    }
+
+    #[test]
+    fn frame_index_resyncs_after_garbage() {
+        let mut data = vec![0x12, 0x34, 0xff, 0x00];
+        data.extend(make_stream(&[0; 3]));
+        let index = Mp3FrameIndex::new(&data);
+        assert_eq!(index.num_frames(), 3);
+        assert_eq!(index.seek_point(2000).unwrap().byte_offset, 4);
+    }
+
+    #[test]
+    fn seek_point_avoids_bit_reservoir() {
+        let data = make_stream(&[0, 100, 0, 200, 300, 0]);
+        let index = Mp3FrameIndex::new(&data);
+
+        // Frame 4 starts at sample 4608; frame 2 is the closest preceding frame
+        // that doesn't use the bit reservoir.
+        assert_eq!(
+            index.seek_point(5000),
+            Some(Mp3SeekPoint {
+                byte_offset: 2 * FRAME_LEN as u64,
+                skip_sample_frames: 5000 - 2304,
+            })
+        );
+
+        // There's no frame before the first one to prime the decoder with.
+        assert_eq!(index.seek_point(100), None);
     }
 }
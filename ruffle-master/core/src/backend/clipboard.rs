@@ -0,0 +1,35 @@
+use downcast_rs::Downcast;
+
+/// Access to the system clipboard, used by text fields for copy, cut and
+/// paste, and by `System.setClipboard`.
+pub trait ClipboardBackend: Downcast {
+    /// Get the text currently on the clipboard, if there is any.
+    fn contents(&mut self) -> Option<String>;
+
+    /// Put the given text on the clipboard.
+    fn set_contents(&mut self, content: String);
+}
+impl_downcast!(ClipboardBackend);
+
+/// A clipboard that is only shared within the player, for platforms that
+/// don't allow access to the system clipboard.
+#[derive(Default)]
+pub struct MemoryClipboardBackend {
+    content: Option<String>,
+}
+
+impl MemoryClipboardBackend {
+    pub fn new() -> Self {
+        Self { content: None }
+    }
+}
+
+impl ClipboardBackend for MemoryClipboardBackend {
+    fn contents(&mut self) -> Option<String> {
+        self.content.clone()
+    }
+
+    fn set_contents(&mut self, content: String) {
+        self.content = Some(content);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::config::GamepadKeyMapping;
+use crate::events::KeyCode;
+use downcast_rs::Downcast;
+
+/// The buttons of a gamepad with the standard layout, as in the W3C Gamepad
+/// specification.
+pub mod standard_button {
+    pub const FACE_BOTTOM: usize = 0;
+    pub const FACE_RIGHT: usize = 1;
+    pub const START: usize = 9;
+    pub const DPAD_UP: usize = 12;
+    pub const DPAD_DOWN: usize = 13;
+    pub const DPAD_LEFT: usize = 14;
+    pub const DPAD_RIGHT: usize = 15;
+}
+
+/// How far a stick has to be pushed before it counts as pressing a key.
+const STICK_THRESHOLD: f64 = 0.5;
+
+/// How far a button has to be pressed before it counts as pressing a key.
+const BUTTON_THRESHOLD: f64 = 0.5;
+
+/// The state of a connected gamepad.
+///
+/// Backends report gamepads with the standard layout where they can, so that
+/// the buttons and axes mean the same thing on every controller.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GamepadState {
+    /// An identifier for the gamepad, which stays the same for as long as it
+    /// is connected.
+    pub id: String,
+
+    /// The name of the gamepad, as reported by the system.
+    pub name: String,
+
+    /// The position of each axis, from -1.0 to 1.0.
+    pub axes: Vec<f64>,
+
+    /// How far each button is pressed, from 0.0 to 1.0.
+    pub buttons: Vec<f64>,
+}
+
+impl GamepadState {
+    fn axis(&self, index: usize) -> f64 {
+        self.axes.get(index).copied().unwrap_or(0.0)
+    }
+
+    fn is_button_down(&self, index: usize) -> bool {
+        self.buttons.get(index).copied().unwrap_or(0.0) >= BUTTON_THRESHOLD
+    }
+
+    /// The keys that this gamepad holds down under a key mapping.
+    ///
+    /// The d-pad and the left stick both steer, and the bottom and right face
+    /// buttons and start press space, shift and enter, which are the most
+    /// common action keys in keyboard-only games.
+    pub fn mapped_keys(&self, mapping: GamepadKeyMapping) -> Vec<KeyCode> {
+        let [up, down, left, right] = match mapping {
+            GamepadKeyMapping::None => return vec![],
+            GamepadKeyMapping::Arrows => {
+                [KeyCode::Up, KeyCode::Down, KeyCode::Left, KeyCode::Right]
+            }
+            GamepadKeyMapping::Wasd => [KeyCode::W, KeyCode::S, KeyCode::A, KeyCode::D],
+        };
+
+        let (x, y) = (self.axis(0), self.axis(1));
+        let bindings = [
+            (
+                up,
+                self.is_button_down(standard_button::DPAD_UP) || y <= -STICK_THRESHOLD,
+            ),
+            (
+                down,
+                self.is_button_down(standard_button::DPAD_DOWN) || y >= STICK_THRESHOLD,
+            ),
+            (
+                left,
+                self.is_button_down(standard_button::DPAD_LEFT) || x <= -STICK_THRESHOLD,
+            ),
+            (
+                right,
+                self.is_button_down(standard_button::DPAD_RIGHT) || x >= STICK_THRESHOLD,
+            ),
+            (
+                KeyCode::Space,
+                self.is_button_down(standard_button::FACE_BOTTOM),
+            ),
+            (
+                KeyCode::Shift,
+                self.is_button_down(standard_button::FACE_RIGHT),
+            ),
+            (KeyCode::Return, self.is_button_down(standard_button::START)),
+        ];
+
+        bindings
+            .iter()
+            .filter(|(_, down)| *down)
+            .map(|(key_code, _)| *key_code)
+            .collect()
+    }
+}
+
+/// Access to the gamepads connected to the computer, which movies see through
+/// `flash.ui.GameInput`.
+pub trait GamepadBackend: Downcast {
+    /// The gamepads that are currently connected.
+    ///
+    /// This is polled once per frame.
+    fn gamepads(&mut self) -> Vec<GamepadState>;
+}
+impl_downcast!(GamepadBackend);
+
+/// Gamepad backend for platforms without gamepad support.
+#[derive(Default)]
+pub struct NullGamepadBackend {}
+
+impl NullGamepadBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl GamepadBackend for NullGamepadBackend {
+    fn gamepads(&mut self) -> Vec<GamepadState> {
+        vec![]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gamepad(axes: Vec<f64>, buttons: &[usize]) -> GamepadState {
+        let mut state = GamepadState {
+            id: "0".to_string(),
+            name: "Test Gamepad".to_string(),
+            axes,
+            buttons: vec![0.0; 17],
+        };
+        for button in buttons {
+            state.buttons[*button] = 1.0;
+        }
+        state
+    }
+
+    #[test]
+    fn no_mapping() {
+        let state = gamepad(vec![1.0, 1.0], &[standard_button::DPAD_UP]);
+        assert_eq!(state.mapped_keys(GamepadKeyMapping::None), vec![]);
+    }
+
+    #[test]
+    fn dpad_and_buttons() {
+        let state = gamepad(
+            vec![],
+            &[standard_button::DPAD_LEFT, standard_button::FACE_BOTTOM],
+        );
+        assert_eq!(
+            state.mapped_keys(GamepadKeyMapping::Arrows),
+            vec![KeyCode::Left, KeyCode::Space]
+        );
+        assert_eq!(
+            state.mapped_keys(GamepadKeyMapping::Wasd),
+            vec![KeyCode::A, KeyCode::Space]
+        );
+    }
+
+    #[test]
+    fn stick_threshold() {
+        let state = gamepad(vec![0.3, -0.8], &[]);
+        assert_eq!(
+            state.mapped_keys(GamepadKeyMapping::Arrows),
+            vec![KeyCode::Up]
+        );
+
+        let state = gamepad(vec![0.5, 0.0], &[standard_button::START]);
+        assert_eq!(
+            state.mapped_keys(GamepadKeyMapping::Arrows),
+            vec![KeyCode::Right, KeyCode::Return]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use downcast_rs::Downcast;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+/// A call from one `LocalConnection` to a method of another.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LocalConnectionMessage {
+    /// The full name of the receiving connection, including its domain
+    /// prefix, if any.
+    pub connection: String,
+
+    /// The domain of the movie that sent the message.
+    pub sender_domain: String,
+
+    /// The method to call on the receiving connection's client.
+    pub method: String,
+
+    /// The arguments of the call, as a sequence of AMF0 values.
+    pub args: Vec<u8>,
+}
+
+/// Carries messages between `LocalConnection`s, which may be in other
+/// players, or other processes altogether.
+///
+/// Connection names are passed to the backend in full, so the backend doesn't
+/// need to know about domains.
+pub trait LocalConnectionBackend: Downcast {
+    /// Start receiving the messages sent to `name`.
+    ///
+    /// Returns `false` if something else is already receiving them.
+    fn connect(&mut self, name: &str) -> bool;
+
+    /// Stop receiving the messages sent to `name`.
+    fn close(&mut self, name: &str);
+
+    /// Send a message.
+    ///
+    /// Returns `false` if nothing is receiving messages for its connection.
+    fn send(&mut self, message: LocalConnectionMessage) -> bool;
+
+    /// Take the messages that were received since the last call.
+    fn poll_messages(&mut self) -> Vec<LocalConnectionMessage>;
+}
+impl_downcast!(LocalConnectionBackend);
+
+/// The messages waiting for each connection on a `LocalConnectionBus`.
+type Mailboxes = HashMap<String, Vec<LocalConnectionMessage>>;
+
+/// Connects the players in a single process, so that their
+/// `LocalConnection`s can talk to each other.
+///
+/// Each player gets its own `InProcessLocalConnectionBackend` made from a
+/// clone of the same bus.
+#[derive(Clone, Default)]
+pub struct LocalConnectionBus(Arc<Mutex<Mailboxes>>);
+
+impl LocalConnectionBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Local connection backend that only reaches the players sharing its bus.
+pub struct InProcessLocalConnectionBackend {
+    bus: LocalConnectionBus,
+
+    /// The connections that this player listens on.
+    names: HashSet<String>,
+}
+
+impl InProcessLocalConnectionBackend {
+    pub fn new(bus: LocalConnectionBus) -> Self {
+        Self {
+            bus,
+            names: HashSet::new(),
+        }
+    }
+}
+
+impl Default for InProcessLocalConnectionBackend {
+    /// A backend with a bus of its own, so that the player's connections can
+    /// only talk among themselves.
+    fn default() -> Self {
+        Self::new(LocalConnectionBus::new())
+    }
+}
+
+impl LocalConnectionBackend for InProcessLocalConnectionBackend {
+    fn connect(&mut self, name: &str) -> bool {
+        let mut mailboxes = self.bus.0.lock().unwrap();
+        if mailboxes.contains_key(name) {
+            return false;
+        }
+        mailboxes.insert(name.to_string(), Vec::new());
+        self.names.insert(name.to_string());
+        true
+    }
+
+    fn close(&mut self, name: &str) {
+        if self.names.remove(name) {
+            self.bus.0.lock().unwrap().remove(name);
+        }
+    }
+
+    fn send(&mut self, message: LocalConnectionMessage) -> bool {
+        match self.bus.0.lock().unwrap().get_mut(&message.connection) {
+            Some(mailbox) => {
+                mailbox.push(message);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn poll_messages(&mut self) -> Vec<LocalConnectionMessage> {
+        let mut mailboxes = self.bus.0.lock().unwrap();
+        let mut messages = Vec::new();
+        for name in &self.names {
+            if let Some(mailbox) = mailboxes.get_mut(name) {
+                messages.append(mailbox);
+            }
+        }
+        messages
+    }
+}
+
+impl Drop for InProcessLocalConnectionBackend {
+    fn drop(&mut self) {
+        // Let other players use this player's names once it's gone.
+        if let Ok(mut mailboxes) = self.bus.0.lock() {
+            for name in &self.names {
+                mailboxes.remove(name);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(connection: &str, method: &str) -> LocalConnectionMessage {
+        LocalConnectionMessage {
+            connection: connection.to_string(),
+            sender_domain: "localhost".to_string(),
+            method: method.to_string(),
+            args: vec![],
+        }
+    }
+
+    #[test]
+    fn players_share_a_bus() {
+        let bus = LocalConnectionBus::new();
+        let mut game = InProcessLocalConnectionBackend::new(bus.clone());
+        let mut preloader = InProcessLocalConnectionBackend::new(bus.clone());
+
+        assert!(game.connect("localhost:game"));
+        assert!(!preloader.connect("localhost:game"));
+        assert!(!game.send(message("localhost:preloader", "start")));
+        assert!(preloader.send(message("localhost:game", "start")));
+        assert!(preloader.poll_messages().is_empty());
+        assert_eq!(
+            game.poll_messages(),
+            vec![message("localhost:game", "start")]
+        );
+        assert!(game.poll_messages().is_empty());
+
+        // Only the player that connected can close a connection.
+        preloader.close("localhost:game");
+        assert!(preloader.send(message("localhost:game", "start")));
+        drop(game);
+        assert!(!preloader.send(message("localhost:game", "start")));
+        assert!(preloader.connect("localhost:game"));
+    }
+}
@@ -1,9 +1,55 @@
-use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+use chrono::{DateTime, Duration, FixedOffset, NaiveDateTime, TimeZone, Utc};
 
 pub trait LocaleBackend {
     fn get_current_date_time(&self) -> DateTime<Utc>;
 
+    /// The local timezone offset in effect right now.
     fn get_timezone(&self) -> FixedOffset;
+
+    /// The local timezone offset in effect at the given instant.
+    ///
+    /// This differs from `get_timezone` for dates on the other side of a
+    /// daylight saving time transition. Backends that don't know the local
+    /// timezone rules may leave this as the current offset.
+    fn get_timezone_at(&self, _time: DateTime<Utc>) -> FixedOffset {
+        self.get_timezone()
+    }
+
+    /// The local timezone offset in effect at the given local wall-clock time.
+    ///
+    /// Times that are skipped or repeated by a daylight saving time
+    /// transition resolve to the offset in effect before it. This is derived
+    /// from `get_timezone_at`, assuming that transitions are days apart.
+    fn get_timezone_at_local(&self, local: NaiveDateTime) -> FixedOffset {
+        let before = self.get_timezone_at(Utc.from_utc_datetime(&(local - Duration::days(1))));
+        let after = self.get_timezone_at(Utc.from_utc_datetime(&(local + Duration::days(1))));
+
+        // An offset applies if `local` read with it lands on an instant using that same offset.
+        // Repeated times match both, so the earlier offset wins; skipped times match neither.
+        [before, after]
+            .iter()
+            .copied()
+            .find(|offset| {
+                let utc = local - Duration::seconds(offset.local_minus_utc().into());
+                self.get_timezone_at(Utc.from_utc_datetime(&utc)) == *offset
+            })
+            .unwrap_or(before)
+    }
+
+    /// Convert an instant into local time, according to the timezone rules
+    /// in effect at that instant.
+    fn to_local_date_time(&self, time: DateTime<Utc>) -> DateTime<FixedOffset> {
+        time.with_timezone(&self.get_timezone_at(time))
+    }
+
+    /// Convert a local wall-clock time into an instant, according to the
+    /// timezone rules in effect at that time.
+    fn from_local_date_time(&self, local: NaiveDateTime) -> Option<DateTime<Utc>> {
+        self.get_timezone_at_local(local)
+            .from_local_datetime(&local)
+            .single()
+            .map(|time| time.with_timezone(&Utc))
+    }
 }
 
 /// Locale backend that mostly does nothing.
@@ -34,3 +80,83 @@ impl Default for NullLocaleBackend {
         NullLocaleBackend::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pretends to be in Central Europe in 2021, which switched from +1:00 to
+    /// +2:00 at 01:00 UTC on March 28 and back at 01:00 UTC on October 31.
+    struct DstLocaleBackend;
+
+    impl LocaleBackend for DstLocaleBackend {
+        fn get_current_date_time(&self) -> DateTime<Utc> {
+            Utc.ymd(2021, 1, 1).and_hms(0, 0, 0)
+        }
+
+        fn get_timezone(&self) -> FixedOffset {
+            FixedOffset::east(3600)
+        }
+
+        fn get_timezone_at(&self, time: DateTime<Utc>) -> FixedOffset {
+            let summer_start = Utc.ymd(2021, 3, 28).and_hms(1, 0, 0);
+            let summer_end = Utc.ymd(2021, 10, 31).and_hms(1, 0, 0);
+            if time >= summer_start && time < summer_end {
+                FixedOffset::east(7200)
+            } else {
+                FixedOffset::east(3600)
+            }
+        }
+    }
+
+    fn local(month: u32, day: u32, hour: u32, min: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd(2021, month, day).and_hms(hour, min, 0)
+    }
+
+    #[test]
+    fn outside_transitions() {
+        let backend = DstLocaleBackend;
+        assert_eq!(
+            backend.get_timezone_at_local(local(1, 15, 12, 0)),
+            FixedOffset::east(3600)
+        );
+        assert_eq!(
+            backend.get_timezone_at_local(local(7, 15, 12, 0)),
+            FixedOffset::east(7200)
+        );
+        assert_eq!(
+            backend.from_local_date_time(local(7, 15, 12, 0)),
+            Some(Utc.ymd(2021, 7, 15).and_hms(10, 0, 0))
+        );
+    }
+
+    #[test]
+    fn dst_gap() {
+        // 02:30 never happened on March 28; it is read with the winter offset.
+        let backend = DstLocaleBackend;
+        let gap = local(3, 28, 2, 30);
+        assert_eq!(backend.get_timezone_at_local(gap), FixedOffset::east(3600));
+
+        let time = backend.from_local_date_time(gap).unwrap();
+        assert_eq!(time, Utc.ymd(2021, 3, 28).and_hms(1, 30, 0));
+        assert_eq!(
+            backend.to_local_date_time(time).naive_local(),
+            local(3, 28, 3, 30)
+        );
+    }
+
+    #[test]
+    fn dst_overlap() {
+        // 02:30 happened twice on October 31; the first, summer one is used.
+        let backend = DstLocaleBackend;
+        let overlap = local(10, 31, 2, 30);
+        assert_eq!(
+            backend.get_timezone_at_local(overlap),
+            FixedOffset::east(7200)
+        );
+
+        let time = backend.from_local_date_time(overlap).unwrap();
+        assert_eq!(time, Utc.ymd(2021, 10, 31).and_hms(0, 30, 0));
+        assert_eq!(backend.to_local_date_time(time).naive_local(), overlap);
+    }
+}
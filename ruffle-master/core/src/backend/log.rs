@@ -1,5 +1,20 @@
+use crate::library::SymbolClassProblem;
+
 pub trait LogBackend {
     fn avm_trace(&self, message: &str);
+
+    /// Reports a problem found while binding a movie's symbol classes as it
+    /// loads.
+    ///
+    /// A movie whose main class can't be bound will show a blank stage, so
+    /// that problem is reported as an error.
+    fn symbol_class_problem(&self, problem: &SymbolClassProblem) {
+        if problem.is_main_class() {
+            log::error!("{}", problem);
+        } else {
+            log::warn!("{}", problem);
+        }
+    }
 }
 
 /// Logging backend that just reroutes traces to the log crate
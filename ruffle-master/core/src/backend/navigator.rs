@@ -141,6 +141,65 @@ impl RequestOptions {
 /// result of type `Result<T, E>`.
 pub type OwnedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'static>>;
 
+/// A part of the body of a response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// The bytes of this part of the body.
+    pub data: Vec<u8>,
+
+    /// The size of the whole body, if it is known.
+    pub total_len: Option<usize>,
+}
+
+/// A response body that is handed over in chunks as it arrives.
+pub trait ChunkStream {
+    /// Attempt to get the next chunk of the body.
+    ///
+    /// This yields `None` once the whole body has been handed over, and should
+    /// not be polled again after that or after yielding an error.
+    fn poll_next_chunk(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Chunk, Error>>>;
+}
+
+/// Type alias for pinned, boxed, and owned chunk streams.
+pub type OwnedChunkStream = Pin<Box<dyn ChunkStream + 'static>>;
+
+/// A `ChunkStream` that hands over the whole response to a fetch as a single
+/// chunk, once it has arrived.
+pub struct SingleChunk(Option<OwnedFuture<Vec<u8>, Error>>);
+
+impl SingleChunk {
+    pub fn new(fetch: OwnedFuture<Vec<u8>, Error>) -> Self {
+        Self(Some(fetch))
+    }
+}
+
+impl ChunkStream for SingleChunk {
+    fn poll_next_chunk(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Chunk, Error>>> {
+        let this = self.get_mut();
+        let fetch = match &mut this.0 {
+            Some(fetch) => fetch,
+            None => return Poll::Ready(None),
+        };
+
+        match fetch.as_mut().poll(cx) {
+            Poll::Ready(result) => {
+                this.0 = None;
+                Poll::Ready(Some(result.map(|data| Chunk {
+                    total_len: Some(data.len()),
+                    data,
+                })))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
 /// A backend interacting with a browser environment.
 pub trait NavigatorBackend {
     /// Cause a browser navigation to a given URL.
@@ -175,6 +234,15 @@ pub trait NavigatorBackend {
     /// Fetch data at a given URL and return it some time in the future.
     fn fetch(&self, url: &str, request_options: RequestOptions) -> OwnedFuture<Vec<u8>, Error>;
 
+    /// Fetch data at a given URL, handing it over in chunks as it arrives.
+    ///
+    /// Nothing should be sent until the stream is first polled. Backends that
+    /// can't stream a response hand it over whole, as a single chunk, which is
+    /// what the default implementation does.
+    fn fetch_chunked(&self, url: &str, request_options: RequestOptions) -> OwnedChunkStream {
+        Box::pin(SingleChunk::new(self.fetch(url, request_options)))
+    }
+
     /// Get the amount of time since the SWF was launched.
     /// Used by the `getTimer` ActionScript call.
     fn time_since_launch(&mut self) -> Duration;
@@ -306,6 +374,49 @@ impl NullExecutor {
     }
 }
 
+/// The size of the chunks that `NullNavigatorBackend` hands files over in.
+const NULL_CHUNK_SIZE: usize = 4096;
+
+/// A `ChunkStream` that reads a file on its first poll, and then hands it over
+/// in chunks of `NULL_CHUNK_SIZE` bytes.
+struct FileChunks {
+    path: PathBuf,
+    data: Option<Vec<u8>>,
+    position: usize,
+}
+
+impl ChunkStream for FileChunks {
+    fn poll_next_chunk(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Chunk, Error>>> {
+        let this = self.get_mut();
+        if this.data.is_none() {
+            match fs::read(&this.path) {
+                Ok(data) => this.data = Some(data),
+                Err(e) => {
+                    this.data = Some(vec![]);
+                    this.position = usize::MAX;
+                    return Poll::Ready(Some(Err(Error::NetworkError(e))));
+                }
+            }
+        }
+
+        let data = this.data.as_deref().unwrap_or_default();
+        if this.position >= data.len() {
+            return Poll::Ready(None);
+        }
+
+        let end = data.len().min(this.position + NULL_CHUNK_SIZE);
+        let chunk = Chunk {
+            data: data[this.position..end].to_vec(),
+            total_len: Some(data.len()),
+        };
+        this.position = end;
+        Poll::Ready(Some(Ok(chunk)))
+    }
+}
+
 /// A null implementation for platforms that do not live in a web browser.
 ///
 /// The NullNavigatorBackend includes a trivial executor that holds owned
@@ -366,6 +477,17 @@ impl NavigatorBackend for NullNavigatorBackend {
         Box::pin(async move { fs::read(path).map_err(Error::NetworkError) })
     }
 
+    fn fetch_chunked(&self, url: &str, _opts: RequestOptions) -> OwnedChunkStream {
+        let mut path = self.relative_base_path.clone();
+        path.push(url);
+
+        Box::pin(FileChunks {
+            path,
+            data: None,
+            position: 0,
+        })
+    }
+
     fn time_since_launch(&mut self) -> Duration {
         Duration::from_millis(0)
     }
@@ -0,0 +1,86 @@
+use downcast_rs::Downcast;
+
+/// Which way up the paper of a print job is.
+/// Equivalent to AS3 `PrintJobOrientation`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PageOrientation {
+    Portrait,
+    Landscape,
+}
+
+impl PageOrientation {
+    /// The name of this orientation in ActionScript.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PageOrientation::Portrait => "portrait",
+            PageOrientation::Landscape => "landscape",
+        }
+    }
+}
+
+/// The paper that the user picked when starting a print job. All sizes are
+/// in points, which are also the pixels of the movie.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PageSettings {
+    /// The width of the area of the page that can be printed on.
+    pub page_width: u32,
+
+    /// The height of the area of the page that can be printed on.
+    pub page_height: u32,
+
+    /// The width of the whole sheet of paper.
+    pub paper_width: u32,
+
+    /// The height of the whole sheet of paper.
+    pub paper_height: u32,
+
+    pub orientation: PageOrientation,
+}
+
+/// A page of a print job, as unmultiplied RGBA pixels, one per point.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrintedPage {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// Printers for `PrintJob`.
+///
+/// A job is started, given its pages one at a time, then sent to the printer
+/// all at once. Pages added and sends made when no job is in progress should
+/// be ignored.
+pub trait PrintBackend: Downcast {
+    /// Ask the user to start a print job, such as with a print dialog.
+    ///
+    /// Returns the paper to print on, or `None` if the user cancelled, a job
+    /// is already in progress, or there is nothing to print with.
+    fn start_job(&mut self) -> Option<PageSettings>;
+
+    /// Add a page to the job in progress.
+    fn add_page(&mut self, page: PrintedPage);
+
+    /// Print the pages of the job in progress, and end it.
+    fn send_job(&mut self);
+}
+impl_downcast!(PrintBackend);
+
+/// Print backend that can't print, as if the user always cancelled.
+#[derive(Default)]
+pub struct NullPrintBackend {}
+
+impl NullPrintBackend {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl PrintBackend for NullPrintBackend {
+    fn start_job(&mut self) -> Option<PageSettings> {
+        None
+    }
+
+    fn add_page(&mut self, _page: PrintedPage) {}
+
+    fn send_job(&mut self) {}
+}
@@ -1,10 +1,10 @@
+pub use crate::commands::{CommandHandler, CommandList};
 use crate::shape_utils::DistilledShape;
 pub use crate::{library::MovieLibrary, transform::Transform, Color};
 use downcast_rs::Downcast;
 use gc_arena::Collect;
 use std::io::Read;
 pub use swf;
-use swf::Matrix;
 
 pub trait RenderBackend: Downcast {
     fn set_viewport_dimensions(&mut self, width: u32, height: u32);
@@ -36,15 +36,25 @@ pub trait RenderBackend: Downcast {
         swf_tag: &swf::DefineBitsLossless,
     ) -> Result<BitmapInfo, Error>;
 
-    fn begin_frame(&mut self, clear: Color);
-    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool);
-    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform);
-    fn draw_rect(&mut self, color: Color, matrix: &Matrix);
-    fn end_frame(&mut self);
-    fn push_mask(&mut self);
-    fn activate_mask(&mut self);
-    fn deactivate_mask(&mut self);
-    fn pop_mask(&mut self);
+    /// Draw a frame: clear the viewport to `clear`, then draw the commands
+    /// recorded for the frame.
+    fn submit_frame(&mut self, clear: Color, commands: CommandList);
+
+    /// Whether the previous frame is kept when the next one begins, so that
+    /// only the parts of the viewport that changed have to be drawn again.
+    fn supports_partial_redraw(&self) -> bool {
+        false
+    }
+
+    /// Restrict the next frame to `area` of the viewport, leaving the rest of
+    /// the previous frame as it was, or draw the whole viewport with `None`.
+    ///
+    /// This is only called if `supports_partial_redraw` returns `true`.
+    fn set_scissor(&mut self, _area: Option<swf::Rectangle>) {}
+
+    /// The features this backend supports, which core uses to choose a
+    /// fallback for anything the backend can't draw.
+    fn capabilities(&self) -> &RenderCapabilities;
 
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap>;
     fn register_bitmap_raw(
@@ -60,6 +70,11 @@ pub trait RenderBackend: Downcast {
         height: u32,
         rgba: Vec<u8>,
     ) -> Result<BitmapHandle, Error>;
+
+    /// Free the memory of a bitmap that will no longer be drawn.
+    ///
+    /// The handle must not be used again afterwards.
+    fn unregister_bitmap(&mut self, bitmap: BitmapHandle);
 }
 impl_downcast!(RenderBackend);
 
@@ -80,11 +95,94 @@ pub struct BitmapInfo {
     pub height: u16,
 }
 
-pub struct NullRenderer;
+/// The features supported by a render backend.
+#[derive(Clone, Debug)]
+pub struct RenderCapabilities {
+    /// The largest width or height, in pixels, of a bitmap the backend can
+    /// draw. Larger bitmaps are scaled down to fit.
+    pub max_texture_size: u32,
+
+    /// Whether display filters can be drawn.
+    ///
+    /// Filters, like layers and cached bitmaps, are drawn offscreen in
+    /// software, so this needs a backend that can upload a new bitmap every
+    /// frame without stalling. Without it, objects are drawn unfiltered.
+    pub supports_filters: bool,
+
+    /// The blend modes, other than `Normal` and `Layer`, that the backend
+    /// draws correctly.
+    pub blend_modes: Vec<swf::BlendMode>,
+}
+
+impl RenderCapabilities {
+    /// Whether `blend_mode` is drawn correctly by the backend.
+    pub fn supports_blend_mode(&self, blend_mode: swf::BlendMode) -> bool {
+        matches!(blend_mode, swf::BlendMode::Normal | swf::BlendMode::Layer)
+            || self.blend_modes.contains(&blend_mode)
+    }
+
+    /// The blend mode to draw with in place of `blend_mode`.
+    ///
+    /// This is `blend_mode` itself if the backend supports it, or else the
+    /// closest blend mode it does support, or `Normal` if there is none.
+    pub fn blend_mode_fallback(&self, blend_mode: swf::BlendMode) -> swf::BlendMode {
+        use swf::BlendMode;
+
+        let fallbacks: &[BlendMode] = match blend_mode {
+            // Both only differ where the result would be clamped.
+            BlendMode::Subtract => &[BlendMode::Difference],
+            BlendMode::Difference => &[BlendMode::Subtract],
+            BlendMode::Lighten => &[BlendMode::Screen],
+            BlendMode::Darken => &[BlendMode::Multiply],
+            BlendMode::HardLight => &[BlendMode::Overlay],
+            BlendMode::Overlay => &[BlendMode::HardLight],
+            _ => &[],
+        };
+
+        std::iter::once(&blend_mode)
+            .chain(fallbacks)
+            .copied()
+            .find(|&blend_mode| self.supports_blend_mode(blend_mode))
+            .unwrap_or(BlendMode::Normal)
+    }
+}
+
+impl Default for RenderCapabilities {
+    /// A backend that supports everything, with the bitmap size limit of
+    /// Flash Player.
+    fn default() -> Self {
+        use swf::BlendMode;
+
+        Self {
+            max_texture_size: 8191,
+            supports_filters: true,
+            blend_modes: vec![
+                BlendMode::Multiply,
+                BlendMode::Screen,
+                BlendMode::Lighten,
+                BlendMode::Darken,
+                BlendMode::Difference,
+                BlendMode::Add,
+                BlendMode::Subtract,
+                BlendMode::Invert,
+                BlendMode::Alpha,
+                BlendMode::Erase,
+                BlendMode::Overlay,
+                BlendMode::HardLight,
+            ],
+        }
+    }
+}
+
+pub struct NullRenderer {
+    capabilities: RenderCapabilities,
+}
 
 impl NullRenderer {
     pub fn new() -> Self {
-        Self
+        Self {
+            capabilities: Default::default(),
+        }
     }
 }
 
@@ -152,15 +250,11 @@ impl RenderBackend for NullRenderer {
             height: 0,
         })
     }
-    fn begin_frame(&mut self, _clear: Color) {}
-    fn end_frame(&mut self) {}
-    fn render_bitmap(&mut self, _bitmap: BitmapHandle, _transform: &Transform, _smoothing: bool) {}
-    fn render_shape(&mut self, _shape: ShapeHandle, _transform: &Transform) {}
-    fn draw_rect(&mut self, _color: Color, _matrix: &Matrix) {}
-    fn push_mask(&mut self) {}
-    fn activate_mask(&mut self) {}
-    fn deactivate_mask(&mut self) {}
-    fn pop_mask(&mut self) {}
+    fn submit_frame(&mut self, _clear: Color, _commands: CommandList) {}
+
+    fn capabilities(&self) -> &RenderCapabilities {
+        &self.capabilities
+    }
 
     fn get_bitmap_pixels(&mut self, _bitmap: BitmapHandle) -> Option<Bitmap> {
         None
@@ -183,6 +277,8 @@ impl RenderBackend for NullRenderer {
     ) -> Result<BitmapHandle, Error> {
         Ok(BitmapHandle(0))
     }
+
+    fn unregister_bitmap(&mut self, _bitmap: BitmapHandle) {}
 }
 
 /// The format of image data in a DefineBitsJpeg2/3 tag.
@@ -269,26 +365,45 @@ pub fn decode_define_bits_jpeg(data: &[u8], alpha_data: Option<&[u8]>) -> Result
 }
 
 pub fn glue_swf_jpeg_to_tables(jpeg_tables: &[u8], jpeg_data: &[u8]) -> Vec<u8> {
-    let mut full_jpeg = Vec::with_capacity(jpeg_tables.len() + jpeg_data.len() - 4);
-    full_jpeg.extend_from_slice(&jpeg_tables[..jpeg_tables.len() - 2]);
-    full_jpeg.extend_from_slice(&jpeg_data[2..]);
-    full_jpeg
+    glue_tables_to_jpeg(jpeg_data, Some(jpeg_tables)).into_owned()
 }
 
 /// Glues the JPEG encoding tables from a JPEGTables SWF tag to the JPEG data
 /// in a DefineBits tag, producing complete JPEG data suitable for a decoder.
+///
+/// Both parts are normally complete JPEG streams, with the tables ending in an
+/// EOI marker and the image data starting with an SOI marker. Either part may
+/// also carry the erroneous header described in `remove_invalid_jpeg_data`.
+/// These markers are stripped so that a single stream remains.
 pub fn glue_tables_to_jpeg<'a>(
     jpeg_data: &'a [u8],
     jpeg_tables: Option<&'a [u8]>,
 ) -> std::borrow::Cow<'a, [u8]> {
+    const SOI: [u8; 2] = [0xFF, 0xD8];
+    const EOI: [u8; 2] = [0xFF, 0xD9];
+
     if let Some(jpeg_tables) = jpeg_tables {
-        if jpeg_tables.len() >= 2 {
-            let mut full_jpeg = Vec::with_capacity(jpeg_tables.len() + jpeg_data.len());
-            full_jpeg.extend_from_slice(&jpeg_tables[..jpeg_tables.len() - 2]);
-            if jpeg_data.len() >= 2 {
-                full_jpeg.extend_from_slice(&jpeg_data[2..]);
-            }
+        let jpeg_tables = remove_invalid_jpeg_data(jpeg_tables);
+        let jpeg_data = remove_invalid_jpeg_data(jpeg_data);
 
+        let mut tables = &jpeg_tables[..];
+        if tables.starts_with(&SOI) {
+            tables = &tables[2..];
+        }
+        if tables.ends_with(&EOI) {
+            tables = &tables[..tables.len() - 2];
+        }
+
+        let mut data = &jpeg_data[..];
+        if data.starts_with(&SOI) {
+            data = &data[2..];
+        }
+
+        if !tables.is_empty() {
+            let mut full_jpeg = Vec::with_capacity(SOI.len() + tables.len() + data.len());
+            full_jpeg.extend_from_slice(&SOI);
+            full_jpeg.extend_from_slice(tables);
+            full_jpeg.extend_from_slice(data);
             return std::borrow::Cow::from(full_jpeg);
         }
     }
@@ -303,12 +418,17 @@ pub fn glue_tables_to_jpeg<'a>(
 /// "Before version 8 of the SWF file format, SWF files could contain an erroneous header of 0xFF, 0xD9, 0xFF, 0xD8 before the JPEG SOI marker."
 /// These bytes need to be removed for the JPEG to decode properly.
 pub fn remove_invalid_jpeg_data(mut data: &[u8]) -> std::borrow::Cow<[u8]> {
+    const INVALID_HEADER: [u8; 4] = [0xFF, 0xD9, 0xFF, 0xD8];
+
     // TODO: Might be better to return an Box<Iterator<Item=u8>> instead of a Cow here,
     // where the spliced iter is a data[..n].chain(data[n+4..])?
-    if data[..4] == [0xFF, 0xD9, 0xFF, 0xD8] {
+    if data.starts_with(&INVALID_HEADER) {
         data = &data[4..];
     }
-    if let Some(pos) = (0..data.len() - 4).find(|&n| data[n..n + 4] == [0xFF, 0xD9, 0xFF, 0xD8]) {
+    if let Some(pos) = data
+        .windows(INVALID_HEADER.len())
+        .position(|window| window == INVALID_HEADER)
+    {
         let mut out_data = Vec::with_capacity(data.len() - 4);
         out_data.extend_from_slice(&data[..pos]);
         out_data.extend_from_slice(&data[pos + 4..]);
@@ -565,6 +685,170 @@ pub fn decode_gif(data: &[u8]) -> Result<Bitmap, Error> {
     })
 }
 
+/// Scale a bitmap down so that neither side is larger than `max_size`,
+/// keeping its aspect ratio.
+///
+/// This lets a backend use bitmaps that are larger than it can upload for
+/// bitmap fills, at a lower resolution. Returns `None` if the bitmap already
+/// fits.
+pub fn fit_bitmap_to_size(bitmap: &Bitmap, max_size: u32) -> Option<Bitmap> {
+    if bitmap.width <= max_size && bitmap.height <= max_size {
+        return None;
+    }
+
+    let scale = f64::from(max_size) / f64::from(bitmap.width.max(bitmap.height));
+    let width = ((f64::from(bitmap.width) * scale) as u32).max(1);
+    let height = ((f64::from(bitmap.height) * scale) as u32).max(1);
+    log::warn!(
+        "Bitmap of {}x{} is too large for the renderer, filling with it at {}x{}",
+        bitmap.width,
+        bitmap.height,
+        width,
+        height
+    );
+
+    let (data, channels) = match &bitmap.data {
+        BitmapFormat::Rgb(data) => (data, 3),
+        BitmapFormat::Rgba(data) => (data, 4),
+    };
+    let mut scaled = Vec::with_capacity(width as usize * height as usize * channels);
+    for y in 0..height {
+        // Sample the source pixel under the center of each scaled pixel.
+        let src_y =
+            ((u64::from(y) * 2 + 1) * u64::from(bitmap.height) / (u64::from(height) * 2)) as usize;
+        for x in 0..width {
+            let src_x = ((u64::from(x) * 2 + 1) * u64::from(bitmap.width) / (u64::from(width) * 2))
+                as usize;
+            let i = (src_y * bitmap.width as usize + src_x) * channels;
+            scaled.extend_from_slice(&data[i..i + channels]);
+        }
+    }
+
+    Some(Bitmap {
+        width,
+        height,
+        data: match bitmap.data {
+            BitmapFormat::Rgb(_) => BitmapFormat::Rgb(scaled),
+            BitmapFormat::Rgba(_) => BitmapFormat::Rgba(scaled),
+        },
+    })
+}
+
+/// A piece of a bitmap, `x` and `y` pixels from its top left corner.
+#[derive(Debug, Clone)]
+pub struct BitmapTile {
+    pub x: u32,
+    pub y: u32,
+    pub bitmap: Bitmap,
+}
+
+/// Split a bitmap into tiles that are no larger than `max_size` on either
+/// side, row by row.
+///
+/// This lets a backend draw bitmaps that are larger than it can upload at
+/// their full resolution, one tile at a time. Returns `None` if the bitmap
+/// already fits.
+pub fn split_bitmap_into_tiles(bitmap: &Bitmap, max_size: u32) -> Option<Vec<BitmapTile>> {
+    if bitmap.width <= max_size && bitmap.height <= max_size {
+        return None;
+    }
+
+    let (data, channels) = match &bitmap.data {
+        BitmapFormat::Rgb(data) => (data, 3),
+        BitmapFormat::Rgba(data) => (data, 4),
+    };
+    let mut tiles = Vec::new();
+    for y in (0..bitmap.height).step_by(max_size as usize) {
+        let height = max_size.min(bitmap.height - y);
+        for x in (0..bitmap.width).step_by(max_size as usize) {
+            let width = max_size.min(bitmap.width - x);
+            let row_len = width as usize * channels;
+            let mut tile = Vec::with_capacity(row_len * height as usize);
+            for row in y..y + height {
+                let start = (row as usize * bitmap.width as usize + x as usize) * channels;
+                tile.extend_from_slice(&data[start..start + row_len]);
+            }
+
+            tiles.push(BitmapTile {
+                x,
+                y,
+                bitmap: Bitmap {
+                    width,
+                    height,
+                    data: match bitmap.data {
+                        BitmapFormat::Rgb(_) => BitmapFormat::Rgb(tile),
+                        BitmapFormat::Rgba(_) => BitmapFormat::Rgba(tile),
+                    },
+                },
+            });
+        }
+    }
+
+    Some(tiles)
+}
+
+/// The number of mip levels in a full mip chain for a bitmap, including the
+/// bitmap itself.
+pub fn mip_level_count(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+/// Build the mip chain of a bitmap for sampling it smoothly below its full
+/// size.
+///
+/// Each level is half the width and height of the one before it, down to a
+/// single pixel; the bitmap itself is not included. Bitmap data is
+/// premultiplied, so every pixel is the plain average of the pixels it covers
+/// in the level above.
+pub fn generate_mipmaps(bitmap: &Bitmap) -> Vec<Bitmap> {
+    let mut levels: Vec<Bitmap> = Vec::new();
+    loop {
+        let previous = levels.last().unwrap_or(bitmap);
+        if previous.width <= 1 && previous.height <= 1 {
+            return levels;
+        }
+        let level = half_size_bitmap(previous);
+        levels.push(level);
+    }
+}
+
+/// Scale a bitmap down to half its width and height with a box filter.
+fn half_size_bitmap(bitmap: &Bitmap) -> Bitmap {
+    let width = (bitmap.width / 2).max(1);
+    let height = (bitmap.height / 2).max(1);
+    let (data, channels) = match &bitmap.data {
+        BitmapFormat::Rgb(data) => (data, 3),
+        BitmapFormat::Rgba(data) => (data, 4),
+    };
+
+    let src_width = bitmap.width as usize;
+    let mut scaled = Vec::with_capacity(width as usize * height as usize * channels);
+    for y in 0..height as usize {
+        let y0 = (y * 2).min(bitmap.height as usize - 1);
+        let y1 = (y * 2 + 1).min(bitmap.height as usize - 1);
+        for x in 0..width as usize {
+            let x0 = (x * 2).min(src_width - 1);
+            let x1 = (x * 2 + 1).min(src_width - 1);
+            for c in 0..channels {
+                let sum: u32 = [(x0, y0), (x1, y0), (x0, y1), (x1, y1)]
+                    .iter()
+                    .map(|(x, y)| u32::from(data[(y * src_width + x) * channels + c]))
+                    .sum();
+                scaled.push(((sum + 2) / 4) as u8);
+            }
+        }
+    }
+
+    Bitmap {
+        width,
+        height,
+        data: match bitmap.data {
+            BitmapFormat::Rgb(_) => BitmapFormat::Rgb(scaled),
+            BitmapFormat::Rgba(_) => BitmapFormat::Rgba(scaled),
+        },
+    }
+}
+
 /// Images in SWFs are stored with premultiplied alpha.
 /// Converts RGBA premultiplied alpha to standard RBGA.
 pub fn unmultiply_alpha_rgba(rgba: &mut [u8]) {
@@ -578,6 +862,19 @@ pub fn unmultiply_alpha_rgba(rgba: &mut [u8]) {
     })
 }
 
+/// Converts standard RGBA to RGBA premultiplied alpha, as bitmaps are passed
+/// to the render backend.
+pub fn premultiply_alpha_rgba(rgba: &mut [u8]) {
+    rgba.chunks_exact_mut(4).for_each(|rgba| {
+        if rgba[3] < 255 {
+            let a = u16::from(rgba[3]);
+            rgba[0] = ((u16::from(rgba[0]) * a + 127) / 255) as u8;
+            rgba[1] = ((u16::from(rgba[1]) * a + 127) / 255) as u8;
+            rgba[2] = ((u16::from(rgba[2]) * a + 127) / 255) as u8;
+        }
+    })
+}
+
 /// Converts an RGBA color from sRGB space to linear color space.
 pub fn srgb_to_linear(color: [f32; 4]) -> [f32; 4] {
     fn to_linear_channel(n: f32) -> f32 {
@@ -603,3 +900,115 @@ fn decompress_zlib(data: &[u8]) -> Result<Vec<u8>, std::io::Error> {
     out_data.shrink_to_fit();
     Ok(out_data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glue_tables_strips_markers() {
+        let tables = [0xFF, 0xD8, 0xFF, 0xDB, 0x01, 0xFF, 0xD9];
+        let data = [0xFF, 0xD8, 0xFF, 0xC0, 0x02, 0xFF, 0xD9];
+        assert_eq!(
+            &glue_tables_to_jpeg(&data, Some(&tables[..]))[..],
+            &[0xFF, 0xD8, 0xFF, 0xDB, 0x01, 0xFF, 0xC0, 0x02, 0xFF, 0xD9]
+        );
+    }
+
+    #[test]
+    fn glue_tables_strips_invalid_headers() {
+        let tables = [
+            0xFF, 0xD9, 0xFF, 0xD8, 0xFF, 0xD8, 0xFF, 0xDB, 0x01, 0xFF, 0xD9,
+        ];
+        let data = [
+            0xFF, 0xD9, 0xFF, 0xD8, 0xFF, 0xD8, 0xFF, 0xC0, 0x02, 0xFF, 0xD9,
+        ];
+        assert_eq!(
+            &glue_tables_to_jpeg(&data, Some(&tables[..]))[..],
+            &[0xFF, 0xD8, 0xFF, 0xDB, 0x01, 0xFF, 0xC0, 0x02, 0xFF, 0xD9]
+        );
+    }
+
+    #[test]
+    fn glue_tables_without_tables() {
+        let data = [0xFF, 0xD8, 0xFF, 0xD9];
+        assert_eq!(&glue_tables_to_jpeg(&data, None)[..], &data);
+        assert_eq!(&glue_tables_to_jpeg(&data, Some(&[][..]))[..], &data);
+        assert_eq!(&remove_invalid_jpeg_data(&[0xFF])[..], &[0xFF]);
+    }
+
+    #[test]
+    fn fit_bitmap_to_size_scales_down() {
+        let bitmap = Bitmap {
+            width: 4,
+            height: 2,
+            data: BitmapFormat::Rgb((0..24).collect()),
+        };
+        assert!(fit_bitmap_to_size(&bitmap, 4).is_none());
+
+        let fitted = fit_bitmap_to_size(&bitmap, 2).unwrap();
+        assert_eq!((fitted.width, fitted.height), (2, 1));
+        match fitted.data {
+            BitmapFormat::Rgb(data) => assert_eq!(data, [15, 16, 17, 21, 22, 23]),
+            BitmapFormat::Rgba(_) => panic!("Bitmap format changed"),
+        }
+    }
+
+    #[test]
+    fn split_bitmap_into_tiles_covers_bitmap() {
+        let bitmap = Bitmap {
+            width: 3,
+            height: 2,
+            data: BitmapFormat::Rgb((0..18).collect()),
+        };
+        assert!(split_bitmap_into_tiles(&bitmap, 3).is_none());
+
+        let tiles = split_bitmap_into_tiles(&bitmap, 2).unwrap();
+        let tiles: Vec<_> = tiles
+            .into_iter()
+            .map(|tile| match tile.bitmap.data {
+                BitmapFormat::Rgb(data) => (tile.x, tile.y, tile.bitmap.width, data),
+                BitmapFormat::Rgba(_) => panic!("Bitmap format changed"),
+            })
+            .collect();
+        assert_eq!(
+            tiles,
+            [
+                (0, 0, 2, vec![0, 1, 2, 3, 4, 5, 9, 10, 11, 12, 13, 14]),
+                (2, 0, 1, vec![6, 7, 8, 15, 16, 17]),
+            ]
+        );
+    }
+
+    #[test]
+    fn generate_mipmaps_halves_to_one_pixel() {
+        let bitmap = Bitmap {
+            width: 4,
+            height: 2,
+            data: BitmapFormat::Rgba(vec![
+                0, 0, 0, 0, 4, 4, 4, 4, 8, 8, 8, 8, 8, 8, 8, 8, //
+                0, 0, 0, 0, 4, 4, 4, 4, 8, 8, 8, 8, 8, 8, 8, 8, //
+            ]),
+        };
+        assert_eq!(mip_level_count(bitmap.width, bitmap.height), 3);
+
+        let levels = generate_mipmaps(&bitmap);
+        let sizes: Vec<_> = levels.iter().map(|l| (l.width, l.height)).collect();
+        assert_eq!(sizes, [(2, 1), (1, 1)]);
+        match &levels[0].data {
+            BitmapFormat::Rgba(data) => assert_eq!(data, &[2, 2, 2, 2, 8, 8, 8, 8]),
+            BitmapFormat::Rgb(_) => panic!("Bitmap format changed"),
+        }
+        match &levels[1].data {
+            BitmapFormat::Rgba(data) => assert_eq!(data, &[5, 5, 5, 5]),
+            BitmapFormat::Rgb(_) => panic!("Bitmap format changed"),
+        }
+    }
+
+    #[test]
+    fn premultiply_alpha() {
+        let mut rgba = [255, 128, 0, 255, 255, 128, 0, 128, 255, 128, 0, 0];
+        premultiply_alpha_rgba(&mut rgba);
+        assert_eq!(rgba, [255, 128, 0, 255, 128, 64, 0, 128, 0, 0, 0, 0]);
+    }
+}
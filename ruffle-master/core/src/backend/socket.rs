@@ -0,0 +1,75 @@
+use downcast_rs::Downcast;
+use generational_arena::Index;
+
+/// Identifies a socket to the backend. The player picks the handle when it
+/// asks the backend to connect, and the backend tags its events with it.
+pub type SocketHandle = Index;
+
+/// Something that happened on a socket since the backend was last polled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SocketEvent {
+    /// The connection was established.
+    Connected(SocketHandle),
+
+    /// Data arrived from the other end.
+    Data(SocketHandle, Vec<u8>),
+
+    /// The other end closed the connection.
+    Closed(SocketHandle),
+
+    /// The connection could not be made, or failed after it was made. The
+    /// socket is closed afterwards.
+    Error(SocketHandle, String),
+}
+
+/// TCP connections for `flash.net.Socket` and `XMLSocket`.
+///
+/// Backends shouldn't block: `connect` and `send` start the work, and its
+/// results are collected once per frame by `poll_events`.
+pub trait SocketBackend: Downcast {
+    /// Start connecting to `host` on `port`.
+    ///
+    /// The backend reports `Connected` or `Error` for `handle` when the
+    /// attempt is over.
+    fn connect(&mut self, handle: SocketHandle, host: &str, port: u16);
+
+    /// Send data on a connected socket.
+    fn send(&mut self, handle: SocketHandle, data: &[u8]);
+
+    /// Close a socket. No more events are reported for it.
+    fn close(&mut self, handle: SocketHandle);
+
+    /// Take the events that happened since the last call.
+    fn poll_events(&mut self) -> Vec<SocketEvent>;
+}
+impl_downcast!(SocketBackend);
+
+/// Socket backend for platforms that can't make TCP connections, which fails
+/// every connection attempt.
+#[derive(Default)]
+pub struct NullSocketBackend {
+    events: Vec<SocketEvent>,
+}
+
+impl NullSocketBackend {
+    pub fn new() -> Self {
+        Self { events: Vec::new() }
+    }
+}
+
+impl SocketBackend for NullSocketBackend {
+    fn connect(&mut self, handle: SocketHandle, _host: &str, _port: u16) {
+        self.events.push(SocketEvent::Error(
+            handle,
+            "Sockets are not supported on this platform".to_string(),
+        ));
+    }
+
+    fn send(&mut self, _handle: SocketHandle, _data: &[u8]) {}
+
+    fn close(&mut self, _handle: SocketHandle) {}
+
+    fn poll_events(&mut self) -> Vec<SocketEvent> {
+        std::mem::take(&mut self.events)
+    }
+}
@@ -1,5 +1,8 @@
 use crate::events::KeyCode;
+use crate::i18n::MessageCatalog;
 use downcast_rs::Downcast;
+#[cfg(feature = "serde")]
+use serde::Serialize;
 
 pub trait UiBackend: Downcast {
     fn is_key_down(&self, key: KeyCode) -> bool;
@@ -15,14 +18,76 @@ pub trait UiBackend: Downcast {
     /// Changes the mouse cursor image.
     fn set_mouse_cursor(&mut self, cursor: MouseCursor);
 
-    /// Set the clipboard to the given content
-    fn set_clipboard_content(&mut self, content: String);
-
     fn is_fullscreen(&self) -> bool;
 
+    /// Asks the UI to enter or leave fullscreen, such as when a movie sets
+    /// the `displayState` of the AVM2 `Stage`.
+    ///
+    /// Returns `false` if the UI refused the request.
+    fn set_fullscreen(&mut self, _is_full: bool) -> bool {
+        false
+    }
+
     /// Displays a warning about unsupported content in Ruffle.
     /// The user can still click an "OK" or "run anyway" message to dismiss the warning.
-    fn display_unsupported_message(&self);
+    ///
+    /// Text shown by this and the other dialogs should come from `messages`.
+    fn display_unsupported_message(&self, messages: &dyn MessageCatalog);
+
+    /// Displays an uncaught ActionScript error to the user, similar to the
+    /// error dialog of the Flash debug player.
+    ///
+    /// The returned value tells the player whether it should keep reporting
+    /// errors, or silently log any further errors for the rest of the session.
+    fn display_script_error(
+        &mut self,
+        _error: &ScriptError,
+        _messages: &dyn MessageCatalog,
+    ) -> ScriptErrorResponse {
+        ScriptErrorResponse::Continue
+    }
+
+    /// Displays a context menu with the movie's own items at the mouse
+    /// position.
+    ///
+    /// When the user picks one of the items, the UI should pass its index to
+    /// `Player::run_context_menu_item`.
+    fn display_context_menu(&mut self, _items: &[ContextMenuItem]) {}
+
+    /// Whether the UI can rotate the stage when a movie asks it to.
+    fn supports_orientation_change(&self) -> bool {
+        false
+    }
+
+    /// Asks the UI to rotate the stage, such as when a movie calls
+    /// `Stage.setOrientation`.
+    ///
+    /// Returns `false` if the UI refused the request. Rotations that the UI
+    /// makes by itself should instead be passed to `Player::set_orientation`.
+    fn set_orientation(&mut self, _orientation: StageOrientation) -> bool {
+        false
+    }
+
+    /// Asks the user to pick a file to open, such as when a movie calls
+    /// `FileReference.browse`.
+    ///
+    /// Once the user picks a file or cancels, the UI should pass the result to
+    /// `Player::finish_file_dialog`. Returns `false` if the UI can't show file
+    /// dialogs.
+    fn display_open_file_dialog(&mut self, _filters: &[FileFilter]) -> bool {
+        false
+    }
+
+    /// Asks the user where to save a file, such as when a movie calls
+    /// `FileReference.save`.
+    ///
+    /// The UI should write `data` to the file the user picks, then pass that
+    /// file to `Player::finish_file_dialog`, or `None` if the user cancelled.
+    /// Returns `false` if the UI can't show file dialogs.
+    fn display_save_file_dialog(&mut self, _default_name: &str, _data: &[u8]) -> bool {
+        false
+    }
+
     // Unused, but kept in case we need it later
     fn message(&self, message: &str);
 }
@@ -49,6 +114,135 @@ pub enum MouseCursor {
     Grab,
 }
 
+/// The orientation of the stage, relative to the default orientation of the
+/// device it is shown on.
+/// Equivalent to AS3 `StageOrientation`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StageOrientation {
+    /// The device is held the way it is meant to be.
+    Default,
+
+    /// The device is rotated to the left, so the stage's left edge is at the
+    /// bottom.
+    RotatedLeft,
+
+    /// The device is rotated to the right, so the stage's right edge is at
+    /// the bottom.
+    RotatedRight,
+
+    /// The device is upside down.
+    UpsideDown,
+
+    /// The orientation can't be determined, such as when the device is lying
+    /// flat.
+    Unknown,
+}
+
+impl StageOrientation {
+    /// The name of this orientation in ActionScript.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StageOrientation::Default => "default",
+            StageOrientation::RotatedLeft => "rotatedLeft",
+            StageOrientation::RotatedRight => "rotatedRight",
+            StageOrientation::UpsideDown => "upsideDown",
+            StageOrientation::Unknown => "unknown",
+        }
+    }
+
+    /// Parse the ActionScript name of an orientation.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(StageOrientation::Default),
+            "rotatedLeft" => Some(StageOrientation::RotatedLeft),
+            "rotatedRight" => Some(StageOrientation::RotatedRight),
+            "upsideDown" => Some(StageOrientation::UpsideDown),
+            "unknown" => Some(StageOrientation::Unknown),
+            _ => None,
+        }
+    }
+}
+
+impl Default for StageOrientation {
+    fn default() -> Self {
+        StageOrientation::Default
+    }
+}
+
+/// A custom item in a context menu, added by the movie.
+/// Communicated from the core to the UI backend via `UiBackend::display_context_menu`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct ContextMenuItem {
+    /// The text of the item.
+    pub caption: String,
+
+    /// Whether the item can be selected, rather than being greyed out.
+    pub enabled: bool,
+
+    /// Whether a separator should be drawn above the item.
+    pub separator_before: bool,
+}
+
+/// A kind of file that a file dialog should offer.
+/// Equivalent to AS3 `FileFilter`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileFilter {
+    /// The description of the files, e.g. "Images".
+    pub description: String,
+
+    /// Patterns that the files' names match, e.g. `*.jpg`.
+    pub patterns: Vec<String>,
+}
+
+/// A file that the user picked in a file dialog.
+/// Communicated from the UI backend to the core via `Player::finish_file_dialog`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DialogFile {
+    /// The name of the file, without its directory.
+    pub name: String,
+
+    /// The contents of the file.
+    pub data: Vec<u8>,
+}
+
+/// An uncaught error thrown by ActionScript code.
+/// Communicated from the core to the UI backend via `UiBackend::display_script_error`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScriptError {
+    /// The error message, e.g. the stringified value of a `throw` statement.
+    pub message: String,
+
+    /// The call stack at the time of the error, outermost frame first.
+    /// This may be empty if the VM does not track stack information.
+    pub stack_trace: Vec<String>,
+
+    /// The URL of the movie that was running, if known.
+    pub movie_url: Option<String>,
+}
+
+impl std::fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)?;
+        for frame in self.stack_trace.iter().rev() {
+            write!(f, "\n\tat {}", frame)?;
+        }
+        Ok(())
+    }
+}
+
+/// The user's choice after being shown a `ScriptError`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptErrorResponse {
+    /// Dismiss this error, and show any future errors as well.
+    Continue,
+
+    /// Dismiss this error, and only log any further errors.
+    /// Equivalent to "Dismiss All" in the debug player.
+    SuppressFurtherErrors,
+}
+
 /// UiBackend that does nothing.
 pub struct NullUiBackend {}
 
@@ -79,13 +273,11 @@ impl UiBackend for NullUiBackend {
 
     fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
 
-    fn set_clipboard_content(&mut self, _content: String) {}
-
     fn is_fullscreen(&self) -> bool {
         false
     }
 
-    fn display_unsupported_message(&self) {}
+    fn display_unsupported_message(&self, _messages: &dyn MessageCatalog) {}
 
     fn message(&self, _message: &str) {}
 }
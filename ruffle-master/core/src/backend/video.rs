@@ -78,6 +78,10 @@ pub trait VideoBackend {
         filter: VideoDeblocking,
     ) -> Result<VideoStreamHandle, Error>;
 
+    /// Discard a video stream that will no longer be decoded, freeing its
+    /// decoder state.
+    fn close_video_stream(&mut self, stream: VideoStreamHandle);
+
     /// Preload a frame of a given video stream.
     ///
     /// No decoding is intended to happen at this point in time. Instead, the
@@ -153,6 +157,10 @@ impl VideoBackend for NullVideoBackend {
         Ok(self.streams.insert(()))
     }
 
+    fn close_video_stream(&mut self, stream: VideoStreamHandle) {
+        self.streams.remove(stream);
+    }
+
     fn preload_video_stream_frame(
         &mut self,
         _stream: VideoStreamHandle,
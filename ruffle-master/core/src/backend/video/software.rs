@@ -7,8 +7,43 @@ use crate::backend::video::{
 use generational_arena::Arena;
 use swf::{VideoCodec, VideoDeblocking};
 
+#[cfg(feature = "h263")]
+mod h263;
+
+#[cfg(feature = "vp6")]
+mod vp6;
+
+/// A decoder for a single video stream.
+///
+/// Decoders own whatever reference frames their codec requires, and are fed
+/// every frame of the stream in decoding order.
+pub trait VideoDecoder {
+    /// Determine what a frame depends on, without decoding it.
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error>;
+
+    /// Decode a frame into RGBA pixels.
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error>;
+}
+
+/// A decoded video frame, in premultiplied RGBA as expected by the render
+/// backend.
+pub struct DecodedFrame {
+    pub width: u16,
+    pub height: u16,
+    pub rgba: Vec<u8>,
+}
+
 /// A single preloaded video stream.
-pub enum VideoStream {}
+pub struct VideoStream {
+    /// The decoder for this stream's codec.
+    decoder: Box<dyn VideoDecoder>,
+
+    /// The bitmap that decoded frames are uploaded into.
+    ///
+    /// This is allocated when the first frame is decoded and reused for every
+    /// subsequent frame of the same size.
+    bitmap: Option<BitmapInfo>,
+}
 
 /// Software video backend that proxies to CPU-only codec implementations that
 /// ship with Ruffle.
@@ -31,40 +66,75 @@ impl SoftwareVideoBackend {
 }
 
 impl VideoBackend for SoftwareVideoBackend {
+    #[cfg_attr(not(any(feature = "h263", feature = "vp6")), allow(unreachable_code))]
+    #[cfg_attr(not(feature = "vp6"), allow(unused_variables))]
     fn register_video_stream(
         &mut self,
         _num_frames: u32,
-        _size: (u16, u16),
+        size: (u16, u16),
         codec: VideoCodec,
         _filter: VideoDeblocking,
     ) -> Result<VideoStreamHandle, Error> {
-        Err(format!("Unsupported video codec type {:?}", codec).into())
+        let decoder: Box<dyn VideoDecoder> = match codec {
+            #[cfg(feature = "h263")]
+            VideoCodec::H263 => Box::new(h263::H263Decoder::new()),
+            #[cfg(feature = "vp6")]
+            VideoCodec::Vp6 => Box::new(vp6::Vp6Decoder::new(false, size)),
+            #[cfg(feature = "vp6")]
+            VideoCodec::Vp6WithAlpha => Box::new(vp6::Vp6Decoder::new(true, size)),
+            _ => return Err(format!("Unsupported video codec type {:?}", codec).into()),
+        };
+
+        Ok(self.streams.insert(VideoStream {
+            decoder,
+            bitmap: None,
+        }))
+    }
+
+    fn close_video_stream(&mut self, stream: VideoStreamHandle) {
+        self.streams.remove(stream);
     }
 
     fn preload_video_stream_frame(
         &mut self,
         stream: VideoStreamHandle,
-        _encoded_frame: EncodedFrame<'_>,
+        encoded_frame: EncodedFrame<'_>,
     ) -> Result<FrameDependency, Error> {
-        let _stream = self
+        let stream = self
             .streams
             .get_mut(stream)
             .ok_or("Unregistered video stream")?;
 
-        unreachable!()
+        stream.decoder.preload_frame(encoded_frame)
     }
 
     fn decode_video_stream_frame(
         &mut self,
         stream: VideoStreamHandle,
-        _encoded_frame: EncodedFrame<'_>,
-        _renderer: &mut dyn RenderBackend,
+        encoded_frame: EncodedFrame<'_>,
+        renderer: &mut dyn RenderBackend,
     ) -> Result<BitmapInfo, Error> {
-        let _stream = self
+        let stream = self
             .streams
             .get_mut(stream)
             .ok_or("Unregistered video stream")?;
 
-        unreachable!()
+        let frame = stream.decoder.decode_frame(encoded_frame)?;
+        let width = frame.width.into();
+        let height = frame.height.into();
+        let handle = match stream.bitmap {
+            Some(bitmap) if bitmap.width == frame.width && bitmap.height == frame.height => {
+                renderer.update_texture(bitmap.handle, width, height, frame.rgba)?
+            }
+            _ => renderer.register_bitmap_raw(width, height, frame.rgba)?,
+        };
+        let bitmap = BitmapInfo {
+            handle,
+            width: frame.width,
+            height: frame.height,
+        };
+        stream.bitmap = Some(bitmap);
+
+        Ok(bitmap)
     }
 }
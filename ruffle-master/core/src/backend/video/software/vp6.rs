@@ -0,0 +1,208 @@
+//! On2 VP6 video decoding.
+
+use crate::backend::render::premultiply_alpha_rgba;
+use crate::backend::video::software::{DecodedFrame, VideoDecoder};
+use crate::backend::video::{EncodedFrame, Error, FrameDependency};
+use h263_rs_yuv::bt601::yuv420_to_rgba;
+use nihav_core::codecs::NADecoderSupport;
+use nihav_core::formats::YUV420_FORMAT;
+use nihav_core::frame::{NAVideoBuffer, NAVideoInfo};
+use nihav_duck::codecs::vp56::{VP56Decoder, VP_YUVA420_FORMAT};
+use nihav_duck::codecs::vp6::VP6BR;
+
+/// VP6 video decoder.
+///
+/// Streams with alpha carry a second VP6 bitstream per frame that encodes the
+/// alpha channel as the luma plane of an ordinary picture.
+pub struct Vp6Decoder {
+    with_alpha: bool,
+
+    /// The size of the video as declared by the stream definition. Decoded
+    /// frames are padded to whole macroblocks and cropped back to this size.
+    bounds: (u16, u16),
+
+    decoder: VP56Decoder,
+    support: NADecoderSupport,
+    bitreader: VP6BR,
+
+    /// Whether the decoder has been initialized from the first keyframe.
+    initialized: bool,
+}
+
+impl Vp6Decoder {
+    pub fn new(with_alpha: bool, bounds: (u16, u16)) -> Self {
+        Self {
+            with_alpha,
+            bounds,
+            // Flash stores VP6 pictures bottom-up.
+            decoder: VP56Decoder::new(6, with_alpha, true),
+            support: NADecoderSupport::new(),
+            bitreader: VP6BR::new(),
+            initialized: false,
+        }
+    }
+
+    /// The VP6 bitstream of the color planes of a frame.
+    fn color_data<'a>(&self, data: &'a [u8]) -> Result<&'a [u8], Error> {
+        if !self.with_alpha {
+            return Ok(data);
+        }
+
+        // Frames with alpha start with a 24-bit offset to the alpha bitstream.
+        if data.len() < 3 {
+            return Err("VP6 frame is too short".into());
+        }
+        let alpha_offset =
+            (usize::from(data[0]) << 16) | (usize::from(data[1]) << 8) | usize::from(data[2]);
+        data.get(3..3 + alpha_offset)
+            .ok_or_else(|| "VP6 alpha offset is out of bounds".into())
+    }
+
+    /// Initialize the decoder with the picture size given by a keyframe.
+    fn init(&mut self, data: &[u8]) -> Result<(), Error> {
+        let (width, height) = keyframe_dimensions(data)?;
+        let format = if self.with_alpha {
+            VP_YUVA420_FORMAT
+        } else {
+            YUV420_FORMAT
+        };
+        let info = NAVideoInfo::new(width, height, true, format);
+        self.decoder
+            .init(&mut self.support, info)
+            .map_err(|e| format!("Could not initialize VP6 decoder: {:?}", e))?;
+        self.initialized = true;
+        Ok(())
+    }
+}
+
+impl VideoDecoder for Vp6Decoder {
+    fn preload_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<FrameDependency, Error> {
+        let data = self.color_data(encoded_frame.data())?;
+        if is_keyframe(data)? {
+            Ok(FrameDependency::None)
+        } else {
+            Ok(FrameDependency::Past)
+        }
+    }
+
+    fn decode_frame(&mut self, encoded_frame: EncodedFrame<'_>) -> Result<DecodedFrame, Error> {
+        if !self.initialized {
+            let data = self.color_data(encoded_frame.data())?;
+            if !is_keyframe(data)? {
+                return Err("VP6 stream does not start with a keyframe".into());
+            }
+            self.init(data)?;
+        }
+
+        // The decoder splits off the alpha bitstream itself.
+        let (frame, _) = self
+            .decoder
+            .decode_frame(&mut self.support, encoded_frame.data(), &mut self.bitreader)
+            .map_err(|e| format!("Could not decode VP6 frame: {:?}", e))?;
+        let picture = frame.get_vbuf().ok_or("VP6 frame is not a YUV picture")?;
+
+        Ok(picture_to_rgba(&picture, self.with_alpha, self.bounds))
+    }
+}
+
+/// Whether a VP6 frame is a keyframe.
+fn is_keyframe(data: &[u8]) -> Result<bool, Error> {
+    let first_byte = data.first().ok_or("VP6 frame is empty")?;
+    Ok(first_byte & 0x80 == 0)
+}
+
+/// Read the size of the coded picture from a keyframe header.
+///
+/// The picture is always a whole number of 16x16 macroblocks.
+fn keyframe_dimensions(data: &[u8]) -> Result<(usize, usize), Error> {
+    if data.len() < 2 {
+        return Err("VP6 keyframe header is too short".into());
+    }
+
+    // A 16-bit offset to the coefficient partition is present if the
+    // partitions are separated, or if the frame uses the simple profile.
+    let separated_coefficients = data[0] & 0x01 != 0;
+    let simple_profile = data[1] & 0x06 == 0;
+    let offset = if separated_coefficients || simple_profile {
+        4
+    } else {
+        2
+    };
+
+    match data.get(offset..offset + 2) {
+        Some(&[rows, cols]) if rows > 0 && cols > 0 => {
+            Ok((usize::from(cols) * 16, usize::from(rows) * 16))
+        }
+        _ => Err("Invalid VP6 keyframe dimensions".into()),
+    }
+}
+
+/// Convert a decoded picture into RGBA, cropped to `bounds`.
+fn picture_to_rgba(
+    picture: &NAVideoBuffer<u8>,
+    with_alpha: bool,
+    bounds: (u16, u16),
+) -> DecodedFrame {
+    let (coded_width, coded_height) = picture.get_dimensions(0);
+    let width = coded_width.min(bounds.0.into());
+    let height = coded_height.min(bounds.1.into());
+    let chroma_width = (width + 1) / 2;
+    let chroma_height = (height + 1) / 2;
+
+    let y = copy_plane(picture, 0, width, height);
+    let u = copy_plane(picture, 1, chroma_width, chroma_height);
+    let v = copy_plane(picture, 2, chroma_width, chroma_height);
+    let mut rgba = yuv420_to_rgba(&y, &u, &v, width, chroma_width);
+
+    if with_alpha {
+        // The color planes are not premultiplied by the alpha plane, but the
+        // render backends expect bitmaps that are.
+        let alpha = copy_plane(picture, 3, width, height);
+        for (pixel, alpha) in rgba.chunks_exact_mut(4).zip(alpha) {
+            pixel[3] = alpha;
+        }
+        premultiply_alpha_rgba(&mut rgba);
+    }
+
+    DecodedFrame {
+        width: width as u16,
+        height: height as u16,
+        rgba,
+    }
+}
+
+/// Copy the top-left `width` by `height` samples of a plane into a tightly
+/// packed buffer.
+fn copy_plane(picture: &NAVideoBuffer<u8>, plane: usize, width: usize, height: usize) -> Vec<u8> {
+    let data = picture.get_data();
+    let offset = picture.get_offset(plane);
+    let stride = picture.get_stride(plane);
+
+    let mut samples = Vec::with_capacity(width * height);
+    for row in 0..height {
+        let start = offset + row * stride;
+        samples.extend_from_slice(&data[start..start + width]);
+    }
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyframe_header() {
+        // Simple profile keyframe with a coefficient partition offset.
+        let simple = [0x00, 0x30, 0x00, 0x00, 0x04, 0x05, 0x04, 0x05];
+        assert!(is_keyframe(&simple).unwrap());
+        assert_eq!(keyframe_dimensions(&simple).unwrap(), (80, 64));
+
+        // Advanced profile keyframe without one.
+        let advanced = [0x00, 0x36, 0x0a, 0x0b, 0x0a, 0x0b];
+        assert_eq!(keyframe_dimensions(&advanced).unwrap(), (176, 160));
+
+        // Interframes have the high bit set.
+        assert!(!is_keyframe(&[0x80, 0x00]).unwrap());
+        assert!(keyframe_dimensions(&[0x00]).is_err());
+    }
+}
@@ -1 +1,4 @@
+pub mod bitmap_data;
+pub mod filters;
+pub mod rasterizer;
 pub mod turbulence;
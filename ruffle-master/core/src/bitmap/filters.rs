@@ -0,0 +1,839 @@
+//! Display object filters
+//!
+//! Filters are evaluated on the CPU: a filtered object is rasterized into an
+//! offscreen `BitmapData`, which each of its filters transforms in turn
+//! before the result is handed to the renderer.
+//!
+//! Pixels are processed as premultiplied `[r, g, b, a]` values in the range
+//! `0.0..=1.0`. Like Flash, blurs approximate a Gaussian blur with a box
+//! blur as wide as the blur amount, repeated once per quality pass.
+
+use crate::bitmap::bitmap_data::{BitmapData, Color};
+use swf::{Filter, GradientRecord};
+
+/// The widest box, in pixels, that a single blur pass may average over.
+const MAX_BLUR: f64 = 255.0;
+
+/// The furthest, in pixels, that a filter may offset its effect.
+///
+/// Filtered objects are never drawn larger than this, so anything further is
+/// out of sight anyway.
+const MAX_DISTANCE: f64 = 2880.0;
+
+/// A premultiplied pixel, as `[r, g, b, a]`.
+type Pixel = [f32; 4];
+
+/// How far a filter may spread its source beyond its edges, in pixels, as
+/// `(x, y)`.
+///
+/// Offscreen bitmaps must be padded by this much for the whole effect to be
+/// visible.
+pub fn filter_padding(filter: &Filter) -> (u32, u32) {
+    let (blur_x, blur_y, passes, distance) = match filter {
+        Filter::BlurFilter(filter) => (filter.blur_x, filter.blur_y, filter.num_passes, 0.0),
+        Filter::GlowFilter(filter) => (filter.blur_x, filter.blur_y, filter.num_passes, 0.0),
+        Filter::DropShadowFilter(filter) => (
+            filter.blur_x,
+            filter.blur_y,
+            filter.num_passes,
+            filter.distance,
+        ),
+        Filter::BevelFilter(filter) => (
+            filter.blur_x,
+            filter.blur_y,
+            filter.num_passes,
+            filter.distance,
+        ),
+        Filter::GradientGlowFilter(filter) => (
+            filter.blur_x,
+            filter.blur_y,
+            filter.num_passes,
+            filter.distance,
+        ),
+        Filter::GradientBevelFilter(filter) => (
+            filter.blur_x,
+            filter.blur_y,
+            filter.num_passes,
+            filter.distance,
+        ),
+        Filter::ConvolutionFilter(filter) => {
+            return (
+                u32::from(filter.num_matrix_cols) / 2,
+                u32::from(filter.num_matrix_rows) / 2,
+            )
+        }
+        Filter::ColorMatrixFilter(_) => return (0, 0),
+    };
+
+    let distance = clamp_distance(distance).abs().ceil() as u32;
+    let passes = u32::from(passes);
+    (
+        (blur_radius(blur_x) as u32)
+            .saturating_mul(passes)
+            .saturating_add(distance),
+        (blur_radius(blur_y) as u32)
+            .saturating_mul(passes)
+            .saturating_add(distance),
+    )
+}
+
+/// Whether a filter only ever draws underneath its source, leaving the
+/// source itself unchanged.
+///
+/// The output of such filters can be split into the source and an effect
+/// behind it with `remove_source`.
+pub fn preserves_source(filter: &Filter) -> bool {
+    match filter {
+        Filter::GlowFilter(filter) => !filter.is_inner && !filter.is_knockout,
+        Filter::DropShadowFilter(filter) => !filter.is_inner && !filter.is_knockout,
+        Filter::BevelFilter(filter) => !filter.is_inner && !filter.is_knockout && !filter.is_on_top,
+        Filter::GradientGlowFilter(filter) => {
+            !filter.is_inner && !filter.is_knockout && !filter.is_on_top
+        }
+        Filter::GradientBevelFilter(filter) => {
+            !filter.is_inner && !filter.is_knockout && !filter.is_on_top
+        }
+        _ => false,
+    }
+}
+
+/// Apply a filter to a bitmap, returning a new bitmap of the same size.
+pub fn apply_filter(source: &BitmapData, filter: &Filter) -> BitmapData {
+    let width = source.width() as usize;
+    let height = source.height() as usize;
+    let mut pixels = unpack(source);
+
+    match filter {
+        Filter::BlurFilter(filter) => {
+            for channel in 0..4 {
+                let mut values: Vec<f32> = pixels.iter().map(|pixel| pixel[channel]).collect();
+                blur(
+                    &mut values,
+                    width,
+                    height,
+                    (filter.blur_x, filter.blur_y),
+                    filter.num_passes,
+                );
+                for (pixel, value) in pixels.iter_mut().zip(values) {
+                    pixel[channel] = value;
+                }
+            }
+        }
+        Filter::GlowFilter(filter) => {
+            let shadow = shadow_alpha(
+                &pixels,
+                width,
+                height,
+                (0, 0),
+                (filter.blur_x, filter.blur_y),
+                filter.num_passes,
+                filter.strength,
+                filter.is_inner,
+            );
+            let color = premultiply(&filter.color);
+            composite(
+                &mut pixels,
+                |i| scale(color, shadow[i]),
+                filter.is_inner,
+                filter.is_knockout,
+                false,
+            );
+        }
+        Filter::DropShadowFilter(filter) => {
+            let shadow = shadow_alpha(
+                &pixels,
+                width,
+                height,
+                offset(filter.angle, filter.distance),
+                (filter.blur_x, filter.blur_y),
+                filter.num_passes,
+                filter.strength,
+                filter.is_inner,
+            );
+            let color = premultiply(&filter.color);
+            composite(
+                &mut pixels,
+                |i| scale(color, shadow[i]),
+                filter.is_inner,
+                filter.is_knockout,
+                false,
+            );
+        }
+        Filter::GradientGlowFilter(filter) => {
+            let shadow = shadow_alpha(
+                &pixels,
+                width,
+                height,
+                offset(filter.angle, filter.distance),
+                (filter.blur_x, filter.blur_y),
+                filter.num_passes,
+                filter.strength,
+                filter.is_inner,
+            );
+            composite(
+                &mut pixels,
+                |i| gradient_color(&filter.colors, shadow[i]),
+                filter.is_inner,
+                filter.is_knockout,
+                filter.is_on_top,
+            );
+        }
+        Filter::BevelFilter(filter) => {
+            let (highlight, shadow) = bevel_alpha(
+                &pixels,
+                width,
+                height,
+                offset(filter.angle, filter.distance / 2.0),
+                (filter.blur_x, filter.blur_y),
+                filter.num_passes,
+                filter.strength,
+            );
+            let highlight_color = premultiply(&filter.highlight_color);
+            let shadow_color = premultiply(&filter.shadow_color);
+            composite(
+                &mut pixels,
+                |i| {
+                    over(
+                        scale(highlight_color, highlight[i]),
+                        scale(shadow_color, shadow[i]),
+                    )
+                },
+                filter.is_inner,
+                filter.is_knockout,
+                filter.is_on_top,
+            );
+        }
+        Filter::GradientBevelFilter(filter) => {
+            let (highlight, shadow) = bevel_alpha(
+                &pixels,
+                width,
+                height,
+                offset(filter.angle, filter.distance / 2.0),
+                (filter.blur_x, filter.blur_y),
+                filter.num_passes,
+                filter.strength,
+            );
+            // The gradient runs from the highlight, through its midpoint where
+            // there is no bevel, to the shadow.
+            composite(
+                &mut pixels,
+                |i| gradient_color(&filter.colors, 0.5 + (shadow[i] - highlight[i]) / 2.0),
+                filter.is_inner,
+                filter.is_knockout,
+                filter.is_on_top,
+            );
+        }
+        Filter::ColorMatrixFilter(filter) => {
+            let m = filter.matrix.map(|value| value as f32);
+            for pixel in pixels.iter_mut() {
+                let [r, g, b, a] = unpremultiply(*pixel);
+                let row = |i: usize| {
+                    m[i] * r + m[i + 1] * g + m[i + 2] * b + m[i + 3] * a + m[i + 4] / 255.0
+                };
+                *pixel = premultiply_values([row(0), row(5), row(10), row(15)]);
+            }
+        }
+        Filter::ConvolutionFilter(filter) => {
+            let source: Vec<Pixel> = pixels.iter().map(|pixel| unpremultiply(*pixel)).collect();
+            let default_color = [
+                f32::from(filter.default_color.r) / 255.0,
+                f32::from(filter.default_color.g) / 255.0,
+                f32::from(filter.default_color.b) / 255.0,
+                f32::from(filter.default_color.a) / 255.0,
+            ];
+            let columns = usize::from(filter.num_matrix_cols);
+            let rows = usize::from(filter.num_matrix_rows);
+            let divisor = if filter.divisor == 0.0 {
+                1.0
+            } else {
+                filter.divisor as f32
+            };
+            let bias = filter.bias as f32 / 255.0;
+
+            for y in 0..height {
+                for x in 0..width {
+                    let mut sum = [0.0; 4];
+                    for row in 0..rows {
+                        for column in 0..columns {
+                            let weight = filter
+                                .matrix
+                                .get(row * columns + column)
+                                .copied()
+                                .unwrap_or_default()
+                                as f32;
+                            let sample_x = x as isize + column as isize - (columns / 2) as isize;
+                            let sample_y = y as isize + row as isize - (rows / 2) as isize;
+                            let sample = if filter.is_clamped {
+                                let sample_x = sample_x.max(0).min(width as isize - 1);
+                                let sample_y = sample_y.max(0).min(height as isize - 1);
+                                source[sample_y as usize * width + sample_x as usize]
+                            } else {
+                                sample_at(&source, width, height, sample_x, sample_y)
+                                    .unwrap_or(default_color)
+                            };
+                            for (total, value) in sum.iter_mut().zip(&sample) {
+                                *total += value * weight;
+                            }
+                        }
+                    }
+
+                    let index = y * width + x;
+                    let mut result = sum.map(|value| value / divisor + bias);
+                    if filter.is_preserve_alpha {
+                        result[3] = source[index][3];
+                    }
+                    pixels[index] = premultiply_values(result);
+                }
+            }
+        }
+    }
+
+    pack(source.width(), source.height(), &pixels)
+}
+
+/// Take the source out of the output of filters that only draw behind it,
+/// leaving just the effect.
+///
+/// Drawing the source back over the result reproduces the filtered output,
+/// which lets the source be rendered as normal instead of from a bitmap.
+pub fn remove_source(filtered: &BitmapData, source: &BitmapData) -> BitmapData {
+    let filtered_pixels = unpack(filtered);
+    let source_pixels = unpack(source);
+    let pixels: Vec<Pixel> = filtered_pixels
+        .iter()
+        .zip(&source_pixels)
+        .map(|(filtered, source)| {
+            let coverage = 1.0 - source[3];
+            if coverage <= 0.0 {
+                [0.0; 4]
+            } else {
+                let mut effect = [0.0; 4];
+                for (channel, value) in effect.iter_mut().enumerate() {
+                    *value = (filtered[channel] - source[channel]) / coverage;
+                }
+                effect
+            }
+        })
+        .collect();
+
+    pack(filtered.width(), filtered.height(), &pixels)
+}
+
+/// Hide a bitmap by the alpha of a soft mask of the same size, as Flash does
+/// for masks that are cached as bitmaps or have filters.
+///
+/// Only the alpha of the mask matters: where it is opaque the source shows
+/// through as is, and where it is transparent the source is hidden.
+pub fn apply_mask(source: &BitmapData, mask: &BitmapData) -> BitmapData {
+    let pixels: Vec<Pixel> = unpack(source)
+        .into_iter()
+        .zip(unpack(mask))
+        .map(|(pixel, mask)| scale(pixel, mask[3]))
+        .collect();
+
+    pack(source.width(), source.height(), &pixels)
+}
+
+/// The width of the box averaged over by each pass of a blur.
+fn blur_size(blur: f64) -> f32 {
+    blur.clamp(0.0, MAX_BLUR) as f32
+}
+
+/// The number of pixels a blur spreads by in each direction per pass.
+fn blur_radius(blur: f64) -> usize {
+    ((blur_size(blur) - 1.0) / 2.0).max(0.0).ceil() as usize
+}
+
+/// Limit a (possibly negative) filter distance to `MAX_DISTANCE`, treating
+/// NaN as no distance at all.
+fn clamp_distance(distance: f64) -> f64 {
+    if distance.is_nan() {
+        0.0
+    } else {
+        distance.clamp(-MAX_DISTANCE, MAX_DISTANCE)
+    }
+}
+
+/// The offset of a shadow cast at `angle` radians, in whole pixels.
+fn offset(angle: f64, distance: f64) -> (isize, isize) {
+    let distance = clamp_distance(distance);
+    (
+        (angle.cos() * distance).round() as isize,
+        (angle.sin() * distance).round() as isize,
+    )
+}
+
+/// Box blur a single channel of an image, once per pass.
+fn blur(channel: &mut [f32], width: usize, height: usize, blur: (f64, f64), passes: u8) {
+    let size_x = blur_size(blur.0);
+    let size_y = blur_size(blur.1);
+    for _ in 0..passes {
+        box_blur_axis(channel, height, width, width, 1, size_x);
+        box_blur_axis(channel, width, height, 1, width, size_y);
+    }
+}
+
+/// Box blur a single channel along one axis.
+///
+/// The channel is made up of `lines` runs of `len` samples each; sample `i`
+/// of line `l` is found at `l * line_step + i * sample_step`. Samples past
+/// the ends of a line are treated as zero.
+///
+/// The box is `size` samples wide. When that doesn't cover a whole number of
+/// samples on each side of the center, the two outermost samples are only
+/// partially weighted.
+fn box_blur_axis(
+    channel: &mut [f32],
+    lines: usize,
+    len: usize,
+    line_step: usize,
+    sample_step: usize,
+    size: f32,
+) {
+    if size <= 1.0 || len == 0 {
+        return;
+    }
+
+    let radius = (size - 1.0) / 2.0;
+    let whole = radius.floor() as usize;
+    let fraction = radius.fract();
+    let mut line = vec![0.0; len];
+    for l in 0..lines {
+        let start = l * line_step;
+        for (i, sample) in line.iter_mut().enumerate() {
+            *sample = channel[start + i * sample_step];
+        }
+
+        // `sum` covers the samples within `whole` of `i`.
+        let mut sum: f32 = line.iter().take(whole + 1).sum();
+        for i in 0..len {
+            let before = i.checked_sub(whole + 1).map_or(0.0, |j| line[j]);
+            let after = line.get(i + whole + 1).copied().unwrap_or(0.0);
+            channel[start + i * sample_step] = (sum + fraction * (before + after)) / size;
+            sum += after;
+            if i >= whole {
+                sum -= line[i - whole];
+            }
+        }
+    }
+}
+
+/// Compute the strength of a shadow at each pixel: the alpha of the source,
+/// moved by `offset`, blurred and scaled by `strength`.
+///
+/// Inner shadows are cast by the transparent areas of the source instead.
+#[allow(clippy::too_many_arguments)]
+fn shadow_alpha(
+    pixels: &[Pixel],
+    width: usize,
+    height: usize,
+    offset: (isize, isize),
+    blur_amount: (f64, f64),
+    passes: u8,
+    strength: f32,
+    inner: bool,
+) -> Vec<f32> {
+    let mut alpha = Vec::with_capacity(pixels.len());
+    for y in 0..height {
+        for x in 0..width {
+            let source = sample_at(
+                pixels,
+                width,
+                height,
+                x as isize - offset.0,
+                y as isize - offset.1,
+            )
+            .map(|pixel| pixel[3])
+            .unwrap_or_default();
+            alpha.push(if inner { 1.0 - source } else { source });
+        }
+    }
+
+    blur(&mut alpha, width, height, blur_amount, passes);
+    for value in alpha.iter_mut() {
+        *value = (*value * strength).min(1.0);
+    }
+    alpha
+}
+
+/// Compute the strengths of the highlight and shadow of a bevel at each
+/// pixel, from the difference of the blurred source alpha either side of it.
+fn bevel_alpha(
+    pixels: &[Pixel],
+    width: usize,
+    height: usize,
+    offset: (isize, isize),
+    blur_amount: (f64, f64),
+    passes: u8,
+    strength: f32,
+) -> (Vec<f32>, Vec<f32>) {
+    let mut alpha: Vec<f32> = pixels.iter().map(|pixel| pixel[3]).collect();
+    blur(&mut alpha, width, height, blur_amount, passes);
+
+    let mut highlight = Vec::with_capacity(pixels.len());
+    let mut shadow = Vec::with_capacity(pixels.len());
+    for y in 0..height as isize {
+        for x in 0..width as isize {
+            let sample = |x: isize, y: isize| {
+                if x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height {
+                    alpha[y as usize * width + x as usize]
+                } else {
+                    0.0
+                }
+            };
+            let ahead = sample(x + offset.0, y + offset.1);
+            let behind = sample(x - offset.0, y - offset.1);
+            highlight.push(((ahead - behind) * strength).clamp(0.0, 1.0));
+            shadow.push(((behind - ahead) * strength).clamp(0.0, 1.0));
+        }
+    }
+    (highlight, shadow)
+}
+
+/// Combine an effect with its source.
+///
+/// `effect` gives the premultiplied color of the effect at each pixel.
+/// Inner effects are clipped to the source, while outer ones are drawn behind
+/// it, or over it if `on_top` is set. Knockout removes the source, leaving
+/// only the effect.
+fn composite(
+    pixels: &mut [Pixel],
+    effect: impl Fn(usize) -> Pixel,
+    inner: bool,
+    knockout: bool,
+    on_top: bool,
+) {
+    for (i, pixel) in pixels.iter_mut().enumerate() {
+        let source = *pixel;
+        let effect = effect(i);
+        *pixel = match (inner, knockout) {
+            (true, true) => scale(effect, source[3]),
+            (true, false) => {
+                let mut result = [0.0; 4];
+                for channel in 0..3 {
+                    result[channel] =
+                        effect[channel] * source[3] + source[channel] * (1.0 - effect[3]);
+                }
+                result[3] = source[3];
+                result
+            }
+            (false, true) => scale(effect, 1.0 - source[3]),
+            (false, false) if on_top => over(effect, source),
+            (false, false) => over(source, effect),
+        };
+    }
+}
+
+/// Draw one premultiplied pixel over another.
+fn over(top: Pixel, bottom: Pixel) -> Pixel {
+    let remaining = 1.0 - top[3];
+    [
+        top[0] + bottom[0] * remaining,
+        top[1] + bottom[1] * remaining,
+        top[2] + bottom[2] * remaining,
+        top[3] + bottom[3] * remaining,
+    ]
+}
+
+/// Scale the opacity of a premultiplied pixel.
+fn scale(pixel: Pixel, amount: f32) -> Pixel {
+    pixel.map(|value| value * amount)
+}
+
+/// Look up the pixel at a position, if it is within the image.
+fn sample_at(pixels: &[Pixel], width: usize, height: usize, x: isize, y: isize) -> Option<Pixel> {
+    if x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height {
+        Some(pixels[y as usize * width + x as usize])
+    } else {
+        None
+    }
+}
+
+/// The premultiplied color of a gradient at a position from `0.0` to `1.0`.
+fn gradient_color(records: &[GradientRecord], position: f32) -> Pixel {
+    let ratio = position.clamp(0.0, 1.0) * 255.0;
+    let (first, last) = match (records.first(), records.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return [0.0; 4],
+    };
+    if ratio <= f32::from(first.ratio) {
+        return premultiply(&first.color);
+    }
+
+    for pair in records.windows(2) {
+        let (start, end) = (&pair[0], &pair[1]);
+        if ratio <= f32::from(end.ratio) {
+            let span = f32::from(end.ratio) - f32::from(start.ratio);
+            let t = if span > 0.0 {
+                (ratio - f32::from(start.ratio)) / span
+            } else {
+                1.0
+            };
+            let start = premultiply(&start.color);
+            let end = premultiply(&end.color);
+            let mut result = [0.0; 4];
+            for (channel, value) in result.iter_mut().enumerate() {
+                *value = start[channel] + (end[channel] - start[channel]) * t;
+            }
+            return result;
+        }
+    }
+
+    premultiply(&last.color)
+}
+
+/// Convert an unmultiplied color into a premultiplied pixel.
+fn premultiply(color: &swf::Color) -> Pixel {
+    premultiply_values([
+        f32::from(color.r) / 255.0,
+        f32::from(color.g) / 255.0,
+        f32::from(color.b) / 255.0,
+        f32::from(color.a) / 255.0,
+    ])
+}
+
+/// Premultiply unmultiplied `[r, g, b, a]` values, clamping them to range.
+fn premultiply_values(values: [f32; 4]) -> Pixel {
+    let [r, g, b, a] = values.map(|value| value.clamp(0.0, 1.0));
+    [r * a, g * a, b * a, a]
+}
+
+/// Convert a premultiplied pixel into unmultiplied `[r, g, b, a]` values.
+fn unpremultiply(pixel: Pixel) -> [f32; 4] {
+    let a = pixel[3];
+    if a <= 0.0 {
+        [0.0; 4]
+    } else {
+        [pixel[0] / a, pixel[1] / a, pixel[2] / a, a]
+    }
+}
+
+/// Read the pixels of a bitmap.
+fn unpack(bitmap: &BitmapData) -> Vec<Pixel> {
+    let transparency = bitmap.transparency();
+    bitmap
+        .pixels()
+        .iter()
+        .map(|color| {
+            let alpha = if transparency { color.alpha() } else { 255 };
+            [
+                f32::from(color.red()) / 255.0,
+                f32::from(color.green()) / 255.0,
+                f32::from(color.blue()) / 255.0,
+                f32::from(alpha) / 255.0,
+            ]
+        })
+        .collect()
+}
+
+/// Build a transparent bitmap from pixels.
+fn pack(width: u32, height: u32, pixels: &[Pixel]) -> BitmapData {
+    let to_byte = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let mut bitmap = BitmapData::default();
+    bitmap.init_pixels(width, height, 0, true);
+    bitmap.set_pixels(
+        pixels
+            .iter()
+            .map(|pixel| {
+                let alpha = to_byte(pixel[3]);
+                Color::argb(
+                    alpha,
+                    to_byte(pixel[0]).min(alpha),
+                    to_byte(pixel[1]).min(alpha),
+                    to_byte(pixel[2]).min(alpha),
+                )
+            })
+            .collect(),
+    );
+    bitmap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A transparent bitmap with a single opaque white pixel.
+    fn dot(size: u32, x: u32, y: u32) -> BitmapData {
+        let mut bitmap = BitmapData::default();
+        bitmap.init_pixels(size, size, 0, true);
+        bitmap.set_pixel32_raw(x, y, Color::argb(255, 255, 255, 255));
+        bitmap
+    }
+
+    fn alpha_at(bitmap: &BitmapData, x: u32, y: u32) -> u8 {
+        bitmap.get_pixel_raw(x, y).unwrap().alpha()
+    }
+
+    #[test]
+    fn blur_spreads_pixels() {
+        let filter = Filter::BlurFilter(Box::new(swf::BlurFilter {
+            blur_x: 2.0,
+            blur_y: 2.0,
+            num_passes: 1,
+        }));
+        let result = apply_filter(&dot(5, 2, 2), &filter);
+        assert_eq!(alpha_at(&result, 2, 2), 64);
+        assert_eq!(alpha_at(&result, 1, 1), 16);
+        assert_eq!(alpha_at(&result, 0, 0), 0);
+        assert_eq!(filter_padding(&filter), (1, 1));
+    }
+
+    #[test]
+    fn bitmap_data_filters_a_rectangle() {
+        let filter = Filter::BlurFilter(Box::new(swf::BlurFilter {
+            blur_x: 2.0,
+            blur_y: 2.0,
+            num_passes: 1,
+        }));
+        let mut source = BitmapData::default();
+        source.init_pixels(7, 7, 0, true);
+        source.copy_pixels(&dot(5, 2, 2), (0, 0, 5, 5), (1, 1), None);
+
+        let mut target = BitmapData::default();
+        target.init_pixels(8, 8, 0, true);
+        target.apply_filter(&source, (1, 1, 5, 5), (3, 3), &filter);
+
+        // The rectangle is filtered just as a bitmap of its own would be,
+        // and lands at the destination point.
+        let expected = apply_filter(&dot(5, 2, 2), &filter);
+        for y in 0..8 {
+            for x in 0..8 {
+                let pixel = if (3..8).contains(&x) && (3..8).contains(&y) {
+                    expected.get_pixel_raw(x - 3, y - 3)
+                } else {
+                    Some(Color::argb(0, 0, 0, 0))
+                };
+                assert_eq!(target.get_pixel_raw(x, y), pixel, "pixel {}, {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn blur_passes_repeat_box() {
+        let filter = Filter::BlurFilter(Box::new(swf::BlurFilter {
+            blur_x: 3.0,
+            blur_y: 1.0,
+            num_passes: 2,
+        }));
+        let result = apply_filter(&dot(7, 3, 3), &filter);
+        assert_eq!(alpha_at(&result, 3, 3), 85);
+        assert_eq!(alpha_at(&result, 2, 3), 57);
+        assert_eq!(alpha_at(&result, 1, 3), 28);
+        assert_eq!(alpha_at(&result, 0, 3), 0);
+        assert_eq!(alpha_at(&result, 3, 2), 0);
+        assert_eq!(filter_padding(&filter), (2, 0));
+    }
+
+    #[test]
+    fn padding_is_bounded() {
+        let filter = Filter::DropShadowFilter(Box::new(swf::DropShadowFilter {
+            color: swf::Color::from_rgb(0, 255),
+            blur_x: f64::INFINITY,
+            blur_y: f64::NAN,
+            angle: 0.0,
+            distance: -1e300,
+            strength: 1.0,
+            is_inner: false,
+            is_knockout: false,
+            num_passes: 255,
+        }));
+        assert_eq!(filter_padding(&filter), (127 * 255 + 2880, 2880));
+        assert_eq!(offset(0.0, f64::INFINITY), (2880, 0));
+        assert_eq!(offset(0.0, f64::NAN), (0, 0));
+    }
+
+    #[test]
+    fn drop_shadow_is_offset_behind_source() {
+        let filter = Filter::DropShadowFilter(Box::new(swf::DropShadowFilter {
+            color: swf::Color::from_rgb(0xFF0000, 255),
+            blur_x: 0.0,
+            blur_y: 0.0,
+            angle: 0.0,
+            distance: 2.0,
+            strength: 1.0,
+            is_inner: false,
+            is_knockout: false,
+            num_passes: 1,
+        }));
+        let result = apply_filter(&dot(5, 1, 1), &filter);
+        assert_eq!(
+            result.get_pixel_raw(1, 1),
+            Some(Color::argb(255, 255, 255, 255))
+        );
+        assert_eq!(
+            result.get_pixel_raw(3, 1),
+            Some(Color::argb(255, 255, 0, 0))
+        );
+        assert!(preserves_source(&filter));
+
+        let effect = remove_source(&result, &dot(5, 1, 1));
+        assert_eq!(alpha_at(&effect, 1, 1), 0);
+        assert_eq!(
+            effect.get_pixel_raw(3, 1),
+            Some(Color::argb(255, 255, 0, 0))
+        );
+    }
+
+    #[test]
+    fn knockout_glow_removes_source() {
+        let filter = Filter::GlowFilter(Box::new(swf::GlowFilter {
+            color: swf::Color::from_rgb(0x00FF00, 255),
+            blur_x: 2.0,
+            blur_y: 2.0,
+            strength: 10.0,
+            is_inner: false,
+            is_knockout: true,
+            num_passes: 1,
+        }));
+        let result = apply_filter(&dot(5, 2, 2), &filter);
+        assert_eq!(alpha_at(&result, 2, 2), 0);
+        assert_eq!(
+            result.get_pixel_raw(1, 2),
+            Some(Color::argb(255, 0, 255, 0))
+        );
+        assert!(!preserves_source(&filter));
+    }
+
+    #[test]
+    fn color_matrix_swaps_channels() {
+        let mut matrix = [0.0; 20];
+        matrix[2] = 1.0;
+        matrix[6] = 1.0;
+        matrix[10] = 1.0;
+        matrix[18] = 1.0;
+        let filter = Filter::ColorMatrixFilter(Box::new(swf::ColorMatrixFilter { matrix }));
+
+        let mut bitmap = BitmapData::default();
+        bitmap.init_pixels(1, 1, 0, true);
+        bitmap.set_pixel32_raw(0, 0, Color::argb(255, 255, 0, 0));
+        let result = apply_filter(&bitmap, &filter);
+        assert_eq!(
+            result.get_pixel_raw(0, 0),
+            Some(Color::argb(255, 0, 0, 255))
+        );
+    }
+
+    #[test]
+    fn mask_hides_by_alpha() {
+        let mut source = BitmapData::default();
+        source.init_pixels(3, 1, 0, true);
+        let mut mask = BitmapData::default();
+        mask.init_pixels(3, 1, 0, true);
+        for x in 0..3 {
+            source.set_pixel32_raw(x, 0, Color::argb(255, 255, 0, 0));
+        }
+        mask.set_pixel32_raw(0, 0, Color::argb(255, 0, 0, 255));
+        mask.set_pixel32_raw(1, 0, Color::argb(128, 0, 0, 128));
+
+        let result = apply_mask(&source, &mask);
+        assert_eq!(
+            result.get_pixel_raw(0, 0),
+            Some(Color::argb(255, 255, 0, 0))
+        );
+        assert_eq!(
+            result.get_pixel_raw(1, 0),
+            Some(Color::argb(128, 128, 0, 0))
+        );
+        assert_eq!(alpha_at(&result, 2, 0), 0);
+    }
+}
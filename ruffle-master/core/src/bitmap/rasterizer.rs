@@ -0,0 +1,754 @@
+//! Software rasterization of display objects into bitmap data
+//!
+//! This backs `BitmapData.draw`, which needs the contents of the display list
+//! as pixels that scripts can read back. The renderers only ever draw to the
+//! screen, so vector content is rasterized on the CPU here instead.
+//!
+//! Each path is sampled once per pixel with no anti-aliasing. Bitmap fills
+//! are only drawn when the caller looks up their bitmaps, and text and masks
+//! are not rasterized yet.
+
+use crate::bitmap::bitmap_data::{BitmapData, Color};
+use crate::bounding_box::BoundingBox;
+use crate::color_transform::ColorTransform;
+use crate::shape_utils::{
+    draw_command_fill_hit_test, draw_command_stroke_hit_test, DrawCommand, DrawPath, WindingRule,
+};
+use crate::transform::{Transform, TransformStack};
+use swf::{BlendMode, FillStyle, Gradient, GradientSpread, LineStyle, Matrix, Twips};
+
+/// Draws display objects into a `BitmapData`.
+pub struct Rasterizer<'a> {
+    /// The bitmap being drawn into.
+    target: &'a mut BitmapData,
+
+    /// The transform from the local space of the object being drawn into
+    /// the pixel space of the target, in twips.
+    pub transform_stack: TransformStack,
+
+    /// The area of the target that may be drawn to, in pixels, as
+    /// `(x_min, y_min, x_max, y_max)` with exclusive maximums.
+    clip: (i32, i32, i32, i32),
+
+    /// How drawn pixels are combined with those already in the target.
+    ///
+    /// This is applied to every path on its own, rather than to the drawn
+    /// object as a whole.
+    pub blend_mode: BlendMode,
+}
+
+impl<'a> Rasterizer<'a> {
+    /// Create a rasterizer drawing into `target`.
+    ///
+    /// `clip` optionally restricts drawing to a rectangle of the target, as
+    /// `(x, y, width, height)` in pixels.
+    pub fn new(
+        target: &'a mut BitmapData,
+        transform: &Transform,
+        clip: Option<(i32, i32, i32, i32)>,
+        blend_mode: BlendMode,
+    ) -> Self {
+        let mut bounds = (0, 0, target.width() as i32, target.height() as i32);
+        if let Some((x, y, width, height)) = clip {
+            bounds = (
+                bounds.0.max(x),
+                bounds.1.max(y),
+                bounds.2.min(x + width),
+                bounds.3.min(y + height),
+            );
+        }
+
+        let mut transform_stack = TransformStack::new();
+        transform_stack.push(transform);
+
+        Self {
+            target,
+            transform_stack,
+            clip: bounds,
+            blend_mode,
+        }
+    }
+
+    /// Fill and stroke a list of paths with the current transform.
+    ///
+    /// Bitmap fills are skipped, as their bitmaps can't be looked up here.
+    pub fn draw_paths(&mut self, paths: &[DrawPath]) {
+        for path in paths {
+            match path {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding,
+                } => self.draw_fill(style, commands, *winding, None),
+                DrawPath::Stroke {
+                    style, commands, ..
+                } => self.draw_stroke(style, commands),
+            }
+        }
+    }
+
+    /// Fill a path with the current transform.
+    ///
+    /// `bitmap` is the bitmap drawn by a bitmap fill, which is skipped
+    /// without it.
+    pub fn draw_fill(
+        &mut self,
+        style: &FillStyle,
+        commands: &[DrawCommand],
+        winding: WindingRule,
+        bitmap: Option<&BitmapData>,
+    ) {
+        self.draw_path(commands, Twips::zero(), |local_point, _| {
+            if draw_command_fill_hit_test(commands, winding, local_point) {
+                fill_color(style, local_point, bitmap)
+            } else {
+                None
+            }
+        });
+    }
+
+    /// Stroke a path with the current transform.
+    pub fn draw_stroke(&mut self, style: &LineStyle, commands: &[DrawCommand]) {
+        self.draw_path(commands, style.width, |local_point, local_matrix| {
+            if !draw_command_stroke_hit_test(commands, style.width, local_point, local_matrix) {
+                return None;
+            }
+
+            match &style.fill_style {
+                Some(fill_style) => fill_color(fill_style, local_point, None),
+                None => Some(style.color.clone()),
+            }
+        });
+    }
+
+    /// Draw another bitmap with the current transform.
+    ///
+    /// With `smoothing`, the bitmap is sampled bilinearly when it is scaled
+    /// or rotated. Otherwise, the nearest pixel is used.
+    pub fn draw_bitmap(&mut self, source: &BitmapData, smoothing: bool) {
+        let bounds = BoundingBox {
+            x_min: Twips::zero(),
+            y_min: Twips::zero(),
+            x_max: Twips::from_pixels(source.width().into()),
+            y_max: Twips::from_pixels(source.height().into()),
+            valid: true,
+        };
+
+        self.draw_area(&bounds, |local_point, _| {
+            if smoothing {
+                return sample_bilinear(source, local_point);
+            }
+
+            let x = local_point.0.to_pixels().floor() as i32;
+            let y = local_point.1.to_pixels().floor() as i32;
+            if source.is_point_in_bounds(x, y) {
+                Some(source.get_pixel32(x, y))
+            } else {
+                None
+            }
+        });
+    }
+
+    /// Draw the pixels covered by a single path.
+    ///
+    /// `stroke_width` widens the area that is sampled, for strokes.
+    fn draw_path(
+        &mut self,
+        commands: &[DrawCommand],
+        stroke_width: Twips,
+        sample: impl Fn((Twips, Twips), &Matrix) -> Option<swf::Color>,
+    ) {
+        // Strokes are at least a pixel wide, however they are scaled.
+        let radius = stroke_width.max(Twips::from_pixels(1.0));
+        let mut bounds = BoundingBox::default();
+        for command in commands {
+            let points = match *command {
+                DrawCommand::MoveTo { x, y } | DrawCommand::LineTo { x, y } => [(x, y), (x, y)],
+                DrawCommand::CurveTo { x1, y1, x2, y2 } => [(x1, y1), (x2, y2)],
+            };
+            for &(x, y) in &points {
+                bounds.encompass(x - radius, y - radius);
+                bounds.encompass(x + radius, y + radius);
+            }
+        }
+
+        self.draw_area(&bounds, |local_point, local_matrix| {
+            sample(local_point, local_matrix)
+                .map(|color| Color::argb(color.a, color.r, color.g, color.b))
+        });
+    }
+
+    /// Blend the colors sampled within an area of local space into the
+    /// target.
+    ///
+    /// `sample` is given each pixel center in local space, along with the
+    /// matrix from pixel space to local space, and returns the unmultiplied
+    /// color at that point, if any.
+    fn draw_area(
+        &mut self,
+        local_bounds: &BoundingBox,
+        sample: impl Fn((Twips, Twips), &Matrix) -> Option<Color>,
+    ) {
+        let transform = self.transform_stack.transform().clone();
+        let matrix = transform.matrix;
+        if !local_bounds.valid || matrix.a * matrix.d - matrix.b * matrix.c == 0.0 {
+            return;
+        }
+        let mut local_matrix = matrix;
+        local_matrix.invert();
+
+        let bounds = local_bounds.transform(&matrix);
+        let x_min = (bounds.x_min.to_pixels().floor() as i32).max(self.clip.0);
+        let y_min = (bounds.y_min.to_pixels().floor() as i32).max(self.clip.1);
+        let x_max = (bounds.x_max.to_pixels().ceil() as i32).min(self.clip.2);
+        let y_max = (bounds.y_max.to_pixels().ceil() as i32).min(self.clip.3);
+
+        for y in y_min..y_max {
+            for x in x_min..x_max {
+                let local_point = local_matrix
+                    * (
+                        Twips::from_pixels(f64::from(x) + 0.5),
+                        Twips::from_pixels(f64::from(y) + 0.5),
+                    );
+                if let Some(color) = sample(local_point, &local_matrix) {
+                    let color = apply_color_transform(color, &transform.color_transform);
+                    self.blend_pixel(x as u32, y as u32, color);
+                }
+            }
+        }
+    }
+
+    /// Blend an unmultiplied color into a pixel of the target.
+    fn blend_pixel(&mut self, x: u32, y: u32, color: Color) {
+        let dest = self.target.get_pixel_raw(x, y).unwrap_or_else(|| 0.into());
+        let transparent = self.target.transparency();
+
+        let result = match self.blend_mode {
+            BlendMode::Alpha | BlendMode::Erase if !transparent => return,
+            BlendMode::Alpha => {
+                let dest = dest.to_un_multiplied_alpha();
+                let alpha = (u16::from(dest.alpha()) * u16::from(color.alpha()) / 255) as u8;
+                dest.with_alpha(alpha).to_premultiplied_alpha(true)
+            }
+            BlendMode::Erase => {
+                let dest = dest.to_un_multiplied_alpha();
+                let alpha = (u16::from(dest.alpha()) * u16::from(255 - color.alpha()) / 255) as u8;
+                dest.with_alpha(alpha).to_premultiplied_alpha(true)
+            }
+            BlendMode::Normal | BlendMode::Layer => {
+                dest.blend_over(&color.to_premultiplied_alpha(true))
+            }
+            blend_mode => {
+                let unmultiplied = dest.to_un_multiplied_alpha();
+                let mix = |dest: u8, source: u8| blend_channel(blend_mode, dest, source);
+                let mixed = Color::argb(
+                    color.alpha(),
+                    mix(unmultiplied.red(), color.red()),
+                    mix(unmultiplied.green(), color.green()),
+                    mix(unmultiplied.blue(), color.blue()),
+                );
+                dest.blend_over(&mixed.to_premultiplied_alpha(true))
+            }
+        };
+
+        let result = if transparent {
+            result
+        } else {
+            result.with_alpha(255)
+        };
+        self.target.set_pixel32_raw(x, y, result);
+    }
+}
+
+/// Combine a single color channel of a pixel with a drawn one.
+fn blend_channel(blend_mode: BlendMode, dest: u8, source: u8) -> u8 {
+    let (d, s) = (i32::from(dest), i32::from(source));
+    let result = match blend_mode {
+        BlendMode::Multiply => d * s / 255,
+        BlendMode::Screen => 255 - (255 - d) * (255 - s) / 255,
+        BlendMode::Lighten => d.max(s),
+        BlendMode::Darken => d.min(s),
+        BlendMode::Difference => (d - s).abs(),
+        BlendMode::Add => d + s,
+        BlendMode::Subtract => d - s,
+        BlendMode::Invert => 255 - d,
+        BlendMode::Overlay if d < 128 => 2 * d * s / 255,
+        BlendMode::Overlay => 255 - 2 * (255 - d) * (255 - s) / 255,
+        BlendMode::HardLight if s < 128 => 2 * d * s / 255,
+        BlendMode::HardLight => 255 - 2 * (255 - d) * (255 - s) / 255,
+        _ => s,
+    };
+
+    result.max(0).min(255) as u8
+}
+
+/// Sample a bitmap at a point in its own space, interpolating between the
+/// four nearest pixels.
+///
+/// Pixels are interpolated with premultiplied alpha, so that transparent
+/// pixels don't bleed their color into their neighbours. Points past the
+/// edges take the color of the edge.
+fn sample_bilinear(source: &BitmapData, point: (Twips, Twips)) -> Option<Color> {
+    let x = point.0.to_pixels() - 0.5;
+    let y = point.1.to_pixels() - 0.5;
+    let (x0, y0) = (x.floor(), y.floor());
+    let (tx, ty) = (x - x0, y - y0);
+
+    let max_x = source.width() as i32 - 1;
+    let max_y = source.height() as i32 - 1;
+    let pixel = |x: f64, y: f64| {
+        let x = (x as i32).max(0).min(max_x) as u32;
+        let y = (y as i32).max(0).min(max_y) as u32;
+        source.get_pixel_raw(x, y).unwrap_or_else(|| 0.into())
+    };
+    let corners = [
+        (pixel(x0, y0), (1.0 - tx) * (1.0 - ty)),
+        (pixel(x0 + 1.0, y0), tx * (1.0 - ty)),
+        (pixel(x0, y0 + 1.0), (1.0 - tx) * ty),
+        (pixel(x0 + 1.0, y0 + 1.0), tx * ty),
+    ];
+    let channel = |get: fn(&Color) -> u8| {
+        let value: f64 = corners
+            .iter()
+            .map(|(color, weight)| f64::from(get(color)) * weight)
+            .sum();
+        value.round().max(0.0).min(255.0) as u8
+    };
+
+    let color = Color::argb(
+        channel(Color::alpha),
+        channel(Color::red),
+        channel(Color::green),
+        channel(Color::blue),
+    );
+    if color.alpha() == 0 {
+        None
+    } else {
+        Some(color.to_un_multiplied_alpha())
+    }
+}
+
+/// Apply a color transform to an unmultiplied color.
+fn apply_color_transform(color: Color, color_transform: &ColorTransform) -> Color {
+    let [r, g, b, a] =
+        color_transform.transform_rgba([color.red(), color.green(), color.blue(), color.alpha()]);
+    Color::argb(a, r, g, b)
+}
+
+/// The color of a fill at a point in the local space of its shape.
+///
+/// Bitmap fills only produce a color when their bitmap is given.
+fn fill_color(
+    fill_style: &FillStyle,
+    point: (Twips, Twips),
+    bitmap: Option<&BitmapData>,
+) -> Option<swf::Color> {
+    match fill_style {
+        FillStyle::Color(color) => Some(color.clone()),
+        FillStyle::LinearGradient(gradient) => {
+            let (x, _) = gradient_point(gradient, point)?;
+            Some(gradient_color(gradient, (x + 1.0) / 2.0))
+        }
+        // Focal gradients are drawn as if their focal point was centered.
+        FillStyle::RadialGradient(gradient) | FillStyle::FocalGradient { gradient, .. } => {
+            let (x, y) = gradient_point(gradient, point)?;
+            Some(gradient_color(gradient, (x * x + y * y).sqrt()))
+        }
+        FillStyle::Bitmap {
+            matrix,
+            is_smoothed,
+            is_repeating,
+            ..
+        } => {
+            let color = bitmap_fill_color(bitmap?, matrix, *is_smoothed, *is_repeating, point)?;
+            Some(swf::Color {
+                r: color.red(),
+                g: color.green(),
+                b: color.blue(),
+                a: color.alpha(),
+            })
+        }
+    }
+}
+
+/// The unmultiplied color of a bitmap fill at a point in the local space of
+/// its shape.
+///
+/// The fill's matrix maps the bitmap onto the shape, with each pixel of the
+/// bitmap a twip wide. Past the edges of the bitmap, repeating fills wrap
+/// around, and other fills take the color of the nearest edge.
+fn bitmap_fill_color(
+    bitmap: &BitmapData,
+    matrix: &Matrix,
+    is_smoothed: bool,
+    is_repeating: bool,
+    point: (Twips, Twips),
+) -> Option<Color> {
+    if bitmap.width() == 0
+        || bitmap.height() == 0
+        || matrix.a * matrix.d - matrix.b * matrix.c == 0.0
+    {
+        return None;
+    }
+
+    // The inverse is worked out here, as twips would round the point to whole
+    // pixels of the bitmap.
+    let (a, b, c, d) = (
+        f64::from(matrix.a),
+        f64::from(matrix.b),
+        f64::from(matrix.c),
+        f64::from(matrix.d),
+    );
+    let px = f64::from((point.0 - matrix.tx).get());
+    let py = f64::from((point.1 - matrix.ty).get());
+    let determinant = a * d - b * c;
+    let mut x = (d * px - c * py) / determinant;
+    let mut y = (a * py - b * px) / determinant;
+    if is_repeating {
+        x = x.rem_euclid(bitmap.width().into());
+        y = y.rem_euclid(bitmap.height().into());
+    }
+
+    if is_smoothed {
+        return sample_bilinear(bitmap, (Twips::from_pixels(x), Twips::from_pixels(y)));
+    }
+
+    let x = (x.floor() as i32).max(0).min(bitmap.width() as i32 - 1);
+    let y = (y.floor() as i32).max(0).min(bitmap.height() as i32 - 1);
+    Some(bitmap.get_pixel32(x, y))
+}
+
+/// Map a point into the space of a gradient, where the gradient square spans
+/// from -1 to 1 on both axes.
+fn gradient_point(gradient: &Gradient, point: (Twips, Twips)) -> Option<(f64, f64)> {
+    let matrix = gradient.matrix;
+    if matrix.a * matrix.d - matrix.b * matrix.c == 0.0 {
+        return None;
+    }
+
+    let mut inverse = matrix;
+    inverse.invert();
+    let (x, y) = inverse * point;
+
+    // The gradient square is 32768 twips wide, centered on the origin.
+    Some((x.get() as f64 / 16384.0, y.get() as f64 / 16384.0))
+}
+
+/// The color of a gradient at a position along it, where 0 is the first
+/// color and 1 is the last.
+fn gradient_color(gradient: &Gradient, position: f64) -> swf::Color {
+    let position = match gradient.spread {
+        GradientSpread::Pad => position.max(0.0).min(1.0),
+        GradientSpread::Repeat => position.rem_euclid(1.0),
+        GradientSpread::Reflect => {
+            let position = position.rem_euclid(2.0);
+            if position > 1.0 {
+                2.0 - position
+            } else {
+                position
+            }
+        }
+    };
+    let ratio = position * 255.0;
+
+    let records = &gradient.records;
+    let first = match records.first() {
+        Some(first) => first,
+        None => {
+            return swf::Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 0,
+            }
+        }
+    };
+    if ratio <= f64::from(first.ratio) {
+        return first.color.clone();
+    }
+
+    for pair in records.windows(2) {
+        let (start, end) = (&pair[0], &pair[1]);
+        if ratio <= f64::from(end.ratio) {
+            let span = f64::from(end.ratio) - f64::from(start.ratio);
+            let t = if span > 0.0 {
+                (ratio - f64::from(start.ratio)) / span
+            } else {
+                1.0
+            };
+            let lerp = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t) as u8;
+
+            return swf::Color {
+                r: lerp(start.color.r, end.color.r),
+                g: lerp(start.color.g, end.color.g),
+                b: lerp(start.color.b, end.color.b),
+                a: lerp(start.color.a, end.color.a),
+            };
+        }
+    }
+
+    records.last().unwrap_or(first).color.clone()
+}
+
+/// Parse the name of a blend mode, as used by `BitmapData.draw`.
+pub fn blend_mode_from_name(name: &str) -> Option<BlendMode> {
+    Some(match name {
+        "normal" => BlendMode::Normal,
+        "layer" => BlendMode::Layer,
+        "multiply" => BlendMode::Multiply,
+        "screen" => BlendMode::Screen,
+        "lighten" => BlendMode::Lighten,
+        "darken" => BlendMode::Darken,
+        "difference" => BlendMode::Difference,
+        "add" => BlendMode::Add,
+        "subtract" => BlendMode::Subtract,
+        "invert" => BlendMode::Invert,
+        "alpha" => BlendMode::Alpha,
+        "erase" => BlendMode::Erase,
+        "overlay" => BlendMode::Overlay,
+        "hardlight" => BlendMode::HardLight,
+        _ => return None,
+    })
+}
+
+/// The name of a blend mode, as used by the `blendMode` property.
+pub fn blend_mode_name(blend_mode: BlendMode) -> &'static str {
+    match blend_mode {
+        BlendMode::Normal => "normal",
+        BlendMode::Layer => "layer",
+        BlendMode::Multiply => "multiply",
+        BlendMode::Screen => "screen",
+        BlendMode::Lighten => "lighten",
+        BlendMode::Darken => "darken",
+        BlendMode::Difference => "difference",
+        BlendMode::Add => "add",
+        BlendMode::Subtract => "subtract",
+        BlendMode::Invert => "invert",
+        BlendMode::Alpha => "alpha",
+        BlendMode::Erase => "erase",
+        BlendMode::Overlay => "overlay",
+        BlendMode::HardLight => "hardlight",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bitmap(width: u32, height: u32, fill_color: i32) -> BitmapData {
+        let mut bitmap = BitmapData::default();
+        bitmap.init_pixels(width, height, fill_color, true);
+        bitmap
+    }
+
+    fn square(size: f64) -> Vec<DrawCommand> {
+        let size = Twips::from_pixels(size);
+        vec![
+            DrawCommand::MoveTo {
+                x: Twips::zero(),
+                y: Twips::zero(),
+            },
+            DrawCommand::LineTo {
+                x: size,
+                y: Twips::zero(),
+            },
+            DrawCommand::LineTo { x: size, y: size },
+            DrawCommand::LineTo {
+                x: Twips::zero(),
+                y: size,
+            },
+            DrawCommand::LineTo {
+                x: Twips::zero(),
+                y: Twips::zero(),
+            },
+        ]
+    }
+
+    #[test]
+    fn fill_is_transformed_and_clipped() {
+        let mut target = bitmap(4, 4, 0);
+        let style = FillStyle::Color(swf::Color {
+            r: 255,
+            g: 0,
+            b: 0,
+            a: 255,
+        });
+        let transform = Transform {
+            matrix: Matrix::translate(Twips::from_pixels(1.0), Twips::from_pixels(1.0)),
+            ..Default::default()
+        };
+
+        let mut rasterizer = Rasterizer::new(
+            &mut target,
+            &transform,
+            Some((0, 0, 2, 4)),
+            BlendMode::Normal,
+        );
+        rasterizer.draw_paths(&[DrawPath::Fill {
+            style: &style,
+            commands: square(2.0),
+            winding: WindingRule::EvenOdd,
+        }]);
+
+        assert_eq!(i32::from(target.get_pixel32(0, 0)), 0);
+        assert_eq!(target.get_pixel32(1, 1), Color::argb(255, 255, 0, 0));
+        assert_eq!(target.get_pixel32(1, 2), Color::argb(255, 255, 0, 0));
+        // Outside of the clip rectangle.
+        assert_eq!(i32::from(target.get_pixel32(2, 1)), 0);
+    }
+
+    #[test]
+    fn nested_color_transforms_apply_child_first() {
+        let style = FillStyle::Color(swf::Color {
+            r: 0,
+            g: 0,
+            b: 0,
+            a: 255,
+        });
+        // Transforms of the color channels, that leave alpha alone.
+        let cxform = |mult: f32, add: i16| {
+            let mult = (mult * 256.0) as i16;
+            ColorTransform::from_fixed([mult, mult, mult, 256], [add, add, add, 0])
+        };
+
+        // A parent that halves, around a child that adds 100, around a
+        // grandchild that doubles.
+        let mut target = bitmap(2, 2, 0);
+        let parent = Transform {
+            color_transform: cxform(0.5, 0),
+            ..Default::default()
+        };
+        let mut rasterizer = Rasterizer::new(&mut target, &parent, None, BlendMode::Normal);
+        for &(mult, add) in &[(1.0, 100), (2.0, 0)] {
+            rasterizer.transform_stack.push(&Transform {
+                color_transform: cxform(mult, add),
+                ..Default::default()
+            });
+        }
+        rasterizer.draw_paths(&[DrawPath::Fill {
+            style: &style,
+            commands: square(2.0),
+            winding: WindingRule::EvenOdd,
+        }]);
+
+        assert_eq!(target.get_pixel32(0, 0), Color::argb(255, 50, 50, 50));
+
+        // A parent that adds 100, around a child that halves.
+        let mut target = bitmap(2, 2, 0);
+        let parent = Transform {
+            color_transform: cxform(1.0, 100),
+            ..Default::default()
+        };
+        let mut rasterizer = Rasterizer::new(&mut target, &parent, None, BlendMode::Normal);
+        rasterizer.transform_stack.push(&Transform {
+            color_transform: cxform(0.5, 0),
+            ..Default::default()
+        });
+        rasterizer.draw_paths(&[DrawPath::Fill {
+            style: &FillStyle::Color(swf::Color {
+                r: 200,
+                g: 200,
+                b: 200,
+                a: 255,
+            }),
+            commands: square(2.0),
+            winding: WindingRule::EvenOdd,
+        }]);
+
+        assert_eq!(target.get_pixel32(1, 1), Color::argb(255, 200, 200, 200));
+    }
+
+    #[test]
+    fn bitmap_is_blended() {
+        let mut target = bitmap(2, 2, 0xFF808080u32 as i32);
+        let source = bitmap(2, 2, 0xFF808080u32 as i32);
+
+        let mut rasterizer = Rasterizer::new(
+            &mut target,
+            &Transform::default(),
+            None,
+            BlendMode::Multiply,
+        );
+        rasterizer.draw_bitmap(&source, false);
+
+        assert_eq!(target.get_pixel32(1, 1), Color::argb(255, 64, 64, 64));
+    }
+
+    #[test]
+    fn smoothed_bitmap_is_interpolated() {
+        // A black pixel next to a white one, scaled up to twice its size.
+        let mut source = bitmap(2, 1, 0xFF000000u32 as i32);
+        source.set_pixel32(1, 0, Color::argb(255, 255, 255, 255));
+        let transform = Transform {
+            matrix: Matrix::scale(2.0, 2.0),
+            ..Default::default()
+        };
+
+        let mut target = bitmap(4, 2, 0);
+        let mut rasterizer = Rasterizer::new(&mut target, &transform, None, BlendMode::Normal);
+        rasterizer.draw_bitmap(&source, true);
+
+        assert_eq!(target.get_pixel32(0, 0), Color::argb(255, 0, 0, 0));
+        assert_eq!(target.get_pixel32(1, 0), Color::argb(255, 64, 64, 64));
+        assert_eq!(target.get_pixel32(2, 1), Color::argb(255, 191, 191, 191));
+        assert_eq!(target.get_pixel32(3, 1), Color::argb(255, 255, 255, 255));
+
+        let mut target = bitmap(4, 2, 0);
+        let mut rasterizer = Rasterizer::new(&mut target, &transform, None, BlendMode::Normal);
+        rasterizer.draw_bitmap(&source, false);
+
+        assert_eq!(target.get_pixel32(1, 0), Color::argb(255, 0, 0, 0));
+        assert_eq!(target.get_pixel32(2, 0), Color::argb(255, 255, 255, 255));
+    }
+
+    #[test]
+    fn bitmap_fill_repeats_or_clamps() {
+        // A black pixel next to a white one, with each pixel of the bitmap
+        // filling a pixel of the target.
+        let mut source = bitmap(2, 1, 0xFF000000u32 as i32);
+        source.set_pixel32(1, 0, Color::argb(255, 255, 255, 255));
+        let fill = |is_repeating| {
+            let style = FillStyle::Bitmap {
+                id: 1,
+                matrix: Matrix::scale(20.0, 20.0),
+                is_smoothed: false,
+                is_repeating,
+            };
+            let mut target = bitmap(4, 1, 0);
+            let mut rasterizer =
+                Rasterizer::new(&mut target, &Transform::default(), None, BlendMode::Normal);
+            rasterizer.draw_fill(&style, &square(4.0), WindingRule::EvenOdd, Some(&source));
+            (0..4)
+                .map(|x| target.get_pixel32(x, 0).red())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(fill(true), [0, 255, 0, 255]);
+        assert_eq!(fill(false), [0, 255, 255, 255]);
+    }
+
+    #[test]
+    fn blend_mode_names_round_trip() {
+        for &blend_mode in &[
+            BlendMode::Normal,
+            BlendMode::Layer,
+            BlendMode::Multiply,
+            BlendMode::Screen,
+            BlendMode::Lighten,
+            BlendMode::Darken,
+            BlendMode::Difference,
+            BlendMode::Add,
+            BlendMode::Subtract,
+            BlendMode::Invert,
+            BlendMode::Alpha,
+            BlendMode::Erase,
+            BlendMode::Overlay,
+            BlendMode::HardLight,
+        ] {
+            assert_eq!(
+                blend_mode_from_name(blend_mode_name(blend_mode)),
+                Some(blend_mode)
+            );
+        }
+    }
+}
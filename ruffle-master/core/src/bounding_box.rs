@@ -96,6 +96,22 @@ impl BoundingBox {
         x_min <= x_max && y_min <= y_max
     }
 
+    /// The area covered by both this bounding box and `other`.
+    pub fn intersection(&self, other: &BoundingBox) -> BoundingBox {
+        if !self.intersects(other) {
+            return Default::default();
+        }
+
+        use std::cmp::{max, min};
+        BoundingBox {
+            x_min: max(self.x_min, other.x_min),
+            y_min: max(self.y_min, other.y_min),
+            x_max: min(self.x_max, other.x_max),
+            y_max: min(self.y_max, other.y_max),
+            valid: true,
+        }
+    }
+
     pub fn contains(&self, (x, y): (Twips, Twips)) -> bool {
         self.valid && x >= self.x_min && x <= self.x_max && y >= self.y_min && y <= self.y_max
     }
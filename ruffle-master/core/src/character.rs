@@ -4,6 +4,7 @@ use crate::display_object::{
 };
 use crate::font::Font;
 use gc_arena::Collect;
+use std::sync::Arc;
 
 #[derive(Clone, Collect)]
 #[collect(no_drop)]
@@ -18,4 +19,5 @@ pub enum Character<'gc> {
     Text(Text<'gc>),
     Sound(#[collect(require_static)] SoundHandle),
     Video(Video<'gc>),
+    BinaryData(#[collect(require_static)] Arc<[u8]>),
 }
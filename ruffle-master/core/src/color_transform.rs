@@ -1,3 +1,11 @@
+//! Color transforms
+//!
+//! Flash keeps the multipliers of a color transform in 8.8 fixed point and the
+//! additions as 16-bit integers, and does all of its math on those: products
+//! are truncated, and anything that overflows saturates. The fields here are
+//! floats for the sake of the renderers, but all arithmetic goes through the
+//! fixed-point values so that colors come out as they do in Flash.
+
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct ColorTransform {
     pub r_mult: f32,
@@ -37,6 +45,83 @@ impl ColorTransform {
             && self.b_add == 0.0
             && self.a_add == 0.0
     }
+
+    /// Build a transform from Flash's fixed-point representation: multipliers
+    /// in 8.8 fixed point and additions in whole color values, both in RGBA
+    /// order.
+    pub fn from_fixed(mult: [i16; 4], add: [i16; 4]) -> Self {
+        Self {
+            r_mult: f32::from(mult[0]) / 256.0,
+            g_mult: f32::from(mult[1]) / 256.0,
+            b_mult: f32::from(mult[2]) / 256.0,
+            a_mult: f32::from(mult[3]) / 256.0,
+            r_add: f32::from(add[0]) / 255.0,
+            g_add: f32::from(add[1]) / 255.0,
+            b_add: f32::from(add[2]) / 255.0,
+            a_add: f32::from(add[3]) / 255.0,
+        }
+    }
+
+    /// The multipliers in 8.8 fixed point, in RGBA order. Flash truncates
+    /// anything finer, and saturates anything out of range.
+    pub fn multipliers(&self) -> [i16; 4] {
+        // Float to integer casts saturate, and turn NaN into 0.
+        let fixed = |mult: f32| (mult * 256.0) as i16;
+        [
+            fixed(self.r_mult),
+            fixed(self.g_mult),
+            fixed(self.b_mult),
+            fixed(self.a_mult),
+        ]
+    }
+
+    /// The additions in whole color values, in RGBA order.
+    pub fn additions(&self) -> [i16; 4] {
+        let fixed = |add: f32| (add * 255.0).round() as i16;
+        [
+            fixed(self.r_add),
+            fixed(self.g_add),
+            fixed(self.b_add),
+            fixed(self.a_add),
+        ]
+    }
+
+    /// Apply this transform to an unmultiplied RGBA color.
+    pub fn transform_rgba(&self, rgba: [u8; 4]) -> [u8; 4] {
+        let mult = self.multipliers();
+        let add = self.additions();
+        [
+            transform_channel(rgba[0], mult[0], add[0]),
+            transform_channel(rgba[1], mult[1], add[1]),
+            transform_channel(rgba[2], mult[2], add[2]),
+            transform_channel(rgba[3], mult[3], add[3]),
+        ]
+    }
+
+    /// Combine this transform with one that is applied before it, such as
+    /// the transform of a child of the object this transform belongs to.
+    ///
+    /// The result applies `inner` first and then `self`: the multipliers are
+    /// multiplied together, and the additions of `inner` are scaled by the
+    /// multipliers of `self` before the additions of `self` are added. This
+    /// is done in fixed point and saturates, as Flash does for nested clips.
+    pub fn concat(&self, inner: &Self) -> Self {
+        let outer_mult = self.multipliers();
+        let outer_add = self.additions();
+        let inner_mult = inner.multipliers();
+        let inner_add = inner.additions();
+
+        let mut mult = [0; 4];
+        let mut add = [0; 4];
+        for i in 0..4 {
+            mult[i] = saturate_i16((i32::from(outer_mult[i]) * i32::from(inner_mult[i])) >> 8);
+            add[i] = saturate_i16(
+                ((i32::from(outer_mult[i]) * i32::from(inner_add[i])) >> 8)
+                    + i32::from(outer_add[i]),
+            );
+        }
+        Self::from_fixed(mult, add)
+    }
 }
 
 impl std::default::Default for ColorTransform {
@@ -54,35 +139,93 @@ impl std::default::Default for ColorTransform {
     }
 }
 
-impl std::ops::Mul for ColorTransform {
-    type Output = Self;
-    fn mul(self, rhs: Self) -> Self {
-        ColorTransform {
-            r_mult: self.r_mult * rhs.r_mult,
-            g_mult: self.g_mult * rhs.g_mult,
-            b_mult: self.b_mult * rhs.b_mult,
-            a_mult: self.a_mult * rhs.a_mult,
-
-            r_add: self.r_mult * rhs.r_add + self.r_add,
-            g_add: self.g_mult * rhs.g_add + self.g_add,
-            b_add: self.b_mult * rhs.b_add + self.b_add,
-            a_add: self.a_mult * rhs.a_add + self.a_add,
-        }
-    }
+/// Clamp a result of fixed-point math to the 16 bits that Flash keeps.
+fn saturate_i16(n: i32) -> i16 {
+    n.max(i32::from(i16::MIN)).min(i32::from(i16::MAX)) as i16
+}
+
+/// Transform a single channel, as Flash does: the product is truncated to an
+/// integer before the addition, and the result saturates.
+fn transform_channel(channel: u8, mult: i16, add: i16) -> u8 {
+    let channel = ((i32::from(channel) * i32::from(mult)) >> 8) + i32::from(add);
+    channel.max(0).min(255) as u8
 }
 
-impl std::ops::MulAssign for ColorTransform {
-    fn mul_assign(&mut self, rhs: Self) {
-        *self = ColorTransform {
-            r_mult: self.r_mult * rhs.r_mult,
-            g_mult: self.g_mult * rhs.g_mult,
-            b_mult: self.b_mult * rhs.b_mult,
-            a_mult: self.a_mult * rhs.a_mult,
-
-            r_add: self.r_mult * rhs.r_add + self.r_add,
-            g_add: self.g_mult * rhs.b_add + self.g_add,
-            b_add: self.b_mult * rhs.g_add + self.b_add,
-            a_add: self.a_mult * rhs.a_add + self.a_add,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(mult: f32, add: i16) -> ColorTransform {
+        ColorTransform::from_fixed([(mult * 256.0) as i16; 4], [add; 4])
+    }
+
+    #[test]
+    fn channels_saturate() {
+        assert_eq!(transform(1.0, 100).transform_rgba([200; 4]), [255; 4]);
+        assert_eq!(transform(1.0, -100).transform_rgba([50; 4]), [0; 4]);
+        assert_eq!(transform(2.0, 0).transform_rgba([200; 4]), [255; 4]);
+        assert_eq!(transform(-1.0, 255).transform_rgba([55; 4]), [200; 4]);
+        assert_eq!(transform(-1.0, 0).transform_rgba([55; 4]), [0; 4]);
+
+        // 255 * 0.5 is 127.5, which Flash truncates.
+        assert_eq!(transform(0.5, 0).transform_rgba([255; 4]), [127; 4]);
+    }
+
+    #[test]
+    fn fixed_point_round_trip() {
+        let cxform = transform(0.3, -40);
+        assert_eq!(cxform.multipliers(), [76; 4]);
+        assert_eq!(cxform.additions(), [-40; 4]);
+        assert_eq!(
+            ColorTransform::from(swf::ColorTransform {
+                r_multiply: 1.0,
+                g_multiply: 0.5,
+                b_multiply: 0.0,
+                a_multiply: 1.0,
+                r_add: 255,
+                g_add: -255,
+                b_add: 17,
+                a_add: 0,
+            })
+            .additions(),
+            [255, -255, 17, 0]
+        );
+    }
+
+    #[test]
+    fn concatenation_saturates() {
+        let cxform = transform(100.0, 20000).concat(&transform(100.0, 20000));
+        assert_eq!(cxform.multipliers(), [i16::MAX; 4]);
+        assert_eq!(cxform.additions(), [i16::MAX; 4]);
+
+        let cxform = transform(0.5, 100).concat(&transform(1.0, -100));
+        assert_eq!(cxform.multipliers(), [128; 4]);
+        assert_eq!(cxform.additions(), [50; 4]);
+    }
+
+    #[test]
+    fn concat_applies_inner_first() {
+        // A child that adds, inside a parent that multiplies: the addition is
+        // scaled too.
+        let cxform = transform(0.5, 0).concat(&transform(1.0, 100));
+        assert_eq!(cxform.transform_rgba([0; 4]), [50; 4]);
+        assert_eq!(cxform.transform_rgba([100; 4]), [100; 4]);
+
+        // A child that multiplies, inside a parent that adds: the addition is
+        // not.
+        let cxform = transform(1.0, 100).concat(&transform(0.5, 0));
+        assert_eq!(cxform.transform_rgba([0; 4]), [100; 4]);
+        assert_eq!(cxform.transform_rgba([100; 4]), [150; 4]);
+
+        // Same as applying each transform in turn.
+        let inner = transform(-0.75, 200);
+        let outer = transform(1.5, -30);
+        for &channel in &[0, 17, 128, 255] {
+            let color = [channel; 4];
+            assert_eq!(
+                outer.concat(&inner).transform_rgba(color),
+                outer.transform_rgba(inner.transform_rgba(color))
+            );
         }
     }
 }
@@ -0,0 +1,210 @@
+//! Drawing commands recorded during a frame, and submitted to the render
+//! backend all at once.
+
+use crate::backend::render::{BitmapHandle, ShapeHandle};
+use crate::transform::Transform;
+use swf::{BlendMode, Color, Matrix};
+
+/// Something that draws the commands in a `CommandList`, such as a render
+/// backend.
+pub trait CommandHandler {
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool);
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform);
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix);
+    fn push_mask(&mut self);
+    fn activate_mask(&mut self);
+    fn deactivate_mask(&mut self);
+    fn pop_mask(&mut self);
+
+    /// Start combining everything rendered with what has already been drawn
+    /// using `blend_mode`, until the matching `pop_blend_mode`.
+    ///
+    /// When blend modes are nested, the innermost one is used.
+    fn push_blend_mode(&mut self, blend_mode: BlendMode);
+    fn pop_blend_mode(&mut self);
+}
+
+/// A single drawing command.
+#[derive(Clone, Debug)]
+pub enum Command {
+    RenderBitmap {
+        bitmap: BitmapHandle,
+        transform: Transform,
+        smoothing: bool,
+    },
+    RenderShape {
+        shape: ShapeHandle,
+        transform: Transform,
+    },
+    DrawRect {
+        color: Color,
+        matrix: Matrix,
+    },
+    PushMask,
+    ActivateMask,
+    DeactivateMask,
+    PopMask,
+    PushBlendMode(BlendMode),
+    PopBlendMode,
+}
+
+/// The drawing commands of a frame, in the order they were recorded.
+///
+/// Display objects record their commands into a list while the stage is
+/// rendered, and the finished list is handed to the render backend, which is
+/// free to batch or reorder commands as long as the result looks the same.
+#[derive(Clone, Debug, Default)]
+pub struct CommandList {
+    commands: Vec<Command>,
+}
+
+impl CommandList {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The recorded commands.
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Draw every command in the list with `handler`, in order.
+    pub fn execute(self, handler: &mut impl CommandHandler) {
+        for command in self.commands {
+            match command {
+                Command::RenderBitmap {
+                    bitmap,
+                    transform,
+                    smoothing,
+                } => handler.render_bitmap(bitmap, &transform, smoothing),
+                Command::RenderShape { shape, transform } => {
+                    handler.render_shape(shape, &transform)
+                }
+                Command::DrawRect { color, matrix } => handler.draw_rect(color, &matrix),
+                Command::PushMask => handler.push_mask(),
+                Command::ActivateMask => handler.activate_mask(),
+                Command::DeactivateMask => handler.deactivate_mask(),
+                Command::PopMask => handler.pop_mask(),
+                Command::PushBlendMode(blend_mode) => handler.push_blend_mode(blend_mode),
+                Command::PopBlendMode => handler.pop_blend_mode(),
+            }
+        }
+    }
+}
+
+impl CommandHandler for CommandList {
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
+        self.commands.push(Command::RenderBitmap {
+            bitmap,
+            transform: transform.clone(),
+            smoothing,
+        });
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        self.commands.push(Command::RenderShape {
+            shape,
+            transform: transform.clone(),
+        });
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+        self.commands.push(Command::DrawRect {
+            color,
+            matrix: *matrix,
+        });
+    }
+
+    fn push_mask(&mut self) {
+        self.commands.push(Command::PushMask);
+    }
+
+    fn activate_mask(&mut self) {
+        self.commands.push(Command::ActivateMask);
+    }
+
+    fn deactivate_mask(&mut self) {
+        self.commands.push(Command::DeactivateMask);
+    }
+
+    fn pop_mask(&mut self) {
+        self.commands.push(Command::PopMask);
+    }
+
+    fn push_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.commands.push(Command::PushBlendMode(blend_mode));
+    }
+
+    fn pop_blend_mode(&mut self) {
+        self.commands.push(Command::PopBlendMode);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swf::Twips;
+
+    fn record_mask_and_shapes(list: &mut impl CommandHandler) {
+        let transform = Transform {
+            matrix: Matrix::translate(Twips::from_pixels(10.0), Twips::zero()),
+            ..Default::default()
+        };
+        list.push_mask();
+        list.draw_rect(Color::from_rgb(0xff0000, 255), &Matrix::scale(2.0, 3.0));
+        list.activate_mask();
+        list.push_blend_mode(BlendMode::Multiply);
+        list.render_shape(ShapeHandle(1), &transform);
+        list.render_bitmap(BitmapHandle(2), &Default::default(), true);
+        list.pop_blend_mode();
+        list.deactivate_mask();
+        list.draw_rect(Color::from_rgb(0xff0000, 255), &Matrix::scale(2.0, 3.0));
+        list.pop_mask();
+    }
+
+    #[test]
+    fn records_in_order() {
+        let mut list = CommandList::new();
+        assert!(list.is_empty());
+        record_mask_and_shapes(&mut list);
+
+        let commands = list.commands();
+        assert_eq!(commands.len(), 10);
+        assert!(matches!(commands[0], Command::PushMask));
+        assert!(matches!(
+            commands[3],
+            Command::PushBlendMode(BlendMode::Multiply)
+        ));
+        match &commands[4] {
+            Command::RenderShape { shape, transform } => {
+                assert_eq!(shape.0, 1);
+                assert_eq!(transform.matrix.tx, Twips::from_pixels(10.0));
+            }
+            other => panic!("Expected a shape, got {:?}", other),
+        }
+        assert!(matches!(
+            commands[5],
+            Command::RenderBitmap {
+                bitmap: BitmapHandle(2),
+                smoothing: true,
+                ..
+            }
+        ));
+        assert!(matches!(commands[9], Command::PopMask));
+    }
+
+    #[test]
+    fn execute_replays_every_command() {
+        let mut list = CommandList::new();
+        record_mask_and_shapes(&mut list);
+        let recorded = format!("{:?}", list.commands());
+
+        let mut replayed = CommandList::new();
+        list.execute(&mut replayed);
+        assert_eq!(format!("{:?}", replayed.commands()), recorded);
+    }
+}
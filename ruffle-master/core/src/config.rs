@@ -1,3 +1,4 @@
+use bitflags::bitflags;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
@@ -28,3 +29,262 @@ impl Default for Letterbox {
         Letterbox::Fullscreen
     }
 }
+
+/// Limits on movies loaded at runtime with `loadMovie` and related APIs.
+///
+/// These protect the player from content that loads itself recursively,
+/// which would otherwise exhaust memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
+pub struct LoaderLimits {
+    /// The maximum number of movie loads that may be in progress at once.
+    pub max_concurrent_loads: usize,
+
+    /// The maximum number of loaded movies that may be nested inside one
+    /// another, counting the root movie.
+    pub max_nesting_depth: usize,
+
+    /// Whether to refuse to load a movie into a clip that is already inside a
+    /// movie loaded from the same URL.
+    ///
+    /// Flash Player allows this, and some content loads itself on purpose, so
+    /// this is off by default; `max_nesting_depth` still stops a movie from
+    /// loading itself forever.
+    pub detect_cycles: bool,
+}
+
+impl Default for LoaderLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrent_loads: 32,
+            max_nesting_depth: 16,
+            detect_cycles: false,
+        }
+    }
+}
+
+/// The quality the stage is drawn at.
+///
+/// Embedders pick the starting quality, and movies may change it with
+/// `_quality` or `Stage.quality`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum StageQuality {
+    Low,
+    Medium,
+    High,
+    Best,
+}
+
+impl Default for StageQuality {
+    fn default() -> Self {
+        StageQuality::High
+    }
+}
+
+impl StageQuality {
+    /// The name ActionScript gives this quality.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StageQuality::Low => "LOW",
+            StageQuality::Medium => "MEDIUM",
+            StageQuality::High => "HIGH",
+            StageQuality::Best => "BEST",
+        }
+    }
+
+    /// Parse the name of a quality, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "low" => Some(StageQuality::Low),
+            "medium" => Some(StageQuality::Medium),
+            "high" => Some(StageQuality::High),
+            "best" => Some(StageQuality::Best),
+            _ => None,
+        }
+    }
+}
+
+/// How the stage is scaled to fit the viewport.
+///
+/// Embedders pick the starting mode, and movies may change it with
+/// `Stage.scaleMode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum StageScaleMode {
+    /// The stage is stretched to fill the viewport, ignoring its aspect
+    /// ratio.
+    ExactFit,
+
+    /// The stage is scaled to cover the whole viewport, keeping its aspect
+    /// ratio, and any overflow is cropped.
+    NoBorder,
+
+    /// The stage isn't scaled, and grows or shrinks with the viewport.
+    NoScale,
+
+    /// The stage is scaled to fit inside the viewport, keeping its aspect
+    /// ratio.
+    ShowAll,
+}
+
+impl Default for StageScaleMode {
+    fn default() -> Self {
+        StageScaleMode::ShowAll
+    }
+}
+
+impl StageScaleMode {
+    /// The name ActionScript gives this scale mode.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StageScaleMode::ExactFit => "exactFit",
+            StageScaleMode::NoBorder => "noBorder",
+            StageScaleMode::NoScale => "noScale",
+            StageScaleMode::ShowAll => "showAll",
+        }
+    }
+
+    /// Parse the name of a scale mode, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "exactfit" => Some(StageScaleMode::ExactFit),
+            "noborder" => Some(StageScaleMode::NoBorder),
+            "noscale" => Some(StageScaleMode::NoScale),
+            "showall" => Some(StageScaleMode::ShowAll),
+            _ => None,
+        }
+    }
+}
+
+bitflags! {
+    /// The edges of the viewport that the stage is aligned to, when it doesn't
+    /// fill the viewport exactly.
+    ///
+    /// The stage is centered along any axis with neither edge set.
+    #[derive(Default)]
+    pub struct StageAlign: u8 {
+        const TOP    = 1 << 0;
+        const BOTTOM = 1 << 1;
+        const LEFT   = 1 << 2;
+        const RIGHT  = 1 << 3;
+    }
+}
+
+impl StageAlign {
+    /// The name ActionScript gives this alignment, such as `TL` or `B`.
+    pub fn to_name(self) -> String {
+        let mut name = String::with_capacity(2);
+        if self.contains(StageAlign::TOP) {
+            name.push('T');
+        } else if self.contains(StageAlign::BOTTOM) {
+            name.push('B');
+        }
+
+        if self.contains(StageAlign::LEFT) {
+            name.push('L');
+        } else if self.contains(StageAlign::RIGHT) {
+            name.push('R');
+        }
+
+        name
+    }
+
+    /// Parse the name of an alignment, ignoring case and any characters
+    /// that aren't edges.
+    pub fn from_name(name: &str) -> Self {
+        let mut align = StageAlign::empty();
+        for c in name.chars() {
+            match c.to_ascii_uppercase() {
+                'T' => align |= StageAlign::TOP,
+                'B' => align |= StageAlign::BOTTOM,
+                'L' => align |= StageAlign::LEFT,
+                'R' => align |= StageAlign::RIGHT,
+                _ => (),
+            }
+        }
+
+        align
+    }
+}
+
+/// How strongly glyphs are snapped to the pixel grid when text is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum TextHinting {
+    /// Glyphs are drawn exactly where they were laid out.
+    None,
+
+    /// The baseline of each glyph is moved onto a whole pixel, which keeps
+    /// horizontal strokes sharp.
+    Vertical,
+
+    /// Each glyph is moved onto a whole pixel in both directions.
+    Full,
+}
+
+/// Options for how glyphs are positioned when text is laid out and drawn.
+///
+/// Flash lays text out with whole pixel advances, while modern text
+/// rendering keeps fractional advances for smoother spacing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, rename_all = "camelCase"))]
+pub struct TextRendering {
+    /// Whether glyph advances may be fractions of a pixel. When this is off,
+    /// each advance is rounded to a whole pixel, as Flash does.
+    pub subpixel_positioning: bool,
+
+    /// How glyphs are snapped to the pixel grid when drawn.
+    pub hinting: TextHinting,
+}
+
+impl Default for TextRendering {
+    fn default() -> Self {
+        Self {
+            subpixel_positioning: true,
+            hinting: TextHinting::None,
+        }
+    }
+}
+
+impl TextRendering {
+    /// The options to use when the stage is drawn at `quality`.
+    ///
+    /// Flash doesn't antialias text at low quality, so glyphs there are
+    /// always kept on whole pixels.
+    pub fn at_quality(self, quality: StageQuality) -> Self {
+        match quality {
+            StageQuality::Low => Self {
+                subpixel_positioning: false,
+                hinting: TextHinting::Full,
+            },
+            _ => self,
+        }
+    }
+}
+
+/// The keys that gamepads press, for movies that only support the keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum GamepadKeyMapping {
+    /// Gamepads don't press any keys, and are only seen through `GameInput`.
+    None,
+
+    /// The d-pad and left stick press the arrow keys.
+    Arrows,
+
+    /// The d-pad and left stick press W, A, S and D.
+    Wasd,
+}
+
+impl Default for GamepadKeyMapping {
+    fn default() -> Self {
+        GamepadKeyMapping::None
+    }
+}
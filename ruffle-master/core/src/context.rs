@@ -2,25 +2,41 @@
 
 use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::{Avm1, Object as Avm1Object, Timers, Value as Avm1Value};
-use crate::avm2::{Avm2, Object as Avm2Object, Value as Avm2Value};
+use crate::avm2::{
+    Avm2, Event as Avm2Event, Object as Avm2Object, SoundChannelObject, Value as Avm2Value,
+};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
+    clipboard::ClipboardBackend,
+    local_connection::LocalConnectionBackend,
     locale::LocaleBackend,
     log::LogBackend,
     navigator::NavigatorBackend,
+    print::PrintBackend,
     render::RenderBackend,
+    socket::SocketBackend,
     storage::StorageBackend,
-    ui::UiBackend,
+    ui::{ScriptError, ScriptErrorResponse, StageOrientation, UiBackend},
     video::VideoBackend,
 };
+use crate::commands::CommandList;
+use crate::config::{StageAlign, StageQuality, StageScaleMode, TextRendering};
 use crate::display_object::{EditText, MovieClip, SoundTransform};
+use crate::events::KeyboardState;
+use crate::executor::Executor;
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
+use crate::i18n::MessageCatalog;
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnectionManager;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::security::SecuritySandbox;
+use crate::socket::SocketManager;
+use crate::streams::StreamManager;
 use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::telemetry::Telemetry;
 use crate::transform::TransformStack;
 use core::fmt;
 use gc_arena::{Collect, MutationContext};
@@ -42,6 +58,30 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// TODO: Move this into a `Stage` display object.
     pub background_color: &'a mut Option<Color>,
 
+    /// The frame rate of the player, in frames per second. Set by the
+    /// `frameRate` property of the AVM2 `Stage`.
+    pub frame_rate: &'a mut f64,
+
+    /// The quality the stage is drawn at. Set by `_quality` in AVM1 and the
+    /// `quality` property of the AVM2 `Stage`.
+    pub quality: &'a mut StageQuality,
+
+    /// How the stage is scaled to fit the viewport. Set by the `scaleMode`
+    /// property of the AVM2 `Stage`.
+    pub stage_scale_mode: &'a mut StageScaleMode,
+
+    /// The edges of the viewport the stage is aligned to. Set by the `align`
+    /// property of the AVM2 `Stage`.
+    pub stage_align: &'a mut StageAlign,
+
+    /// The orientation of the stage on the device. Changed by the UI when the
+    /// device rotates, or by `Stage.setOrientation` in AVM2.
+    pub orientation: &'a mut StageOrientation,
+
+    /// How the player positions glyphs in text, before the stage quality is
+    /// taken into account.
+    pub text_rendering: TextRendering,
+
     /// The mutation context to allocate and mutate `GcCell` types.
     pub gc_context: MutationContext<'gc, 'gc_context>,
 
@@ -77,6 +117,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The UI backend, used to detect user interactions.
     pub ui: &'a mut dyn UiBackend,
 
+    /// The clipboard backend, used by text fields to copy and paste text.
+    pub clipboard: &'a mut dyn ClipboardBackend,
+
     /// The storage backend, used for storing persistent state
     pub storage: &'a mut dyn StorageBackend,
 
@@ -89,6 +132,15 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The video backend, used for video decoding
     pub video: &'a mut dyn VideoBackend,
 
+    /// The socket backend, used for TCP connections
+    pub socket: &'a mut dyn SocketBackend,
+
+    /// The local connection backend, which carries `LocalConnection` messages
+    pub local_connection: &'a mut dyn LocalConnectionBackend,
+
+    /// The print backend, used by `PrintJob`
+    pub print: &'a mut dyn PrintBackend,
+
     /// The RNG, used by the AVM `RandomNumber` opcode,  `Math.random(),` and `random()`.
     pub rng: &'a mut SmallRng,
 
@@ -101,6 +153,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The location of the mouse when it was last over the player.
     pub mouse_position: &'a (Twips, Twips),
 
+    /// The keys held down, shared by both AVMs.
+    pub keyboard: &'a KeyboardState,
+
     /// The object being dragged via a `startDrag` action.
     pub drag_object: &'a mut Option<crate::player::DragObject<'gc>>,
 
@@ -122,6 +177,15 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The system properties
     pub system: &'a mut SystemProperties,
 
+    /// The security sandbox of the root movie.
+    pub security: &'a mut SecuritySandbox,
+
+    /// The text of the player's own dialogs, as supplied by the embedder.
+    pub messages: &'a dyn MessageCatalog,
+
+    /// The embedder's sink for compatibility statistics.
+    pub telemetry: &'a mut Telemetry,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     pub instance_counter: &'a mut i32,
 
@@ -158,17 +222,104 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
 
     /// This frame's current fake time offset, used to pretend passage of time in time functions
     pub time_offset: &'a mut u32,
+
+    /// Whether the user asked to stop being shown uncaught script errors.
+    pub script_errors_suppressed: &'a mut bool,
+
+    /// The player's queue of asynchronous tasks.
+    pub executor: &'a mut Executor,
+
+    /// Where the code currently being run came from, for use in log messages.
+    pub log_context: LogContext<'gc>,
+
+    /// The player's `NetStream`s.
+    pub stream_manager: &'a mut StreamManager<'gc>,
+
+    /// The player's `Socket`s and `XMLSocket`s.
+    pub socket_manager: &'a mut SocketManager<'gc>,
+
+    /// The player's `LocalConnection`s.
+    pub local_connection_manager: &'a mut LocalConnectionManager<'gc>,
+}
+
+/// Identifies the display object whose code is currently running.
+///
+/// Activations set this when they are created, so it is scoped to the update
+/// context they own and reverts once they finish.
+#[derive(Copy, Clone, Default)]
+pub struct LogContext<'gc> {
+    clip: Option<DisplayObject<'gc>>,
+}
+
+impl<'gc> LogContext<'gc> {
+    /// A log context for code running on behalf of the given display object.
+    pub fn for_clip(clip: DisplayObject<'gc>) -> Self {
+        Self { clip: Some(clip) }
+    }
+
+    /// The display object whose code is running, if any.
+    pub fn clip(&self) -> Option<DisplayObject<'gc>> {
+        self.clip
+    }
+
+    /// Describe this context as a suffix for log messages.
+    ///
+    /// This names the clip's path, its character ID along with any symbol name
+    /// that the movie gave it, and the URL of the movie the clip belongs to.
+    /// Returns an empty string if no clip's code is running.
+    pub fn describe(&self, library: &Library<'gc>) -> String {
+        let clip = match self.clip {
+            Some(clip) => clip,
+            None => return String::new(),
+        };
+
+        let mut description = format!(" -- in {}", clip.path());
+        let id = clip.id();
+        let movie = clip.movie();
+        if id != 0 {
+            description.push_str(&format!(" (character {}", id));
+            let debug_name = movie
+                .clone()
+                .and_then(|movie| library.library_for_movie(movie))
+                .and_then(|library| library.debug_name(id));
+            if let Some(debug_name) = debug_name {
+                description.push_str(&format!(" \"{}\"", debug_name));
+            }
+            description.push(')');
+        }
+        if let Some(url) = movie.as_ref().and_then(|movie| movie.url()) {
+            description.push_str(&format!(" of {}", url));
+        }
+        description
+    }
 }
 
 /// Convenience methods for controlling audio.
 impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
     pub fn update_sounds(&mut self) {
-        self.audio_manager.update_sounds(
+        for (instance, channel) in self.audio_manager.sounds_needing_samples(self.audio) {
+            if let Err(e) = Avm2::request_sample_data(self, instance, channel) {
+                log::error!("Encountered AVM2 error when requesting sample data: {}", e);
+            }
+        }
+
+        let completed_channels = self.audio_manager.update_sounds(
             self.audio,
             self.gc_context,
             self.action_queue,
             *self.levels.get(&0).unwrap(),
         );
+
+        for channel in completed_channels {
+            if let Err(e) =
+                Avm2::dispatch_event(self, Avm2Event::new("soundComplete"), channel.into())
+            {
+                self.report_script_error(
+                    format!("Encountered AVM2 error when dispatching soundComplete: {}", e),
+                    vec![],
+                );
+            }
+        }
     }
 
     pub fn global_sound_transform(&self) -> &SoundTransform {
@@ -191,6 +342,28 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             .start_sound(self.audio, sound, settings, owner, avm1_object)
     }
 
+    pub fn start_dynamic_sound(
+        &mut self,
+        avm2_object: SoundChannelObject<'gc>,
+    ) -> Option<SoundInstanceHandle> {
+        self.audio_manager
+            .start_dynamic_sound(self.audio, avm2_object)
+    }
+
+    pub fn finish_dynamic_sound(&mut self, instance: SoundInstanceHandle) {
+        self.audio_manager
+            .finish_dynamic_sound(self.audio, instance)
+    }
+
+    pub fn attach_avm2_sound_channel(
+        &mut self,
+        instance: SoundInstanceHandle,
+        avm2_object: SoundChannelObject<'gc>,
+    ) {
+        self.audio_manager
+            .attach_avm2_sound_channel(self.audio, instance, avm2_object)
+    }
+
     pub fn stop_sound(&mut self, instance: SoundInstanceHandle) {
         self.audio_manager.stop_sound(self.audio, instance)
     }
@@ -251,6 +424,12 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
         UpdateContext {
             action_queue: self.action_queue,
             background_color: self.background_color,
+            frame_rate: self.frame_rate,
+            quality: self.quality,
+            stage_scale_mode: self.stage_scale_mode,
+            stage_align: self.stage_align,
+            orientation: self.orientation,
+            text_rendering: self.text_rendering,
             gc_context: self.gc_context,
             library: self.library,
             player_version: self.player_version,
@@ -263,17 +442,25 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             locale: self.locale,
             log: self.log,
             ui: self.ui,
+            clipboard: self.clipboard,
             video: self.video,
+            socket: self.socket,
+            local_connection: self.local_connection,
+            print: self.print,
             storage: self.storage,
             rng: self.rng,
             levels: self.levels,
             mouse_hovered_object: self.mouse_hovered_object,
             mouse_position: self.mouse_position,
+            keyboard: self.keyboard,
             drag_object: self.drag_object,
             stage_size: self.stage_size,
             player: self.player.clone(),
             load_manager: self.load_manager,
             system: self.system,
+            security: self.security,
+            messages: self.messages,
+            telemetry: self.telemetry,
             instance_counter: self.instance_counter,
             shared_objects: self.shared_objects,
             unbound_text_fields: self.unbound_text_fields,
@@ -286,6 +473,40 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             focus_tracker: self.focus_tracker,
             times_get_time_called: self.times_get_time_called,
             time_offset: self.time_offset,
+            script_errors_suppressed: self.script_errors_suppressed,
+            executor: self.executor,
+            log_context: self.log_context,
+            stream_manager: self.stream_manager,
+            socket_manager: self.socket_manager,
+            local_connection_manager: self.local_connection_manager,
+        }
+    }
+
+    /// Describes where the code currently being run came from.
+    ///
+    /// This is appended to script warnings, so that they say which symbol in
+    /// which movie produced them.
+    pub fn log_context_description(&self) -> String {
+        self.log_context.describe(self.library)
+    }
+
+    /// Reports an uncaught ActionScript error to the UI backend.
+    ///
+    /// The error is always logged. It is only shown to the user if they have
+    /// not previously chosen to suppress further errors.
+    pub fn report_script_error(&mut self, message: String, stack_trace: Vec<String>) {
+        let error = ScriptError {
+            message,
+            stack_trace,
+            movie_url: self.swf.url().map(|url| url.to_string()),
+        };
+        log::error!("Uncaught script error: {}", error);
+
+        if !*self.script_errors_suppressed
+            && self.ui.display_script_error(&error, self.messages)
+                == ScriptErrorResponse::SuppressFurtherErrors
+        {
+            *self.script_errors_suppressed = true;
         }
     }
 }
@@ -364,12 +585,82 @@ impl<'gc> Default for ActionQueue<'gc> {
     }
 }
 
+/// A view of the stage in a render target.
+///
+/// The player keeps one view for its own viewport, and can draw the same stage
+/// into any number of other views, such as an inspector zoomed in on part of
+/// the stage.
+#[derive(Clone, Debug)]
+pub struct View {
+    /// The transform from the stage into the viewport.
+    pub matrix: Matrix,
+
+    /// The area of the stage that is visible in the viewport, in stage
+    /// coordinates. Anything outside of it is culled.
+    pub bounds: BoundingBox,
+}
+
+impl View {
+    /// Create a view that shows the given area of the stage, scaled to fit a
+    /// viewport of the given size in pixels and centered in it.
+    pub fn for_area(area: &BoundingBox, viewport_width: u32, viewport_height: u32) -> Self {
+        let area_width = area.width().to_pixels().max(1.0);
+        let area_height = area.height().to_pixels().max(1.0);
+        let scale =
+            (f64::from(viewport_width) / area_width).min(f64::from(viewport_height) / area_height);
+        let margin_width = (f64::from(viewport_width) - area_width * scale) / 2.0;
+        let margin_height = (f64::from(viewport_height) - area_height * scale) / 2.0;
+
+        let matrix = Matrix {
+            a: scale as f32,
+            b: 0.0,
+            c: 0.0,
+            d: scale as f32,
+            tx: Twips::from_pixels(margin_width - area.x_min.to_pixels() * scale),
+            ty: Twips::from_pixels(margin_height - area.y_min.to_pixels() * scale),
+        };
+
+        let mut view = Self {
+            matrix,
+            bounds: Default::default(),
+        };
+        view.bounds = view.stage_area(viewport_width, viewport_height);
+        view
+    }
+
+    /// The area of the stage that is visible in a viewport of the given size.
+    pub fn stage_area(&self, viewport_width: u32, viewport_height: u32) -> BoundingBox {
+        let viewport = BoundingBox {
+            x_min: Twips::zero(),
+            y_min: Twips::zero(),
+            x_max: Twips::from_pixels(viewport_width.into()),
+            y_max: Twips::from_pixels(viewport_height.into()),
+            valid: true,
+        };
+        let mut inverse = self.matrix;
+        inverse.invert();
+        viewport.transform(&inverse)
+    }
+
+    /// Convert a position in the viewport, in pixels, to stage coordinates.
+    pub fn to_stage(&self, x: f64, y: f64) -> (Twips, Twips) {
+        let mut inverse = self.matrix;
+        inverse.invert();
+        inverse * (Twips::from_pixels(x), Twips::from_pixels(y))
+    }
+}
+
 /// Shared data used during rendering.
 /// `Player` creates this when it renders a frame and passes it down to display objects.
 pub struct RenderContext<'a, 'gc> {
-    /// The renderer, used by the display objects to draw themselves.
+    /// The renderer, used by the display objects to register the bitmaps they
+    /// draw offscreen.
     pub renderer: &'a mut dyn RenderBackend,
 
+    /// The commands that the display objects record to draw themselves, which
+    /// are submitted to the renderer once the whole frame is recorded.
+    pub commands: CommandList,
+
     /// The library, which provides access to fonts and other definitions when rendering.
     pub library: &'a Library<'gc>,
 
@@ -384,6 +675,9 @@ pub struct RenderContext<'a, 'gc> {
     /// Whether to allow pushing a new mask. A masker-inside-a-masker does not work in Flash, instead
     /// causing the inner mask to be included as part of the outer mask. Maskee-inside-a-maskee works as one expects.
     pub allow_mask: bool,
+
+    /// How glyphs in text are positioned, at the current stage quality.
+    pub text_rendering: TextRendering,
 }
 
 /// The type of action being run.
@@ -0,0 +1,288 @@
+//! Movie protection and the debugger password policy.
+//!
+//! A movie can declare that it must not be imported into authoring tools with
+//! a `Protect` tag, and that a debugger may attach to it with an
+//! `EnableDebugger` or `EnableDebugger2` tag. Both tags optionally carry the
+//! MD5-crypt hash of a password, which Flash Player checks before allowing a
+//! debugger to connect.
+
+use crate::tag_utils::SwfStream;
+use swf::{Tag, TagCode};
+
+/// The protection and debugging settings declared by a movie's tags.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MovieProtection {
+    /// Set if the movie contains a `Protect` tag, to the password hash it
+    /// holds. The hash is empty if no password was given.
+    protect_password: Option<String>,
+
+    /// Set if the movie contains an `EnableDebugger` tag, to the password
+    /// hash it holds. The hash is empty if no password is required.
+    debugger_password: Option<String>,
+}
+
+impl MovieProtection {
+    /// Read the protection settings of a movie from its tags.
+    ///
+    /// Only the tags before the first frame is shown are considered, which is
+    /// where authoring tools place them.
+    pub fn from_tags(reader: &mut SwfStream<'_>, encoding: &'static swf::Encoding) -> Self {
+        let mut protection = Self::default();
+
+        loop {
+            let tag_start = reader.get_ref();
+            let (tag_code, tag_len) = match reader.read_tag_code_and_length() {
+                Ok(tag) => tag,
+                Err(_) => break,
+            };
+            if tag_len > reader.get_ref().len() {
+                break;
+            }
+
+            match TagCode::from_u16(tag_code) {
+                Some(TagCode::Protect)
+                | Some(TagCode::EnableDebugger)
+                | Some(TagCode::EnableDebugger2) => {
+                    *reader.get_mut() = tag_start;
+                    match reader.read_tag() {
+                        Ok(Tag::Protect(password)) => {
+                            protection.protect_password = Some(
+                                password
+                                    .map(|password| password.to_string_lossy(encoding))
+                                    .unwrap_or_default(),
+                            );
+                        }
+                        Ok(Tag::EnableDebugger(password)) => {
+                            protection.debugger_password = Some(password.to_string_lossy(encoding));
+                        }
+                        Ok(_) => {}
+                        Err(e) => log::warn!("Error reading protection tag: {}", e),
+                    }
+                    continue;
+                }
+                Some(TagCode::ShowFrame) | Some(TagCode::End) => break,
+                _ => {}
+            }
+
+            *reader.get_mut() = &reader.get_ref()[tag_len..];
+        }
+
+        protection
+    }
+
+    /// Whether the movie asks not to be imported into authoring tools.
+    pub fn is_protected(&self) -> bool {
+        self.protect_password.is_some()
+    }
+
+    /// Whether the movie allows a debugger to attach to it.
+    pub fn is_debugger_enabled(&self) -> bool {
+        self.debugger_password.is_some()
+    }
+
+    /// The MD5-crypt hash of the password required to attach a debugger, if
+    /// the movie allows one at all.
+    pub fn debugger_password_hash(&self) -> Option<&str> {
+        self.debugger_password.as_deref()
+    }
+
+    /// Check whether a debugger may attach to the movie with the given
+    /// password.
+    ///
+    /// Like Flash Player, a movie without an `EnableDebugger` tag can't be
+    /// debugged, and one without a password hash can be debugged with any
+    /// password.
+    pub fn can_attach_debugger(&self, password: &str) -> bool {
+        match self.debugger_password.as_deref() {
+            None => false,
+            Some("") => true,
+            Some(hash) => verify_md5_crypt(password, hash),
+        }
+    }
+}
+
+/// Check a password against an MD5-crypt hash, of the form
+/// `$1$<salt>$<hash>`.
+fn verify_md5_crypt(password: &str, hash: &str) -> bool {
+    let salt = match hash
+        .strip_prefix("$1$")
+        .and_then(|rest| rest.split('$').next())
+    {
+        Some(salt) => salt,
+        None => return false,
+    };
+
+    md5_crypt(password.as_bytes(), salt.as_bytes()) == hash
+}
+
+/// Hash a password with the MD5-based crypt scheme used by `EnableDebugger2`
+/// tags.
+fn md5_crypt(password: &[u8], salt: &[u8]) -> String {
+    const MAGIC: &[u8] = b"$1$";
+    const ITOA64: &[u8] = b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+    let salt = &salt[..salt.len().min(8)];
+
+    let alternate = md5(&[password, salt, password].concat());
+
+    let mut input = [password, MAGIC, salt].concat();
+    for chunk in alternate.iter().cycle().take(password.len()) {
+        input.push(*chunk);
+    }
+    let mut i = password.len();
+    while i > 0 {
+        input.push(if i & 1 != 0 { 0 } else { password[0] });
+        i >>= 1;
+    }
+    let mut digest = md5(&input);
+
+    for round in 0..1000 {
+        let mut input = Vec::with_capacity(password.len() * 2 + salt.len() + 16);
+        if round & 1 != 0 {
+            input.extend_from_slice(password);
+        } else {
+            input.extend_from_slice(&digest);
+        }
+        if round % 3 != 0 {
+            input.extend_from_slice(salt);
+        }
+        if round % 7 != 0 {
+            input.extend_from_slice(password);
+        }
+        if round & 1 != 0 {
+            input.extend_from_slice(&digest);
+        } else {
+            input.extend_from_slice(password);
+        }
+        digest = md5(&input);
+    }
+
+    let mut output = String::with_capacity(MAGIC.len() + salt.len() + 23);
+    output.push_str("$1$");
+    output.push_str(&String::from_utf8_lossy(salt));
+    output.push('$');
+    let mut encode = |value: u32, count: usize| {
+        for i in 0..count {
+            output.push(ITOA64[((value >> (6 * i)) & 0x3f) as usize].into());
+        }
+    };
+    for &(a, b, c) in &[(0, 6, 12), (1, 7, 13), (2, 8, 14), (3, 9, 15), (4, 10, 5)] {
+        encode(
+            (u32::from(digest[a]) << 16) | (u32::from(digest[b]) << 8) | u32::from(digest[c]),
+            4,
+        );
+    }
+    encode(u32::from(digest[11]), 2);
+
+    output
+}
+
+/// Compute the MD5 digest of some data.
+fn md5(data: &[u8]) -> [u8; 16] {
+    const SHIFTS: [u32; 64] = [
+        7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5,
+        9, 14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10,
+        15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+    ];
+
+    let constants: Vec<u32> = (0..64)
+        .map(|i| ((i as f64 + 1.0).sin().abs() * 4_294_967_296.0) as u32)
+        .collect();
+
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64).wrapping_mul(8)).to_le_bytes());
+
+    let mut state: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+    for block in message.chunks_exact(64) {
+        let mut words = [0u32; 16];
+        for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+        }
+
+        let [mut a, mut b, mut c, mut d] = state;
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+            let f = f
+                .wrapping_add(a)
+                .wrapping_add(constants[i])
+                .wrapping_add(words[g]);
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(f.rotate_left(SHIFTS[i]));
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+    }
+
+    let mut digest = [0; 16];
+    for (bytes, word) in digest.chunks_exact_mut(4).zip(&state) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn md5_digest() {
+        let hex = |digest: [u8; 16]| -> String {
+            digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+        };
+        assert_eq!(hex(md5(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(
+            hex(md5(b"The quick brown fox jumps over the lazy dog")),
+            "9e107d9d372bb6826bd81d3542a419d6"
+        );
+    }
+
+    #[test]
+    fn md5_crypt_hash() {
+        assert_eq!(
+            md5_crypt(b"password", b"saltsalt"),
+            "$1$saltsalt$qjXMvbEw8oaL.CzflDtaK/"
+        );
+        assert_eq!(md5_crypt(b"", b"abc"), "$1$abc$Or2rbeUYTvt12aiVzMuS/.");
+        assert_eq!(
+            md5_crypt(
+                b"ruffle debugger password that is long enough to exceed one block of sixty four bytes",
+                b"UoT/"
+            ),
+            "$1$UoT/$jyEHNatH8gzjMdagzQPRJ."
+        );
+    }
+
+    #[test]
+    fn debugger_password_policy() {
+        let disabled = MovieProtection::default();
+        assert!(!disabled.can_attach_debugger(""));
+
+        let no_password = MovieProtection {
+            protect_password: None,
+            debugger_password: Some(String::new()),
+        };
+        assert!(no_password.can_attach_debugger("anything"));
+
+        let password = MovieProtection {
+            protect_password: Some(String::new()),
+            debugger_password: Some("$1$saltsalt$qjXMvbEw8oaL.CzflDtaK/".to_string()),
+        };
+        assert!(password.is_protected());
+        assert!(password.can_attach_debugger("password"));
+        assert!(!password.can_attach_debugger("wrong"));
+    }
+}
@@ -2,6 +2,13 @@ use crate::avm1::{
     Error as Avm1Error, Object as Avm1Object, TObject as Avm1TObject, Value as Avm1Value,
 };
 use crate::avm2::{Avm2, Event as Avm2Event, TObject as Avm2TObject, Value as Avm2Value};
+use crate::backend::render::{BitmapHandle, RenderBackend};
+use crate::bitmap::bitmap_data::BitmapData;
+use crate::bitmap::filters::{
+    apply_filter, apply_mask, filter_padding, preserves_source, remove_source,
+};
+use crate::bitmap::rasterizer::Rasterizer;
+use crate::commands::CommandHandler;
 use crate::context::{RenderContext, UpdateContext};
 use crate::drawing::Drawing;
 use crate::player::NEWEST_PLAYER_VERSION;
@@ -13,10 +20,11 @@ use crate::vminterface::{AvmType, Instantiator};
 use bitflags::bitflags;
 use gc_arena::{Collect, MutationContext};
 use ruffle_macros::enum_trait_object;
-use std::cell::{Ref, RefMut};
+use std::cell::{Cell, Ref, RefCell, RefMut};
 use std::cmp::min;
 use std::fmt::Debug;
 use std::sync::Arc;
+use swf::{BlendMode, Filter, Rectangle};
 
 mod bitmap;
 mod button;
@@ -35,7 +43,7 @@ pub use crate::display_object::container::{
 };
 use crate::events::{ClipEvent, ClipEventResult};
 pub use bitmap::Bitmap;
-pub use button::Button;
+pub use button::{Avm2ButtonStates, Button};
 pub use edit_text::{AutoSizeMode, EditText, TextSelection};
 pub use graphic::Graphic;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
@@ -82,6 +90,29 @@ pub struct DisplayObjectBase<'gc> {
 
     /// Bit flags for various display object properites.
     flags: DisplayObjectFlags,
+
+    /// The filters applied to this display object when it is rendered.
+    #[collect(require_static)]
+    filters: Vec<Filter>,
+
+    /// The bitmap that this display object was last rendered into offscreen,
+    /// to apply its filters or because it is cached as a bitmap.
+    #[collect(require_static)]
+    bitmap_cache: Cell<Option<BitmapCache>>,
+
+    /// How this display object is combined with what is drawn behind it.
+    #[collect(require_static)]
+    blend_mode: BlendMode,
+
+    /// The scale-9 grid of this display object, which keeps its corners from
+    /// being scaled. An empty rectangle means there is no grid.
+    #[collect(require_static)]
+    scaling_grid: Rectangle,
+
+    /// How this display object changed since it was last rendered, to find
+    /// the areas of the stage that have to be drawn again.
+    #[collect(require_static)]
+    dirty_state: DirtyState,
 }
 
 impl<'gc> Default for DisplayObjectBase<'gc> {
@@ -102,7 +133,14 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             masker: None,
             maskee: None,
             sound_transform: Default::default(),
-            flags: DisplayObjectFlags::VISIBLE,
+            flags: DisplayObjectFlags::VISIBLE
+                | DisplayObjectFlags::MOUSE_ENABLED
+                | DisplayObjectFlags::MOUSE_CHILDREN,
+            filters: Vec::new(),
+            bitmap_cache: Cell::new(None),
+            blend_mode: BlendMode::Normal,
+            scaling_grid: Default::default(),
+            dirty_state: Default::default(),
         }
     }
 }
@@ -112,7 +150,10 @@ impl<'gc> DisplayObjectBase<'gc> {
     /// Reset all properties that would be adjusted by a movie load.
     fn reset_for_movie_load(&mut self) {
         let flags_to_keep = self.flags & DisplayObjectFlags::LOCK_ROOT;
-        self.flags = flags_to_keep | DisplayObjectFlags::VISIBLE;
+        self.flags = flags_to_keep
+            | DisplayObjectFlags::VISIBLE
+            | DisplayObjectFlags::MOUSE_ENABLED
+            | DisplayObjectFlags::MOUSE_CHILDREN;
     }
 
     fn id(&self) -> CharacterId {
@@ -354,6 +395,50 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.sound_transform = sound_transform;
     }
 
+    fn filters(&self) -> &[Filter] {
+        &self.filters
+    }
+
+    fn set_filters(&mut self, filters: Vec<Filter>) {
+        self.filters = filters;
+    }
+
+    fn bitmap_cache(&self) -> Option<BitmapCache> {
+        self.bitmap_cache.get()
+    }
+
+    fn set_bitmap_cache(&self, cache: Option<BitmapCache>) {
+        self.bitmap_cache.set(cache);
+    }
+
+    fn is_bitmap_cached(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::CACHE_AS_BITMAP)
+    }
+
+    fn set_is_bitmap_cached(&mut self, value: bool) {
+        self.flags.set(DisplayObjectFlags::CACHE_AS_BITMAP, value);
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    fn scaling_grid(&self) -> &Rectangle {
+        &self.scaling_grid
+    }
+
+    fn set_scaling_grid(&mut self, scaling_grid: Rectangle) {
+        self.scaling_grid = scaling_grid;
+    }
+
+    fn dirty_state(&self) -> &DirtyState {
+        &self.dirty_state
+    }
+
     fn visible(&self) -> bool {
         self.flags.contains(DisplayObjectFlags::VISIBLE)
     }
@@ -362,6 +447,22 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.flags.set(DisplayObjectFlags::VISIBLE, value);
     }
 
+    fn mouse_enabled(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::MOUSE_ENABLED)
+    }
+
+    fn set_mouse_enabled(&mut self, value: bool) {
+        self.flags.set(DisplayObjectFlags::MOUSE_ENABLED, value);
+    }
+
+    fn mouse_children(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::MOUSE_CHILDREN)
+    }
+
+    fn set_mouse_children(&mut self, value: bool) {
+        self.flags.set(DisplayObjectFlags::MOUSE_CHILDREN, value);
+    }
+
     fn lock_root(&self) -> bool {
         self.flags.contains(DisplayObjectFlags::LOCK_ROOT)
     }
@@ -453,6 +554,14 @@ pub trait TDisplayObject<'gc>:
     /// Composite DisplayObjects that only contain children should return `&Default::default()`
     fn self_bounds(&self) -> BoundingBox;
 
+    /// The untransformed inherent bounding box of this object, leaving out
+    /// the width of any strokes.
+    ///
+    /// Objects without strokes can leave this as their `self_bounds`.
+    fn self_edge_bounds(&self) -> BoundingBox {
+        self.self_bounds()
+    }
+
     /// The untransformed bounding box of this object including children.
     fn bounds(&self) -> BoundingBox {
         self.bounds_with_transform(&Matrix::default())
@@ -485,6 +594,21 @@ pub trait TDisplayObject<'gc>:
         bounds
     }
 
+    /// Gets the bounds of this object and all children, transformed by a given matrix,
+    /// leaving out the width of strokes like `self_edge_bounds`.
+    fn edge_bounds_with_transform(&self, matrix: &Matrix) -> BoundingBox {
+        let mut bounds = self.self_edge_bounds().transform(matrix);
+
+        if let Some(ctr) = self.as_container() {
+            for child in ctr.iter_execution_list() {
+                let matrix = *matrix * *child.matrix();
+                bounds.union(&child.edge_bounds_with_transform(&matrix));
+            }
+        }
+
+        bounds
+    }
+
     fn place_frame(&self) -> u16;
     fn set_place_frame(&self, gc_context: MutationContext<'gc, '_>, frame: u16);
 
@@ -511,6 +635,18 @@ pub trait TDisplayObject<'gc>:
         matrix
     }
 
+    /// Returns the color transform that this object is drawn with, which
+    /// includes the color transforms of all of its parents.
+    fn local_to_global_color_transform(&self) -> ColorTransform {
+        let mut node = self.parent();
+        let mut color_transform = *self.color_transform();
+        while let Some(display_object) = node {
+            color_transform = display_object.color_transform().concat(&color_transform);
+            node = display_object.parent();
+        }
+        color_transform
+    }
+
     /// Returns the matrix for transforming from global stage to this object's local space.
     fn global_to_local_matrix(&self) -> Matrix {
         let mut matrix = self.local_to_global_matrix();
@@ -775,6 +911,22 @@ pub trait TDisplayObject<'gc>:
     /// Returned by the `_visible`/`visible` ActionScript properties.
     fn set_visible(&self, gc_context: MutationContext<'gc, '_>, value: bool);
 
+    /// Whether this display object can be the target of mouse events.
+    /// Returned by the AVM2 `mouseEnabled` property.
+    fn mouse_enabled(&self) -> bool;
+
+    /// Sets whether this display object can be the target of mouse events.
+    fn set_mouse_enabled(&self, gc_context: MutationContext<'gc, '_>, value: bool);
+
+    /// Whether the children of this display object can be the targets of
+    /// mouse events. If not, the mouse hits them on behalf of this object.
+    /// Returned by the AVM2 `mouseChildren` property.
+    fn mouse_children(&self) -> bool;
+
+    /// Sets whether the children of this display object can be the targets of
+    /// mouse events.
+    fn set_mouse_children(&self, gc_context: MutationContext<'gc, '_>, value: bool);
+
     /// The sound transform for sounds played inside this display object.
     fn sound_transform(&self) -> Ref<SoundTransform>;
 
@@ -785,6 +937,144 @@ pub trait TDisplayObject<'gc>:
         sound_transform: SoundTransform,
     );
 
+    /// The filters applied to this display object when it is rendered.
+    /// Returned by the `filters` ActionScript property.
+    fn filters(&self) -> Ref<[Filter]>;
+
+    /// Sets the filters applied to this display object when it is rendered.
+    /// Set by the `filters` ActionScript property.
+    fn set_filters(&self, gc_context: MutationContext<'gc, '_>, filters: Vec<Filter>);
+
+    /// The bitmap this display object was last rendered into offscreen.
+    fn bitmap_cache(&self) -> Option<BitmapCache>;
+
+    /// Sets the bitmap this display object was rendered into offscreen.
+    fn set_bitmap_cache(&self, cache: Option<BitmapCache>);
+
+    /// Marks the bitmap caches of this display object and its ancestors as
+    /// out of date, so that they are rendered again before they are next
+    /// drawn.
+    ///
+    /// This must be called whenever the appearance of the object changes in a
+    /// way that isn't captured by its world transform.
+    fn invalidate_cached_bitmap(&self) {
+        if let Some(mut cache) = self.bitmap_cache() {
+            cache.valid = false;
+            self.set_bitmap_cache(Some(cache));
+        }
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap();
+        }
+    }
+
+    /// Frees the bitmaps this display object and its children were rendered
+    /// into offscreen, once they are removed from the display list.
+    fn release_bitmap_caches(&self, renderer: &mut dyn RenderBackend) {
+        release_bitmap_cache((*self).into(), renderer);
+        if let Some(container) = self.as_container() {
+            for child in container.iter_render_list() {
+                child.release_bitmap_caches(renderer);
+            }
+        }
+    }
+
+    /// Whether this display object is rendered through a bitmap cache.
+    /// Returned by the `cacheAsBitmap` ActionScript property.
+    fn is_bitmap_cached(&self) -> bool;
+
+    /// Sets whether this display object is rendered through a bitmap cache.
+    /// Set by the `cacheAsBitmap` ActionScript property.
+    fn set_is_bitmap_cached(&self, gc_context: MutationContext<'gc, '_>, value: bool);
+
+    /// How this display object is combined with what is drawn behind it.
+    /// Returned by the `blendMode` ActionScript property.
+    fn blend_mode(&self) -> BlendMode;
+
+    /// Sets how this display object is combined with what is drawn behind it.
+    /// Set by the `blendMode` ActionScript property.
+    fn set_blend_mode(&self, gc_context: MutationContext<'gc, '_>, blend_mode: BlendMode);
+
+    /// The scale-9 grid of this display object, in its local space.
+    /// Returned by the `scale9Grid` ActionScript property.
+    ///
+    /// The rectangle is empty if the object has no grid.
+    fn scaling_grid(&self) -> Rectangle;
+
+    /// Sets the scale-9 grid of this display object.
+    /// Set by the `scale9Grid` ActionScript property, and by the
+    /// `DefineScalingGrid` tag for the character it was created from.
+    fn set_scaling_grid(&self, gc_context: MutationContext<'gc, '_>, scaling_grid: Rectangle);
+
+    /// How this display object changed since it was last rendered.
+    fn dirty_state(&self) -> Ref<DirtyState>;
+
+    /// Marks this display object as changed as a whole since it was last
+    /// rendered, such as by being moved, so that the stage is drawn again
+    /// where it was and where it now is.
+    fn invalidate_render(&self) {
+        self.dirty_state().mark();
+        if let Some(parent) = self.parent() {
+            parent.invalidate_cached_bitmap();
+        }
+    }
+
+    /// Marks the content of this display object as changed since it was last
+    /// rendered, so that it is drawn again.
+    fn invalidate_content(&self) {
+        self.dirty_state().mark();
+        self.invalidate_cached_bitmap();
+    }
+
+    /// Records that `child` was removed from this display object, so that the
+    /// stage is drawn again where the child was last rendered.
+    fn invalidate_removed_child(&self, child: DisplayObject<'gc>) {
+        self.dirty_state()
+            .add_removed(&child.dirty_state().bounds());
+        self.invalidate_cached_bitmap();
+    }
+
+    /// Adds the areas of the viewport that changed since this display object
+    /// and its children were last rendered to `region`, and remembers where
+    /// they are now for the next frame.
+    ///
+    /// `view_matrix` maps the stage into the viewport, and `padding` is how far
+    /// the filters of the ancestors of this object spread its content, in
+    /// pixels.
+    fn collect_dirty_region(
+        &self,
+        view_matrix: &Matrix,
+        padding: (i32, i32),
+        region: &mut BoundingBox,
+    ) {
+        let padding = add_filter_padding(padding, &self.filters());
+        let bounds = if self.visible() {
+            render_area(
+                (*self).into(),
+                &(*view_matrix * self.local_to_global_matrix()),
+                padding,
+            )
+        } else {
+            Default::default()
+        };
+
+        let state = self.dirty_state();
+        if state.dirty.replace(false) {
+            region.union(&state.bounds.borrow());
+            region.union(&bounds);
+        }
+        region.union(&state.removed_bounds.replace(Default::default()));
+        state.bounds.replace(bounds);
+        drop(state);
+
+        if self.visible() {
+            if let Some(container) = self.as_container() {
+                for child in container.iter_render_list() {
+                    child.collect_dirty_region(view_matrix, padding, region);
+                }
+            }
+        }
+    }
+
     /// Whether this display object is used as the _root of itself and its children.
     /// Returned by the `_lockroot` ActionScript property.
     fn lock_root(&self) -> bool;
@@ -921,35 +1211,56 @@ pub trait TDisplayObject<'gc>:
         if self.maskee().is_some() {
             return;
         }
-        context.transform_stack.push(&*self.transform());
 
+        let this: DisplayObject<'gc> = (*self).into();
         let mask = self.masker();
-        let mut mask_transform = crate::transform::Transform::default();
-        if let Some(m) = mask {
-            mask_transform.matrix = self.global_to_local_matrix();
-            mask_transform.matrix *= m.local_to_global_matrix();
-            context.renderer.push_mask();
-            context.allow_mask = false;
-            context.transform_stack.push(&mask_transform);
-            m.render_self(context);
-            context.transform_stack.pop();
-            context.allow_mask = true;
-            context.renderer.activate_mask();
-        }
-        self.render_self(context);
-        if let Some(m) = mask {
-            context.renderer.deactivate_mask();
-            context.allow_mask = false;
-            context.transform_stack.push(&mask_transform);
-            m.render_self(context);
-            context.transform_stack.pop();
-            context.allow_mask = true;
-            context.renderer.pop_mask();
+        if let Some(mask) = mask.filter(|mask| is_soft_mask(*mask)) {
+            let mask_matrix =
+                *self.matrix() * self.global_to_local_matrix() * mask.local_to_global_matrix();
+            if render_soft_masked(&[this], mask, mask_matrix, context) {
+                return;
+            }
+        }
+
+        context.transform_stack.push(&*self.transform());
+        match mask {
+            Some(mask) => render_clipped(this, mask, context),
+            None => render_content(this, context),
         }
 
         context.transform_stack.pop();
     }
 
+    /// Draw this object into a bitmap with the software rasterizer, in its
+    /// own local space.
+    ///
+    /// This is used by `BitmapData.draw` and display filters. Objects that
+    /// can't be rasterized draw nothing.
+    fn rasterize_self(&self, _renderer: &mut dyn RenderBackend, _rasterizer: &mut Rasterizer<'_>) {}
+
+    /// Draw this object into a bitmap with the software rasterizer, in the
+    /// local space of its parent.
+    ///
+    /// Masks are not supported, so masking objects are skipped and the
+    /// objects they mask are drawn in full.
+    fn rasterize(&self, renderer: &mut dyn RenderBackend, rasterizer: &mut Rasterizer<'_>) {
+        if self.maskee().is_some() {
+            return;
+        }
+
+        let blend_mode = self.blend_mode();
+        let parent_blend_mode = rasterizer.blend_mode;
+        if !matches!(blend_mode, BlendMode::Normal | BlendMode::Layer) {
+            rasterizer.blend_mode = blend_mode;
+        }
+
+        rasterizer.transform_stack.push(&*self.transform());
+        self.rasterize_self(renderer, rasterizer);
+        rasterizer.transform_stack.pop();
+
+        rasterizer.blend_mode = parent_blend_mode;
+    }
+
     fn unload(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
         // Unload children.
         if let Some(ctr) = self.as_container() {
@@ -992,10 +1303,21 @@ pub trait TDisplayObject<'gc>:
     fn as_video(self) -> Option<Video<'gc>> {
         None
     }
+    fn as_bitmap(self) -> Option<Bitmap<'gc>> {
+        None
+    }
     fn as_drawing(&self, _gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
         None
     }
 
+    /// The drawing of this object, if it has one.
+    ///
+    /// Unlike `as_drawing`, this doesn't replace the shape of a timeline
+    /// graphic with an empty drawing.
+    fn drawing(&self) -> Option<Ref<'_, Drawing>> {
+        None
+    }
+
     fn apply_place_object(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1025,6 +1347,15 @@ pub trait TDisplayObject<'gc>:
                     video.seek(context, ratio.into());
                 }
             }
+            if let Some(filters) = &place_object.filters {
+                self.set_filters(context.gc_context, filters.clone());
+            }
+            if let Some(blend_mode) = place_object.blend_mode {
+                self.set_blend_mode(context.gc_context, blend_mode);
+            }
+            if let Some(is_bitmap_cached) = place_object.is_bitmap_cached {
+                self.set_is_bitmap_cached(context.gc_context, is_bitmap_cached);
+            }
             // Clip events only apply to movie clips.
             if let (Some(clip_actions), Some(clip)) =
                 (&place_object.clip_actions, self.as_movie_clip())
@@ -1061,6 +1392,8 @@ pub trait TDisplayObject<'gc>:
         self.set_color_transform(gc_context, &*other.color_transform());
         self.set_clip_depth(gc_context, other.clip_depth());
         self.set_name(gc_context, &*other.name());
+        self.set_filters(gc_context, other.filters().to_vec());
+        self.set_blend_mode(gc_context, other.blend_mode());
         if let (Some(mut me), Some(other)) = (self.as_morph_shape(), other.as_morph_shape()) {
             me.set_ratio(gc_context, other.ratio());
         }
@@ -1095,11 +1428,54 @@ pub trait TDisplayObject<'gc>:
         &self,
         _context: &mut UpdateContext<'_, 'gc, '_>,
         pos: (Twips, Twips),
+        options: HitTestOptions,
     ) -> bool {
+        if options.contains(HitTestOptions::SKIP_INVISIBLE) && !self.visible() {
+            return false;
+        }
+
         // Default to using bounding box.
         self.hit_test_bounds(pos)
     }
 
+    /// Whether this object is used as a mask, either by a script or as a
+    /// clipping layer. Masks are neither drawn nor picked by the mouse.
+    fn is_mask(&self) -> bool {
+        self.maskee().is_some() || (self.clip_depth() > 0 && self.allow_as_mask())
+    }
+
+    /// Tests if a given stage position is hidden from this object by a mask,
+    /// either the mask set on it by a script or a clipping layer of its parent
+    /// that it is placed under.
+    ///
+    /// `clipped` are the depths hidden by the clipping layers of the parent at
+    /// this position, as found by `clipped_depths_at`.
+    fn is_masked_at(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        pos: (Twips, Twips),
+        clipped: &[(Depth, Depth)],
+    ) -> bool {
+        if let Some(masker) = self.masker() {
+            if !masker.hit_test_shape(context, pos, HitTestOptions::empty()) {
+                return true;
+            }
+        }
+
+        let depth = self.depth();
+        clipped
+            .iter()
+            .any(|&(layer_depth, clip_depth)| layer_depth < depth && depth < clip_depth)
+    }
+
+    /// Find the object that receives mouse events when the mouse is over
+    /// `pos`, if it is this object or one of its children.
+    ///
+    /// Invisible objects, and everything inside them, are never picked. Fully
+    /// transparent objects are picked just like opaque ones. AVM1 clips and
+    /// buttons with `enabled` off are still picked, and so block whatever is
+    /// beneath them, but ignore the events. AVM2 objects also obey
+    /// `mouseEnabled` and `mouseChildren`; see `MovieClip::mouse_pick_avm2`.
     fn mouse_pick(
         &self,
         _context: &mut UpdateContext<'_, 'gc, '_>,
@@ -1268,6 +1644,690 @@ pub trait TDisplayObject<'gc>:
     }
 }
 
+/// The largest size, in pixels, of the offscreen bitmap a display object is
+/// rendered into to apply its filters or cache it as a bitmap.
+///
+/// Objects that would need a larger one are rendered directly, without
+/// filters.
+const MAX_FILTER_SIZE: u32 = 2880;
+
+/// The bitmap a display object was last rendered into offscreen, which is
+/// drawn again in later frames until the object changes.
+#[derive(Debug, Clone, Copy)]
+pub struct BitmapCache {
+    /// The texture holding the rendered object, or `None` if it drew nothing
+    /// offscreen and is rendered directly instead.
+    handle: Option<BitmapHandle>,
+
+    /// The world transform the object was rendered with.
+    matrix: Matrix,
+    color_transform: ColorTransform,
+
+    /// The position of the bitmap on the stage, in pixels, when the object
+    /// was rendered.
+    x_min: i32,
+    y_min: i32,
+
+    /// Whether the object still needs to be rendered over the bitmap.
+    render_source: bool,
+
+    /// Whether the bitmap still matches the object. Objects that can change
+    /// without invalidating it are never valid.
+    valid: bool,
+}
+
+impl BitmapCache {
+    /// Whether the bitmap can be drawn for an object with the given world
+    /// transform. Objects that have only moved reuse their bitmap at the new
+    /// position.
+    fn matches(&self, transform: &Transform) -> bool {
+        self.valid
+            && self.matrix.a == transform.matrix.a
+            && self.matrix.b == transform.matrix.b
+            && self.matrix.c == transform.matrix.c
+            && self.matrix.d == transform.matrix.d
+            && self.color_transform == transform.color_transform
+    }
+
+    /// Draw the bitmap for an object with the given world transform.
+    fn render(&self, context: &mut RenderContext<'_, '_>, transform: &Transform) {
+        let handle = match self.handle {
+            Some(handle) => handle,
+            None => return,
+        };
+        let transform = Transform {
+            matrix: Matrix::translate(
+                Twips::from_pixels(self.x_min.into()) + transform.matrix.tx - self.matrix.tx,
+                Twips::from_pixels(self.y_min.into()) + transform.matrix.ty - self.matrix.ty,
+            ),
+            color_transform: Default::default(),
+        };
+        context.commands.render_bitmap(handle, &transform, true);
+    }
+}
+
+/// How a display object changed since it was last rendered.
+///
+/// Each frame, the areas of the viewport that objects covered before and
+/// after they changed are redrawn, and the rest of the stage is left as it
+/// was.
+#[derive(Debug, Clone)]
+pub struct DirtyState {
+    /// Whether the object changed since it was last rendered.
+    dirty: Cell<bool>,
+
+    /// The area of the viewport the object covered when it was last rendered.
+    bounds: RefCell<BoundingBox>,
+
+    /// The areas of the viewport covered by children that were removed from
+    /// the object since it was last rendered.
+    removed_bounds: RefCell<BoundingBox>,
+}
+
+impl Default for DirtyState {
+    fn default() -> Self {
+        Self {
+            dirty: Cell::new(true),
+            bounds: Default::default(),
+            removed_bounds: Default::default(),
+        }
+    }
+}
+
+impl DirtyState {
+    /// Marks the object as changed since it was last rendered.
+    pub fn mark(&self) {
+        self.dirty.set(true);
+    }
+
+    /// The area of the viewport the object covered when it was last rendered.
+    pub fn bounds(&self) -> BoundingBox {
+        self.bounds.borrow().clone()
+    }
+
+    /// Adds an area of the viewport that has to be drawn again because a
+    /// child was removed from the object.
+    pub fn add_removed(&self, bounds: &BoundingBox) {
+        self.removed_bounds.borrow_mut().union(bounds);
+    }
+}
+
+/// Add how far `filters` spread the content of a display object to `padding`,
+/// in pixels.
+fn add_filter_padding(padding: (i32, i32), filters: &[Filter]) -> (i32, i32) {
+    filters.iter().fold(padding, |(padding_x, padding_y), filter| {
+        let (x, y) = filter_padding(filter);
+        (
+            padding_x.saturating_add(x.min(i32::MAX as u32) as i32),
+            padding_y.saturating_add(y.min(i32::MAX as u32) as i32),
+        )
+    })
+}
+
+/// The area of the viewport that a display object covers when drawn with
+/// `matrix`, grown by `padding` pixels for filters and a pixel for
+/// anti-aliasing.
+fn render_area(
+    display_object: DisplayObject<'_>,
+    matrix: &Matrix,
+    (padding_x, padding_y): (i32, i32),
+) -> BoundingBox {
+    let mut bounds = display_object.bounds_with_transform(matrix);
+    if bounds.valid {
+        let padding_x = Twips::from_pixels(f64::from(padding_x) + 1.0);
+        let padding_y = Twips::from_pixels(f64::from(padding_y) + 1.0);
+        bounds.x_min -= padding_x;
+        bounds.y_min -= padding_y;
+        bounds.x_max += padding_x;
+        bounds.y_max += padding_y;
+    }
+    bounds
+}
+
+/// Whether the content of a display object only changes when its bitmap cache
+/// is invalidated, so that it can be drawn from that cache.
+///
+/// Several bitmaps can show the same `BitmapData`, and only the first to run
+/// a frame after it changes notices, so those are always rendered again.
+fn is_static_content(display_object: DisplayObject<'_>) -> bool {
+    let is_static = match display_object {
+        DisplayObject::Bitmap(bitmap) => bitmap.bitmap_data().is_none(),
+        _ => true,
+    };
+    is_static
+        && display_object.as_container().map_or(true, |container| {
+            container.iter_render_list().all(is_static_content)
+        })
+}
+
+/// Free the bitmap a display object was last rendered into offscreen.
+fn release_bitmap_cache(display_object: DisplayObject<'_>, renderer: &mut dyn RenderBackend) {
+    if let Some(cache) = display_object.bitmap_cache() {
+        if let Some(handle) = cache.handle {
+            renderer.unregister_bitmap(handle);
+        }
+        display_object.set_bitmap_cache(None);
+    }
+}
+
+/// Whether a display object must be composited offscreen as a layer, so that
+/// children with the alpha and erase blend modes only affect its own content.
+fn needs_layer(display_object: DisplayObject<'_>) -> bool {
+    display_object.blend_mode() == BlendMode::Layer
+        && display_object.as_container().map_or(false, |container| {
+            container
+                .iter_render_list()
+                .any(|child| matches!(child.blend_mode(), BlendMode::Alpha | BlendMode::Erase))
+        })
+}
+
+/// Render a display object, with its blend mode, without its mask.
+fn render_content<'gc>(display_object: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
+    let push_blend_mode = push_blend_mode(display_object, context);
+    let offscreen = !display_object.filters().is_empty()
+        || needs_layer(display_object)
+        || (display_object.is_bitmap_cached() && is_static_content(display_object));
+    if !offscreen {
+        release_bitmap_cache(display_object, context.renderer);
+    }
+    if !offscreen || !render_offscreen(display_object, context) {
+        display_object.render_self(context);
+    }
+    if push_blend_mode {
+        context.commands.pop_blend_mode();
+    }
+}
+
+/// Start drawing with the blend mode of a display object.
+///
+/// Masks ignore blend modes, so this only applies to the object itself.
+/// Returns whether a blend mode was pushed, which must then be popped.
+fn push_blend_mode(display_object: DisplayObject<'_>, context: &mut RenderContext<'_, '_>) -> bool {
+    let blend_mode = context
+        .renderer
+        .capabilities()
+        .blend_mode_fallback(display_object.blend_mode());
+    let push_blend_mode = !matches!(blend_mode, BlendMode::Normal | BlendMode::Layer);
+    if push_blend_mode {
+        context.commands.push_blend_mode(blend_mode);
+    }
+    push_blend_mode
+}
+
+/// Render a display object clipped to the shapes of its mask.
+fn render_clipped<'gc>(
+    display_object: DisplayObject<'gc>,
+    mask: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+) {
+    let mask_transform = Transform {
+        matrix: display_object.global_to_local_matrix() * mask.local_to_global_matrix(),
+        color_transform: Default::default(),
+    };
+    context.commands.push_mask();
+    context.allow_mask = false;
+    context.transform_stack.push(&mask_transform);
+    mask.render_self(context);
+    context.transform_stack.pop();
+    context.allow_mask = true;
+    context.commands.activate_mask();
+
+    render_content(display_object, context);
+
+    context.commands.deactivate_mask();
+    context.allow_mask = false;
+    context.transform_stack.push(&mask_transform);
+    mask.render_self(context);
+    context.transform_stack.pop();
+    context.allow_mask = true;
+    context.commands.pop_mask();
+}
+
+/// Render a display object offscreen, through its filters, as a layer or
+/// because it is cached as a bitmap.
+///
+/// The object is rasterized into an offscreen bitmap in screen space, which
+/// its filters are then applied to in turn before it is drawn. If every
+/// filter only draws behind the object, the object itself is rendered as
+/// normal over the effect, so that content the rasterizer can't draw still
+/// appears.
+///
+/// The bitmap is kept as the object's bitmap cache, and drawn again instead
+/// while the object stays the same.
+///
+/// Returns `false` if the object still needs to be rendered itself.
+fn render_offscreen<'gc>(
+    display_object: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+) -> bool {
+    if !context.renderer.capabilities().supports_filters {
+        return false;
+    }
+
+    let world_transform = context.transform_stack.transform().clone();
+    let cache = display_object.bitmap_cache();
+    if let Some(cache) = cache.filter(|cache| cache.matches(&world_transform)) {
+        cache.render(context, &world_transform);
+        return !cache.render_source;
+    }
+
+    let area = match offscreen_area(display_object, &world_transform.matrix, context) {
+        Some(area) => area,
+        None => {
+            release_bitmap_cache(display_object, context.renderer);
+            return false;
+        }
+    };
+    let (x_min, y_min, width, height) = area;
+    let source = rasterize_offscreen(display_object, context, &world_transform, area);
+
+    // Content that the rasterizer can't draw, such as text, is rendered
+    // without its filters rather than not at all. That is remembered until
+    // the object changes, so it isn't rasterized again every frame.
+    if source.pixels().iter().all(|pixel| pixel.alpha() == 0) {
+        release_bitmap_cache(display_object, context.renderer);
+        display_object.set_bitmap_cache(Some(BitmapCache {
+            handle: None,
+            matrix: world_transform.matrix,
+            color_transform: world_transform.color_transform,
+            x_min,
+            y_min,
+            render_source: true,
+            valid: is_static_content(display_object),
+        }));
+        return false;
+    }
+
+    let filters = display_object.filters();
+    let mut filtered = filters.iter().fold(source.clone(), |bitmap, filter| {
+        apply_filter(&bitmap, filter)
+    });
+    let render_source = !filters.is_empty() && filters.iter().all(preserves_source);
+    if render_source {
+        filtered = remove_source(&filtered, &source);
+    }
+    drop(filters);
+
+    let rgba = filtered.pixels_rgba();
+    let handle = match cache.and_then(|cache| cache.handle) {
+        Some(handle) => context.renderer.update_texture(handle, width, height, rgba),
+        None => context.renderer.register_bitmap_raw(width, height, rgba),
+    };
+    match handle {
+        Ok(handle) => {
+            let cache = BitmapCache {
+                handle: Some(handle),
+                matrix: world_transform.matrix,
+                color_transform: world_transform.color_transform,
+                x_min,
+                y_min,
+                render_source,
+                valid: is_static_content(display_object),
+            };
+            display_object.set_bitmap_cache(Some(cache));
+            cache.render(context, &world_transform);
+        }
+        Err(e) => log::warn!("Failed to upload offscreen display object: {}", e),
+    }
+
+    !render_source
+}
+
+/// The area of the screen, in whole pixels, that a display object drawn with
+/// `matrix` covers, grown to fit the effects of its filters.
+///
+/// Returns `None` if the object covers nothing, or the area is too large to
+/// render offscreen.
+fn offscreen_area(
+    display_object: DisplayObject<'_>,
+    matrix: &Matrix,
+    context: &mut RenderContext<'_, '_>,
+) -> Option<(i32, i32, u32, u32)> {
+    let bounds = display_object.bounds_with_transform(matrix);
+    if !bounds.valid {
+        return None;
+    }
+
+    let (padding_x, padding_y) = add_filter_padding((0, 0), &display_object.filters());
+    let x_min = (bounds.x_min.to_pixels().floor() as i32).saturating_sub(padding_x);
+    let y_min = (bounds.y_min.to_pixels().floor() as i32).saturating_sub(padding_y);
+    let x_max = (bounds.x_max.to_pixels().ceil() as i32).saturating_add(padding_x);
+    let y_max = (bounds.y_max.to_pixels().ceil() as i32).saturating_add(padding_y);
+    let width = (i64::from(x_max) - i64::from(x_min)).clamp(0, u32::MAX.into()) as u32;
+    let height = (i64::from(y_max) - i64::from(y_min)).clamp(0, u32::MAX.into()) as u32;
+    let max_size = MAX_FILTER_SIZE.min(context.renderer.capabilities().max_texture_size);
+    if width == 0 || height == 0 || width > max_size || height > max_size {
+        return None;
+    }
+
+    Some((x_min, y_min, width, height))
+}
+
+/// Rasterize a display object drawn with `world_transform` into a bitmap of
+/// an area of the screen, without its filters.
+fn rasterize_offscreen<'gc>(
+    display_object: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+    world_transform: &Transform,
+    (x_min, y_min, width, height): (i32, i32, u32, u32),
+) -> BitmapData {
+    let offscreen_transform = Transform {
+        matrix: Matrix::translate(
+            Twips::from_pixels((-x_min).into()),
+            Twips::from_pixels((-y_min).into()),
+        ) * world_transform.matrix,
+        color_transform: world_transform.color_transform,
+    };
+
+    let mut bitmap = BitmapData::default();
+    bitmap.init_pixels(width, height, 0, true);
+    let mut rasterizer =
+        Rasterizer::new(&mut bitmap, &offscreen_transform, None, BlendMode::Normal);
+    display_object.rasterize_self(context.renderer, &mut rasterizer);
+    drop(rasterizer);
+    bitmap
+}
+
+/// Whether a mask is drawn as a soft mask, which hides the object it masks by
+/// the alpha of its pixels, rather than clipping it to its shapes.
+///
+/// Flash does this when the mask is cached as a bitmap or has filters, so
+/// that a mask with a gradient or a blur feathers the edges of the object.
+fn is_soft_mask(mask: DisplayObject<'_>) -> bool {
+    mask.is_bitmap_cached() || !mask.filters().is_empty()
+}
+
+/// Render display objects through a soft mask.
+///
+/// The objects are drawn with their own transforms on top of the current
+/// one, as the children of the object being rendered are, and `mask_matrix`
+/// places the mask in the same space. They are rasterized offscreen over the
+/// area of the screen they cover, each through its own filters, and the mask
+/// is rasterized over the same area through its filters. The objects are then
+/// hidden by the alpha of the mask and drawn as a bitmap, whose texture is
+/// kept in the mask's bitmap cache to be reused.
+///
+/// The blend mode of a masked object applies when it is the only one.
+///
+/// Returns `false` if the objects can't be rendered offscreen, and should be
+/// clipped to the shapes of the mask instead.
+fn render_soft_masked<'gc>(
+    content: &[DisplayObject<'gc>],
+    mask: DisplayObject<'gc>,
+    mask_matrix: Matrix,
+    context: &mut RenderContext<'_, 'gc>,
+) -> bool {
+    if !context.renderer.capabilities().supports_filters {
+        return false;
+    }
+
+    let base_transform = context.transform_stack.transform().clone();
+    let transforms: Vec<Transform> = content
+        .iter()
+        .map(|object| {
+            context.transform_stack.push(&*object.transform());
+            let transform = context.transform_stack.transform().clone();
+            context.transform_stack.pop();
+            transform
+        })
+        .collect();
+
+    let mut bounds: Option<(i32, i32, i32, i32)> = None;
+    for (object, transform) in content.iter().zip(&transforms) {
+        if let Some((x, y, width, height)) = offscreen_area(*object, &transform.matrix, context) {
+            let (x_max, y_max) = (
+                x.saturating_add(width as i32),
+                y.saturating_add(height as i32),
+            );
+            bounds = Some(match bounds {
+                Some((x_min, y_min, old_x_max, old_y_max)) => (
+                    x_min.min(x),
+                    y_min.min(y),
+                    old_x_max.max(x_max),
+                    old_y_max.max(y_max),
+                ),
+                None => (x, y, x_max, y_max),
+            });
+        }
+    }
+    let (x_min, y_min, x_max, y_max) = match bounds {
+        Some(bounds) => bounds,
+        None => return false,
+    };
+    let width = (x_max - x_min) as u32;
+    let height = (y_max - y_min) as u32;
+    let max_size = MAX_FILTER_SIZE.min(context.renderer.capabilities().max_texture_size);
+    if width > max_size || height > max_size {
+        return false;
+    }
+    let area = (x_min, y_min, width, height);
+
+    let mut source = BitmapData::default();
+    source.init_pixels(width, height, 0, true);
+    for (object, transform) in content.iter().zip(&transforms) {
+        let bitmap = rasterize_offscreen(*object, context, transform, area);
+        let bitmap = object
+            .filters()
+            .iter()
+            .fold(bitmap, |bitmap, filter| apply_filter(&bitmap, filter));
+        let mut rasterizer =
+            Rasterizer::new(&mut source, &Transform::default(), None, BlendMode::Normal);
+        rasterizer.draw_bitmap(&bitmap, false);
+    }
+
+    // Content that the rasterizer can't draw, such as text, is clipped by
+    // the mask rather than not drawn at all.
+    if source.pixels().iter().all(|pixel| pixel.alpha() == 0) {
+        return false;
+    }
+
+    let mask_transform = Transform {
+        matrix: base_transform.matrix * mask_matrix,
+        color_transform: mask.local_to_global_color_transform(),
+    };
+    let mask_bitmap = rasterize_offscreen(mask, context, &mask_transform, area);
+    let mask_bitmap = mask
+        .filters()
+        .iter()
+        .fold(mask_bitmap, |bitmap, filter| apply_filter(&bitmap, filter));
+    let masked = apply_mask(&source, &mask_bitmap);
+
+    let rgba = masked.pixels_rgba();
+    let handle = match mask.bitmap_cache().and_then(|cache| cache.handle) {
+        Some(handle) => context.renderer.update_texture(handle, width, height, rgba),
+        None => context.renderer.register_bitmap_raw(width, height, rgba),
+    };
+    match handle {
+        Ok(handle) => {
+            // Either side can change without the other noticing, so this is
+            // never reused as is.
+            let cache = BitmapCache {
+                handle: Some(handle),
+                matrix: base_transform.matrix,
+                color_transform: base_transform.color_transform,
+                x_min,
+                y_min,
+                render_source: false,
+                valid: false,
+            };
+            mask.set_bitmap_cache(Some(cache));
+
+            let push_blend_mode = match content {
+                [object] => push_blend_mode(*object, context),
+                _ => false,
+            };
+            cache.render(context, &base_transform);
+            if push_blend_mode {
+                context.commands.pop_blend_mode();
+            }
+        }
+        Err(e) => log::warn!("Failed to upload soft masked display objects: {}", e),
+    }
+
+    true
+}
+
+/// Render a display object that is scaled with a scale-9 grid.
+///
+/// The content is rendered once for each of the nine slices of the grid,
+/// masked to the area of that slice, with `render_content`. Objects that are
+/// rotated or skewed are scaled as normal, like in Flash Player.
+///
+/// Returns `false` if the grid doesn't apply, and the object still needs to be
+/// rendered itself.
+fn render_scale9<'gc>(
+    display_object: DisplayObject<'gc>,
+    context: &mut RenderContext<'_, 'gc>,
+    render_content: impl Fn(&mut RenderContext<'_, 'gc>),
+) -> bool {
+    let grid = display_object.scaling_grid();
+    if grid.x_max <= grid.x_min || grid.y_max <= grid.y_min {
+        return false;
+    }
+
+    let matrix = *display_object.matrix();
+    let (scale_x, scale_y) = (f64::from(matrix.a.abs()), f64::from(matrix.d.abs()));
+    if matrix.b != 0.0 || matrix.c != 0.0 || scale_x == 0.0 || scale_y == 0.0 {
+        return false;
+    }
+    if scale_x == 1.0 && scale_y == 1.0 {
+        return false;
+    }
+
+    let bounds = display_object.bounds();
+    if !bounds.valid {
+        return false;
+    }
+
+    for slice in scale9_slices(&bounds, &grid, scale_x, scale_y) {
+        let mask = context.transform_stack.transform().matrix
+            * Matrix::create_box(
+                (slice.bounds.x_max - slice.bounds.x_min).to_pixels() as f32,
+                (slice.bounds.y_max - slice.bounds.y_min).to_pixels() as f32,
+                0.0,
+                slice.bounds.x_min,
+                slice.bounds.y_min,
+            );
+        context.commands.push_mask();
+        context.commands.draw_rect(Color::from_rgb(0, 0xff), &mask);
+        context.commands.activate_mask();
+        context.transform_stack.push(&Transform {
+            matrix: slice.matrix,
+            color_transform: Default::default(),
+        });
+        render_content(context);
+        context.transform_stack.pop();
+        context.commands.deactivate_mask();
+        context.commands.draw_rect(Color::from_rgb(0, 0xff), &mask);
+        context.commands.pop_mask();
+    }
+
+    true
+}
+
+/// One of the nine slices of a display object scaled with a scale-9 grid.
+#[derive(Debug)]
+struct Scale9Slice {
+    /// The area this slice is drawn into, in the object's local space.
+    bounds: BoundingBox,
+
+    /// The transform from the object's content to this slice, applied on top
+    /// of the object's own transform.
+    matrix: Matrix,
+}
+
+/// Split the content of a display object into the slices of its scale-9 grid.
+///
+/// The corners of the grid keep their size when the object is scaled by
+/// `scale_x` and `scale_y`, the edges only stretch along their length and the
+/// center stretches to fill the rest of the scaled bounds. Once the object is
+/// smaller than its corners, the corners shrink instead.
+fn scale9_slices(
+    bounds: &BoundingBox,
+    grid: &Rectangle,
+    scale_x: f64,
+    scale_y: f64,
+) -> Vec<Scale9Slice> {
+    let x_min: f64 = bounds.x_min.get().into();
+    let x_max: f64 = bounds.x_max.get().into();
+    let y_min: f64 = bounds.y_min.get().into();
+    let y_max: f64 = bounds.y_max.get().into();
+    let grid_x_min = f64::from(grid.x_min.get()).max(x_min);
+    let grid_x_max = f64::from(grid.x_max.get()).min(x_max);
+    let grid_y_min = f64::from(grid.y_min.get()).max(y_min);
+    let grid_y_max = f64::from(grid.y_max.get()).min(y_max);
+    if grid_x_max <= grid_x_min || grid_y_max <= grid_y_min {
+        return vec![];
+    }
+
+    let columns = scale9_axis([x_min, grid_x_min, grid_x_max, x_max], scale_x);
+    let rows = scale9_axis([y_min, grid_y_min, grid_y_max, y_max], scale_y);
+
+    let mut slices = Vec::with_capacity(9);
+    for &(y_source, y_start, y_end, y_scale) in &rows {
+        for &(x_source, x_start, x_end, x_scale) in &columns {
+            if x_end - x_start < 1.0 || y_end - y_start < 1.0 {
+                continue;
+            }
+            slices.push(Scale9Slice {
+                bounds: BoundingBox {
+                    x_min: Twips::new(x_start.round() as i32),
+                    y_min: Twips::new(y_start.round() as i32),
+                    x_max: Twips::new(x_end.round() as i32),
+                    y_max: Twips::new(y_end.round() as i32),
+                    valid: true,
+                },
+                matrix: Matrix {
+                    a: x_scale as f32,
+                    b: 0.0,
+                    c: 0.0,
+                    d: y_scale as f32,
+                    tx: Twips::new((x_start - x_source * x_scale).round() as i32),
+                    ty: Twips::new((y_start - y_source * y_scale).round() as i32),
+                },
+            });
+        }
+    }
+    slices
+}
+
+/// Split one axis of a scale-9 grid, given as the bounds and grid lines along
+/// it, into its three slices.
+///
+/// Each slice is returned as the position its content starts at, the range it
+/// is drawn into and the scale of its content, all relative to the object's
+/// own scale along the axis.
+fn scale9_axis(lines: [f64; 4], scale: f64) -> [(f64, f64, f64, f64); 3] {
+    let [min, grid_min, grid_max, max] = lines;
+    let target = (max - min) * scale;
+    let fixed = (grid_min - min) + (max - grid_max);
+    let (edge_scale, center_scale) = if fixed <= target {
+        (1.0, (target - fixed) / (grid_max - grid_min))
+    } else {
+        (target / fixed, 0.0)
+    };
+
+    let start = min * scale;
+    let center_start = start + (grid_min - min) * edge_scale;
+    let center_end = center_start + (grid_max - grid_min) * center_scale;
+    let end = start + target;
+    [
+        (min, start / scale, center_start / scale, edge_scale / scale),
+        (
+            grid_min,
+            center_start / scale,
+            center_end / scale,
+            center_scale / scale,
+        ),
+        (
+            grid_max,
+            center_end / scale,
+            end / scale,
+            edge_scale / scale,
+        ),
+    ]
+}
+
 pub enum DisplayObjectPtr {}
 
 // To use this macro: `use crate::impl_display_object_sansbounds;` or `use crate::prelude::*;`
@@ -1296,6 +2356,7 @@ macro_rules! impl_display_object_sansbounds {
             &self,
             context: gc_arena::MutationContext<'gc, '_>,
         ) -> std::cell::RefMut<swf::Matrix> {
+            self.invalidate_render();
             std::cell::RefMut::map(self.0.write(context), |o| o.$field.matrix_mut())
         }
         fn color_transform(&self) -> std::cell::Ref<crate::color_transform::ColorTransform> {
@@ -1305,6 +2366,7 @@ macro_rules! impl_display_object_sansbounds {
             &self,
             context: gc_arena::MutationContext<'gc, '_>,
         ) -> std::cell::RefMut<crate::color_transform::ColorTransform> {
+            self.invalidate_render();
             std::cell::RefMut::map(self.0.write(context), |o| o.$field.color_transform_mut())
         }
         fn set_color_transform(
@@ -1312,6 +2374,7 @@ macro_rules! impl_display_object_sansbounds {
             context: gc_arena::MutationContext<'gc, '_>,
             color_transform: &crate::color_transform::ColorTransform,
         ) {
+            self.invalidate_render();
             self.0
                 .write(context)
                 .$field
@@ -1321,24 +2384,28 @@ macro_rules! impl_display_object_sansbounds {
             self.0.write(gc_context).$field.rotation()
         }
         fn set_rotation(&self, gc_context: gc_arena::MutationContext<'gc, '_>, degrees: Degrees) {
+            self.invalidate_render();
             self.0.write(gc_context).$field.set_rotation(degrees)
         }
         fn scale_x(&self, gc_context: gc_arena::MutationContext<'gc, '_>) -> Percent {
             self.0.write(gc_context).$field.scale_x()
         }
         fn set_scale_x(&self, gc_context: gc_arena::MutationContext<'gc, '_>, value: Percent) {
+            self.invalidate_render();
             self.0.write(gc_context).$field.set_scale_x(value)
         }
         fn scale_y(&self, gc_context: gc_arena::MutationContext<'gc, '_>) -> Percent {
             self.0.write(gc_context).$field.scale_y()
         }
         fn set_scale_y(&self, gc_context: gc_arena::MutationContext<'gc, '_>, value: Percent) {
+            self.invalidate_render();
             self.0.write(gc_context).$field.set_scale_y(value)
         }
         fn alpha(&self) -> f64 {
             self.0.read().$field.alpha()
         }
         fn set_alpha(&self, gc_context: gc_arena::MutationContext<'gc, '_>, value: f64) {
+            self.invalidate_render();
             self.0.write(gc_context).$field.set_alpha(value)
         }
         fn name(&self) -> std::cell::Ref<str> {
@@ -1355,6 +2422,7 @@ macro_rules! impl_display_object_sansbounds {
             context: gc_arena::MutationContext<'gc, '_>,
             depth: crate::prelude::Depth,
         ) {
+            self.invalidate_render();
             self.0.write(context).$field.set_clip_depth(depth)
         }
         fn parent(&self) -> Option<crate::display_object::DisplayObject<'gc>> {
@@ -1402,6 +2470,7 @@ macro_rules! impl_display_object_sansbounds {
                 }
             }
             self.0.write(context).$field.set_masker(node);
+            self.invalidate_render();
         }
         fn maskee(&self) -> Option<DisplayObject<'gc>> {
             self.0.read().$field.maskee()
@@ -1418,6 +2487,7 @@ macro_rules! impl_display_object_sansbounds {
                 }
             }
             self.0.write(context).$field.set_maskee(node);
+            self.invalidate_render();
         }
         fn removed(&self) -> bool {
             self.0.read().$field.removed()
@@ -1439,11 +2509,80 @@ macro_rules! impl_display_object_sansbounds {
                 .set_sound_transform(value);
             context.set_sound_transforms_dirty();
         }
+        fn filters(&self) -> std::cell::Ref<[swf::Filter]> {
+            std::cell::Ref::map(self.0.read(), |r| r.$field.filters())
+        }
+        fn set_filters(
+            &self,
+            gc_context: gc_arena::MutationContext<'gc, '_>,
+            filters: Vec<swf::Filter>,
+        ) {
+            self.0.write(gc_context).$field.set_filters(filters);
+            self.invalidate_content();
+        }
+        fn bitmap_cache(&self) -> Option<crate::display_object::BitmapCache> {
+            self.0.read().$field.bitmap_cache()
+        }
+        fn set_bitmap_cache(&self, cache: Option<crate::display_object::BitmapCache>) {
+            self.0.read().$field.set_bitmap_cache(cache)
+        }
+        fn is_bitmap_cached(&self) -> bool {
+            self.0.read().$field.is_bitmap_cached()
+        }
+        fn set_is_bitmap_cached(
+            &self,
+            gc_context: gc_arena::MutationContext<'gc, '_>,
+            value: bool,
+        ) {
+            self.invalidate_render();
+            self.0.write(gc_context).$field.set_is_bitmap_cached(value);
+        }
+        fn blend_mode(&self) -> swf::BlendMode {
+            self.0.read().$field.blend_mode()
+        }
+        fn set_blend_mode(
+            &self,
+            gc_context: gc_arena::MutationContext<'gc, '_>,
+            blend_mode: swf::BlendMode,
+        ) {
+            self.0.write(gc_context).$field.set_blend_mode(blend_mode);
+            self.invalidate_render();
+        }
+        fn scaling_grid(&self) -> swf::Rectangle {
+            self.0.read().$field.scaling_grid().clone()
+        }
+        fn set_scaling_grid(
+            &self,
+            gc_context: gc_arena::MutationContext<'gc, '_>,
+            scaling_grid: swf::Rectangle,
+        ) {
+            self.0
+                .write(gc_context)
+                .$field
+                .set_scaling_grid(scaling_grid);
+            self.invalidate_content();
+        }
+        fn dirty_state(&self) -> std::cell::Ref<crate::display_object::DirtyState> {
+            std::cell::Ref::map(self.0.read(), |r| r.$field.dirty_state())
+        }
         fn visible(&self) -> bool {
             self.0.read().$field.visible()
         }
         fn set_visible(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
             self.0.write(context).$field.set_visible(value);
+            self.invalidate_render();
+        }
+        fn mouse_enabled(&self) -> bool {
+            self.0.read().$field.mouse_enabled()
+        }
+        fn set_mouse_enabled(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_mouse_enabled(value);
+        }
+        fn mouse_children(&self) -> bool {
+            self.0.read().$field.mouse_children()
+        }
+        fn set_mouse_children(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_mouse_children(value);
         }
         fn lock_root(&self) -> bool {
             self.0.read().$field.lock_root()
@@ -1509,15 +2648,18 @@ macro_rules! impl_display_object {
             self.0.read().$field.x()
         }
         fn set_x(&self, gc_context: gc_arena::MutationContext<'gc, '_>, value: f64) {
+            self.invalidate_render();
             self.0.write(gc_context).$field.set_x(value)
         }
         fn y(&self) -> f64 {
             self.0.read().$field.y()
         }
         fn set_y(&self, gc_context: gc_arena::MutationContext<'gc, '_>, value: f64) {
+            self.invalidate_render();
             self.0.write(gc_context).$field.set_y(value)
         }
         fn set_matrix(&self, context: gc_arena::MutationContext<'gc, '_>, matrix: &swf::Matrix) {
+            self.invalidate_render();
             self.0.write(context).$field.set_matrix(matrix)
         }
     };
@@ -1533,7 +2675,7 @@ bitflags! {
     /// Bit flags used by `DisplayObject`.
     #[derive(Collect)]
     #[collect(no_drop)]
-    struct DisplayObjectFlags: u8 {
+    struct DisplayObjectFlags: u16 {
         /// Whether this object has been removed from the display list.
         /// Necessary in AVM1 to throw away queued actions from removed movie clips.
         const REMOVED                  = 1 << 0;
@@ -1559,6 +2701,32 @@ bitflags! {
         /// Whether this object has `_lockroot` set to true, in which case
         /// it becomes the _root of itself and of any children
         const LOCK_ROOT                = 1 << 6;
+
+        /// Whether this object is rendered through a bitmap cache
+        /// (`cacheAsBitmap` property).
+        const CACHE_AS_BITMAP          = 1 << 7;
+
+        /// Whether this object can be the target of mouse events
+        /// (`mouseEnabled` property).
+        const MOUSE_ENABLED            = 1 << 8;
+
+        /// Whether the children of this object can be the targets of mouse
+        /// events (`mouseChildren` property).
+        const MOUSE_CHILDREN           = 1 << 9;
+    }
+}
+
+bitflags! {
+    /// Options for `hit_test_shape`.
+    pub struct HitTestOptions: u8 {
+        /// Ignore invisible objects, along with everything inside them.
+        ///
+        /// Only the mouse does this: `hitTest` and `hitTestPoint` also hit
+        /// invisible objects.
+        const SKIP_INVISIBLE = 1 << 0;
+
+        /// The options for finding the object under the mouse.
+        const MOUSE_PICK = Self::SKIP_INVISIBLE.bits;
     }
 }
 
@@ -1640,3 +2808,481 @@ impl Default for SoundTransform {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+    use crate::avm2::ScriptObject as Avm2ScriptObject;
+    use crate::backend::render::{
+        Bitmap, BitmapInfo, MovieLibrary, NullRenderer, RenderCapabilities, ShapeHandle,
+    };
+    use crate::commands::CommandList;
+    use crate::library::Library;
+    use crate::shape_utils::{DistilledShape, DrawCommand};
+    use crate::tag_utils::SwfSlice;
+    use crate::transform::TransformStack;
+    use swf::FillStyle;
+
+    /// Make a clip to pick, drawn with a 10x10 square if `drawn` is set.
+    /// AVM1 clips are given a mouse handler, so that they act as buttons.
+    fn test_clip<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        avm2: bool,
+        drawn: bool,
+    ) -> MovieClip<'gc> {
+        let gc_context = activation.context.gc_context;
+        let movie = SwfSlice::empty(Arc::new(SwfMovie::empty(8)));
+        let clip = if avm2 {
+            let object = Avm2ScriptObject::bare_object(gc_context);
+            MovieClip::new_with_avm2(movie, object, object, gc_context)
+        } else {
+            let clip = MovieClip::new(movie, gc_context);
+            clip.post_instantiation(
+                &mut activation.context,
+                clip.into(),
+                None,
+                Instantiator::Avm1,
+                false,
+            );
+            clip
+        };
+
+        if drawn {
+            if !avm2 {
+                let object = clip.object().coerce_to_object(activation);
+                object.set("onRelease", true.into(), activation).unwrap();
+            }
+
+            let mut drawing = clip.as_drawing(gc_context).unwrap();
+            drawing.set_fill_style(Some(FillStyle::Color(Color {
+                r: 0,
+                g: 0,
+                b: 0,
+                a: 255,
+            })));
+            for &(x, y) in &[
+                (0.0, 0.0),
+                (10.0, 0.0),
+                (10.0, 10.0),
+                (0.0, 10.0),
+                (0.0, 0.0),
+            ] {
+                drawing.draw_command(DrawCommand::LineTo {
+                    x: Twips::from_pixels(x),
+                    y: Twips::from_pixels(y),
+                });
+            }
+            drawing.set_fill_style(None);
+        }
+
+        clip
+    }
+
+    /// The setup of a clip inside a parent clip, both of the same VM.
+    #[derive(Clone, Copy)]
+    struct PickCase {
+        avm2: bool,
+        visible: bool,
+        alpha: f64,
+        enabled: bool,
+        mouse_enabled: bool,
+        parent_visible: bool,
+        parent_mouse_enabled: bool,
+        parent_mouse_children: bool,
+    }
+
+    const AVM1: PickCase = PickCase {
+        avm2: false,
+        visible: true,
+        alpha: 1.0,
+        enabled: true,
+        mouse_enabled: true,
+        parent_visible: true,
+        parent_mouse_enabled: true,
+        parent_mouse_children: true,
+    };
+
+    const AVM2: PickCase = PickCase { avm2: true, ..AVM1 };
+
+    #[derive(Debug, PartialEq)]
+    enum Picked {
+        Clip,
+        Parent,
+        Nothing,
+    }
+
+    /// Which object Flash Player sends mouse events to when the mouse is over
+    /// a clip, depending on the clip and its parent.
+    #[test]
+    fn mouse_pick_compatibility() {
+        let table = [
+            ("AVM1 button clip", AVM1, Picked::Clip),
+            (
+                "AVM1 _visible = false",
+                PickCase {
+                    visible: false,
+                    ..AVM1
+                },
+                Picked::Nothing,
+            ),
+            (
+                "AVM1 _alpha = 0",
+                PickCase { alpha: 0.0, ..AVM1 },
+                Picked::Clip,
+            ),
+            // Disabled clips block the mouse, but receive no events.
+            (
+                "AVM1 enabled = false",
+                PickCase {
+                    enabled: false,
+                    ..AVM1
+                },
+                Picked::Clip,
+            ),
+            (
+                "AVM1 invisible parent",
+                PickCase {
+                    parent_visible: false,
+                    ..AVM1
+                },
+                Picked::Nothing,
+            ),
+            ("AVM2 sprite", AVM2, Picked::Clip),
+            (
+                "AVM2 visible = false",
+                PickCase {
+                    visible: false,
+                    ..AVM2
+                },
+                Picked::Nothing,
+            ),
+            (
+                "AVM2 alpha = 0",
+                PickCase { alpha: 0.0, ..AVM2 },
+                Picked::Clip,
+            ),
+            (
+                "AVM2 mouseEnabled = false",
+                PickCase {
+                    mouse_enabled: false,
+                    ..AVM2
+                },
+                Picked::Parent,
+            ),
+            (
+                "AVM2 invisible parent",
+                PickCase {
+                    parent_visible: false,
+                    ..AVM2
+                },
+                Picked::Nothing,
+            ),
+            (
+                "AVM2 parent mouseChildren = false",
+                PickCase {
+                    parent_mouse_children: false,
+                    ..AVM2
+                },
+                Picked::Parent,
+            ),
+            (
+                "AVM2 parent mouseEnabled = false",
+                PickCase {
+                    parent_mouse_enabled: false,
+                    ..AVM2
+                },
+                Picked::Clip,
+            ),
+            (
+                "AVM2 parent mouseEnabled = false, mouseChildren = false",
+                PickCase {
+                    parent_mouse_enabled: false,
+                    parent_mouse_children: false,
+                    ..AVM2
+                },
+                Picked::Nothing,
+            ),
+            (
+                "AVM2 parent and sprite mouseEnabled = false",
+                PickCase {
+                    parent_mouse_enabled: false,
+                    mouse_enabled: false,
+                    ..AVM2
+                },
+                Picked::Nothing,
+            ),
+        ];
+
+        for (name, case, expected) in table.iter() {
+            with_avm(8, |activation, _root| {
+                let parent = test_clip(activation, case.avm2, false);
+                let clip = test_clip(activation, case.avm2, true);
+                let context = &mut activation.context;
+                let gc_context = context.gc_context;
+                parent.replace_at_depth(context, clip.into(), 1);
+
+                clip.set_visible(gc_context, case.visible);
+                clip.set_alpha(gc_context, case.alpha);
+                clip.set_enabled(context, case.enabled);
+                clip.set_mouse_enabled(gc_context, case.mouse_enabled);
+                parent.set_visible(gc_context, case.parent_visible);
+                parent.set_mouse_enabled(gc_context, case.parent_mouse_enabled);
+                parent.set_mouse_children(gc_context, case.parent_mouse_children);
+
+                let point = (Twips::from_pixels(5.0), Twips::from_pixels(5.0));
+                let picked = match parent.mouse_pick(context, parent.into(), point) {
+                    Some(picked) if DisplayObject::ptr_eq(picked, clip.into()) => Picked::Clip,
+                    Some(picked) if DisplayObject::ptr_eq(picked, parent.into()) => Picked::Parent,
+                    Some(_) => panic!("{}: picked an unknown object", name),
+                    None => Picked::Nothing,
+                };
+                assert_eq!(picked, *expected, "{}", name);
+
+                Ok(())
+            });
+        }
+    }
+
+    #[test]
+    fn hit_tests_include_invisible_objects() {
+        with_avm(8, |activation, _root| {
+            let clip = test_clip(activation, false, true);
+            clip.set_visible(activation.context.gc_context, false);
+
+            let point = (Twips::from_pixels(5.0), Twips::from_pixels(5.0));
+            let context = &mut activation.context;
+            assert!(clip.hit_test_shape(context, point, HitTestOptions::empty()));
+            assert!(!clip.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn hit_tests_respect_masks() {
+        with_avm(8, |activation, _root| {
+            let parent = test_clip(activation, true, false);
+            let clip = test_clip(activation, true, true);
+            let mask = test_clip(activation, true, true);
+            let context = &mut activation.context;
+            let gc_context = context.gc_context;
+            parent.replace_at_depth(context, clip.into(), 1);
+            parent.replace_at_depth(context, mask.into(), 2);
+            mask.set_x(gc_context, 5.0);
+            clip.set_masker(gc_context, Some(mask.into()), true);
+            mask.set_maskee(gc_context, Some(clip.into()), true);
+
+            let hidden = (Twips::from_pixels(2.0), Twips::from_pixels(5.0));
+            let shown = (Twips::from_pixels(8.0), Twips::from_pixels(5.0));
+            let mask_only = (Twips::from_pixels(12.0), Twips::from_pixels(5.0));
+            assert!(!parent.hit_test_shape(context, hidden, HitTestOptions::empty()));
+            assert!(parent.hit_test_shape(context, shown, HitTestOptions::empty()));
+            assert!(!parent.hit_test_shape(context, mask_only, HitTestOptions::empty()));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn scale9_axis_keeps_edges_when_enlarged() {
+        assert_eq!(
+            scale9_axis([0.0, 10.0, 90.0, 100.0], 2.0),
+            [
+                (0.0, 0.0, 5.0, 0.5),
+                (10.0, 5.0, 95.0, 1.125),
+                (90.0, 95.0, 100.0, 0.5)
+            ]
+        );
+    }
+
+    #[test]
+    fn scale9_axis_shrinks_edges_when_too_small() {
+        assert_eq!(
+            scale9_axis([0.0, 10.0, 90.0, 100.0], 0.1),
+            [
+                (0.0, 0.0, 50.0, 5.0),
+                (10.0, 50.0, 50.0, 0.0),
+                (90.0, 50.0, 100.0, 5.0)
+            ]
+        );
+    }
+
+    type RenderError = Box<dyn std::error::Error>;
+
+    /// A renderer that can draw filters, and counts the offscreen bitmaps it
+    /// is given.
+    struct CountingRenderer {
+        inner: NullRenderer,
+        capabilities: RenderCapabilities,
+        registered: usize,
+        updated: usize,
+        unregistered: usize,
+    }
+
+    impl CountingRenderer {
+        fn new() -> Self {
+            Self {
+                inner: NullRenderer::new(),
+                capabilities: RenderCapabilities {
+                    supports_filters: true,
+                    ..Default::default()
+                },
+                registered: 0,
+                updated: 0,
+                unregistered: 0,
+            }
+        }
+    }
+
+    impl RenderBackend for CountingRenderer {
+        fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+            self.inner.set_viewport_dimensions(width, height)
+        }
+        fn register_shape(
+            &mut self,
+            shape: DistilledShape,
+            library: Option<&MovieLibrary<'_>>,
+        ) -> ShapeHandle {
+            self.inner.register_shape(shape, library)
+        }
+        fn replace_shape(
+            &mut self,
+            shape: DistilledShape,
+            library: Option<&MovieLibrary<'_>>,
+            handle: ShapeHandle,
+        ) {
+            self.inner.replace_shape(shape, library, handle)
+        }
+        fn register_glyph_shape(&mut self, shape: &swf::Glyph) -> ShapeHandle {
+            self.inner.register_glyph_shape(shape)
+        }
+        fn register_bitmap_jpeg(
+            &mut self,
+            data: &[u8],
+            jpeg_tables: Option<&[u8]>,
+        ) -> Result<BitmapInfo, RenderError> {
+            self.inner.register_bitmap_jpeg(data, jpeg_tables)
+        }
+        fn register_bitmap_jpeg_2(&mut self, data: &[u8]) -> Result<BitmapInfo, RenderError> {
+            self.inner.register_bitmap_jpeg_2(data)
+        }
+        fn register_bitmap_jpeg_3(
+            &mut self,
+            data: &[u8],
+            alpha_data: &[u8],
+        ) -> Result<BitmapInfo, RenderError> {
+            self.inner.register_bitmap_jpeg_3(data, alpha_data)
+        }
+        fn register_bitmap_png(
+            &mut self,
+            swf_tag: &swf::DefineBitsLossless,
+        ) -> Result<BitmapInfo, RenderError> {
+            self.inner.register_bitmap_png(swf_tag)
+        }
+        fn submit_frame(&mut self, clear: Color, commands: CommandList) {
+            self.inner.submit_frame(clear, commands)
+        }
+        fn capabilities(&self) -> &RenderCapabilities {
+            &self.capabilities
+        }
+        fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+            self.inner.get_bitmap_pixels(bitmap)
+        }
+        fn register_bitmap_raw(
+            &mut self,
+            _width: u32,
+            _height: u32,
+            _rgba: Vec<u8>,
+        ) -> Result<BitmapHandle, RenderError> {
+            self.registered += 1;
+            Ok(BitmapHandle(self.registered))
+        }
+        fn update_texture(
+            &mut self,
+            bitmap: BitmapHandle,
+            _width: u32,
+            _height: u32,
+            _rgba: Vec<u8>,
+        ) -> Result<BitmapHandle, RenderError> {
+            self.updated += 1;
+            Ok(bitmap)
+        }
+        fn unregister_bitmap(&mut self, _bitmap: BitmapHandle) {
+            self.unregistered += 1;
+        }
+    }
+
+    fn render_with<'gc>(
+        renderer: &mut CountingRenderer,
+        library: &Library<'gc>,
+        object: MovieClip<'gc>,
+    ) {
+        let mut transform_stack = TransformStack::new();
+        let mut context = RenderContext {
+            renderer,
+            commands: CommandList::new(),
+            library,
+            transform_stack: &mut transform_stack,
+            view_bounds: BoundingBox {
+                x_min: Twips::new(-100_000),
+                y_min: Twips::new(-100_000),
+                x_max: Twips::new(100_000),
+                y_max: Twips::new(100_000),
+                valid: true,
+            },
+            clip_depth_stack: vec![],
+            allow_mask: true,
+            text_rendering: Default::default(),
+        };
+        object.render(&mut context);
+    }
+
+    #[test]
+    fn filters_are_rendered_again_only_after_a_change() {
+        with_avm(8, |activation, _root| {
+            let mut parent = test_clip(activation, false, false);
+            let clip = test_clip(activation, false, true);
+            let context = &mut activation.context;
+            let gc_context = context.gc_context;
+            parent.replace_at_depth(context, clip.into(), 1);
+            let blur = |amount| {
+                Filter::BlurFilter(Box::new(swf::BlurFilter {
+                    blur_x: amount,
+                    blur_y: amount,
+                    num_passes: 1,
+                }))
+            };
+            clip.set_filters(gc_context, vec![blur(4.0)]);
+
+            let mut renderer = CountingRenderer::new();
+            render_with(&mut renderer, context.library, parent);
+            render_with(&mut renderer, context.library, parent);
+            assert_eq!((renderer.registered, renderer.updated), (1, 0));
+
+            // Moving the object draws the same bitmap somewhere else.
+            clip.set_x(gc_context, 20.0);
+            render_with(&mut renderer, context.library, parent);
+            assert_eq!((renderer.registered, renderer.updated), (1, 0));
+
+            clip.set_filters(gc_context, vec![blur(8.0)]);
+            render_with(&mut renderer, context.library, parent);
+            render_with(&mut renderer, context.library, parent);
+            assert_eq!((renderer.registered, renderer.updated), (1, 1));
+
+            // The bitmap is freed once the object no longer needs it.
+            clip.set_filters(gc_context, vec![]);
+            render_with(&mut renderer, context.library, parent);
+            assert_eq!(renderer.unregistered, 1);
+            assert!(clip.bitmap_cache().is_none());
+
+            clip.set_filters(gc_context, vec![blur(4.0)]);
+            render_with(&mut renderer, context.library, parent);
+            assert!(clip.bitmap_cache().is_some());
+            parent.remove_child(context, clip.into(), Lists::all());
+            assert!(clip.bitmap_cache().is_none());
+
+            Ok(())
+        });
+    }
+}
@@ -1,11 +1,20 @@
 //! Bitmap display object
 
-use crate::backend::render::BitmapHandle;
+use crate::avm1::Object as Avm1Object;
+use crate::avm2::{
+    Activation as Avm2Activation, Error as Avm2Error, Namespace as Avm2Namespace,
+    Object as Avm2Object, QName as Avm2QName, StageObject as Avm2StageObject,
+    TObject as Avm2TObject,
+};
+use crate::backend::render::{BitmapHandle, RenderBackend};
+use crate::bitmap::rasterizer::Rasterizer;
+use crate::commands::CommandHandler;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
 use crate::types::{Degrees, Percent};
-use gc_arena::{Collect, Gc, GcCell};
+use crate::vminterface::{AvmType, Instantiator};
+use gc_arena::{Collect, Gc, GcCell, MutationContext};
 
 /// A Bitmap display object is a raw bitamp on the stage.
 /// This can only be instanitated on the display list in SWFv9 AVM2 files.
@@ -23,7 +32,8 @@ pub struct Bitmap<'gc>(GcCell<'gc, BitmapData<'gc>>);
 pub struct BitmapData<'gc> {
     base: DisplayObjectBase<'gc>,
     static_data: Gc<'gc, BitmapStatic>,
-    bitmap_data: Option<GcCell<'gc, crate::avm1::object::bitmap_data::BitmapData>>,
+    bitmap_data: Option<GcCell<'gc, crate::bitmap::bitmap_data::BitmapData>>,
+    avm2_object: Option<Avm2Object<'gc>>,
     smoothing: bool,
 }
 
@@ -34,7 +44,7 @@ impl<'gc> Bitmap<'gc> {
         bitmap_handle: BitmapHandle,
         width: u16,
         height: u16,
-        bitmap_data: Option<GcCell<'gc, crate::avm1::object::bitmap_data::BitmapData>>,
+        bitmap_data: Option<GcCell<'gc, crate::bitmap::bitmap_data::BitmapData>>,
         smoothing: bool,
     ) -> Self {
         Bitmap(GcCell::allocate(
@@ -45,12 +55,13 @@ impl<'gc> Bitmap<'gc> {
                     context.gc_context,
                     BitmapStatic {
                         id,
-                        bitmap_handle,
+                        bitmap_handle: Some(bitmap_handle),
                         width,
                         height,
                     },
                 ),
                 bitmap_data,
+                avm2_object: None,
                 smoothing,
             },
         ))
@@ -66,17 +77,101 @@ impl<'gc> Bitmap<'gc> {
         Self::new_with_bitmap_data(context, id, bitmap_handle, width, height, None, true)
     }
 
-    #[allow(dead_code)]
-    pub fn bitmap_handle(self) -> BitmapHandle {
+    /// Construct an empty `Bitmap` for an AVM2 `Bitmap` object.
+    ///
+    /// It shows nothing until it is given some bitmap data.
+    pub fn new_with_avm2(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        avm2_object: Avm2Object<'gc>,
+        smoothing: bool,
+    ) -> Self {
+        Bitmap(GcCell::allocate(
+            context.gc_context,
+            BitmapData {
+                base: Default::default(),
+                static_data: Gc::allocate(
+                    context.gc_context,
+                    BitmapStatic {
+                        id: 0,
+                        bitmap_handle: None,
+                        width: 0,
+                        height: 0,
+                    },
+                ),
+                bitmap_data: None,
+                avm2_object: Some(avm2_object),
+                smoothing,
+            },
+        ))
+    }
+
+    pub fn bitmap_handle(self) -> Option<BitmapHandle> {
         self.0.read().static_data.bitmap_handle
     }
 
     pub fn width(self) -> u16 {
-        self.0.read().static_data.width
+        let read = self.0.read();
+        match &read.bitmap_data {
+            Some(bitmap_data) => bitmap_data.read().width() as u16,
+            None => read.static_data.width,
+        }
     }
 
     pub fn height(self) -> u16 {
-        self.0.read().static_data.height
+        let read = self.0.read();
+        match &read.bitmap_data {
+            Some(bitmap_data) => bitmap_data.read().height() as u16,
+            None => read.static_data.height,
+        }
+    }
+
+    /// The pixels this bitmap shows, if it is backed by a `BitmapData`.
+    pub fn bitmap_data(self) -> Option<GcCell<'gc, crate::bitmap::bitmap_data::BitmapData>> {
+        self.0.read().bitmap_data
+    }
+
+    /// Replace the pixels this bitmap shows.
+    ///
+    /// The new bitmap data is registered with the renderer so that its
+    /// contents can be uploaded as they change.
+    pub fn set_bitmap_data(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        bitmap_data: Option<GcCell<'gc, crate::bitmap::bitmap_data::BitmapData>>,
+    ) {
+        let static_data = match bitmap_data {
+            Some(bitmap_data) => {
+                let mut write = bitmap_data.write(context.gc_context);
+                BitmapStatic {
+                    id: 0,
+                    bitmap_handle: write.bitmap_handle(context.renderer),
+                    width: write.width() as u16,
+                    height: write.height() as u16,
+                }
+            }
+            None => BitmapStatic {
+                id: 0,
+                bitmap_handle: None,
+                width: 0,
+                height: 0,
+            },
+        };
+
+        let mut write = self.0.write(context.gc_context);
+        write.static_data = Gc::allocate(context.gc_context, static_data);
+        write.bitmap_data = bitmap_data;
+        drop(write);
+
+        self.invalidate_content();
+    }
+
+    pub fn smoothing(self) -> bool {
+        self.0.read().smoothing
+    }
+
+    pub fn set_smoothing(self, gc_context: MutationContext<'gc, '_>, smoothing: bool) {
+        self.0.write(gc_context).smoothing = smoothing;
+        self.invalidate_content();
     }
 }
 
@@ -97,18 +192,67 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
         }
     }
 
+    fn post_instantiation(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        display_object: DisplayObject<'gc>,
+        _init_object: Option<Avm1Object<'gc>>,
+        _instantiated_by: Instantiator,
+        run_frame: bool,
+    ) {
+        let has_avm2_object = self.0.read().avm2_object.is_some();
+        if !has_avm2_object && self.movie().is_some() && self.vm_type(context) == AvmType::Avm2 {
+            let mut allocator = || {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let mut proto = activation.context.avm2.prototypes().bitmap;
+                let constr = proto
+                    .get_property(
+                        proto,
+                        &Avm2QName::new(Avm2Namespace::public(), "constructor"),
+                        &mut activation,
+                    )?
+                    .coerce_to_object(&mut activation)?;
+
+                let object = Avm2StageObject::for_display_object(
+                    activation.context.gc_context,
+                    display_object,
+                    proto,
+                )
+                .into();
+                constr.call(Some(object), &[], &mut activation, Some(proto))?;
+
+                Ok(object)
+            };
+            let result: Result<Avm2Object<'gc>, Avm2Error> = allocator();
+
+            match result {
+                Ok(object) => self.0.write(context.gc_context).avm2_object = Some(object),
+                Err(e) => log::error!("Got {} when constructing AVM2 side of display object", e),
+            }
+        }
+
+        if run_frame {
+            self.run_frame(context);
+        }
+    }
+
     fn run_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
-        if let Some(bitmap_data) = &self.0.read().bitmap_data {
+        let read = self.0.read();
+        if let (Some(bitmap_data), Some(bitmap_handle)) =
+            (&read.bitmap_data, read.static_data.bitmap_handle)
+        {
             let bd = bitmap_data.read();
             if bd.dirty() {
                 let _ = context.renderer.update_texture(
-                    self.0.read().static_data.bitmap_handle,
+                    bitmap_handle,
                     bd.width(),
                     bd.height(),
                     bd.pixels_rgba(),
                 );
                 drop(bd);
                 bitmap_data.write(context.gc_context).set_dirty(false);
+                drop(read);
+                self.invalidate_content();
             }
         }
     }
@@ -120,11 +264,41 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
         }
 
         let bitmap_data = self.0.read();
-        context.renderer.render_bitmap(
-            bitmap_data.static_data.bitmap_handle,
-            context.transform_stack.transform(),
-            bitmap_data.smoothing,
-        );
+        if let Some(bitmap_handle) = bitmap_data.static_data.bitmap_handle {
+            context.commands.render_bitmap(
+                bitmap_handle,
+                context.transform_stack.transform(),
+                bitmap_data.smoothing,
+            );
+        }
+    }
+
+    fn rasterize_self(&self, renderer: &mut dyn RenderBackend, rasterizer: &mut Rasterizer<'_>) {
+        let smoothing = self.smoothing();
+        if let Some(bitmap_data) = self.bitmap_data() {
+            rasterizer.draw_bitmap(&bitmap_data.read(), smoothing);
+        } else if let Some(bitmap) = self
+            .bitmap_handle()
+            .and_then(|handle| renderer.get_bitmap_pixels(handle))
+        {
+            let pixels: Vec<i32> = bitmap.data.into();
+            let mut bitmap_data = crate::bitmap::bitmap_data::BitmapData::default();
+            bitmap_data.init_pixels(bitmap.width, bitmap.height, 0, true);
+            bitmap_data.set_pixels(pixels.into_iter().map(|p| p.into()).collect());
+            rasterizer.draw_bitmap(&bitmap_data, smoothing);
+        }
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .avm2_object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
+    fn as_bitmap(self) -> Option<Bitmap<'gc>> {
+        Some(self)
     }
 }
 
@@ -133,7 +307,11 @@ impl<'gc> TDisplayObject<'gc> for Bitmap<'gc> {
 #[collect(no_drop)]
 struct BitmapStatic {
     id: CharacterId,
-    bitmap_handle: BitmapHandle,
+
+    /// The renderer's handle to the pixels of this bitmap.
+    ///
+    /// This is `None` for script-created bitmaps that have no bitmap data.
+    bitmap_handle: Option<BitmapHandle>,
     width: u16,
     height: u16,
 }
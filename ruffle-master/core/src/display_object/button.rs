@@ -1,15 +1,22 @@
 use crate::avm1::{Object, StageObject, Value};
+use crate::avm2::{
+    Activation as Avm2Activation, Error as Avm2Error, Namespace as Avm2Namespace,
+    Object as Avm2Object, QName as Avm2QName, StageObject as Avm2StageObject,
+    TObject as Avm2TObject, Value as Avm2Value,
+};
+use crate::backend::render::RenderBackend;
 use crate::backend::ui::MouseCursor;
+use crate::bitmap::rasterizer::Rasterizer;
 use crate::context::{ActionType, RenderContext, UpdateContext};
 use crate::display_object::container::{
     dispatch_added_event, dispatch_removed_event, ChildContainer,
 };
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::display_object::{DisplayObjectBase, HitTestOptions, TDisplayObject};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
 use crate::prelude::*;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::types::{Degrees, Percent};
-use crate::vminterface::Instantiator;
+use crate::vminterface::{AvmType, Instantiator};
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::collections::BTreeMap;
 use std::convert::TryFrom;
@@ -34,6 +41,31 @@ pub struct ButtonData<'gc> {
     has_focus: bool,
     enabled: bool,
     use_hand_cursor: bool,
+
+    /// The `SimpleButton` of this button, in AVM2 movies.
+    avm2_object: Option<Avm2Object<'gc>>,
+
+    /// The class that AVM2 instances of this button are constructed as, if
+    /// its symbol was bound to one by a `SymbolClass` tag.
+    avm2_constructor: Option<Avm2Object<'gc>>,
+
+    /// The display objects of each state of an AVM2 button, which replace
+    /// the records of the button's definition.
+    avm2_states: Option<Avm2ButtonStates<'gc>>,
+}
+
+/// The display objects that an AVM2 button shows in each of its states, and
+/// the one whose shape the mouse must be over to hit it.
+///
+/// The objects are not on the display list until their state is entered,
+/// but are always parented to the button, so that they can be hit tested.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(no_drop)]
+pub struct Avm2ButtonStates<'gc> {
+    pub up: Option<DisplayObject<'gc>>,
+    pub over: Option<DisplayObject<'gc>>,
+    pub down: Option<DisplayObject<'gc>>,
+    pub hit_test: Option<DisplayObject<'gc>>,
 }
 
 impl<'gc> Button<'gc> {
@@ -93,6 +125,47 @@ impl<'gc> Button<'gc> {
                 has_focus: false,
                 enabled: true,
                 use_hand_cursor: true,
+                avm2_object: None,
+                avm2_constructor: None,
+                avm2_states: None,
+            },
+        ))
+    }
+
+    /// Construct an empty AVM2 button, for `new SimpleButton()`.
+    pub fn empty_avm2(
+        movie: Arc<SwfMovie>,
+        avm2_object: Avm2Object<'gc>,
+        gc_context: MutationContext<'gc, '_>,
+    ) -> Self {
+        let static_data = ButtonStatic {
+            swf: movie,
+            id: 0,
+            records: Vec::new(),
+            actions: Vec::new(),
+            up_to_over_sound: None,
+            over_to_down_sound: None,
+            down_to_over_sound: None,
+            over_to_up_sound: None,
+        };
+
+        Button(GcCell::allocate(
+            gc_context,
+            ButtonData {
+                base: Default::default(),
+                static_data: GcCell::allocate(gc_context, static_data),
+                container: ChildContainer::new(),
+                hit_area: BTreeMap::new(),
+                state: self::ButtonState::Up,
+                initialized: true,
+                object: None,
+                tracking: ButtonTracking::Push,
+                has_focus: false,
+                enabled: true,
+                use_hand_cursor: true,
+                avm2_object: Some(avm2_object),
+                avm2_constructor: None,
+                avm2_states: Some(Default::default()),
             },
         ))
     }
@@ -133,6 +206,12 @@ impl<'gc> Button<'gc> {
         context: &mut crate::context::UpdateContext<'_, 'gc, '_>,
         state: ButtonState,
     ) {
+        if self.0.read().avm2_states.is_some() {
+            self.0.write(context.gc_context).state = state;
+            self.show_avm2_state(context);
+            return;
+        }
+
         let mut removed_depths: fnv::FnvHashSet<_> =
             self.iter_render_list().map(|o| o.depth()).collect();
 
@@ -150,6 +229,7 @@ impl<'gc> Button<'gc> {
         // Children that exist in both states should persist and not be recreated.
         // TODO: This behavior probably differs in AVM2 (I suspect they always get recreated).
         let mut children = Vec::new();
+        let mut transforms = Vec::new();
 
         for record in &write.static_data.read().records {
             if record.states.contains(swf_state) {
@@ -179,16 +259,19 @@ impl<'gc> Button<'gc> {
                     }
                 };
 
-                // Set transform of child (and modify previous child if it already existed)
-                child.set_matrix(context.gc_context, &record.matrix);
-                child.set_color_transform(
-                    context.gc_context,
-                    &record.color_transform.clone().into(),
-                );
+                transforms.push((child, record.matrix.clone(), record.color_transform.clone()));
             }
         }
         drop(write);
 
+        // Set transform of child (and modify previous child if it already existed).
+        // This invalidates the rendering of the button, so it can't happen whilst
+        // holding the write lock.
+        for (child, matrix, color_transform) in transforms {
+            child.set_matrix(context.gc_context, &matrix);
+            child.set_color_transform(context.gc_context, &color_transform.into());
+        }
+
         // Kill children that no longer exist in this state.
         for depth in removed_depths {
             if let Some(child) = self.child_by_depth(depth) {
@@ -223,6 +306,224 @@ impl<'gc> Button<'gc> {
         self.0.read().use_hand_cursor
     }
 
+    pub fn track_as_menu(self) -> bool {
+        self.0.read().tracking == ButtonTracking::Menu
+    }
+
+    pub fn set_track_as_menu(self, context: &mut UpdateContext<'_, 'gc, '_>, track_as_menu: bool) {
+        self.0.write(context.gc_context).tracking = if track_as_menu {
+            ButtonTracking::Menu
+        } else {
+            ButtonTracking::Push
+        };
+    }
+
+    pub fn set_avm2_constructor(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        constructor: Option<Avm2Object<'gc>>,
+    ) {
+        self.0.write(gc_context).avm2_constructor = constructor;
+    }
+
+    /// The display objects of each state of this button, if it is an AVM2
+    /// button.
+    pub fn avm2_states(self) -> Option<Avm2ButtonStates<'gc>> {
+        self.0.read().avm2_states.clone()
+    }
+
+    /// Replace the display objects of each state of this AVM2 button, and
+    /// show the one for the state it is in.
+    pub fn set_avm2_states(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        states: Avm2ButtonStates<'gc>,
+    ) {
+        let self_display_object = self.into();
+        for child in [states.up, states.over, states.down, states.hit_test]
+            .iter()
+            .flatten()
+        {
+            child.set_parent(context.gc_context, Some(self_display_object));
+        }
+
+        self.0.write(context.gc_context).avm2_states = Some(states);
+        self.show_avm2_state(context);
+    }
+
+    /// Put the display object of this AVM2 button's current state on the
+    /// display list, in place of the one that was there.
+    fn show_avm2_state(mut self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let read = self.0.read();
+        let states = match &read.avm2_states {
+            Some(states) => states,
+            None => return,
+        };
+        let child = match read.state {
+            ButtonState::Up => states.up,
+            ButtonState::Over => states.over,
+            ButtonState::Down => states.down,
+        };
+        drop(read);
+
+        let shown: Vec<_> = self.iter_render_list().collect();
+        for old_child in shown {
+            if child.map_or(true, |child| !DisplayObject::ptr_eq(child, old_child)) {
+                self.remove_child(context, old_child, Lists::all());
+                dispatch_removed_event(old_child, context);
+            }
+        }
+
+        if let Some(child) = child {
+            if self.is_empty() {
+                child.set_parent(context.gc_context, Some(self.into()));
+                self.replace_at_depth(context, child, 0);
+                dispatch_added_event(self.into(), child, false, context);
+            }
+        }
+    }
+
+    /// Build the display object of one state of an AVM2 button from the
+    /// records of its definition.
+    ///
+    /// A state made of a single record is that record's object, and any
+    /// other state is a clip holding each of them.
+    fn build_avm2_state(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        swf_state: swf::ButtonState,
+    ) -> Option<DisplayObject<'gc>> {
+        let movie = self.movie().unwrap();
+        let records: Vec<_> = self
+            .0
+            .read()
+            .static_data
+            .read()
+            .records
+            .iter()
+            .filter(|record| record.states.contains(swf_state))
+            .cloned()
+            .collect();
+
+        let mut children = Vec::new();
+        for record in records {
+            match context
+                .library
+                .library_for_movie_mut(movie.clone())
+                .instantiate_by_id(record.id, context.gc_context)
+            {
+                Ok(child) => {
+                    child.set_matrix(context.gc_context, &record.matrix);
+                    child.set_color_transform(context.gc_context, &record.color_transform.into());
+                    child.set_depth(context.gc_context, record.depth.into());
+                    child.construct_frame(context);
+                    child.post_instantiation(context, child, None, Instantiator::Movie, false);
+                    children.push(child);
+                }
+                Err(error) => log::error!(
+                    "Button ID {}: could not instantiate child ID {}: {}",
+                    self.id(),
+                    record.id,
+                    error
+                ),
+            }
+        }
+
+        if children.len() <= 1 {
+            return children.pop();
+        }
+
+        let mut activation = Avm2Activation::from_nothing(context.reborrow());
+        let mut clip_proto = activation.context.avm2.prototypes().movieclip;
+        let container = clip_proto
+            .get_property(
+                clip_proto,
+                &Avm2QName::new(Avm2Namespace::public(), "constructor"),
+                &mut activation,
+            )
+            .and_then(|constr| {
+                let constr = constr.coerce_to_object(&mut activation)?;
+                let object = clip_proto.construct(&mut activation, &[])?;
+                constr.call(Some(object), &[], &mut activation, Some(clip_proto))?;
+                Ok(object.as_display_object())
+            });
+        let container = match container {
+            Ok(Some(container)) => container,
+            Ok(None) => return None,
+            Err(e) => {
+                log::error!("Got {} when constructing AVM2 button state", e);
+                return None;
+            }
+        };
+
+        if let Some(clip) = container.as_container() {
+            for child in children {
+                child.set_parent(context.gc_context, Some(container));
+                clip.replace_at_depth(context, child, child.depth());
+            }
+        }
+
+        Some(container)
+    }
+
+    /// Allocate and construct the `SimpleButton` of a button placed on an
+    /// AVM2 timeline, with the states of its definition.
+    fn construct_as_avm2_object(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        display_object: DisplayObject<'gc>,
+    ) {
+        let states = Avm2ButtonStates {
+            up: self.build_avm2_state(context, swf::ButtonState::UP),
+            over: self.build_avm2_state(context, swf::ButtonState::OVER),
+            down: self.build_avm2_state(context, swf::ButtonState::DOWN),
+            hit_test: self.build_avm2_state(context, swf::ButtonState::HIT_TEST),
+        };
+        self.0.write(context.gc_context).initialized = true;
+        self.set_avm2_states(context, states);
+
+        let constructor = self.0.read().avm2_constructor;
+        let mut allocator = || {
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let mut constructor = match constructor {
+                Some(constructor) => constructor,
+                None => {
+                    let mut proto = activation.context.avm2.prototypes().simple_button;
+                    proto
+                        .get_property(
+                            proto,
+                            &Avm2QName::new(Avm2Namespace::public(), "constructor"),
+                            &mut activation,
+                        )?
+                        .coerce_to_object(&mut activation)?
+                }
+            };
+            let proto = constructor
+                .get_property(
+                    constructor,
+                    &Avm2QName::new(Avm2Namespace::public(), "prototype"),
+                    &mut activation,
+                )?
+                .coerce_to_object(&mut activation)?;
+
+            let object: Avm2Object<'gc> = Avm2StageObject::for_display_object(
+                activation.context.gc_context,
+                display_object,
+                proto,
+            )
+            .into();
+            self.0.write(activation.context.gc_context).avm2_object = Some(object);
+            constructor.call(Some(object), &[], &mut activation, Some(proto))?;
+
+            Ok(())
+        };
+        let result: Result<(), Avm2Error> = allocator();
+
+        if let Err(e) = result {
+            log::error!("Got {} when constructing AVM2 side of button", e);
+        }
+    }
+
     pub fn set_use_hand_cursor(
         self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -253,6 +554,13 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
     ) {
         self.set_default_instance_name(context);
 
+        if self.vm_type(context) == AvmType::Avm2 {
+            if self.0.read().avm2_object.is_none() {
+                self.construct_as_avm2_object(context, display_object);
+            }
+            return;
+        }
+
         let mut mc = self.0.write(context.gc_context);
         if mc.object.is_none() {
             let object = StageObject::for_display_object(
@@ -311,7 +619,13 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
             drop(read);
 
             for (child, depth) in new_children {
+                // The hit test state is never displayed, so its children don't
+                // run frames, scripts or clip events. Clips still need the
+                // shapes of their first frame to be hit by the mouse.
                 child.post_instantiation(context, child, None, Instantiator::Movie, false);
+                if let Some(clip) = child.as_movie_clip() {
+                    clip.build_first_frame(context);
+                }
                 self.0
                     .write(context.gc_context)
                     .hit_area
@@ -328,6 +642,10 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         self.render_children(context);
     }
 
+    fn rasterize_self(&self, renderer: &mut dyn RenderBackend, rasterizer: &mut Rasterizer<'_>) {
+        self.rasterize_children(renderer, rasterizer);
+    }
+
     fn self_bounds(&self) -> BoundingBox {
         // No inherent bounds; contains child DisplayObjects.
         BoundingBox::default()
@@ -337,9 +655,14 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
         point: (Twips, Twips),
+        options: HitTestOptions,
     ) -> bool {
+        if options.contains(HitTestOptions::SKIP_INVISIBLE) && !self.visible() {
+            return false;
+        }
+
         for child in self.iter_execution_list() {
-            if child.hit_test_shape(context, point) {
+            if child.hit_test_shape(context, point, options) {
                 return true;
             }
         }
@@ -353,17 +676,17 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         self_node: DisplayObject<'gc>,
         point: (Twips, Twips),
     ) -> Option<DisplayObject<'gc>> {
-        // The button is hovered if the mouse is over any child nodes.
+        // The button is hovered only if the mouse is over the shapes of its
+        // hit test state. The visible states (and any clips inside them) never
+        // receive the mouse, and a button with only a hit test state is an
+        // invisible, but still clickable, region.
         if self.visible() {
-            for child in self.iter_render_list().rev() {
-                let result = child.mouse_pick(context, child, point);
-                if result.is_some() {
-                    return result;
-                }
-            }
-
-            for child in self.0.read().hit_area.values() {
-                if child.hit_test_shape(context, point) {
+            let hit_area: Vec<_> = match &self.0.read().avm2_states {
+                Some(states) => states.hit_test.into_iter().collect(),
+                None => self.0.read().hit_area.values().copied().collect(),
+            };
+            for child in hit_area {
+                if child.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK) {
                     return Some(self_node);
                 }
             }
@@ -372,7 +695,9 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
     }
 
     fn mouse_cursor(&self) -> MouseCursor {
-        if self.use_hand_cursor() {
+        // Disabled clips and buttons are still hit by the mouse, but don't
+        // look clickable.
+        if self.use_hand_cursor() && self.enabled() {
             MouseCursor::Hand
         } else {
             MouseCursor::Arrow
@@ -387,6 +712,14 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
             .unwrap_or(Value::Undefined)
     }
 
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .avm2_object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
     fn as_button(&self) -> Option<Self> {
         Some(*self)
     }
@@ -448,11 +781,19 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         match (cur_state, new_state) {
             (ButtonState::Up, ButtonState::Over) => {
                 write.run_actions(context, swf::ButtonActionCondition::IDLE_TO_OVER_UP, None);
-                write.play_sound(context, write.static_data.read().up_to_over_sound.as_ref());
+                write.play_sound(
+                    context,
+                    self_display_object,
+                    write.static_data.read().up_to_over_sound.as_ref(),
+                );
             }
             (ButtonState::Over, ButtonState::Up) => {
                 write.run_actions(context, swf::ButtonActionCondition::OVER_UP_TO_IDLE, None);
-                write.play_sound(context, write.static_data.read().over_to_up_sound.as_ref());
+                write.play_sound(
+                    context,
+                    self_display_object,
+                    write.static_data.read().over_to_up_sound.as_ref(),
+                );
             }
             (ButtonState::Over, ButtonState::Down) => {
                 write.run_actions(
@@ -462,6 +803,7 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
                 );
                 write.play_sound(
                     context,
+                    self_display_object,
                     write.static_data.read().over_to_down_sound.as_ref(),
                 );
             }
@@ -473,6 +815,7 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
                 );
                 write.play_sound(
                     context,
+                    self_display_object,
                     write.static_data.read().down_to_over_sound.as_ref(),
                 );
             }
@@ -482,11 +825,11 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         // Queue ActionScript-defined event handlers after the SWF defined ones.
         // (e.g., clip.onRelease = foo).
         if context.swf.version() >= 6 {
-            if let Some(name) = event.method_name() {
+            if let (Some(object), Some(name)) = (write.object, event.method_name()) {
                 context.action_queue.queue_actions(
                     self_display_object,
                     ActionType::Method {
-                        object: write.object.unwrap(),
+                        object,
                         name,
                         args: vec![],
                     },
@@ -531,9 +874,15 @@ impl<'gc> TDisplayObjectContainer<'gc> for Button<'gc> {
 }
 
 impl<'gc> ButtonData<'gc> {
+    /// Play a state transition sound set by `DefineButtonSound`.
+    ///
+    /// The sound is owned by the button, so it is affected by the sound
+    /// transforms of the button and its ancestors. The sync setting of the
+    /// sound is respected in the same way as for `StartSound` tags.
     fn play_sound(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
+        owner: DisplayObject<'gc>,
         sound: Option<&swf::ButtonSound>,
     ) {
         if let Some((id, sound_info)) = sound {
@@ -542,7 +891,21 @@ impl<'gc> ButtonData<'gc> {
                 .library_for_movie_mut(self.movie())
                 .get_sound(*id)
             {
-                let _ = context.start_sound(sound_handle, sound_info, None, None);
+                use swf::SoundEvent;
+                match sound_info.event {
+                    SoundEvent::Event => {
+                        let _ = context.start_sound(sound_handle, sound_info, Some(owner), None);
+                    }
+                    SoundEvent::Start => {
+                        if !context.is_sound_playing_with_handle(sound_handle) {
+                            let _ =
+                                context.start_sound(sound_handle, sound_info, Some(owner), None);
+                        }
+                    }
+                    SoundEvent::Stop => context.stop_sounds_with_handle(sound_handle),
+                }
+            } else {
+                log::warn!("DefineButtonSound: Sound ID {} doesn't exist", id);
             }
         }
     }
@@ -1,10 +1,15 @@
 //! Container mix-in for display objects
 
 use crate::avm2::{Avm2, Event as Avm2Event, Value as Avm2Value};
+use crate::backend::render::RenderBackend;
+use crate::bitmap::rasterizer::Rasterizer;
+use crate::commands::CommandHandler;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::button::Button;
 use crate::display_object::movie_clip::MovieClip;
-use crate::display_object::{Depth, DisplayObject, TDisplayObject};
+use crate::display_object::{
+    is_soft_mask, render_soft_masked, Depth, DisplayObject, HitTestOptions, TDisplayObject,
+};
 use crate::string_utils::swf_string_eq_ignore_case;
 use bitflags::bitflags;
 use gc_arena::{Collect, MutationContext};
@@ -13,6 +18,7 @@ use std::cmp::Ordering;
 use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::ops::RangeBounds;
+use swf::Twips;
 
 /// Dispatch the `removedFromStage` event on a child and all of it's
 /// grandchildren, recursively.
@@ -310,11 +316,38 @@ pub trait TDisplayObjectContainer<'gc>:
         RenderIter::from_container(self.into())
     }
 
+    /// The depths of the children of this container that are hidden at `pos`
+    /// by one of its clipping layers, as `(layer depth, clip depth)` ranges
+    /// that exclude both ends.
+    ///
+    /// Each clipping layer is only hit tested once, so this should be found
+    /// once for all children rather than for each child in turn.
+    fn clipped_depths_at(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        pos: (Twips, Twips),
+    ) -> Vec<(Depth, Depth)> {
+        let mut clipped = Vec::new();
+        for child in self.iter_render_list() {
+            if child.clip_depth() > 0
+                && child.allow_as_mask()
+                && !child.hit_test_shape(context, pos, HitTestOptions::empty())
+            {
+                clipped.push((child.depth(), child.clip_depth()));
+            }
+        }
+        clipped
+    }
+
     /// Renders the children of this container in render list order.
     fn render_children(self, context: &mut RenderContext<'_, 'gc>) {
         let mut clip_depth = 0;
         let mut clip_depth_stack: Vec<(Depth, DisplayObject<'_>)> = vec![];
-        for child in self.iter_render_list() {
+        let children: Vec<DisplayObject<'gc>> = self.iter_render_list().collect();
+        let mut i = 0;
+        while i < children.len() {
+            let child = children[i];
+            i += 1;
             let depth = child.depth();
 
             // Check if we need to pop off a mask.
@@ -324,21 +357,41 @@ pub trait TDisplayObjectContainer<'gc>:
                 // Clear the mask stencil and pop the mask.
                 let (prev_clip_depth, clip_child) = clip_depth_stack.pop().unwrap();
                 clip_depth = prev_clip_depth;
-                context.renderer.deactivate_mask();
+                context.commands.deactivate_mask();
                 context.allow_mask = false;
                 clip_child.render(context);
                 context.allow_mask = true;
-                context.renderer.pop_mask();
+                context.commands.pop_mask();
+            }
+            if context.allow_mask
+                && child.clip_depth() > 0
+                && child.allow_as_mask()
+                && is_soft_mask(child)
+            {
+                // Render the clipped children through the mask all at once.
+                let clipped_len = children[i..]
+                    .iter()
+                    .take_while(|clipped| clipped.depth() < child.clip_depth())
+                    .count();
+                let clipped: Vec<DisplayObject<'gc>> = children[i..i + clipped_len]
+                    .iter()
+                    .copied()
+                    .filter(|clipped| clipped.clip_depth() == 0 && clipped.visible())
+                    .collect();
+                if render_soft_masked(&clipped, child, *child.matrix(), context) {
+                    i += clipped_len;
+                    continue;
+                }
             }
             if context.allow_mask && child.clip_depth() > 0 && child.allow_as_mask() {
                 // Push and render the mask.
                 clip_depth_stack.push((clip_depth, child));
                 clip_depth = child.clip_depth();
-                context.renderer.push_mask();
+                context.commands.push_mask();
                 context.allow_mask = false;
                 child.render(context);
                 context.allow_mask = true;
-                context.renderer.activate_mask();
+                context.commands.activate_mask();
             } else if child.visible() {
                 // Normal child.
                 child.render(context);
@@ -347,11 +400,23 @@ pub trait TDisplayObjectContainer<'gc>:
 
         // Pop any remaining masks.
         for (_, clip_child) in clip_depth_stack.into_iter().rev() {
-            context.renderer.deactivate_mask();
+            context.commands.deactivate_mask();
             context.allow_mask = false;
             clip_child.render(context);
             context.allow_mask = true;
-            context.renderer.pop_mask();
+            context.commands.pop_mask();
+        }
+    }
+
+    /// Draws the children of this container into a bitmap with the software
+    /// rasterizer, in render list order.
+    ///
+    /// Clipping layers are skipped, as masks are not supported.
+    fn rasterize_children(self, renderer: &mut dyn RenderBackend, rasterizer: &mut Rasterizer<'_>) {
+        for child in self.iter_render_list() {
+            if child.clip_depth() == 0 && child.visible() {
+                child.rasterize(renderer, rasterizer);
+            }
         }
     }
 }
@@ -449,8 +514,12 @@ macro_rules! impl_display_object_container {
             if let Some(removed_child) = removed_child {
                 removed_child.unload(context);
                 removed_child.set_parent(context.gc_context, None);
+                self.invalidate_removed_child(removed_child);
+                removed_child.release_bitmap_caches(context.renderer);
             }
 
+            child.invalidate_render();
+
             removed_child
         }
 
@@ -474,6 +543,8 @@ macro_rules! impl_display_object_container {
                 child,
                 depth,
             );
+
+            child.invalidate_render();
         }
 
         fn insert_at_index(
@@ -507,6 +578,8 @@ macro_rules! impl_display_object_container {
                 .$field
                 .insert_at_id(context, child, index);
 
+            child.invalidate_render();
+
             if parent_changed {
                 dispatch_added_event(
                     DisplayObject::from(*self),
@@ -527,6 +600,12 @@ macro_rules! impl_display_object_container {
                 .write(context.gc_context)
                 .$field
                 .swap_at_id(index1, index2);
+
+            for index in [index1, index2].iter() {
+                if let Some(child) = self.0.read().$field.get_id(*index) {
+                    child.invalidate_render();
+                }
+            }
         }
 
         fn remove_child(
@@ -554,6 +633,11 @@ macro_rules! impl_display_object_container {
 
             drop(write);
 
+            if removed_from_render_list {
+                self.invalidate_removed_child(child);
+                child.release_bitmap_caches(context.renderer);
+            }
+
             if removed_from_execution_list {
                 child.unload(context);
 
@@ -585,6 +669,8 @@ macro_rules! impl_display_object_container {
             use crate::display_object::container::dispatch_removed_event;
             for removed in removed_list.iter() {
                 dispatch_removed_event(*removed, context);
+                self.invalidate_removed_child(*removed);
+                removed.release_bitmap_caches(context.renderer);
             }
 
             let mut write = self.0.write(context.gc_context);
@@ -604,6 +690,7 @@ macro_rules! impl_display_object_container {
 
                 write = self.0.write(context.gc_context);
             }
+            drop(write);
         }
 
         fn clear(&mut self, context: &mut UpdateContext<'_, 'gc, '_>) {
@@ -612,12 +699,14 @@ macro_rules! impl_display_object_container {
                 self.0.read().$field.iter_render_list().collect();
             for removed in removed_children {
                 dispatch_removed_event(removed, context);
+                self.invalidate_removed_child(removed);
+                removed.release_bitmap_caches(context.renderer);
             }
 
             self.0
                 .write(context.gc_context)
                 .$field
-                .clear(context.gc_context)
+                .clear(context.gc_context);
         }
 
         fn is_empty(self) -> bool {
@@ -6,16 +6,17 @@ use crate::avm1::{
     Value as Avm1Value,
 };
 use crate::avm2::{
-    Activation as Avm2Activation, Namespace as Avm2Namespace, Object as Avm2Object,
+    Activation as Avm2Activation, Avm2, Namespace as Avm2Namespace, Object as Avm2Object,
     QName as Avm2QName, StageObject as Avm2StageObject, TObject as Avm2TObject,
 };
 use crate::backend::ui::MouseCursor;
+use crate::commands::CommandHandler;
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::display_object::{DisplayObjectBase, HitTestOptions, TDisplayObject};
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode};
-use crate::font::{Glyph, TextRenderSettings};
-use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, TextFormat};
+use crate::font::{hint_glyph, Glyph, TextRenderSettings};
+use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, TextDirection, TextFormat};
 use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
 use crate::string_utils;
@@ -123,6 +124,9 @@ pub struct EditTextData<'gc> {
     /// apply.
     autosize: AutoSizeMode,
 
+    /// The base direction of the paragraphs of text in this field.
+    text_direction: TextDirection,
+
     /// The calculated layout box.
     layout: Vec<LayoutBox<'gc>>,
 
@@ -135,6 +139,10 @@ pub struct EditTextData<'gc> {
     /// The AVM1 object handle
     object: Option<AvmObject<'gc>>,
 
+    /// The `StyleSheet` object whose styles are applied to the HTML text of
+    /// this text field (AVM2 only).
+    style_sheet: Option<Avm2Object<'gc>>,
+
     /// The variable path that this text field is bound to (AVM1 only).
     variable: Option<String>,
 
@@ -147,6 +155,15 @@ pub struct EditTextData<'gc> {
     /// The selected portion of the text, or None if the text is not selected.
     selection: Option<TextSelection>,
 
+    /// Whether the user is selecting text by dragging the mouse.
+    is_selecting: bool,
+
+    /// Whether the blinking caret is shown this frame.
+    caret_blink_on: bool,
+
+    /// The portion of the text that an input method editor is still composing.
+    composition: Option<TextSelection>,
+
     /// Whether or not this EditText has the current keyboard focus
     has_focus: bool,
 
@@ -203,6 +220,7 @@ impl<'gc> EditText<'gc> {
             bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0),
             swf_tag.is_word_wrap,
             swf_tag.is_device_font,
+            TextDirection::default(),
         );
 
         let has_background = swf_tag.has_border;
@@ -271,14 +289,19 @@ impl<'gc> EditText<'gc> {
                 is_html,
                 drawing: Drawing::new(),
                 object: None,
+                style_sheet: None,
                 layout,
                 intrinsic_bounds,
                 bounds,
                 autosize: AutoSizeMode::None,
+                text_direction: TextDirection::default(),
                 variable: variable.map(|s| s.to_string_lossy(encoding)),
                 bound_stage_object: None,
                 firing_variable_binding: false,
                 selection: None,
+                is_selecting: false,
+                caret_blink_on: false,
+                composition: None,
                 has_focus: false,
                 render_settings: Default::default(),
             },
@@ -431,13 +454,47 @@ impl<'gc> EditText<'gc> {
         let mut write = self.0.write(context.gc_context);
 
         write.document = doc;
-        write.text_spans.lower_from_html(doc);
+        drop(write);
+
+        self.lower_document(context);
+    }
+
+    /// Lower the retained HTML document into text spans, applying the styles
+    /// of this field's style sheet, if it has one.
+    fn lower_document(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let mut write = self.0.write(context.gc_context);
+        let doc = write.document;
+
+        match write.style_sheet {
+            Some(object) => match object.as_style_sheet() {
+                Some(style_sheet) => write.text_spans.lower_from_css(doc, &style_sheet),
+                None => write.text_spans.lower_from_html(doc),
+            },
+            None => write.text_spans.lower_from_html(doc),
+        }
 
         drop(write);
 
         self.relayout(context);
     }
 
+    /// The `StyleSheet` object applied to this text field, if any.
+    pub fn style_sheet(self) -> Option<Avm2Object<'gc>> {
+        self.0.read().style_sheet
+    }
+
+    /// Set the `StyleSheet` object applied to this text field.
+    ///
+    /// The current HTML text is styled again with the new style sheet.
+    pub fn set_style_sheet(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        style_sheet: Option<Avm2Object<'gc>>,
+    ) {
+        self.0.write(context.gc_context).style_sheet = style_sheet;
+        self.lower_document(context);
+    }
+
     pub fn text_length(self) -> usize {
         self.0.read().text_spans.text().len()
     }
@@ -479,6 +536,7 @@ impl<'gc> EditText<'gc> {
 
     pub fn set_editable(self, is_editable: bool, context: &mut UpdateContext<'_, 'gc, '_>) {
         self.0.write(context.gc_context).is_editable = is_editable;
+        self.invalidate_content();
     }
 
     pub fn is_multiline(self) -> bool {
@@ -516,6 +574,24 @@ impl<'gc> EditText<'gc> {
         self.relayout(context);
     }
 
+    /// The base direction of the paragraphs of text in this field.
+    pub fn text_direction(self) -> TextDirection {
+        self.0.read().text_direction
+    }
+
+    /// Change the base direction of the paragraphs of text in this field.
+    ///
+    /// With `TextDirection::Auto`, each paragraph is laid out in the direction
+    /// of the first letter in it that has one.
+    pub fn set_text_direction(
+        self,
+        direction: TextDirection,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+    ) {
+        self.0.write(context.gc_context).text_direction = direction;
+        self.relayout(context);
+    }
+
     pub fn autosize(self) -> AutoSizeMode {
         self.0.read().autosize
     }
@@ -767,13 +843,14 @@ impl<'gc> EditText<'gc> {
             width,
             is_word_wrap,
             edit_text.is_device_font,
+            edit_text.text_direction,
         );
 
         edit_text.layout = new_layout;
         edit_text.intrinsic_bounds = intrinsic_bounds;
 
         match autosize {
-            AutoSizeMode::None => {}
+            AutoSizeMode::None => drop(edit_text),
             AutoSizeMode::Left => {
                 if !is_word_wrap {
                     edit_text.bounds.set_width(intrinsic_bounds.width());
@@ -811,6 +888,8 @@ impl<'gc> EditText<'gc> {
                 self.redraw_border(context.gc_context);
             }
         }
+
+        self.invalidate_content();
     }
 
     /// Measure the width and height of the `EditText`'s current text load.
@@ -839,7 +918,7 @@ impl<'gc> EditText<'gc> {
                     && edit_text.is_editable
                     && selection.start() >= *start
                     && selection.end() <= *end
-                    && Utc::now().timestamp_subsec_millis() / 500 == 0
+                    && edit_text.caret_blink_on
                 {
                     Some((selection.start() - start, end - start))
                 } else {
@@ -885,7 +964,7 @@ impl<'gc> EditText<'gc> {
                                     Twips::from_pixels(2.0),
                                 );
                             context
-                                .renderer
+                                .commands
                                 .draw_rect(Color::from_rgb(0x000000, 0xFF), &selection_box);
 
                             // Set text color to white
@@ -909,9 +988,13 @@ impl<'gc> EditText<'gc> {
                     }
 
                     // Render glyph.
+                    let glyph_transform = hint_glyph(
+                        context.transform_stack.transform(),
+                        context.text_rendering.hinting,
+                    );
                     context
-                        .renderer
-                        .render_shape(glyph.shape_handle, context.transform_stack.transform());
+                        .commands
+                        .render_shape(glyph.shape_handle, &glyph_transform);
                     context.transform_stack.pop();
 
                     if let Some((caret_pos, length)) = caret {
@@ -924,7 +1007,7 @@ impl<'gc> EditText<'gc> {
                                     x + Twips::from_pixels(-1.0),
                                     Twips::from_pixels(2.0),
                                 );
-                            context.renderer.draw_rect(color.clone(), &caret);
+                            context.commands.draw_rect(color.clone(), &caret);
                         } else if pos == length - 1 && caret_pos == length {
                             let caret = context.transform_stack.transform().matrix
                                 * Matrix::create_box(
@@ -934,7 +1017,7 @@ impl<'gc> EditText<'gc> {
                                     x + advance,
                                     Twips::from_pixels(2.0),
                                 );
-                            context.renderer.draw_rect(color.clone(), &caret);
+                            context.commands.draw_rect(color.clone(), &caret);
                         }
                     }
                 },
@@ -1098,6 +1181,8 @@ impl<'gc> EditText<'gc> {
         } else {
             text.selection = None;
         }
+        drop(text);
+        self.invalidate_content();
     }
 
     pub fn set_render_settings(
@@ -1105,7 +1190,8 @@ impl<'gc> EditText<'gc> {
         gc_context: MutationContext<'gc, '_>,
         settings: TextRenderSettings,
     ) {
-        self.0.write(gc_context).render_settings = settings
+        self.0.write(gc_context).render_settings = settings;
+        self.invalidate_content();
     }
 
     pub fn screen_position_to_index(self, position: (Twips, Twips)) -> Option<usize> {
@@ -1138,7 +1224,11 @@ impl<'gc> EditText<'gc> {
                             && local_position.1 >= Twips::zero()
                             && local_position.1 <= params.height()
                         {
-                            if local_position.0 >= x + (advance / 2) {
+                            // The end of a right-to-left character is on its
+                            // left side.
+                            let is_end = (local_position.0 >= x + (advance / 2))
+                                != params.is_right_to_left();
+                            if is_end {
                                 result = Some(string_utils::next_char_boundary(text, pos));
                             } else {
                                 result = Some(pos);
@@ -1155,6 +1245,118 @@ impl<'gc> EditText<'gc> {
         None
     }
 
+    /// Get the URL and target window of the link under a point on the screen,
+    /// if there is one.
+    pub fn link_at_position(self, position: (Twips, Twips)) -> Option<(String, Option<String>)> {
+        let text = self.0.read();
+        let position = self.global_to_local(position);
+        let position = (
+            position.0 + Twips::from_pixels(Self::INTERNAL_PADDING),
+            position.1 + Twips::from_pixels(Self::INTERNAL_PADDING),
+        );
+
+        for layout_box in text.layout.iter() {
+            let transform: Transform = layout_box.bounds().origin().into();
+            let mut matrix = transform.matrix;
+            matrix.invert();
+            let local_position = matrix * position;
+
+            if let Some((text, tf, font, params, color)) =
+                layout_box.as_renderable_text(text.text_spans.text())
+            {
+                let url = match &tf.url {
+                    Some(url) if !url.is_empty() => url,
+                    _ => continue,
+                };
+
+                let mut hit = false;
+                let baseline_adjustment =
+                    font.get_baseline_for_height(params.height()) - params.height();
+                font.evaluate(
+                    text,
+                    self.text_transform(color, baseline_adjustment),
+                    params,
+                    |_pos, _transform, _glyph: &Glyph, advance, x| {
+                        hit |= local_position.0 >= x
+                            && local_position.0 <= x + advance
+                            && local_position.1 >= Twips::zero()
+                            && local_position.1 <= params.height();
+                    },
+                );
+                if hit {
+                    let target = tf.target.clone().filter(|target| !target.is_empty());
+                    return Some((url.clone(), target));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Whether any of the text in this field links to a URL.
+    fn has_links(self) -> bool {
+        self.0
+            .read()
+            .text_spans
+            .iter_spans()
+            .any(|(_, _, _, span)| !span.url.is_empty())
+    }
+
+    /// Follow a link that the user clicked on.
+    ///
+    /// `event:` links dispatch a `link` event to the AVM2 text field, and
+    /// `asfunction:` links call a function on the AVM1 clip that contains the
+    /// text field. Anything else is opened by the navigator.
+    fn open_link(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        url: String,
+        target: Option<String>,
+    ) {
+        let scheme_len = url.find(':').map_or(0, |colon| colon + 1);
+        let scheme = url[..scheme_len].to_ascii_lowercase();
+
+        if scheme == "event:" {
+            if let Avm2Value::Object(object) = self.object2() {
+                if let Err(e) =
+                    Avm2::dispatch_text_event(context, object, "link", &url[scheme_len..])
+                {
+                    log::error!("Got {} when dispatching link event", e);
+                }
+            }
+        } else if scheme == "asfunction:" {
+            let parent = match self.parent() {
+                Some(parent) => parent,
+                None => return,
+            };
+            if let Avm1Value::Object(object) = parent.object() {
+                let mut parts = url[scheme_len..].splitn(2, ',');
+                let name = parts.next().unwrap_or_default().trim();
+                let globals = context.avm1.global_object_cell();
+                let swf_version = context.swf.header().version;
+                let mut activation = Avm1Activation::from_nothing(
+                    context.reborrow(),
+                    ActivationIdentifier::root("[Link]"),
+                    swf_version,
+                    globals,
+                    parent,
+                );
+                let args: Vec<Avm1Value<'gc>> = parts
+                    .next()
+                    .map(|arg| {
+                        AvmString::new(activation.context.gc_context, arg.to_string()).into()
+                    })
+                    .into_iter()
+                    .collect();
+                if let Err(e) = object.call_method(name, &args, &mut activation) {
+                    log::error!("Got {} when calling asfunction link", e);
+                }
+            }
+        } else {
+            context.navigator.navigate_to_url(url, target, None);
+        }
+    }
+
     pub fn text_input(self, character: char, context: &mut UpdateContext<'_, 'gc, '_>) {
         if !self.0.read().is_editable {
             return;
@@ -1215,21 +1417,145 @@ impl<'gc> EditText<'gc> {
             }
 
             if changed {
-                let globals = context.avm1.global_object_cell();
-                let swf_version = context.swf.header().version;
-                let mut activation = Avm1Activation::from_nothing(
-                    context.reborrow(),
-                    ActivationIdentifier::root("[Propagate Text Binding]"),
-                    swf_version,
-                    globals,
-                    self.into(),
-                );
-                self.propagate_text_binding(&mut activation);
-                self.on_changed(&mut activation);
+                self.notify_changed(context);
+            }
+        }
+    }
+
+    /// Replace the selected text with text entered by the user, such as from
+    /// the clipboard or an input method editor.
+    pub fn insert_text(self, text: &str, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if !self.0.read().is_editable {
+            return;
+        }
+
+        if let Some(selection) = self.selection() {
+            if selection.is_caret() && text.is_empty() {
+                return;
+            }
+
+            self.replace_text(selection.start(), selection.end(), text, context);
+            self.set_selection(
+                Some(TextSelection::for_position(selection.start() + text.len())),
+                context.gc_context,
+            );
+            self.notify_changed(context);
+        }
+    }
+
+    /// Select all of the text.
+    pub fn select_all(self, gc_context: MutationContext<'gc, '_>) {
+        if self.0.read().selection.is_some() {
+            let length = self.text_length();
+            self.set_selection(Some(TextSelection::for_range(0, length)), gc_context);
+        }
+    }
+
+    /// Copy the selected text to the clipboard.
+    ///
+    /// Nothing is copied out of password fields.
+    pub fn copy(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if self.is_password() {
+            return;
+        }
+
+        if let Some(selection) = self.selection().filter(|s| !s.is_caret()) {
+            let text = self.text()[selection.start()..selection.end()].to_string();
+            context.clipboard.set_contents(text);
+        }
+    }
+
+    /// Copy the selected text to the clipboard, and remove it from the field.
+    pub fn cut(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if self.is_password() || !self.is_editable() {
+            return;
+        }
+
+        if self.selection().map_or(false, |s| !s.is_caret()) {
+            self.copy(context);
+            self.insert_text("", context);
+        }
+    }
+
+    /// Replace the selected text with the contents of the clipboard.
+    ///
+    /// Single-line fields only take the first line of the pasted text.
+    pub fn paste(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if let Some(mut text) = context.clipboard.contents() {
+            if !self.is_multiline() {
+                if let Some(end) = text.find(|c: char| c == '\n' || c == '\r') {
+                    text.truncate(end);
+                }
+            }
+            self.insert_text(&text, context);
+        }
+    }
+
+    /// Show the text that an input method editor is composing at the caret,
+    /// in place of what it had composed before.
+    ///
+    /// Composed text is not propagated to bound variables until it is
+    /// committed with `commit_composition`. An empty string removes it again.
+    pub fn set_composition(self, text: &str, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if !self.0.read().is_editable {
+            return;
+        }
+
+        let range = {
+            let edit_text = self.0.read();
+            match edit_text.composition {
+                Some(composition) => Some(composition),
+                None if text.is_empty() => None,
+                None => edit_text.selection,
             }
+        };
+
+        if let Some(mut range) = range {
+            range.clamp(self.text_length());
+            self.replace_text(range.start(), range.end(), text, context);
+            let end = range.start() + text.len();
+            let mut edit_text = self.0.write(context.gc_context);
+            edit_text.composition = if text.is_empty() {
+                None
+            } else {
+                Some(TextSelection::for_range(range.start(), end))
+            };
+            edit_text.selection = Some(TextSelection::for_position(end));
         }
     }
 
+    /// Replace the text that an input method editor was composing with the
+    /// text it settled on.
+    pub fn commit_composition(self, text: &str, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let composition = self.0.write(context.gc_context).composition.take();
+        if let Some(mut composition) = composition {
+            composition.clamp(self.text_length());
+            self.replace_text(composition.start(), composition.end(), "", context);
+            self.set_selection(
+                Some(TextSelection::for_position(composition.start())),
+                context.gc_context,
+            );
+        }
+
+        self.insert_text(text, context);
+    }
+
+    /// Propagate a change made by the user to the bound variable, and notify
+    /// any `onChanged` listeners.
+    fn notify_changed(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let globals = context.avm1.global_object_cell();
+        let swf_version = context.swf.header().version;
+        let mut activation = Avm1Activation::from_nothing(
+            context.reborrow(),
+            ActivationIdentifier::root("[Propagate Text Binding]"),
+            swf_version,
+            globals,
+            self.into(),
+        );
+        self.propagate_text_binding(&mut activation);
+        self.on_changed(&mut activation);
+    }
+
     fn initialize_as_broadcaster(&self, activation: &mut Avm1Activation<'_, 'gc, '_>) {
         if let Avm1Value::Object(object) = self.object() {
             activation.context.avm1.broadcaster_functions().initialize(
@@ -1347,8 +1673,18 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         Some(self.0.read().static_data.swf.clone())
     }
 
-    fn run_frame(&self, _context: &mut UpdateContext) {
-        // Noop
+    fn run_frame(&self, context: &mut UpdateContext) {
+        // The caret blinks twice a second, so the text is drawn again whenever
+        // it appears or disappears.
+        let caret_blink_on = Utc::now().timestamp_subsec_millis() / 500 == 0;
+        let mut edit_text = self.0.write(context.gc_context);
+        let has_caret =
+            edit_text.is_editable && edit_text.selection.map_or(false, |s| s.is_caret());
+        if has_caret && edit_text.caret_blink_on != caret_blink_on {
+            edit_text.caret_blink_on = caret_blink_on;
+            drop(edit_text);
+            self.invalidate_content();
+        }
     }
 
     fn as_edit_text(&self) -> Option<EditText<'gc>> {
@@ -1495,7 +1831,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         edit_text.drawing.render(context, movie);
 
-        context.renderer.push_mask();
+        context.commands.push_mask();
         let mask = Matrix::create_box(
             edit_text.bounds.width().to_pixels() as f32,
             edit_text.bounds.height().to_pixels() as f32,
@@ -1503,11 +1839,11 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
             Twips::zero(),
             Twips::zero(),
         );
-        context.renderer.draw_rect(
+        context.commands.draw_rect(
             Color::from_rgb(0, 0xff),
             &(context.transform_stack.transform().matrix * mask),
         );
-        context.renderer.activate_mask();
+        context.commands.activate_mask();
 
         // TODO: Where does this come from? How is this different than INTERNAL_PADDING? Does this apply to y as well?
         // If this is actually right, offset the border in `redraw_border` instead of doing an extra push.
@@ -1523,10 +1859,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         if edit_text.layout.is_empty() && edit_text.is_editable {
             let selection = edit_text.selection;
             if let Some(selection) = selection {
-                if selection.is_caret()
-                    && selection.start() == 0
-                    && Utc::now().timestamp_subsec_millis() / 500 == 0
-                {
+                if selection.is_caret() && selection.start() == 0 && edit_text.caret_blink_on {
                     let caret = context.transform_stack.transform().matrix
                         * Matrix::create_box(
                             1.0,
@@ -1540,7 +1873,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
                             Twips::from_pixels(2.0),
                         );
                     context
-                        .renderer
+                        .commands
                         .draw_rect(Color::from_rgb(0x000000, 0xFF), &caret);
                 }
             }
@@ -1552,12 +1885,12 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
 
         context.transform_stack.pop();
 
-        context.renderer.deactivate_mask();
-        context.renderer.draw_rect(
+        context.commands.deactivate_mask();
+        context.commands.draw_rect(
             Color::from_rgb(0, 0xff),
             &(context.transform_stack.transform().matrix * mask),
         );
-        context.renderer.pop_mask();
+        context.commands.pop_mask();
 
         context.transform_stack.pop();
     }
@@ -1606,7 +1939,15 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         point: (Twips, Twips),
     ) -> Option<DisplayObject<'gc>> {
         // The button is hovered if the mouse is over any child nodes.
-        if self.visible() && self.is_selectable() && self.hit_test_shape(context, point) {
+        // Text with links can be clicked even if it can't be selected. AVM2
+        // text fields are interactive objects, and always receive the mouse
+        // unless `mouseEnabled` is off.
+        let is_avm2 = matches!(self.object2(), Avm2Value::Object(_));
+        if self.visible()
+            && self.mouse_enabled()
+            && (self.is_selectable() || self.has_links() || is_avm2)
+            && self.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
+        {
             Some(self_node)
         } else {
             None
@@ -1622,7 +1963,11 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
         text.has_focus = focused;
         if !focused {
             text.selection = None;
+            text.is_selecting = false;
+            text.composition = None;
         }
+        drop(text);
+        self.invalidate_content();
     }
 
     fn is_focusable(&self) -> bool {
@@ -1637,6 +1982,11 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
     ) -> ClipEventResult {
         match event {
             ClipEvent::Press => {
+                if let Some((url, target)) = self.link_at_position(*context.mouse_position) {
+                    self.open_link(context, url, target);
+                    return ClipEventResult::Handled;
+                }
+
                 let tracker = context.focus_tracker;
                 tracker.set(Some((*self).into()), context);
                 if let Some(position) = self
@@ -1648,45 +1998,102 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
                     self.0.write(context.gc_context).selection =
                         Some(TextSelection::for_position(self.text_length()));
                 }
+                self.0.write(context.gc_context).is_selecting = true;
+                self.invalidate_content();
                 ClipEventResult::Handled
             }
+            ClipEvent::MouseMove => {
+                // Extend the selection to wherever the mouse was dragged.
+                if self.0.read().is_selecting {
+                    if let Some(position) = self.screen_position_to_index(*context.mouse_position) {
+                        let mut edit_text = self.0.write(context.gc_context);
+                        if let Some(selection) = &mut edit_text.selection {
+                            selection.to = position;
+                        }
+                        drop(edit_text);
+                        self.invalidate_content();
+                    }
+                }
+                ClipEventResult::NotHandled
+            }
+            ClipEvent::MouseUp => {
+                self.0.write(context.gc_context).is_selecting = false;
+                ClipEventResult::NotHandled
+            }
             ClipEvent::KeyPress { key_code } => {
                 let mut edit_text = self.0.write(context.gc_context);
                 let selection = edit_text.selection;
                 if let Some(mut selection) = selection {
                     let text = edit_text.text_spans.text();
                     let length = text.len();
+                    let extend = context.keyboard.is_key_down(KeyCode::Shift);
                     match key_code {
                         ButtonKeyCode::Left => {
-                            if (context.ui.is_key_down(KeyCode::Shift) || selection.is_caret())
+                            if (context.keyboard.is_key_down(KeyCode::Shift)
+                                || selection.is_caret())
                                 && selection.to > 0
                             {
                                 selection.to = string_utils::prev_char_boundary(text, selection.to);
-                                if !context.ui.is_key_down(KeyCode::Shift) {
+                                if !context.keyboard.is_key_down(KeyCode::Shift) {
                                     selection.from = selection.to;
                                 }
-                            } else if !context.ui.is_key_down(KeyCode::Shift) {
+                            } else if !context.keyboard.is_key_down(KeyCode::Shift) {
                                 selection.to = selection.start();
                                 selection.from = selection.to;
                             }
                         }
                         ButtonKeyCode::Right => {
-                            if (context.ui.is_key_down(KeyCode::Shift) || selection.is_caret())
+                            if (context.keyboard.is_key_down(KeyCode::Shift)
+                                || selection.is_caret())
                                 && selection.to < length
                             {
                                 selection.to = string_utils::next_char_boundary(text, selection.to);
-                                if !context.ui.is_key_down(KeyCode::Shift) {
+                                if !context.keyboard.is_key_down(KeyCode::Shift) {
                                     selection.from = selection.to;
                                 }
-                            } else if !context.ui.is_key_down(KeyCode::Shift) {
+                            } else if !context.keyboard.is_key_down(KeyCode::Shift) {
                                 selection.to = selection.end();
                                 selection.from = selection.to;
                             }
                         }
+                        ButtonKeyCode::Home => {
+                            let (start, _) = line_bounds(text, selection.to);
+                            selection.move_caret(start, extend);
+                        }
+                        ButtonKeyCode::End => {
+                            let (_, end) = line_bounds(text, selection.to);
+                            selection.move_caret(end, extend);
+                        }
+                        ButtonKeyCode::Up => {
+                            let (start, _) = line_bounds(text, selection.to);
+                            let position = if start > 0 {
+                                // Keep the caret in the same column of the line above.
+                                let column = text[start..selection.to].chars().count();
+                                let (prev_start, prev_end) = line_bounds(text, start - 1);
+                                prev_start + column_offset(&text[prev_start..prev_end], column)
+                            } else {
+                                0
+                            };
+                            selection.move_caret(position, extend);
+                        }
+                        ButtonKeyCode::Down => {
+                            let (start, end) = line_bounds(text, selection.to);
+                            let position = if end < length {
+                                // Keep the caret in the same column of the line below.
+                                let column = text[start..selection.to].chars().count();
+                                let (next_start, next_end) = line_bounds(text, end + 1);
+                                next_start + column_offset(&text[next_start..next_end], column)
+                            } else {
+                                length
+                            };
+                            selection.move_caret(position, extend);
+                        }
                         _ => {}
                     }
                     selection.clamp(length);
                     edit_text.selection = Some(selection);
+                    drop(edit_text);
+                    self.invalidate_content();
                     ClipEventResult::Handled
                 } else {
                     ClipEventResult::NotHandled
@@ -1729,6 +2136,30 @@ struct EditTextStaticData {
     is_device_font: bool,
 }
 
+/// The positions where the line containing `position` starts and ends, not
+/// counting the line break.
+fn line_bounds(text: &str, position: usize) -> (usize, usize) {
+    let is_line_break = |c: char| c == '\n' || c == '\r';
+    let start = text[..position]
+        .rfind(is_line_break)
+        .map(|i| i + 1)
+        .unwrap_or(0);
+    let end = text[position..]
+        .find(is_line_break)
+        .map(|i| position + i)
+        .unwrap_or_else(|| text.len());
+    (start, end)
+}
+
+/// The byte offset of the given column in a line, or the end of the line if
+/// it is shorter than that.
+fn column_offset(line: &str, column: usize) -> usize {
+    line.char_indices()
+        .nth(column)
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| line.len())
+}
+
 #[derive(Copy, Clone, Debug, Collect)]
 #[collect(require_static)]
 pub struct TextSelection {
@@ -1785,6 +2216,15 @@ impl TextSelection {
         }
     }
 
+    /// Moves the caret to the given position. If `extend` is set, the text
+    /// between there and the start of the selection is selected.
+    pub fn move_caret(&mut self, position: usize, extend: bool) {
+        self.to = position;
+        if !extend {
+            self.from = position;
+        }
+    }
+
     /// Checks whether the given position falls within the range of this selection
     pub fn contains(&self, pos: usize) -> bool {
         pos >= self.start() && pos < self.end()
@@ -4,16 +4,19 @@ use crate::avm2::{
     Object as Avm2Object, QName as Avm2QName, StageObject as Avm2StageObject,
     TObject as Avm2TObject,
 };
-use crate::backend::render::ShapeHandle;
+use crate::backend::render::{RenderBackend, ShapeHandle};
+use crate::bitmap::rasterizer::Rasterizer;
+use crate::commands::CommandHandler;
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::display_object::{DisplayObjectBase, HitTestOptions, TDisplayObject};
 use crate::drawing::Drawing;
 use crate::prelude::*;
+use crate::shape_utils::DistilledShape;
 use crate::tag_utils::SwfMovie;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmType, Instantiator};
 use gc_arena::{Collect, GcCell, MutationContext};
-use std::cell::RefMut;
+use std::cell::{Ref, RefMut};
 use std::sync::Arc;
 
 #[derive(Clone, Debug, Collect, Copy)]
@@ -64,6 +67,7 @@ impl<'gc> Graphic<'gc> {
     pub fn new_with_avm2(
         context: &mut UpdateContext<'_, 'gc, '_>,
         avm2_object: Avm2Object<'gc>,
+        movie: Arc<SwfMovie>,
     ) -> Self {
         let static_data = GraphicStatic {
             id: 0,
@@ -83,7 +87,7 @@ impl<'gc> Graphic<'gc> {
                 },
                 shape: Vec::new(),
             },
-            movie: None,
+            movie: Some(movie),
         };
         let drawing = Drawing::new();
 
@@ -106,6 +110,10 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         self.0.read().static_data.id
     }
 
+    fn movie(&self) -> Option<Arc<SwfMovie>> {
+        self.0.read().static_data.movie.clone()
+    }
+
     fn self_bounds(&self) -> BoundingBox {
         if let Some(drawing) = &self.0.read().drawing {
             drawing.self_bounds()
@@ -114,6 +122,14 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
         }
     }
 
+    fn self_edge_bounds(&self) -> BoundingBox {
+        if let Some(drawing) = &self.0.read().drawing {
+            drawing.self_edge_bounds()
+        } else {
+            (&self.0.read().static_data.shape.edge_bounds).into()
+        }
+    }
+
     fn run_frame(&self, _context: &mut UpdateContext) {
         // Noop
     }
@@ -128,16 +144,30 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
             drawing.render(context, self.0.read().static_data.movie.clone());
         } else if let Some(render_handle) = self.0.read().static_data.render_handle {
             context
-                .renderer
+                .commands
                 .render_shape(render_handle, context.transform_stack.transform())
         }
     }
 
+    fn rasterize_self(&self, _renderer: &mut dyn RenderBackend, rasterizer: &mut Rasterizer<'_>) {
+        let read = self.0.read();
+        if let Some(drawing) = &read.drawing {
+            drawing.rasterize(rasterizer);
+        } else {
+            rasterizer.draw_paths(&DistilledShape::from(&read.static_data.shape).paths);
+        }
+    }
+
     fn hit_test_shape(
         &self,
         _context: &mut UpdateContext<'_, 'gc, '_>,
         point: (Twips, Twips),
+        options: HitTestOptions,
     ) -> bool {
+        if options.contains(HitTestOptions::SKIP_INVISIBLE) && !self.visible() {
+            return false;
+        }
+
         // Transform point to local coordinates and test.
         if self.world_bounds().contains(point) {
             let local_matrix = self.global_to_local_matrix();
@@ -207,6 +237,7 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
     }
 
     fn as_drawing(&self, gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
+        self.invalidate_content();
         let mut write = self.0.write(gc_context);
         if write.drawing.is_none() {
             write.drawing = Some(Drawing::new());
@@ -214,6 +245,15 @@ impl<'gc> TDisplayObject<'gc> for Graphic<'gc> {
 
         Some(RefMut::map(write, |m| m.drawing.as_mut().unwrap()))
     }
+
+    fn drawing(&self) -> Option<Ref<'_, Drawing>> {
+        let read = self.0.read();
+        if read.drawing.is_some() {
+            Some(Ref::map(read, |m| m.drawing.as_ref().unwrap()))
+        } else {
+            None
+        }
+    }
 }
 
 /// Static data shared between all instances of a graphic.
@@ -1,10 +1,21 @@
-use crate::backend::render::ShapeHandle;
+use crate::avm1::Object as Avm1Object;
+use crate::avm2::{
+    Activation as Avm2Activation, Error as Avm2Error, Namespace as Avm2Namespace,
+    Object as Avm2Object, QName as Avm2QName, StageObject as Avm2StageObject,
+    TObject as Avm2TObject,
+};
+use crate::backend::render::{MovieLibrary, RenderBackend, ShapeHandle};
+use crate::bitmap::rasterizer::Rasterizer;
+use crate::commands::CommandHandler;
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::display_object::{DisplayObjectBase, HitTestOptions, TDisplayObject};
 use crate::prelude::*;
+use crate::shape_utils::DistilledShape;
 use crate::tag_utils::SwfMovie;
 use crate::types::{Degrees, Percent};
+use crate::vminterface::{AvmType, Instantiator};
 use gc_arena::{Collect, Gc, GcCell, MutationContext};
+use std::cell::{Ref, RefCell};
 use std::sync::Arc;
 use swf::Twips;
 
@@ -18,6 +29,7 @@ pub struct MorphShapeData<'gc> {
     base: DisplayObjectBase<'gc>,
     static_data: Gc<'gc, MorphShapeStatic>,
     ratio: u16,
+    avm2_object: Option<Avm2Object<'gc>>,
 }
 
 impl<'gc> MorphShape<'gc> {
@@ -31,6 +43,7 @@ impl<'gc> MorphShape<'gc> {
                 base: Default::default(),
                 static_data: Gc::allocate(gc_context, static_data),
                 ratio: 0,
+                avm2_object: None,
             },
         ))
     }
@@ -41,6 +54,7 @@ impl<'gc> MorphShape<'gc> {
 
     pub fn set_ratio(&mut self, gc_context: MutationContext<'gc, '_>, ratio: u16) {
         self.0.write(gc_context).ratio = ratio;
+        self.invalidate_content();
     }
 }
 
@@ -51,6 +65,10 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
         self.0.read().static_data.id
     }
 
+    fn movie(&self) -> Option<Arc<SwfMovie>> {
+        Some(self.0.read().static_data.movie.clone())
+    }
+
     fn as_morph_shape(&self) -> Option<Self> {
         Some(*self)
     }
@@ -59,47 +77,119 @@ impl<'gc> TDisplayObject<'gc> for MorphShape<'gc> {
         // Noop
     }
 
-    fn render_self(&self, context: &mut RenderContext) {
-        if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
-            context
-                .renderer
-                .render_shape(frame.shape_handle, context.transform_stack.transform());
-        } else {
-            log::warn!("Missing ratio for morph shape");
+    fn post_instantiation(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        display_object: DisplayObject<'gc>,
+        _init_object: Option<Avm1Object<'gc>>,
+        _instantiated_by: Instantiator,
+        run_frame: bool,
+    ) {
+        if self.vm_type(context) == AvmType::Avm2 {
+            let mut allocator = || {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let mut proto = activation.context.avm2.prototypes().morph_shape;
+                let constr = proto
+                    .get_property(
+                        proto,
+                        &Avm2QName::new(Avm2Namespace::public(), "constructor"),
+                        &mut activation,
+                    )?
+                    .coerce_to_object(&mut activation)?;
+
+                let object = Avm2StageObject::for_display_object(
+                    activation.context.gc_context,
+                    display_object,
+                    proto,
+                )
+                .into();
+                constr.call(Some(object), &[], &mut activation, Some(proto))?;
+
+                Ok(object)
+            };
+            let result: Result<Avm2Object<'gc>, Avm2Error> = allocator();
+
+            match result {
+                Ok(object) => self.0.write(context.gc_context).avm2_object = Some(object),
+                Err(e) => log::error!("Got {} when constructing AVM2 side of display object", e),
+            }
+        }
+
+        if run_frame {
+            self.run_frame(context);
         }
     }
 
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .avm2_object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
+    fn render_self(&self, context: &mut RenderContext) {
+        let read = self.0.read();
+        let static_data = read.static_data;
+        let library = context
+            .library
+            .library_for_movie(Arc::clone(&static_data.movie));
+        let shape_handle = static_data.shape_handle(context.renderer, library, read.ratio);
+        context
+            .commands
+            .render_shape(shape_handle, context.transform_stack.transform());
+    }
+
+    fn rasterize_self(&self, _renderer: &mut dyn RenderBackend, rasterizer: &mut Rasterizer<'_>) {
+        let read = self.0.read();
+        let frame = read.static_data.frame(read.ratio);
+        rasterizer.draw_paths(&DistilledShape::from(&frame.shape).paths);
+    }
+
     fn self_bounds(&self) -> BoundingBox {
-        // TODO: Use the bounds of the current ratio.
-        if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
-            frame.bounds.clone()
-        } else {
-            BoundingBox::default()
-        }
+        let read = self.0.read();
+        let frame = read.static_data.frame(read.ratio);
+        frame.bounds.clone()
     }
 
     fn hit_test_shape(
         &self,
         _context: &mut UpdateContext<'_, 'gc, '_>,
         point: (Twips, Twips),
+        options: HitTestOptions,
     ) -> bool {
+        if options.contains(HitTestOptions::SKIP_INVISIBLE) && !self.visible() {
+            return false;
+        }
+
         if self.world_bounds().contains(point) {
-            if let Some(frame) = self.0.read().static_data.frames.get(&self.ratio()) {
-                let local_matrix = self.global_to_local_matrix();
-                let point = local_matrix * point;
-                return crate::shape_utils::shape_hit_test(&frame.shape, point, &local_matrix);
-            } else {
-                log::warn!("Missing ratio for morph shape");
-            }
+            let local_matrix = self.global_to_local_matrix();
+            let point = local_matrix * point;
+            let read = self.0.read();
+            let frame = read.static_data.frame(read.ratio);
+            return crate::shape_utils::shape_hit_test(&frame.shape, point, &local_matrix);
         }
 
         false
     }
 }
 
+/// Ratios that round to the same multiple of this share an interpolated
+/// frame. This moves no edge by more than a pixel in a tween spanning 4000
+/// pixels, and keeps long tweens from interpolating a frame for every ratio.
+const RATIO_BUCKET_SIZE: u32 = 16;
+
+/// The ratio that the frame shown for `ratio` is interpolated at.
+fn ratio_bucket(ratio: u16) -> u16 {
+    let half = RATIO_BUCKET_SIZE / 2;
+    let bucket = (u32::from(ratio) + half) / RATIO_BUCKET_SIZE * RATIO_BUCKET_SIZE;
+    bucket.min(u16::MAX.into()) as u16
+}
+
 /// A precalculated intermediate frame for a morph shape.
 struct Frame {
-    shape_handle: ShapeHandle,
+    /// The shape registered with the renderer, once the frame was drawn.
+    shape_handle: Option<ShapeHandle>,
     shape: swf::Shape,
     bounds: BoundingBox,
 }
@@ -112,7 +202,9 @@ pub struct MorphShapeStatic {
     id: CharacterId,
     start: swf::MorphShape,
     end: swf::MorphShape,
-    frames: fnv::FnvHashMap<u16, Frame>,
+
+    /// The frames interpolated so far, keyed by ratio bucket.
+    frames: RefCell<fnv::FnvHashMap<u16, Frame>>,
     movie: Arc<SwfMovie>,
 }
 
@@ -126,7 +218,7 @@ impl MorphShapeStatic {
             id: swf_tag.id,
             start: swf_tag.start.clone(),
             end: swf_tag.end.clone(),
-            frames: fnv::FnvHashMap::default(),
+            frames: Default::default(),
             movie,
         };
         // Pre-register the start and end states.
@@ -135,14 +227,48 @@ impl MorphShapeStatic {
         morph_shape
     }
 
+    /// Interpolate the frame for `ratio` and register it with the renderer
+    /// ahead of it being drawn, unless a frame in the same bucket already
+    /// was.
     pub fn register_ratio(&mut self, context: &mut UpdateContext<'_, '_, '_>, ratio: u16) {
-        if self.frames.contains_key(&ratio) {
-            // Already registered.
-            return;
+        let library = context.library.library_for_movie(Arc::clone(&self.movie));
+        self.shape_handle(context.renderer, library, ratio);
+    }
+
+    /// The frame shown for `ratio`, interpolating it if no frame in the same
+    /// bucket was needed before.
+    fn frame(&self, ratio: u16) -> Ref<'_, Frame> {
+        let bucket = ratio_bucket(ratio);
+        if !self.frames.borrow().contains_key(&bucket) {
+            let frame = self.interpolate(bucket);
+            self.frames.borrow_mut().insert(bucket, frame);
         }
 
-        let library = context.library.library_for_movie(Arc::clone(&self.movie));
+        Ref::map(self.frames.borrow(), |frames| &frames[&bucket])
+    }
+
+    /// The shape handle of the frame shown for `ratio`, registering the frame
+    /// with the renderer the first time it is drawn.
+    fn shape_handle(
+        &self,
+        renderer: &mut dyn RenderBackend,
+        library: Option<&MovieLibrary<'_>>,
+        ratio: u16,
+    ) -> ShapeHandle {
+        let bucket = ratio_bucket(ratio);
+        if let Some(shape_handle) = self.frame(bucket).shape_handle {
+            return shape_handle;
+        }
 
+        let mut frames = self.frames.borrow_mut();
+        let frame = frames.get_mut(&bucket).unwrap();
+        let shape_handle = renderer.register_shape((&frame.shape).into(), library);
+        frame.shape_handle = Some(shape_handle);
+        shape_handle
+    }
+
+    /// Interpolate between the start and end shapes at `ratio`.
+    fn interpolate(&self, ratio: u16) -> Frame {
         // Interpolate MorphShapes into a Shape.
         use swf::{FillStyle, LineStyle, ShapeRecord, ShapeStyles};
         // Start shape is ratio 65535, end shape is ratio 0.
@@ -268,12 +394,11 @@ impl MorphShapeStatic {
             shape,
         };
 
-        let frame = Frame {
-            shape_handle: context.renderer.register_shape((&shape).into(), library),
+        Frame {
+            shape_handle: None,
             shape,
             bounds: bounds.into(),
-        };
-        self.frames.insert(ratio, frame);
+        }
     }
 
     fn update_pos(x: &mut Twips, y: &mut Twips, record: &swf::ShapeRecord) {
@@ -512,3 +637,86 @@ fn lerp_gradient(start: &swf::Gradient, end: &swf::Gradient, a: f32, b: f32) ->
         records,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::render::NullRenderer;
+    use swf::{ShapeRecord, StyleChangeData};
+
+    /// A horizontal line that grows from 5 to 15 pixels over the tween.
+    fn growing_line() -> MorphShapeStatic {
+        let morph_shape = |width| swf::MorphShape {
+            shape_bounds: Default::default(),
+            edge_bounds: Default::default(),
+            fill_styles: vec![],
+            line_styles: vec![],
+            shape: vec![
+                ShapeRecord::StyleChange(StyleChangeData {
+                    move_to: Some((Twips::zero(), Twips::zero())),
+                    fill_style_0: None,
+                    fill_style_1: None,
+                    line_style: None,
+                    new_styles: None,
+                }),
+                ShapeRecord::StraightEdge {
+                    delta_x: Twips::from_pixels(width),
+                    delta_y: Twips::zero(),
+                },
+            ],
+        };
+
+        MorphShapeStatic {
+            id: 1,
+            start: morph_shape(5.0),
+            end: morph_shape(15.0),
+            frames: Default::default(),
+            movie: Arc::new(SwfMovie::empty(8)),
+        }
+    }
+
+    #[test]
+    fn ratio_buckets() {
+        assert_eq!(ratio_bucket(0), 0);
+        assert_eq!(ratio_bucket(7), 0);
+        assert_eq!(ratio_bucket(8), 16);
+        assert_eq!(ratio_bucket(32768), 32768);
+        // The end of the tween isn't rounded away.
+        assert_eq!(ratio_bucket(65530), 65535);
+        assert_eq!(ratio_bucket(65535), 65535);
+    }
+
+    #[test]
+    fn frames_are_shared_within_a_bucket() {
+        let morph_shape = growing_line();
+        assert_eq!(
+            morph_shape.frame(3).bounds.x_max,
+            Twips::from_pixels(5.0)
+        );
+        morph_shape.frame(5);
+        assert_eq!(morph_shape.frames.borrow().len(), 1);
+
+        assert_eq!(
+            morph_shape.frame(32770).bounds.x_max,
+            Twips::from_pixels(10.0)
+        );
+        assert_eq!(
+            morph_shape.frame(65535).bounds.x_max,
+            Twips::from_pixels(15.0)
+        );
+        assert_eq!(morph_shape.frames.borrow().len(), 3);
+    }
+
+    #[test]
+    fn frames_are_registered_when_drawn() {
+        let morph_shape = growing_line();
+        let mut renderer = NullRenderer::new();
+
+        morph_shape.frame(100);
+        assert!(morph_shape.frames.borrow()[&96].shape_handle.is_none());
+
+        morph_shape.shape_handle(&mut renderer, None, 90);
+        assert!(morph_shape.frames.borrow()[&96].shape_handle.is_some());
+        assert_eq!(morph_shape.frames.borrow().len(), 1);
+    }
+}
@@ -4,11 +4,13 @@ use crate::avm1::{
 };
 use crate::avm2::Activation as Avm2Activation;
 use crate::avm2::{
-    Avm2, Error as Avm2Error, Namespace as Avm2Namespace, Object as Avm2Object, QName as Avm2QName,
-    StageObject as Avm2StageObject, TObject as Avm2TObject, Value as Avm2Value,
+    Avm2, Error as Avm2Error, Event as Avm2Event, Namespace as Avm2Namespace, Object as Avm2Object,
+    QName as Avm2QName, StageObject as Avm2StageObject, TObject as Avm2TObject, Value as Avm2Value,
 };
 use crate::backend::audio::{PreloadStreamHandle, SoundHandle, SoundInstanceHandle};
+use crate::backend::render::RenderBackend;
 use crate::backend::ui::MouseCursor;
+use crate::bitmap::rasterizer::Rasterizer;
 use bitflags::bitflags;
 
 use crate::avm1::activation::{Activation as Avm1Activation, ActivationIdentifier};
@@ -19,12 +21,13 @@ use crate::display_object::container::{
     ChildContainer, TDisplayObjectContainer,
 };
 use crate::display_object::{
-    Bitmap, Button, DisplayObjectBase, EditText, Graphic, MorphShapeStatic, TDisplayObject, Text,
-    Video,
+    render_scale9, Bitmap, Button, DisplayObjectBase, EditText, Graphic, HitTestOptions,
+    MorphShapeStatic, TDisplayObject, Text, Video,
 };
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult};
 use crate::font::Font;
+use crate::library::SymbolClassProblem;
 use crate::prelude::*;
 use crate::tag_utils::{self, DecodeResult, SwfMovie, SwfSlice, SwfStream};
 use crate::types::{Degrees, Percent};
@@ -83,8 +86,19 @@ pub struct MovieClipData<'gc> {
     has_focus: bool,
     enabled: bool,
     use_hand_cursor: bool,
+
+    /// Whether an AVM2 sprite shows the hand cursor, as set by `buttonMode`.
+    button_mode: bool,
+
+    /// The object whose shape is hit by the mouse in place of this one's, as
+    /// set by `Sprite.hitArea`.
+    hit_area: Option<DisplayObject<'gc>>,
     last_queued_script_frame: Option<FrameNumber>,
     queued_script_frame: Option<FrameNumber>,
+
+    /// The `FrameLabel` objects handed out for this clip's labels, by name,
+    /// which receive `frameLabel` events when their frame is entered.
+    avm2_frame_labels: HashMap<String, Avm2Object<'gc>>,
 }
 
 impl<'gc> MovieClip<'gc> {
@@ -110,8 +124,11 @@ impl<'gc> MovieClip<'gc> {
                 has_focus: false,
                 enabled: true,
                 use_hand_cursor: true,
+                button_mode: false,
+                hit_area: None,
                 last_queued_script_frame: None,
                 queued_script_frame: None,
+                avm2_frame_labels: HashMap::new(),
             },
         ))
     }
@@ -142,8 +159,11 @@ impl<'gc> MovieClip<'gc> {
                 has_focus: false,
                 enabled: true,
                 use_hand_cursor: true,
+                button_mode: false,
+                hit_area: None,
                 last_queued_script_frame: None,
                 queued_script_frame: None,
+                avm2_frame_labels: HashMap::new(),
             },
         ))
     }
@@ -177,8 +197,11 @@ impl<'gc> MovieClip<'gc> {
                 has_focus: false,
                 enabled: true,
                 use_hand_cursor: true,
+                button_mode: false,
+                hit_area: None,
                 last_queued_script_frame: None,
                 queued_script_frame: None,
+                avm2_frame_labels: HashMap::new(),
             },
         ))
     }
@@ -244,6 +267,10 @@ impl<'gc> MovieClip<'gc> {
                 .0
                 .write(context.gc_context)
                 .csm_text_settings(context, reader),
+            TagCode::DefineBinaryData => self
+                .0
+                .write(context.gc_context)
+                .define_binary_data(context, reader),
             TagCode::DefineBits => self
                 .0
                 .write(context.gc_context)
@@ -357,6 +384,10 @@ impl<'gc> MovieClip<'gc> {
             TagCode::DoInitAction => self.do_init_action(context, reader, tag_len),
             TagCode::DoAbc => self.do_abc(context, reader, tag_len),
             TagCode::SymbolClass => self.symbol_class(context, reader),
+            TagCode::DefineScalingGrid => self
+                .0
+                .write(context.gc_context)
+                .define_scaling_grid(context, reader),
             TagCode::DefineSceneAndFrameLabelData => {
                 self.scene_and_frame_labels(reader, &mut static_data)
             }
@@ -573,50 +604,199 @@ impl<'gc> MovieClip<'gc> {
             let id = reader.read_u16()?;
             let class_name = reader.read_str()?.to_string_lossy(reader.encoding());
 
-            if let Some(name) =
-                Avm2QName::from_symbol_class(&class_name, activation.context.gc_context)
+            if id != 0 {
+                activation
+                    .context
+                    .library
+                    .library_for_movie_mut(movie.clone())
+                    .register_debug_name(id, &class_name);
+            }
+
+            if let Err(problem) =
+                self.bind_symbol_class(&mut activation, movie.clone(), id, &class_name)
             {
-                let library = activation
+                activation.context.log.symbol_class_problem(&problem);
+                activation
                     .context
                     .library
-                    .library_for_movie_mut(movie.clone());
-                let domain = library.avm2_domain();
-                let proto = domain
-                    .get_defined_value(&mut activation, name.clone())
-                    .and_then(|v| v.coerce_to_object(&mut activation));
-
-                match proto {
-                    Ok(proto) => {
-                        let library = activation
-                            .context
-                            .library
-                            .library_for_movie_mut(movie.clone());
-
-                        if id == 0 {
-                            //TODO: This assumes only the root movie has `SymbolClass` tags.
-                            self.set_avm2_constructor(activation.context.gc_context, Some(proto));
-                            self.allocate_as_avm2_object(&mut activation.context, self.into());
-                            self.construct_as_avm2_object(&mut activation.context);
-                        } else if let Some(Character::MovieClip(mc)) = library.character_by_id(id) {
-                            mc.set_avm2_constructor(activation.context.gc_context, Some(proto))
-                        } else {
-                            log::warn!(
-                                "Symbol class {} cannot be assigned to invalid character id {}",
-                                class_name,
-                                id
-                            );
+                    .library_for_movie_mut(movie.clone())
+                    .add_symbol_class_problem(problem);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Bind a character of this movie to the class named for it by a
+    /// `SymbolClass` tag, or explain why it can't be bound.
+    fn bind_symbol_class(
+        self,
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        movie: Arc<SwfMovie>,
+        id: CharacterId,
+        class_name: &str,
+    ) -> Result<(), SymbolClassProblem> {
+        let library = activation
+            .context
+            .library
+            .library_for_movie_mut(movie.clone());
+
+        let avm_type = library.avm_type();
+        if avm_type != AvmType::Avm2 {
+            return Err(SymbolClassProblem::AvmMismatch {
+                id,
+                class_name: class_name.to_string(),
+                avm_type,
+            });
+        }
+
+        // A later binding replaces an earlier one, but is still reported.
+        let duplicate =
+            library
+                .symbol_class(id)
+                .map(|bound_class_name| SymbolClassProblem::DuplicateBinding {
+                    id,
+                    class_name: class_name.to_string(),
+                    bound_class_name: bound_class_name.to_string(),
+                });
+
+        let name = Avm2QName::from_symbol_class(class_name, activation.context.gc_context)
+            .ok_or_else(|| SymbolClassProblem::InvalidClassName {
+                id,
+                class_name: class_name.to_string(),
+            })?;
+        let domain = library.avm2_domain();
+        let mut proto = domain
+            .get_defined_value(activation, name)
+            .and_then(|v| v.coerce_to_object(activation))
+            .map_err(|_| SymbolClassProblem::MissingClass {
+                id,
+                class_name: class_name.to_string(),
+            })?;
+
+        let library = activation
+            .context
+            .library
+            .library_for_movie_mut(movie.clone());
+
+        if id == 0 {
+            //TODO: This assumes only the root movie has `SymbolClass` tags.
+            library.bind_symbol_class(id, class_name);
+            self.set_avm2_constructor(activation.context.gc_context, Some(proto));
+            self.allocate_as_avm2_object(&mut activation.context, self.into());
+            self.construct_as_avm2_object(&mut activation.context);
+
+            return duplicate.map_or(Ok(()), Err);
+        }
+
+        match library.character_by_id(id) {
+            Some(Character::MovieClip(mc)) => {
+                mc.set_avm2_constructor(activation.context.gc_context, Some(proto))
+            }
+            Some(Character::Button(button)) => {
+                button.set_avm2_constructor(activation.context.gc_context, Some(proto))
+            }
+            Some(Character::Sound(sound)) => {
+                let sound = *sound;
+                let sound_proto = proto
+                    .get_property(
+                        proto,
+                        &Avm2QName::new(Avm2Namespace::public(), "prototype"),
+                        activation,
+                    )
+                    .and_then(|v| v.coerce_to_object(activation));
+
+                match sound_proto {
+                    Ok(sound_proto) => sound_proto.set_sound(activation.context.gc_context, sound),
+                    Err(e) => log::warn!(
+                        "Got AVM2 error {} when attempting to assign sound symbol class {}",
+                        e,
+                        class_name
+                    ),
+                }
+            }
+            Some(Character::Bitmap(bitmap)) => {
+                // Bitmap symbols are bound to `BitmapData` subclasses, whose
+                // instances start out with a copy of the symbol's pixels.
+                let pixels = bitmap
+                    .bitmap_handle()
+                    .and_then(|handle| activation.context.renderer.get_bitmap_pixels(handle));
+                let bitmap_data_proto = proto
+                    .get_property(
+                        proto,
+                        &Avm2QName::new(Avm2Namespace::public(), "prototype"),
+                        activation,
+                    )
+                    .and_then(|v| v.coerce_to_object(activation));
+
+                match (pixels, bitmap_data_proto) {
+                    (Some(pixels), Ok(bitmap_data_proto)) => {
+                        let mut bitmap_data = crate::bitmap::bitmap_data::BitmapData::default();
+                        bitmap_data.init_pixels(pixels.width, pixels.height, 0, true);
+                        let pixels: Vec<i32> = pixels.data.into();
+                        bitmap_data.set_pixels(pixels.into_iter().map(|p| p.into()).collect());
+                        bitmap_data_proto.init_bitmap_data(
+                            activation.context.gc_context,
+                            GcCell::allocate(activation.context.gc_context, bitmap_data),
+                        );
+                    }
+                    (None, _) => log::warn!(
+                        "Could not read the pixels of bitmap symbol class {}",
+                        class_name
+                    ),
+                    (_, Err(e)) => log::warn!(
+                        "Got AVM2 error {} when attempting to assign bitmap symbol class {}",
+                        e,
+                        class_name
+                    ),
+                }
+            }
+            Some(Character::BinaryData(data)) => {
+                // Binary data symbols are bound to `ByteArray` subclasses,
+                // whose instances start out with a copy of the data.
+                let data = data.clone();
+                let byte_array_proto = proto
+                    .get_property(
+                        proto,
+                        &Avm2QName::new(Avm2Namespace::public(), "prototype"),
+                        activation,
+                    )
+                    .and_then(|v| v.coerce_to_object(activation));
+
+                match byte_array_proto {
+                    Ok(byte_array_proto) => {
+                        if let Some(mut storage) =
+                            byte_array_proto.as_bytearray_mut(activation.context.gc_context)
+                        {
+                            storage.write_bytes_at(&data, 0);
                         }
                     }
                     Err(e) => log::warn!(
-                        "Got AVM2 error {} when attempting to assign symbol class {}",
+                        "Got AVM2 error {} when attempting to assign binary data symbol class {}",
                         e,
                         class_name
                     ),
                 }
             }
+            Some(Character::Font(_)) => {
+                // There is no AVM2 `Font` class yet, so the font is only
+                // usable through text fields that name it.
+            }
+            _ => {
+                return Err(SymbolClassProblem::InvalidCharacter {
+                    id,
+                    class_name: class_name.to_string(),
+                })
+            }
         }
 
-        Ok(())
+        activation
+            .context
+            .library
+            .library_for_movie_mut(movie)
+            .bind_symbol_class(id, class_name);
+
+        duplicate.map_or(Ok(()), Err)
     }
 
     #[inline]
@@ -659,9 +839,13 @@ impl<'gc> MovieClip<'gc> {
         Ok(())
     }
 
-    #[allow(dead_code)]
+    /// Whether the playhead of this clip advances every frame.
+    ///
+    /// Clips with a single frame can't be played, and are never playing,
+    /// even while they are entering their only frame.
     pub fn playing(self) -> bool {
-        self.0.read().playing()
+        let mc = self.0.read();
+        mc.playing() && mc.total_frames() > 1
     }
 
     pub fn programmatically_played(self) -> bool {
@@ -891,6 +1075,53 @@ impl<'gc> MovieClip<'gc> {
         values
     }
 
+    /// Get the `FrameLabel` object previously created for a label.
+    pub fn avm2_frame_label(self, label: &str) -> Option<Avm2Object<'gc>> {
+        self.0.read().avm2_frame_labels.get(label).copied()
+    }
+
+    /// Keep the `FrameLabel` object created for a label, so that the same
+    /// object is returned, and receives its events, from then on.
+    pub fn set_avm2_frame_label(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        label: String,
+        object: Avm2Object<'gc>,
+    ) {
+        self.0
+            .write(gc_context)
+            .avm2_frame_labels
+            .insert(label, object);
+    }
+
+    /// Dispatch `frameLabel` to the `FrameLabel` objects of the labels on a
+    /// frame.
+    fn dispatch_frame_label_events(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        frame: FrameNumber,
+    ) {
+        let read = self.0.read();
+        let objects: Vec<Avm2Object<'gc>> = read
+            .static_data
+            .frame_labels
+            .iter()
+            .filter(|(_label, label_frame)| **label_frame == frame)
+            .filter_map(|(label, _label_frame)| read.avm2_frame_labels.get(label).copied())
+            .collect();
+        drop(read);
+
+        for object in objects {
+            let mut frame_label_evt = Avm2Event::new("frameLabel");
+            frame_label_evt.set_bubbles(false);
+            frame_label_evt.set_cancelable(false);
+
+            if let Err(e) = Avm2::dispatch_event(context, frame_label_evt, object) {
+                log::error!("Encountered AVM2 error when dispatching event: {}", e);
+            }
+        }
+    }
+
     pub fn total_frames(self) -> FrameNumber {
         self.0.read().static_data.total_frames
     }
@@ -1093,6 +1324,83 @@ impl<'gc> MovieClip<'gc> {
         self.0.write(context.gc_context).queued_script_frame = Some(frame_id);
     }
 
+    /// Place the display objects of this clip's first frame, and those of the
+    /// clips inside them, without running any scripts or clip events.
+    ///
+    /// This is how clips that are never displayed, such as the children of a
+    /// button's hit test state, get their shapes.
+    pub fn build_first_frame(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if self.current_frame() != 0 {
+            return;
+        }
+        self.0.write(context.gc_context).current_frame = 1;
+
+        let mc = self.0.read();
+        let tag_stream_start = mc.static_data.swf.as_ref().as_ptr() as u64;
+        let data = mc.static_data.swf.clone();
+        let mut reader = data.read_from(mc.tag_stream_pos);
+        drop(mc);
+
+        use swf::TagCode;
+        let tag_callback = |reader: &mut SwfStream<'_>, tag_code, tag_len| match tag_code {
+            TagCode::PlaceObject => self.build_place_object(context, reader, tag_len, 1),
+            TagCode::PlaceObject2 => self.build_place_object(context, reader, tag_len, 2),
+            TagCode::PlaceObject3 => self.build_place_object(context, reader, tag_len, 3),
+            TagCode::PlaceObject4 => self.build_place_object(context, reader, tag_len, 4),
+            _ => Ok(()),
+        };
+        let _ = tag_utils::decode_tags(&mut reader, tag_callback, TagCode::ShowFrame);
+
+        self.0.write(context.gc_context).tag_stream_pos =
+            reader.get_ref().as_ptr() as u64 - tag_stream_start;
+    }
+
+    /// Place a display object for `build_first_frame`, building its own first
+    /// frame the same way.
+    fn build_place_object(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'_>,
+        tag_len: usize,
+        version: u8,
+    ) -> DecodeResult {
+        let place_object = if version == 1 {
+            reader.read_place_object(tag_len)
+        } else {
+            reader.read_place_object_2_or_3(version)
+        }?;
+        let depth = place_object.depth.into();
+        match place_object.action {
+            swf::PlaceObjectAction::Place(id) | swf::PlaceObjectAction::Replace(id) => {
+                let child = match context
+                    .library
+                    .library_for_movie_mut(self.movie().unwrap())
+                    .instantiate_by_id(id, context.gc_context)
+                {
+                    Ok(child) => child,
+                    Err(_) => return Ok(()),
+                };
+                self.replace_at_depth(context, child, depth);
+                child.set_instantiated_by_timeline(context.gc_context, true);
+                child.set_depth(context.gc_context, depth);
+                child.set_parent(context.gc_context, Some(self.into()));
+                child.set_place_frame(context.gc_context, 1);
+                child.apply_place_object(context, self.movie(), &place_object);
+                child.post_instantiation(context, child, None, Instantiator::Movie, false);
+                if let Some(clip) = child.as_movie_clip() {
+                    clip.build_first_frame(context);
+                }
+            }
+            swf::PlaceObjectAction::Modify => {
+                if let Some(child) = self.child_by_depth(depth) {
+                    child.apply_place_object(context, self.movie(), &place_object);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Instantiate a given child object on the timeline at a given depth.
     #[allow(clippy::too_many_arguments)]
     fn instantiate_child(
@@ -1578,17 +1886,23 @@ impl<'gc> MovieClip<'gc> {
         }
     }
 
+    /// Set the AVM2 frame script that runs when `frame_id` is entered,
+    /// replacing any it already had. A `callable` of `None` removes the
+    /// frame's script.
     pub fn register_frame_script(
         self,
         frame_id: FrameNumber,
-        callable: Avm2Object<'gc>,
+        callable: Option<Avm2Object<'gc>>,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) {
         let mut write = self.0.write(context.gc_context);
 
-        write
-            .frame_scripts
-            .push(Avm2FrameScript { frame_id, callable });
+        write.frame_scripts.retain(|fs| fs.frame_id != frame_id);
+        if let Some(callable) = callable {
+            write
+                .frame_scripts
+                .push(Avm2FrameScript { frame_id, callable });
+        }
     }
 
     pub fn set_focusable(self, focusable: bool, context: &mut UpdateContext<'_, 'gc, '_>) {
@@ -1642,6 +1956,71 @@ impl<'gc> MovieClip<'gc> {
         self.0.write(context.gc_context).enabled = enabled;
     }
 
+    /// Find the object under `point` in an AVM2 movie.
+    ///
+    /// Every AVM2 sprite receives mouse events, so the topmost sprite or
+    /// other interactive child wins. Shapes and other non-interactive children
+    /// are hit on behalf of the sprite containing them, as are all children
+    /// when `mouseChildren` is off. A sprite with `mouseEnabled` off is never
+    /// the target itself, and lets those hits through to whatever is beneath.
+    ///
+    /// A sprite with a `hitArea` is hit by the shape of its hit area, even if
+    /// that is invisible, rather than by its own content. A hit area that is
+    /// one of the sprite's children never receives mouse events itself.
+    fn mouse_pick_avm2(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        self_node: DisplayObject<'gc>,
+        point: (Twips, Twips),
+    ) -> Option<DisplayObject<'gc>> {
+        let mouse_enabled = self.mouse_enabled();
+        let mouse_children = self.mouse_children();
+        if !self.visible() || (!mouse_enabled && !mouse_children) {
+            return None;
+        }
+
+        let hit_area = self.hit_area();
+        let clipped = self.clipped_depths_at(context, point);
+        for child in self.iter_render_list().rev() {
+            if child.is_mask() || child.is_masked_at(context, point, &clipped) {
+                continue;
+            }
+            if hit_area.map_or(false, |hit_area| DisplayObject::ptr_eq(hit_area, child)) {
+                continue;
+            }
+            if mouse_children {
+                let result = child.mouse_pick(context, child, point);
+                if result.is_some() {
+                    return result;
+                }
+            }
+            if mouse_enabled
+                && hit_area.is_none()
+                && child.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
+            {
+                return Some(self_node);
+            }
+        }
+
+        if let Some(hit_area) = hit_area {
+            if mouse_enabled && hit_area.hit_test_shape(context, point, HitTestOptions::empty()) {
+                return Some(self_node);
+            }
+        } else if mouse_enabled && self.world_bounds().contains(point) {
+            let local_matrix = self.global_to_local_matrix();
+            if self
+                .0
+                .read()
+                .drawing
+                .hit_test(local_matrix * point, &local_matrix)
+            {
+                return Some(self_node);
+            }
+        }
+
+        None
+    }
+
     pub fn use_hand_cursor(self) -> bool {
         self.0.read().use_hand_cursor
     }
@@ -1653,6 +2032,26 @@ impl<'gc> MovieClip<'gc> {
     ) {
         self.0.write(context.gc_context).use_hand_cursor = use_hand_cursor;
     }
+
+    pub fn button_mode(self) -> bool {
+        self.0.read().button_mode
+    }
+
+    pub fn set_button_mode(self, context: &mut UpdateContext<'_, 'gc, '_>, button_mode: bool) {
+        self.0.write(context.gc_context).button_mode = button_mode;
+    }
+
+    pub fn hit_area(self) -> Option<DisplayObject<'gc>> {
+        self.0.read().hit_area
+    }
+
+    pub fn set_hit_area(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        hit_area: Option<DisplayObject<'gc>>,
+    ) {
+        self.0.write(context.gc_context).hit_area = hit_area;
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
@@ -1732,7 +2131,9 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         drop(mc);
 
         // Run my SWF tags.
-        if self.playing() {
+        // Single-frame clips still have to enter their first frame, so this
+        // looks at the playing flag directly.
+        if self.0.read().playing() {
             self.run_frame_internal((*self).into(), context, true);
         }
 
@@ -1759,6 +2160,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                 write.queued_script_frame = None;
 
                 if is_fresh_frame {
+                    drop(write);
+                    self.dispatch_frame_label_events(context, frame_id);
+                    write = self.0.write(context.gc_context);
+
                     while let Some(fs) = write.frame_scripts.get(index) {
                         if fs.frame_id == frame_id {
                             let callable = fs.callable;
@@ -1769,7 +2174,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                                 &[],
                                 context,
                             ) {
-                                log::error!("Error occured when running AVM2 frame script: {}", e);
+                                context.report_script_error(
+                                    format!("Error occured when running AVM2 frame script: {}", e),
+                                    vec![],
+                                );
                             }
                             write = self.0.write(context.gc_context);
                         }
@@ -1790,24 +2198,47 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn render_self(&self, context: &mut RenderContext<'_, 'gc>) {
-        let movie = self.movie();
+        let render_content = |context: &mut RenderContext<'_, 'gc>| {
+            self.0.read().drawing.render(context, self.movie());
+            self.render_children(context);
+        };
+        if !render_scale9((*self).into(), context, render_content) {
+            render_content(context);
+        }
+    }
 
-        self.0.read().drawing.render(context, movie);
-        self.render_children(context);
+    fn rasterize_self(&self, renderer: &mut dyn RenderBackend, rasterizer: &mut Rasterizer<'_>) {
+        self.0.read().drawing.rasterize(rasterizer);
+        self.rasterize_children(renderer, rasterizer);
     }
 
     fn self_bounds(&self) -> BoundingBox {
         self.0.read().drawing.self_bounds()
     }
 
+    fn self_edge_bounds(&self) -> BoundingBox {
+        self.0.read().drawing.self_edge_bounds()
+    }
+
     fn hit_test_shape(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
         point: (Twips, Twips),
+        options: HitTestOptions,
     ) -> bool {
+        if options.contains(HitTestOptions::SKIP_INVISIBLE) && !self.visible() {
+            return false;
+        }
+
         if self.world_bounds().contains(point) {
+            // Masks aren't part of the clip's art, and only the parts of
+            // masked children that show through their mask count.
+            let clipped = self.clipped_depths_at(context, point);
             for child in self.iter_execution_list() {
-                if child.hit_test_shape(context, point) {
+                if child.is_mask() || child.is_masked_at(context, point, &clipped) {
+                    continue;
+                }
+                if child.hit_test_shape(context, point, options) {
                     return true;
                 }
             }
@@ -1828,6 +2259,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
         self_node: DisplayObject<'gc>,
         point: (Twips, Twips),
     ) -> Option<DisplayObject<'gc>> {
+        if let Avm2Value::Object(_) = self.object2() {
+            return self.mouse_pick_avm2(context, self_node, point);
+        }
+
         if self.visible() {
             if self.world_bounds().contains(point) {
                 // This movieclip operates in "button mode" if it has a mouse handler,
@@ -1848,14 +2283,19 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                     }
                 };
 
-                if is_button_mode && self.hit_test_shape(context, point) {
+                if is_button_mode && self.hit_test_shape(context, point, HitTestOptions::MOUSE_PICK)
+                {
                     return Some(self_node);
                 }
             }
 
             // Maybe we could skip recursing down at all if !world_bounds.contains(point),
             // but a child button can have an invisible hit area outside the parent's bounds.
+            let clipped = self.clipped_depths_at(context, point);
             for child in self.iter_render_list().rev() {
+                if child.is_mask() || child.is_masked_at(context, point, &clipped) {
+                    continue;
+                }
                 let result = child.mouse_pick(context, child, point);
                 if result.is_some() {
                     return result;
@@ -1867,7 +2307,18 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn mouse_cursor(&self) -> MouseCursor {
-        if self.use_hand_cursor() {
+        // AVM2 sprites only show the hand cursor in `buttonMode`.
+        if let Avm2Value::Object(_) = self.object2() {
+            return if self.button_mode() && self.use_hand_cursor() {
+                MouseCursor::Hand
+            } else {
+                MouseCursor::Arrow
+            };
+        }
+
+        // Disabled clips and buttons are still hit by the mouse, but don't
+        // look clickable.
+        if self.use_hand_cursor() && self.enabled() {
             MouseCursor::Hand
         } else {
             MouseCursor::Arrow
@@ -1910,9 +2361,14 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn as_drawing(&self, gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
+        self.invalidate_content();
         Some(RefMut::map(self.0.write(gc_context), |s| &mut s.drawing))
     }
 
+    fn drawing(&self) -> Option<Ref<'_, Drawing>> {
+        Some(Ref::map(self.0.read(), |s| &s.drawing))
+    }
+
     fn post_instantiation(
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -2406,6 +2862,35 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_scaling_grid(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
+    ) -> DecodeResult {
+        let id = reader.read_u16()?;
+        let splitter_rect = reader.read_rectangle()?;
+        let library = context.library.library_for_movie_mut(self.movie());
+        match library.character_by_id(id) {
+            Some(Character::MovieClip(clip)) => {
+                clip.set_scaling_grid(context.gc_context, splitter_rect);
+            }
+            Some(_) => {
+                log::warn!(
+                    "Tried to apply DefineScalingGrid to non-sprite character ID {}",
+                    id
+                );
+            }
+            None => {
+                log::warn!(
+                    "Tried to apply DefineScalingGrid to unregistered character ID {}",
+                    id
+                );
+            }
+        }
+        Ok(())
+    }
+
     #[inline]
     fn preload_video_frame(
         &mut self,
@@ -2465,6 +2950,24 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn define_binary_data(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
+    ) -> DecodeResult {
+        use std::io::Read;
+        let id = reader.read_u16()?;
+        reader.read_u32()?; // Reserved
+        let mut data = Vec::new();
+        reader.get_mut().read_to_end(&mut data)?;
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .register_character(id, Character::BinaryData(data.into()));
+        Ok(())
+    }
+
     #[inline]
     fn define_bits_jpeg_2(
         &mut self,
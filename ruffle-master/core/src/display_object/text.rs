@@ -1,10 +1,18 @@
+use crate::avm1::Object as Avm1Object;
+use crate::avm2::{
+    Activation as Avm2Activation, Error as Avm2Error, Namespace as Avm2Namespace,
+    Object as Avm2Object, QName as Avm2QName, StageObject as Avm2StageObject,
+    TObject as Avm2TObject,
+};
+use crate::commands::CommandHandler;
 use crate::context::{RenderContext, UpdateContext};
-use crate::display_object::{DisplayObjectBase, TDisplayObject};
-use crate::font::TextRenderSettings;
+use crate::display_object::{DisplayObjectBase, HitTestOptions, TDisplayObject};
+use crate::font::{hint_glyph, TextRenderSettings};
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
 use crate::transform::Transform;
 use crate::types::{Degrees, Percent};
+use crate::vminterface::{AvmType, Instantiator};
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::sync::Arc;
 
@@ -18,6 +26,7 @@ pub struct TextData<'gc> {
     base: DisplayObjectBase<'gc>,
     static_data: gc_arena::Gc<'gc, TextStatic>,
     render_settings: TextRenderSettings,
+    avm2_object: Option<Avm2Object<'gc>>,
 }
 
 impl<'gc> Text<'gc> {
@@ -41,6 +50,7 @@ impl<'gc> Text<'gc> {
                     },
                 ),
                 render_settings: Default::default(),
+                avm2_object: None,
             },
         ))
     }
@@ -52,6 +62,38 @@ impl<'gc> Text<'gc> {
     ) {
         self.0.write(gc_context).render_settings = settings
     }
+
+    /// The characters of this text, recovered from the code points of the
+    /// glyphs it draws.
+    ///
+    /// Each block of text that moves to a new line is separated from the
+    /// last by a newline. Glyphs of fonts that aren't in the library are
+    /// skipped.
+    pub fn text(self, context: &mut UpdateContext<'_, 'gc, '_>) -> String {
+        let read = self.0.read();
+        let library = context
+            .library
+            .library_for_movie_mut(read.static_data.swf.clone());
+
+        let mut text = String::new();
+        let mut font_id = 0;
+        for block in &read.static_data.text_blocks {
+            if block.y_offset.is_some() && !text.is_empty() {
+                text.push('\n');
+            }
+            font_id = block.font_id.unwrap_or(font_id);
+
+            if let Some(font) = library.get_font(font_id) {
+                for c in &block.glyphs {
+                    if let Some(glyph) = font.get_glyph(c.index as usize) {
+                        text.extend(std::char::from_u32(glyph.code.into()));
+                    }
+                }
+            }
+        }
+
+        text
+    }
 }
 
 impl<'gc> TDisplayObject<'gc> for Text<'gc> {
@@ -69,6 +111,57 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
         // Noop
     }
 
+    fn post_instantiation(
+        &self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        display_object: DisplayObject<'gc>,
+        _init_object: Option<Avm1Object<'gc>>,
+        _instantiated_by: Instantiator,
+        run_frame: bool,
+    ) {
+        if self.vm_type(context) == AvmType::Avm2 {
+            let mut allocator = || {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let mut proto = activation.context.avm2.prototypes().static_text;
+                let constr = proto
+                    .get_property(
+                        proto,
+                        &Avm2QName::new(Avm2Namespace::public(), "constructor"),
+                        &mut activation,
+                    )?
+                    .coerce_to_object(&mut activation)?;
+
+                let object = Avm2StageObject::for_display_object(
+                    activation.context.gc_context,
+                    display_object,
+                    proto,
+                )
+                .into();
+                constr.call(Some(object), &[], &mut activation, Some(proto))?;
+
+                Ok(object)
+            };
+            let result: Result<Avm2Object<'gc>, Avm2Error> = allocator();
+
+            match result {
+                Ok(object) => self.0.write(context.gc_context).avm2_object = Some(object),
+                Err(e) => log::error!("Got {} when constructing AVM2 side of display object", e),
+            }
+        }
+
+        if run_frame {
+            self.run_frame(context);
+        }
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .avm2_object
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
     fn render_self(&self, context: &mut RenderContext) {
         let tf = self.0.read();
         context.transform_stack.push(&Transform {
@@ -111,9 +204,13 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
                 for c in &block.glyphs {
                     if let Some(glyph) = font.get_glyph(c.index as usize) {
                         context.transform_stack.push(&transform);
+                        let glyph_transform = hint_glyph(
+                            context.transform_stack.transform(),
+                            context.text_rendering.hinting,
+                        );
                         context
-                            .renderer
-                            .render_shape(glyph.shape_handle, context.transform_stack.transform());
+                            .commands
+                            .render_shape(glyph.shape_handle, &glyph_transform);
                         context.transform_stack.pop();
                         transform.matrix.tx += Twips::new(c.advance);
                     }
@@ -131,7 +228,12 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
         &self,
         context: &mut UpdateContext<'_, 'gc, '_>,
         mut point: (Twips, Twips),
+        options: HitTestOptions,
     ) -> bool {
+        if options.contains(HitTestOptions::SKIP_INVISIBLE) && !self.visible() {
+            return false;
+        }
+
         if self.world_bounds().contains(point) {
             // Texts using the "Advanced text rendering" always hit test using their bounding box.
             if self.0.read().render_settings.is_advanced() {
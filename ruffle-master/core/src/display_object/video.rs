@@ -1,22 +1,25 @@
 //! Video player display object
 
-use crate::avm1::{Object as Avm1Object, StageObject as Avm1StageObject};
+use crate::avm1::{Object as Avm1Object, StageObject as Avm1StageObject, Value as Avm1Value};
 use crate::avm2::{Object as Avm2Object, StageObject as Avm2StageObject};
-use crate::backend::render::BitmapHandle;
+use crate::backend::render::{BitmapHandle, BitmapInfo};
 use crate::backend::video::{EncodedFrame, VideoStreamHandle};
 use crate::bounding_box::BoundingBox;
 use crate::collect::CollectWrapper;
+use crate::commands::CommandHandler;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::prelude::*;
+use crate::streams::StreamHandle;
 use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::transform::Transform;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmObject, AvmType, Instantiator};
 use gc_arena::{Collect, GcCell, MutationContext};
 use std::borrow::{Borrow, BorrowMut};
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
-use swf::{CharacterId, DefineVideoStream, VideoFrame};
+use swf::{CharacterId, DefineVideoStream, VideoDeblocking, VideoFrame};
 
 /// A Video display object is a high-level interface to a video player.
 ///
@@ -52,6 +55,26 @@ pub struct VideoData<'gc> {
     /// the prior keyframe. The first frame in the stream will always be
     /// treated as a keyframe regardless of it being flagged as one.
     keyframes: BTreeSet<u32>,
+
+    /// The `NetStream` attached to this video player with `attachVideo`.
+    ///
+    /// While a stream is attached, the video shows the stream's frames rather
+    /// than its own embedded ones.
+    #[collect(require_static)]
+    net_stream: Option<StreamHandle>,
+
+    /// The last frame taken from the attached `NetStream`, and its number
+    /// among the frames the stream showed.
+    net_stream_frame: Option<CollectWrapper<(BitmapInfo, u32)>>,
+
+    /// Whether frames are smoothed when the video is scaled.
+    smoothing: bool,
+
+    /// The deblocking filter set by the `deblocking` ActionScript property.
+    ///
+    /// `0` leaves the choice to the stream, `1` disables deblocking and higher
+    /// values select a filter, in the order of `swf::VideoDeblocking`.
+    deblocking: i32,
 }
 
 /// An optionally-instantiated video stream.
@@ -85,6 +108,16 @@ pub enum VideoSource {
         /// to reconstruct a reference to the embedded bitstream.
         frames: BTreeMap<u32, (usize, usize)>,
     },
+
+    /// A video player constructed by ActionScript, which has no frames of its
+    /// own and only shows an attached `NetStream`.
+    Unbound {
+        /// The width of the video player, in pixels.
+        width: u16,
+
+        /// The height of the video player, in pixels.
+        height: u16,
+    },
 }
 
 impl<'gc> Video<'gc> {
@@ -94,6 +127,15 @@ impl<'gc> Video<'gc> {
         streamdef: DefineVideoStream,
         mc: MutationContext<'gc, '_>,
     ) -> Self {
+        let smoothing = streamdef.is_smoothed;
+        let deblocking = match streamdef.deblocking {
+            VideoDeblocking::UseVideoPacketValue => 0,
+            VideoDeblocking::None => 1,
+            VideoDeblocking::Level1 => 2,
+            VideoDeblocking::Level2 => 3,
+            VideoDeblocking::Level3 => 4,
+            VideoDeblocking::Level4 => 5,
+        };
         let source = GcCell::allocate(
             mc,
             VideoSource::Swf {
@@ -112,6 +154,37 @@ impl<'gc> Video<'gc> {
                 decoded_frame: None,
                 object: None,
                 keyframes: BTreeSet::new(),
+                net_stream: None,
+                net_stream_frame: None,
+                smoothing,
+                deblocking,
+            },
+        ))
+    }
+
+    /// Construct a video player for an AVM2 `Video` object, with no video
+    /// stream of its own.
+    pub fn new_with_avm2(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        avm2_object: Avm2Object<'gc>,
+        width: u16,
+        height: u16,
+    ) -> Self {
+        let source = GcCell::allocate(context.gc_context, VideoSource::Unbound { width, height });
+
+        Video(GcCell::allocate(
+            context.gc_context,
+            VideoData {
+                base: Default::default(),
+                source,
+                stream: VideoStream::Uninstantiated(0),
+                decoded_frame: None,
+                object: Some(avm2_object.into()),
+                keyframes: BTreeSet::new(),
+                net_stream: None,
+                net_stream_frame: None,
+                smoothing: false,
+                deblocking: 0,
             },
         ))
     }
@@ -145,9 +218,53 @@ impl<'gc> Video<'gc> {
                     log::warn!("Invalid bitstream subslice on frame {}", tag.frame_num);
                 }
             }
+            VideoSource::Unbound { .. } => {
+                log::warn!("Attempted to preload frame into a video without a stream");
+            }
         }
     }
 
+    /// Attach a `NetStream` to this video player, or detach the current one.
+    pub fn attach_net_stream(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        net_stream: Option<StreamHandle>,
+    ) {
+        let mut write = self.0.write(gc_context);
+        write.net_stream = net_stream;
+        write.net_stream_frame = None;
+        drop(write);
+        self.invalidate_content();
+    }
+
+    /// Stop showing the current frame of an attached `NetStream`.
+    pub fn clear(self, gc_context: MutationContext<'gc, '_>) {
+        self.0.write(gc_context).net_stream_frame = None;
+        self.invalidate_content();
+    }
+
+    /// Whether frames are smoothed when the video is scaled.
+    pub fn smoothing(self) -> bool {
+        self.0.read().smoothing
+    }
+
+    pub fn set_smoothing(self, gc_context: MutationContext<'gc, '_>, smoothing: bool) {
+        self.0.write(gc_context).smoothing = smoothing;
+        self.invalidate_content();
+    }
+
+    /// The deblocking filter set by the `deblocking` ActionScript property.
+    ///
+    /// Frames are currently always decoded with the deblocking chosen by the
+    /// stream, so this is only remembered for scripts to read back.
+    pub fn deblocking(self) -> i32 {
+        self.0.read().deblocking
+    }
+
+    pub fn set_deblocking(self, gc_context: MutationContext<'gc, '_>, deblocking: i32) {
+        self.0.write(gc_context).deblocking = deblocking;
+    }
+
     /// Seek to a particular frame in the video stream.
     ///
     /// This function ensures that the given `frame_id` is valid by first
@@ -157,6 +274,11 @@ impl<'gc> Video<'gc> {
     /// order. This matches Flash Player behavior.
     pub fn seek(self, context: &mut UpdateContext<'_, 'gc, '_>, mut frame_id: u32) {
         let read = self.0.read();
+        if read.net_stream.is_some() {
+            // The timeline doesn't control attached streams.
+            return;
+        }
+
         if let VideoStream::Uninstantiated(_) = &read.stream {
             drop(read);
 
@@ -168,6 +290,7 @@ impl<'gc> Video<'gc> {
 
         let num_frames = match &*read.source.read() {
             VideoSource::Swf { streamdef, .. } => Some(streamdef.num_frames),
+            VideoSource::Unbound { .. } => None,
         };
 
         if let Some(num_frames) = num_frames {
@@ -261,6 +384,10 @@ impl<'gc> Video<'gc> {
                     }
                 }
             },
+            VideoSource::Unbound { .. } => Err(Box::from(format!(
+                "Attempted to seek to frame {} of a video without a stream",
+                frame_id
+            ))),
         };
 
         drop(read);
@@ -269,6 +396,7 @@ impl<'gc> Video<'gc> {
             Ok(bitmap) => {
                 self.0.write(context.gc_context).decoded_frame =
                     Some((frame_id, CollectWrapper(bitmap)));
+                self.invalidate_content();
             }
             Err(e) => log::error!("Got error when seeking to video frame {}: {}", frame_id, e),
         }
@@ -292,7 +420,8 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
     ) {
         let mut write = self.0.write(context.gc_context);
 
-        let (stream, movie, keyframes) = match &*write.source.read() {
+        let source = write.source;
+        let (stream, movie, keyframes) = match &*source.read() {
             VideoSource::Swf {
                 streamdef,
                 movie,
@@ -338,6 +467,17 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
                 (stream, movie.clone(), keyframes)
             }
+            VideoSource::Unbound { .. } => {
+                // Video players constructed by ActionScript have no stream to
+                // register, and were given their object by their constructor.
+                drop(write);
+
+                if run_frame {
+                    self.run_frame(context);
+                }
+
+                return;
+            }
         };
 
         let starting_seek = if let VideoStream::Uninstantiated(seek_to) = write.stream {
@@ -382,9 +522,24 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         }
     }
 
+    fn run_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let net_stream = self.0.read().net_stream;
+        if let Some(net_stream) = net_stream {
+            let frame = context.stream_manager.current_frame(net_stream);
+            let mut write = self.0.write(context.gc_context);
+            let last_frame = write.net_stream_frame.as_ref().map(|frame| frame.0 .1);
+            if let Some(frame) = frame.filter(|frame| Some(frame.1) != last_frame) {
+                write.net_stream_frame = Some(CollectWrapper(frame));
+                drop(write);
+                self.invalidate_content();
+            }
+        }
+    }
+
     fn id(&self) -> CharacterId {
         match (*self.0.read().source.read()).borrow() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
+            VideoSource::Unbound { .. } => 0,
         }
     }
 
@@ -396,11 +551,33 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 bounding_box.set_width(Twips::from_pixels(streamdef.width as f64));
                 bounding_box.set_height(Twips::from_pixels(streamdef.height as f64));
             }
+            VideoSource::Unbound { width, height } => {
+                bounding_box.set_width(Twips::from_pixels(*width as f64));
+                bounding_box.set_height(Twips::from_pixels(*height as f64));
+            }
         }
 
         bounding_box
     }
 
+    fn object(&self) -> Avm1Value<'gc> {
+        self.0
+            .read()
+            .object
+            .and_then(|o| o.as_avm1_object().ok())
+            .map(Avm1Value::from)
+            .unwrap_or(Avm1Value::Undefined)
+    }
+
+    fn object2(&self) -> Avm2Value<'gc> {
+        self.0
+            .read()
+            .object
+            .and_then(|o| o.as_avm2_object().ok())
+            .map(Avm2Value::from)
+            .unwrap_or(Avm2Value::Undefined)
+    }
+
     fn render(&self, context: &mut RenderContext) {
         if !self.world_bounds().intersects(&context.view_bounds) {
             // Off-screen; culled
@@ -409,10 +586,32 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
         context.transform_stack.push(&*self.transform());
 
-        if let Some((_frame_id, ref bitmap)) = self.0.read().decoded_frame {
-            context
-                .renderer
-                .render_bitmap(bitmap.0, context.transform_stack.transform(), false);
+        let read = self.0.read();
+        if read.net_stream.is_some() {
+            // Stream frames are scaled to fill the video player.
+            if let Some(CollectWrapper((frame, _))) = read.net_stream_frame {
+                let bounds = self.self_bounds();
+                let scale = Transform {
+                    matrix: Matrix::scale(
+                        bounds.width().to_pixels() as f32 / f32::from(frame.width),
+                        bounds.height().to_pixels() as f32 / f32::from(frame.height),
+                    ),
+                    ..Default::default()
+                };
+                context.transform_stack.push(&scale);
+                context.commands.render_bitmap(
+                    frame.handle,
+                    context.transform_stack.transform(),
+                    read.smoothing,
+                );
+                context.transform_stack.pop();
+            }
+        } else if let Some((_frame_id, ref bitmap)) = read.decoded_frame {
+            context.commands.render_bitmap(
+                bitmap.0,
+                context.transform_stack.transform(),
+                read.smoothing,
+            );
         } else {
             log::warn!("Video has no decoded frame to render.");
         }
@@ -1,7 +1,9 @@
 use crate::backend::render::ShapeHandle;
+use crate::bitmap::rasterizer::Rasterizer;
 use crate::bounding_box::BoundingBox;
+use crate::commands::CommandHandler;
 use crate::context::RenderContext;
-use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use crate::shape_utils::{DistilledShape, DrawCommand, DrawPath, WindingRule};
 use crate::tag_utils::SwfMovie;
 use gc_arena::Collect;
 use std::cell::Cell;
@@ -15,9 +17,9 @@ pub struct Drawing {
     shape_bounds: BoundingBox,
     edge_bounds: BoundingBox,
     dirty: Cell<bool>,
-    fills: Vec<(FillStyle, Vec<DrawCommand>)>,
+    fills: Vec<DrawingFill>,
     lines: Vec<(LineStyle, Vec<DrawCommand>)>,
-    current_fill: Option<(FillStyle, Vec<DrawCommand>)>,
+    current_fill: Option<DrawingFill>,
     current_line: Option<(LineStyle, Vec<DrawCommand>)>,
     cursor: (Twips, Twips),
 }
@@ -72,8 +74,13 @@ impl Drawing {
 
                     this.set_line_style(None);
                 }
-                DrawPath::Fill { style, commands } => {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding,
+                } => {
                     this.set_fill_style(Some(style.clone()));
+                    this.set_winding_rule(winding);
 
                     for command in commands {
                         this.draw_command(command);
@@ -94,15 +101,37 @@ impl Drawing {
             self.fills.push(existing);
         }
         if let Some(style) = style {
-            self.current_fill = Some((
+            self.current_fill = Some(DrawingFill {
                 style,
-                vec![DrawCommand::MoveTo {
+                winding: WindingRule::EvenOdd,
+                commands: vec![DrawCommand::MoveTo {
                     x: self.cursor.0,
                     y: self.cursor.1,
                 }],
-            ));
+            });
+        }
+
+        self.dirty.set(true);
+    }
+
+    /// Change how the current fill is filled where its outline overlaps
+    /// itself.
+    pub fn set_winding_rule(&mut self, winding: WindingRule) {
+        if let Some(fill) = &mut self.current_fill {
+            fill.winding = winding;
+            self.dirty.set(true);
         }
+    }
 
+    /// Replace the contents of this drawing with a copy of another.
+    pub fn copy_from(&mut self, other: &Drawing) {
+        self.shape_bounds = other.shape_bounds.clone();
+        self.edge_bounds = other.edge_bounds.clone();
+        self.fills = other.fills.clone();
+        self.lines = other.lines.clone();
+        self.current_fill = other.current_fill.clone();
+        self.current_line = other.current_line.clone();
+        self.cursor = other.cursor;
         self.dirty.set(true);
     }
 
@@ -134,6 +163,16 @@ impl Drawing {
         self.dirty.set(true);
     }
 
+    /// The style of the lines being drawn, if any.
+    pub fn line_style(&self) -> Option<&LineStyle> {
+        self.current_line.as_ref().map(|(style, _)| style)
+    }
+
+    /// The point the next drawing command starts from.
+    pub fn cursor(&self) -> (Twips, Twips) {
+        self.cursor
+    }
+
     pub fn draw_command(&mut self, command: DrawCommand) {
         let mut include_last = false;
         let stroke_width = if let Some((style, _)) = &self.current_line {
@@ -161,15 +200,15 @@ impl Drawing {
         if let Some((_, commands)) = &mut self.current_line {
             commands.push(command.clone());
         }
-        if let Some((_, commands)) = &mut self.current_fill {
-            commands.push(command);
+        if let Some(fill) = &mut self.current_fill {
+            fill.commands.push(command);
         }
 
         if include_last {
             if let Some(command) = self
                 .current_fill
                 .as_ref()
-                .and_then(|(_, commands)| commands.last())
+                .and_then(|fill| fill.commands.last())
             {
                 stretch_bounding_box(&mut self.shape_bounds, command, stroke_width);
                 stretch_bounding_box(&mut self.edge_bounds, command, Twips::zero());
@@ -191,39 +230,7 @@ impl Drawing {
     pub fn render(&self, context: &mut RenderContext, movie: Option<Arc<SwfMovie>>) {
         if self.dirty.get() {
             self.dirty.set(false);
-            let mut paths = Vec::new();
-
-            for (style, commands) in &self.fills {
-                paths.push(DrawPath::Fill {
-                    style,
-                    commands: commands.to_owned(),
-                })
-            }
-
-            // TODO: If the current_fill is not closed, we should automatically close current_line
-
-            if let Some((style, commands)) = &self.current_fill {
-                paths.push(DrawPath::Fill {
-                    style,
-                    commands: commands.to_owned(),
-                })
-            }
-
-            for (style, commands) in &self.lines {
-                paths.push(DrawPath::Stroke {
-                    style,
-                    commands: commands.to_owned(),
-                    is_closed: false, // TODO: Determine this
-                })
-            }
-
-            if let Some((style, commands)) = &self.current_line {
-                paths.push(DrawPath::Stroke {
-                    style,
-                    commands: commands.to_owned(),
-                    is_closed: false, // TODO: Determine this
-                })
-            }
+            let paths = self.paths();
 
             let shape = DistilledShape {
                 paths,
@@ -243,19 +250,63 @@ impl Drawing {
 
         if let Some(handle) = self.render_handle.get() {
             context
-                .renderer
+                .commands
                 .render_shape(handle, context.transform_stack.transform());
         }
     }
 
+    /// Draw this drawing into a bitmap with the software rasterizer.
+    pub fn rasterize(&self, rasterizer: &mut Rasterizer) {
+        rasterizer.draw_paths(&self.paths());
+    }
+
+    /// The fills and strokes that make up this drawing.
+    pub fn paths(&self) -> Vec<DrawPath> {
+        let mut paths = Vec::new();
+
+        for fill in self.fills.iter().chain(&self.current_fill) {
+            paths.push(DrawPath::Fill {
+                style: &fill.style,
+                commands: fill.commands.to_owned(),
+                winding: fill.winding,
+            })
+        }
+
+        // TODO: If the current_fill is not closed, we should automatically close current_line
+
+        for (style, commands) in &self.lines {
+            paths.push(DrawPath::Stroke {
+                style,
+                commands: commands.to_owned(),
+                is_closed: false, // TODO: Determine this
+            })
+        }
+
+        if let Some((style, commands)) = &self.current_line {
+            paths.push(DrawPath::Stroke {
+                style,
+                commands: commands.to_owned(),
+                is_closed: false, // TODO: Determine this
+            })
+        }
+
+        paths
+    }
+
     pub fn self_bounds(&self) -> BoundingBox {
         self.shape_bounds.clone()
     }
 
+    /// The bounds of the edges of this drawing, leaving out the width of its
+    /// strokes.
+    pub fn self_edge_bounds(&self) -> BoundingBox {
+        self.edge_bounds.clone()
+    }
+
     pub fn hit_test(&self, point: (Twips, Twips), local_matrix: &swf::Matrix) -> bool {
         use crate::shape_utils;
-        for path in &self.fills {
-            if shape_utils::draw_command_fill_hit_test(&path.1, point) {
+        for fill in &self.fills {
+            if shape_utils::draw_command_fill_hit_test(&fill.commands, fill.winding, point) {
                 return true;
             }
         }
@@ -280,6 +331,14 @@ impl Drawing {
     }
 }
 
+/// A fill of a drawing, and the outline it fills.
+#[derive(Clone, Debug)]
+struct DrawingFill {
+    style: FillStyle,
+    winding: WindingRule,
+    commands: Vec<DrawCommand>,
+}
+
 fn stretch_bounding_box(
     bounding_box: &mut BoundingBox,
     command: &DrawCommand,
@@ -2,30 +2,84 @@
 
 use std::borrow::Cow;
 
-/// Converts an `f64` to a String with (hopefully) the same output as Flash.
+/// Converts an `f64` to a String with the same output as AVM1.
 /// For example, NAN returns `"NaN"`, and infinity returns `"Infinity"`.
+///
+/// AVM1 rounds numbers to 15 significant digits, so `0.1 + 0.2` is `"0.3"`,
+/// and uses exponential notation below `1e-5` and from `1e15` upwards.
 pub fn f64_to_string(n: f64) -> Cow<'static, str> {
+    format_f64(n, Some(15), -5, 15)
+}
+
+/// Converts an `f64` to a String according to ECMA-262's `ToString`, which
+/// is what AVM2 uses.
+///
+/// Numbers are printed with the fewest digits that still read back as the
+/// same number, so `0.1 + 0.2` is `"0.30000000000000004"`, and use
+/// exponential notation below `1e-6` and from `1e21` upwards. Like Flash
+/// Player, numbers from `1e21` upwards are cut (not rounded) to 15
+/// significant digits.
+pub fn f64_to_ecma_string(n: f64) -> Cow<'static, str> {
+    format_f64(n, None, -6, 21)
+}
+
+/// The most significant digits Flash Player prints for large numbers.
+const MAX_LARGE_DIGITS: usize = 15;
+
+/// Formats a number with `precision` significant digits (or as few as
+/// needed, if `None`), in positional notation if its decimal exponent is in
+/// `min_exp..max_exp` and in exponential notation otherwise.
+fn format_f64(n: f64, precision: Option<usize>, min_exp: i32, max_exp: i32) -> Cow<'static, str> {
     if n.is_nan() {
-        Cow::Borrowed("NaN")
+        return Cow::Borrowed("NaN");
     } else if n == f64::INFINITY {
-        Cow::Borrowed("Infinity")
+        return Cow::Borrowed("Infinity");
     } else if n == f64::NEG_INFINITY {
-        Cow::Borrowed("-Infinity")
-    } else if n != 0.0 && (n.abs() >= 1e15 || n.abs() < 1e-5) {
-        // Exponential notation.
-        // Cheating a bit here; Flash always put a sign in front of the exponent, e.g. 1e+15.
-        // Can't do this with rust format params, so shove it in there manually.
-        let mut s = format!("{:e}", n);
-        if let Some(i) = s.find('e') {
-            if s.as_bytes().get(i + 1) != Some(&b'-') {
-                s.insert(i + 1, '+');
-            }
+        return Cow::Borrowed("-Infinity");
+    } else if n == 0.0 {
+        return Cow::Borrowed("0");
+    }
+
+    let scientific = match precision {
+        Some(precision) => format!("{:.*e}", precision - 1, n.abs()),
+        None => format!("{:e}", n.abs()),
+    };
+    let (mantissa, exp) = scientific.split_at(scientific.find('e').unwrap());
+    let exp: i32 = exp[1..].parse().unwrap();
+    let mut digits = mantissa.replace('.', "");
+    if exp >= max_exp {
+        digits.truncate(MAX_LARGE_DIGITS);
+    }
+    let digits = digits.trim_end_matches('0');
+
+    let mut out = String::new();
+    if n < 0.0 {
+        out.push('-');
+    }
+    if exp < min_exp || exp >= max_exp {
+        out.push_str(&digits[..1]);
+        if digits.len() > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
         }
-        Cow::Owned(s)
+        out.push_str(if exp < 0 { "e-" } else { "e+" });
+        out.push_str(&exp.abs().to_string());
+    } else if exp < 0 {
+        out.push_str("0.");
+        out.extend(std::iter::repeat('0').take((-exp - 1) as usize));
+        out.push_str(digits);
     } else {
-        // Normal number.
-        Cow::Owned(n.to_string())
+        let whole_len = exp as usize + 1;
+        if digits.len() > whole_len {
+            out.push_str(&digits[..whole_len]);
+            out.push('.');
+            out.push_str(&digits[whole_len..]);
+        } else {
+            out.push_str(digits);
+            out.extend(std::iter::repeat('0').take(whole_len - digits.len()));
+        }
     }
+    Cow::Owned(out)
 }
 
 /// Converts an `f64` to an `u16` with ECMAScript `ToUInt16` wrapping behavior.
@@ -60,3 +114,31 @@ pub fn f64_to_wrapping_u32(n: f64) -> u32 {
 pub fn f64_to_wrapping_i32(n: f64) -> i32 {
     f64_to_wrapping_u32(n) as i32
 }
+
+#[cfg(test)]
+mod test {
+    #[test]
+    fn f64_to_ecma_string() {
+        use super::f64_to_ecma_string;
+        assert_eq!(f64_to_ecma_string(0.0), "0");
+        assert_eq!(f64_to_ecma_string(-0.0), "0");
+        assert_eq!(f64_to_ecma_string(-990.123), "-990.123");
+        assert_eq!(f64_to_ecma_string(f64::NAN), "NaN");
+        assert_eq!(f64_to_ecma_string(f64::NEG_INFINITY), "-Infinity");
+        assert_eq!(f64_to_ecma_string(0.1 + 0.2), "0.30000000000000004");
+        assert_eq!(f64_to_ecma_string(1e20), "100000000000000000000");
+        assert_eq!(f64_to_ecma_string(1e21), "1e+21");
+        assert_eq!(f64_to_ecma_string(-1.5e300), "-1.5e+300");
+        assert_eq!(
+            f64_to_ecma_string(1.2315987654321987e21),
+            "1.23159876543219e+21"
+        );
+        assert_eq!(
+            f64_to_ecma_string(1.2315987654321987e-8),
+            "1.2315987654321987e-8"
+        );
+        assert_eq!(f64_to_ecma_string(1e-6), "0.000001");
+        assert_eq!(f64_to_ecma_string(1.5e-7), "1.5e-7");
+        assert_eq!(f64_to_ecma_string(5e-324), "5e-324");
+    }
+}
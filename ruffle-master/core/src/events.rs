@@ -10,6 +10,8 @@ pub enum PlayerEvent {
     MouseLeft,
     MouseWheel { delta: MouseWheelDelta },
     TextInput { codepoint: char },
+    TextComposition { text: String },
+    TextCompositionEnd { text: String },
 }
 
 /// The distance scrolled by the mouse wheel.
@@ -238,6 +240,103 @@ pub enum KeyCode {
     Apostrophe = 222,
 }
 
+/// A set of `KeyCode`s.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+struct KeySet([u64; 4]);
+
+impl KeySet {
+    fn contains(&self, key_code: KeyCode) -> bool {
+        let code = u8::from(key_code) as usize;
+        self.0[code / 64] & (1 << (code % 64)) != 0
+    }
+
+    fn insert(&mut self, key_code: KeyCode) {
+        let code = u8::from(key_code) as usize;
+        self.0[code / 64] |= 1 << (code % 64);
+    }
+
+    fn remove(&mut self, key_code: KeyCode) {
+        let code = u8::from(key_code) as usize;
+        self.0[code / 64] &= !(1 << (code % 64));
+    }
+}
+
+/// The state of the keyboard, as seen by ActionScript.
+///
+/// This is shared between AVM1's `Key` and AVM2's `KeyboardEvent`, so that
+/// both see the same keys held down. It only changes when the player handles
+/// a key event, rather than whenever the UI backend notices one.
+///
+/// Movies often poll `Key.isDown` once per frame, so a key that is pressed and
+/// released before the next frame runs is kept down until that frame is over.
+/// Otherwise, quick taps would be missed entirely.
+#[derive(Debug, Clone)]
+pub struct KeyboardState {
+    /// The keys that are currently held down.
+    down: KeySet,
+
+    /// The keys that were pressed since the last frame.
+    pressed_this_frame: KeySet,
+
+    /// The keys that were pressed and released since the last frame, which
+    /// are released once the frame is over.
+    released_this_frame: KeySet,
+
+    /// The most recently pressed or released key.
+    last_key_code: KeyCode,
+}
+
+impl Default for KeyboardState {
+    fn default() -> Self {
+        Self {
+            down: Default::default(),
+            pressed_this_frame: Default::default(),
+            released_this_frame: Default::default(),
+            last_key_code: KeyCode::Unknown,
+        }
+    }
+}
+
+impl KeyboardState {
+    /// Whether a key is held down.
+    pub fn is_key_down(&self, key_code: KeyCode) -> bool {
+        key_code != KeyCode::Unknown && self.down.contains(key_code)
+    }
+
+    /// The most recently pressed or released key.
+    pub fn last_key_code(&self) -> KeyCode {
+        self.last_key_code
+    }
+
+    /// Record a key being pressed.
+    pub fn key_down(&mut self, key_code: KeyCode) {
+        self.last_key_code = key_code;
+        self.down.insert(key_code);
+        self.pressed_this_frame.insert(key_code);
+        self.released_this_frame.remove(key_code);
+    }
+
+    /// Record a key being released.
+    pub fn key_up(&mut self, key_code: KeyCode) {
+        self.last_key_code = key_code;
+        if self.pressed_this_frame.contains(key_code) {
+            self.released_this_frame.insert(key_code);
+        } else {
+            self.down.remove(key_code);
+        }
+    }
+
+    /// Release the keys that were tapped during the frame that just ran.
+    pub fn end_frame(&mut self) {
+        for (down, released) in self.down.0.iter_mut().zip(&self.released_this_frame.0) {
+            *down &= !released;
+        }
+
+        self.pressed_this_frame = KeySet::default();
+        self.released_this_frame = KeySet::default();
+    }
+}
+
 /// Key codes for SWF4 keyPress button handlers. These are annoyingly different than
 /// `Key.isDown` key codes.
 /// TODO: After 18, these are mostly ASCII... should we just use u8? How are different
@@ -378,3 +477,42 @@ pub fn key_code_to_button_key_code(key_code: KeyCode) -> Option<ButtonKeyCode> {
     };
     Some(out)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn held_keys() {
+        let mut keyboard = KeyboardState::default();
+        keyboard.key_down(KeyCode::Left);
+        keyboard.key_down(KeyCode::Apostrophe);
+        keyboard.end_frame();
+        assert!(keyboard.is_key_down(KeyCode::Left));
+        assert!(keyboard.is_key_down(KeyCode::Apostrophe));
+        assert!(!keyboard.is_key_down(KeyCode::Right));
+
+        keyboard.key_up(KeyCode::Left);
+        assert!(!keyboard.is_key_down(KeyCode::Left));
+        assert!(keyboard.is_key_down(KeyCode::Apostrophe));
+        assert_eq!(keyboard.last_key_code(), KeyCode::Left);
+    }
+
+    #[test]
+    fn tapped_keys_last_until_end_of_frame() {
+        let mut keyboard = KeyboardState::default();
+        keyboard.key_down(KeyCode::Space);
+        keyboard.key_up(KeyCode::Space);
+        assert!(keyboard.is_key_down(KeyCode::Space));
+
+        keyboard.end_frame();
+        assert!(!keyboard.is_key_down(KeyCode::Space));
+
+        // Pressing the key again in the same frame cancels the release.
+        keyboard.key_down(KeyCode::Z);
+        keyboard.key_up(KeyCode::Z);
+        keyboard.key_down(KeyCode::Z);
+        keyboard.end_frame();
+        assert!(keyboard.is_key_down(KeyCode::Z));
+    }
+}
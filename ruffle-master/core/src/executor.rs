@@ -0,0 +1,310 @@
+//! Player-owned task queue for asynchronous work.
+//!
+//! Backends hand the player futures for I/O such as network fetches. Rather
+//! than having every such future lock the player when it finishes, a task
+//! resolves to a `Completion`: a callback that the player runs with its update
+//! context. The `Executor` polls every task once per frame, at the frame
+//! boundary, and runs the completions of finished tasks in the order that the
+//! tasks were spawned. This keeps delivery deterministic regardless of the
+//! order in which the backend finishes the underlying I/O.
+
+use crate::backend::navigator::{Chunk, OwnedChunkStream, OwnedFuture};
+use crate::context::UpdateContext;
+use crate::loader::Error;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::ptr::null;
+use std::rc::Rc;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// A callback run by the player once a task has finished.
+pub type Completion = Box<
+    dyn for<'a, 'gc, 'gc_context> FnOnce(
+        &mut UpdateContext<'a, 'gc, 'gc_context>,
+    ) -> Result<(), Error>,
+>;
+
+/// A future that produces a `Completion` to be run by the player.
+pub type Task = OwnedFuture<Completion, Error>;
+
+/// A callback run by the player with each chunk of a stream, and with `None`
+/// once the stream has ended.
+type ChunkHandler = Box<
+    dyn for<'a, 'gc, 'gc_context> FnMut(
+        &mut UpdateContext<'a, 'gc, 'gc_context>,
+        Option<Result<Chunk, Error>>,
+    ) -> Result<(), Error>,
+>;
+
+/// Outstanding work on an `Executor`.
+enum Work {
+    /// A task, which completes once.
+    Task(Task),
+
+    /// A stream, whose chunks are each handed to the handler.
+    Stream(OwnedChunkStream, Rc<RefCell<ChunkHandler>>),
+}
+
+/// Identifies a task spawned onto an `Executor`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TaskHandle(u64);
+
+/// The player's queue of outstanding tasks.
+#[derive(Default)]
+pub struct Executor {
+    /// All outstanding tasks and streams, in the order they were spawned.
+    tasks: VecDeque<(TaskHandle, Work)>,
+
+    /// The handle that will be given to the next spawned task.
+    next_handle: u64,
+}
+
+impl Executor {
+    /// Construct an empty executor.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The handle that the next spawned task will be given.
+    ///
+    /// This lets a task's completion know which task it belongs to.
+    pub fn next_handle(&self) -> TaskHandle {
+        TaskHandle(self.next_handle)
+    }
+
+    /// Add a task to the queue.
+    ///
+    /// The task will first be polled at the next frame boundary.
+    pub fn spawn(&mut self, task: Task) -> TaskHandle {
+        self.push(Work::Task(task))
+    }
+
+    /// Add a stream to the queue, passing each of its chunks to `on_chunk`.
+    ///
+    /// `on_chunk` is given `None` once the stream has ended, and is not called
+    /// again after that or after it was given an error.
+    pub fn spawn_stream<F>(&mut self, stream: OwnedChunkStream, on_chunk: F) -> TaskHandle
+    where
+        F: for<'a, 'gc, 'gc_context> FnMut(
+                &mut UpdateContext<'a, 'gc, 'gc_context>,
+                Option<Result<Chunk, Error>>,
+            ) -> Result<(), Error>
+            + 'static,
+    {
+        let on_chunk: ChunkHandler = Box::new(on_chunk);
+        self.push(Work::Stream(stream, Rc::new(RefCell::new(on_chunk))))
+    }
+
+    fn push(&mut self, work: Work) -> TaskHandle {
+        let handle = TaskHandle(self.next_handle);
+        self.next_handle += 1;
+        self.tasks.push_back((handle, work));
+        handle
+    }
+
+    /// Spawn a future whose result is passed to `on_complete`.
+    pub fn spawn_with<T, F>(
+        &mut self,
+        future: impl Future<Output = Result<T, Error>> + 'static,
+        on_complete: F,
+    ) -> TaskHandle
+    where
+        T: 'static,
+        F: for<'a, 'gc, 'gc_context> FnOnce(
+                &mut UpdateContext<'a, 'gc, 'gc_context>,
+                Result<T, Error>,
+            ) -> Result<(), Error>
+            + 'static,
+    {
+        self.spawn(Box::pin(async move {
+            let result = future.await;
+            let completion: Completion =
+                Box::new(move |context: &mut UpdateContext<'_, '_, '_>| {
+                    on_complete(context, result)
+                });
+            Ok(completion)
+        }))
+    }
+
+    /// Drop a task without running its completion.
+    ///
+    /// Returns `false` if the task already finished or was cancelled.
+    pub fn cancel(&mut self, handle: TaskHandle) -> bool {
+        let len = self.tasks.len();
+        self.tasks.retain(|(task_handle, _)| *task_handle != handle);
+        self.tasks.len() != len
+    }
+
+    /// Whether any tasks are still outstanding.
+    pub fn has_pending_tasks(&self) -> bool {
+        !self.tasks.is_empty()
+    }
+
+    /// Poll every outstanding task once, and every stream until it has no
+    /// more chunks ready.
+    ///
+    /// Returns the completions of the tasks that finished, and a completion
+    /// for each chunk that a stream yielded, in the order the tasks and
+    /// streams were spawned. Tasks that fail are logged and dropped.
+    pub fn poll(&mut self) -> Vec<Completion> {
+        let waker = null_waker();
+        let mut context = Context::from_waker(&waker);
+        let mut completions = vec![];
+
+        for _ in 0..self.tasks.len() {
+            let (handle, work) = self.tasks.pop_front().unwrap();
+            match work {
+                Work::Task(mut task) => match task.as_mut().poll(&mut context) {
+                    Poll::Pending => self.tasks.push_back((handle, Work::Task(task))),
+                    Poll::Ready(Ok(completion)) => completions.push(completion),
+                    Poll::Ready(Err(e)) => log::error!("Async task failed: {}", e),
+                },
+                Work::Stream(mut stream, on_chunk) => loop {
+                    let chunk = match stream.as_mut().poll_next_chunk(&mut context) {
+                        Poll::Pending => {
+                            self.tasks
+                                .push_back((handle, Work::Stream(stream, on_chunk)));
+                            break;
+                        }
+                        Poll::Ready(chunk) => chunk,
+                    };
+
+                    let is_end = !matches!(chunk, Some(Ok(_)));
+                    let handler = on_chunk.clone();
+                    completions.push(Box::new(move |context: &mut UpdateContext<'_, '_, '_>| {
+                        (&mut *handler.borrow_mut())(context, chunk)
+                    }));
+                    if is_end {
+                        break;
+                    }
+                },
+            }
+        }
+
+        completions
+    }
+}
+
+/// Tasks are polled on every frame, so there is nothing for a waker to do.
+fn null_waker() -> Waker {
+    unsafe fn clone(_data: *const ()) -> RawWaker {
+        RawWaker::new(null(), &NULL_WAKER_VTABLE)
+    }
+    unsafe fn do_nothing(_data: *const ()) {}
+
+    const NULL_WAKER_VTABLE: RawWakerVTable =
+        RawWakerVTable::new(clone, do_nothing, do_nothing, do_nothing);
+
+    unsafe { Waker::from_raw(RawWaker::new(null(), &NULL_WAKER_VTABLE)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::navigator::ChunkStream;
+
+    /// A future that is pending for a number of polls before it completes.
+    struct Delay(u32);
+
+    impl Future for Delay {
+        type Output = Result<(), Error>;
+
+        fn poll(mut self: std::pin::Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if self.0 == 0 {
+                Poll::Ready(Ok(()))
+            } else {
+                self.0 -= 1;
+                Poll::Pending
+            }
+        }
+    }
+
+    fn finished_tasks(executor: &mut Executor, log: &Rc<RefCell<Vec<u32>>>) -> Vec<u32> {
+        executor.poll();
+        log.replace(vec![])
+    }
+
+    fn spawn_logged(executor: &mut Executor, log: &Rc<RefCell<Vec<u32>>>, id: u32, delay: u32) {
+        let log = log.clone();
+        executor.spawn(Box::pin(async move {
+            Delay(delay).await?;
+            log.borrow_mut().push(id);
+            Ok(Box::new(|_context: &mut UpdateContext<'_, '_, '_>| Ok(())) as Completion)
+        }));
+    }
+
+    #[test]
+    fn tasks_complete_in_spawn_order() {
+        let mut executor = Executor::new();
+        let log = Rc::new(RefCell::new(vec![]));
+
+        spawn_logged(&mut executor, &log, 1, 1);
+        spawn_logged(&mut executor, &log, 2, 0);
+        spawn_logged(&mut executor, &log, 3, 0);
+        spawn_logged(&mut executor, &log, 4, 2);
+
+        assert_eq!(finished_tasks(&mut executor, &log), vec![2, 3]);
+        assert_eq!(finished_tasks(&mut executor, &log), vec![1]);
+        assert!(executor.has_pending_tasks());
+        assert_eq!(finished_tasks(&mut executor, &log), vec![4]);
+        assert!(!executor.has_pending_tasks());
+    }
+
+    /// A stream that yields a number of one byte chunks, and is pending
+    /// between each of them.
+    struct Drip {
+        remaining: u8,
+        ready: bool,
+    }
+
+    impl ChunkStream for Drip {
+        fn poll_next_chunk(
+            mut self: std::pin::Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Chunk, Error>>> {
+            if self.remaining == 0 {
+                return Poll::Ready(None);
+            }
+            self.ready = !self.ready;
+            if self.ready {
+                return Poll::Pending;
+            }
+            self.remaining -= 1;
+            Poll::Ready(Some(Ok(Chunk {
+                data: vec![self.remaining],
+                total_len: None,
+            })))
+        }
+    }
+
+    #[test]
+    fn streams_deliver_each_chunk() {
+        let mut executor = Executor::new();
+        let stream = Drip {
+            remaining: 2,
+            ready: true,
+        };
+        executor.spawn_stream(Box::pin(stream), |_context, _chunk| Ok(()));
+
+        // The first chunk, and then the second chunk and the end of the stream.
+        assert_eq!(executor.poll().len(), 1);
+        assert!(executor.has_pending_tasks());
+        assert_eq!(executor.poll().len(), 2);
+        assert!(!executor.has_pending_tasks());
+    }
+
+    #[test]
+    fn cancelled_tasks_never_complete() {
+        let mut executor = Executor::new();
+        let log = Rc::new(RefCell::new(vec![]));
+
+        spawn_logged(&mut executor, &log, 1, 1);
+        let handle = executor.spawn(Box::pin(async { Err(Error::Cancelled) }));
+        assert!(executor.cancel(handle));
+        assert!(!executor.cancel(handle));
+
+        assert!(finished_tasks(&mut executor, &log).is_empty());
+        assert_eq!(finished_tasks(&mut executor, &log), vec![1]);
+    }
+}
@@ -6,6 +6,11 @@ use crate::avm1::Value as Avm1Value;
 use crate::avm1::{
     AvmString as Avm1String, Object as Avm1Object, ScriptObject as Avm1ScriptObject,
 };
+use crate::avm2::{
+    Activation as Avm2Activation, ArrayObject as Avm2ArrayObject, ArrayStorage as Avm2ArrayStorage,
+    Namespace as Avm2Namespace, Object as Avm2Object, QName as Avm2QName,
+    ScriptObject as Avm2ScriptObject, TObject as _, Value as Avm2Value,
+};
 use crate::context::UpdateContext;
 use gc_arena::Collect;
 use std::collections::BTreeMap;
@@ -185,6 +190,113 @@ impl Value {
     }
 }
 
+impl Value {
+    pub fn from_avm2<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        value: Avm2Value<'gc>,
+    ) -> Result<Value, crate::avm2::Error> {
+        Self::from_avm2_inner(activation, value, &mut Vec::new())
+    }
+
+    /// Converts an AVM2 value, tracking the objects currently being converted
+    /// in `ancestors` so that self-referencing structures become `null`
+    /// instead of recursing forever.
+    fn from_avm2_inner<'gc>(
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+        value: Avm2Value<'gc>,
+        ancestors: &mut Vec<Avm2Object<'gc>>,
+    ) -> Result<Value, crate::avm2::Error> {
+        Ok(match value {
+            Avm2Value::Undefined | Avm2Value::Null => Value::Null,
+            Avm2Value::Bool(value) => Value::Bool(value),
+            Avm2Value::Number(value) => Value::Number(value),
+            Avm2Value::Unsigned(value) => Value::Number(f64::from(value)),
+            Avm2Value::Integer(value) => Value::Number(f64::from(value)),
+            Avm2Value::String(value) => Value::String(value.to_string()),
+            Avm2Value::Object(mut object) => {
+                if ancestors
+                    .iter()
+                    .any(|ancestor| Avm2Object::ptr_eq(*ancestor, object))
+                {
+                    return Ok(Value::Null);
+                }
+                ancestors.push(object);
+
+                let array_values = object
+                    .as_array_storage()
+                    .map(|array| array.iter().collect::<Vec<_>>());
+                let result = if let Some(array_values) = array_values {
+                    let mut values = Vec::with_capacity(array_values.len());
+                    for value in array_values {
+                        values.push(Value::from_avm2_inner(
+                            activation,
+                            value.unwrap_or(Avm2Value::Undefined),
+                            ancestors,
+                        )?);
+                    }
+                    Value::List(values)
+                } else {
+                    let mut values = BTreeMap::new();
+                    let mut index = 1;
+                    while let Some(name) = object.get_enumerant_name(index) {
+                        let value = object.get_property(object, &name, activation)?;
+                        values.insert(
+                            name.local_name().to_string(),
+                            Value::from_avm2_inner(activation, value, ancestors)?,
+                        );
+                        index += 1;
+                    }
+                    Value::Object(values)
+                };
+
+                ancestors.pop();
+                result
+            }
+        })
+    }
+
+    pub fn into_avm2<'gc>(
+        self,
+        activation: &mut Avm2Activation<'_, 'gc, '_>,
+    ) -> Result<Avm2Value<'gc>, crate::avm2::Error> {
+        Ok(match self {
+            Value::Null => Avm2Value::Null,
+            Value::Bool(value) => Avm2Value::Bool(value),
+            Value::Number(value) => Avm2Value::Number(value),
+            Value::String(value) => {
+                Avm2Value::String(Avm1String::new(activation.context.gc_context, value))
+            }
+            Value::Object(values) => {
+                let mut object = Avm2ScriptObject::object(
+                    activation.context.gc_context,
+                    activation.avm2().prototypes().object,
+                );
+                for (key, value) in values {
+                    let value = value.into_avm2(activation)?;
+                    let name = Avm2QName::new(
+                        Avm2Namespace::public(),
+                        Avm1String::new(activation.context.gc_context, key),
+                    );
+                    object.set_property(object, &name, value, activation)?;
+                }
+                object.into()
+            }
+            Value::List(values) => {
+                let mut storage = Avm2ArrayStorage::new(0);
+                for value in values {
+                    storage.push(value.into_avm2(activation)?);
+                }
+                Avm2ArrayObject::from_array(
+                    storage,
+                    activation.avm2().prototypes().array,
+                    activation.context.gc_context,
+                )
+                .into()
+            }
+        })
+    }
+}
+
 #[derive(Collect, Clone)]
 #[collect(no_drop)]
 pub enum Callback<'gc> {
@@ -192,6 +304,9 @@ pub enum Callback<'gc> {
         this: Avm1Value<'gc>,
         method: Avm1Object<'gc>,
     },
+    Avm2 {
+        method: Avm2Object<'gc>,
+    },
 }
 
 impl<'gc> Callback<'gc> {
@@ -227,6 +342,33 @@ impl<'gc> Callback<'gc> {
                     Value::Null
                 }
             }
+            Callback::Avm2 { method } => {
+                let mut activation = Avm2Activation::from_nothing(context.reborrow());
+                let mut avm2_args = Vec::new();
+                for arg in args {
+                    match arg.into_avm2(&mut activation) {
+                        Ok(arg) => avm2_args.push(arg),
+                        Err(e) => {
+                            log::error!("Unable to convert ExternalInterface argument: {}", e);
+                            return Value::Null;
+                        }
+                    }
+                }
+                match method
+                    .call(None, &avm2_args, &mut activation, None)
+                    .and_then(|value| Value::from_avm2(&mut activation, value))
+                {
+                    Ok(result) => result,
+                    Err(e) => {
+                        log::error!(
+                            "Unhandled AVM2 exception in ExternalInterface callback {}: {}",
+                            name,
+                            e
+                        );
+                        Value::Null
+                    }
+                }
+            }
         }
     }
 }
@@ -276,6 +418,10 @@ impl<'gc> ExternalInterface<'gc> {
         }
     }
 
+    pub fn remove_callback(&mut self, name: &str) {
+        self.callbacks.remove(name);
+    }
+
     pub fn get_callback(&self, name: &str) -> Option<Callback<'gc>> {
         self.callbacks.get(name).cloned()
     }
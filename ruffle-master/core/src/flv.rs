@@ -0,0 +1,322 @@
+//! FLV container demuxing
+//!
+//! FLV files are a sequence of timestamped tags, each of which carries a
+//! chunk of audio, a single video frame, or a script data message encoded in
+//! AMF0. This module splits a file into its tags so that `NetStream` can feed
+//! them to the audio and video decoders.
+
+use crate::amf::{Amf0Reader, ScriptValue};
+use std::convert::TryInto;
+
+pub type Error = Box<dyn std::error::Error>;
+
+/// The size of the FLV file header, not counting the first previous tag size.
+pub const HEADER_LENGTH: usize = 9;
+
+/// The size of a tag header.
+const TAG_HEADER_LENGTH: usize = 11;
+
+/// A demuxed FLV file.
+#[derive(Debug, PartialEq)]
+pub struct FlvFile<'a> {
+    /// Whether the header declares that the file contains audio.
+    pub has_audio: bool,
+
+    /// Whether the header declares that the file contains video.
+    pub has_video: bool,
+
+    /// Every complete tag in the file, in file order.
+    pub tags: Vec<FlvTag<'a>>,
+}
+
+/// A single tag of an FLV file.
+#[derive(Debug, PartialEq)]
+pub struct FlvTag<'a> {
+    /// The time at which this tag takes effect, in milliseconds.
+    pub timestamp: u32,
+
+    pub data: FlvTagData<'a>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum FlvTagData<'a> {
+    Audio {
+        /// The SoundFormat field of the tag, e.g. 2 for MP3.
+        format: u8,
+
+        /// The sample rate in Hz.
+        sample_rate: u16,
+        is_16_bit: bool,
+        is_stereo: bool,
+        data: &'a [u8],
+    },
+    Video {
+        /// Whether this frame can be decoded without any prior frames.
+        is_keyframe: bool,
+
+        /// The CodecID field of the tag, e.g. 2 for Sorenson H.263.
+        codec_id: u8,
+
+        /// The frame's bitstream, in the same form as a SWF `VideoFrame` tag.
+        data: &'a [u8],
+    },
+    Script {
+        /// The name of the handler this message is sent to, e.g. `onMetaData`.
+        name: String,
+        value: ScriptValue,
+    },
+
+    /// A tag of a type that we don't understand.
+    Unknown(u8),
+}
+
+/// Demux an FLV file.
+///
+/// Progressive downloads may be cut off part way through a tag; any trailing
+/// incomplete tag is ignored. Tags that can't be parsed are skipped.
+pub fn parse_flv(data: &[u8]) -> Result<FlvFile<'_>, Error> {
+    let first_tag = first_tag_position(data)?;
+    let flags = data[4];
+    let (tags, _) = parse_flv_tags(data, first_tag);
+
+    Ok(FlvFile {
+        has_audio: flags & 0x04 != 0,
+        has_video: flags & 0x01 != 0,
+        tags,
+    })
+}
+
+/// Check the header of an FLV file, and find the position of its first tag.
+pub fn first_tag_position(data: &[u8]) -> Result<usize, Error> {
+    if data.len() < HEADER_LENGTH || &data[0..3] != b"FLV" {
+        return Err("Not an FLV file".into());
+    }
+    let data_offset = u32::from_be_bytes(data[5..9].try_into().unwrap()) as usize;
+
+    // Each tag is preceded by the size of the previous one, which we don't need.
+    data_offset
+        .max(HEADER_LENGTH)
+        .checked_add(4)
+        .ok_or_else(|| "Invalid FLV header size".into())
+}
+
+/// Demux the complete tags of an FLV file, starting at `pos`.
+///
+/// Returns the tags along with the position after the last of them, from
+/// which demuxing can continue once more of the file has downloaded.
+pub fn parse_flv_tags(data: &[u8], mut pos: usize) -> (Vec<FlvTag<'_>>, usize) {
+    let mut tags = vec![];
+    while let Some(header) = pos
+        .checked_add(TAG_HEADER_LENGTH)
+        .and_then(|header_end| data.get(pos..header_end))
+    {
+        let tag_type = header[0] & 0x1f;
+        let size = read_u24(&header[1..4]) as usize;
+        let timestamp = read_u24(&header[4..7]) | (u32::from(header[7]) << 24);
+        let body_start = pos + TAG_HEADER_LENGTH;
+        let body = match data.get(body_start..body_start + size) {
+            Some(body) => body,
+            None => break,
+        };
+
+        let data = match tag_type {
+            8 => parse_audio(body),
+            9 => parse_video(body),
+            18 => parse_script(body),
+            _ => Ok(FlvTagData::Unknown(tag_type)),
+        };
+        match data {
+            Ok(data) => tags.push(FlvTag { timestamp, data }),
+            Err(e) => log::warn!("Skipping malformed FLV tag at offset {}: {}", pos, e),
+        }
+        pos = body_start + size + 4;
+    }
+
+    (tags, pos)
+}
+
+fn read_u24(data: &[u8]) -> u32 {
+    (u32::from(data[0]) << 16) | (u32::from(data[1]) << 8) | u32::from(data[2])
+}
+
+fn parse_audio(body: &[u8]) -> Result<FlvTagData<'_>, Error> {
+    let flags = *body.first().ok_or("Empty FLV audio tag")?;
+    let format = flags >> 4;
+    let sample_rate = match format {
+        // Nellymoser and MP3 variants with a fixed sample rate.
+        4 => 16000,
+        5 | 14 => 8000,
+        _ => [5512, 11025, 22050, 44100][usize::from((flags >> 2) & 0b11)],
+    };
+
+    Ok(FlvTagData::Audio {
+        format,
+        sample_rate,
+        is_16_bit: flags & 0b10 != 0,
+        is_stereo: flags & 0b1 != 0,
+        data: &body[1..],
+    })
+}
+
+fn parse_video(body: &[u8]) -> Result<FlvTagData<'_>, Error> {
+    let flags = *body.first().ok_or("Empty FLV video tag")?;
+    let codec_id = flags & 0x0f;
+
+    // VP6 frames in FLV start with a byte giving the amount of cropping to
+    // apply, which isn't present in SWF video frames.
+    let data = match codec_id {
+        4 | 5 => body.get(2..).ok_or("Truncated FLV VP6 frame")?,
+        _ => &body[1..],
+    };
+
+    Ok(FlvTagData::Video {
+        is_keyframe: flags >> 4 == 1,
+        codec_id,
+        data,
+    })
+}
+
+fn parse_script(body: &[u8]) -> Result<FlvTagData<'_>, Error> {
+    let mut reader = Amf0Reader::new(body);
+    let name = match reader.read_value()? {
+        ScriptValue::String(name) => name,
+        _ => return Err("FLV script tag does not start with a handler name".into()),
+    };
+    let value = reader.read_value()?;
+
+    Ok(FlvTagData::Script { name, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(tag_type: u8, timestamp: u32, body: &[u8]) -> Vec<u8> {
+        let mut tag = vec![tag_type];
+        tag.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        tag.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+        tag.push((timestamp >> 24) as u8);
+        tag.extend_from_slice(&[0, 0, 0]);
+        tag.extend_from_slice(body);
+        tag.extend_from_slice(&(body.len() as u32 + 11).to_be_bytes());
+        tag
+    }
+
+    fn amf0_string(s: &str) -> Vec<u8> {
+        let mut data = vec![0x02];
+        data.extend_from_slice(&(s.len() as u16).to_be_bytes());
+        data.extend_from_slice(s.as_bytes());
+        data
+    }
+
+    #[test]
+    fn demux() {
+        let mut file = b"FLV\x01\x05\x00\x00\x00\x09\x00\x00\x00\x00".to_vec();
+
+        let mut metadata = amf0_string("onMetaData");
+        metadata.extend_from_slice(&[0x08, 0, 0, 0, 2]);
+        metadata.extend_from_slice(b"\x00\x08duration\x00");
+        metadata.extend_from_slice(&2.5f64.to_be_bytes());
+        metadata.extend_from_slice(b"\x00\x06stereo\x01\x01");
+        metadata.extend_from_slice(&[0, 0, 0x09]);
+        file.extend(tag(18, 0, &metadata));
+
+        file.extend(tag(9, 0, &[0x12, 0xaa, 0xbb]));
+        file.extend(tag(9, 40, &[0x24, 0x00, 0xcc]));
+        file.extend(tag(8, 0x0100_0000, &[0x2e, 0xff, 0xfb]));
+
+        // A truncated tag at the end of a partial download.
+        file.extend(&tag(9, 80, &[0x22, 0x01, 0x02])[..8]);
+
+        let flv = parse_flv(&file).unwrap();
+        assert!(flv.has_audio);
+        assert!(flv.has_video);
+        assert_eq!(flv.tags.len(), 4);
+
+        match &flv.tags[0].data {
+            FlvTagData::Script { name, value } => {
+                assert_eq!(name, "onMetaData");
+                assert_eq!(value.get("duration").and_then(|v| v.as_number()), Some(2.5));
+                assert_eq!(value.get("stereo"), Some(&ScriptValue::Boolean(true)));
+            }
+            other => panic!("Expected script tag, got {:?}", other),
+        }
+        assert_eq!(
+            flv.tags[1].data,
+            FlvTagData::Video {
+                is_keyframe: true,
+                codec_id: 2,
+                data: &[0xaa, 0xbb],
+            }
+        );
+        // VP6 frames lose their adjustment byte.
+        assert_eq!(
+            flv.tags[2].data,
+            FlvTagData::Video {
+                is_keyframe: false,
+                codec_id: 4,
+                data: &[0xcc],
+            }
+        );
+        assert_eq!(flv.tags[3].timestamp, 0x0100_0000);
+        assert_eq!(
+            flv.tags[3].data,
+            FlvTagData::Audio {
+                format: 2,
+                sample_rate: 44100,
+                is_16_bit: true,
+                is_stereo: false,
+                data: &[0xff, 0xfb],
+            }
+        );
+    }
+
+    #[test]
+    fn skip_bad_script_tag() {
+        let mut file = b"FLV\x01\x01\x00\x00\x00\x09\x00\x00\x00\x00".to_vec();
+        file.extend(tag(18, 0, &[0x00, 0x01]));
+        file.extend(tag(18, 0, &amf0_string("onCuePoint")[..4]));
+        file.extend(tag(9, 40, &[0x12, 0xaa]));
+
+        let flv = parse_flv(&file).unwrap();
+        assert_eq!(
+            flv.tags,
+            vec![FlvTag {
+                timestamp: 40,
+                data: FlvTagData::Video {
+                    is_keyframe: true,
+                    codec_id: 2,
+                    data: &[0xaa],
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn demux_in_parts() {
+        let mut file = b"FLV\x01\x01\x00\x00\x00\x09\x00\x00\x00\x00".to_vec();
+        file.extend(tag(9, 0, &[0x12, 0xaa]));
+        file.extend(tag(9, 40, &[0x22, 0xbb]));
+
+        let first_tag = first_tag_position(&file).unwrap();
+        let (tags, pos) = parse_flv_tags(&file[..30], first_tag);
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].timestamp, 0);
+
+        let (tags, pos) = parse_flv_tags(&file, pos);
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].timestamp, 40);
+        assert_eq!(pos, file.len());
+    }
+
+    #[test]
+    fn header_size_past_end() {
+        let flv = parse_flv(b"FLV\x01\x01\xff\xff\xff\xff\x00\x00\x00\x00").unwrap();
+        assert!(flv.tags.is_empty());
+    }
+
+    #[test]
+    fn not_flv() {
+        assert!(parse_flv(b"CWS\x0a\x00\x00\x00\x00\x00").is_err());
+    }
+}
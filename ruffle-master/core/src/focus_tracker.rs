@@ -1,7 +1,55 @@
 use crate::avm1::{Avm1, Value};
 use crate::context::UpdateContext;
+use crate::display_object::TDisplayObjectContainer;
 pub use crate::display_object::{DisplayObject, TDisplayObject};
 use gc_arena::{Collect, GcCell, MutationContext};
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// An object that the focus can be moved to with Tab, as reported to
+/// accessibility and debugging tools.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TabStop {
+    /// The path of the object, such as `_level0.menu.start`.
+    pub path: String,
+
+    /// What kind of object this is: a `button`, `movieClip` or `textField`.
+    pub kind: &'static str,
+
+    /// The bounds of the object on the stage, in pixels.
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+
+    /// Whether the object has focus.
+    pub has_focus: bool,
+}
+
+impl TabStop {
+    fn from_object(object: DisplayObject<'_>, has_focus: bool) -> Self {
+        let kind = if object.as_button().is_some() {
+            "button"
+        } else if object.as_edit_text().is_some() {
+            "textField"
+        } else {
+            "movieClip"
+        };
+        let bounds = object.world_bounds();
+
+        Self {
+            path: object.path(),
+            kind,
+            x: bounds.x_min.to_pixels(),
+            y: bounds.y_min.to_pixels(),
+            width: (bounds.x_max - bounds.x_min).to_pixels(),
+            height: (bounds.y_max - bounds.y_min).to_pixels(),
+            has_focus,
+        }
+    }
+}
 
 #[derive(Clone, Copy, Collect, Debug)]
 #[collect(no_drop)]
@@ -56,4 +104,93 @@ impl<'gc> FocusTracker<'gc> {
             ],
         );
     }
+
+    /// Every object that Tab moves the focus between, in the order it visits
+    /// them.
+    ///
+    /// Like Flash Player's automatic tab order, this is the reading order of
+    /// the objects on the stage: from top to bottom, then from left to right.
+    pub fn tab_order(&self, context: &UpdateContext<'_, 'gc, '_>) -> Vec<DisplayObject<'gc>> {
+        let mut tab_order = Vec::new();
+        for level in context.levels.values() {
+            collect_tab_stops(*level, &mut tab_order);
+        }
+
+        tab_order.sort_by_key(|object| {
+            let bounds = object.world_bounds();
+            (bounds.y_min, bounds.x_min)
+        });
+        tab_order
+    }
+
+    /// Describe the tab order for accessibility and debugging tools.
+    pub fn tab_stops(&self, context: &UpdateContext<'_, 'gc, '_>) -> Vec<TabStop> {
+        let focused = self.get();
+        self.tab_order(context)
+            .into_iter()
+            .map(|object| {
+                let has_focus = focused.map(|f| f.as_ptr() == object.as_ptr()) == Some(true);
+                TabStop::from_object(object, has_focus)
+            })
+            .collect()
+    }
+
+    /// Move the focus to the next object in the tab order, or the previous
+    /// one if `reverse` is set.
+    ///
+    /// If nothing in the tab order has focus yet, the focus moves to its first
+    /// object, or its last one when going in reverse.
+    pub fn cycle(&self, context: &mut UpdateContext<'_, 'gc, '_>, reverse: bool) {
+        let tab_order = self.tab_order(context);
+        if tab_order.is_empty() {
+            return;
+        }
+
+        let len = tab_order.len();
+        let current = self.get().and_then(|focused| {
+            tab_order
+                .iter()
+                .position(|object| object.as_ptr() == focused.as_ptr())
+        });
+        let next = match (current, reverse) {
+            (Some(i), false) => (i + 1) % len,
+            (Some(i), true) => (i + len - 1) % len,
+            (None, false) => 0,
+            (None, true) => len - 1,
+        };
+
+        self.set(Some(tab_order[next]), context);
+    }
+}
+
+/// Add `object` and its descendants to `tab_order` if the focus can be moved
+/// to them with Tab.
+///
+/// Hidden objects are skipped along with their children, and so are buttons
+/// and clips that are disabled. Text fields are only visited if they can be
+/// typed into.
+fn collect_tab_stops<'gc>(object: DisplayObject<'gc>, tab_order: &mut Vec<DisplayObject<'gc>>) {
+    if !object.visible() {
+        return;
+    }
+
+    let is_tab_stop = if let Some(button) = object.as_button() {
+        button.enabled()
+    } else if let Some(text) = object.as_edit_text() {
+        text.is_editable()
+    } else if let Some(clip) = object.as_movie_clip() {
+        clip.is_focusable() && clip.enabled()
+    } else {
+        false
+    };
+
+    if is_tab_stop {
+        tab_order.push(object);
+    }
+
+    if let Some(container) = object.as_container() {
+        for child in container.iter_render_list() {
+            collect_tab_stops(child, tab_order);
+        }
+    }
 }
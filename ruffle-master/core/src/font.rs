@@ -1,5 +1,6 @@
 use crate::backend::render::{RenderBackend, ShapeHandle};
-use crate::html::TextSpan;
+use crate::config::TextHinting;
+use crate::html::{shape_text, TextSpan};
 use crate::prelude::*;
 use crate::transform::Transform;
 use gc_arena::{Collect, Gc, MutationContext};
@@ -11,6 +12,27 @@ pub fn round_down_to_pixel(t: Twips) -> Twips {
     Twips::from_pixels(t.to_pixels().floor())
 }
 
+/// Move the transform a glyph is drawn with onto the pixel grid, in as many
+/// directions as `hinting` asks for.
+///
+/// The transform should already include the view matrix, so that the glyph
+/// lands on a whole pixel of the screen.
+pub fn hint_glyph(transform: &Transform, hinting: TextHinting) -> Transform {
+    let snap = |t: Twips| Twips::from_pixels(t.to_pixels().round());
+    let mut transform = transform.clone();
+
+    match hinting {
+        TextHinting::None => {}
+        TextHinting::Vertical => transform.matrix.ty = snap(transform.matrix.ty),
+        TextHinting::Full => {
+            transform.matrix.tx = snap(transform.matrix.tx);
+            transform.matrix.ty = snap(transform.matrix.ty);
+        }
+    }
+
+    transform
+}
+
 type Error = Box<dyn std::error::Error>;
 
 /// Parameters necessary to evaluate a font.
@@ -30,6 +52,17 @@ pub struct EvalParameters {
     /// pairs of letters, separate from the ordinary width between glyphs. This
     /// parameter allows enabling or disabling that feature.
     kerning: bool,
+
+    /// Whether the text is right-to-left.
+    ///
+    /// Right-to-left text is drawn in reverse, so that its first character is
+    /// the rightmost one.
+    right_to_left: bool,
+
+    /// Whether glyph advances may be fractions of a pixel.
+    ///
+    /// When this is off, every advance is rounded to a whole pixel.
+    subpixel_positioning: bool,
 }
 
 impl EvalParameters {
@@ -40,6 +73,8 @@ impl EvalParameters {
             height,
             letter_spacing,
             kerning,
+            right_to_left: false,
+            subpixel_positioning: true,
         }
     }
 
@@ -50,13 +85,32 @@ impl EvalParameters {
             height: Twips::from_pixels(span.size),
             letter_spacing: Twips::from_pixels(span.letter_spacing),
             kerning: span.kerning,
+            right_to_left: false,
+            subpixel_positioning: true,
         }
     }
 
+    /// Change the direction the text is drawn in.
+    pub fn with_right_to_left(mut self, right_to_left: bool) -> Self {
+        self.right_to_left = right_to_left;
+        self
+    }
+
+    /// Change whether glyph advances may be fractions of a pixel.
+    pub fn with_subpixel_positioning(mut self, subpixel_positioning: bool) -> Self {
+        self.subpixel_positioning = subpixel_positioning;
+        self
+    }
+
     /// Get the height the font would be evaluated at.
     pub fn height(&self) -> Twips {
         self.height
     }
+
+    /// Whether the text is drawn right-to-left.
+    pub fn is_right_to_left(&self) -> bool {
+        self.right_to_left
+    }
 }
 
 #[derive(Debug, Clone, Collect, Copy)]
@@ -112,6 +166,7 @@ impl<'gc> Font<'gc> {
                 shape_handle: renderer.register_glyph_shape(swf_glyph),
                 advance: swf_glyph.advance.unwrap_or(0),
                 shape: crate::shape_utils::swf_glyph_to_shape(swf_glyph),
+                code: swf_glyph.code,
             };
             let index = glyphs.len();
             glyphs.push(glyph);
@@ -221,6 +276,10 @@ impl<'gc> Font<'gc> {
     /// of transforms and glyphs which will be consumed by the `glyph_func`
     /// closure. This corresponds to the series of drawing operations necessary
     /// to render the text on a single horizontal line.
+    ///
+    /// The text is shaped before it is drawn, so glyphs are produced in the
+    /// order they appear from left to right, which is not necessarily the
+    /// order of the text for right-to-left scripts.
     pub fn evaluate<FGlyph>(
         &self,
         text: &str,
@@ -235,7 +294,10 @@ impl<'gc> Font<'gc> {
 
         transform.matrix.a = scale;
         transform.matrix.d = scale;
-        let mut char_indices = text.char_indices().peekable();
+        let shaped = shape_text(text, params.right_to_left, |c| {
+            self.get_glyph_for_char(c).is_some()
+        });
+        let mut char_indices = shaped.into_iter().peekable();
         let has_kerning_info = self.has_kerning_info();
         let mut x = Twips::zero();
         while let Some((pos, c)) = char_indices.next() {
@@ -245,8 +307,11 @@ impl<'gc> Font<'gc> {
                     let next_char = char_indices.peek().cloned().unwrap_or((0, '\0')).1;
                     advance += self.get_kerning_offset(c, next_char);
                 }
-                let twips_advance =
+                let mut twips_advance =
                     Twips::new((advance.get() as f32 * scale) as i32) + params.letter_spacing;
+                if !params.subpixel_positioning {
+                    twips_advance = Twips::from_pixels(twips_advance.to_pixels().round());
+                }
 
                 glyph_func(pos, &transform, &glyph, twips_advance, x);
 
@@ -382,6 +447,9 @@ pub struct Glyph {
     pub shape_handle: ShapeHandle,
     pub shape: swf::Shape,
     pub advance: i16,
+
+    /// The code point that this glyph draws.
+    pub code: u16,
 }
 
 /// Structure which identifies a particular font by name and properties.
@@ -507,6 +575,26 @@ mod tests {
         })
     }
 
+    #[test]
+    fn advances_without_subpixel_positioning() {
+        with_device_font(|_mc, df| {
+            let params =
+                EvalParameters::from_parts(Twips::from_pixels(13.0), Twips::from_pixels(0.0), true)
+                    .with_subpixel_positioning(false);
+
+            df.evaluate(
+                "The quick brown fox",
+                Default::default(),
+                params,
+                |_pos, transform, _glyph, advance, x| {
+                    assert_eq!(advance.get() % Twips::TWIPS_PER_PIXEL as i32, 0);
+                    assert_eq!(x.get() % Twips::TWIPS_PER_PIXEL as i32, 0);
+                    assert_eq!(transform.matrix.tx, x);
+                },
+            );
+        });
+    }
+
     #[test]
     fn wrap_line_no_breakpoint() {
         with_device_font(|_mc, df| {
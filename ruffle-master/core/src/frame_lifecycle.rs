@@ -0,0 +1,137 @@
+//! The phases of a frame.
+//!
+//! Flash Player runs every frame as a fixed sequence of phases, and many AS3
+//! game loops depend on the exact order in which their events arrive:
+//!
+//! 1. `enterFrame` is broadcast.
+//! 2. The timelines advance: new children are constructed, and the rest of
+//!    the frame's tags (and any AVM1 code) run.
+//! 3. `frameConstructed` is broadcast.
+//! 4. Frame scripts run.
+//! 5. `exitFrame` is broadcast.
+//! 6. If `stage.invalidate()` was called, `render` is broadcast, just before
+//!    the frame is drawn.
+//!
+//! Ruffle holds a frame's `exitFrame` back until the next frame starts, and
+//! broadcasts it right before that frame's `enterFrame`. Scripts still see
+//! the phases in the same order, and a player that stops after a frame has
+//! not yet left it. The only exception is a frame that ends with `render`,
+//! which has to come after `exitFrame`.
+
+use crate::avm2::{Avm2, Event as Avm2Event};
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+
+/// A phase of a frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramePhase {
+    /// `enterFrame` is broadcast.
+    Enter,
+
+    /// Timelines advance to their next frame.
+    Construct,
+
+    /// `frameConstructed` is broadcast.
+    FrameConstructed,
+
+    /// Frame scripts run.
+    FrameScripts,
+
+    /// `exitFrame` is broadcast.
+    Exit,
+
+    /// `render` is broadcast, if the stage was invalidated.
+    Render,
+}
+
+impl FramePhase {
+    /// Every phase, in the order that they run.
+    pub const ALL: [FramePhase; 6] = [
+        FramePhase::Enter,
+        FramePhase::Construct,
+        FramePhase::FrameConstructed,
+        FramePhase::FrameScripts,
+        FramePhase::Exit,
+        FramePhase::Render,
+    ];
+}
+
+/// Run a frame on every level, one phase at a time.
+///
+/// Broadcast events reach every listener no matter which level it belongs
+/// to, so they are only sent once per phase.
+pub fn run_frame_phases<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    levels: &[DisplayObject<'gc>],
+) {
+    // The previous frame's `exitFrame`, if it was held back.
+    if context.avm2.take_exit_frame_pending() {
+        run_phase(context, levels, FramePhase::Exit);
+    }
+
+    for phase in FramePhase::ALL.iter() {
+        if *phase == FramePhase::Exit && !context.avm2.is_stage_invalidated() {
+            context.avm2.defer_exit_frame();
+        } else {
+            run_phase(context, levels, *phase);
+        }
+    }
+}
+
+fn run_phase<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    levels: &[DisplayObject<'gc>],
+    phase: FramePhase,
+) {
+    match phase {
+        FramePhase::Enter => {
+            if let Some(level) = levels.first() {
+                level.enter_frame(context);
+            }
+        }
+        FramePhase::Construct => {
+            for level in levels {
+                level.construct_frame(context);
+            }
+            for level in levels {
+                level.run_frame(context);
+            }
+        }
+        FramePhase::FrameConstructed => {
+            if let Some(level) = levels.first() {
+                level.frame_constructed(context);
+            }
+        }
+        FramePhase::FrameScripts => {
+            for level in levels {
+                level.run_frame_scripts(context);
+            }
+        }
+        FramePhase::Exit => {
+            if let Some(level) = levels.first() {
+                level.exit_frame(context);
+            }
+        }
+        FramePhase::Render => {
+            if context.avm2.take_stage_invalidated() {
+                broadcast_render(context);
+            }
+        }
+    }
+}
+
+/// Broadcast a `render` event to every display object listening for one.
+fn broadcast_render(context: &mut UpdateContext<'_, '_, '_>) {
+    let mut render_evt = Avm2Event::new("render");
+    render_evt.set_bubbles(false);
+    render_evt.set_cancelable(false);
+
+    let dobject_proto = context.avm2.prototypes().display_object;
+
+    if let Err(e) = Avm2::broadcast_event(context, render_evt, dobject_proto) {
+        log::error!(
+            "Encountered AVM2 error when broadcasting render event: {}",
+            e
+        );
+    }
+}
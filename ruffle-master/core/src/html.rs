@@ -3,12 +3,16 @@
 mod dimensions;
 mod iterators;
 mod layout;
+mod shaping;
+mod style_sheet;
 mod text_format;
 
 pub use dimensions::BoxBounds;
 pub use dimensions::Position;
 pub use dimensions::Size;
 pub use layout::{LayoutBox, LayoutContent};
+pub use shaping::{shape_text, TextDirection};
+pub use style_sheet::{Style, StyleSheet};
 pub use text_format::{FormatSpans, TextFormat, TextSpan};
 
 #[cfg(test)]
@@ -5,6 +5,7 @@ use crate::context::UpdateContext;
 use crate::drawing::Drawing;
 use crate::font::{EvalParameters, Font};
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::shaping::TextDirection;
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use crate::shape_utils::DrawCommand;
 use crate::string_utils;
@@ -13,6 +14,7 @@ use gc_arena::{Collect, GcCell, MutationContext};
 use std::cmp::{max, min};
 use std::sync::Arc;
 use swf::Twips;
+use unicode_bidi::{BidiInfo, Level};
 
 /// Draw an underline on a particular drawing.
 ///
@@ -52,6 +54,10 @@ pub struct LayoutContext<'a, 'gc> {
     /// The underlying bundle of text being formatted.
     text: &'a str,
 
+    /// The embedding level of every byte of the text, and the direction of
+    /// each of its paragraphs.
+    bidi: BidiInfo<'a>,
+
     /// The highest font size observed within the current line.
     max_font_size: Twips,
 
@@ -88,15 +94,25 @@ pub struct LayoutContext<'a, 'gc> {
 
     /// The total width of the text field being laid out.
     max_bounds: Twips,
+
+    /// Whether glyph advances may be fractions of a pixel.
+    subpixel_positioning: bool,
 }
 
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
-    fn new(movie: Arc<SwfMovie>, max_bounds: Twips, text: &'a str) -> Self {
+    fn new(
+        movie: Arc<SwfMovie>,
+        max_bounds: Twips,
+        text: &'a str,
+        direction: TextDirection,
+        subpixel_positioning: bool,
+    ) -> Self {
         Self {
             movie,
             cursor: Default::default(),
             font: None,
             text,
+            bidi: BidiInfo::new(text, direction.paragraph_level()),
             max_font_size: Default::default(),
             boxes: Vec::new(),
             exterior_bounds: None,
@@ -105,9 +121,15 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             current_line: 0,
             current_line_span: Default::default(),
             max_bounds,
+            subpixel_positioning,
         }
     }
 
+    /// The parameters to evaluate fonts with for text in `span`.
+    fn eval_params(&self, span: &TextSpan) -> EvalParameters {
+        EvalParameters::from_span(span).with_subpixel_positioning(self.subpixel_positioning)
+    }
+
     /// Calculate the font-provided leading present on this line.
     fn font_leading_adjustment(&self) -> Twips {
         // Flash appears to round up the font's leading to the nearest pixel
@@ -227,6 +249,8 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
             return;
         }
 
+        self.reorder_line();
+
         let mut line_bounds = None;
         let mut box_count: i32 = 0;
         for linebox in self.boxes.get_mut(self.current_line..).unwrap() {
@@ -320,6 +344,61 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         }
     }
 
+    /// Rearrange the boxes on the current line from the order of the text to
+    /// the order they are displayed in.
+    ///
+    /// This is rule L2 of the Unicode Bidirectional Algorithm, applied to
+    /// whole boxes: every run of boxes at or above each odd embedding level is
+    /// reversed, starting from the highest level. Any space left before a box,
+    /// such as by a tab, moves along with it.
+    fn reorder_line(&mut self) {
+        let line = self.boxes.split_off(self.current_line);
+        let max_level = line.iter().map(|b| b.bidi_level()).max().unwrap_or(0);
+        if max_level == 0 {
+            self.boxes.extend(line);
+            return;
+        }
+        let min_odd_level = line.iter().map(|b| b.bidi_level()).min().unwrap_or(0) | 1;
+
+        let line_start = line
+            .first()
+            .map(|b| b.bounds.offset_x())
+            .unwrap_or_else(Twips::zero);
+        let mut previous_end = line_start;
+        let mut line: Vec<(LayoutBox<'gc>, Twips)> = line
+            .into_iter()
+            .map(|linebox| {
+                let gap = linebox.bounds.offset_x() - previous_end;
+                previous_end = linebox.bounds.extent_x();
+                (linebox, gap)
+            })
+            .collect();
+
+        for level in (min_odd_level..=max_level).rev() {
+            let mut i = 0;
+            while i < line.len() {
+                if line[i].0.bidi_level() >= level {
+                    let run_end = line[i..]
+                        .iter()
+                        .position(|(b, _)| b.bidi_level() < level)
+                        .map_or(line.len(), |end| i + end);
+                    line[i..run_end].reverse();
+                    i = run_end;
+                } else {
+                    i += 1;
+                }
+            }
+        }
+
+        let mut x = line_start;
+        for (mut linebox, gap) in line {
+            x += gap;
+            linebox.bounds += Position::from((x - linebox.bounds.offset_x(), Twips::zero()));
+            x += linebox.bounds.width();
+            self.boxes.push(linebox);
+        }
+    }
+
     /// Adjust the text layout cursor down to the next line in response to an
     /// explicit newline.
     ///
@@ -440,11 +519,43 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
     ///
     /// This function bypasses the text fragmentation necessary for justify to
     /// work and it should only be called internally.
+    ///
+    /// Text that changes direction is split into a box for each run of
+    /// characters at the same embedding level, so that the boxes can be
+    /// reordered when the line is finished.
     fn append_text_fragment(&mut self, text: &'a str, start: usize, end: usize, span: &TextSpan) {
-        let params = EvalParameters::from_span(span);
+        let mut run_start = start;
+        while run_start < end {
+            let level = self.bidi.levels[run_start];
+            let run_end = (run_start..end)
+                .find(|i| self.bidi.levels[*i] != level)
+                .unwrap_or(end);
+
+            self.append_text_run(
+                &text[run_start - start..run_end - start],
+                run_start,
+                run_end,
+                level,
+                span,
+            );
+            run_start = run_end;
+        }
+    }
+
+    /// Append a run of text at a single embedding level to the current line.
+    fn append_text_run(
+        &mut self,
+        text: &'a str,
+        start: usize,
+        end: usize,
+        level: Level,
+        span: &TextSpan,
+    ) {
+        let params = self.eval_params(span).with_right_to_left(level.is_rtl());
         let text_size = Size::from(self.font.unwrap().measure(text, params, false));
         let text_bounds = BoxBounds::from_position_and_size(self.cursor, text_size);
-        let mut new_text = LayoutBox::from_text(start, end, self.font.unwrap(), span);
+        let mut new_text =
+            LayoutBox::from_text(start, end, level, self.font.unwrap(), params, span);
 
         new_text.bounds = text_bounds;
 
@@ -470,10 +581,10 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
 
             bullet_cursor.set_x(Twips::from_pixels(18.0));
 
-            let params = EvalParameters::from_span(span);
+            let params = self.eval_params(span);
             let text_size = Size::from(bullet_font.measure("\u{2022}", params, false));
             let text_bounds = BoxBounds::from_position_and_size(bullet_cursor, text_size);
-            let mut new_bullet = LayoutBox::from_bullet(bullet_font, span);
+            let mut new_bullet = LayoutBox::from_bullet(bullet_font, params, span);
 
             new_bullet.bounds = text_bounds;
 
@@ -571,6 +682,10 @@ pub enum LayoutContent<'gc> {
         /// The end position of the text to render.
         end: usize,
 
+        /// The embedding level of the text, which is odd for right-to-left
+        /// text.
+        bidi_level: u8,
+
         /// The formatting options for the text box.
         text_format: TextFormat,
 
@@ -614,14 +729,20 @@ pub enum LayoutContent<'gc> {
 
 impl<'gc> LayoutBox<'gc> {
     /// Construct a text box for a text node.
-    pub fn from_text(start: usize, end: usize, font: Font<'gc>, span: &TextSpan) -> Self {
-        let params = EvalParameters::from_span(span);
-
+    pub fn from_text(
+        start: usize,
+        end: usize,
+        bidi_level: Level,
+        font: Font<'gc>,
+        params: EvalParameters,
+        span: &TextSpan,
+    ) -> Self {
         Self {
             bounds: Default::default(),
             content: LayoutContent::Text {
                 start,
                 end,
+                bidi_level: bidi_level.number(),
                 text_format: span.get_text_format(),
                 font,
                 params,
@@ -631,9 +752,7 @@ impl<'gc> LayoutBox<'gc> {
     }
 
     /// Construct a bullet.
-    pub fn from_bullet(font: Font<'gc>, span: &TextSpan) -> Self {
-        let params = EvalParameters::from_span(span);
-
+    pub fn from_bullet(font: Font<'gc>, params: EvalParameters, span: &TextSpan) -> Self {
         Self {
             bounds: Default::default(),
             content: LayoutContent::Bullet {
@@ -664,14 +783,22 @@ impl<'gc> LayoutBox<'gc> {
         bounds: Twips,
         is_word_wrap: bool,
         is_device_font: bool,
+        direction: TextDirection,
     ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
-        let mut layout_context = LayoutContext::new(movie, bounds, fs.displayed_text());
+        let text_rendering = context.text_rendering.at_quality(*context.quality);
+        let mut layout_context = LayoutContext::new(
+            movie,
+            bounds,
+            fs.displayed_text(),
+            direction,
+            text_rendering.subpixel_positioning,
+        );
 
         for (span_start, _end, span_text, span) in fs.iter_spans() {
             if let Some(font) = layout_context.resolve_font(context, &span, is_device_font) {
                 layout_context.newspan(span);
 
-                let params = EvalParameters::from_span(span);
+                let params = layout_context.eval_params(span);
 
                 for text in span_text.split(&['\n', '\r', '\t'][..]) {
                     let slice_start = text.as_ptr() as usize - span_text.as_ptr() as usize;
@@ -785,6 +912,7 @@ impl<'gc> LayoutBox<'gc> {
                 font,
                 params,
                 color,
+                ..
             } => Some((
                 text.get(*start..*end)?,
                 &text_format,
@@ -819,6 +947,16 @@ impl<'gc> LayoutBox<'gc> {
         }
     }
 
+    /// The embedding level of the text in this box, which is zero for
+    /// anything that isn't text.
+    pub fn bidi_level(&self) -> u8 {
+        match &self.content {
+            LayoutContent::Text { bidi_level, .. } => *bidi_level,
+            LayoutContent::Bullet { .. } => 0,
+            LayoutContent::Drawing(..) => 0,
+        }
+    }
+
     pub fn is_bullet(&self) -> bool {
         match &self.content {
             LayoutContent::Text { .. } => false,
@@ -0,0 +1,262 @@
+//! Text shaping for bidirectional and cursive scripts
+//!
+//! This covers what can be done from the characters alone, without the
+//! font's OpenType tables:
+//!
+//! * Paragraphs are split into runs by the Unicode bidirectional algorithm,
+//!   so Hebrew and Arabic read right-to-left, with mirrored brackets.
+//! * Arabic letters take the joining form for their neighbours, and lam-alef
+//!   pairs become a ligature, by mapping them to the Arabic Presentation
+//!   Forms-B characters that SWF fonts carry glyphs for.
+//!
+//! There is no GSUB or GPOS shaping. Indic scripts, which need their font's
+//! reordering and conjunct substitutions, are drawn unshaped, as are marks
+//! that the font would position and any ligatures other than lam-alef.
+
+use gc_arena::Collect;
+use unicode_bidi::Level;
+
+/// The base direction of the paragraphs in a text field.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum TextDirection {
+    /// Each paragraph takes its direction from its first strong character,
+    /// and is left-to-right if it has none.
+    Auto,
+
+    /// Every paragraph is left-to-right.
+    LeftToRight,
+
+    /// Every paragraph is right-to-left.
+    RightToLeft,
+}
+
+impl Default for TextDirection {
+    fn default() -> Self {
+        TextDirection::Auto
+    }
+}
+
+impl TextDirection {
+    /// The embedding level to start paragraphs at, or `None` if it should be
+    /// detected from the text.
+    pub fn paragraph_level(self) -> Option<Level> {
+        match self {
+            TextDirection::Auto => None,
+            TextDirection::LeftToRight => Some(Level::ltr()),
+            TextDirection::RightToLeft => Some(Level::rtl()),
+        }
+    }
+}
+
+/// How an Arabic character joins with its neighbours.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Joining {
+    /// Never joins, such as spaces, Latin letters and hamza.
+    Never,
+
+    /// Only joins with the character before it, such as alef.
+    Right,
+
+    /// Joins with the characters on both sides, such as beh.
+    Dual,
+
+    /// Joins with both sides without changing shape itself, such as tatweel.
+    Causing,
+
+    /// Skipped over when joining, such as the harakat.
+    Transparent,
+}
+
+/// The joining behaviour of a character, and the first of its presentation
+/// forms in the Arabic Presentation Forms-B block.
+///
+/// The forms of a character are stored in the order isolated, final, initial
+/// and medial; right-joining characters only have the first two.
+fn arabic_joining(c: char) -> (Joining, Option<u32>) {
+    use Joining::{Causing, Dual, Never, Right, Transparent};
+
+    // The presentation forms of U+0621 to U+064A, which are laid out in the
+    // same order as the letters themselves.
+    const LETTERS: [(Joining, u32); 42] = [
+        (Never, 0xFE80), // HAMZA
+        (Right, 0xFE81), // ALEF WITH MADDA ABOVE
+        (Right, 0xFE83), // ALEF WITH HAMZA ABOVE
+        (Right, 0xFE85), // WAW WITH HAMZA ABOVE
+        (Right, 0xFE87), // ALEF WITH HAMZA BELOW
+        (Dual, 0xFE89),  // YEH WITH HAMZA ABOVE
+        (Right, 0xFE8D), // ALEF
+        (Dual, 0xFE8F),  // BEH
+        (Right, 0xFE93), // TEH MARBUTA
+        (Dual, 0xFE95),  // TEH
+        (Dual, 0xFE99),  // THEH
+        (Dual, 0xFE9D),  // JEEM
+        (Dual, 0xFEA1),  // HAH
+        (Dual, 0xFEA5),  // KHAH
+        (Right, 0xFEA9), // DAL
+        (Right, 0xFEAB), // THAL
+        (Right, 0xFEAD), // REH
+        (Right, 0xFEAF), // ZAIN
+        (Dual, 0xFEB1),  // SEEN
+        (Dual, 0xFEB5),  // SHEEN
+        (Dual, 0xFEB9),  // SAD
+        (Dual, 0xFEBD),  // DAD
+        (Dual, 0xFEC1),  // TAH
+        (Dual, 0xFEC5),  // ZAH
+        (Dual, 0xFEC9),  // AIN
+        (Dual, 0xFECD),  // GHAIN
+        (Dual, 0),       // KEHEH WITH TWO DOTS ABOVE
+        (Dual, 0),       // KEHEH WITH THREE DOTS BELOW
+        (Dual, 0),       // FARSI YEH WITH INVERTED V
+        (Dual, 0),       // FARSI YEH WITH TWO DOTS ABOVE
+        (Dual, 0),       // FARSI YEH WITH THREE DOTS ABOVE
+        (Causing, 0),    // TATWEEL
+        (Dual, 0xFED1),  // FEH
+        (Dual, 0xFED5),  // QAF
+        (Dual, 0xFED9),  // KAF
+        (Dual, 0xFEDD),  // LAM
+        (Dual, 0xFEE1),  // MEEM
+        (Dual, 0xFEE5),  // NOON
+        (Dual, 0xFEE9),  // HEH
+        (Right, 0xFEED), // WAW
+        (Right, 0xFEEF), // ALEF MAKSURA
+        (Dual, 0xFEF1),  // YEH
+    ];
+
+    match c {
+        '\u{0621}'..='\u{064A}' => {
+            let (joining, forms) = LETTERS[c as usize - 0x0621];
+            (joining, Some(forms).filter(|forms| *forms != 0))
+        }
+        '\u{064B}'..='\u{065F}' | '\u{0670}' => (Transparent, None),
+        '\u{200D}' => (Causing, None),
+        _ => (Never, None),
+    }
+}
+
+/// The lam-alef ligature that replaces a lam followed by `alef`, if any.
+fn lam_alef_ligature(alef: char) -> Option<u32> {
+    match alef {
+        '\u{0622}' => Some(0xFEF5),
+        '\u{0623}' => Some(0xFEF7),
+        '\u{0625}' => Some(0xFEF9),
+        '\u{0627}' => Some(0xFEFB),
+        _ => None,
+    }
+}
+
+/// The character that is drawn in place of `c` in right-to-left text.
+fn mirrored(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '<' => '>',
+        '>' => '<',
+        '[' => ']',
+        ']' => '[',
+        '{' => '}',
+        '}' => '{',
+        '\u{00AB}' => '\u{00BB}',
+        '\u{00BB}' => '\u{00AB}',
+        '\u{2039}' => '\u{203A}',
+        '\u{203A}' => '\u{2039}',
+        _ => c,
+    }
+}
+
+/// Shape a run of text that has a single direction, producing the characters
+/// to draw in the order they are drawn, along with the byte position in
+/// `text` that each of them came from.
+///
+/// Arabic letters are replaced by the presentation form for the way they join
+/// with their neighbours, and lam-alef pairs by their ligature. Right-to-left
+/// runs are reversed, and their brackets mirrored. Substitutions are only made
+/// when `has_glyph` says that the font can draw the replacement, since SWF
+/// fonts only contain the glyphs that the movie needed.
+///
+/// Scripts that need the font's own substitution tables, such as the Indic
+/// scripts, are passed through unchanged.
+pub fn shape_text(
+    text: &str,
+    is_rtl: bool,
+    has_glyph: impl Fn(char) -> bool,
+) -> Vec<(usize, char)> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let joining: Vec<Joining> = chars.iter().map(|(_, c)| arabic_joining(*c).0).collect();
+
+    // The joining behaviour of the closest character on each side that isn't
+    // transparent.
+    let joining_before = |i: usize| {
+        joining[..i]
+            .iter()
+            .rev()
+            .find(|j| **j != Joining::Transparent)
+            .copied()
+            .unwrap_or(Joining::Never)
+    };
+    let joining_after = |i: usize| {
+        joining[i + 1..]
+            .iter()
+            .find(|j| **j != Joining::Transparent)
+            .copied()
+            .unwrap_or(Joining::Never)
+    };
+    let form = |forms: u32, offset: u32| {
+        std::char::from_u32(forms + offset).filter(|form| has_glyph(*form))
+    };
+
+    let mut shaped = Vec::with_capacity(chars.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let (pos, c) = chars[i];
+        let (joins, forms) = arabic_joining(c);
+        let joins_before = matches!(joins, Joining::Right | Joining::Dual | Joining::Causing)
+            && matches!(joining_before(i), Joining::Dual | Joining::Causing);
+        let joins_after = matches!(joins, Joining::Dual | Joining::Causing)
+            && matches!(
+                joining_after(i),
+                Joining::Right | Joining::Dual | Joining::Causing
+            );
+
+        let ligature = if c == '\u{0644}' {
+            chars
+                .get(i + 1)
+                .and_then(|(_, alef)| lam_alef_ligature(*alef))
+                .and_then(|ligature| form(ligature, joins_before as u32))
+        } else {
+            None
+        };
+
+        if let Some(ligature) = ligature {
+            shaped.push((pos, ligature));
+            i += 2;
+            continue;
+        }
+
+        let c = match forms {
+            Some(forms) => {
+                let offset = match (joins_before, joins_after) {
+                    (false, false) => 0,
+                    (true, false) => 1,
+                    (false, true) => 2,
+                    (true, true) => 3,
+                };
+                form(forms, offset)
+                    // A font without the medial or initial form of a letter
+                    // can still draw it unjoined on the following side.
+                    .or_else(|| form(forms, joins_before as u32))
+                    .unwrap_or(c)
+            }
+            None if is_rtl => Some(mirrored(c)).filter(|m| has_glyph(*m)).unwrap_or(c),
+            None => c,
+        };
+        shaped.push((pos, c));
+        i += 1;
+    }
+
+    if is_rtl {
+        shaped.reverse();
+    }
+
+    shaped
+}
@@ -0,0 +1,259 @@
+//! CSS style sheets for HTML text
+
+use crate::html::text_format::TextFormat;
+use crate::xml::{XmlName, XmlNode};
+use gc_arena::Collect;
+use std::collections::BTreeMap;
+
+/// The properties of a single CSS style, keyed by their ActionScript names
+/// (e.g. `fontFamily` for `font-family`).
+pub type Style = BTreeMap<String, String>;
+
+/// A set of CSS styles that can be applied to HTML text.
+///
+/// Styles are looked up by selector: a tag name such as `p`, or a class name
+/// such as `.heading`. Selectors are case-insensitive.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct StyleSheet {
+    styles: BTreeMap<String, Style>,
+}
+
+impl StyleSheet {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Parse CSS text and add the styles it declares to this style sheet.
+    ///
+    /// Declarations for a selector that already has a style are merged into
+    /// it. Returns `false` if the text could not be parsed, in which case
+    /// no styles are added.
+    pub fn parse_css(&mut self, css: &str) -> bool {
+        let css = strip_comments(css);
+        let mut parsed: Vec<(String, Style)> = Vec::new();
+
+        let mut rest = css.trim();
+        while !rest.is_empty() {
+            let open = match rest.find('{') {
+                Some(open) => open,
+                None => return false,
+            };
+            let close = match rest[open..].find('}') {
+                Some(close) => open + close,
+                None => return false,
+            };
+
+            let mut style = Style::new();
+            for declaration in rest[open + 1..close].split(';') {
+                if declaration.trim().is_empty() {
+                    continue;
+                }
+
+                let mut parts = declaration.splitn(2, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(name), Some(value)) if !name.trim().is_empty() => {
+                        style.insert(
+                            property_name(name.trim()),
+                            value
+                                .trim()
+                                .trim_matches(|c: char| c == '"' || c == '\'')
+                                .to_string(),
+                        );
+                    }
+                    _ => return false,
+                }
+            }
+
+            for selector in rest[..open].split(',') {
+                let selector = selector.trim();
+                if selector.is_empty() {
+                    return false;
+                }
+                parsed.push((selector.to_ascii_lowercase(), style.clone()));
+            }
+
+            rest = rest[close + 1..].trim_start();
+        }
+
+        for (selector, style) in parsed {
+            self.styles.entry(selector).or_default().extend(style);
+        }
+
+        true
+    }
+
+    /// Get the style for a selector.
+    pub fn style(&self, selector: &str) -> Option<&Style> {
+        self.styles.get(&selector.to_ascii_lowercase())
+    }
+
+    /// Replace the style for a selector, or remove it if `style` is `None`.
+    pub fn set_style(&mut self, selector: &str, style: Option<Style>) {
+        let selector = selector.to_ascii_lowercase();
+        match style {
+            Some(style) => {
+                self.styles.insert(selector, style);
+            }
+            None => {
+                self.styles.remove(&selector);
+            }
+        }
+    }
+
+    /// The selectors of every style in this style sheet.
+    pub fn style_names(&self) -> impl Iterator<Item = &str> {
+        self.styles.keys().map(|name| name.as_str())
+    }
+
+    /// Remove all styles from this style sheet.
+    pub fn clear(&mut self) {
+        self.styles.clear();
+    }
+
+    /// Convert a style to the text format it describes.
+    pub fn transform(style: &Style) -> TextFormat {
+        apply_style(style, Default::default())
+    }
+
+    /// Apply the styles that match an HTML element to a text format.
+    ///
+    /// The style for the element's tag is applied first, followed by the
+    /// style for its `class` attribute, so that class styles take precedence.
+    pub fn apply_to_node(&self, node: XmlNode<'_>, mut tf: TextFormat) -> TextFormat {
+        if let Some(name) = node.tag_name() {
+            if let Some(style) = self.style(&name.node_name()) {
+                tf = apply_style(style, tf);
+            }
+        }
+
+        if let Some(class) = node.attribute_value_ignore_ascii_case(&XmlName::from_str("class")) {
+            for class in class.split_whitespace() {
+                if let Some(style) = self.style(&format!(".{}", class)) {
+                    tf = apply_style(style, tf);
+                }
+            }
+        }
+
+        tf
+    }
+}
+
+/// Remove `/* ... */` comments from CSS text.
+fn strip_comments(css: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        result.push_str(&rest[..start]);
+        rest = match rest[start + 2..].find("*/") {
+            Some(end) => &rest[start + 2 + end + 2..],
+            None => "",
+        };
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Convert a CSS property name to the name ActionScript uses for it, such as
+/// `font-family` to `fontFamily`.
+fn property_name(css_name: &str) -> String {
+    let mut name = String::with_capacity(css_name.len());
+    let mut uppercase_next = false;
+    for c in css_name.chars() {
+        if c == '-' {
+            uppercase_next = true;
+        } else if uppercase_next {
+            name.push(c.to_ascii_uppercase());
+            uppercase_next = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+/// Parse a CSS length, ignoring any `px` or `pt` unit.
+fn parse_length(value: &str) -> Option<f64> {
+    value
+        .trim()
+        .trim_end_matches("px")
+        .trim_end_matches("pt")
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Parse a CSS color of the form `#RRGGBB`.
+fn parse_color(value: &str) -> Option<swf::Color> {
+    let hex = value.trim().strip_prefix('#')?;
+    if hex.len() != 6 {
+        return None;
+    }
+    let rgb = u32::from_str_radix(hex, 16).ok()?;
+    Some(swf::Color::from_rgb(rgb, 255))
+}
+
+/// Apply the properties of a style that Flash Player supports to a text
+/// format. Anything else is ignored.
+fn apply_style(style: &Style, mut tf: TextFormat) -> TextFormat {
+    for (name, value) in style {
+        let value = value.trim();
+        match name.as_str() {
+            "color" => {
+                if let Some(color) = parse_color(value) {
+                    tf.color = Some(color);
+                }
+            }
+            "fontFamily" => {
+                if let Some(family) = value.split(',').next() {
+                    tf.font = Some(
+                        family
+                            .trim()
+                            .trim_matches(|c: char| c == '"' || c == '\'')
+                            .into(),
+                    );
+                }
+            }
+            "fontSize" => {
+                if let Some(size) = parse_length(value) {
+                    tf.size = Some(size);
+                }
+            }
+            "fontWeight" => match value {
+                "bold" => tf.bold = Some(true),
+                "normal" => tf.bold = Some(false),
+                _ => {}
+            },
+            "fontStyle" => match value {
+                "italic" => tf.italic = Some(true),
+                "normal" => tf.italic = Some(false),
+                _ => {}
+            },
+            "textDecoration" => match value {
+                "underline" => tf.underline = Some(true),
+                "none" => tf.underline = Some(false),
+                _ => {}
+            },
+            "textAlign" => match value {
+                "left" => tf.align = Some(swf::TextAlign::Left),
+                "center" => tf.align = Some(swf::TextAlign::Center),
+                "right" => tf.align = Some(swf::TextAlign::Right),
+                "justify" => tf.align = Some(swf::TextAlign::Justify),
+                _ => {}
+            },
+            "marginLeft" => tf.left_margin = parse_length(value).or(tf.left_margin),
+            "marginRight" => tf.right_margin = parse_length(value).or(tf.right_margin),
+            "textIndent" => tf.indent = parse_length(value).or(tf.indent),
+            "leading" => tf.leading = parse_length(value).or(tf.leading),
+            "letterSpacing" => tf.letter_spacing = parse_length(value).or(tf.letter_spacing),
+            "kerning" => match value {
+                "true" => tf.kerning = Some(true),
+                "false" => tf.kerning = Some(false),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+
+    tf
+}
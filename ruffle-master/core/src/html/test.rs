@@ -1,6 +1,8 @@
 //! Tests for HTML module
 
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::shaping::shape_text;
+use crate::html::style_sheet::StyleSheet;
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
 use swf::{Rectangle, Twips};
 
@@ -863,3 +865,66 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+#[test]
+fn stylesheet_parse_css() {
+    let mut ss = StyleSheet::new();
+
+    assert!(ss.parse_css(
+        "/* headings */ h1, .Title { font-size: 24px; font-weight: bold }\np { color: #FF0000; }"
+    ));
+
+    let h1 = ss.style("h1").unwrap();
+    assert_eq!(Some("24px"), h1.get("fontSize").map(|v| v.as_str()));
+    assert_eq!(Some("bold"), h1.get("fontWeight").map(|v| v.as_str()));
+    assert!(ss.style(".title").is_some());
+    assert_eq!(
+        Some("#FF0000"),
+        ss.style("P").unwrap().get("color").map(|v| v.as_str())
+    );
+    assert_eq!(
+        vec![".title", "h1", "p"],
+        ss.style_names().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn stylesheet_parse_css_invalid() {
+    let mut ss = StyleSheet::new();
+
+    assert!(!ss.parse_css("p { color: #FF0000; } h1 { font-size"));
+    assert!(!ss.parse_css("p { color }"));
+    assert_eq!(0, ss.style_names().count());
+}
+
+#[test]
+fn shape_text_arabic_joining() {
+    // Beh, beh, alef: initial, medial and final forms, drawn right-to-left.
+    let shaped = shape_text("\u{0628}\u{0628}\u{0627}", true, |_| true);
+
+    assert_eq!(
+        shaped,
+        vec![(4, '\u{FE8E}'), (2, '\u{FE92}'), (0, '\u{FE91}')]
+    );
+}
+
+#[test]
+fn shape_text_lam_alef() {
+    let shaped = shape_text("\u{0644}\u{0627}", true, |_| true);
+
+    assert_eq!(shaped, vec![(0, '\u{FEFB}')]);
+}
+
+#[test]
+fn shape_text_missing_forms() {
+    let shaped = shape_text("\u{0628}\u{0628}", true, |c| c < '\u{FE70}');
+
+    assert_eq!(shaped, vec![(2, '\u{0628}'), (0, '\u{0628}')]);
+}
+
+#[test]
+fn shape_text_mirrored_brackets() {
+    let shaped = shape_text("(\u{05D0})", true, |_| true);
+
+    assert_eq!(shaped, vec![(3, '('), (1, '\u{05D0}'), (0, ')')]);
+}
@@ -12,6 +12,7 @@ use crate::avm2::{
 };
 use crate::context::UpdateContext;
 use crate::html::iterators::TextSpanIter;
+use crate::html::style_sheet::StyleSheet;
 use crate::tag_utils::SwfMovie;
 use crate::xml::{Step, XmlDocument, XmlName, XmlNode};
 use gc_arena::{Collect, MutationContext};
@@ -1594,6 +1595,17 @@ impl FormatSpans {
     /// styling. There's also a `lower_from_css` that respects both
     /// presentational markup and CSS stylesheets.
     pub fn lower_from_html(&mut self, tree: XmlDocument<'_>) {
+        self.lower(tree, None);
+    }
+
+    /// Lower an HTML tree into text-span representation, styling each
+    /// element with both its presentational markup and the styles of the
+    /// given stylesheet that match it.
+    pub fn lower_from_css(&mut self, tree: XmlDocument<'_>, style_sheet: &StyleSheet) {
+        self.lower(tree, Some(style_sheet));
+    }
+
+    fn lower(&mut self, tree: XmlDocument<'_>, style_sheet: Option<&StyleSheet>) {
         let mut format_stack = vec![self.default_format.clone()];
         let mut last_successful_format = None;
 
@@ -1632,13 +1644,19 @@ impl FormatSpans {
                             .unwrap()
                             .node_name()
                             .eq_ignore_ascii_case("br") => {}
-                Step::In(node) => format_stack.push(TextFormat::from_presentational_markup(
-                    node,
-                    format_stack
-                        .last()
-                        .cloned()
-                        .unwrap_or_else(Default::default),
-                )),
+                Step::In(node) => {
+                    let mut tf = TextFormat::from_presentational_markup(
+                        node,
+                        format_stack
+                            .last()
+                            .cloned()
+                            .unwrap_or_else(Default::default),
+                    );
+                    if let Some(style_sheet) = style_sheet {
+                        tf = style_sheet.apply_to_node(node, tf);
+                    }
+                    format_stack.push(tf);
+                }
                 Step::Around(node) if node.is_text() => {
                     self.replace_text(
                         self.text.len(),
@@ -0,0 +1,139 @@
+//! Text shown by the player itself
+//!
+//! Dialogs that the player shows, as opposed to text that comes from the
+//! movie, get their text from a `MessageCatalog` so that embedders can
+//! translate it. Anything that a catalog doesn't translate is shown in
+//! English.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// A piece of text shown by the player.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Message {
+    /// The title of the warning shown for movies that Ruffle can't run yet.
+    UnsupportedContentTitle,
+
+    /// The warning shown for movies that Ruffle can't run yet.
+    UnsupportedContent,
+
+    /// The title of the dialog that shows an uncaught script error.
+    ScriptErrorTitle,
+
+    /// Names the movie that threw an uncaught script error. `{url}` is
+    /// replaced by the URL of the movie.
+    ScriptErrorMovie,
+
+    /// Asks whether to keep showing uncaught script errors.
+    ScriptErrorContinue,
+}
+
+impl Message {
+    /// The identifier of this message, used as its key in catalogs that are
+    /// loaded from data, such as a `HashMap`.
+    pub fn id(self) -> &'static str {
+        match self {
+            Message::UnsupportedContentTitle => "unsupported-content-title",
+            Message::UnsupportedContent => "unsupported-content",
+            Message::ScriptErrorTitle => "script-error-title",
+            Message::ScriptErrorMovie => "script-error-movie",
+            Message::ScriptErrorContinue => "script-error-continue",
+        }
+    }
+
+    /// The English text of this message.
+    pub fn english(self) -> &'static str {
+        match self {
+            Message::UnsupportedContentTitle => "Ruffle - Unsupported content",
+            // TODO: Move link to https://ruffle.rs/faq or similar
+            Message::UnsupportedContent => {
+                "This content is not yet supported by Ruffle and will likely not run as intended.\n\
+                \n\
+                See the following link for more info:\n\
+                https://github.com/ruffle-rs/ruffle/wiki/Frequently-Asked-Questions-For-Users"
+            }
+            Message::ScriptErrorTitle => "Ruffle - ActionScript error",
+            Message::ScriptErrorMovie => "Movie: {url}",
+            Message::ScriptErrorContinue => "Continue showing script errors?",
+        }
+    }
+}
+
+/// Translations of the text shown by the player, supplied by the embedder.
+pub trait MessageCatalog {
+    /// Translate `message`, or return `None` to show it in English.
+    fn translate(&self, message: Message) -> Option<Cow<'_, str>>;
+
+    /// The text to show for `message`.
+    fn text(&self, message: Message) -> Cow<'_, str> {
+        self.translate(message)
+            .unwrap_or_else(|| Cow::Borrowed(message.english()))
+    }
+
+    /// The text to show for `message`, with each `{name}` placeholder
+    /// replaced by its value in `args`.
+    fn format(&self, message: Message, args: &[(&str, &str)]) -> String {
+        let mut text = self.text(message).into_owned();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        text
+    }
+}
+
+/// The catalog used when the embedder doesn't supply one, which shows
+/// everything in English.
+#[derive(Default)]
+pub struct EnglishMessageCatalog;
+
+impl MessageCatalog for EnglishMessageCatalog {
+    fn translate(&self, _message: Message) -> Option<Cow<'_, str>> {
+        None
+    }
+}
+
+/// Translations keyed by `Message::id`.
+impl MessageCatalog for HashMap<String, String> {
+    fn translate(&self, message: Message) -> Option<Cow<'_, str>> {
+        self.get(message.id())
+            .map(|text| Cow::Borrowed(text.as_str()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fall_back_to_english() {
+        let mut catalog = HashMap::new();
+        catalog.insert(
+            "script-error-title".to_string(),
+            "Ruffle - Erreur ActionScript".to_string(),
+        );
+        catalog.insert(
+            "script-error-movie".to_string(),
+            "Animation : {url}".to_string(),
+        );
+
+        assert_eq!(
+            catalog.text(Message::ScriptErrorTitle),
+            "Ruffle - Erreur ActionScript"
+        );
+        assert_eq!(
+            catalog.text(Message::ScriptErrorContinue),
+            "Continue showing script errors?"
+        );
+        assert_eq!(
+            catalog.format(
+                Message::ScriptErrorMovie,
+                &[("url", "http://example.com/a.swf")]
+            ),
+            "Animation : http://example.com/a.swf"
+        );
+        assert_eq!(
+            EnglishMessageCatalog.format(Message::ScriptErrorMovie, &[("url", "a.swf")]),
+            "Movie: a.swf"
+        );
+    }
+}
@@ -18,6 +18,7 @@ extern crate smallvec;
 #[macro_use]
 extern crate downcast_rs;
 
+pub mod amf;
 #[macro_use]
 mod avm1;
 mod avm2;
@@ -26,21 +27,34 @@ mod bounding_box;
 mod character;
 mod collect;
 pub mod color_transform;
+pub mod commands;
 pub mod context;
+pub mod debugger;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
+pub mod executor;
+pub mod flv;
 pub mod focus_tracker;
 mod font;
+mod frame_lifecycle;
 mod html;
+pub mod i18n;
 mod library;
 pub mod loader;
+pub mod local_connection;
+pub mod net;
 mod player;
 mod prelude;
+pub mod print_job;
 pub mod property_map;
+pub mod security;
 pub mod shape_utils;
+pub mod socket;
+pub mod streams;
 pub mod string_utils;
 pub mod tag_utils;
+pub mod telemetry;
 mod transform;
 mod types;
 mod vminterface;
@@ -1,4 +1,5 @@
 use crate::backend::audio::SoundHandle;
+use crate::backend::render::BitmapHandle;
 use crate::character::Character;
 use crate::display_object::{Bitmap, TDisplayObject};
 use crate::font::{Font, FontDescriptor};
@@ -55,12 +56,109 @@ impl<'gc> Avm1ConstructorRegistry<'gc> {
     }
 }
 
+/// A problem found while binding the classes named in a `SymbolClass` tag to
+/// the characters of a movie.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SymbolClassProblem {
+    /// The class name isn't a valid ActionScript name.
+    InvalidClassName { id: CharacterId, class_name: String },
+
+    /// No class with this name was defined by the movie's ABC code.
+    MissingClass { id: CharacterId, class_name: String },
+
+    /// The movie runs on AVM1, so its characters can't be bound to classes.
+    AvmMismatch {
+        id: CharacterId,
+        class_name: String,
+        avm_type: AvmType,
+    },
+
+    /// The character was already bound to another class. The later binding
+    /// replaces it.
+    DuplicateBinding {
+        id: CharacterId,
+        class_name: String,
+        bound_class_name: String,
+    },
+
+    /// The character doesn't exist, or is a kind of character that can't be
+    /// bound to a class.
+    InvalidCharacter { id: CharacterId, class_name: String },
+}
+
+impl SymbolClassProblem {
+    /// The character the binding was for. Character `0` is the main timeline,
+    /// so its class is the movie's main class.
+    pub fn id(&self) -> CharacterId {
+        match self {
+            Self::InvalidClassName { id, .. }
+            | Self::MissingClass { id, .. }
+            | Self::AvmMismatch { id, .. }
+            | Self::DuplicateBinding { id, .. }
+            | Self::InvalidCharacter { id, .. } => *id,
+        }
+    }
+
+    /// Whether this problem stops the movie's main class from being bound,
+    /// which leaves the stage blank.
+    pub fn is_main_class(&self) -> bool {
+        self.id() == 0
+    }
+}
+
+impl std::fmt::Display for SymbolClassProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_main_class() {
+            write!(f, "Main class ")?;
+        } else {
+            write!(f, "Symbol class ")?;
+        }
+
+        match self {
+            Self::InvalidClassName { class_name, .. } => {
+                write!(f, "{} is not a valid class name", class_name)
+            }
+            Self::MissingClass { class_name, .. } => write!(
+                f,
+                "{} could not be bound: the class is not defined in any ABC code",
+                class_name
+            ),
+            Self::AvmMismatch {
+                class_name,
+                avm_type,
+                ..
+            } => write!(
+                f,
+                "{} could not be bound: the movie is an {:?} movie",
+                class_name, avm_type
+            ),
+            Self::DuplicateBinding {
+                id,
+                class_name,
+                bound_class_name,
+            } => write!(
+                f,
+                "{} replaces {} as the class of character {}",
+                class_name, bound_class_name, id
+            ),
+            Self::InvalidCharacter { id, class_name } => write!(
+                f,
+                "{} could not be bound: character {} does not exist or cannot have a class",
+                class_name, id
+            ),
+        }
+    }
+}
+
 /// Symbol library for a single given SWF.
 #[derive(Collect)]
 #[collect(no_drop)]
 pub struct MovieLibrary<'gc> {
     characters: HashMap<CharacterId, Character<'gc>>,
     export_characters: PropertyMap<Character<'gc>>,
+    /// Human-readable names for characters, taken from their export or class
+    /// names. These are only used to identify symbols in log messages.
+    debug_names: HashMap<CharacterId, String>,
     jpeg_tables: Option<Vec<u8>>,
     fonts: HashMap<FontDescriptor, Font<'gc>>,
     avm_type: AvmType,
@@ -68,6 +166,11 @@ pub struct MovieLibrary<'gc> {
     /// Shared reference to the constructor registry used for this movie.
     /// Should be `None` if this is an AVM2 movie.
     avm1_constructor_registry: Option<Gc<'gc, Avm1ConstructorRegistry<'gc>>>,
+    /// The class name bound to each character by `SymbolClass` tags.
+    symbol_classes: HashMap<CharacterId, String>,
+    /// Every problem found while binding symbol classes, in tag order.
+    #[collect(require_static)]
+    symbol_class_problems: Vec<SymbolClassProblem>,
 }
 
 impl<'gc> MovieLibrary<'gc> {
@@ -75,11 +178,14 @@ impl<'gc> MovieLibrary<'gc> {
         MovieLibrary {
             characters: HashMap::new(),
             export_characters: PropertyMap::new(),
+            debug_names: HashMap::new(),
             jpeg_tables: None,
             fonts: HashMap::new(),
             avm_type,
             avm2_domain: None,
             avm1_constructor_registry: None,
+            symbol_classes: HashMap::new(),
+            symbol_class_problems: Vec::new(),
         }
     }
 
@@ -106,6 +212,9 @@ impl<'gc> MovieLibrary<'gc> {
         if let Some(character) = self.characters.get(&id) {
             self.export_characters
                 .insert(export_name, character.clone(), false);
+            self.debug_names
+                .entry(id)
+                .or_insert_with(|| export_name.to_string());
             Some(character)
         } else {
             log::warn!(
@@ -117,6 +226,19 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Registers a class name for a given character ID, for use in log
+    /// messages.
+    ///
+    /// Class names take precedence over export names.
+    pub fn register_debug_name(&mut self, id: CharacterId, name: &str) {
+        self.debug_names.insert(id, name.to_string());
+    }
+
+    /// The export or class name of a character, if it has one.
+    pub fn debug_name(&self, id: CharacterId) -> Option<&str> {
+        self.debug_names.get(&id).map(String::as_str)
+    }
+
     pub fn contains_character(&self, id: CharacterId) -> bool {
         self.characters.contains_key(&id)
     }
@@ -194,6 +316,26 @@ impl<'gc> MovieLibrary<'gc> {
         }
     }
 
+    /// Find the ID of the bitmap character that displays the given bitmap.
+    pub fn bitmap_id_by_handle(&self, handle: BitmapHandle) -> Option<CharacterId> {
+        self.characters
+            .iter()
+            .find_map(|(id, character)| match character {
+                Character::Bitmap(bitmap) if bitmap.bitmap_handle() == Some(handle) => Some(*id),
+                _ => None,
+            })
+    }
+
+    /// Find a character ID that is not used by this library.
+    ///
+    /// IDs are handed out from the top of the range down, so that they do not
+    /// clash with the characters defined by the movie itself.
+    pub fn unused_character_id(&self) -> Option<CharacterId> {
+        (0..=CharacterId::MAX)
+            .rev()
+            .find(|id| !self.characters.contains_key(id))
+    }
+
     pub fn get_font(&self, id: CharacterId) -> Option<Font<'gc>> {
         if let Some(&Character::Font(font)) = self.characters.get(&id) {
             Some(font)
@@ -272,6 +414,25 @@ impl<'gc> MovieLibrary<'gc> {
         self.avm_type = new_type;
     }
 
+    /// Record that a character is bound to a class.
+    pub fn bind_symbol_class(&mut self, id: CharacterId, class_name: &str) {
+        self.symbol_classes.insert(id, class_name.to_string());
+    }
+
+    /// The name of the class a character is bound to, if any.
+    pub fn symbol_class(&self, id: CharacterId) -> Option<&str> {
+        self.symbol_classes.get(&id).map(|name| name.as_str())
+    }
+
+    pub fn add_symbol_class_problem(&mut self, problem: SymbolClassProblem) {
+        self.symbol_class_problems.push(problem);
+    }
+
+    /// The problems found while binding this movie's symbol classes.
+    pub fn symbol_class_problems(&self) -> &[SymbolClassProblem] {
+        &self.symbol_class_problems
+    }
+
     pub fn set_avm2_domain(&mut self, avm2_domain: Avm2Domain<'gc>) {
         self.avm2_domain = Some(avm2_domain);
     }
@@ -4,8 +4,10 @@ use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::{Avm1, AvmString, Object, TObject, Value};
 use crate::avm2::Domain as Avm2Domain;
 use crate::backend::navigator::OwnedFuture;
+use crate::config::LoaderLimits;
 use crate::context::{ActionQueue, ActionType};
 use crate::display_object::{DisplayObject, MorphShape, TDisplayObject};
+use crate::net::{self, Progress};
 use crate::player::{Player, NEWEST_PLAYER_VERSION};
 use crate::property_map::PropertyMap;
 use crate::tag_utils::SwfMovie;
@@ -56,6 +58,15 @@ pub enum Error {
     #[error("Network unavailable.")]
     NetworkUnavailable,
 
+    #[error("Loading from {0} is not allowed by the security sandbox")]
+    SecurityViolation(String),
+
+    #[error("Refused to load {0}: {1}")]
+    LimitExceeded(String, String),
+
+    #[error("Refused to load {0}: the loader does not allow content with code")]
+    CodeImportNotAllowed(String),
+
     // TODO: We can't support lifetimes on this error object yet (or we'll need some backends inside
     // the GC arena). We're losing info here. How do we fix that?
     #[error("Error running avm1 script: {0}")]
@@ -74,11 +85,16 @@ impl From<crate::avm1::error::Error<'_>> for Error {
 }
 
 /// Holds all in-progress loads for the player.
-pub struct LoadManager<'gc>(Arena<Loader<'gc>>);
+pub struct LoadManager<'gc> {
+    loaders: Arena<Loader<'gc>>,
+
+    /// Limits on movie loads, set by the embedder.
+    limits: LoaderLimits,
+}
 
 unsafe impl<'gc> Collect for LoadManager<'gc> {
     fn trace(&self, cc: CollectionContext) {
-        for (_, loader) in self.0.iter() {
+        for (_, loader) in self.loaders.iter() {
             loader.trace(cc)
         }
     }
@@ -87,7 +103,86 @@ unsafe impl<'gc> Collect for LoadManager<'gc> {
 impl<'gc> LoadManager<'gc> {
     /// Construct a new `LoadManager`.
     pub fn new() -> Self {
-        Self(Arena::new())
+        Self {
+            loaders: Arena::new(),
+            limits: LoaderLimits::default(),
+        }
+    }
+
+    /// The limits applied to movie loads.
+    pub fn limits(&self) -> LoaderLimits {
+        self.limits
+    }
+
+    /// Change the limits applied to movie loads.
+    ///
+    /// Loads that are already in progress are not affected.
+    pub fn set_limits(&mut self, limits: LoaderLimits) {
+        self.limits = limits;
+    }
+
+    /// Check whether loading the movie at `url` into `target_clip` would
+    /// exceed the loader limits.
+    ///
+    /// `handle` is the loader making the request, which is not counted
+    /// against the concurrent load limit.
+    fn check_movie_limits(
+        &self,
+        handle: Handle,
+        target_clip: DisplayObject<'gc>,
+        url: &str,
+    ) -> Result<(), String> {
+        let pending_loads = self
+            .loaders
+            .iter()
+            .filter(|(other, loader)| {
+                *other != handle
+                    && matches!(
+                        loader,
+                        Loader::Movie {
+                            loader_status: LoaderStatus::Pending,
+                            ..
+                        }
+                    )
+            })
+            .count();
+        if pending_loads >= self.limits.max_concurrent_loads {
+            return Err(format!(
+                "{} movie loads are already in progress",
+                pending_loads
+            ));
+        }
+
+        // Walk up through the movies that contain the target clip. The target's
+        // own movie is about to be replaced, so it doesn't count.
+        let mut nesting_depth = 0;
+        let mut last_movie: Option<Arc<SwfMovie>> = None;
+        let mut ancestor = target_clip.parent();
+        while let Some(clip) = ancestor {
+            if let Some(movie) = clip.movie() {
+                let is_new_movie = last_movie
+                    .as_ref()
+                    .map(|last| !Arc::ptr_eq(last, &movie))
+                    .unwrap_or(true);
+                if is_new_movie {
+                    if self.limits.detect_cycles && movie.url() == Some(url) {
+                        return Err("the target clip is already inside this movie".to_string());
+                    }
+                    nesting_depth += 1;
+                    last_movie = Some(movie);
+                }
+            }
+            ancestor = clip.parent();
+        }
+
+        if nesting_depth >= self.limits.max_nesting_depth {
+            return Err(format!(
+                "loaded movies are already nested {} deep",
+                nesting_depth
+            ));
+        }
+
+        Ok(())
     }
 
     /// Add a new loader to the `LoadManager`.
@@ -97,8 +192,8 @@ impl<'gc> LoadManager<'gc> {
     /// finishes, the handle will be invalidated (and the underlying loader
     /// deleted).
     pub fn add_loader(&mut self, loader: Loader<'gc>) -> Handle {
-        let handle = self.0.insert(loader);
-        self.0
+        let handle = self.loaders.insert(loader);
+        self.loaders
             .get_mut(handle)
             .unwrap()
             .introduce_loader_handle(handle);
@@ -108,12 +203,12 @@ impl<'gc> LoadManager<'gc> {
 
     /// Retrieve a loader by handle.
     pub fn get_loader(&self, handle: Handle) -> Option<&Loader<'gc>> {
-        self.0.get(handle)
+        self.loaders.get(handle)
     }
 
     /// Retrieve a loader by handle for mutation.
     pub fn get_loader_mut(&mut self, handle: Handle) -> Option<&mut Loader<'gc>> {
-        self.0.get_mut(handle)
+        self.loaders.get_mut(handle)
     }
 
     /// Kick off the root movie load.
@@ -148,12 +243,39 @@ impl<'gc> LoadManager<'gc> {
         fetch: OwnedFuture<Vec<u8>, Error>,
         url: String,
         target_broadcaster: Option<Object<'gc>>,
+    ) -> OwnedFuture<(), Error> {
+        self.load_movie_into_clip_with_options(
+            player,
+            target_clip,
+            fetch,
+            url,
+            target_broadcaster,
+            MovieLoadOptions::default(),
+        )
+    }
+
+    /// Kick off a movie clip load, with the options of an AS3
+    /// `LoaderContext`.
+    ///
+    /// If the options ask for a policy file to be checked, `fetch` should
+    /// have been made with `net::fetch_data`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_movie_into_clip_with_options(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_clip: DisplayObject<'gc>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
+        target_broadcaster: Option<Object<'gc>>,
+        options: MovieLoadOptions<'gc>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Movie {
             self_handle: None,
             target_clip,
             target_broadcaster,
             loader_status: LoaderStatus::Pending,
+            options,
         };
         let handle = self.add_loader(loader);
 
@@ -174,14 +296,14 @@ impl<'gc> LoadManager<'gc> {
     ) {
         let mut invalidated_loaders = vec![];
 
-        for (index, loader) in self.0.iter_mut() {
+        for (index, loader) in self.loaders.iter_mut() {
             if loader.movie_clip_loaded(loaded_clip, clip_object, queue) {
                 invalidated_loaders.push(index);
             }
         }
 
         for index in invalidated_loaders {
-            self.0.remove(index);
+            self.loaders.remove(index);
         }
     }
 
@@ -269,6 +391,32 @@ pub enum LoaderStatus {
     Failed,
 }
 
+/// How a movie is loaded into a clip, as set by an AS3 `LoaderContext`.
+#[derive(Clone, Collect)]
+#[collect(no_drop)]
+pub struct MovieLoadOptions<'gc> {
+    /// The application domain that the movie's classes are defined in.
+    ///
+    /// If `None`, the movie gets a new domain whose parent is the global
+    /// domain, so its classes don't clash with those of other movies.
+    pub avm2_domain: Option<Avm2Domain<'gc>>,
+
+    /// Whether content that contains code may be loaded.
+    ///
+    /// Every movie contains code as far as this is concerned, so when this
+    /// is `false`, loading a movie fails.
+    pub allow_code_import: bool,
+}
+
+impl<'gc> Default for MovieLoadOptions<'gc> {
+    fn default() -> Self {
+        Self {
+            avm2_domain: None,
+            allow_code_import: true,
+        }
+    }
+}
+
 /// A struct that holds garbage-collected pointers for asynchronous code.
 #[derive(Collect)]
 #[collect(no_drop)]
@@ -302,6 +450,9 @@ pub enum Loader<'gc> {
         /// or an error has occurred (in which case we don't care about the
         /// loader anymore).
         loader_status: LoaderStatus,
+
+        /// Where the movie's classes go, and what content may be loaded.
+        options: MovieLoadOptions<'gc>,
     },
 
     /// Loader that is loading form data into an AVM1 object scope.
@@ -378,14 +529,11 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            player
-                .lock()
-                .expect("Could not lock player!!")
-                .update(|uc| -> Result<(), Error> {
-                    url = uc.navigator.resolve_relative_url(&url).into_owned();
+            net::deliver(&player, |uc| -> Result<(), Error> {
+                url = uc.navigator.resolve_relative_url(&url).into_owned();
 
-                    Ok(())
-                })?;
+                Ok(())
+            })?;
 
             let data = (fetch.await)
                 .and_then(|data| Ok((data.len(), SwfMovie::from_data(&data, Some(url.clone()))?)));
@@ -398,6 +546,8 @@ impl<'gc> Loader<'gc> {
 
                 Ok(())
             } else {
+                net::deliver(&player, |uc| uc.telemetry.movie_load_failed());
+
                 Err(Error::FetchError(url))
             }
         })
@@ -426,27 +576,23 @@ impl<'gc> Loader<'gc> {
             .expect("Could not upgrade weak reference to player");
 
         Box::pin(async move {
-            player
-                .lock()
-                .expect("Could not lock player!!")
-                .update(|uc| -> Result<(), Error> {
-                    url = uc.navigator.resolve_relative_url(&url).into_owned();
+            let allow_code_import = net::deliver(&player, |uc| -> Result<bool, Error> {
+                url = uc.navigator.resolve_relative_url(&url).into_owned();
 
-                    let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
+                let (clip, broadcaster, allow_code_import) =
+                    match uc.load_manager.get_loader(handle) {
                         Some(Loader::Movie {
                             target_clip,
                             target_broadcaster,
+                            options,
                             ..
-                        }) => (*target_clip, *target_broadcaster),
+                        }) => (*target_clip, *target_broadcaster, options.allow_code_import),
                         None => return Err(Error::Cancelled),
                         _ => unreachable!(),
                     };
 
-                    clip.as_movie_clip().unwrap().unload(uc);
-
-                    clip.as_movie_clip()
-                        .unwrap()
-                        .replace_with_movie(uc.gc_context, None);
+                if let Err(reason) = uc.load_manager.check_movie_limits(handle, clip, &url) {
+                    log::warn!("Refusing to load movie {}: {}", url, reason);
 
                     if let Some(broadcaster) = broadcaster {
                         Avm1::run_stack_frame_for_method(
@@ -455,137 +601,171 @@ impl<'gc> Loader<'gc> {
                             NEWEST_PLAYER_VERSION,
                             uc,
                             "broadcastMessage",
-                            &["onLoadStart".into(), Value::Object(broadcaster)],
+                            &[
+                                "onLoadError".into(),
+                                Value::Object(broadcaster),
+                                "LoadNeverCompleted".into(),
+                            ],
                         );
                     }
 
-                    Ok(())
-                })?;
+                    // Refused loads never reach the target clip, so nothing else
+                    // would clean up the loader.
+                    uc.load_manager.loaders.remove(handle);
+
+                    return Err(Error::LimitExceeded(url.clone(), reason));
+                }
+
+                clip.as_movie_clip().unwrap().unload(uc);
+
+                clip.as_movie_clip()
+                    .unwrap()
+                    .replace_with_movie(uc.gc_context, None);
+
+                if let Some(broadcaster) = broadcaster {
+                    Avm1::run_stack_frame_for_method(
+                        clip,
+                        broadcaster,
+                        NEWEST_PLAYER_VERSION,
+                        uc,
+                        "broadcastMessage",
+                        &["onLoadStart".into(), Value::Object(broadcaster)],
+                    );
+                }
+
+                Ok(allow_code_import)
+            })?;
 
             let data = (fetch.await)
-                .and_then(|data| Ok((data.len(), SwfMovie::from_data(&data, Some(url.clone()))?)));
+                .and_then(|data| Ok((data.len(), SwfMovie::from_data(&data, Some(url.clone()))?)))
+                .and_then(|loaded| {
+                    if allow_code_import {
+                        Ok(loaded)
+                    } else {
+                        let e = Error::CodeImportNotAllowed(url.clone());
+                        log::warn!("{}", e);
+                        Err(e)
+                    }
+                });
             if let Ok((length, movie)) = data {
                 let movie = Arc::new(movie);
 
-                player
-                    .lock()
-                    .expect("Could not lock player!!")
-                    .update(|uc| {
-                        let domain =
-                            Avm2Domain::movie_domain(uc.gc_context, uc.avm2.global_domain());
+                net::deliver(&player, |uc| {
+                    let (clip, broadcaster, domain) = match uc.load_manager.get_loader(handle) {
+                        Some(Loader::Movie {
+                            target_clip,
+                            target_broadcaster,
+                            options,
+                            ..
+                        }) => (*target_clip, *target_broadcaster, options.avm2_domain),
+                        None => return Err(Error::Cancelled),
+                        _ => unreachable!(),
+                    };
+
+                    let domain = domain.unwrap_or_else(|| {
+                        Avm2Domain::movie_domain(uc.gc_context, uc.avm2.global_domain())
+                    });
+                    uc.library
+                        .library_for_movie_mut(movie.clone())
+                        .set_avm2_domain(domain);
+
+                    if let Some(broadcaster) = broadcaster {
+                        let progress = Progress::complete(length);
+                        Avm1::run_stack_frame_for_method(
+                            clip,
+                            broadcaster,
+                            NEWEST_PLAYER_VERSION,
+                            uc,
+                            "broadcastMessage",
+                            &[
+                                "onLoadProgress".into(),
+                                Value::Object(broadcaster),
+                                progress.bytes_loaded.into(),
+                                progress.bytes_total.into(),
+                            ],
+                        );
+                    }
+
+                    let mut mc = clip
+                        .as_movie_clip()
+                        .expect("Attempted to load movie into not movie clip");
+
+                    mc.replace_with_movie(uc.gc_context, Some(movie.clone()));
+                    mc.post_instantiation(uc, clip, None, Instantiator::Movie, false);
+
+                    let mut morph_shapes = fnv::FnvHashMap::default();
+                    mc.preload(uc, &mut morph_shapes);
+
+                    // Finalize morph shapes.
+                    for (id, static_data) in morph_shapes {
+                        let morph_shape = MorphShape::new(uc.gc_context, static_data);
                         uc.library
                             .library_for_movie_mut(movie.clone())
-                            .set_avm2_domain(domain);
-
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Movie {
-                                target_clip,
-                                target_broadcaster,
-                                ..
-                            }) => (*target_clip, *target_broadcaster),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
-
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &[
-                                    "onLoadProgress".into(),
-                                    Value::Object(broadcaster),
-                                    length.into(),
-                                    length.into(),
-                                ],
+                            .register_character(
+                                id,
+                                crate::character::Character::MorphShape(morph_shape),
                             );
-                        }
-
-                        let mut mc = clip
-                            .as_movie_clip()
-                            .expect("Attempted to load movie into not movie clip");
-
-                        mc.replace_with_movie(uc.gc_context, Some(movie.clone()));
-                        mc.post_instantiation(uc, clip, None, Instantiator::Movie, false);
-
-                        let mut morph_shapes = fnv::FnvHashMap::default();
-                        mc.preload(uc, &mut morph_shapes);
-
-                        // Finalize morph shapes.
-                        for (id, static_data) in morph_shapes {
-                            let morph_shape = MorphShape::new(uc.gc_context, static_data);
-                            uc.library
-                                .library_for_movie_mut(movie.clone())
-                                .register_character(
-                                    id,
-                                    crate::character::Character::MorphShape(morph_shape),
-                                );
-                        }
+                    }
 
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &["onLoadComplete".into(), Value::Object(broadcaster)],
-                            );
-                        }
+                    if let Some(broadcaster) = broadcaster {
+                        Avm1::run_stack_frame_for_method(
+                            clip,
+                            broadcaster,
+                            NEWEST_PLAYER_VERSION,
+                            uc,
+                            "broadcastMessage",
+                            &["onLoadComplete".into(), Value::Object(broadcaster)],
+                        );
+                    }
 
-                        if let Some(Loader::Movie { loader_status, .. }) =
-                            uc.load_manager.get_loader_mut(handle)
-                        {
-                            *loader_status = LoaderStatus::Succeeded;
-                        };
+                    if let Some(Loader::Movie { loader_status, .. }) =
+                        uc.load_manager.get_loader_mut(handle)
+                    {
+                        *loader_status = LoaderStatus::Succeeded;
+                    };
 
-                        Ok(())
-                    })
+                    Ok(())
+                })
             } else {
                 //TODO: Inspect the fetch error.
                 //This requires cooperation from the backend to send abstract
                 //error types we can actually inspect.
                 //This also can get errors from decoding an invalid SWF file,
                 //too. We should distinguish those to player code.
-                player
-                    .lock()
-                    .expect("Could not lock player!!")
-                    .update(|uc| -> Result<(), Error> {
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Movie {
-                                target_clip,
-                                target_broadcaster,
-                                ..
-                            }) => (*target_clip, *target_broadcaster),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
-
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &[
-                                    "onLoadError".into(),
-                                    Value::Object(broadcaster),
-                                    "LoadNeverCompleted".into(),
-                                ],
-                            );
-                        }
+                net::deliver(&player, |uc| -> Result<(), Error> {
+                    let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
+                        Some(Loader::Movie {
+                            target_clip,
+                            target_broadcaster,
+                            ..
+                        }) => (*target_clip, *target_broadcaster),
+                        None => return Err(Error::Cancelled),
+                        _ => unreachable!(),
+                    };
 
-                        if let Some(Loader::Movie { loader_status, .. }) =
-                            uc.load_manager.get_loader_mut(handle)
-                        {
-                            *loader_status = LoaderStatus::Failed;
-                        };
+                    if let Some(broadcaster) = broadcaster {
+                        Avm1::run_stack_frame_for_method(
+                            clip,
+                            broadcaster,
+                            NEWEST_PLAYER_VERSION,
+                            uc,
+                            "broadcastMessage",
+                            &[
+                                "onLoadError".into(),
+                                Value::Object(broadcaster),
+                                "LoadNeverCompleted".into(),
+                            ],
+                        );
+                    }
+
+                    if let Some(Loader::Movie { loader_status, .. }) =
+                        uc.load_manager.get_loader_mut(handle)
+                    {
+                        *loader_status = LoaderStatus::Failed;
+                    };
 
-                        Ok(())
-                    })
+                    Ok(())
+                })
             }
         })
     }
@@ -608,7 +788,7 @@ impl<'gc> Loader<'gc> {
             let data = fetch.await?;
 
             // Fire the load handler.
-            player.lock().unwrap().update(|uc| {
+            net::deliver(&player, |uc| {
                 let loader = uc.load_manager.get_loader(handle);
                 let that = match loader {
                     Some(&Loader::Form { target_object, .. }) => target_object,
@@ -655,7 +835,7 @@ impl<'gc> Loader<'gc> {
             let data = fetch.await;
 
             // Fire the load handler.
-            player.lock().unwrap().update(|uc| {
+            net::deliver(&player, |uc| {
                 let loader = uc.load_manager.get_loader(handle);
                 let that = match loader {
                     Some(&Loader::LoadVars { target_object, .. }) => target_object,
@@ -670,6 +850,14 @@ impl<'gc> Loader<'gc> {
 
                 match data {
                     Ok(data) => {
+                        let progress = Progress::complete(data.len());
+                        that.set(
+                            "_bytesLoaded",
+                            progress.bytes_loaded.into(),
+                            &mut activation,
+                        )?;
+                        that.set("_bytesTotal", progress.bytes_total.into(), &mut activation)?;
+
                         // Fire the onData method with the loaded string.
                         let string_data =
                             AvmString::new(activation.context.gc_context, UTF_8.decode(&data).0);
@@ -758,78 +946,74 @@ impl<'gc> Loader<'gc> {
             if let Ok(data) = data {
                 let xmlstring = String::from_utf8(data)?;
 
-                player.lock().expect("Could not lock player!!").update(
-                    |uc| -> Result<(), Error> {
-                        let (mut node, active_clip) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Xml {
-                                target_node,
-                                active_clip,
-                                ..
-                            }) => (*target_node, *active_clip),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
-
-                        let object =
-                            node.script_object(uc.gc_context, Some(uc.avm1.prototypes().xml_node));
-                        Avm1::run_stack_frame_for_method(
+                net::deliver(&player, |uc| -> Result<(), Error> {
+                    let (mut node, active_clip) = match uc.load_manager.get_loader(handle) {
+                        Some(Loader::Xml {
+                            target_node,
                             active_clip,
-                            object,
-                            NEWEST_PLAYER_VERSION,
-                            uc,
-                            "onHTTPStatus",
-                            &[200.into()],
-                        );
+                            ..
+                        }) => (*target_node, *active_clip),
+                        None => return Err(Error::Cancelled),
+                        _ => unreachable!(),
+                    };
 
-                        Avm1::run_stack_frame_for_method(
-                            active_clip,
-                            object,
-                            NEWEST_PLAYER_VERSION,
-                            uc,
-                            "onData",
-                            &[AvmString::new(uc.gc_context, xmlstring).into()],
-                        );
+                    let object =
+                        node.script_object(uc.gc_context, Some(uc.avm1.prototypes().xml_node));
+                    Avm1::run_stack_frame_for_method(
+                        active_clip,
+                        object,
+                        NEWEST_PLAYER_VERSION,
+                        uc,
+                        "onHTTPStatus",
+                        &[200.into()],
+                    );
 
-                        Ok(())
-                    },
-                )?;
-            } else {
-                player.lock().expect("Could not lock player!!").update(
-                    |uc| -> Result<(), Error> {
-                        let (mut node, active_clip) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Xml {
-                                target_node,
-                                active_clip,
-                                ..
-                            }) => (*target_node, *active_clip),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
-
-                        let object =
-                            node.script_object(uc.gc_context, Some(uc.avm1.prototypes().xml_node));
+                    Avm1::run_stack_frame_for_method(
+                        active_clip,
+                        object,
+                        NEWEST_PLAYER_VERSION,
+                        uc,
+                        "onData",
+                        &[AvmString::new(uc.gc_context, xmlstring).into()],
+                    );
 
-                        Avm1::run_stack_frame_for_method(
+                    Ok(())
+                })?;
+            } else {
+                net::deliver(&player, |uc| -> Result<(), Error> {
+                    let (mut node, active_clip) = match uc.load_manager.get_loader(handle) {
+                        Some(Loader::Xml {
+                            target_node,
                             active_clip,
-                            object,
-                            NEWEST_PLAYER_VERSION,
-                            uc,
-                            "onHTTPStatus",
-                            &[404.into()],
-                        );
+                            ..
+                        }) => (*target_node, *active_clip),
+                        None => return Err(Error::Cancelled),
+                        _ => unreachable!(),
+                    };
 
-                        Avm1::run_stack_frame_for_method(
-                            active_clip,
-                            object,
-                            NEWEST_PLAYER_VERSION,
-                            uc,
-                            "onData",
-                            &[],
-                        );
+                    let object =
+                        node.script_object(uc.gc_context, Some(uc.avm1.prototypes().xml_node));
 
-                        Ok(())
-                    },
-                )?;
+                    Avm1::run_stack_frame_for_method(
+                        active_clip,
+                        object,
+                        NEWEST_PLAYER_VERSION,
+                        uc,
+                        "onHTTPStatus",
+                        &[404.into()],
+                    );
+
+                    Avm1::run_stack_frame_for_method(
+                        active_clip,
+                        object,
+                        NEWEST_PLAYER_VERSION,
+                        uc,
+                        "onData",
+                        &[],
+                    );
+
+                    Ok(())
+                })?;
             }
 
             Ok(())
@@ -0,0 +1,382 @@
+//! Messages between movies for `LocalConnection`.
+//!
+//! A `LocalConnection` listens on a name, and other connections call methods
+//! on it by sending messages to that name. The `LocalConnectionBackend` carries
+//! the messages, which may be bound for other players or even other
+//! processes; the `LocalConnectionManager` remembers which script object
+//! listens on each name, and once per frame delivers the messages that
+//! arrived, and tells senders whether their messages were received.
+//!
+//! Names are qualified with the domain of the movie that uses them, so that
+//! unrelated sites can't talk to each other by accident, unless they start
+//! with an underscore. The arguments of a call travel as AMF0.
+
+use crate::amf::{read_amf0_values, write_amf0_values, ScriptValue};
+use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::property::Attribute;
+use crate::avm1::{
+    root_error_handler, AvmString, Object as Avm1Object, ScriptObject, TObject, Value as Avm1Value,
+};
+use crate::avm2::{Avm2, Object as Avm2Object};
+use crate::backend::local_connection::LocalConnectionMessage;
+use crate::context::UpdateContext;
+use crate::streams::script_value_to_avm1;
+use crate::tag_utils::SwfMovie;
+use gc_arena::{Collect, CollectionContext};
+use url::Url;
+
+/// The methods of `LocalConnection` itself, which can't be called by other
+/// movies.
+const RESERVED_METHODS: &[&str] = &[
+    "send",
+    "connect",
+    "close",
+    "domain",
+    "allowDomain",
+    "allowInsecureDomain",
+];
+
+/// The script object of a `LocalConnection`.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub enum LocalConnectionObject<'gc> {
+    Avm1(Avm1Object<'gc>),
+    Avm2(Avm2Object<'gc>),
+}
+
+impl<'gc> LocalConnectionObject<'gc> {
+    fn ptr_eq(self, other: Self) -> bool {
+        match (self, other) {
+            (LocalConnectionObject::Avm1(a), LocalConnectionObject::Avm1(b)) => {
+                Avm1Object::ptr_eq(a, b)
+            }
+            (LocalConnectionObject::Avm2(a), LocalConnectionObject::Avm2(b)) => {
+                Avm2Object::ptr_eq(a, b)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A connection that is listening for messages.
+struct Listener<'gc> {
+    object: LocalConnectionObject<'gc>,
+
+    /// The full name that the connection listens on.
+    name: String,
+}
+
+/// All of the player's `LocalConnection`s.
+pub struct LocalConnectionManager<'gc> {
+    listeners: Vec<Listener<'gc>>,
+
+    /// The connections that sent messages since the last frame, and whether
+    /// each message found a listener.
+    statuses: Vec<(LocalConnectionObject<'gc>, bool)>,
+}
+
+unsafe impl<'gc> Collect for LocalConnectionManager<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        for listener in &self.listeners {
+            listener.object.trace(cc);
+        }
+        for (object, _) in &self.statuses {
+            object.trace(cc);
+        }
+    }
+}
+
+impl<'gc> Default for LocalConnectionManager<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'gc> LocalConnectionManager<'gc> {
+    pub fn new() -> Self {
+        Self {
+            listeners: Vec::new(),
+            statuses: Vec::new(),
+        }
+    }
+
+    /// Whether `object` is listening for messages.
+    pub fn is_connected(&self, object: LocalConnectionObject<'gc>) -> bool {
+        self.listeners
+            .iter()
+            .any(|listener| listener.object.ptr_eq(object))
+    }
+
+    /// Start listening for messages sent to `name`.
+    ///
+    /// Returns `false` if the name isn't valid, `object` is already
+    /// listening, or another connection already listens on the name.
+    pub fn connect(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: LocalConnectionObject<'gc>,
+        name: &str,
+    ) -> bool {
+        if name.is_empty()
+            || name.contains(':')
+            || context.local_connection_manager.is_connected(object)
+        {
+            return false;
+        }
+
+        let name = full_name(&domain(context.swf), name);
+        if !context.local_connection.connect(&name) {
+            return false;
+        }
+
+        context
+            .local_connection_manager
+            .listeners
+            .push(Listener { object, name });
+        true
+    }
+
+    /// Stop listening for messages.
+    ///
+    /// Returns `false` if `object` wasn't listening.
+    pub fn close(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: LocalConnectionObject<'gc>,
+    ) -> bool {
+        let listeners = &mut context.local_connection_manager.listeners;
+        match listeners
+            .iter()
+            .position(|listener| listener.object.ptr_eq(object))
+        {
+            Some(index) => {
+                let listener = listeners.remove(index);
+                context.local_connection.close(&listener.name);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Call `method` on the connection listening on `name`.
+    ///
+    /// `name` may be qualified with another domain, as in
+    /// `example.com:connection`. The sender is told whether the message was
+    /// received on the next frame.
+    pub fn send(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: LocalConnectionObject<'gc>,
+        name: &str,
+        method: &str,
+        args: &[ScriptValue],
+    ) {
+        let sender_domain = domain(context.swf);
+        let connection = if name.contains(':') {
+            name.to_string()
+        } else {
+            full_name(&sender_domain, name)
+        };
+
+        let delivered = context.local_connection.send(LocalConnectionMessage {
+            connection,
+            sender_domain,
+            method: method.to_string(),
+            args: write_amf0_values(args),
+        });
+        context
+            .local_connection_manager
+            .statuses
+            .push((object, delivered));
+    }
+
+    /// Deliver the messages that arrived since the last frame, then tell the
+    /// senders of the messages sent since then whether they were received.
+    pub fn tick(context: &mut UpdateContext<'_, 'gc, '_>) {
+        for message in context.local_connection.poll_messages() {
+            let object = match context
+                .local_connection_manager
+                .listeners
+                .iter()
+                .find(|listener| listener.name == message.connection)
+            {
+                Some(listener) => listener.object,
+                None => continue,
+            };
+            if RESERVED_METHODS.contains(&message.method.as_str()) {
+                log::warn!(
+                    "LocalConnection: {} can't be called by another movie",
+                    message.method
+                );
+                continue;
+            }
+            let args = match read_amf0_values(&message.args) {
+                Ok(args) => args,
+                Err(e) => {
+                    log::warn!("LocalConnection: couldn't read message: {}", e);
+                    continue;
+                }
+            };
+
+            match object {
+                LocalConnectionObject::Avm1(object) => {
+                    Self::receive_avm1(context, object, &message, &args)
+                }
+                LocalConnectionObject::Avm2(object) => {
+                    if let Err(e) = Avm2::receive_local_connection_message(
+                        context,
+                        object,
+                        &message.sender_domain,
+                        &message.method,
+                        &args,
+                    ) {
+                        log::error!(
+                            "Encountered AVM2 error when receiving LocalConnection message: {}",
+                            e
+                        );
+                    }
+                }
+            }
+        }
+
+        let statuses = std::mem::take(&mut context.local_connection_manager.statuses);
+        for (object, delivered) in statuses {
+            let level = if delivered { "status" } else { "error" };
+            match object {
+                LocalConnectionObject::Avm1(object) => Self::status_avm1(context, object, level),
+                LocalConnectionObject::Avm2(object) => {
+                    if let Err(e) = Avm2::dispatch_status_event(context, object, None, level) {
+                        log::error!(
+                            "Encountered AVM2 error when dispatching LocalConnection status: {}",
+                            e
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Call the method named by a message on an AVM1 `LocalConnection`.
+    ///
+    /// Messages from other domains are only received if the connection's
+    /// `allowDomain` handler accepts the sender's domain.
+    fn receive_avm1(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Avm1Object<'gc>,
+        message: &LocalConnectionMessage,
+        args: &[ScriptValue],
+    ) {
+        if !context.levels.contains_key(&0) {
+            return;
+        }
+
+        let is_same_domain = message.sender_domain == domain(context.swf);
+        let mut activation = Activation::from_stub(
+            context.reborrow(),
+            ActivationIdentifier::root("[LocalConnection]"),
+        );
+
+        if !is_same_domain {
+            let sender_domain =
+                AvmString::new(activation.context.gc_context, message.sender_domain.clone());
+            let allowed = match object.get("allowDomain", &mut activation) {
+                Ok(Avm1Value::Object(_)) => object
+                    .call_method("allowDomain", &[sender_domain.into()], &mut activation)
+                    .map(|allowed| allowed.as_bool(activation.current_swf_version())),
+                _ => Ok(false),
+            };
+            match allowed {
+                Ok(true) => {}
+                Ok(false) => {
+                    log::warn!(
+                        "LocalConnection: ignored a message from {}",
+                        message.sender_domain
+                    );
+                    return;
+                }
+                Err(e) => {
+                    root_error_handler(&mut activation, e);
+                    return;
+                }
+            }
+        }
+
+        let args: Vec<_> = args
+            .iter()
+            .map(|arg| script_value_to_avm1(&mut activation, arg))
+            .collect();
+        match object.get(&message.method, &mut activation) {
+            Ok(Avm1Value::Object(_)) => {
+                if let Err(e) = object.call_method(&message.method, &args, &mut activation) {
+                    root_error_handler(&mut activation, e);
+                }
+            }
+            _ => log::warn!(
+                "LocalConnection: no method {} to receive a message",
+                message.method
+            ),
+        }
+    }
+
+    /// Call the `onStatus` handler of an AVM1 `LocalConnection` that sent a
+    /// message.
+    fn status_avm1(context: &mut UpdateContext<'_, 'gc, '_>, object: Avm1Object<'gc>, level: &str) {
+        if !context.levels.contains_key(&0) {
+            return;
+        }
+
+        let mut activation = Activation::from_stub(
+            context.reborrow(),
+            ActivationIdentifier::root("[LocalConnection]"),
+        );
+        match object.get("onStatus", &mut activation) {
+            Ok(Avm1Value::Object(_)) => {}
+            _ => return,
+        }
+
+        let gc_context = activation.context.gc_context;
+        let info = ScriptObject::object(
+            gc_context,
+            Some(activation.context.avm1.prototypes().object),
+        );
+        info.define_value(
+            gc_context,
+            "level",
+            AvmString::new(gc_context, level.to_string()).into(),
+            Attribute::empty(),
+        );
+        if let Err(e) = object.call_method("onStatus", &[info.into()], &mut activation) {
+            root_error_handler(&mut activation, e);
+        }
+    }
+}
+
+/// The domain that a movie's connections belong to: the host it was loaded
+/// from, or `localhost` for local files.
+pub fn domain(movie: &SwfMovie) -> String {
+    movie
+        .url()
+        .and_then(|url| Url::parse(url).ok())
+        .and_then(|url| url.host_str().map(str::to_string))
+        .unwrap_or_else(|| "localhost".to_string())
+}
+
+/// Qualify a connection name with a domain, unless it starts with an
+/// underscore.
+fn full_name(domain: &str, name: &str) -> String {
+    if name.starts_with('_') {
+        name.to_string()
+    } else {
+        format!("{}:{}", domain, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_qualified_by_domain() {
+        assert_eq!(full_name("example.com", "game"), "example.com:game");
+        assert_eq!(full_name("example.com", "_game"), "_game");
+
+        assert_eq!(domain(&SwfMovie::empty(8)), "localhost");
+    }
+}
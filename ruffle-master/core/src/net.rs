@@ -0,0 +1,406 @@
+//! Network requests
+//!
+//! Every API that loads data over the network (`loadMovie`, `loadVariables`,
+//! `LoadVars`, `XML`, `MovieClipLoader`, and the root movie) goes through this
+//! module. It builds the request, applies the security sandbox, hands the
+//! request to the `NavigatorBackend`, and delivers the response back into the
+//! player so that any resulting script runs through the action queue.
+//!
+//! Loads that read data from another site, rather than displaying its
+//! content, also need that site's policy file to allow them; these go through
+//! `fetch_data`.
+
+use crate::backend::navigator::{
+    Chunk, ChunkStream, NavigationMethod, OwnedChunkStream, OwnedFuture, RequestOptions,
+};
+use crate::context::UpdateContext;
+use crate::executor::TaskHandle;
+use crate::loader::Error;
+use crate::player::Player;
+use crate::security::{Access, PolicyFile};
+use indexmap::IndexMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use url::{form_urlencoded, Url};
+
+/// The URL schemes that content may be loaded from.
+const ALLOWED_SCHEMES: &[&str] = &["http", "https", "file"];
+
+/// A request to be fetched by the navigator.
+pub struct Request {
+    /// The URL to fetch.
+    url: String,
+
+    /// The method and body of the request.
+    options: RequestOptions,
+}
+
+impl Request {
+    /// Construct a GET request.
+    pub fn get(url: String) -> Self {
+        Self {
+            url,
+            options: RequestOptions::get(),
+        }
+    }
+
+    /// Construct a POST request with an optional body and mime type.
+    pub fn post(url: String, body: Option<(Vec<u8>, String)>) -> Self {
+        Self {
+            url,
+            options: RequestOptions::post(body),
+        }
+    }
+
+    /// Construct a request that sends a set of form values.
+    ///
+    /// GET requests append the values to the query string and POST requests
+    /// send them URL-encoded in the body. If no method is given, the values
+    /// are not sent at all.
+    pub fn with_form_values(
+        url: String,
+        form_values: &IndexMap<String, String>,
+        method: Option<NavigationMethod>,
+    ) -> Self {
+        let method = match method {
+            Some(method) => method,
+            None => return Self::get(url),
+        };
+
+        let query_string = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(form_values.iter())
+            .finish();
+
+        match method {
+            NavigationMethod::Get if url.find('?').is_none() => {
+                Self::get(format!("{}?{}", url, query_string))
+            }
+            NavigationMethod::Get => Self::get(format!("{}&{}", url, query_string)),
+            NavigationMethod::Post => Self::post(
+                url,
+                Some((
+                    query_string.into_bytes(),
+                    "application/x-www-form-urlencoded".to_string(),
+                )),
+            ),
+        }
+    }
+
+    /// The URL this request will be sent to.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// The method and body of this request.
+    pub fn options(&self) -> &RequestOptions {
+        &self.options
+    }
+}
+
+/// How much of a load has completed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of bytes received so far.
+    pub bytes_loaded: usize,
+
+    /// The total size of the response, or zero if it isn't known yet.
+    pub bytes_total: usize,
+}
+
+impl Progress {
+    /// The progress of a load that has received all of its data.
+    pub fn complete(len: usize) -> Self {
+        Self {
+            bytes_loaded: len,
+            bytes_total: len,
+        }
+    }
+
+    /// Whether all of the data has been received.
+    pub fn is_complete(&self) -> bool {
+        self.bytes_total > 0 && self.bytes_loaded >= self.bytes_total
+    }
+}
+
+/// Check that the security sandbox allows loading from `url`.
+///
+/// Relative URLs are always allowed, as they resolve against the movie's own
+/// location. Absolute URLs must use a scheme that the player can load content
+/// from; in particular, `javascript:` URLs may only be used for navigation.
+pub fn check_url(url: &str) -> Result<(), Error> {
+    match Url::parse(url) {
+        Ok(parsed) if !ALLOWED_SCHEMES.contains(&parsed.scheme()) => {
+            Err(Error::SecurityViolation(url.to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Start fetching a request.
+///
+/// The returned future should be handed to a loader, which delivers the
+/// response once it arrives. Requests that the security sandbox denies
+/// produce a future that fails immediately, so the loader's error handling
+/// runs as it would for any other failed load.
+pub fn fetch(context: &UpdateContext<'_, '_, '_>, request: Request) -> OwnedFuture<Vec<u8>, Error> {
+    if let Err(e) = check_load_access(context, &request.url) {
+        return denied(e);
+    }
+
+    context.navigator.fetch(&request.url, request.options)
+}
+
+/// Start fetching a request, like `fetch`, but hand the response over in
+/// chunks as it arrives.
+pub fn fetch_chunked(context: &UpdateContext<'_, '_, '_>, request: Request) -> OwnedChunkStream {
+    let access = match check_load_access(context, &request.url) {
+        Ok(()) => Box::pin(async { Ok(()) }),
+        Err(e) => denied(e),
+    };
+
+    Box::pin(CheckedChunks {
+        access: Some(access),
+        chunks: context
+            .navigator
+            .fetch_chunked(&request.url, request.options),
+    })
+}
+
+/// Check that the security sandbox allows loading content from `url`.
+fn check_load_access(context: &UpdateContext<'_, '_, '_>, url: &str) -> Result<(), Error> {
+    check_url(url)?;
+
+    if context.security.check_load(url) != Access::Allowed {
+        return Err(Error::SecurityViolation(url.to_string()));
+    }
+
+    Ok(())
+}
+
+/// Start fetching data that the movie will read, such as text or variables.
+///
+/// If the data is on another site whose policy file hasn't been loaded yet,
+/// the policy file is fetched first, and the request is only sent if the
+/// policy file allows it.
+pub fn fetch_data(
+    context: &UpdateContext<'_, '_, '_>,
+    request: Request,
+) -> OwnedFuture<Vec<u8>, Error> {
+    let access = check_data_access(context, &request.url);
+
+    // Nothing is sent until this future is first polled, which only happens
+    // once the security sandbox has allowed it.
+    let fetch = context.navigator.fetch(&request.url, request.options);
+
+    Box::pin(async move {
+        access.await?;
+        fetch.await
+    })
+}
+
+/// Start fetching data that the movie will read, like `fetch_data`, but hand
+/// the response over in chunks as it arrives.
+pub fn fetch_data_chunked(
+    context: &UpdateContext<'_, '_, '_>,
+    request: Request,
+) -> OwnedChunkStream {
+    Box::pin(CheckedChunks {
+        access: Some(check_data_access(context, &request.url)),
+        chunks: context
+            .navigator
+            .fetch_chunked(&request.url, request.options),
+    })
+}
+
+/// Check that the security sandbox allows the movie to read data from `url`.
+///
+/// If the data is on another site whose policy file hasn't been loaded yet,
+/// the returned future fetches it first, and then checks against it.
+fn check_data_access(context: &UpdateContext<'_, '_, '_>, url: &str) -> OwnedFuture<(), Error> {
+    if let Err(e) = check_url(url) {
+        return denied(e);
+    }
+
+    match context.security.check_data_load(url) {
+        Access::Allowed => Box::pin(async { Ok(()) }),
+        Access::Denied => denied(Error::SecurityViolation(url.to_string())),
+        Access::NeedsPolicyFile(policy_url) => {
+            let security = context.security.clone();
+            let policy_fetch = context
+                .navigator
+                .fetch(policy_url.as_str(), RequestOptions::get());
+            let url = url.to_string();
+
+            Box::pin(async move {
+                let policy_file = parse_policy_file(&policy_url, policy_fetch.await);
+                security.add_policy_file(policy_url, policy_file);
+
+                if security.check_data_load(&url) == Access::Allowed {
+                    Ok(())
+                } else {
+                    let e = Error::SecurityViolation(url);
+                    log::warn!("{}", e);
+                    Err(e)
+                }
+            })
+        }
+    }
+}
+
+/// A `ChunkStream` that only starts streaming a response once the security
+/// sandbox has allowed it.
+struct CheckedChunks {
+    /// The security check, until it has passed.
+    access: Option<OwnedFuture<(), Error>>,
+
+    /// The response.
+    chunks: OwnedChunkStream,
+}
+
+impl ChunkStream for CheckedChunks {
+    fn poll_next_chunk(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Chunk, Error>>> {
+        let this = self.get_mut();
+        if let Some(access) = &mut this.access {
+            match access.as_mut().poll(cx) {
+                Poll::Ready(Ok(())) => this.access = None,
+                Poll::Ready(Err(e)) => return Poll::Ready(Some(Err(e))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        this.chunks.as_mut().poll_next_chunk(cx)
+    }
+}
+
+/// Fetch a policy file that the movie asked for with
+/// `Security.loadPolicyFile`, so that later data loads take it into account.
+///
+/// Socket policy files (`xmlsocket://` URLs) are not supported.
+pub fn load_policy_file(context: &mut UpdateContext<'_, '_, '_>, url: &str) {
+    let policy_url = match context.security.resolve(url) {
+        Some(policy_url) if matches!(policy_url.scheme(), "http" | "https") => policy_url,
+        _ => {
+            log::warn!("Ignoring unsupported policy file {}", url);
+            return;
+        }
+    };
+
+    let request = Request::get(policy_url.to_string());
+    spawn_fetch(context, request, move |context, response| {
+        let policy_file = parse_policy_file(&policy_url, response);
+        context.security.add_policy_file(policy_url, policy_file);
+        Ok(())
+    });
+}
+
+/// Parse a fetched policy file, logging why it can't be used if it failed to
+/// load or is invalid.
+fn parse_policy_file(url: &Url, response: Result<Vec<u8>, Error>) -> Option<PolicyFile> {
+    let data = match response {
+        Ok(data) => data,
+        Err(e) => {
+            log::warn!("Could not load policy file {}: {}", url, e);
+            return None;
+        }
+    };
+
+    match PolicyFile::parse(&data) {
+        Ok(policy_file) => Some(policy_file),
+        Err(e) => {
+            log::warn!("Ignoring policy file {}: {}", url, e);
+            None
+        }
+    }
+}
+
+/// A future for a request that the security sandbox denied.
+fn denied<T>(e: Error) -> OwnedFuture<T, Error> {
+    log::warn!("{}", e);
+    Box::pin(async move { Err(e) })
+}
+
+/// Fetch a request on the player's executor.
+///
+/// `on_response` runs with the update context at the first frame boundary
+/// after the response arrives. Responses to requests spawned in the same
+/// frame are delivered in the order the requests were made.
+pub fn spawn_fetch<F>(
+    context: &mut UpdateContext<'_, '_, '_>,
+    request: Request,
+    on_response: F,
+) -> TaskHandle
+where
+    F: for<'a, 'gc, 'gc_context> FnOnce(
+            &mut UpdateContext<'a, 'gc, 'gc_context>,
+            Result<Vec<u8>, Error>,
+        ) -> Result<(), Error>
+        + 'static,
+{
+    let fetch = fetch(context, request);
+    context.executor.spawn_with(fetch, on_response)
+}
+
+/// Deliver the result of a load into the player.
+///
+/// `func` runs with the player's update context, and the action queue is
+/// flushed afterwards so that any handlers it queued run immediately.
+pub fn deliver<F, R>(player: &Mutex<Player>, func: F) -> R
+where
+    F: for<'a, 'gc, 'gc_context> FnOnce(&mut UpdateContext<'a, 'gc, 'gc_context>) -> R,
+{
+    player.lock().expect("Could not lock player!!").update(func)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn form_values_request() {
+        let mut form_values = IndexMap::new();
+        form_values.insert("a".to_string(), "1 2".to_string());
+        form_values.insert("b".to_string(), "&".to_string());
+
+        let request = Request::with_form_values(
+            "http://example.com/test".to_string(),
+            &form_values,
+            Some(NavigationMethod::Get),
+        );
+        assert_eq!(request.url(), "http://example.com/test?a=1+2&b=%26");
+
+        let request = Request::with_form_values(
+            "http://example.com/test?x=y".to_string(),
+            &form_values,
+            Some(NavigationMethod::Get),
+        );
+        assert_eq!(request.url(), "http://example.com/test?x=y&a=1+2&b=%26");
+
+        let request = Request::with_form_values(
+            "http://example.com/test".to_string(),
+            &form_values,
+            Some(NavigationMethod::Post),
+        );
+        assert_eq!(request.url(), "http://example.com/test");
+        assert_eq!(
+            request.options().body(),
+            &Some((
+                b"a=1+2&b=%26".to_vec(),
+                "application/x-www-form-urlencoded".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn security_check() {
+        assert!(check_url("data.xml").is_ok());
+        assert!(check_url("../movies/clip.swf").is_ok());
+        assert!(check_url("http://example.com/data.txt").is_ok());
+        assert!(check_url("file:///home/user/data.txt").is_ok());
+        assert!(check_url("javascript:alert(1)").is_err());
+    }
+}
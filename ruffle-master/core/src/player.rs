@@ -4,29 +4,49 @@ use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::object::Object;
 use crate::avm1::property::Attribute;
 use crate::avm1::{Avm1, AvmString, ScriptObject, TObject, Timers, Value};
-use crate::avm2::{Avm2, Domain as Avm2Domain};
+use crate::avm2::{Avm2, Domain as Avm2Domain, Event as Avm2Event, Value as Avm2Value};
 use crate::backend::{
     audio::{AudioBackend, AudioManager},
+    clipboard::ClipboardBackend,
+    gamepad::GamepadBackend,
+    local_connection::LocalConnectionBackend,
     locale::LocaleBackend,
     log::LogBackend,
-    navigator::{NavigatorBackend, RequestOptions},
+    navigator::NavigatorBackend,
+    print::{NullPrintBackend, PrintBackend},
     render::RenderBackend,
+    socket::SocketBackend,
     storage::StorageBackend,
-    ui::{MouseCursor, UiBackend},
+    ui::{DialogFile, MouseCursor, StageOrientation, UiBackend},
     video::VideoBackend,
 };
-use crate::config::Letterbox;
-use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
+use crate::commands::{CommandHandler, CommandList};
+use crate::config::{
+    GamepadKeyMapping, Letterbox, LoaderLimits, StageAlign, StageQuality, StageScaleMode,
+    TextRendering,
+};
+use crate::context::{ActionQueue, ActionType, LogContext, RenderContext, UpdateContext, View};
 use crate::display_object::{EditText, MorphShape, MovieClip};
-use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent};
+use crate::events::{
+    ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, KeyboardState, PlayerEvent,
+};
+use crate::executor::Executor;
 use crate::external::Value as ExternalValue;
 use crate::external::{ExternalInterface, ExternalInterfaceProvider};
-use crate::focus_tracker::FocusTracker;
+use crate::focus_tracker::{FocusTracker, TabStop};
+use crate::frame_lifecycle::run_frame_phases;
+use crate::i18n::{EnglishMessageCatalog, MessageCatalog};
 use crate::library::Library;
 use crate::loader::LoadManager;
+use crate::local_connection::LocalConnectionManager;
+use crate::net::{self, Request};
 use crate::prelude::*;
 use crate::property_map::PropertyMap;
+use crate::security::SecuritySandbox;
+use crate::socket::SocketManager;
+use crate::streams::StreamManager;
 use crate::tag_utils::SwfMovie;
+use crate::telemetry::{AvmVersion, Telemetry, TelemetrySink};
 use crate::transform::TransformStack;
 use crate::vminterface::{AvmType, Instantiator};
 use gc_arena::{make_arena, ArenaParameters, Collect, GcCell};
@@ -94,6 +114,15 @@ struct GcRootData<'gc> {
 
     /// Manager of active sound instances.
     audio_manager: AudioManager<'gc>,
+
+    /// The player's `NetStream`s.
+    stream_manager: StreamManager<'gc>,
+
+    /// The player's `Socket`s and `XMLSocket`s.
+    socket_manager: SocketManager<'gc>,
+
+    /// The player's `LocalConnection`s.
+    local_connection_manager: LocalConnectionManager<'gc>,
 }
 
 impl<'gc> GcRootData<'gc> {
@@ -115,6 +144,9 @@ impl<'gc> GcRootData<'gc> {
         &mut Timers<'gc>,
         &mut ExternalInterface<'gc>,
         &mut AudioManager<'gc>,
+        &mut StreamManager<'gc>,
+        &mut SocketManager<'gc>,
+        &mut LocalConnectionManager<'gc>,
     ) {
         (
             &mut self.levels,
@@ -129,6 +161,9 @@ impl<'gc> GcRootData<'gc> {
             &mut self.timers,
             &mut self.external_interface,
             &mut self.audio_manager,
+            &mut self.stream_manager,
+            &mut self.socket_manager,
+            &mut self.local_connection_manager,
         )
     }
 }
@@ -143,6 +178,11 @@ type Storage = Box<dyn StorageBackend>;
 type Locale = Box<dyn LocaleBackend>;
 type Log = Box<dyn LogBackend>;
 type Ui = Box<dyn UiBackend>;
+type Clipboard = Box<dyn ClipboardBackend>;
+type Gamepad = Box<dyn GamepadBackend>;
+type Socket = Box<dyn SocketBackend>;
+type LocalConnection = Box<dyn LocalConnectionBackend>;
+type Print = Box<dyn PrintBackend>;
 type Video = Box<dyn VideoBackend>;
 
 pub struct Player {
@@ -165,6 +205,14 @@ pub struct Player {
     is_playing: bool,
     needs_render: bool,
 
+    /// Whether the whole viewport has to be drawn again in the next frame,
+    /// rather than only the areas that changed, such as after it was resized.
+    needs_full_render: bool,
+
+    /// The areas of the viewport that changed since the stage was last
+    /// rendered.
+    dirty_region: BoundingBox,
+
     renderer: Renderer,
     audio: Audio,
     navigator: Navigator,
@@ -172,8 +220,16 @@ pub struct Player {
     locale: Locale,
     log: Log,
     ui: Ui,
+    clipboard: Clipboard,
+    gamepad: Gamepad,
+    socket: Socket,
+    local_connection: LocalConnection,
     video: Video,
 
+    /// The printer for `PrintJob`, which can't print unless the embedder
+    /// provides one.
+    print: Print,
+
     transform_stack: TransformStack,
     view_matrix: Matrix,
     inverse_view_matrix: Matrix,
@@ -184,6 +240,12 @@ pub struct Player {
     gc_arena: GcArena,
     background_color: Option<Color>,
 
+    /// The background color that the viewport was last cleared to, so that it
+    /// is drawn again in full when scripts or the movie change the color.
+    rendered_background_color: Option<Color>,
+
+    /// The frame rate of the root movie, which every movie loaded into it
+    /// also runs at, whatever the frame rate in its own header.
     frame_rate: f64,
 
     /// A time budget for executing frames.
@@ -202,14 +264,51 @@ pub struct Player {
     movie_height: u32,
     letterbox: Letterbox,
 
+    /// How the stage is scaled to fit the viewport.
+    scale_mode: StageScaleMode,
+
+    /// The edges of the viewport that the stage is aligned to.
+    stage_align: StageAlign,
+
+    /// The orientation of the stage on the device.
+    orientation: StageOrientation,
+
+    /// Whether the focus can be moved and used with the keyboard alone.
+    keyboard_navigation: bool,
+
+    /// The quality the stage is drawn at.
+    quality: StageQuality,
+
+    /// How glyphs in text are positioned, before the quality is taken into
+    /// account.
+    text_rendering: TextRendering,
+
     mouse_pos: (Twips, Twips),
     is_mouse_down: bool,
 
+    /// The keys held down, as seen by ActionScript.
+    keyboard: KeyboardState,
+
+    /// The keys that gamepads press, for movies without gamepad support.
+    gamepad_key_mapping: GamepadKeyMapping,
+
+    /// The keys that gamepads were holding down when they were last polled.
+    gamepad_keys_down: Vec<KeyCode>,
+
     /// The current mouse cursor icon.
     mouse_cursor: MouseCursor,
 
     system: SystemProperties,
 
+    /// The security sandbox of the root movie.
+    security: SecuritySandbox,
+
+    /// The text of the player's own dialogs.
+    messages: Box<dyn MessageCatalog>,
+
+    /// Where compatibility statistics go, if the embedder wants them.
+    telemetry: Telemetry,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     instance_counter: i32,
 
@@ -230,6 +329,16 @@ pub struct Player {
     /// The current frame of the main timeline, if available.
     /// The first frame is frame 1.
     current_frame: Option<u16>,
+
+    /// Whether the user chose to stop being shown uncaught script errors.
+    script_errors_suppressed: bool,
+
+    /// Outstanding asynchronous tasks, polled at each frame boundary.
+    executor: Executor,
+
+    /// Whether the embedder allows a debugger to attach to any movie,
+    /// regardless of its `EnableDebugger` tag and password.
+    debugger_override: bool,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -244,6 +353,10 @@ impl Player {
         video: Video,
         log: Log,
         ui: Ui,
+        clipboard: Clipboard,
+        gamepad: Gamepad,
+        socket: Socket,
+        local_connection: LocalConnection,
     ) -> Result<Arc<Mutex<Self>>, Error> {
         let fake_movie = Arc::new(SwfMovie::empty(NEWEST_PLAYER_VERSION));
         let movie_width = 550;
@@ -259,8 +372,11 @@ impl Player {
 
             is_playing: false,
             needs_render: true,
+            needs_full_render: true,
+            dirty_region: Default::default(),
 
             background_color: None,
+            rendered_background_color: None,
             transform_stack: TransformStack::new(),
             view_matrix: Default::default(),
             inverse_view_matrix: Default::default(),
@@ -286,6 +402,9 @@ impl Player {
                         external_interface: ExternalInterface::new(),
                         focus_tracker: FocusTracker::new(gc_context),
                         audio_manager: AudioManager::new(),
+                        stream_manager: StreamManager::new(),
+                        socket_manager: SocketManager::new(),
+                        local_connection_manager: LocalConnectionManager::new(),
                     },
                 ))
             }),
@@ -300,9 +419,18 @@ impl Player {
             viewport_width: movie_width,
             viewport_height: movie_height,
             letterbox: Letterbox::Fullscreen,
+            scale_mode: Default::default(),
+            stage_align: Default::default(),
+            orientation: Default::default(),
+            keyboard_navigation: false,
+            quality: Default::default(),
+            text_rendering: Default::default(),
 
             mouse_pos: (Twips::zero(), Twips::zero()),
             is_mouse_down: false,
+            keyboard: KeyboardState::default(),
+            gamepad_key_mapping: GamepadKeyMapping::default(),
+            gamepad_keys_down: Vec::new(),
             mouse_cursor: MouseCursor::Arrow,
 
             renderer,
@@ -311,14 +439,25 @@ impl Player {
             locale,
             log,
             ui,
+            clipboard,
+            gamepad,
+            socket,
+            local_connection,
             video,
+            print: Box::new(NullPrintBackend::new()),
             self_reference: None,
             system: SystemProperties::default(),
+            security: SecuritySandbox::new(),
+            messages: Box::new(EnglishMessageCatalog),
+            telemetry: Telemetry::new(),
             instance_counter: 0,
             time_til_next_timer: None,
             storage,
             max_execution_duration: Duration::from_secs(15),
             current_frame: None,
+            script_errors_suppressed: false,
+            executor: Executor::new(),
+            debugger_override: false,
         };
 
         player.mutate_with_update_context(|context| {
@@ -353,7 +492,7 @@ impl Player {
     /// off.
     pub fn fetch_root_movie(&mut self, movie_url: &str, parameters: PropertyMap<String>) {
         self.mutate_with_update_context(|context| {
-            let fetch = context.navigator.fetch(movie_url, RequestOptions::get());
+            let fetch = net::fetch(context, Request::get(movie_url.to_string()));
             let process = context.load_manager.load_root_movie(
                 context.player.clone().unwrap(),
                 fetch,
@@ -381,6 +520,7 @@ impl Player {
         self.movie_width = movie.width();
         self.movie_height = movie.height();
         self.frame_rate = movie.header().frame_rate.into();
+        self.security.set_movie(&movie);
         self.swf = movie;
         self.instance_counter = 0;
 
@@ -567,6 +707,11 @@ impl Player {
         self.is_playing = v;
     }
 
+    /// Whether anything on the stage changed since it was last rendered.
+    ///
+    /// Frames that change nothing visually, such as those of a menu waiting
+    /// for input, leave this unset, so that embedders can skip presenting
+    /// them.
     pub fn needs_render(&self) -> bool {
         self.needs_render
     }
@@ -576,7 +721,7 @@ impl Player {
     }
 
     pub fn set_background_color(&mut self, color: Option<Color>) {
-        self.background_color = color
+        self.background_color = color;
     }
 
     pub fn letterbox(&self) -> Letterbox {
@@ -584,12 +729,99 @@ impl Player {
     }
 
     pub fn set_letterbox(&mut self, letterbox: Letterbox) {
-        self.letterbox = letterbox
+        self.letterbox = letterbox;
+        self.needs_full_render = true;
+    }
+
+    pub fn scale_mode(&self) -> StageScaleMode {
+        self.scale_mode
+    }
+
+    pub fn set_scale_mode(&mut self, scale_mode: StageScaleMode) {
+        self.scale_mode = scale_mode;
+        self.build_matrices();
+    }
+
+    pub fn stage_align(&self) -> StageAlign {
+        self.stage_align
+    }
+
+    pub fn set_stage_align(&mut self, stage_align: StageAlign) {
+        self.stage_align = stage_align;
+        self.build_matrices();
+    }
+
+    pub fn orientation(&self) -> StageOrientation {
+        self.orientation
+    }
+
+    /// Called by the UI when the device was rotated.
+    ///
+    /// The stage is sent an `orientationChanging` event first, and only
+    /// rotates if the movie doesn't cancel it. The UI should still pass the
+    /// new viewport size to `set_viewport_dimensions`.
+    pub fn set_orientation(&mut self, orientation: StageOrientation) {
+        if orientation == self.orientation {
+            return;
+        }
+
+        self.mutate_with_update_context(|context| {
+            let before = *context.orientation;
+            let stage = context.avm2.stage_object();
+
+            let is_cancelled = match stage {
+                Some(stage) => Avm2::dispatch_stage_orientation_event(
+                    context,
+                    stage,
+                    "orientationChanging",
+                    before,
+                    orientation,
+                )
+                .unwrap_or_else(|e| {
+                    log::error!("Got {} when dispatching orientationChanging event", e);
+                    false
+                }),
+                None => false,
+            };
+
+            if !is_cancelled {
+                *context.orientation = orientation;
+
+                if let Some(stage) = stage {
+                    if let Err(e) = Avm2::dispatch_stage_orientation_event(
+                        context,
+                        stage,
+                        "orientationChange",
+                        before,
+                        orientation,
+                    ) {
+                        log::error!("Got {} when dispatching orientationChange event", e);
+                    }
+                }
+            }
+
+            Self::run_actions(context);
+        });
+    }
+
+    /// The size of the stage as seen by the movie.
+    ///
+    /// This is the size of the movie, unless the stage isn't scaled, in which
+    /// case the stage is as large as the viewport.
+    fn stage_size(&self) -> (u32, u32) {
+        if self.scale_mode == StageScaleMode::NoScale {
+            (self.viewport_width, self.viewport_height)
+        } else {
+            (self.movie_width, self.movie_height)
+        }
     }
 
+    /// Only a stage that is scaled to fit inside the viewport leaves a
+    /// margin that can be letterboxed.
     fn should_letterbox(&self) -> bool {
-        self.letterbox == Letterbox::On
-            || (self.letterbox == Letterbox::Fullscreen && self.ui.is_fullscreen())
+        self.scale_mode == StageScaleMode::ShowAll
+            && (self.letterbox == Letterbox::On
+                || (self.letterbox == Letterbox::Fullscreen && self.ui.is_fullscreen()))
     }
 
     pub fn warn_on_unsupported_content(&self) -> bool {
@@ -613,20 +845,41 @@ impl Player {
     }
 
     pub fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        let old_stage_size = self.stage_size();
         self.viewport_width = width;
         self.viewport_height = height;
         self.build_matrices();
+
+        if self.stage_size() != old_stage_size {
+            self.mutate_with_update_context(|context| {
+                if let Some(stage) = context.avm2.stage_object() {
+                    let resize_evt = Avm2Event::new("resize");
+                    if let Err(e) = Avm2::dispatch_event(context, resize_evt, stage) {
+                        log::error!("Got {} when dispatching resize event", e);
+                    }
+                }
+                Self::run_actions(context);
+            });
+        }
     }
 
     pub fn handle_event(&mut self, event: PlayerEvent) {
         let mut needs_render = self.needs_render;
 
+        match event {
+            PlayerEvent::KeyDown { key_code } => self.keyboard.key_down(key_code),
+            PlayerEvent::KeyUp { key_code } => self.keyboard.key_up(key_code),
+            _ => (),
+        }
+
         if cfg!(feature = "avm_debug") {
             if let PlayerEvent::KeyDown {
                 key_code: KeyCode::V,
             } = event
             {
-                if self.ui.is_key_down(KeyCode::Control) && self.ui.is_key_down(KeyCode::Alt) {
+                if self.keyboard.is_key_down(KeyCode::Control)
+                    && self.keyboard.is_key_down(KeyCode::Alt)
+                {
                     self.mutate_with_update_context(|context| {
                         let mut dumper = VariableDumper::new("  ");
                         let levels = context.levels.clone();
@@ -661,7 +914,9 @@ impl Player {
                 key_code: KeyCode::D,
             } = event
             {
-                if self.ui.is_key_down(KeyCode::Control) && self.ui.is_key_down(KeyCode::Alt) {
+                if self.keyboard.is_key_down(KeyCode::Control)
+                    && self.keyboard.is_key_down(KeyCode::Alt)
+                {
                     self.mutate_with_update_context(|context| {
                         if context.avm1.show_debug_output() {
                             log::info!(
@@ -679,6 +934,64 @@ impl Player {
                     });
                 }
             }
+
+            if let PlayerEvent::KeyDown {
+                key_code: KeyCode::T,
+            } = event
+            {
+                if self.keyboard.is_key_down(KeyCode::Control)
+                    && self.keyboard.is_key_down(KeyCode::Alt)
+                {
+                    let tab_stops = self.tab_stops();
+                    let mut output = String::new();
+                    for (index, tab_stop) in tab_stops.iter().enumerate() {
+                        output.push_str(&format!(
+                            "  {}: {} {}{}\n",
+                            index,
+                            tab_stop.kind,
+                            tab_stop.path,
+                            if tab_stop.has_focus { " (focused)" } else { "" }
+                        ));
+                    }
+                    log::info!("Tab order:\n{}", output);
+                }
+            }
+        }
+
+        // Keyboard navigation moves the focus with Tab, and presses the
+        // focused button with Enter or Space.
+        if self.keyboard_navigation {
+            match event {
+                PlayerEvent::KeyDown {
+                    key_code: KeyCode::Tab,
+                } => {
+                    let reverse = self.keyboard.is_key_down(KeyCode::Shift);
+                    self.mutate_with_update_context(|context| {
+                        let tracker = context.focus_tracker;
+                        tracker.cycle(context, reverse);
+                    });
+                    needs_render = true;
+                }
+                PlayerEvent::KeyDown {
+                    key_code: KeyCode::Return,
+                }
+                | PlayerEvent::KeyDown {
+                    key_code: KeyCode::Space,
+                } => {
+                    self.press_focused_object(true);
+                    needs_render = true;
+                }
+                PlayerEvent::KeyUp {
+                    key_code: KeyCode::Return,
+                }
+                | PlayerEvent::KeyUp {
+                    key_code: KeyCode::Space,
+                } => {
+                    self.press_focused_object(false);
+                    needs_render = true;
+                }
+                _ => (),
+            }
         }
 
         // Update mouse position from mouse events.
@@ -737,6 +1050,40 @@ impl Player {
             });
         }
 
+        if let PlayerEvent::TextComposition { text: composition }
+        | PlayerEvent::TextCompositionEnd { text: composition } = &event
+        {
+            let is_end = matches!(event, PlayerEvent::TextCompositionEnd { .. });
+            self.mutate_with_update_context(|context| {
+                if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
+                    if is_end {
+                        text.commit_composition(composition, context);
+                    } else {
+                        text.set_composition(composition, context);
+                    }
+                }
+            });
+        }
+
+        // Text editing shortcuts for the focused text field.
+        if let PlayerEvent::KeyDown { key_code } = event {
+            if self.keyboard.is_key_down(KeyCode::Control)
+                && !self.keyboard.is_key_down(KeyCode::Alt)
+            {
+                self.mutate_with_update_context(|context| {
+                    if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
+                        match key_code {
+                            KeyCode::A => text.select_all(context.gc_context),
+                            KeyCode::C => text.copy(context),
+                            KeyCode::X => text.cut(context),
+                            KeyCode::V => text.paste(context),
+                            _ => (),
+                        }
+                    }
+                });
+            }
+        }
+
         // Propagate clip events.
         self.mutate_with_update_context(|context| {
             let (clip_event, listener) = match event {
@@ -785,6 +1132,29 @@ impl Player {
                     false,
                 );
             }
+
+            // AVM2 keyboard events go to the focused object, or the stage.
+            let key_event = match event {
+                PlayerEvent::KeyDown { key_code } => Some(("keyDown", key_code)),
+                PlayerEvent::KeyUp { key_code } => Some(("keyUp", key_code)),
+                _ => None,
+            };
+
+            if let Some((event_type, key_code)) = key_event {
+                let focused = match context.focus_tracker.get().map(|o| o.object2()) {
+                    Some(Avm2Value::Object(object)) => Some(object),
+                    _ => None,
+                };
+                let char_code = context.ui.last_key_char().map(u32::from).unwrap_or(0);
+
+                if let Some(target) = focused.or_else(|| context.avm2.stage_object()) {
+                    if let Err(e) = Avm2::dispatch_keyboard_event(
+                        context, target, event_type, key_code, char_code,
+                    ) {
+                        log::error!("Got {} when dispatching {} event", e, event_type);
+                    }
+                }
+            }
         });
 
         let mut is_mouse_down = self.is_mouse_down;
@@ -795,21 +1165,47 @@ impl Player {
                 }
             }
 
+            let hovered = context.mouse_hovered_object;
             match event {
                 PlayerEvent::MouseDown { .. } => {
                     is_mouse_down = true;
                     needs_render = true;
-                    if let Some(node) = context.mouse_hovered_object {
+                    if let Some(node) = hovered {
                         node.handle_clip_event(context, ClipEvent::Press);
                     }
+                    Self::dispatch_avm2_mouse_event(context, hovered, "mouseDown", None, true, 0);
                 }
 
                 PlayerEvent::MouseUp { .. } => {
                     is_mouse_down = false;
                     needs_render = true;
-                    if let Some(node) = context.mouse_hovered_object {
+                    if let Some(node) = hovered {
                         node.handle_clip_event(context, ClipEvent::Release);
                     }
+                    Self::dispatch_avm2_mouse_event(context, hovered, "mouseUp", None, false, 0);
+                    Self::dispatch_avm2_mouse_event(context, hovered, "click", None, false, 0);
+                }
+
+                PlayerEvent::MouseMove { .. } => {
+                    Self::dispatch_avm2_mouse_event(
+                        context,
+                        hovered,
+                        "mouseMove",
+                        None,
+                        is_mouse_down,
+                        0,
+                    );
+                }
+
+                PlayerEvent::MouseWheel { delta } => {
+                    Self::dispatch_avm2_mouse_event(
+                        context,
+                        hovered,
+                        "mouseWheel",
+                        None,
+                        is_mouse_down,
+                        delta.lines() as i32,
+                    );
                 }
 
                 _ => (),
@@ -823,6 +1219,60 @@ impl Player {
         }
     }
 
+    /// Press or release the focused object as if it was clicked, for keyboard
+    /// navigation.
+    ///
+    /// Text fields are left alone, as they take Enter and Space as text.
+    fn press_focused_object(&mut self, is_down: bool) {
+        self.mutate_with_update_context(|context| {
+            let focused = match context.focus_tracker.get() {
+                Some(focused) if focused.as_edit_text().is_none() => focused,
+                _ => return,
+            };
+
+            if is_down {
+                focused.handle_clip_event(context, ClipEvent::Press);
+                Self::dispatch_avm2_mouse_event(context, Some(focused), "mouseDown", None, true, 0);
+            } else {
+                focused.handle_clip_event(context, ClipEvent::Release);
+                Self::dispatch_avm2_mouse_event(context, Some(focused), "mouseUp", None, false, 0);
+                Self::dispatch_avm2_mouse_event(context, Some(focused), "click", None, false, 0);
+            }
+
+            Self::run_actions(context);
+        });
+    }
+
+    /// Dispatch an AVM2 `MouseEvent` for a mouse action over `node`.
+    ///
+    /// Events over nothing, or over an object without an AVM2 counterpart,
+    /// are sent to the stage instead.
+    fn dispatch_avm2_mouse_event<'gc>(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        node: Option<DisplayObject<'gc>>,
+        event_type: &'static str,
+        related: Option<DisplayObject<'gc>>,
+        button_down: bool,
+        delta: i32,
+    ) {
+        let avm2_object = |node: Option<DisplayObject<'gc>>| match node.map(|node| node.object2()) {
+            Some(Avm2Value::Object(object)) => Some(object),
+            _ => None,
+        };
+
+        let target = match avm2_object(node).or_else(|| context.avm2.stage_object()) {
+            Some(target) => target,
+            None => return,
+        };
+        let related = avm2_object(related);
+
+        if let Err(e) =
+            Avm2::dispatch_mouse_event(context, target, event_type, related, button_down, delta)
+        {
+            log::error!("Got {} when dispatching {} event", e, event_type);
+        }
+    }
+
     /// Update dragged object, if any.
     fn update_drag(&mut self) {
         let mouse_pos = self.mouse_pos;
@@ -874,11 +1324,22 @@ impl Player {
 
             let cur_hovered = context.mouse_hovered_object;
 
-            if cur_hovered.map(|d| d.as_ptr()) != new_hovered.map(|d| d.as_ptr()) {
+            let hover_changed = cur_hovered.map(|d| d.as_ptr()) != new_hovered.map(|d| d.as_ptr());
+            if hover_changed {
                 // RollOut of previous node.
                 if let Some(node) = cur_hovered {
                     if !node.removed() {
                         node.handle_clip_event(context, ClipEvent::RollOut);
+                        for event_type in &["mouseOut", "rollOut"] {
+                            Self::dispatch_avm2_mouse_event(
+                                context,
+                                Some(node),
+                                *event_type,
+                                new_hovered,
+                                false,
+                                0,
+                            );
+                        }
                     }
                 }
 
@@ -887,15 +1348,33 @@ impl Player {
                 if let Some(node) = new_hovered {
                     new_cursor = node.mouse_cursor();
                     node.handle_clip_event(context, ClipEvent::RollOver);
+                    for event_type in &["mouseOver", "rollOver"] {
+                        Self::dispatch_avm2_mouse_event(
+                            context,
+                            Some(node),
+                            *event_type,
+                            cur_hovered,
+                            false,
+                            0,
+                        );
+                    }
                 }
 
                 context.mouse_hovered_object = new_hovered;
 
                 Self::run_actions(context);
-                true
+            } else if let Some(node) = cur_hovered {
+                new_cursor = node.mouse_cursor();
             } else {
-                false
+                new_cursor = MouseCursor::Arrow;
+            }
+
+            // A cursor chosen with `Mouse.cursor` wins over the hovered object's.
+            if let Some(cursor) = context.avm2.mouse_cursor() {
+                new_cursor = cursor;
             }
+
+            hover_changed
         });
 
         // Update mouse cursor if it has changed.
@@ -931,86 +1410,223 @@ impl Player {
                 lib.register_character(id, crate::character::Character::MorphShape(morph_shape));
             }
         });
+        let avm_version = if is_action_script_3 {
+            AvmVersion::Avm2
+        } else {
+            AvmVersion::Avm1
+        };
+        self.telemetry.movie_loaded(&self.swf, avm_version);
         if is_action_script_3 && self.warn_on_unsupported_content {
-            self.ui.display_unsupported_message();
+            self.ui.display_unsupported_message(self.messages.as_ref());
         }
     }
 
     pub fn run_frame(&mut self) {
+        self.run_tasks();
+        self.poll_gamepads();
+
         self.update(|update_context| {
+            StreamManager::tick(update_context);
+            SocketManager::tick(update_context);
+            LocalConnectionManager::tick(update_context);
+
             // TODO: In what order are levels run?
             // NOTE: We have to copy all the layer pointers into a separate list
             // because level updates can create more levels, which we don't
             // want to run frames on
             let levels: Vec<_> = update_context.levels.values().copied().collect();
 
-            if let Some(level) = levels.first() {
-                level.exit_frame(update_context);
-            }
+            run_frame_phases(update_context, &levels);
 
-            if let Some(level) = levels.first() {
-                level.enter_frame(update_context);
-            }
+            update_context.update_sounds();
+        });
+        self.keyboard.end_frame();
+        if self.update_dirty_region() {
+            self.needs_render = true;
+        }
+    }
 
-            for level in levels.iter() {
-                level.construct_frame(update_context);
-            }
+    /// The color the stage is cleared to before it is drawn.
+    fn clear_color(&self) -> Color {
+        self.background_color
+            .clone()
+            .unwrap_or_else(|| Color::from_rgb(0xffffff, 255))
+    }
 
-            if let Some(level) = levels.first() {
-                level.frame_constructed(update_context);
+    /// Add the areas of the viewport where display objects changed since they
+    /// were last looked at to the region that has to be drawn again.
+    ///
+    /// Returns whether anything has to be drawn in the next render.
+    fn update_dirty_region(&mut self) -> bool {
+        let view_matrix = self.view_matrix;
+        let region = self.gc_arena.mutate(|_gc_context, gc_root| {
+            let root_data = gc_root.0.read();
+            let mut region = BoundingBox::default();
+            for (_depth, level) in root_data.levels.iter() {
+                level.collect_dirty_region(&view_matrix, (0, 0), &mut region);
             }
+            region
+        });
+        self.dirty_region.union(&region);
 
-            for level in levels.iter() {
-                level.run_frame(update_context);
-            }
+        self.dirty_region.valid
+            || self.needs_full_render
+            || self.rendered_background_color.as_ref() != Some(&self.clear_color())
+    }
+
+    /// Check the connected gamepads, and pass any changes on to `GameInput`
+    /// and to the keys that the gamepads are mapped to.
+    fn poll_gamepads(&mut self) {
+        let gamepads = self.gamepad.gamepads();
 
-            for level in levels.iter() {
-                level.run_frame_scripts(update_context);
+        let mut keys_down = Vec::new();
+        for gamepad in &gamepads {
+            for key_code in gamepad.mapped_keys(self.gamepad_key_mapping) {
+                if !keys_down.contains(&key_code) {
+                    keys_down.push(key_code);
+                }
             }
+        }
 
-            update_context.update_sounds();
+        let released: Vec<KeyCode> = self
+            .gamepad_keys_down
+            .iter()
+            .filter(|key_code| !keys_down.contains(key_code))
+            .copied()
+            .collect();
+        let pressed: Vec<KeyCode> = keys_down
+            .iter()
+            .filter(|key_code| !self.gamepad_keys_down.contains(key_code))
+            .copied()
+            .collect();
+        self.gamepad_keys_down = keys_down;
+
+        for key_code in released {
+            self.handle_event(PlayerEvent::KeyUp { key_code });
+        }
+        for key_code in pressed {
+            self.handle_event(PlayerEvent::KeyDown { key_code });
+        }
+
+        self.mutate_with_update_context(|context| {
+            if let Err(e) = Avm2::update_game_input(context, &gamepads) {
+                log::error!("Got {} when updating game input devices", e);
+            }
         });
-        self.needs_render = true;
     }
 
+    /// Draw the stage into the viewport.
+    ///
+    /// Only the areas of the viewport where display objects changed since the
+    /// last frame are drawn again, if the renderer keeps the previous frame,
+    /// and nothing is drawn at all if nothing changed.
     pub fn render(&mut self) {
+        self.update_dirty_region();
+        let dirty_region = std::mem::take(&mut self.dirty_region);
+
+        let background_color = self.clear_color();
+        let full_render = std::mem::take(&mut self.needs_full_render)
+            || self.rendered_background_color.as_ref() != Some(&background_color);
+        if !full_render && !dirty_region.valid {
+            self.needs_render = false;
+            return;
+        }
+
+        let mut view_bounds = self.view_bounds.clone();
+        if !full_render && self.renderer.supports_partial_redraw() {
+            // Snap the region to whole pixels, so that no partly covered
+            // pixels are left behind.
+            let to_pixels =
+                |twips: Twips, round: fn(f64) -> f64| Twips::from_pixels(round(twips.to_pixels()));
+            let area = swf::Rectangle {
+                x_min: to_pixels(dirty_region.x_min, f64::floor),
+                y_min: to_pixels(dirty_region.y_min, f64::floor),
+                x_max: to_pixels(dirty_region.x_max, f64::ceil),
+                y_max: to_pixels(dirty_region.y_max, f64::ceil),
+            };
+            let stage_region = dirty_region.transform(&self.inverse_view_matrix);
+            view_bounds = view_bounds.intersection(&stage_region);
+            self.renderer.set_scissor(Some(area));
+        } else {
+            self.renderer.set_scissor(None);
+        }
+
+        self.rendered_background_color = Some(background_color.clone());
+
+        let mut commands = self.render_stage(&View {
+            matrix: self.view_matrix,
+            bounds: view_bounds,
+        });
+
+        if self.should_letterbox() {
+            self.draw_letterbox(&mut commands);
+        }
+
+        self.renderer.submit_frame(background_color, commands);
+        self.needs_render = false;
+    }
+
+    /// Draw the stage into a view other than the player's own viewport, such
+    /// as an inspector zoomed in on part of the stage.
+    ///
+    /// The view is drawn in full with the player's renderer, so it should be
+    /// pointed at the target of the view first. This does not change what
+    /// the player considers to be on screen, but its own viewport is drawn
+    /// again in full the next time it renders.
+    pub fn render_view(&mut self, view: &View) {
         let background_color = self
             .background_color
             .clone()
             .unwrap_or_else(|| Color::from_rgb(0xffffff, 255));
-        self.renderer.begin_frame(background_color);
 
+        self.renderer.set_scissor(None);
+        let commands = self.render_stage(view);
+        self.renderer.submit_frame(background_color, commands);
+
+        self.needs_full_render = true;
+    }
+
+    /// The view of the stage in the player's own viewport.
+    pub fn view(&self) -> View {
+        View {
+            matrix: self.view_matrix,
+            bounds: self.view_bounds.clone(),
+        }
+    }
+
+    /// Record the commands to draw every level of the stage into the given
+    /// view.
+    fn render_stage(&mut self, view: &View) -> CommandList {
+        let text_rendering = self.text_rendering.at_quality(self.quality);
         let (renderer, transform_stack) = (&mut self.renderer, &mut self.transform_stack);
 
         transform_stack.push(&crate::transform::Transform {
-            matrix: self.view_matrix,
+            matrix: view.matrix,
             ..Default::default()
         });
 
-        let view_bounds = self.view_bounds.clone();
-        self.gc_arena.mutate(|_gc_context, gc_root| {
+        let commands = self.gc_arena.mutate(|_gc_context, gc_root| {
             let root_data = gc_root.0.read();
             let mut render_context = RenderContext {
                 renderer: renderer.deref_mut(),
+                commands: CommandList::new(),
                 library: &root_data.library,
                 transform_stack,
-                view_bounds,
+                view_bounds: view.bounds.clone(),
                 clip_depth_stack: vec![],
                 allow_mask: true,
+                text_rendering,
             };
 
             for (_depth, level) in root_data.levels.iter() {
                 level.render(&mut render_context);
             }
+
+            render_context.commands
         });
         transform_stack.pop();
 
-        if self.should_letterbox() {
-            self.draw_letterbox();
-        }
-
-        self.renderer.end_frame();
-        self.needs_render = false;
+        commands
     }
 
     /// The current frame of the main timeline, if available.
@@ -1027,7 +1643,7 @@ impl Player {
         &mut self.audio
     }
 
-    // The frame rate of the current movie in FPS.
+    // The frame rate of the root movie in FPS, shared by all loaded movies.
     pub fn frame_rate(&self) -> f64 {
         self.frame_rate
     }
@@ -1060,6 +1676,38 @@ impl Player {
         &mut self.ui
     }
 
+    pub fn clipboard(&self) -> &Clipboard {
+        &self.clipboard
+    }
+
+    pub fn clipboard_mut(&mut self) -> &mut Clipboard {
+        &mut self.clipboard
+    }
+
+    pub fn gamepad(&self) -> &Gamepad {
+        &self.gamepad
+    }
+
+    pub fn gamepad_mut(&mut self) -> &mut Gamepad {
+        &mut self.gamepad
+    }
+
+    pub fn socket(&self) -> &Socket {
+        &self.socket
+    }
+
+    pub fn socket_mut(&mut self) -> &mut Socket {
+        &mut self.socket
+    }
+
+    pub fn local_connection(&self) -> &LocalConnection {
+        &self.local_connection
+    }
+
+    pub fn local_connection_mut(&mut self) -> &mut LocalConnection {
+        &mut self.local_connection
+    }
+
     pub fn locale(&self) -> &Locale {
         &self.locale
     }
@@ -1170,7 +1818,10 @@ impl Player {
                     if let Err(e) =
                         Avm2::run_stack_frame_for_callable(callable, reciever, &args[..], context)
                     {
-                        log::error!("Unhandled AVM2 exception in event handler: {}", e);
+                        context.report_script_error(
+                            format!("Unhandled AVM2 exception in event handler: {}", e),
+                            vec![],
+                        );
                     }
                 }
             }
@@ -1184,23 +1835,62 @@ impl Player {
             (self.viewport_width as f32, self.viewport_height as f32);
         let movie_aspect = movie_width / movie_height;
         let viewport_aspect = viewport_width / viewport_height;
-        let (scale, margin_width, margin_height) = if viewport_aspect > movie_aspect {
-            let scale = viewport_height / movie_height;
-            (scale, (viewport_width - movie_width * scale) / 2.0, 0.0)
-        } else {
-            let scale = viewport_width / movie_width;
-            (scale, 0.0, (viewport_height - movie_height * scale) / 2.0)
+        let (scale_x, scale_y) = match self.scale_mode {
+            StageScaleMode::ShowAll => {
+                let scale = if viewport_aspect > movie_aspect {
+                    viewport_height / movie_height
+                } else {
+                    viewport_width / movie_width
+                };
+                (scale, scale)
+            }
+            StageScaleMode::NoBorder => {
+                let scale = if viewport_aspect > movie_aspect {
+                    viewport_width / movie_width
+                } else {
+                    viewport_height / movie_height
+                };
+                (scale, scale)
+            }
+            StageScaleMode::ExactFit => {
+                (viewport_width / movie_width, viewport_height / movie_height)
+            }
+            StageScaleMode::NoScale => (1.0, 1.0),
+        };
+
+        // The space left around the stage, which is negative when the stage
+        // is cropped, goes on the sides it isn't aligned to.
+        let align_margin = |space: f32, start: StageAlign, end: StageAlign| {
+            if self.stage_align.contains(start) {
+                0.0
+            } else if self.stage_align.contains(end) {
+                space
+            } else {
+                space / 2.0
+            }
         };
+        let margin_width = align_margin(
+            viewport_width - movie_width * scale_x,
+            StageAlign::LEFT,
+            StageAlign::RIGHT,
+        );
+        let margin_height = align_margin(
+            viewport_height - movie_height * scale_y,
+            StageAlign::TOP,
+            StageAlign::BOTTOM,
+        );
+
         self.view_matrix = Matrix {
-            a: scale,
+            a: scale_x,
             b: 0.0,
             c: 0.0,
-            d: scale,
+            d: scale_y,
             tx: Twips::from_pixels(margin_width.into()),
             ty: Twips::from_pixels(margin_height.into()),
         };
         self.inverse_view_matrix = self.view_matrix;
         self.inverse_view_matrix.invert();
+        self.needs_full_render = true;
 
         self.view_bounds = if self.should_letterbox() {
             // No letterbox: movie area
@@ -1213,13 +1903,11 @@ impl Player {
             }
         } else {
             // No letterbox: full visible stage area
-            let margin_width = f64::from(margin_width / scale);
-            let margin_height = f64::from(margin_height / scale);
             BoundingBox {
-                x_min: Twips::from_pixels(-margin_width),
-                y_min: Twips::from_pixels(-margin_height),
-                x_max: Twips::from_pixels(f64::from(self.movie_width) + margin_width),
-                y_max: Twips::from_pixels(f64::from(self.movie_height) + margin_height),
+                x_min: Twips::from_pixels(f64::from(-margin_width / scale_x)),
+                y_min: Twips::from_pixels(f64::from(-margin_height / scale_y)),
+                x_max: Twips::from_pixels(f64::from((viewport_width - margin_width) / scale_x)),
+                y_max: Twips::from_pixels(f64::from((viewport_height - margin_height) / scale_y)),
                 valid: true,
             }
         };
@@ -1231,57 +1919,92 @@ impl Player {
     where
         F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc, '_>) -> R,
     {
+        let (stage_width, stage_height) = self.stage_size();
+        let (old_scale_mode, old_stage_align) = (self.scale_mode, self.stage_align);
+
         // We have to do this piecewise borrowing of fields before the closure to avoid
         // completely borrowing `self`.
         let (
             player_version,
             swf,
             background_color,
+            frame_rate,
+            quality,
+            stage_scale_mode,
+            stage_align,
+            orientation,
+            text_rendering,
             renderer,
             audio,
             navigator,
             ui,
+            clipboard,
             rng,
             mouse_position,
+            keyboard,
             stage_width,
             stage_height,
             player,
             system_properties,
+            security,
+            messages,
+            telemetry,
             instance_counter,
             storage,
             locale,
             logging,
             video,
+            socket,
+            local_connection,
+            print,
             needs_render,
             max_execution_duration,
             current_frame,
             time_offset,
+            script_errors_suppressed,
+            executor,
         ) = (
             self.player_version,
             &self.swf,
             &mut self.background_color,
+            &mut self.frame_rate,
+            &mut self.quality,
+            &mut self.scale_mode,
+            &mut self.stage_align,
+            &mut self.orientation,
+            self.text_rendering,
             self.renderer.deref_mut(),
             self.audio.deref_mut(),
             self.navigator.deref_mut(),
             self.ui.deref_mut(),
+            self.clipboard.deref_mut(),
             &mut self.rng,
             &self.mouse_pos,
-            Twips::from_pixels(self.movie_width.into()),
-            Twips::from_pixels(self.movie_height.into()),
+            &self.keyboard,
+            Twips::from_pixels(stage_width.into()),
+            Twips::from_pixels(stage_height.into()),
             self.self_reference.clone(),
             &mut self.system,
+            &mut self.security,
+            self.messages.as_ref(),
+            &mut self.telemetry,
             &mut self.instance_counter,
             self.storage.deref_mut(),
             self.locale.deref_mut(),
             self.log.deref_mut(),
             self.video.deref_mut(),
+            self.socket.deref_mut(),
+            self.local_connection.deref_mut(),
+            self.print.deref_mut(),
             &mut self.needs_render,
             self.max_execution_duration,
             &mut self.current_frame,
             &mut self.time_offset,
+            &mut self.script_errors_suppressed,
+            &mut self.executor,
         );
 
-        self.gc_arena.mutate(|gc_context, gc_root| {
+        let ret = self.gc_arena.mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.0.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
             let focus_tracker = root_data.focus_tracker;
@@ -1298,6 +2021,9 @@ impl Player {
                 timers,
                 external_interface,
                 audio_manager,
+                stream_manager,
+                socket_manager,
+                local_connection_manager,
             ) = root_data.update_context_params();
 
             let mut update_context = UpdateContext {
@@ -1305,26 +2031,40 @@ impl Player {
                 swf,
                 library,
                 background_color,
+                frame_rate,
+                quality,
+                stage_scale_mode,
+                stage_align,
+                orientation,
+                text_rendering,
                 rng,
                 renderer,
                 audio,
                 navigator,
                 ui,
+                clipboard,
                 action_queue,
                 gc_context,
                 levels,
                 mouse_hovered_object,
                 mouse_position,
+                keyboard,
                 drag_object,
                 stage_size: (stage_width, stage_height),
                 player,
                 load_manager,
                 system: system_properties,
+                security,
+                messages,
+                telemetry,
                 instance_counter,
                 storage,
                 locale,
                 log: logging,
                 video,
+                socket,
+                local_connection,
+                print,
                 shared_objects,
                 unbound_text_fields,
                 timers,
@@ -1338,6 +2078,12 @@ impl Player {
                 times_get_time_called: 0,
                 time_offset,
                 audio_manager,
+                script_errors_suppressed,
+                executor,
+                log_context: LogContext::default(),
+                stream_manager,
+                socket_manager,
+                local_connection_manager,
             };
 
             let ret = f(&mut update_context);
@@ -1352,7 +2098,34 @@ impl Player {
             root_data.mouse_hovered_object = update_context.mouse_hovered_object;
 
             ret
-        })
+        });
+
+        // Scripts may have changed how the stage is laid out in the viewport.
+        if self.scale_mode != old_scale_mode || self.stage_align != old_stage_align {
+            self.build_matrices();
+        }
+
+        ret
+    }
+
+    /// Poll outstanding asynchronous tasks and deliver the results of those
+    /// that have finished.
+    ///
+    /// Completions run in the order their tasks were spawned, followed by any
+    /// actions they queued.
+    pub fn run_tasks(&mut self) {
+        let completions = self.executor.poll();
+        if completions.is_empty() {
+            return;
+        }
+
+        self.update(|context| {
+            for completion in completions {
+                if let Err(e) = completion(context) {
+                    log::error!("Error delivering async task result: {}", e);
+                }
+            }
+        });
     }
 
     /// Loads font data from the given buffer.
@@ -1427,6 +2200,74 @@ impl Player {
         self.mutate_with_update_context(|context| context.avm1.has_mouse_listener())
     }
 
+    /// Show the context menu for a right-click on the hovered object.
+    ///
+    /// The menu comes from the `contextMenu` of the hovered object or its
+    /// nearest ancestor that has one. Returns `false` if the movie has no
+    /// items to show, in which case the UI may show its own menu instead.
+    pub fn show_context_menu(&mut self) -> bool {
+        let items = self.mutate_with_update_context(|context| {
+            let mut targets = Vec::new();
+            let mut node = context
+                .mouse_hovered_object
+                .or_else(|| context.levels.get(&0).copied());
+            while let Some(dobj) = node {
+                if let Avm2Value::Object(object) = dobj.object2() {
+                    targets.push(object);
+                }
+                node = dobj.parent();
+            }
+
+            if targets.is_empty() {
+                return Vec::new();
+            }
+
+            let items = match Avm2::prepare_context_menu(context, &targets) {
+                Ok(items) => items,
+                Err(e) => {
+                    log::error!("Got {} when preparing the context menu", e);
+                    Vec::new()
+                }
+            };
+            Self::run_actions(context);
+
+            items
+        });
+
+        if items.is_empty() {
+            return false;
+        }
+
+        self.ui.display_context_menu(&items);
+        true
+    }
+
+    /// Run the item at `index` of the context menu that was last shown by
+    /// `show_context_menu`.
+    pub fn run_context_menu_item(&mut self, index: usize) {
+        self.mutate_with_update_context(|context| {
+            if let Err(e) = Avm2::run_context_menu_item(context, index) {
+                log::error!("Got {} when running context menu item {}", e, index);
+            }
+            Self::run_actions(context);
+        });
+        self.needs_render = true;
+    }
+
+    /// Tell the movie which file the user picked in the last file dialog
+    /// that was shown, or `None` if they cancelled.
+    ///
+    /// For save dialogs, the file should already have been written.
+    pub fn finish_file_dialog(&mut self, file: Option<DialogFile>) {
+        self.mutate_with_update_context(|context| {
+            if let Err(e) = Avm2::finish_file_dialog(context, file) {
+                log::error!("Got {} when finishing a file dialog", e);
+            }
+            Self::run_actions(context);
+        });
+        self.needs_render = true;
+    }
+
     pub fn add_external_interface(&mut self, provider: Box<dyn ExternalInterfaceProvider>) {
         self.mutate_with_update_context(|context| {
             context.external_interface.add_provider(provider)
@@ -1459,55 +2300,150 @@ impl Player {
         self.max_execution_duration = max_execution_duration
     }
 
-    fn draw_letterbox(&mut self) {
+    /// Set whether calls to unimplemented builtins throw an error in the movie.
+    pub fn keyboard_navigation(&self) -> bool {
+        self.keyboard_navigation
+    }
+
+    /// Let the user move the focus with Tab, and press the focused button
+    /// with Enter or Space, so that movies can be used without a mouse.
+    pub fn set_keyboard_navigation(&mut self, keyboard_navigation: bool) {
+        self.keyboard_navigation = keyboard_navigation;
+    }
+
+    /// The objects that Tab moves the focus between, in order, for auditing
+    /// how accessible a movie is.
+    pub fn tab_stops(&mut self) -> Vec<TabStop> {
+        self.mutate_with_update_context(|context| context.focus_tracker.tab_stops(context))
+    }
+
+    pub fn set_strict_mode(&mut self, strict_mode: bool) {
+        self.mutate_with_update_context(|context| {
+            context.avm1.set_strict_mode(strict_mode);
+            context.avm2.set_strict_mode(strict_mode);
+        })
+    }
+
+    pub fn set_loader_limits(&mut self, limits: LoaderLimits) {
+        self.mutate_with_update_context(|context| context.load_manager.set_limits(limits))
+    }
+
+    /// Set the translations of the text shown in the player's own dialogs.
+    pub fn set_message_catalog(&mut self, messages: Box<dyn MessageCatalog>) {
+        self.messages = messages;
+    }
+
+    /// Send anonymized compatibility statistics to `sink`, or stop sending
+    /// them if it is `None`.
+    pub fn set_telemetry_sink(&mut self, sink: Option<Box<dyn TelemetrySink>>) {
+        self.telemetry.set_sink(sink);
+    }
+
+    /// Print `PrintJob`s with `print`.
+    pub fn set_print_backend(&mut self, print: Box<dyn PrintBackend>) {
+        self.print = print;
+    }
+
+    pub fn permissive_security(&self) -> bool {
+        self.security.is_permissive()
+    }
+
+    /// Set whether movies may load from anywhere, ignoring their security
+    /// sandbox and the policy files of other sites.
+    pub fn set_permissive_security(&mut self, permissive: bool) {
+        self.security.set_permissive(permissive);
+    }
+
+    pub fn quality(&self) -> StageQuality {
+        self.quality
+    }
+
+    pub fn set_quality(&mut self, quality: StageQuality) {
+        self.quality = quality;
+        self.needs_render = true;
+    }
+
+    pub fn text_rendering(&self) -> TextRendering {
+        self.text_rendering
+    }
+
+    /// Change how glyphs in text are positioned.
+    ///
+    /// Text that has already been laid out keeps its glyph advances until it
+    /// is next laid out, but is drawn with the new hinting right away.
+    pub fn set_text_rendering(&mut self, text_rendering: TextRendering) {
+        self.text_rendering = text_rendering;
+        self.needs_render = true;
+    }
+
+    pub fn gamepad_key_mapping(&self) -> GamepadKeyMapping {
+        self.gamepad_key_mapping
+    }
+
+    /// Set the keys that gamepads press, for movies that only support the
+    /// keyboard.
+    pub fn set_gamepad_key_mapping(&mut self, mapping: GamepadKeyMapping) {
+        self.gamepad_key_mapping = mapping;
+    }
+
+    /// Whether the root movie asks not to be imported into authoring tools.
+    pub fn is_movie_protected(&self) -> bool {
+        self.swf.protection().is_protected()
+    }
+
+    /// Whether the root movie allows a debugger to attach to it.
+    pub fn is_debugger_enabled(&self) -> bool {
+        self.debugger_override || self.swf.protection().is_debugger_enabled()
+    }
+
+    pub fn debugger_override(&self) -> bool {
+        self.debugger_override
+    }
+
+    /// Set whether a debugger may attach to any movie, ignoring the movie's
+    /// own debugging settings and password.
+    pub fn set_debugger_override(&mut self, debugger_override: bool) {
+        self.debugger_override = debugger_override;
+    }
+
+    /// Check whether a debugger may attach to the root movie with the given
+    /// password.
+    pub fn can_attach_debugger(&self, password: &str) -> bool {
+        self.debugger_override || self.swf.protection().can_attach_debugger(password)
+    }
+
+    fn draw_letterbox(&self, commands: &mut CommandList) {
         let black = Color::from_rgb(0, 255);
         let viewport_width = self.viewport_width as f32;
         let viewport_height = self.viewport_height as f32;
 
-        let margin_width = self.view_matrix.tx.to_pixels() as f32;
-        let margin_height = self.view_matrix.ty.to_pixels() as f32;
-        if margin_height > 0.0 {
-            self.renderer.draw_rect(
-                black.clone(),
-                &Matrix::create_box(
-                    viewport_width,
-                    margin_height,
-                    0.0,
-                    Twips::default(),
-                    Twips::default(),
-                ),
-            );
-            self.renderer.draw_rect(
-                black,
-                &Matrix::create_box(
-                    viewport_width,
-                    margin_height,
-                    0.0,
-                    Twips::default(),
-                    Twips::from_pixels((viewport_height - margin_height) as f64),
-                ),
-            );
-        } else if margin_width > 0.0 {
-            self.renderer.draw_rect(
-                black.clone(),
-                &Matrix::create_box(
-                    margin_width,
-                    viewport_height,
-                    0.0,
-                    Twips::default(),
-                    Twips::default(),
-                ),
-            );
-            self.renderer.draw_rect(
-                black,
-                &Matrix::create_box(
-                    margin_width,
-                    viewport_height,
-                    0.0,
-                    Twips::from_pixels((viewport_width - margin_width) as f64),
-                    Twips::default(),
-                ),
-            );
+        // The edges of the stage in the viewport, which aren't centered when
+        // the stage is aligned to a side.
+        let left = self.view_matrix.tx.to_pixels() as f32;
+        let top = self.view_matrix.ty.to_pixels() as f32;
+        let right = left + self.movie_width as f32 * self.view_matrix.a;
+        let bottom = top + self.movie_height as f32 * self.view_matrix.d;
+
+        let bars = [
+            (0.0, 0.0, viewport_width, top),
+            (0.0, bottom, viewport_width, viewport_height - bottom),
+            (0.0, 0.0, left, viewport_height),
+            (right, 0.0, viewport_width - right, viewport_height),
+        ];
+
+        for (x, y, width, height) in bars.iter() {
+            if *width > 0.0 && *height > 0.0 {
+                commands.draw_rect(
+                    black.clone(),
+                    &Matrix::create_box(
+                        *width,
+                        *height,
+                        0.0,
+                        Twips::from_pixels(f64::from(*x)),
+                        Twips::from_pixels(f64::from(*y)),
+                    ),
+                );
+            }
         }
     }
 }
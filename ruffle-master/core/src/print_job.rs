@@ -0,0 +1,69 @@
+//! Printing for `PrintJob`
+//!
+//! Both VMs' `PrintJob`s go through here. Pages are drawn with the software
+//! rasterizer that backs `BitmapData.draw`, at one pixel per point, and handed
+//! to the `PrintBackend` one at a time.
+
+use crate::backend::print::{PageSettings, PrintedPage};
+use crate::bitmap::bitmap_data::BitmapData;
+use crate::bitmap::rasterizer::Rasterizer;
+use crate::bounding_box::BoundingBox;
+use crate::context::UpdateContext;
+use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::transform::Transform;
+use swf::{BlendMode, Matrix, Twips};
+
+/// Ask the user to start a print job.
+///
+/// Returns the paper to print on, or `None` if no job was started.
+pub fn start(context: &mut UpdateContext<'_, '_, '_>) -> Option<PageSettings> {
+    context.print.start_job()
+}
+
+/// Add a page showing `object` to the job in progress.
+///
+/// `area` is the part of the object to print, in its own coordinates, or all
+/// of it if `None`. Anything that doesn't fit on the page is cut off. Returns
+/// `false` if there is nothing to print.
+pub fn add_page<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    object: DisplayObject<'gc>,
+    area: Option<BoundingBox>,
+    settings: &PageSettings,
+) -> bool {
+    let area = area.unwrap_or_else(|| object.bounds());
+    if !area.valid {
+        return false;
+    }
+
+    let width =
+        ((area.x_max - area.x_min).to_pixels().ceil().max(0.0) as u32).min(settings.page_width);
+    let height =
+        ((area.y_max - area.y_min).to_pixels().ceil().max(0.0) as u32).min(settings.page_height);
+    if width == 0 || height == 0 {
+        return false;
+    }
+
+    // Pages start out as white paper.
+    let mut page = BitmapData::default();
+    page.init_pixels(width, height, -1, false);
+
+    let transform = Transform {
+        matrix: Matrix::translate(Twips::zero() - area.x_min, Twips::zero() - area.y_min),
+        color_transform: Default::default(),
+    };
+    let mut rasterizer = Rasterizer::new(&mut page, &transform, None, BlendMode::Normal);
+    object.rasterize_self(context.renderer, &mut rasterizer);
+
+    context.print.add_page(PrintedPage {
+        width,
+        height,
+        rgba: page.pixels_rgba(),
+    });
+    true
+}
+
+/// Print the pages of the job in progress.
+pub fn send(context: &mut UpdateContext<'_, '_, '_>) {
+    context.print.send_job();
+}
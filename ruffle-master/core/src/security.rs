@@ -0,0 +1,664 @@
+//! Security sandbox
+//!
+//! Every movie runs in a sandbox that depends on where it was loaded from.
+//! The sandbox decides what the movie may load: local movies may read either
+//! local files or the network, but never both, and remote movies may only
+//! read data from other domains if those domains publish a policy file
+//! (`crossdomain.xml`) that grants them access.
+
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::Arc;
+use swf::TagCode;
+use thiserror::Error;
+use url::Url;
+
+/// The sandbox that a movie runs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SandboxType {
+    /// Loaded over the network. May freely load from its own domain.
+    Remote,
+
+    /// Loaded from the file system. May load local files, but may not
+    /// contact the network.
+    LocalWithFile,
+
+    /// Loaded from the file system, with `useNetwork` set in its
+    /// `FileAttributes`. May read from the network if a policy file allows
+    /// it, but may not load local files.
+    LocalWithNetwork,
+
+    /// Trusted by the user or the embedder. May load from anywhere.
+    LocalTrusted,
+
+    /// An AIR application. May load from anywhere.
+    Application,
+}
+
+impl SandboxType {
+    /// Pick the sandbox of a movie loaded from `url`.
+    ///
+    /// Movies without a URL were handed to the player directly by the
+    /// embedder, and are trusted.
+    pub fn for_url(url: Option<&Url>, use_network: bool) -> Self {
+        match url.map(Url::scheme) {
+            None => SandboxType::LocalTrusted,
+            Some("file") if use_network => SandboxType::LocalWithNetwork,
+            Some("file") => SandboxType::LocalWithFile,
+            Some(_) => SandboxType::Remote,
+        }
+    }
+
+    /// The name of this sandbox, as given by `Security.sandboxType`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SandboxType::Remote => "remote",
+            SandboxType::LocalWithFile => "localWithFile",
+            SandboxType::LocalWithNetwork => "localWithNetwork",
+            SandboxType::LocalTrusted => "localTrusted",
+            SandboxType::Application => "application",
+        }
+    }
+}
+
+impl fmt::Display for SandboxType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Which policy files a site honours, from the `site-control` element of its
+/// master policy file at `/crossdomain.xml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetaPolicy {
+    /// No policy files are honoured, not even the master.
+    None,
+
+    /// Only the master policy file is honoured. This is the default.
+    MasterOnly,
+
+    /// Policy files served as `text/x-cross-domain-policy` are honoured.
+    ByContentType,
+
+    /// Policy files named `crossdomain.xml` are honoured.
+    ByFtpFilename,
+
+    /// Every policy file on the site is honoured.
+    All,
+}
+
+impl MetaPolicy {
+    /// Parse the value of `permitted-cross-domain-policies`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(MetaPolicy::None),
+            "master-only" => Some(MetaPolicy::MasterOnly),
+            "by-content-type" => Some(MetaPolicy::ByContentType),
+            "by-ftp-filename" => Some(MetaPolicy::ByFtpFilename),
+            "all" => Some(MetaPolicy::All),
+            _ => None,
+        }
+    }
+}
+
+/// A domain that has been granted access, either by a policy file or by
+/// `Security.allowDomain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AllowAccessFrom {
+    /// The domain, which may be `*` or start with a `*.` wildcard.
+    domain: String,
+
+    /// Whether movies served over plain HTTP are refused access to HTTPS
+    /// content.
+    secure: bool,
+}
+
+impl AllowAccessFrom {
+    /// Whether this grants access to a movie from `domain` to content served
+    /// over HTTPS if `target_is_secure`.
+    fn allows(&self, domain: Option<&str>, is_secure: bool, target_is_secure: bool) -> bool {
+        if self.secure && target_is_secure && !is_secure {
+            return false;
+        }
+
+        if self.domain == "*" {
+            return true;
+        }
+
+        let domain = match domain {
+            Some(domain) => domain.to_ascii_lowercase(),
+            None => return false,
+        };
+        let pattern = self.domain.to_ascii_lowercase();
+        match pattern.strip_prefix("*.") {
+            Some(suffix) => domain == suffix || domain.ends_with(&format!(".{}", suffix)),
+            None => domain == pattern,
+        }
+    }
+}
+
+/// An error encountered while parsing a policy file.
+#[derive(Error, Debug)]
+pub enum PolicyFileError {
+    #[error("Policy file is not valid UTF-8")]
+    InvalidEncoding(#[from] std::str::Utf8Error),
+
+    #[error("Policy file is not valid XML: {0}")]
+    InvalidXml(#[from] quick_xml::Error),
+
+    #[error("Policy file has no <cross-domain-policy> element")]
+    NotAPolicyFile,
+}
+
+/// A cross-domain policy file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolicyFile {
+    /// The meta-policy, if this policy file declares one.
+    meta_policy: Option<MetaPolicy>,
+
+    /// The domains that may read from the site.
+    allow_access_from: Vec<AllowAccessFrom>,
+}
+
+impl PolicyFile {
+    /// Parse a policy file.
+    pub fn parse(data: &[u8]) -> Result<Self, PolicyFileError> {
+        let mut reader = Reader::from_str(std::str::from_utf8(data)?);
+        let mut buf = Vec::new();
+        let mut policy = PolicyFile::default();
+        let mut depth = 0;
+        let mut has_root = false;
+
+        loop {
+            buf.clear();
+            let (element, is_empty) = match reader.read_event(&mut buf)? {
+                Event::Start(element) => (element, false),
+                Event::Empty(element) => (element, true),
+                Event::End(_) => {
+                    depth -= 1;
+                    continue;
+                }
+                Event::Eof => break,
+                _ => continue,
+            };
+
+            if depth == 0 {
+                if element.name() != b"cross-domain-policy" {
+                    return Err(PolicyFileError::NotAPolicyFile);
+                }
+                has_root = true;
+            } else if depth == 1 {
+                match element.name() {
+                    b"site-control" => {
+                        policy.meta_policy =
+                            attribute(&element, b"permitted-cross-domain-policies")?
+                                .and_then(|name| MetaPolicy::from_name(&name));
+                    }
+                    b"allow-access-from" => {
+                        if let Some(domain) = attribute(&element, b"domain")? {
+                            let secure = attribute(&element, b"secure")?;
+                            policy.allow_access_from.push(AllowAccessFrom {
+                                domain,
+                                secure: secure.as_deref() != Some("false"),
+                            });
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            if !is_empty {
+                depth += 1;
+            }
+        }
+
+        if !has_root {
+            return Err(PolicyFileError::NotAPolicyFile);
+        }
+
+        Ok(policy)
+    }
+
+    /// The meta-policy that this policy file declares, if any.
+    pub fn meta_policy(&self) -> Option<MetaPolicy> {
+        self.meta_policy
+    }
+
+    /// Whether this policy file grants access to a movie from `domain`.
+    ///
+    /// `is_secure` is whether the movie was served over HTTPS, and
+    /// `target_is_secure` whether the content it is reading is.
+    pub fn allows(&self, domain: Option<&str>, is_secure: bool, target_is_secure: bool) -> bool {
+        self.allow_access_from
+            .iter()
+            .any(|entry| entry.allows(domain, is_secure, target_is_secure))
+    }
+}
+
+/// Get the value of an attribute of an XML element.
+fn attribute(element: &BytesStart<'_>, name: &[u8]) -> Result<Option<String>, PolicyFileError> {
+    for attribute in element.attributes() {
+        let attribute = attribute?;
+        if attribute.key == name {
+            return Ok(Some(String::from_utf8_lossy(&attribute.value).into_owned()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Whether the sandbox allows a load.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Access {
+    Allowed,
+    Denied,
+
+    /// The load is cross-domain, and the master policy file of the other
+    /// domain, at the given URL, has to be fetched before it can be decided.
+    NeedsPolicyFile(Url),
+}
+
+/// The sandbox of the root movie, and the policy files loaded so far.
+#[derive(Clone)]
+pub struct SecuritySandbox {
+    /// The sandbox that the root movie runs in.
+    sandbox_type: SandboxType,
+
+    /// Where the root movie was loaded from.
+    movie_url: Option<Url>,
+
+    /// Whether the embedder turned off all security checks.
+    permissive: bool,
+
+    /// The domains granted access with `Security.allowDomain`.
+    allowed_domains: Vec<AllowAccessFrom>,
+
+    /// Policy files by the URL they were loaded from, or `None` for policy
+    /// files that failed to load, so that they aren't requested again.
+    ///
+    /// This is shared with in-progress loads, which store the policy files
+    /// that they fetch.
+    policy_files: Rc<RefCell<HashMap<Url, Option<PolicyFile>>>>,
+}
+
+impl SecuritySandbox {
+    /// Construct the sandbox used before a root movie is loaded, which trusts
+    /// everything.
+    pub fn new() -> Self {
+        Self {
+            sandbox_type: SandboxType::LocalTrusted,
+            movie_url: None,
+            permissive: false,
+            allowed_domains: Vec::new(),
+            policy_files: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    /// Place the sandbox around a new root movie.
+    pub fn set_movie(&mut self, movie: &Arc<SwfMovie>) {
+        self.movie_url = movie.url().and_then(|url| Url::parse(url).ok());
+        self.sandbox_type = SandboxType::for_url(self.movie_url.as_ref(), uses_network(movie));
+        self.allowed_domains.clear();
+    }
+
+    /// The sandbox that the root movie runs in.
+    pub fn sandbox_type(&self) -> SandboxType {
+        self.sandbox_type
+    }
+
+    /// Whether all security checks are turned off.
+    pub fn is_permissive(&self) -> bool {
+        self.permissive
+    }
+
+    /// Turn all security checks off or back on.
+    ///
+    /// This lets embedders run content that expects to be trusted, at the
+    /// cost of letting it read from any site the user can reach.
+    pub fn set_permissive(&mut self, permissive: bool) {
+        self.permissive = permissive;
+    }
+
+    /// Grant movies from `domain` access to script the root movie.
+    ///
+    /// If `allow_insecure` is set, movies served over HTTP may script a root
+    /// movie served over HTTPS.
+    pub fn allow_domain(&mut self, domain: &str, allow_insecure: bool) {
+        self.allowed_domains.push(AllowAccessFrom {
+            domain: domain.to_string(),
+            secure: !allow_insecure,
+        });
+    }
+
+    /// Whether a movie loaded from `url` may script the root movie.
+    pub fn allows_scripting_from(&self, url: &Url) -> bool {
+        if self.permissive || self.is_same_origin(url) {
+            return true;
+        }
+
+        let is_secure = url.scheme() == "https";
+        let target_is_secure = self.movie_url.as_ref().map(Url::scheme) == Some("https");
+        self.allowed_domains
+            .iter()
+            .any(|entry| entry.allows(url.host_str(), is_secure, target_is_secure))
+    }
+
+    /// Store a policy file, or record that it failed to load.
+    pub fn add_policy_file(&self, url: Url, policy_file: Option<PolicyFile>) {
+        self.policy_files.borrow_mut().insert(url, policy_file);
+    }
+
+    /// Resolve a URL that the root movie loads from.
+    pub fn resolve(&self, url: &str) -> Option<Url> {
+        match &self.movie_url {
+            Some(movie_url) => movie_url.join(url).ok(),
+            None => Url::parse(url).ok(),
+        }
+    }
+
+    /// Whether the root movie may load content, such as another movie or an
+    /// image, from `url`.
+    ///
+    /// Content is not readable by the movie, so no policy file is needed.
+    pub fn check_load(&self, url: &str) -> Access {
+        self.access(url, false)
+    }
+
+    /// Whether the root movie may read data, such as text or variables, from
+    /// `url`.
+    pub fn check_data_load(&self, url: &str) -> Access {
+        self.access(url, true)
+    }
+
+    /// Whether the root movie may open a socket to `host`.
+    ///
+    /// Socket policy files aren't supported, so remote movies may only
+    /// connect back to the host that they were loaded from.
+    pub fn allows_socket(&self, host: &str) -> bool {
+        if self.permissive {
+            return true;
+        }
+
+        match self.sandbox_type {
+            SandboxType::LocalTrusted
+            | SandboxType::LocalWithNetwork
+            | SandboxType::Application => true,
+            SandboxType::LocalWithFile => false,
+            SandboxType::Remote => self
+                .movie_url
+                .as_ref()
+                .and_then(Url::host_str)
+                .map_or(false, |movie_host| movie_host.eq_ignore_ascii_case(host)),
+        }
+    }
+
+    fn access(&self, url: &str, is_data: bool) -> Access {
+        if self.permissive {
+            return Access::Allowed;
+        }
+
+        let url = match self.resolve(url) {
+            Some(url) => url,
+            None => return Access::Allowed,
+        };
+        let is_local = url.scheme() == "file";
+
+        match self.sandbox_type {
+            SandboxType::LocalTrusted | SandboxType::Application => Access::Allowed,
+            SandboxType::LocalWithFile if is_local => Access::Allowed,
+            SandboxType::LocalWithFile => Access::Denied,
+            _ if is_local => Access::Denied,
+            SandboxType::Remote if self.is_same_origin(&url) => Access::Allowed,
+            _ if !is_data => Access::Allowed,
+            _ => self.policy_file_access(&url),
+        }
+    }
+
+    /// Check the policy files of the site that `url` is on.
+    fn policy_file_access(&self, url: &Url) -> Access {
+        let master_url = match url.join("/crossdomain.xml") {
+            Ok(master_url) => master_url,
+            Err(_) => return Access::Denied,
+        };
+
+        let policy_files = self.policy_files.borrow();
+        let master = match policy_files.get(&master_url) {
+            Some(master) => master.as_ref(),
+            None => return Access::NeedsPolicyFile(master_url),
+        };
+
+        let meta_policy = master
+            .and_then(PolicyFile::meta_policy)
+            .unwrap_or(MetaPolicy::MasterOnly);
+        if meta_policy == MetaPolicy::None {
+            return Access::Denied;
+        }
+
+        // Local movies have no domain, so only `*` grants them access.
+        let domain = match self.sandbox_type {
+            SandboxType::Remote => self.movie_url.as_ref().and_then(Url::host_str),
+            _ => None,
+        };
+        let is_secure = self.movie_url.as_ref().map(Url::scheme) == Some("https");
+        let target_is_secure = url.scheme() == "https";
+        let allows =
+            |policy_file: &PolicyFile| policy_file.allows(domain, is_secure, target_is_secure);
+
+        if master.map_or(false, allows) {
+            return Access::Allowed;
+        }
+
+        // We can't see the content type of a policy file, so the other
+        // meta-policies are all treated as `all`.
+        if meta_policy != MetaPolicy::MasterOnly {
+            for (policy_url, policy_file) in policy_files.iter() {
+                let policy_file = match policy_file {
+                    Some(policy_file) => policy_file,
+                    None => continue,
+                };
+
+                // A policy file only covers its own directory and below.
+                let directory = &policy_url.path()[..=policy_url.path().rfind('/').unwrap_or(0)];
+                if policy_url.origin() == url.origin()
+                    && url.path().starts_with(directory)
+                    && allows(policy_file)
+                {
+                    return Access::Allowed;
+                }
+            }
+        }
+
+        Access::Denied
+    }
+
+    /// Whether `url` is on the same site as the root movie.
+    fn is_same_origin(&self, url: &Url) -> bool {
+        match &self.movie_url {
+            Some(movie_url) => movie_url.origin() == url.origin(),
+            None => false,
+        }
+    }
+}
+
+impl Default for SecuritySandbox {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether a movie asks to be placed in the `localWithNetwork` sandbox when it
+/// is run locally.
+fn uses_network(movie: &Arc<SwfMovie>) -> bool {
+    let slice = SwfSlice::from(movie.clone());
+    let mut reader = slice.read_from(0);
+    match reader.read_tag_code_and_length() {
+        Ok((tag_code, _tag_len))
+            if TagCode::from_u16(tag_code) == Some(TagCode::FileAttributes) =>
+        {
+            match reader.read_file_attributes() {
+                Ok(attributes) => attributes.use_network_sandbox,
+                Err(e) => {
+                    log::error!("Got {} when reading FileAttributes", e);
+                    false
+                }
+            }
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sandbox(movie_url: &str, sandbox_type: SandboxType) -> SecuritySandbox {
+        let mut sandbox = SecuritySandbox::new();
+        sandbox.movie_url = Some(Url::parse(movie_url).unwrap());
+        sandbox.sandbox_type = sandbox_type;
+        sandbox
+    }
+
+    #[test]
+    fn parse_policy_file() {
+        let policy = PolicyFile::parse(
+            br#"<?xml version="1.0"?>
+            <!DOCTYPE cross-domain-policy SYSTEM "http://www.adobe.com/xml/dtds/cross-domain-policy.dtd">
+            <cross-domain-policy>
+                <site-control permitted-cross-domain-policies="all"/>
+                <allow-access-from domain="*.example.com"/>
+                <allow-access-from domain="games.test" secure="false"/>
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+
+        assert_eq!(policy.meta_policy(), Some(MetaPolicy::All));
+        assert!(policy.allows(Some("example.com"), false, false));
+        assert!(policy.allows(Some("www.Example.com"), false, false));
+        assert!(!policy.allows(Some("badexample.com"), false, false));
+        assert!(!policy.allows(Some("www.example.com"), false, true));
+        assert!(policy.allows(Some("games.test"), false, true));
+        assert!(!policy.allows(None, false, false));
+
+        assert!(PolicyFile::parse(b"<html><body/></html>").is_err());
+        assert!(PolicyFile::parse(b"").is_err());
+    }
+
+    #[test]
+    fn local_sandboxes() {
+        let with_file = sandbox("file:///movies/game.swf", SandboxType::LocalWithFile);
+        assert_eq!(with_file.check_data_load("data.txt"), Access::Allowed);
+        assert_eq!(
+            with_file.check_load("http://example.com/game.swf"),
+            Access::Denied
+        );
+
+        let with_network = sandbox("file:///movies/game.swf", SandboxType::LocalWithNetwork);
+        assert_eq!(with_network.check_load("data.txt"), Access::Denied);
+        assert_eq!(
+            with_network.check_load("http://example.com/game.swf"),
+            Access::Allowed
+        );
+
+        let trusted = sandbox("file:///movies/game.swf", SandboxType::LocalTrusted);
+        assert_eq!(
+            trusted.check_data_load("http://example.com/data.txt"),
+            Access::Allowed
+        );
+    }
+
+    #[test]
+    fn cross_domain_policy() {
+        let remote = sandbox("http://games.test/game.swf", SandboxType::Remote);
+        assert_eq!(remote.check_data_load("scores.txt"), Access::Allowed);
+        assert_eq!(
+            remote.check_load("http://example.com/game.swf"),
+            Access::Allowed
+        );
+        assert_eq!(remote.check_data_load("file:///etc/passwd"), Access::Denied);
+
+        let master_url = Url::parse("http://example.com/crossdomain.xml").unwrap();
+        assert_eq!(
+            remote.check_data_load("http://example.com/data/scores.txt"),
+            Access::NeedsPolicyFile(master_url.clone())
+        );
+
+        remote.add_policy_file(master_url, None);
+        assert_eq!(
+            remote.check_data_load("http://example.com/data/scores.txt"),
+            Access::Denied
+        );
+
+        remote.add_policy_file(
+            Url::parse("http://example.com/crossdomain.xml").unwrap(),
+            Some(
+                PolicyFile::parse(
+                    br#"<cross-domain-policy>
+                        <site-control permitted-cross-domain-policies="all"/>
+                    </cross-domain-policy>"#,
+                )
+                .unwrap(),
+            ),
+        );
+        remote.add_policy_file(
+            Url::parse("http://example.com/data/policy.xml").unwrap(),
+            Some(
+                PolicyFile::parse(
+                    br#"<cross-domain-policy>
+                        <allow-access-from domain="games.test"/>
+                    </cross-domain-policy>"#,
+                )
+                .unwrap(),
+            ),
+        );
+        assert_eq!(
+            remote.check_data_load("http://example.com/data/scores.txt"),
+            Access::Allowed
+        );
+        assert_eq!(
+            remote.check_data_load("http://example.com/private/scores.txt"),
+            Access::Denied
+        );
+
+        let mut permissive = sandbox("http://games.test/game.swf", SandboxType::Remote);
+        permissive.set_permissive(true);
+        assert_eq!(
+            permissive.check_data_load("http://example.com/data.txt"),
+            Access::Allowed
+        );
+    }
+
+    #[test]
+    fn allow_domain() {
+        let mut sandbox = sandbox("https://games.test/game.swf", SandboxType::Remote);
+        let other = Url::parse("https://cdn.example.com/ad.swf").unwrap();
+        let insecure = Url::parse("http://cdn.example.com/ad.swf").unwrap();
+        assert!(!sandbox.allows_scripting_from(&other));
+
+        sandbox.allow_domain("*.example.com", false);
+        assert!(sandbox.allows_scripting_from(&other));
+        assert!(!sandbox.allows_scripting_from(&insecure));
+
+        sandbox.allow_domain("cdn.example.com", true);
+        assert!(sandbox.allows_scripting_from(&insecure));
+    }
+
+    #[test]
+    fn socket_hosts() {
+        let remote = sandbox("http://games.test/game.swf", SandboxType::Remote);
+        assert!(remote.allows_socket("games.test"));
+        assert!(remote.allows_socket("GAMES.test"));
+        assert!(!remote.allows_socket("chat.example.com"));
+
+        let with_file = sandbox("file:///movies/game.swf", SandboxType::LocalWithFile);
+        assert!(!with_file.allows_socket("localhost"));
+
+        let mut permissive = with_file;
+        permissive.set_permissive(true);
+        assert!(permissive.allows_socket("chat.example.com"));
+    }
+}
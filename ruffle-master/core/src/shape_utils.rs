@@ -73,9 +73,24 @@ pub enum DrawPath<'a> {
     Fill {
         style: &'a FillStyle,
         commands: Vec<DrawCommand>,
+        winding: WindingRule,
     },
 }
 
+/// How the inside of a fill is decided where its outline overlaps itself.
+///
+/// Fills converted from SWF shapes wind holes in the opposite direction to
+/// their outlines, so both rules fill them the same way.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WindingRule {
+    /// A point is inside the fill if a ray from it crosses the outline an
+    /// odd number of times.
+    EvenOdd,
+
+    /// A point is inside the fill if the outline winds around it at all.
+    NonZero,
+}
+
 /// `DistilledShape` represents a ready-to-be-consumed collection of paths (both fills and strokes)
 /// that has been converted down from another source (such as SWF's `swf::Shape` format).
 #[derive(Debug, PartialEq, Clone)]
@@ -542,6 +557,7 @@ impl<'a> ShapeConverter<'a> {
             self.commands.push(DrawPath::Fill {
                 style,
                 commands: path.into_draw_commands().collect(),
+                winding: WindingRule::EvenOdd,
             });
         }
 
@@ -648,6 +664,7 @@ mod tests {
                     y: Twips::from_pixels(100.0),
                 },
             ],
+            winding: WindingRule::EvenOdd,
         }];
         assert_eq!(commands, expected);
     }
@@ -712,6 +729,7 @@ mod tests {
                     y: Twips::from_pixels(200.0),
                 },
             ],
+            winding: WindingRule::EvenOdd,
         }];
         assert_eq!(commands, expected);
     }
@@ -858,6 +876,7 @@ pub fn shape_hit_test(
 /// Test whether the given point is contained with in the paths specified by the draw commands.
 pub fn draw_command_fill_hit_test(
     commands: &[DrawCommand],
+    winding_rule: WindingRule,
     (point_x, point_y): (Twips, Twips),
 ) -> bool {
     let mut x = Twips::zero();
@@ -883,7 +902,10 @@ pub fn draw_command_fill_hit_test(
             }
         }
     }
-    winding & 0b1 != 0
+    match winding_rule {
+        WindingRule::EvenOdd => winding & 0b1 != 0,
+        WindingRule::NonZero => winding != 0,
+    }
 }
 
 /// Test whether the given point is contained with in the strokes specified by the draw commands.
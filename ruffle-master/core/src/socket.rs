@@ -0,0 +1,297 @@
+//! TCP connections for `flash.net.Socket` and `XMLSocket`.
+//!
+//! The `SocketBackend` makes the connections; the `SocketManager` remembers
+//! which script object each of them belongs to, and once per frame turns the
+//! backend's events into callbacks and events for those objects. `XMLSocket`s
+//! exchange null-terminated strings, which are split apart here, while
+//! `Socket`s get the raw bytes.
+
+use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::{
+    root_error_handler, AvmString, Object as Avm1Object, TObject, Value as Avm1Value,
+};
+use crate::avm2::{Avm2, Event as Avm2Event, Object as Avm2Object};
+use crate::backend::socket::{SocketEvent, SocketHandle};
+use crate::context::UpdateContext;
+use gc_arena::{Collect, CollectionContext};
+use generational_arena::Arena;
+
+/// The script object that a connection belongs to.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub enum SocketObject<'gc> {
+    /// An AVM1 `XMLSocket`.
+    Avm1XmlSocket(Avm1Object<'gc>),
+
+    /// An AVM2 `flash.net.Socket`, which exchanges raw bytes.
+    Avm2Socket(Avm2Object<'gc>),
+
+    /// An AVM2 `flash.net.XMLSocket`.
+    Avm2XmlSocket(Avm2Object<'gc>),
+}
+
+impl<'gc> SocketObject<'gc> {
+    fn ptr_eq(self, other: Self) -> bool {
+        match (self, other) {
+            (SocketObject::Avm1XmlSocket(a), SocketObject::Avm1XmlSocket(b)) => {
+                Avm1Object::ptr_eq(a, b)
+            }
+            (SocketObject::Avm2Socket(a), SocketObject::Avm2Socket(b))
+            | (SocketObject::Avm2XmlSocket(a), SocketObject::Avm2XmlSocket(b)) => {
+                Avm2Object::ptr_eq(a, b)
+            }
+            _ => false,
+        }
+    }
+
+    fn is_xml(self) -> bool {
+        !matches!(self, SocketObject::Avm2Socket(_))
+    }
+}
+
+/// A connection, or an attempt at making one.
+struct Socket<'gc> {
+    object: SocketObject<'gc>,
+
+    /// Whether the backend reported that the connection was made.
+    connected: bool,
+
+    /// The bytes received on an XML socket since the end of the last
+    /// message.
+    partial_message: Vec<u8>,
+}
+
+/// What to tell a socket's script object.
+enum Delivery {
+    Connected,
+    Data(Vec<u8>),
+    Messages(Vec<String>),
+    Closed,
+
+    /// The connection couldn't be made.
+    Failed(String),
+}
+
+/// All of the player's socket connections.
+pub struct SocketManager<'gc> {
+    sockets: Arena<Socket<'gc>>,
+}
+
+unsafe impl<'gc> Collect for SocketManager<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        for (_, socket) in self.sockets.iter() {
+            socket.object.trace(cc)
+        }
+    }
+}
+
+impl<'gc> Default for SocketManager<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'gc> SocketManager<'gc> {
+    pub fn new() -> Self {
+        Self {
+            sockets: Arena::new(),
+        }
+    }
+
+    fn find(&self, object: SocketObject<'gc>) -> Option<SocketHandle> {
+        self.sockets
+            .iter()
+            .find(|(_, socket)| socket.object.ptr_eq(object))
+            .map(|(handle, _)| handle)
+    }
+
+    /// Whether `object` has an open connection.
+    pub fn is_connected(&self, object: SocketObject<'gc>) -> bool {
+        self.find(object)
+            .map_or(false, |handle| self.sockets[handle].connected)
+    }
+
+    /// Start connecting `object` to `host` on `port`, closing any connection
+    /// that it already has.
+    ///
+    /// Returns `false` if the security sandbox doesn't allow the connection.
+    pub fn connect(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: SocketObject<'gc>,
+        host: &str,
+        port: u16,
+    ) -> bool {
+        Self::close(context, object);
+
+        if !context.security.allows_socket(host) {
+            log::warn!(
+                "Connection to {}:{} is not allowed by the security sandbox",
+                host,
+                port
+            );
+            return false;
+        }
+
+        let handle = context.socket_manager.sockets.insert(Socket {
+            object,
+            connected: false,
+            partial_message: Vec::new(),
+        });
+        context.socket.connect(handle, host, port);
+        true
+    }
+
+    /// Send data on `object`'s connection.
+    ///
+    /// Returns `false` if it isn't connected.
+    pub fn send(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: SocketObject<'gc>,
+        data: &[u8],
+    ) -> bool {
+        match context.socket_manager.find(object) {
+            Some(handle) if context.socket_manager.sockets[handle].connected => {
+                context.socket.send(handle, data);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Close `object`'s connection, if it has one.
+    ///
+    /// Closing a connection from script doesn't tell the script about it.
+    pub fn close(context: &mut UpdateContext<'_, 'gc, '_>, object: SocketObject<'gc>) {
+        if let Some(handle) = context.socket_manager.find(object) {
+            context.socket_manager.sockets.remove(handle);
+            context.socket.close(handle);
+        }
+    }
+
+    /// Deliver everything that happened on the player's connections since the
+    /// last frame.
+    pub fn tick(context: &mut UpdateContext<'_, 'gc, '_>) {
+        for event in context.socket.poll_events() {
+            let handle = match &event {
+                SocketEvent::Connected(handle)
+                | SocketEvent::Data(handle, _)
+                | SocketEvent::Closed(handle)
+                | SocketEvent::Error(handle, _) => *handle,
+            };
+            // Events can still arrive for connections that were just closed.
+            let socket = match context.socket_manager.sockets.get_mut(handle) {
+                Some(socket) => socket,
+                None => continue,
+            };
+            let object = socket.object;
+
+            let delivery = match event {
+                SocketEvent::Connected(_) => {
+                    socket.connected = true;
+                    Delivery::Connected
+                }
+                SocketEvent::Data(_, data) if object.is_xml() => {
+                    socket.partial_message.extend_from_slice(&data);
+                    let mut messages = Vec::new();
+                    while let Some(end) = socket.partial_message.iter().position(|b| *b == 0) {
+                        let message: Vec<u8> = socket.partial_message.drain(..=end).collect();
+                        messages.push(String::from_utf8_lossy(&message[..end]).into_owned());
+                    }
+                    Delivery::Messages(messages)
+                }
+                SocketEvent::Data(_, data) => Delivery::Data(data),
+                SocketEvent::Closed(_) => {
+                    context.socket_manager.sockets.remove(handle);
+                    Delivery::Closed
+                }
+                SocketEvent::Error(_, message) => {
+                    let was_connected = socket.connected;
+                    context.socket_manager.sockets.remove(handle);
+                    log::warn!("Socket error: {}", message);
+                    if was_connected {
+                        Delivery::Closed
+                    } else {
+                        Delivery::Failed(message)
+                    }
+                }
+            };
+
+            match object {
+                SocketObject::Avm1XmlSocket(object) => {
+                    Self::deliver_avm1(context, object, delivery)
+                }
+                SocketObject::Avm2Socket(object) | SocketObject::Avm2XmlSocket(object) => {
+                    Self::deliver_avm2(context, object, delivery)
+                }
+            }
+        }
+    }
+
+    /// Call the handlers of an AVM1 `XMLSocket`.
+    fn deliver_avm1(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Avm1Object<'gc>,
+        delivery: Delivery,
+    ) {
+        if !context.levels.contains_key(&0) {
+            return;
+        }
+
+        let mut activation = Activation::from_stub(
+            context.reborrow(),
+            ActivationIdentifier::root("[XMLSocket]"),
+        );
+        let calls: Vec<(&str, Vec<Avm1Value<'gc>>)> = match delivery {
+            Delivery::Connected => vec![("onConnect", vec![true.into()])],
+            Delivery::Failed(_) => vec![("onConnect", vec![false.into()])],
+            Delivery::Closed => vec![("onClose", vec![])],
+            Delivery::Messages(messages) => messages
+                .into_iter()
+                .map(|message| {
+                    let message = AvmString::new(activation.context.gc_context, message);
+                    ("onData", vec![message.into()])
+                })
+                .collect(),
+            Delivery::Data(_) => vec![],
+        };
+
+        for (name, args) in calls {
+            // Sockets without a handler for a callback silently ignore it.
+            match object.get(name, &mut activation) {
+                Ok(Avm1Value::Object(_)) => {}
+                _ => continue,
+            }
+            if let Err(e) = object.call_method(name, &args, &mut activation) {
+                root_error_handler(&mut activation, e);
+            }
+        }
+    }
+
+    /// Dispatch events to an AVM2 `Socket` or `XMLSocket`.
+    fn deliver_avm2(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        object: Avm2Object<'gc>,
+        delivery: Delivery,
+    ) {
+        let result = match delivery {
+            Delivery::Connected => {
+                Avm2::dispatch_event(context, Avm2Event::new("connect"), object).map(|_| ())
+            }
+            Delivery::Closed => {
+                Avm2::dispatch_event(context, Avm2Event::new("close"), object).map(|_| ())
+            }
+            Delivery::Failed(message) => Avm2::dispatch_io_error_event(context, object, &message),
+            Delivery::Data(data) => Avm2::receive_socket_data(context, object, &data),
+            Delivery::Messages(messages) => messages
+                .iter()
+                .try_for_each(|message| Avm2::dispatch_data_event(context, object, message)),
+        };
+
+        if let Err(e) = result {
+            log::error!(
+                "Encountered AVM2 error when dispatching socket event: {}",
+                e
+            );
+        }
+    }
+}
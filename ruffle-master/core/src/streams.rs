@@ -0,0 +1,916 @@
+//! Progressive playback of FLV files for `NetStream`.
+//!
+//! A `NetStream` demuxes an FLV file as it downloads, and plays it back in
+//! step with the player's frame rate: video frames are decoded through the
+//! `VideoBackend` as the playhead reaches them, and script data messages such
+//! as `onMetaData` and `onCuePoint` are delivered to the stream's script
+//! object. Playback starts with the first tags of the file, and waits for more
+//! of it whenever the playhead catches up with the download.
+//!
+//! The audio track is registered with the `AudioBackend` as a single sound,
+//! so it can only start once the whole file has downloaded; it starts from the
+//! playhead at that point.
+
+use crate::amf::ScriptValue;
+use crate::avm1::activation::{Activation, ActivationIdentifier};
+use crate::avm1::object::date_object::DateObject;
+use crate::avm1::property::Attribute;
+use crate::avm1::{
+    root_error_handler, AvmString, Object as Avm1Object, TObject, Value as Avm1Value,
+};
+use crate::backend::audio::{AudioBackend, SoundHandle, SoundInstanceHandle};
+use crate::backend::navigator::Chunk;
+use crate::backend::render::{BitmapInfo, RenderBackend};
+use crate::backend::video::{EncodedFrame, VideoBackend, VideoStreamHandle};
+use crate::context::UpdateContext;
+use crate::executor::TaskHandle;
+use crate::flv::{self, FlvTag, FlvTagData};
+use crate::loader::Error;
+use crate::net::{self, Request};
+use chrono::{TimeZone, Utc};
+use gc_arena::{Collect, CollectionContext};
+use generational_arena::{Arena, Index};
+use swf::{AudioCompression, SoundEvent, SoundFormat, SoundInfo, VideoCodec, VideoDeblocking};
+
+pub type StreamHandle = Index;
+
+/// All of the player's `NetStream`s.
+pub struct StreamManager<'gc> {
+    streams: Arena<NetStream<'gc>>,
+}
+
+unsafe impl<'gc> Collect for StreamManager<'gc> {
+    fn trace(&self, cc: CollectionContext) {
+        for (_, stream) in self.streams.iter() {
+            stream.avm1_object.trace(cc)
+        }
+    }
+}
+
+impl<'gc> Default for StreamManager<'gc> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'gc> StreamManager<'gc> {
+    pub fn new() -> Self {
+        Self {
+            streams: Arena::new(),
+        }
+    }
+
+    /// Create a new, idle stream.
+    pub fn create_stream(&mut self) -> StreamHandle {
+        self.streams.insert(NetStream::default())
+    }
+
+    pub fn get(&self, handle: StreamHandle) -> Option<&NetStream<'gc>> {
+        self.streams.get(handle)
+    }
+
+    /// Set the script object that receives a stream's status and script data
+    /// messages.
+    pub fn set_avm1_object(&mut self, handle: StreamHandle, object: Avm1Object<'gc>) {
+        if let Some(stream) = self.streams.get_mut(handle) {
+            stream.avm1_object = Some(object);
+        }
+    }
+
+    /// Start downloading and playing an FLV file.
+    pub fn play(context: &mut UpdateContext<'_, 'gc, '_>, handle: StreamHandle, url: String) {
+        Self::close(context, handle);
+
+        let chunks = net::fetch_chunked(context, Request::get(url));
+        let task = context.executor.next_handle();
+        context
+            .executor
+            .spawn_stream(chunks, move |context, chunk| {
+                StreamManager::receive_chunk(context, handle, task, chunk);
+                Ok(())
+            });
+
+        if let Some(stream) = context.stream_manager.streams.get_mut(handle) {
+            stream.load = Some(task);
+            stream.playing = true;
+        }
+    }
+
+    /// Stop playback and discard the stream's media.
+    pub fn close(context: &mut UpdateContext<'_, 'gc, '_>, handle: StreamHandle) {
+        let stream = match context.stream_manager.streams.get_mut(handle) {
+            Some(stream) => stream,
+            None => return,
+        };
+
+        let load = stream.load.take();
+        let sound_instance = stream.sound_instance.take();
+        let media = stream.media.take();
+        *stream = NetStream {
+            avm1_object: stream.avm1_object,
+            frames_shown: stream.frames_shown,
+            ..Default::default()
+        };
+
+        if let Some(load) = load {
+            context.executor.cancel(load);
+        }
+        if let Some(sound_instance) = sound_instance {
+            context.stop_sound(sound_instance);
+        }
+        if let Some(media) = media {
+            media.release(context.audio, context.video);
+        }
+    }
+
+    /// Pause or resume playback.
+    ///
+    /// If `paused` is `None`, the stream toggles between paused and playing.
+    pub fn pause(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        handle: StreamHandle,
+        paused: Option<bool>,
+    ) {
+        let stream = match context.stream_manager.streams.get_mut(handle) {
+            Some(stream) if stream.playing => stream,
+            _ => return,
+        };
+
+        let paused = paused.unwrap_or(!stream.paused);
+        if paused == stream.paused {
+            return;
+        }
+        stream.paused = paused;
+
+        if paused {
+            stream.status("NetStream.Pause.Notify", "status");
+            if let Some(sound_instance) = stream.sound_instance.take() {
+                context.stop_sound(sound_instance);
+            }
+        } else {
+            stream.status("NetStream.Unpause.Notify", "status");
+            Self::start_sound(context, handle);
+        }
+    }
+
+    /// Move the playhead to the keyframe at or before `time` seconds.
+    pub fn seek(context: &mut UpdateContext<'_, 'gc, '_>, handle: StreamHandle, time: f64) {
+        let stream = match context.stream_manager.streams.get_mut(handle) {
+            Some(stream) => stream,
+            None => return,
+        };
+        let media = match &stream.media {
+            Some(media) => media,
+            None => return,
+        };
+
+        let mut time = (time * 1000.0).max(0.0).min(media.duration());
+        if let Some(video) = &media.video {
+            time = video
+                .frames
+                .iter()
+                .filter(|frame| frame.is_keyframe && frame.time <= time)
+                .map(|frame| frame.time)
+                .last()
+                .unwrap_or(0.0);
+        }
+        stream.next_message = media
+            .messages
+            .iter()
+            .position(|message| message.time >= time)
+            .unwrap_or(media.messages.len());
+        stream.time = time;
+        stream.decoded_frame = None;
+        stream.status("NetStream.Seek.Notify", "status");
+        stream.decode_video(context.video, context.renderer);
+
+        if stream.playing && !stream.paused {
+            Self::start_sound(context, handle);
+        }
+    }
+
+    /// The most recently decoded video frame of a stream, and how many frames
+    /// the stream showed up to and including it.
+    pub fn current_frame(&self, handle: StreamHandle) -> Option<(BitmapInfo, u32)> {
+        let stream = self.streams.get(handle)?;
+        stream.frame.map(|frame| (frame, stream.frames_shown))
+    }
+
+    /// Advance every playing stream by one frame, and deliver any messages
+    /// that they have produced to script.
+    pub fn tick(context: &mut UpdateContext<'_, 'gc, '_>) {
+        let frame_duration = 1000.0 / *context.frame_rate;
+        let handles: Vec<_> = context
+            .stream_manager
+            .streams
+            .iter()
+            .map(|(handle, _)| handle)
+            .collect();
+
+        for &handle in &handles {
+            let stream = &mut context.stream_manager.streams[handle];
+            if !stream.playing || stream.paused || stream.media.is_none() {
+                continue;
+            }
+
+            if stream.buffering {
+                continue;
+            }
+
+            stream.time += frame_duration;
+            stream.queue_messages();
+            stream.decode_video(context.video, context.renderer);
+
+            let (duration, loaded_duration) = stream.media.as_ref().map_or((0.0, 0.0), |media| {
+                (media.duration(), media.loaded_duration)
+            });
+            if stream.load.is_some() {
+                // Wait for more of the file before playing past what has
+                // downloaded so far.
+                if stream.time >= loaded_duration {
+                    stream.time = loaded_duration;
+                    stream.buffering = true;
+                    stream.status("NetStream.Buffer.Empty", "status");
+                }
+            } else if stream.time >= duration {
+                stream.time = duration;
+                stream.playing = false;
+                stream.status("NetStream.Play.Stop", "status");
+                if let Some(sound_instance) = stream.sound_instance.take() {
+                    context.stop_sound(sound_instance);
+                }
+            }
+        }
+
+        for handle in handles {
+            Self::deliver_events(context, handle);
+        }
+    }
+
+    /// Demux the next chunk of a stream's download, or finish the download
+    /// if `chunk` is `None`.
+    fn receive_chunk(
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        handle: StreamHandle,
+        task: TaskHandle,
+        chunk: Option<Result<Chunk, Error>>,
+    ) {
+        // Ignore what's left of a download that has since been cancelled.
+        let stream = match context.stream_manager.streams.get_mut(handle) {
+            Some(stream) if stream.load == Some(task) => stream,
+            _ => return,
+        };
+
+        match chunk {
+            Some(Ok(chunk)) => {
+                stream.data.extend_from_slice(&chunk.data);
+                stream.bytes_loaded += chunk.data.len();
+                stream.bytes_total = chunk.total_len.unwrap_or(0).max(stream.bytes_loaded);
+            }
+            Some(Err(e)) => return Self::load_failed(context, handle, &e.to_string()),
+            None => {
+                stream.load = None;
+                stream.bytes_total = stream.bytes_loaded;
+            }
+        }
+        let finished = stream.load.is_none();
+
+        if stream.media.is_none() {
+            // Wait for the whole header before checking it.
+            if stream.data.len() < flv::HEADER_LENGTH && !finished {
+                return;
+            }
+            match flv::first_tag_position(&stream.data) {
+                Ok(first_tag) => {
+                    stream.next_tag = first_tag;
+                    stream.media = Some(Media::default());
+                    stream.status("NetStream.Play.Start", "status");
+                    stream.status("NetStream.Buffer.Full", "status");
+                }
+                Err(e) => {
+                    let e = format!("Invalid FLV file: {}", e);
+                    return Self::load_failed(context, handle, &e);
+                }
+            }
+        }
+
+        let media = stream.media.as_mut().unwrap();
+        let (tags, next_tag) = flv::parse_flv_tags(&stream.data, stream.next_tag);
+        let has_new_tags = !tags.is_empty();
+        media.add_tags(tags, context.video);
+        if finished {
+            media.finish(context.audio);
+        }
+
+        // Only the tags after `next_tag` are still needed.
+        let next_tag = next_tag.min(stream.data.len());
+        stream.data.drain(..next_tag);
+        stream.next_tag = 0;
+
+        if stream.buffering && (has_new_tags || finished) {
+            stream.buffering = false;
+            stream.status("NetStream.Buffer.Full", "status");
+        }
+        stream.queue_messages();
+        stream.decode_video(context.video, context.renderer);
+
+        if finished && stream.playing && !stream.paused {
+            Self::start_sound(context, handle);
+        }
+    }
+
+    /// Stop a stream whose download failed or isn't a valid FLV file.
+    fn load_failed(context: &mut UpdateContext<'_, 'gc, '_>, handle: StreamHandle, e: &str) {
+        log::warn!("NetStream failed to load: {}", e);
+        Self::close(context, handle);
+        if let Some(stream) = context.stream_manager.streams.get_mut(handle) {
+            stream.status("NetStream.Play.StreamNotFound", "error");
+        }
+    }
+
+    /// (Re)start a stream's audio track from its playhead.
+    fn start_sound(context: &mut UpdateContext<'_, 'gc, '_>, handle: StreamHandle) {
+        let stream = match context.stream_manager.streams.get_mut(handle) {
+            Some(stream) => stream,
+            None => return,
+        };
+        let sound = match stream.media.as_ref().and_then(|media| media.audio) {
+            Some(sound) => sound,
+            None => return,
+        };
+        let old_instance = stream.sound_instance.take();
+        // Sound positions are always measured in 44.1kHz samples.
+        let in_sample = (stream.time * 44.1) as u32;
+
+        if let Some(old_instance) = old_instance {
+            context.stop_sound(old_instance);
+        }
+        let sound_info = SoundInfo {
+            event: SoundEvent::Event,
+            in_sample: Some(in_sample).filter(|sample| *sample > 0),
+            out_sample: None,
+            num_loops: 1,
+            envelope: None,
+        };
+        let instance = context.start_sound(sound, &sound_info, None, None);
+
+        if let Some(stream) = context.stream_manager.streams.get_mut(handle) {
+            stream.sound_instance = instance;
+        }
+    }
+
+    /// Call the script handlers for a stream's queued messages.
+    fn deliver_events(context: &mut UpdateContext<'_, 'gc, '_>, handle: StreamHandle) {
+        let (object, events) = match context.stream_manager.streams.get_mut(handle) {
+            Some(stream) if !stream.events.is_empty() => {
+                (stream.avm1_object, std::mem::take(&mut stream.events))
+            }
+            _ => return,
+        };
+        let object = match object {
+            Some(object) => object,
+            None => return,
+        };
+        if !context.levels.contains_key(&0) {
+            return;
+        }
+
+        let mut activation = Activation::from_stub(
+            context.reborrow(),
+            ActivationIdentifier::root("[NetStream]"),
+        );
+        for event in events {
+            let (name, arg) = match event {
+                StreamEvent::Status { code, level } => (
+                    "onStatus".to_string(),
+                    status_object(&mut activation, code, level),
+                ),
+                StreamEvent::Script { name, value } => {
+                    let arg = script_value_to_avm1(&mut activation, &value);
+                    (name, arg)
+                }
+            };
+            // Streams without a handler for a message silently ignore it.
+            match object.get(&name, &mut activation) {
+                Ok(Avm1Value::Object(_)) => {}
+                _ => continue,
+            }
+            if let Err(e) = object.call_method(&name, &[arg], &mut activation) {
+                root_error_handler(&mut activation, e);
+            }
+        }
+    }
+}
+
+/// A single `NetStream`.
+#[derive(Default)]
+pub struct NetStream<'gc> {
+    /// The script object that receives this stream's messages.
+    avm1_object: Option<Avm1Object<'gc>>,
+
+    /// The download in progress, if any.
+    load: Option<TaskHandle>,
+
+    /// The demuxed contents of the file, once its header has downloaded.
+    media: Option<Media>,
+
+    /// The part of the file that has downloaded but hasn't been demuxed yet.
+    data: Vec<u8>,
+
+    /// The position in `data` of the next tag to demux.
+    next_tag: usize,
+
+    bytes_loaded: usize,
+    bytes_total: usize,
+
+    /// Whether playback caught up with the download, and is waiting for more
+    /// of the file.
+    buffering: bool,
+
+    /// The playhead, in milliseconds.
+    time: f64,
+
+    /// Whether `play` was called and the stream hasn't yet reached its end.
+    playing: bool,
+    paused: bool,
+
+    /// The most recently decoded video frame.
+    frame: Option<BitmapInfo>,
+
+    /// The index of the video frame that `frame` holds.
+    decoded_frame: Option<usize>,
+
+    /// How many times `frame` changed, which tells videos showing the stream
+    /// when to draw it again.
+    frames_shown: u32,
+
+    /// The index of the next script data message to deliver.
+    next_message: usize,
+
+    sound_instance: Option<SoundInstanceHandle>,
+
+    /// Messages waiting to be delivered to script.
+    events: Vec<StreamEvent>,
+}
+
+impl<'gc> NetStream<'gc> {
+    /// The playhead, in seconds.
+    pub fn time(&self) -> f64 {
+        self.time / 1000.0
+    }
+
+    pub fn bytes_loaded(&self) -> usize {
+        self.bytes_loaded
+    }
+
+    pub fn bytes_total(&self) -> usize {
+        self.bytes_total
+    }
+
+    fn status(&mut self, code: &'static str, level: &'static str) {
+        self.events.push(StreamEvent::Status { code, level });
+    }
+
+    /// Queue every script data message that the playhead has reached.
+    fn queue_messages(&mut self) {
+        let media = match &self.media {
+            Some(media) => media,
+            None => return,
+        };
+
+        while let Some(message) = media.messages.get(self.next_message) {
+            if message.time > self.time {
+                break;
+            }
+            self.events.push(StreamEvent::Script {
+                name: message.name.clone(),
+                value: message.value.clone(),
+            });
+            self.next_message += 1;
+        }
+    }
+
+    /// Decode video up to the frame at the playhead.
+    ///
+    /// Frames are decoded in order from the last decoded frame, or from the
+    /// closest keyframe if that is nearer.
+    fn decode_video(&mut self, video: &mut dyn VideoBackend, renderer: &mut dyn RenderBackend) {
+        let track = match self.media.as_ref().and_then(|media| media.video.as_ref()) {
+            Some(track) => track,
+            None => return,
+        };
+        let video_stream = match track.stream {
+            Some(video_stream) => video_stream,
+            None => return,
+        };
+        let target = match track
+            .frames
+            .iter()
+            .rposition(|frame| frame.time <= self.time)
+        {
+            Some(target) => target,
+            None => return,
+        };
+
+        let keyframe = track.frames[..=target]
+            .iter()
+            .rposition(|frame| frame.is_keyframe)
+            .unwrap_or(0);
+        let start = match self.decoded_frame {
+            Some(decoded) if decoded == target => return,
+            Some(decoded) if decoded < target => keyframe.max(decoded + 1),
+            _ => keyframe,
+        };
+
+        for (frame_id, frame) in track.frames.iter().enumerate().take(target + 1).skip(start) {
+            let encoded_frame = EncodedFrame {
+                codec: track.codec,
+                data: &frame.data,
+                frame_id: frame_id as u32,
+            };
+            match video.decode_video_stream_frame(video_stream, encoded_frame, renderer) {
+                Ok(bitmap) => {
+                    self.frame = Some(bitmap);
+                    self.frames_shown = self.frames_shown.wrapping_add(1);
+                }
+                Err(e) => log::warn!("Could not decode FLV video frame {}: {}", frame_id, e),
+            }
+        }
+        self.decoded_frame = Some(target);
+    }
+}
+
+/// A message for a stream's script object.
+enum StreamEvent {
+    /// An `onStatus` message.
+    Status {
+        code: &'static str,
+        level: &'static str,
+    },
+
+    /// A script data message from the file, such as `onMetaData`.
+    Script { name: String, value: ScriptValue },
+}
+
+/// The demuxed contents of an FLV file, as far as it has downloaded.
+#[derive(Default)]
+struct Media {
+    video: Option<VideoTrack>,
+
+    /// The format and data of the audio track, until the whole file has
+    /// downloaded and it's registered as a sound.
+    audio_data: Option<(SoundFormat, Vec<u8>)>,
+
+    /// The audio track, registered as a single sound.
+    audio: Option<SoundHandle>,
+
+    messages: Vec<ScriptMessage>,
+
+    /// The time of the latest tag that has downloaded, in milliseconds.
+    loaded_duration: f64,
+
+    /// The length of the file given by its metadata, in milliseconds.
+    metadata_duration: Option<f64>,
+}
+
+struct VideoTrack {
+    codec: VideoCodec,
+
+    /// The decoder for the track, unless the video backend can't decode it.
+    stream: Option<VideoStreamHandle>,
+
+    frames: Vec<VideoTrackFrame>,
+}
+
+struct VideoTrackFrame {
+    time: f64,
+    is_keyframe: bool,
+    data: Vec<u8>,
+}
+
+struct ScriptMessage {
+    time: f64,
+    name: String,
+    value: ScriptValue,
+}
+
+impl Media {
+    /// The length of the file, in milliseconds.
+    fn duration(&self) -> f64 {
+        let metadata_duration = self.metadata_duration.unwrap_or(0.0);
+        f64::max(self.loaded_duration, metadata_duration)
+    }
+
+    /// The display size of the video, as given by the file's metadata.
+    fn metadata_size(&self) -> Option<(u16, u16)> {
+        self.messages
+            .iter()
+            .filter(|message| message.name == "onMetaData")
+            .find_map(|message| {
+                let width = message.value.get("width")?.as_number()?;
+                let height = message.value.get("height")?.as_number()?;
+                Some((width as u16, height as u16))
+            })
+    }
+
+    /// Add the next tags of the file.
+    fn add_tags(&mut self, tags: Vec<FlvTag<'_>>, video_backend: &mut dyn VideoBackend) {
+        for tag in tags {
+            let time = f64::from(tag.timestamp);
+            self.loaded_duration = f64::max(self.loaded_duration, time);
+
+            match tag.data {
+                FlvTagData::Video {
+                    is_keyframe,
+                    codec_id,
+                    data,
+                } => {
+                    let codec = match codec_id {
+                        2 => VideoCodec::H263,
+                        3 => VideoCodec::ScreenVideo,
+                        4 => VideoCodec::Vp6,
+                        5 => VideoCodec::Vp6WithAlpha,
+                        6 => VideoCodec::ScreenVideoV2,
+                        _ => {
+                            log::warn!("Unsupported FLV video codec {}", codec_id);
+                            continue;
+                        }
+                    };
+                    if self.video.is_none() {
+                        // The metadata is usually written before the video.
+                        // Without it, decoded frames are shown uncropped.
+                        let size = self.metadata_size().unwrap_or((u16::MAX, u16::MAX));
+                        let stream = video_backend
+                            .register_video_stream(
+                                0,
+                                size,
+                                codec,
+                                VideoDeblocking::UseVideoPacketValue,
+                            )
+                            .map_err(|e| log::warn!("Could not play video in FLV: {}", e))
+                            .ok();
+                        self.video = Some(VideoTrack {
+                            codec,
+                            stream,
+                            frames: vec![],
+                        });
+                    }
+                    let track = self.video.as_mut().unwrap();
+                    track.frames.push(VideoTrackFrame {
+                        time,
+                        is_keyframe: is_keyframe || track.frames.is_empty(),
+                        data: data.to_vec(),
+                    });
+                }
+                FlvTagData::Audio {
+                    format,
+                    sample_rate,
+                    is_16_bit,
+                    is_stereo,
+                    data,
+                } => {
+                    let compression = match format {
+                        0 => AudioCompression::UncompressedUnknownEndian,
+                        2 | 14 => AudioCompression::Mp3,
+                        3 => AudioCompression::Uncompressed,
+                        4 => AudioCompression::Nellymoser16Khz,
+                        5 => AudioCompression::Nellymoser8Khz,
+                        6 => AudioCompression::Nellymoser,
+                        _ => {
+                            log::warn!("Unsupported FLV audio format {}", format);
+                            continue;
+                        }
+                    };
+                    let (_, audio_data) = self.audio_data.get_or_insert_with(|| {
+                        let format = SoundFormat {
+                            compression,
+                            sample_rate,
+                            is_stereo,
+                            is_16_bit,
+                        };
+                        // MP3 sounds start with a latency seek, which FLVs don't have.
+                        let data = if format.compression == AudioCompression::Mp3 {
+                            vec![0, 0]
+                        } else {
+                            vec![]
+                        };
+                        (format, data)
+                    });
+                    audio_data.extend_from_slice(data);
+                }
+                FlvTagData::Script { name, value } => {
+                    if name == "onMetaData" {
+                        self.metadata_duration = value
+                            .get("duration")
+                            .and_then(ScriptValue::as_number)
+                            .map(|duration| duration * 1000.0);
+                    }
+                    self.messages.push(ScriptMessage { time, name, value });
+                }
+                FlvTagData::Unknown(tag_type) => {
+                    log::warn!("Unknown FLV tag type {}", tag_type);
+                }
+            }
+        }
+    }
+
+    /// Register the audio track, once the whole file has downloaded.
+    fn finish(&mut self, audio_backend: &mut dyn AudioBackend) {
+        let duration = self.duration();
+        self.audio = self.audio_data.take().and_then(|(format, data)| {
+            let num_samples = sound_sample_count(&format, &data, duration);
+            audio_backend
+                .register_sound(&swf::Sound {
+                    id: 0,
+                    format,
+                    num_samples,
+                    data: &data,
+                })
+                .map_err(|e| log::warn!("Could not play audio in FLV: {}", e))
+                .ok()
+        });
+    }
+
+    /// Free the sound and video decoder that the file was registered with.
+    fn release(self, audio_backend: &mut dyn AudioBackend, video_backend: &mut dyn VideoBackend) {
+        if let Some(sound) = self.audio {
+            audio_backend.unregister_sound(sound);
+        }
+        if let Some(video_stream) = self.video.and_then(|track| track.stream) {
+            video_backend.close_video_stream(video_stream);
+        }
+    }
+}
+
+/// The number of sample frames in an audio track.
+///
+/// This is exact for uncompressed audio. Compressed tracks are estimated from
+/// the length of the file, and backends that can count them precisely do so.
+fn sound_sample_count(format: &SoundFormat, data: &[u8], duration: f64) -> u32 {
+    match format.compression {
+        AudioCompression::Uncompressed | AudioCompression::UncompressedUnknownEndian => {
+            let bytes_per_sample =
+                (if format.is_16_bit { 2 } else { 1 }) * (if format.is_stereo { 2 } else { 1 });
+            (data.len() / bytes_per_sample) as u32
+        }
+        _ => (duration * f64::from(format.sample_rate) / 1000.0) as u32,
+    }
+}
+
+/// Build the info object passed to `onStatus`.
+fn status_object<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    code: &str,
+    level: &str,
+) -> Avm1Value<'gc> {
+    let gc_context = activation.context.gc_context;
+    let info = Avm1Object::from(crate::avm1::ScriptObject::object(
+        gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    ));
+    info.define_value(
+        gc_context,
+        "code",
+        AvmString::new(gc_context, code.to_string()).into(),
+        Attribute::empty(),
+    );
+    info.define_value(
+        gc_context,
+        "level",
+        AvmString::new(gc_context, level.to_string()).into(),
+        Attribute::empty(),
+    );
+    info.into()
+}
+
+/// Convert a value from AMF0 data, such as FLV script data, into an AVM1
+/// value.
+pub(crate) fn script_value_to_avm1<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: &ScriptValue,
+) -> Avm1Value<'gc> {
+    let gc_context = activation.context.gc_context;
+    match value {
+        ScriptValue::Number(n) => (*n).into(),
+        ScriptValue::Boolean(b) => (*b).into(),
+        ScriptValue::String(s) => AvmString::new(gc_context, s.clone()).into(),
+        ScriptValue::Object(properties) => {
+            let object = Avm1Object::from(crate::avm1::ScriptObject::object(
+                gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            ));
+            for (name, value) in properties {
+                let value = script_value_to_avm1(activation, value);
+                object.define_value(gc_context, name, value, Attribute::empty());
+            }
+            object.into()
+        }
+        ScriptValue::StrictArray(values) => {
+            let array = crate::avm1::ScriptObject::array(
+                gc_context,
+                Some(activation.context.avm1.prototypes().array),
+            );
+            for (i, value) in values.iter().enumerate() {
+                let value = script_value_to_avm1(activation, value);
+                array.set_array_element(i, value, gc_context);
+            }
+            array.into()
+        }
+        ScriptValue::Date(time) => DateObject::with_date_time(
+            gc_context,
+            Some(activation.context.avm1.prototypes().date),
+            Some(Utc.timestamp_millis(*time as i64)),
+        )
+        .into(),
+        ScriptValue::Null => Avm1Value::Null,
+        ScriptValue::Undefined => Avm1Value::Undefined,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    fn video_tag(timestamp: u32, is_keyframe: bool) -> Vec<u8> {
+        let body = [if is_keyframe { 0x12 } else { 0x22 }, 0];
+        let mut tag = vec![9, 0, 0, body.len() as u8];
+        tag.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+        tag.extend_from_slice(&[0, 0, 0, 0]);
+        tag.extend_from_slice(&body);
+        tag.extend_from_slice(&(body.len() as u32 + 11).to_be_bytes());
+        tag
+    }
+
+    fn status_codes(stream: &NetStream<'_>) -> Vec<&'static str> {
+        stream
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                StreamEvent::Status { code, .. } => Some(*code),
+                StreamEvent::Script { .. } => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plays_while_downloading() {
+        let mut file = b"FLV\x01\x01\x00\x00\x00\x09\x00\x00\x00\x00".to_vec();
+        for i in 0..4 {
+            file.extend(video_tag(i * 100, i == 0));
+        }
+        // The header and the first two frames, and part of the third.
+        let split = 13 + 2 * 17 + 5;
+
+        with_avm(8, |activation, _root| {
+            let context = &mut activation.context;
+            let handle = context.stream_manager.create_stream();
+            StreamManager::play(context, handle, "video.flv".to_string());
+            let task = context.stream_manager.streams[handle].load.unwrap();
+            let chunk = |data: &[u8]| {
+                Some(Ok(Chunk {
+                    data: data.to_vec(),
+                    total_len: Some(file.len()),
+                }))
+            };
+
+            StreamManager::receive_chunk(context, handle, task, chunk(&file[..split]));
+            let stream = &context.stream_manager.streams[handle];
+            assert_eq!(stream.bytes_loaded(), split);
+            assert_eq!(stream.bytes_total(), file.len());
+            assert_eq!(
+                status_codes(stream),
+                vec!["NetStream.Play.Start", "NetStream.Buffer.Full"]
+            );
+            assert_eq!(stream.media.as_ref().unwrap().loaded_duration, 100.0);
+
+            // Playback waits at the last frame that has downloaded.
+            StreamManager::tick(context);
+            StreamManager::tick(context);
+            let stream = &context.stream_manager.streams[handle];
+            assert!(stream.buffering);
+            assert!(stream.playing);
+            assert_eq!(stream.time(), 0.1);
+            StreamManager::tick(context);
+            assert_eq!(context.stream_manager.streams[handle].time(), 0.1);
+
+            StreamManager::receive_chunk(context, handle, task, chunk(&file[split..]));
+            StreamManager::receive_chunk(context, handle, task, None);
+            let stream = &context.stream_manager.streams[handle];
+            assert!(!stream.buffering);
+            assert!(stream.load.is_none());
+            assert_eq!(stream.bytes_loaded(), file.len());
+            assert_eq!(status_codes(stream), vec!["NetStream.Buffer.Full"]);
+            assert_eq!(stream.media.as_ref().unwrap().duration(), 300.0);
+
+            // Chunks of a download that was replaced are ignored.
+            StreamManager::close(context, handle);
+            StreamManager::receive_chunk(context, handle, task, chunk(&file));
+            let stream = &context.stream_manager.streams[handle];
+            assert!(stream.media.is_none());
+            assert_eq!(stream.bytes_loaded(), 0);
+
+            Ok(())
+        });
+    }
+}
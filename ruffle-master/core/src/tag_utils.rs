@@ -1,4 +1,5 @@
 use crate::backend::navigator::url_from_relative_path;
+use crate::debugger::MovieProtection;
 use crate::property_map::PropertyMap;
 use gc_arena::Collect;
 use std::path::Path;
@@ -28,6 +29,9 @@ pub struct SwfMovie {
 
     /// The suggest encoding for this SWF.
     encoding: &'static swf::Encoding,
+
+    /// Whether this SWF may be imported or debugged.
+    protection: MovieProtection,
 }
 
 impl SwfMovie {
@@ -46,6 +50,7 @@ impl SwfMovie {
             url: None,
             parameters: PropertyMap::new(),
             encoding: swf::UTF_8,
+            protection: MovieProtection::default(),
         }
     }
 
@@ -61,6 +66,7 @@ impl SwfMovie {
             url: source.url.clone(),
             parameters: source.parameters.clone(),
             encoding: source.encoding,
+            protection: source.protection.clone(),
         }
     }
 
@@ -80,12 +86,17 @@ impl SwfMovie {
     pub fn from_data(swf_data: &[u8], url: Option<String>) -> Result<Self, Error> {
         let swf_buf = swf::read::decompress_swf(swf_data)?;
         let encoding = swf::SwfStr::encoding_for_version(swf_buf.header.version);
+        let protection = MovieProtection::from_tags(
+            &mut swf::read::Reader::new(&swf_buf.data, swf_buf.header.version),
+            encoding,
+        );
         Ok(Self {
             header: swf_buf.header,
             data: swf_buf.data,
             url,
             parameters: PropertyMap::new(),
             encoding,
+            protection,
         })
     }
 
@@ -123,6 +134,11 @@ impl SwfMovie {
         self.url.as_deref()
     }
 
+    /// Get whether this SWF may be imported or debugged.
+    pub fn protection(&self) -> &MovieProtection {
+        &self.protection
+    }
+
     pub fn parameters(&self) -> &PropertyMap<String> {
         &self.parameters
     }
@@ -0,0 +1,195 @@
+//! Compatibility statistics for embedders
+//!
+//! Embedders that want to know which Flash features their collections rely
+//! on can give the player a `TelemetrySink`, which is told about coarse
+//! events such as movies loading and scripts calling builtins that Ruffle
+//! doesn't implement yet. Nothing is recorded unless a sink is set, and the
+//! events never carry URLs, parameters, or other data from the movie itself,
+//! so that what the sink aggregates stays anonymous.
+
+use crate::tag_utils::SwfMovie;
+use std::collections::{BTreeSet, HashSet};
+use swf::TagCode;
+
+/// The ActionScript virtual machine that a movie or builtin belongs to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AvmVersion {
+    Avm1,
+    Avm2,
+}
+
+/// Something that the player noticed about the movies it runs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TelemetryEvent<'a> {
+    /// A root movie loaded.
+    MovieLoaded {
+        swf_version: u8,
+        avm_version: AvmVersion,
+
+        /// The code of every kind of tag in the movie, including those in
+        /// its sprites and those that Ruffle doesn't know, in ascending
+        /// order.
+        tag_codes: &'a [u16],
+    },
+
+    /// A root movie couldn't be fetched or parsed.
+    MovieLoadFailed,
+
+    /// A movie used a builtin that Ruffle doesn't implement yet, such as
+    /// `Sound.loadSound`. Each builtin is reported once per player.
+    Unimplemented {
+        avm_version: AvmVersion,
+        name: &'a str,
+    },
+}
+
+/// Receives compatibility statistics, supplied by the embedder.
+pub trait TelemetrySink {
+    fn record(&mut self, event: TelemetryEvent<'_>);
+}
+
+/// The player's telemetry sink, if the embedder has opted in.
+#[derive(Default)]
+pub struct Telemetry {
+    sink: Option<Box<dyn TelemetrySink>>,
+
+    /// The unimplemented builtins that were already reported.
+    reported_unimplemented: HashSet<(AvmVersion, String)>,
+}
+
+impl Telemetry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_sink(&mut self, sink: Option<Box<dyn TelemetrySink>>) {
+        self.sink = sink;
+        self.reported_unimplemented.clear();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sink.is_some()
+    }
+
+    /// Report that `movie` was loaded as the root movie.
+    pub fn movie_loaded(&mut self, movie: &SwfMovie, avm_version: AvmVersion) {
+        if let Some(sink) = &mut self.sink {
+            let tag_codes: Vec<u16> = tag_codes(movie.data(), movie.version())
+                .into_iter()
+                .collect();
+            sink.record(TelemetryEvent::MovieLoaded {
+                swf_version: movie.version(),
+                avm_version,
+                tag_codes: &tag_codes,
+            });
+        }
+    }
+
+    /// Report that the root movie couldn't be loaded.
+    pub fn movie_load_failed(&mut self) {
+        if let Some(sink) = &mut self.sink {
+            sink.record(TelemetryEvent::MovieLoadFailed);
+        }
+    }
+
+    /// Report the use of a builtin that Ruffle does not implement yet.
+    pub fn unimplemented(&mut self, avm_version: AvmVersion, name: &str) {
+        if let Some(sink) = &mut self.sink {
+            if self
+                .reported_unimplemented
+                .insert((avm_version, name.to_string()))
+            {
+                sink.record(TelemetryEvent::Unimplemented { avm_version, name });
+            }
+        }
+    }
+}
+
+/// The codes of the tags in a tag stream, and in the sprites defined in it.
+///
+/// A truncated stream is read as far as it goes.
+fn tag_codes(data: &[u8], version: u8) -> BTreeSet<u16> {
+    let mut codes = BTreeSet::new();
+    add_tag_codes(data, version, &mut codes);
+    codes
+}
+
+fn add_tag_codes(mut data: &[u8], version: u8, codes: &mut BTreeSet<u16>) {
+    loop {
+        let mut reader = swf::read::Reader::new(data, version);
+        let (tag_code, tag_len) = match reader.read_tag_code_and_length() {
+            Ok(tag) => tag,
+            Err(_) => return,
+        };
+        let rest = reader.get_ref();
+        if tag_len > rest.len() {
+            return;
+        }
+
+        codes.insert(tag_code);
+        if tag_code == TagCode::End as u16 {
+            return;
+        } else if tag_code == TagCode::DefineSprite as u16 && tag_len >= 4 {
+            // Skip the sprite's ID and frame count.
+            add_tag_codes(&rest[4..tag_len], version, codes);
+        }
+
+        data = &rest[tag_len..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct RecordingSink(Rc<RefCell<Vec<String>>>);
+
+    impl TelemetrySink for RecordingSink {
+        fn record(&mut self, event: TelemetryEvent<'_>) {
+            self.0.borrow_mut().push(format!("{:?}", event));
+        }
+    }
+
+    #[test]
+    fn tag_codes_include_sprites() {
+        let mut data = vec![];
+        // SetBackgroundColor
+        data.extend_from_slice(&[0x43, 0x02, 0xff, 0xff, 0xff]);
+        // DefineSprite 1 with 1 frame, containing ShowFrame and End
+        data.extend_from_slice(&[0xc8, 0x09, 0x01, 0x00, 0x01, 0x00, 0x40, 0x00, 0x00, 0x00]);
+        // A tag that Ruffle doesn't know, with code 1000
+        data.extend_from_slice(&[0x00, 0xfa]);
+        // ShowFrame and End, followed by data that is ignored
+        data.extend_from_slice(&[0x40, 0x00, 0x00, 0x00, 0x43, 0x02]);
+
+        assert_eq!(
+            tag_codes(&data, 8).into_iter().collect::<Vec<_>>(),
+            vec![0, 1, 9, 39, 1000]
+        );
+    }
+
+    #[test]
+    fn unimplemented_reported_once() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut telemetry = Telemetry::new();
+        telemetry.unimplemented(AvmVersion::Avm1, "Sound.loadSound");
+        assert!(events.borrow().is_empty());
+
+        telemetry.set_sink(Some(Box::new(RecordingSink(events.clone()))));
+        telemetry.unimplemented(AvmVersion::Avm1, "Sound.loadSound");
+        telemetry.unimplemented(AvmVersion::Avm1, "Sound.loadSound");
+        telemetry.unimplemented(AvmVersion::Avm2, "Sound.loadSound");
+        telemetry.movie_load_failed();
+
+        assert_eq!(
+            *events.borrow(),
+            vec![
+                "Unimplemented { avm_version: Avm1, name: \"Sound.loadSound\" }",
+                "Unimplemented { avm_version: Avm2, name: \"Sound.loadSound\" }",
+                "MovieLoadFailed",
+            ]
+        );
+    }
+}
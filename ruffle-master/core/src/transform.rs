@@ -47,7 +47,9 @@ impl TransformStack {
     pub fn push(&mut self, transform: &Transform) {
         let cur_transform = self.transform();
         let matrix = cur_transform.matrix * transform.matrix;
-        let color_transform = cur_transform.color_transform * transform.color_transform;
+        let color_transform = cur_transform
+            .color_transform
+            .concat(&transform.color_transform);
         self.0.push(Transform {
             matrix,
             color_transform,
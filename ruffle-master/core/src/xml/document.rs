@@ -51,6 +51,11 @@ pub struct XmlDocumentData<'gc> {
 }
 
 impl<'gc> XmlDocument<'gc> {
+    /// Check if two documents are the same document.
+    pub fn ptr_eq(a: XmlDocument<'gc>, b: XmlDocument<'gc>) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
     /// Construct a new, empty XML document.
     pub fn new(mc: MutationContext<'gc, '_>) -> Self {
         let document = Self(GcCell::allocate(
@@ -42,6 +42,9 @@ pub enum Error {
     #[error("Cannot insert child into itself")]
     CannotInsertIntoSelf,
 
+    #[error("Cannot insert a node into one of its own descendants")]
+    CannotInsertAncestor,
+
     #[error("Not an element")]
     NotAnElement,
 
@@ -1,7 +1,7 @@
 //! XML tests
 
 use crate::xml;
-use crate::xml::{XmlDocument, XmlName};
+use crate::xml::{XmlDocument, XmlName, XmlNode};
 use gc_arena::rootless_arena;
 
 /// Tests very basic parsing of a single-element document.
@@ -247,3 +247,117 @@ fn ignore_white() {
         assert!(root.next().is_none());
     })
 }
+
+/// Asserts that the parent, sibling, and document links of every node in a
+/// tree agree with the child lists of their parents.
+fn assert_tree_consistent(node: XmlNode<'_>) {
+    let children: Vec<_> = match node.children() {
+        Some(children) => children.collect(),
+        None => return,
+    };
+
+    for (i, child) in children.iter().enumerate() {
+        let parent = child.parent().unwrap().expect("Child should have a parent");
+        assert!(XmlNode::ptr_eq(parent, node), "Child's parent is wrong");
+        assert!(
+            XmlDocument::ptr_eq(child.document(), node.document()),
+            "Child belongs to a different document"
+        );
+
+        let prev = child.prev_sibling().unwrap();
+        match i.checked_sub(1).map(|p| children[p]) {
+            Some(expected) => assert!(XmlNode::ptr_eq(prev.unwrap(), expected)),
+            None => assert!(prev.is_none(), "First child has a previous sibling"),
+        }
+
+        let next = child.next_sibling().unwrap();
+        match children.get(i + 1) {
+            Some(expected) => assert!(XmlNode::ptr_eq(next.unwrap(), *expected)),
+            None => assert!(next.is_none(), "Last child has a next sibling"),
+        }
+
+        assert_tree_consistent(*child);
+    }
+}
+
+/// Tests that moving nodes around a tree keeps all links consistent.
+#[test]
+fn mutation_invariants() {
+    rootless_arena(|mc| {
+        let xml = XmlDocument::new(mc);
+        let mut root = xml.as_node();
+        root.replace_with_str(mc, "<a><b/><c/><d/></a><e><f/></e>", true, false)
+            .expect("Parsed document");
+
+        let mut a = root.get_child_by_index(0).unwrap();
+        let mut e = root.get_child_by_index(1).unwrap();
+        let b = a.get_child_by_index(0).unwrap();
+        let mut d = a.get_child_by_index(2).unwrap();
+        let f = e.get_child_by_index(0).unwrap();
+
+        // Move a node to the end of its own parent.
+        a.append_child(mc, b).expect("Moved b to the end of a");
+        assert_eq!(a.child_position(b), Some(2));
+        assert_eq!(a.children_len(), 3);
+        assert_tree_consistent(root);
+
+        // Move a node forward within its own parent.
+        let c = a.get_child_by_index(0).unwrap();
+        a.insert_child(mc, a.child_position(b).unwrap(), c)
+            .expect("Moved c before b");
+        assert_eq!(a.child_position(d), Some(0));
+        assert_eq!(a.child_position(c), Some(1));
+        assert_eq!(a.child_position(b), Some(2));
+        assert_tree_consistent(root);
+
+        // Move a node into another parent.
+        e.insert_child(mc, 0, d).expect("Moved d into e");
+        assert_eq!(a.children_len(), 2);
+        assert_eq!(e.child_position(d), Some(0));
+        assert_eq!(e.child_position(f), Some(1));
+        assert_tree_consistent(root);
+
+        // Remove a node entirely.
+        e.remove_child(mc, f).expect("Removed f");
+        assert!(f.parent().unwrap().is_none());
+        assert!(f.prev_sibling().unwrap().is_none());
+        assert!(f.next_sibling().unwrap().is_none());
+        assert_tree_consistent(root);
+
+        // A node cannot be moved into its own subtree.
+        assert!(d.append_child(mc, e).is_err());
+        assert!(a.append_child(mc, a).is_err());
+        assert_tree_consistent(root);
+
+        let result = root
+            .into_string(&mut |_| true)
+            .expect("Successful toString");
+        assert_eq!(result, "<a><c /><b /></a><e><d /></e>");
+    })
+}
+
+/// Tests that deep clones are independent, self-consistent trees.
+#[test]
+fn deep_clone() {
+    rootless_arena(|mc| {
+        let xml = XmlDocument::new(mc);
+        let mut root = xml.as_node();
+        root.replace_with_str(mc, "<a><b><c/></b>text</a>", true, false)
+            .expect("Parsed document");
+
+        let a = root.get_child_by_index(0).unwrap();
+        let clone = a.duplicate(mc, true);
+
+        assert!(clone.parent().unwrap().is_none());
+        assert!(!XmlDocument::ptr_eq(clone.document(), a.document()));
+        assert_tree_consistent(clone);
+
+        let b = clone.get_child_by_index(0).unwrap();
+        let c = b.get_child_by_index(0).unwrap();
+        assert!(XmlDocument::ptr_eq(c.document(), clone.document()));
+
+        let shallow = a.duplicate(mc, false);
+        assert_eq!(shallow.children_len(), 0);
+        assert_eq!(shallow.tag_name(), a.tag_name());
+    })
+}
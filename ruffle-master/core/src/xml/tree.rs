@@ -140,6 +140,11 @@ pub enum XmlNodeData<'gc> {
 }
 
 impl<'gc> XmlNode<'gc> {
+    /// Check if two nodes are the same node.
+    pub fn ptr_eq(a: XmlNode<'gc>, b: XmlNode<'gc>) -> bool {
+        GcCell::ptr_eq(a.0, b.0)
+    }
+
     /// Construct a new XML text node.
     pub fn new_text(
         mc: MutationContext<'gc, '_>,
@@ -476,6 +481,12 @@ impl<'gc> XmlNode<'gc> {
             _ => return Err(Error::CannotAdoptHere),
         };
 
+        if let Some(grandchildren) = child.children() {
+            for grandchild in grandchildren {
+                grandchild.set_document(mc, document);
+            }
+        }
+
         if child.is_doctype() {
             document.link_doctype(mc, child);
         }
@@ -487,6 +498,23 @@ impl<'gc> XmlNode<'gc> {
         Ok(())
     }
 
+    /// Move this node and all of its descendants into a new document.
+    fn set_document(self, mc: MutationContext<'gc, '_>, new_document: XmlDocument<'gc>) {
+        match &mut *self.0.write(mc) {
+            XmlNodeData::DocumentRoot { .. } => return,
+            XmlNodeData::Element { document, .. } => *document = new_document,
+            XmlNodeData::Text { document, .. } => *document = new_document,
+            XmlNodeData::Comment { document, .. } => *document = new_document,
+            XmlNodeData::DocType { document, .. } => *document = new_document,
+        };
+
+        if let Some(children) = self.children() {
+            for child in children {
+                child.set_document(mc, new_document);
+            }
+        }
+    }
+
     /// Get the parent, if this node has one.
     ///
     /// If the node cannot have a parent, then this function yields Err.
@@ -658,13 +686,32 @@ impl<'gc> XmlNode<'gc> {
     pub fn insert_child(
         &mut self,
         mc: MutationContext<'gc, '_>,
-        position: usize,
+        mut position: usize,
         child: XmlNode<'gc>,
     ) -> Result<(), Error> {
         if GcCell::ptr_eq(self.0, child.0) {
             return Err(Error::CannotInsertIntoSelf);
         }
 
+        if let Some(mut ancestors) = self.ancestors() {
+            if ancestors.any(|ancestor| GcCell::ptr_eq(ancestor.0, child.0)) {
+                return Err(Error::CannotInsertAncestor);
+            }
+        }
+
+        if !self.has_children() {
+            return Err(Error::NotAnElement);
+        }
+
+        // Moving a node within its current parent shifts the positions of
+        // the children that follow it.
+        if let Some(old_position) = self.child_position(child) {
+            self.remove_child(mc, child)?;
+            if old_position < position {
+                position -= 1;
+            }
+        }
+
         match &mut *self.0.write(mc) {
             XmlNodeData::Element {
                 ref mut children, ..
@@ -976,6 +1023,23 @@ impl<'gc> XmlNode<'gc> {
     /// cloned.
     pub fn duplicate(self, gc_context: MutationContext<'gc, '_>, deep: bool) -> XmlNode<'gc> {
         let mut document = self.document().duplicate(gc_context);
+        let clone = self.duplicate_into(gc_context, document, deep);
+
+        document.link_root_node(gc_context, clone);
+
+        clone
+    }
+
+    /// Create a duplicate copy of this node, belonging to the given document.
+    ///
+    /// If the `deep` flag is set true, then the entire node tree will be
+    /// cloned into the same document.
+    fn duplicate_into(
+        self,
+        gc_context: MutationContext<'gc, '_>,
+        document: XmlDocument<'gc>,
+        deep: bool,
+    ) -> XmlNode<'gc> {
         let mut clone = XmlNode(GcCell::allocate(
             gc_context,
             match &*self.0.read() {
@@ -1028,13 +1092,15 @@ impl<'gc> XmlNode<'gc> {
             },
         ));
 
-        document.link_root_node(gc_context, clone);
-
         if deep {
             if let Some(children) = self.children() {
                 for (position, child) in children.enumerate() {
                     clone
-                        .insert_child(gc_context, position, child.duplicate(gc_context, deep))
+                        .insert_child(
+                            gc_context,
+                            position,
+                            child.duplicate_into(gc_context, document, deep),
+                        )
                         .expect("If I can see my children then my clone should accept children");
                 }
             }
@@ -5,10 +5,14 @@
 use approx::assert_relative_eq;
 use ruffle_core::backend::{
     audio::NullAudioBackend,
+    clipboard::MemoryClipboardBackend,
+    gamepad::NullGamepadBackend,
+    local_connection::InProcessLocalConnectionBackend,
     locale::NullLocaleBackend,
     log::LogBackend,
     navigator::{NullExecutor, NullNavigatorBackend},
-    render::NullRenderer,
+    render::{BitmapFormat, NullRenderer, RenderBackend},
+    socket::NullSocketBackend,
     storage::{MemoryStorageBackend, StorageBackend},
     ui::NullUiBackend,
     video::NullVideoBackend,
@@ -17,7 +21,8 @@ use ruffle_core::context::UpdateContext;
 use ruffle_core::external::Value as ExternalValue;
 use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider};
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::Player;
+use ruffle_core::{Player, PlayerEvent};
+use ruffle_render_software::SoftwareRenderBackend;
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -71,6 +76,25 @@ macro_rules! swf_tests_approx {
     };
 }
 
+// This macro generates test cases for a given list of SWFs using `test_swf_image`.
+macro_rules! swf_tests_image {
+    ($($(#[$attr:meta])* ($name:ident, $path:expr, $num_frames:literal),)*) => {
+        $(
+        #[test]
+        $(#[$attr])*
+        fn $name() -> Result<(), Error> {
+            set_logger();
+            test_swf_image(
+                concat!("tests/swfs/", $path, "/test.swf"),
+                $num_frames,
+                concat!("tests/swfs/", $path, "/output.txt"),
+                concat!("tests/swfs/", $path, "/expected.png"),
+            )
+        }
+        )*
+    };
+}
+
 // List of SWFs to test.
 // Format: (test_name, test_folder, number_of_frames_to_run)
 // The test folder is a relative to core/tests/swfs
@@ -86,6 +110,7 @@ swf_tests! {
     (as2_bitxor, "avm1/bitxor", 1),
     (function_base_clip, "avm1/function_base_clip", 2),
     (call, "avm1/call", 2),
+    (call_goto_paths, "avm1/call_goto_paths", 1),
     (color, "avm1/color", 1),
     (clip_events, "avm1/clip_events", 4),
     (unload_clip_event, "avm1/unload_clip_event", 2),
@@ -334,11 +359,16 @@ swf_tests! {
     (bad_swf_tag_past_eof, "avm1/bad_swf_tag_past_eof", 1),
     (sound, "avm1/sound", 1),
     (action_to_integer, "avm1/action_to_integer", 1),
+    (trace_values, "avm1/trace_values", 1),
     (as3_hello_world, "avm2/hello_world", 1),
+    (as3_trace_values, "avm2/trace_values", 1),
+    (as3_sampler, "avm2/sampler", 1),
     (as3_function_call, "avm2/function_call", 1),
     (as3_function_call_via_call, "avm2/function_call_via_call", 1),
     (as3_constructor_call, "avm2/constructor_call", 1),
     (as3_class_methods, "avm2/class_methods", 1),
+    (as3_class_init_order, "avm2/class_init_order", 1),
+    (as3_class_init_cycle, "avm2/class_init_cycle", 1),
     (as3_es3_inheritance, "avm2/es3_inheritance", 1),
     (as3_es4_inheritance, "avm2/es4_inheritance", 1),
     (as3_stored_properties, "avm2/stored_properties", 1),
@@ -464,6 +494,7 @@ swf_tests! {
     (as3_movieclip_currentscene, "avm2/movieclip_currentscene", 5),
     (as3_movieclip_scenes, "avm2/movieclip_scenes", 5),
     (as3_movieclip_play, "avm2/movieclip_play", 5),
+    (as3_movieclip_isplaying, "avm2/movieclip_isplaying", 14),
     (as3_movieclip_constr, "avm2/movieclip_constr", 1),
     (as3_lazyinit, "avm2/lazyinit", 1),
     (as3_trace, "avm2/trace", 1),
@@ -510,6 +541,7 @@ swf_tests! {
     (as3_function_call_rest, "avm2/function_call_rest", 1),
     (as3_eventdispatcher_haseventlistener, "avm2/eventdispatcher_haseventlistener", 1),
     (as3_eventdispatcher_willtrigger, "avm2/eventdispatcher_willtrigger", 1),
+    (as3_eventdispatcher_weakreference, "avm2/eventdispatcher_weakreference", 1),
     (as3_movieclip_willtrigger, "avm2/movieclip_willtrigger", 3),
     (as3_eventdispatcher_dispatchevent, "avm2/eventdispatcher_dispatchevent", 1),
     (as3_eventdispatcher_dispatchevent_handlerorder, "avm2/eventdispatcher_dispatchevent_handlerorder", 1),
@@ -520,6 +552,8 @@ swf_tests! {
     (as3_movieclip_dispatchevent_cancel, "avm2/movieclip_dispatchevent_cancel", 1),
     (as3_movieclip_dispatchevent_target, "avm2/movieclip_dispatchevent_target", 1),
     (as3_movieclip_dispatchevent_selfadd, "avm2/movieclip_dispatchevent_selfadd", 1),
+    (as3_urlstream_progress, "avm2/urlstream_progress", 3),
+    (as3_symbolclass_assets, "avm2/symbolclass_assets", 1),
     (as3_string_constr, "avm2/string_constr", 1),
     (as3_string_length, "avm2/string_length", 1),
     (as3_string_char_at, "avm2/string_char_at", 1),
@@ -530,10 +564,29 @@ swf_tests! {
     (as3_movieclip_displayevents_timeline, "avm2/movieclip_displayevents_timeline", 5),
     (as3_movieclip_displayevents_looping, "avm2/movieclip_displayevents_looping", 5),
     (as3_movieclip_displayevents_dblhandler, "avm2/movieclip_displayevents_dblhandler", 4),
+    (as3_frame_phase_order, "avm2/frame_phase_order", 4),
+    (as3_frame_phase_stop, "avm2/frame_phase_stop", 5),
     (as3_regexp_constr, "avm2/regexp_constr", 1),
     (as3_regexp_test, "avm2/regexp_test", 1),
     (as3_regexp_exec, "avm2/regexp_exec", 1),
     (as3_point, "avm2/point", 1),
+    (as3_matrix, "avm2/matrix", 1),
+    (as3_rectangle, "avm2/rectangle", 1),
+    (as3_colortransform, "avm2/colortransform", 1),
+    (as3_transform, "avm2/transform", 1),
+    (as3_bitmapdata_pixels, "avm2/bitmapdata_pixels", 1),
+    (as3_bitmapdata_applyfilter, "avm2/bitmapdata_applyfilter", 1),
+    (as3_unimplemented_strict_mode, "avm2/unimplemented_strict_mode", 1),
+    (as3_mask, "avm2/mask", 3),
+    (as3_method_dispatch, "avm2/method_dispatch", 1),
+    (as3_closure_scope, "avm2/closure_scope", 1),
+    (as3_closure_loop_capture, "avm2/closure_loop_capture", 1),
+    (as3_trait_linking, "avm2/trait_linking", 6),
+    (as3_trait_override_resolution, "avm2/trait_override_resolution", 1),
+    (as3_interface_dispatch, "avm2/interface_dispatch", 1),
+    (as3_add_frame_script, "avm2/add_frame_script", 9),
+    (as3_graphics_data, "avm2/graphics_data", 1),
+    (as3_display_bounds, "avm2/display_bounds", 1),
     (as3_edittext_default_format, "avm2/edittext_default_format", 1),
     (as3_edittext_html_entity, "avm2/edittext_html_entity", 1),
     #[ignore] (as3_edittext_html_roundtrip, "avm2/edittext_html_roundtrip", 1),
@@ -576,6 +629,12 @@ swf_tests_approx! {
     (as3_edittext_font_size, "avm2/edittext_font_size", 1, epsilon = 0.1),
 }
 
+// These tests also compare the last frame, drawn by the software renderer, with "expected.png".
+swf_tests_image! {
+    (bitmap_fill_render, "avm1/bitmap_fill_render", 1),
+    (color_transform_nesting, "avm1/color_transform_nesting", 1),
+}
+
 #[test]
 fn external_interface_avm1() -> Result<(), Error> {
     set_logger();
@@ -630,6 +689,24 @@ fn external_interface_avm1() -> Result<(), Error> {
     )
 }
 
+#[test]
+fn external_interface_avm2() -> Result<(), Error> {
+    set_logger();
+    test_swf_with_hooks(
+        "tests/swfs/avm2/external_interface/test.swf",
+        1,
+        "tests/swfs/avm2/external_interface/output.txt",
+        |player| {
+            player
+                .lock()
+                .unwrap()
+                .add_external_interface(Box::new(ExternalInterfaceTestProvider::new()));
+            Ok(())
+        },
+        |_| Ok(()),
+    )
+}
+
 #[test]
 fn shared_object_avm1() -> Result<(), Error> {
     set_logger();
@@ -687,6 +764,44 @@ fn timeout_avm1() -> Result<(), Error> {
     )
 }
 
+#[test]
+fn button_sprite_hit_area_avm1() -> Result<(), Error> {
+    set_logger();
+    // The hit test state of the button is a clip, which is never shown, but
+    // must still have the shape of its first frame to be clicked.
+    test_swf_with_hooks(
+        "tests/swfs/avm1/button_sprite_hit_area/test.swf",
+        1,
+        "tests/swfs/avm1/button_sprite_hit_area/output.txt",
+        |_| Ok(()),
+        |player| {
+            let mut player = player.lock().unwrap();
+            player.set_viewport_dimensions(200, 200);
+            player.handle_event(PlayerEvent::MouseMove { x: 50.0, y: 50.0 });
+            player.handle_event(PlayerEvent::MouseDown { x: 50.0, y: 50.0 });
+            player.handle_event(PlayerEvent::MouseUp { x: 50.0, y: 50.0 });
+            player.handle_event(PlayerEvent::MouseMove { x: 150.0, y: 150.0 });
+            Ok(())
+        },
+    )
+}
+
+#[test]
+fn unimplemented_strict_mode_avm2() -> Result<(), Error> {
+    set_logger();
+    // In strict mode, calling an unimplemented builtin stops the script.
+    test_swf_with_hooks(
+        "tests/swfs/avm2/unimplemented_strict_mode/test.swf",
+        1,
+        "tests/swfs/avm2/unimplemented_strict_mode/output_strict.txt",
+        |player| {
+            player.lock().unwrap().set_strict_mode(true);
+            Ok(())
+        },
+        |_| Ok(()),
+    )
+}
+
 /// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
 /// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
 /// test failures to show nice diffs.
@@ -802,6 +917,91 @@ fn test_swf_approx(
     Ok(())
 }
 
+/// Loads an SWF and runs it through the Ruffle core for a number of frames,
+/// drawing it with the software renderer.
+/// Tests that the trace output matches the given expected output, and that the last frame
+/// matches the given image. If it doesn't, the frame is saved as `actual.png` next to the image.
+fn test_swf_image(
+    swf_path: &str,
+    num_frames: u32,
+    expected_output_path: &str,
+    expected_image_path: &str,
+) -> Result<(), Error> {
+    let movie = SwfMovie::from_path(swf_path)?;
+    let (width, height) = (movie.width(), movie.height());
+    let frame = Rc::new(RefCell::new(Vec::new()));
+    let captured_frame = frame.clone();
+
+    let mut expected_output = std::fs::read_to_string(expected_output_path)?.replace("\r\n", "\n");
+    if expected_output.ends_with('\n') {
+        expected_output = expected_output[0..expected_output.len() - "\n".len()].to_string();
+    }
+
+    let trace_log = run_swf_with_renderer(
+        swf_path,
+        num_frames,
+        Box::new(SoftwareRenderBackend::new(width, height)),
+        |player| {
+            player
+                .lock()
+                .unwrap()
+                .set_viewport_dimensions(width, height);
+            Ok(())
+        },
+        |player| {
+            let mut player = player.lock().unwrap();
+            player.render();
+            let renderer = player
+                .renderer_mut()
+                .downcast_mut::<SoftwareRenderBackend>()
+                .unwrap();
+            if let BitmapFormat::Rgba(rgba) = renderer.capture_frame().data {
+                *captured_frame.borrow_mut() = rgba;
+            }
+            Ok(())
+        },
+    )?;
+    assert_eq!(
+        trace_log, expected_output,
+        "ruffle output != flash player output"
+    );
+
+    let frame = frame.borrow();
+    let (expected_width, expected_height, expected_frame) = read_png(expected_image_path)?;
+    if (expected_width, expected_height) != (width, height) || *frame != expected_frame {
+        let actual_path = Path::new(expected_image_path).with_file_name("actual.png");
+        write_png(&actual_path, width, height, &frame)?;
+        panic!(
+            "ruffle frame != {}, see {}",
+            expected_image_path,
+            actual_path.to_string_lossy()
+        );
+    }
+
+    Ok(())
+}
+
+/// Read an 8-bit RGBA PNG, returning its width, height and pixels.
+fn read_png(path: &str) -> Result<(u32, u32, Vec<u8>), Error> {
+    let decoder = png::Decoder::new(std::fs::File::open(path)?);
+    let (info, mut reader) = decoder.read_info()?;
+    if info.color_type != png::ColorType::RGBA || info.bit_depth != png::BitDepth::Eight {
+        return Err(format!("{} is not an 8-bit RGBA image", path).into());
+    }
+
+    let mut data = vec![0; info.buffer_size()];
+    reader.next_frame(&mut data)?;
+    Ok((info.width, info.height, data))
+}
+
+fn write_png(path: &Path, width: u32, height: u32, rgba: &[u8]) -> Result<(), Error> {
+    let mut encoder = png::Encoder::new(std::fs::File::create(path)?, width, height);
+    encoder.set_color(png::ColorType::RGBA);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header()?.write_image_data(rgba)?;
+    Ok(())
+}
+
 /// Loads an SWF and runs it through the Ruffle core for a number of frames.
 /// Tests that the trace output matches the given expected output.
 fn run_swf(
@@ -809,6 +1009,24 @@ fn run_swf(
     num_frames: u32,
     before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
     before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+) -> Result<String, Error> {
+    run_swf_with_renderer(
+        swf_path,
+        num_frames,
+        Box::new(NullRenderer::new()),
+        before_start,
+        before_end,
+    )
+}
+
+/// Loads an SWF and runs it through the Ruffle core for a number of frames,
+/// with the given render backend.
+fn run_swf_with_renderer(
+    swf_path: &str,
+    num_frames: u32,
+    renderer: Box<dyn RenderBackend>,
+    before_start: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
+    before_end: impl FnOnce(Arc<Mutex<Player>>) -> Result<(), Error>,
 ) -> Result<String, Error> {
     let base_path = Path::new(swf_path).parent().unwrap();
     let (mut executor, channel) = NullExecutor::new();
@@ -817,7 +1035,7 @@ fn run_swf(
     let trace_output = Rc::new(RefCell::new(Vec::new()));
 
     let player = Player::new(
-        Box::new(NullRenderer),
+        renderer,
         Box::new(NullAudioBackend::new()),
         Box::new(NullNavigatorBackend::with_base_path(base_path, channel)),
         Box::new(MemoryStorageBackend::default()),
@@ -825,6 +1043,10 @@ fn run_swf(
         Box::new(NullVideoBackend::new()),
         Box::new(TestLogBackend::new(trace_output.clone())),
         Box::new(NullUiBackend::new()),
+        Box::new(MemoryClipboardBackend::new()),
+        Box::new(NullGamepadBackend::new()),
+        Box::new(NullSocketBackend::new()),
+        Box::new(InProcessLocalConnectionBackend::default()),
     )?;
     player.lock().unwrap().set_root_movie(Arc::new(movie));
     player
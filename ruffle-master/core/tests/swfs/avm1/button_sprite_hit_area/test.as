@@ -0,0 +1,9 @@
+// The button `btn` at 20, 20 has only a hit test state, which is a clip
+// holding a 100x100 square and a frame script that traces
+// "hit clip script". Its events trace their names.
+//
+// The test moves the mouse over the button at 50, 50, clicks it, and moves
+// the mouse away to 150, 150.
+
+trace("frame 1");
+stop();
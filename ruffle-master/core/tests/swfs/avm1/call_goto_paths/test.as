@@ -0,0 +1,50 @@
+// The actions of the first frame of the root timeline, which has these clips:
+//  - "clip", with a stop action on frame 1, a trace of "clip frame 2" on
+//    frame 2, and a trace of "clip frame 3" on frame 3, labelled "three".
+//  - "clip.inner", with a stop action on frame 1, and a trace of
+//    "inner frame 2" on frame 2, labelled "two".
+//  - "other", with a stop action on frame 1 and an empty frame 2.
+// The test SWF was assembled by hand, since the scene bias of the second goto
+// is only emitted for gotos to other scenes.
+
+// Paths from the root.
+call("/clip:2");
+call("/clip:three");
+call("_root.clip:2");
+
+// Paths from the target of a tellTarget block.
+tellTarget ("/clip") {
+	call("inner:2");
+	call("inner:two");
+}
+tellTarget ("/clip/inner") {
+	call("_parent:three");
+	call("2");
+}
+
+// Frames and labels that don't exist.
+call("/clip:9");
+call("/clip:missing");
+call("/missing:2");
+trace("done calling");
+
+// Gotos from the target of a tellTarget block.
+tellTarget ("/clip") {
+	gotoAndStop("inner:two");
+}
+trace("/clip/inner._currentframe: " + getProperty("/clip/inner", _currentframe));
+tellTarget ("/clip") {
+	// GotoFrame2 of frame 1, with a scene bias of 1.
+	gotoAndStop(1 + 1);
+}
+trace("/clip._currentframe: " + getProperty("/clip", _currentframe));
+tellTarget ("/other") {
+	gotoAndStop(70000);
+}
+trace("/other._currentframe: " + getProperty("/other", _currentframe));
+
+// The SWF pushes "left on the stack" before this block, and traces the top of
+// the stack after it. The frame is popped even though the target doesn't exist.
+tellTarget ("/missing") {
+	gotoAndStop(2);
+}
@@ -0,0 +1,36 @@
+package {
+	import flash.display.MovieClip;
+
+	// The timeline of this movie has three empty frames.
+	public dynamic class Test extends MovieClip {
+		var loops = 0;
+
+		public function Test() {
+			this.addFrameScript(0, this.frame1, 1, this.frame2, 2, this.frame3);
+			this.addFrameScript(1, this.frame2Replaced);
+			this.addFrameScript(2, null);
+			this.addFrameScript(70000, this.frame1);
+		}
+
+		function frame1() {
+			this.loops = this.loops + 1;
+			trace("frame 1 script, loop " + this.loops);
+			if (this.loops == 2) {
+				this.addFrameScript(2, this.frame3);
+			}
+		}
+
+		function frame2() {
+			trace("frame 2 script");
+		}
+
+		function frame2Replaced() {
+			trace("replaced frame 2 script");
+		}
+
+		function frame3() {
+			trace("frame 3 script");
+			this.addFrameScript(1, null);
+		}
+	}
+}
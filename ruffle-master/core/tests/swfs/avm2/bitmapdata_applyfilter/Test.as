@@ -0,0 +1,19 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.filters.BlurFilter;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var s = new BitmapData(7, 7, true, 0);
+			s.setPixel32(3, 3, 0xffffffff);
+			var d = new BitmapData(8, 8, true, 0x00000000);
+			d.applyFilter(s, new Rectangle(1, 1, 5, 5), new Point(3, 3), new BlurFilter(2, 2, 1));
+			trace(d.getPixel32(5, 5), d.getPixel32(4, 4), d.getPixel32(3, 3), d.getPixel32(2, 2));
+			s.applyFilter(s, new Rectangle(0, 0, 7, 7), new Point(0, 0), new BlurFilter(2, 2, 1));
+			trace(s.getPixel32(3, 3), s.getPixel32(2, 3), s.getPixel32(0, 0));
+		}
+	}
+}
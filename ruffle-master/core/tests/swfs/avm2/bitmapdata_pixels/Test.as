@@ -0,0 +1,43 @@
+package {
+	import flash.display.BitmapData;
+	import flash.display.MovieClip;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			var b:BitmapData = new BitmapData(4, 4, false, 0x112233);
+			trace(b.width, b.height, b.transparent);
+			trace(b.getPixel(0, 0), b.getPixel32(0, 0));
+			b.setPixel(1, 1, 0xabcdef);
+			trace(b.getPixel(1, 1), b.getPixel(1, 2));
+			trace(b.getPixel(-1, 0), b.getPixel(4, 0));
+			b.setPixel(10, 10, 0xffffff);
+
+			b.fillRect(new Rectangle(2, 2, 10, 10), 0x00ff00);
+			trace(b.getPixel(1, 1), b.getPixel(2, 2), b.getPixel(3, 3));
+			b.fillRect(new Rectangle(-5, -5, 6, 6), 0x0000ff);
+			trace(b.getPixel(0, 0), b.getPixel(1, 0));
+			b.fillRect(new Rectangle(1, 1, 2147483647, 2147483647), 0x808080);
+			trace(b.getPixel(0, 0), b.getPixel(3, 3));
+
+			var t:BitmapData = new BitmapData(2, 2, true, 0x80ff0000);
+			trace(t.getPixel32(0, 0), t.getPixel(0, 0));
+			t.setPixel32(1, 1, 0x00000000);
+			trace(t.getPixel32(1, 1));
+
+			var d:BitmapData = new BitmapData(4, 4, false, 0);
+			var s:BitmapData = new BitmapData(2, 2, false, 0x445566);
+			s.setPixel(1, 1, 0x778899);
+			d.copyPixels(s, new Rectangle(0, 0, 2, 2), new Point(1, 1));
+			trace(d.getPixel(0, 0), d.getPixel(1, 1), d.getPixel(2, 2), d.getPixel(3, 3));
+			d.copyPixels(s, new Rectangle(-1, -1, 3, 3), new Point(0, 0));
+			trace(d.getPixel(0, 0), d.getPixel(1, 1), d.getPixel(2, 2));
+			d.copyPixels(s, new Rectangle(0, 0, 2147483647, 2147483647), new Point(3, 0));
+			trace(d.getPixel(3, 0), d.getPixel(3, 1));
+			d.copyPixels(s, new Rectangle(0, 0, 2, 2), new Point(2147483647, 0));
+			d.copyPixels(s, new Rectangle(0, 0, 2, 2), new Point(-2147483647, 0));
+			trace(d.getPixel(3, 0));
+		}
+	}
+}
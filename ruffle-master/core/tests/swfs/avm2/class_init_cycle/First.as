@@ -0,0 +1,13 @@
+package {
+	public class First {
+		public static var label = "first";
+
+		{
+			trace("First class init");
+			trace("Second.label is " + Second.label);
+			trace("First class init done");
+		}
+	}
+}
+
+trace("First script initializer");
@@ -0,0 +1,15 @@
+package {
+	public class Second {
+		public static var label = "second";
+
+		{
+			trace("Second class init");
+			// `First` is still being initialized, so it hasn't been stored
+			// yet.
+			trace("First is " + First);
+			trace("Second class init done");
+		}
+	}
+}
+
+trace("Second script initializer");
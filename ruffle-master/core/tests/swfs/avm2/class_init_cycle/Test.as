@@ -0,0 +1,11 @@
+package {
+	import flash.display.MovieClip;
+
+	public dynamic class Test extends MovieClip {
+	}
+}
+
+trace("Script initializer");
+trace("First.label is " + First.label);
+trace("Second.label is " + Second.label);
+trace("First is " + First);
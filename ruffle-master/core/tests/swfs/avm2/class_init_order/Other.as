@@ -0,0 +1,11 @@
+package {
+	public class Other {
+		public static var value = "other";
+
+		{
+			trace("Other class init");
+		}
+	}
+}
+
+trace("Other script initializer");
@@ -0,0 +1,37 @@
+package {
+	import flash.display.MovieClip;
+
+	public dynamic class Test extends MovieClip {
+	}
+}
+
+class Base {
+	public static var count = 0;
+
+	{
+		trace("Base class init");
+		trace("bump() is " + bump());
+	}
+
+	public static function bump() {
+		count = count + 1;
+		return count;
+	}
+}
+
+class Sub extends Base {
+	{
+		trace("Sub class init");
+		trace("Base.count is " + Base.count);
+	}
+
+	public static function hello() {
+		trace("Sub.hello()");
+	}
+}
+
+trace("Script initializer");
+Sub.hello();
+trace("Base.bump() is " + Base.bump());
+Other.value = "changed";
+trace("Other.value is " + Other.value);
@@ -0,0 +1,37 @@
+package {
+	import flash.display.MovieClip;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			// Each call to `makeCounter` gets an activation of its own, which
+			// the closure it returns keeps alive.
+			var makeCounter = function(start) {
+				var n = start;
+				return function() {
+					n = n + 1;
+					return n;
+				};
+			};
+
+			var counters = [];
+			for (var i = 0; i < 3; i++) {
+				counters[i] = makeCounter(i * 10);
+			}
+
+			for (i = 0; i < 3; i++) {
+				trace(counters[i](), counters[i]());
+			}
+			trace(counters[0]());
+
+			// A closure keeps the scope chain it was created with, even once
+			// its creator has pushed other scopes.
+			var f;
+			with ({v: "first"}) {
+				f = function() { return v; };
+			}
+			with ({v: "second"}) {
+				trace(f(), v);
+			}
+		}
+	}
+}
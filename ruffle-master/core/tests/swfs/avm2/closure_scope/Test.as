@@ -0,0 +1,24 @@
+package {
+	import flash.display.MovieClip;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			// `fns` and `i` live in the activation object, which the closures
+			// share, while each `v` is captured from its own `with` scope.
+			var fns:Object = null;
+			for (var i:int = 0; i < 3; i++) {
+				with ({v: i}) {
+					fns = {
+						prev: fns,
+						v: function() { return v; },
+						i: function() { return i; }
+					};
+				}
+			}
+
+			for (; fns !== null; fns = fns.prev) {
+				trace(fns.v(), fns.i());
+			}
+		}
+	}
+}
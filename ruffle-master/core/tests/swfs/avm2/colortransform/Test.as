@@ -0,0 +1,25 @@
+package {
+	import flash.display.MovieClip;
+	import flash.geom.ColorTransform;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			var c:ColorTransform = new ColorTransform();
+			trace(c);
+			c = new ColorTransform(0.5, 0.25, 2, 1, 10, -20, 30, 40);
+			trace(c);
+			trace(c.redMultiplier, c.greenMultiplier, c.blueMultiplier, c.alphaMultiplier);
+			trace(c.redOffset, c.greenOffset, c.blueOffset, c.alphaOffset);
+			trace(new ColorTransform(1, 1, 1, 1, 10, 20, 30, 0).color);
+
+			// Setting the color replaces the color channels but keeps alpha.
+			c.color = 0x123456;
+			trace(c);
+			trace(c.color == 0x123456);
+
+			var a:ColorTransform = new ColorTransform(2, 1, 1, 0.5, 10, 0, 0, 0);
+			a.concat(new ColorTransform(0.5, 1, 1, 1, 4, 0, 0, 100));
+			trace(a);
+		}
+	}
+}
@@ -0,0 +1,38 @@
+package {
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.geom.Point;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			var child:Shape = new Shape();
+			child.x = 100;
+			child.y = 50;
+			child.scaleX = 2;
+			child.graphics.lineStyle(4, 0x0000ff);
+			child.graphics.beginFill(0xff0000);
+			child.graphics.drawRect(0, 0, 20, 10);
+			child.graphics.endFill();
+			this.addChild(child);
+
+			trace("// child.getBounds(child)");
+			trace(child.getBounds(child));
+			trace("// child.getRect(child)");
+			trace(child.getRect(child));
+			trace("// child.getBounds(this)");
+			trace(child.getBounds(this));
+			trace("// child.getRect(this)");
+			trace(child.getRect(this));
+			trace("// this.getBounds(child)");
+			trace(this.getBounds(child));
+			trace("// this.getRect(child)");
+			trace(this.getRect(child));
+			trace("// child.localToGlobal(new Point(5, 5))");
+			trace(child.localToGlobal(new Point(5, 5)));
+			trace("// child.globalToLocal(new Point(100, 50))");
+			trace(child.globalToLocal(new Point(100, 50)));
+			trace("// child.globalToLocal(child.localToGlobal(new Point(-3, 7)))");
+			trace(child.globalToLocal(child.localToGlobal(new Point(-3, 7))));
+		}
+	}
+}
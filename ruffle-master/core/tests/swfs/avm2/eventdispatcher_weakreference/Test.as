@@ -0,0 +1,25 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.events.EventDispatcher;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			var d = new EventDispatcher();
+			d.addEventListener("ping", this.onPing, false, 0, true);
+			trace(d.hasEventListener("ping"));
+			d.dispatchEvent(new Event("ping"));
+			d.addEventListener("ping", this.onPing, false, 0, false);
+			d.dispatchEvent(new Event("ping"));
+			d.removeEventListener("ping", this.onPing);
+			trace(d.hasEventListener("ping"));
+			d.dispatchEvent(new Event("ping"));
+			this.addEventListener("pong", this.onPing, false, 0, true);
+			this.dispatchEvent(new Event("pong"));
+		}
+
+		public function onPing(e) {
+			trace("onPing " + e.type);
+		}
+	}
+}
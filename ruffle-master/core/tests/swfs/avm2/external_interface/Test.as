@@ -0,0 +1,32 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+	import flash.external.ExternalInterface;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+
+		public function onEnterFrame(e:Event):void {
+			this.removeEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+
+			trace(ExternalInterface.available);
+			trace(ExternalInterface.call("ping"));
+
+			// Self-referencing values become `null` rather than recursing forever.
+			var a:Array = [1, "two"];
+			a[2] = a;
+			trace(ExternalInterface.call("trace", a));
+
+			var o:Object = {name: "root"};
+			o.self = o;
+			o.child = {parent: o, value: 5};
+			trace(ExternalInterface.call("trace", o));
+
+			// Shared values that don't form a cycle are converted each time.
+			var shared:Object = {x: 1};
+			trace(ExternalInterface.call("trace", [shared, shared]));
+		}
+	}
+}
@@ -0,0 +1,54 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+
+	public dynamic class Test extends MovieClip {
+		var ticks = 0;
+
+		public function Test() {
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+			this.addEventListener(Event.FRAME_CONSTRUCTED, this.onFrameConstructed);
+			this.addEventListener(Event.EXIT_FRAME, this.onExitFrame);
+			this.addEventListener(Event.RENDER, this.onRender);
+			this.addFrameScript(0, this.frame1, 1, this.frame2);
+		}
+
+		function onEnterFrame(e) {
+			this.ticks = this.ticks + 1;
+			if (this.ticks > 1) {
+				trace("tick " + this.ticks + ": enterFrame");
+			}
+			if (this.ticks == 2) {
+				this.stage.invalidate();
+			}
+		}
+
+		function onFrameConstructed(e) {
+			if (this.ticks > 1) {
+				trace("tick " + this.ticks + ": frameConstructed");
+			}
+		}
+
+		function onExitFrame(e) {
+			if (this.ticks > 1) {
+				trace("tick " + this.ticks + ": exitFrame");
+			}
+		}
+
+		function onRender(e) {
+			trace("tick " + this.ticks + ": render");
+		}
+
+		function frame1() {
+			if (this.ticks > 1) {
+				trace("tick " + this.ticks + ": frame script");
+			}
+		}
+
+		function frame2() {
+			if (this.ticks > 1) {
+				trace("tick " + this.ticks + ": frame script");
+			}
+		}
+	}
+}
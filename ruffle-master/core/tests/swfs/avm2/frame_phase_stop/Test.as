@@ -0,0 +1,47 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+
+	public dynamic class Test extends MovieClip {
+		var ticks = 0;
+
+		public function Test() {
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+			this.addEventListener(Event.FRAME_CONSTRUCTED, this.onFrameConstructed);
+			this.addEventListener(Event.EXIT_FRAME, this.onExitFrame);
+			this.addFrameScript(0, this.frame1, 1, this.frame2, 2, this.frame3);
+		}
+
+		function onEnterFrame(e) {
+			this.ticks = this.ticks + 1;
+			if (this.ticks > 1) {
+				trace("tick " + this.ticks + ": enterFrame, frame " + this.currentFrame);
+			}
+		}
+
+		function onFrameConstructed(e) {
+			if (this.ticks > 1) {
+				trace("tick " + this.ticks + ": frameConstructed, frame " + this.currentFrame);
+			}
+		}
+
+		function onExitFrame(e) {
+			if (this.ticks > 1) {
+				trace("tick " + this.ticks + ": exitFrame, frame " + this.currentFrame);
+			}
+		}
+
+		function frame1() {
+			trace("frame 1 script");
+		}
+
+		function frame2() {
+			trace("frame 2 script");
+		}
+
+		function frame3() {
+			trace("frame 3 script");
+			this.stop();
+		}
+	}
+}
@@ -0,0 +1,57 @@
+package {
+	import flash.display.IGraphicsData;
+	import flash.display.MovieClip;
+	import flash.display.Shape;
+	import flash.utils.getQualifiedClassName;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			this.graphics.beginFill(0xff0000, 0.5);
+			this.graphics.drawRect(0, 0, 10, 10);
+			this.graphics.endFill();
+
+			var child:Shape = new Shape();
+			child.x = 10;
+			child.y = 20;
+			child.graphics.lineStyle(2, 0x00ff00);
+			child.graphics.moveTo(0, 0);
+			child.graphics.lineTo(5, 5);
+			this.addChild(child);
+
+			trace("// this.graphics.readGraphicsData()");
+			this.describe(this.graphics.readGraphicsData());
+			trace("// this.graphics.readGraphicsData(false)");
+			this.describe(this.graphics.readGraphicsData(false));
+			trace("// child.graphics.readGraphicsData()");
+			this.describe(child.graphics.readGraphicsData());
+
+			var copy:Shape = new Shape();
+			copy.graphics.drawGraphicsData(this.graphics.readGraphicsData());
+			trace("// copy.graphics.readGraphicsData()");
+			this.describe(copy.graphics.readGraphicsData());
+		}
+
+		function describe(data:Vector.<IGraphicsData>) {
+			for (var i = 0; i < data.length; i++) {
+				var item = data[i];
+				var name = getQualifiedClassName(item);
+				if (name == "flash.display::GraphicsSolidFill") {
+					trace(name, item.color, item.alpha);
+				} else if (name == "flash.display::GraphicsStroke") {
+					trace(name, item.thickness, this.fillColor(item.fill));
+				} else if (name == "flash.display::GraphicsPath") {
+					trace(name, item.commands, item.data, item.winding);
+				} else {
+					trace(name);
+				}
+			}
+		}
+
+		function fillColor(fill) {
+			if (fill == null) {
+				return fill;
+			}
+			return fill.color;
+		}
+	}
+}
@@ -0,0 +1,52 @@
+package {
+	import flash.display.MovieClip;
+
+	// Calls through an interface-typed reference use the name the interface
+	// declared the method with, in the interface's own namespace, while the
+	// classes implementing it declare the method as public.
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			var g:Greeter = new Greeter();
+			var p:Polite = new Polite();
+			var i:Inherited = new Inherited();
+			trace(IGreeter(g).greet(), IGreeter(g).name);
+			trace(IGreeter(p).greet(), IGreeter(p).name);
+			trace(IPolite(p).thank());
+			trace(IGreeter(i).greet(), IGreeter(i).name);
+			trace(g.greet(), p.greet(), p.thank(), i.greet());
+			trace(g is IGreeter, g is IPolite, p is IPolite, i is IGreeter);
+		}
+	}
+}
+
+interface IGreeter {
+	function greet();
+	function get name();
+}
+
+interface IPolite extends IGreeter {
+	function thank();
+}
+
+class Greeter implements IGreeter {
+	public function greet() {
+		return "Greeter.greet";
+	}
+
+	public function get name() {
+		return "greeter";
+	}
+}
+
+class Polite extends Greeter implements IPolite {
+	public override function greet() {
+		return "Polite.greet";
+	}
+
+	public function thank() {
+		return "Polite.thank";
+	}
+}
+
+class Inherited extends Greeter {
+}
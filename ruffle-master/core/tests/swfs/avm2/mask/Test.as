@@ -0,0 +1,37 @@
+package {
+	import flash.display.DisplayObject;
+	import flash.display.MovieClip;
+	import flash.events.Event;
+
+	// The stage holds, on its second frame:
+	//  * `a`, a 100x100 square at 0, 0
+	//  * `m`, a 50x50 square at 0, 0
+	//  * `clip`, a 50x50 square at 200, 0, clipping up to depth 5
+	//  * `b`, a 100x100 square at 200, 0, clipped by `clip`
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+
+		public function onEnterFrame(e:Event):void {
+			if (this.numChildren == 0) {
+				return;
+			}
+			this.removeEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+
+			var a:DisplayObject = this.getChildByName("a");
+			var m:DisplayObject = this.getChildByName("m");
+			trace(a.mask, m.mask);
+			trace(this.hitTestPoint(75, 75, true), this.hitTestPoint(25, 25, true));
+
+			a.mask = m;
+			trace(a.mask == m, m.mask);
+			trace(this.hitTestPoint(75, 75, true), this.hitTestPoint(25, 25, true));
+			trace(this.hitTestPoint(225, 25, true), this.hitTestPoint(275, 75, true));
+
+			a.mask = null;
+			trace(a.mask);
+			trace(this.hitTestPoint(75, 75, true), this.hitTestPoint(25, 25, true));
+		}
+	}
+}
@@ -0,0 +1,50 @@
+package {
+	import flash.display.MovieClip;
+	import flash.geom.Matrix;
+	import flash.geom.Point;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			var m:Matrix = new Matrix();
+			trace(m);
+			m.translate(10, 20);
+			trace(m);
+			m.scale(2, 4);
+			trace(m);
+
+			var i:Matrix = m.clone();
+			i.invert();
+			trace(i);
+
+			trace(m.transformPoint(new Point(1, 1)));
+			trace(m.deltaTransformPoint(new Point(1, 1)));
+
+			var a:Matrix = new Matrix(1, 0, 0, 1, 5, 0);
+			a.concat(new Matrix(2, 0, 0, 2, 0, 0));
+			trace(a);
+
+			var r:Matrix = new Matrix();
+			r.rotate(Math.PI / 2);
+			trace(Math.round(r.a), Math.round(r.b), Math.round(r.c), Math.round(r.d));
+
+			var b:Matrix = new Matrix();
+			b.createBox(2, 3, 0, 10, 20);
+			trace(b);
+
+			var g:Matrix = new Matrix();
+			g.createGradientBox(100, 200, 0, 10, 20);
+			trace(g);
+
+			var c:Matrix = new Matrix();
+			c.setTo(1, 2, 3, 4, 5, 6);
+			trace(c);
+
+			var d:Matrix = new Matrix();
+			d.copyFrom(c);
+			trace(d);
+			d.identity();
+			trace(d);
+			trace(c.a, c.b, c.c, c.d, c.tx, c.ty);
+		}
+	}
+}
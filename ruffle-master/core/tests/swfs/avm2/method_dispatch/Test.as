@@ -0,0 +1,48 @@
+package {
+	import flash.display.MovieClip;
+
+	// The calls below can't be written in ActionScript; the test SWF was
+	// assembled by hand. `callmethod(o, id)` is the `callmethod` instruction
+	// calling the method of `o` with dispatch id `id`, and `callstatic(o, m)`
+	// is the `callstatic` instruction calling the ABC method `m` on `o`.
+	//
+	// Dispatch ids: greet = 1, describe = 2, huge = 1000000000.
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			var b:Base = new Base();
+			var d:Derived = new Derived();
+			var l:Leaf = new Leaf();
+			trace(callmethod(b, 1), callmethod(b, 2));
+			trace(callmethod(d, 1), callmethod(d, 2));
+			trace(callmethod(l, 1));
+			trace(callmethod(b, 1000000000), callmethod(l, 1000000000));
+			trace(callstatic(d, Base.greet));
+			trace(callstatic(d, Derived.greet));
+			trace(callstatic(l, Derived.greet));
+			trace(callstatic(l, Base.describe));
+		}
+	}
+}
+
+class Base {
+	public function greet() {
+		return "base greet";
+	}
+
+	public function describe() {
+		return "base describe";
+	}
+
+	public function huge() {
+		return "base huge";
+	}
+}
+
+class Derived extends Base {
+	public override function greet() {
+		return "derived greet, " + super.greet();
+	}
+}
+
+class Leaf extends Derived {
+}
@@ -0,0 +1,57 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+
+	public dynamic class Test extends MovieClip {
+		var ticks = 0;
+
+		public function Test() {
+			trace("constructed: " + this.isPlaying);
+
+			var single = new MovieClip();
+			single.play();
+			trace("single-frame play(): " + single.isPlaying);
+			single.gotoAndPlay(1);
+			trace("single-frame gotoAndPlay(1): " + single.isPlaying);
+			single.nextFrame();
+			trace("single-frame nextFrame(): " + single.currentFrame + " " + single.isPlaying);
+
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+
+		function onEnterFrame(e) {
+			this.ticks = this.ticks + 1;
+			if (this.ticks > 3) {
+				this.report("tick " + this.ticks);
+			}
+			if (this.ticks == 1) {
+				this.play();
+				trace("play(): " + this.isPlaying);
+			}
+			if (this.ticks == 3) {
+				this.gotoAndStop(2);
+				this.report("gotoAndStop(2)");
+			}
+			if (this.ticks == 5) {
+				this.gotoAndPlay(3);
+				this.report("gotoAndPlay(3)");
+			}
+			if (this.ticks == 9) {
+				this.stop();
+				this.report("stop()");
+			}
+			if (this.ticks == 10) {
+				this.nextFrame();
+				this.report("nextFrame()");
+			}
+			if (this.ticks == 11) {
+				this.gotoAndPlay(4);
+				this.report("gotoAndPlay(4)");
+			}
+		}
+
+		function report(what) {
+			trace(what + ": " + this.currentFrame + " " + this.isPlaying);
+		}
+	}
+}
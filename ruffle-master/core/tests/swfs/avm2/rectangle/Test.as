@@ -0,0 +1,56 @@
+package {
+	import flash.display.MovieClip;
+	import flash.geom.Point;
+	import flash.geom.Rectangle;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			var r:Rectangle = new Rectangle(10, 20, 30, 40);
+			trace(r);
+			trace(r.left, r.top, r.right, r.bottom);
+			trace(r.topLeft, r.bottomRight, r.size);
+			trace(r.contains(10, 20), r.contains(40, 60), r.contains(39, 59));
+			trace(r.containsPoint(new Point(25, 30)));
+			trace(r.containsRect(new Rectangle(15, 25, 5, 5)), r.containsRect(new Rectangle(15, 25, 50, 5)));
+
+			var o:Rectangle = new Rectangle(30, 40, 30, 40);
+			trace(r.intersects(o), r.intersection(o));
+			trace(r.union(o));
+			var far:Rectangle = new Rectangle(100, 100, 1, 1);
+			trace(r.intersects(far), r.intersection(far));
+			trace(r.union(new Rectangle()));
+
+			var c:Rectangle = r.clone();
+			trace(c.equals(r), c == r);
+			c.inflate(5, 10);
+			trace(c);
+			c.inflatePoint(new Point(1, 2));
+			trace(c);
+			c.offset(-16, -32);
+			trace(c);
+			c.offsetPoint(new Point(1, 2));
+			trace(c);
+
+			c.right = 50;
+			c.bottom = 60;
+			trace(c);
+			c.left = 10;
+			c.top = 20;
+			trace(c);
+			c.topLeft = new Point(0, 0);
+			trace(c);
+			c.bottomRight = new Point(5, 5);
+			trace(c);
+			c.size = new Point(7, 8);
+			trace(c);
+
+			c.setEmpty();
+			trace(c, c.isEmpty());
+			c.setTo(1, 2, 3, 4);
+			trace(c, c.isEmpty());
+			c.copyFrom(r);
+			trace(c);
+			trace(new Rectangle(0, 0, -1, 5).isEmpty());
+		}
+	}
+}
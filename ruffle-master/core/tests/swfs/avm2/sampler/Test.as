@@ -0,0 +1,33 @@
+package {
+	import flash.display.MovieClip;
+	import flash.sampler.*;
+	import flash.utils.getQualifiedClassName;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			trace(new QName("a"));
+			trace(new QName("http://example.com", "b"));
+			trace(new QName(null, "c"));
+			var q:QName = new QName("ns", "d");
+			trace(q.uri);
+			trace(q.localName);
+
+			var names:Object = getMemberNames({foo: 1});
+			trace(names.length);
+			for (var i:int = 0; i < names.length; i++) {
+				this.dump(names[i]);
+			}
+
+			startSampling();
+			var x:Array = [1, 2, 3];
+			stopSampling();
+			trace(getSampleCount());
+			trace(getSamples().length);
+			trace(getSize(x) > 0);
+		}
+
+		public function dump(n:*):void {
+			trace(getQualifiedClassName(n), n, n.localName, n.uri == "");
+		}
+	}
+}
@@ -0,0 +1,40 @@
+package {
+	import flash.display.MovieClip;
+
+	// The movie's SymbolClass tag binds:
+	//  - a 2x1 DefineBitsLossless2 bitmap to `Pic`
+	//  - a DefineBinaryData holding the bytes 7, 8, 9 to `Data`
+	//  - an empty sprite to `First`, and then again to `Second`
+	// The sprite is placed on the first frame.
+	public class Test extends MovieClip {
+	}
+}
+
+import flash.display.BitmapData;
+import flash.display.MovieClip;
+import flash.utils.ByteArray;
+
+class Pic extends BitmapData {
+	public function Pic(w, h) {
+		super(w, h);
+	}
+}
+
+class Data extends ByteArray {
+}
+
+class First extends MovieClip {
+	public function First() {
+		trace("First constructed");
+	}
+}
+
+class Second extends MovieClip {
+	public function Second() {
+		trace("Second constructed");
+		var d = new Data();
+		trace("data", d.length, d.readUnsignedByte(), d.readUnsignedByte(), d.readUnsignedByte());
+		var e = new Data();
+		trace("copy", e.length, e.position, e.readUnsignedByte());
+	}
+}
@@ -0,0 +1,22 @@
+package {
+	import flash.display.MovieClip;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			trace(undefined);
+			trace(null);
+			trace(-0.0);
+			trace(0.1 + 0.2);
+			trace(1 / 3);
+			trace(1e21);
+			trace(true, false);
+			trace([1, [2, 3], "a", null, undefined]);
+			trace([]);
+			trace({});
+			trace(1, "a", null, undefined);
+			trace("" + -0.0);
+			trace("" + [1, null]);
+			trace("" + null + undefined);
+		}
+	}
+}
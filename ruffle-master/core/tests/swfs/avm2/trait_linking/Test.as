@@ -0,0 +1,96 @@
+package {
+	import flash.display.MovieClip;
+	import flash.events.Event;
+
+	// The invalid classes below can't be compiled; the test SWF was assembled
+	// by hand, with each of them in a script of its own. Each fails with a
+	// VerifyError when it's first used, which ends that enterFrame handler.
+	public dynamic class Test extends MovieClip {
+		var ticks = 0;
+
+		public function Test() {
+			var l:Leaf = new Leaf();
+			trace(l.greet());
+			trace(l.fixed());
+			trace(l.value);
+			l.value = 5;
+			trace(l.label);
+			trace(new Middle().greet());
+			trace(new Base().value);
+			this.addEventListener(Event.ENTER_FRAME, this.onEnterFrame);
+		}
+
+		function onEnterFrame(e) {
+			this.ticks = this.ticks + 1;
+			if (this.ticks == 2) {
+				trace("Linking OverridesFinal");
+				trace(new OverridesFinal().fixed());
+			}
+			if (this.ticks == 3) {
+				trace("Linking MissingOverride");
+				trace(new MissingOverride().greet());
+			}
+			if (this.ticks == 4) {
+				trace("Linking OverridesGetterWithMethod");
+				trace(new OverridesGetterWithMethod().value());
+			}
+			if (this.ticks == 5) {
+				trace(new Leaf().greet());
+			}
+		}
+	}
+}
+
+class Base {
+	public var label = "base label";
+
+	public function greet() {
+		return "Base.greet";
+	}
+
+	public final function fixed() {
+		return "Base.fixed";
+	}
+
+	public function get value() {
+		return "Base.value";
+	}
+
+	public function set value(value) {
+		trace("Base set value " + value);
+	}
+}
+
+class Middle extends Base {
+	public override function greet() {
+		return "Middle.greet, " + super.greet();
+	}
+
+	public override function get value() {
+		return "Middle.value";
+	}
+}
+
+class Leaf extends Middle {
+	public override function greet() {
+		return "Leaf.greet, " + super.greet();
+	}
+}
+
+class OverridesFinal extends Base {
+	public override function fixed() {
+		return "OverridesFinal.fixed";
+	}
+}
+
+class MissingOverride extends Base {
+	public function greet() {
+		return "MissingOverride.greet";
+	}
+}
+
+class OverridesGetterWithMethod extends Base {
+	public override function value() {
+		return "OverridesGetterWithMethod.value";
+	}
+}
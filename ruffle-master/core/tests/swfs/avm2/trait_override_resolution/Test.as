@@ -0,0 +1,60 @@
+package {
+	import flash.display.MovieClip;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			var b:Base = new Leaf();
+			trace(b.describe());
+			trace(Leaf(b).extra());
+			trace(new Middle().describe());
+			trace(new Middle().extra());
+			trace(new Sibling().describe());
+			trace(new Sibling().extra());
+			trace(new Base().describe());
+		}
+	}
+}
+
+class Base {
+	public function greet() {
+		return "Base.greet";
+	}
+
+	public function get name() {
+		return "Base";
+	}
+
+	// Calls from the base class dispatch to the most derived override.
+	public function describe() {
+		return "I am " + this.greet() + " named " + this.name;
+	}
+}
+
+// Inherits everything from `Base` without overriding it.
+class Middle extends Base {
+	public function extra() {
+		return "Middle.extra";
+	}
+}
+
+// `super` skips over `Middle`, which doesn't override `greet`.
+class Leaf extends Middle {
+	public override function greet() {
+		return "Leaf.greet, " + super.greet();
+	}
+
+	public override function extra() {
+		return "Leaf.extra, " + super.extra();
+	}
+
+	public override function get name() {
+		return "Leaf";
+	}
+}
+
+// Overriding `greet` here must not affect `Leaf`, which shares the same bases.
+class Sibling extends Middle {
+	public override function greet() {
+		return "Sibling.greet";
+	}
+}
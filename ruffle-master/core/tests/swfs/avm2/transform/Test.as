@@ -0,0 +1,33 @@
+package {
+	import flash.display.MovieClip;
+	import flash.geom.ColorTransform;
+	import flash.geom.Matrix;
+	import flash.geom.Transform;
+
+	public dynamic class Test extends MovieClip {
+		public function Test() {
+			var t:Transform = this.transform;
+			trace(t.matrix);
+			this.x = 10;
+			this.scaleX = 2;
+			trace(t.matrix);
+
+			// The matrix is a copy, so changing it doesn't move the clip.
+			var m:Matrix = t.matrix;
+			m.tx = 50;
+			trace(this.x);
+
+			t.matrix = new Matrix(1, 0, 0, 4, 5, 6);
+			trace(this.x, this.y, this.scaleX, this.scaleY);
+			trace(this.transform.concatenatedMatrix);
+
+			trace(t.colorTransform);
+			t.colorTransform = new ColorTransform(0.5, 1, 1, 1, 0, 0, 0, 0);
+			trace(this.transform.colorTransform);
+			this.alpha = 0.5;
+			trace(this.transform.colorTransform.alphaMultiplier);
+			trace(this.transform.concatenatedColorTransform);
+			trace(t.matrix);
+		}
+	}
+}
@@ -0,0 +1,18 @@
+package {
+	import flash.display.MovieClip;
+	import flash.geom.Point;
+
+	public class Test extends MovieClip {
+		public function Test() {
+			this.addEventListener("enterFrame", this.onEnterFrame);
+		}
+
+		public function onEnterFrame(e) {
+			this.removeEventListener("enterFrame", this.onEnterFrame);
+			trace("before");
+			var found = this.getObjectsUnderPoint(new Point(0, 0));
+			trace("found " + found.length);
+			trace("after");
+		}
+	}
+}
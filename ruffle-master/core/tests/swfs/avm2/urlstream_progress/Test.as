@@ -0,0 +1,32 @@
+package {
+	import flash.display.MovieClip;
+	import flash.net.URLRequest;
+	import flash.net.URLStream;
+
+	// data.bin holds 10000 bytes, where byte `i` is `(i * 7) % 256`.
+	public class Test extends MovieClip {
+		var stream;
+
+		public function Test() {
+			this.stream = new URLStream();
+			this.stream.addEventListener("open", this.onOpen);
+			this.stream.addEventListener("progress", this.onProgress);
+			this.stream.addEventListener("complete", this.onComplete);
+			this.stream.load(new URLRequest("data.bin"));
+			trace("connected: " + this.stream.connected);
+		}
+
+		public function onOpen(e) {
+			trace("open, bytesAvailable: " + this.stream.bytesAvailable);
+		}
+
+		public function onProgress(e) {
+			trace("progress " + e.bytesLoaded + " of " + e.bytesTotal + ", bytesAvailable: " + this.stream.bytesAvailable);
+			trace("read " + this.stream.readUnsignedByte() + ", bytesAvailable: " + this.stream.bytesAvailable);
+		}
+
+		public function onComplete(e) {
+			trace("complete, bytesAvailable: " + this.stream.bytesAvailable + ", connected: " + this.stream.connected);
+		}
+	}
+}
@@ -1,12 +1,13 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use generational_arena::Arena;
 use ruffle_core::backend::audio::decoders::{
-    self, AdpcmDecoder, Mp3Decoder, NellymoserDecoder, PcmDecoder, SeekableDecoder,
+    self, AdpcmDecoder, Mp3Decoder, Mp3FrameIndex, NellymoserDecoder, PcmDecoder, SeekableDecoder,
 };
 use ruffle_core::backend::audio::{
-    swf, AudioBackend, SoundHandle, SoundInstanceHandle, SoundTransform,
+    swf, AudioBackend, CaptureActivityCallback, SoundHandle, SoundInstanceHandle, SoundTransform,
 };
 use ruffle_core::tag_utils::SwfSlice;
+use std::collections::{HashMap, VecDeque};
 use std::io::Cursor;
 use std::sync::{Arc, Mutex};
 use swf::AudioCompression;
@@ -18,6 +19,8 @@ pub struct CpalAudioBackend {
     stream: Stream,
     sounds: Arena<Sound>,
     sound_instances: Arc<Mutex<Arena<SoundInstance>>>,
+    dynamic_sounds: HashMap<SoundInstanceHandle, Arc<Mutex<DynamicSoundBuffer>>>,
+    capture_stream: Option<Stream>,
 }
 
 // Because of https://github.com/RustAudio/cpal/pull/348, we have to initialize cpal on a
@@ -42,6 +45,9 @@ struct Sound {
 
     /// Number of samples to skip encoder delay.
     skip_sample_frames: u16,
+
+    /// The decoded sample frames, once `extract_samples` has first read them.
+    decoded: Option<Vec<[i16; 2]>>,
 }
 
 /// An actively playing instance of a sound.
@@ -142,6 +148,8 @@ impl CpalAudioBackend {
             stream: Stream(stream),
             sounds: Arena::new(),
             sound_instances,
+            dynamic_sounds: HashMap::new(),
+            capture_stream: None,
         })
     }
 
@@ -187,6 +195,18 @@ impl CpalAudioBackend {
     fn make_resampler<S: Send + dasp::signal::Signal<Frame = [i16; 2]>>(
         &self,
         format: &swf::SoundFormat,
+        signal: S,
+    ) -> dasp::signal::interpolate::Converter<
+        S,
+        impl dasp::interpolate::Interpolator<Frame = [i16; 2]>,
+    > {
+        self.make_resampler_from_rate(format.sample_rate.into(), signal)
+    }
+
+    /// Resamples a stream with the given sample rate to the output sample rate.
+    fn make_resampler_from_rate<S: Send + dasp::signal::Signal<Frame = [i16; 2]>>(
+        &self,
+        sample_rate: f64,
         mut signal: S,
     ) -> dasp::signal::interpolate::Converter<
         S,
@@ -198,7 +218,7 @@ impl CpalAudioBackend {
         dasp::signal::interpolate::Converter::from_hz_to_hz(
             signal,
             interpolator,
-            format.sample_rate.into(),
+            sample_rate,
             self.output_config.sample_rate.0.into(),
         )
     }
@@ -237,11 +257,15 @@ impl CpalAudioBackend {
     /// Creates a `dasp::signal::Signal` that decodes and resamples a "stream" sound.
     fn make_signal_from_stream<'a>(
         &self,
-        format: &swf::SoundFormat,
+        stream_info: &swf::SoundStreamHead,
+        clip_frame: u16,
         data_stream: SwfSlice,
     ) -> Result<Box<dyn 'a + Send + dasp::signal::Signal<Frame = [i16; 2]>>, Error> {
+        let format = &stream_info.stream_format;
+
         // Instantiate a decoder for the compression that the sound data uses.
-        let clip_stream_decoder = decoders::make_stream_decoder(format, data_stream)?;
+        let clip_stream_decoder =
+            decoders::make_stream_decoder(stream_info, clip_frame, data_stream)?;
 
         // Convert the `Decoder` to a `Signal`, and resample it the the output
         // sample rate.
@@ -267,6 +291,16 @@ impl CpalAudioBackend {
         Ok(Box::new(signal))
     }
 
+    /// Calculates the activity level of a block of captured audio, from the
+    /// peak amplitude of its samples.
+    fn capture_activity_level<T: cpal::Sample>(buffer: &[T]) -> f64 {
+        let peak = buffer
+            .iter()
+            .map(|sample| sample.to_f32().abs())
+            .fold(0.0, f32::max);
+        f64::from(peak.min(1.0)) * 100.0
+    }
+
     /// Callback to the audio thread.
     /// Refill the output buffer by stepping through all active sounds
     /// and mixing in their output.
@@ -327,15 +361,27 @@ impl AudioBackend for CpalAudioBackend {
             (0, swf_sound.data)
         };
 
+        // The sample count in the SWF header can't be trusted for VBR MP3s.
+        let num_sample_frames = if swf_sound.format.compression == AudioCompression::Mp3 {
+            Mp3FrameIndex::new(data).sound_sample_frames(swf_sound.num_samples, skip_sample_frames)
+        } else {
+            swf_sound.num_samples
+        };
+
         let sound = Sound {
             format: swf_sound.format.clone(),
             data: Arc::from(data),
-            num_sample_frames: swf_sound.num_samples,
+            num_sample_frames,
             skip_sample_frames,
+            decoded: None,
         };
         Ok(self.sounds.insert(sound))
     }
 
+    fn unregister_sound(&mut self, sound: SoundHandle) {
+        self.sounds.remove(sound);
+    }
+
     fn play(&mut self) {
         self.stream.0.play().expect("Error trying to resume CPAL audio stream. This feature may not be supported by your audio device.");
     }
@@ -347,16 +393,14 @@ impl AudioBackend for CpalAudioBackend {
     fn start_stream(
         &mut self,
         _stream_handle: Option<SoundHandle>,
-        _clip_frame: u16,
+        clip_frame: u16,
         clip_data: SwfSlice,
         stream_info: &swf::SoundStreamHead,
     ) -> Result<SoundInstanceHandle, Error> {
-        let format = &stream_info.stream_format;
-
         // The audio data for stream sounds is distributed among the frames of a
         // movie clip. The stream tag reader will parse through the SWF and
         // feed the decoder audio data on the fly.
-        let signal = self.make_signal_from_stream(format, clip_data)?;
+        let signal = self.make_signal_from_stream(stream_info, clip_frame, clip_data)?;
 
         let mut sound_instances = self.sound_instances.lock().unwrap();
         let handle = sound_instances.insert(SoundInstance {
@@ -405,6 +449,7 @@ impl AudioBackend for CpalAudioBackend {
     fn stop_sound(&mut self, sound: SoundInstanceHandle) {
         let mut sound_instances = self.sound_instances.lock().unwrap();
         sound_instances.remove(sound);
+        self.dynamic_sounds.remove(&sound);
     }
 
     fn stop_all_sounds(&mut self) {
@@ -417,6 +462,7 @@ impl AudioBackend for CpalAudioBackend {
             sound_instances.remove(i);
         }
         sound_instances.clear();
+        self.dynamic_sounds.clear();
     }
 
     fn get_sound_position(&self, instance: SoundInstanceHandle) -> Option<u32> {
@@ -444,9 +490,170 @@ impl AudioBackend for CpalAudioBackend {
         }
     }
 
+    fn start_dynamic_sound(&mut self) -> Result<SoundInstanceHandle, Error> {
+        let buffer = Arc::new(Mutex::new(DynamicSoundBuffer::default()));
+        let signal = DynamicSoundSignal(Arc::clone(&buffer));
+        let signal = Box::new(self.make_resampler_from_rate(44100.0, signal));
+
+        let mut sound_instances = self.sound_instances.lock().unwrap();
+        // Forget any dynamic sounds that the audio thread has finished with.
+        self.dynamic_sounds
+            .retain(|handle, _| sound_instances.contains(*handle));
+        let handle = sound_instances.insert(SoundInstance {
+            handle: None,
+            signal,
+            active: true,
+            left_transform: [1.0, 0.0],
+            right_transform: [0.0, 1.0],
+        });
+        self.dynamic_sounds.insert(handle, buffer);
+        Ok(handle)
+    }
+
+    fn queue_samples(&mut self, instance: SoundInstanceHandle, samples: &[[f32; 2]]) {
+        use dasp::Sample;
+        if let Some(buffer) = self.dynamic_sounds.get(&instance) {
+            let mut buffer = buffer.lock().unwrap();
+            buffer.samples.extend(
+                samples
+                    .iter()
+                    .map(|[left, right]| [left.to_sample::<i16>(), right.to_sample::<i16>()]),
+            );
+        }
+    }
+
+    fn finish_dynamic_sound(&mut self, instance: SoundInstanceHandle) {
+        if let Some(buffer) = self.dynamic_sounds.get(&instance) {
+            buffer.lock().unwrap().is_finished = true;
+        }
+    }
+
+    fn queued_sample_count(&self, instance: SoundInstanceHandle) -> Option<usize> {
+        let sound_instances = self.sound_instances.lock().unwrap();
+        if sound_instances.contains(instance) {
+            self.dynamic_sounds
+                .get(&instance)
+                .map(|buffer| buffer.lock().unwrap().samples.len())
+        } else {
+            None
+        }
+    }
+
+    fn extract_samples(
+        &mut self,
+        sound: SoundHandle,
+        start_sample_frame: u32,
+        num_sample_frames: u32,
+    ) -> Option<Vec<[f32; 2]>> {
+        use dasp::Sample;
+
+        // Extracted samples are always in 44.1KHz.
+        const EXTRACT_SAMPLE_RATE: u64 = 44100;
+
+        // Decode the whole sound once, so that reading it in pieces doesn't
+        // decode it again from the start for every piece.
+        let sound = self.sounds.get_mut(sound)?;
+        if sound.decoded.is_none() {
+            let data = Cursor::new(ArcAsRef(Arc::clone(&sound.data)));
+            let decoder = decoders::make_decoder(&sound.format, data).ok()?;
+            sound.decoded = Some(
+                decoder
+                    .skip(sound.skip_sample_frames.into())
+                    .take(sound.num_sample_frames as usize)
+                    .collect(),
+            );
+        }
+        let decoded = sound.decoded.as_deref().unwrap_or_default();
+
+        // Resample from the sound's sample rate, picking the nearest frame.
+        let sample_rate = u64::from(sound.format.sample_rate);
+        let num_output_frames = decoded.len() as u64 * EXTRACT_SAMPLE_RATE / sample_rate.max(1);
+        let start = u64::from(start_sample_frame).min(num_output_frames);
+        let end = start
+            .saturating_add(num_sample_frames.into())
+            .min(num_output_frames);
+        let samples = (start..end)
+            .filter_map(|i| decoded.get((i * sample_rate / EXTRACT_SAMPLE_RATE) as usize))
+            .map(|[left, right]| [left.to_sample::<f32>(), right.to_sample::<f32>()])
+            .collect();
+        Some(samples)
+    }
+
+    fn capture_device_names(&self) -> Vec<String> {
+        cpal::default_host()
+            .input_devices()
+            .map(|devices| devices.filter_map(|device| device.name().ok()).collect())
+            .unwrap_or_default()
+    }
+
+    fn start_capture(&mut self, mut on_activity: CaptureActivityCallback) -> Result<(), Error> {
+        let device = cpal::default_host()
+            .default_input_device()
+            .ok_or("No audio capture devices available")?;
+        let config = device.default_input_config()?;
+        let sample_format = config.sample_format();
+        let config = cpal::StreamConfig::from(config);
+        let error_handler = move |err| log::error!("Audio capture stream error: {}", err);
+
+        use cpal::SampleFormat;
+        let stream = match sample_format {
+            SampleFormat::F32 => device.build_input_stream(
+                &config,
+                move |buffer: &[f32], _| on_activity(Self::capture_activity_level(buffer)),
+                error_handler,
+            ),
+            SampleFormat::I16 => device.build_input_stream(
+                &config,
+                move |buffer: &[i16], _| on_activity(Self::capture_activity_level(buffer)),
+                error_handler,
+            ),
+            SampleFormat::U16 => device.build_input_stream(
+                &config,
+                move |buffer: &[u16], _| on_activity(Self::capture_activity_level(buffer)),
+                error_handler,
+            ),
+        }?;
+
+        stream.play()?;
+        self.capture_stream = Some(Stream(stream));
+        Ok(())
+    }
+
+    fn stop_capture(&mut self) {
+        self.capture_stream = None;
+    }
+
     fn tick(&mut self) {}
 }
 
+/// The sample frames queued for a dynamic sound, shared between the backend
+/// and the audio thread.
+#[derive(Default)]
+struct DynamicSoundBuffer {
+    /// Queued sample frames at 44.1KHz.
+    samples: VecDeque<[i16; 2]>,
+
+    /// Whether no more samples will be queued.
+    is_finished: bool,
+}
+
+/// A signal that plays back the samples queued for a dynamic sound.
+/// Outputs silence if the queue runs dry before the sound is finished.
+struct DynamicSoundSignal(Arc<Mutex<DynamicSoundBuffer>>);
+
+impl dasp::signal::Signal for DynamicSoundSignal {
+    type Frame = [i16; 2];
+
+    fn next(&mut self) -> Self::Frame {
+        self.0.lock().unwrap().samples.pop_front().unwrap_or([0, 0])
+    }
+
+    fn is_exhausted(&self) -> bool {
+        let buffer = self.0.lock().unwrap();
+        buffer.is_finished && buffer.samples.is_empty()
+    }
+}
+
 /// A dummy wrapper struct to implement `AsRef<[u8]>` for `Arc<Vec<u8>`.
 /// Not having this trait causes problems when trying to use `Cursor<Vec<u8>>`.
 struct ArcAsRef(Arc<[u8]>);
@@ -0,0 +1,34 @@
+use ::clipboard::{ClipboardContext, ClipboardProvider};
+use ruffle_core::backend::clipboard::ClipboardBackend;
+
+pub struct DesktopClipboardBackend {
+    clipboard: Option<ClipboardContext>,
+}
+
+impl DesktopClipboardBackend {
+    pub fn new() -> Self {
+        let clipboard = match ClipboardProvider::new() {
+            Ok(clipboard) => Some(clipboard),
+            Err(e) => {
+                log::warn!("Unable to access the clipboard: {}", e);
+                None
+            }
+        };
+
+        Self { clipboard }
+    }
+}
+
+impl ClipboardBackend for DesktopClipboardBackend {
+    fn contents(&mut self) -> Option<String> {
+        self.clipboard.as_mut()?.get_contents().ok()
+    }
+
+    fn set_contents(&mut self, content: String) {
+        if let Some(clipboard) = &mut self.clipboard {
+            if let Err(e) = clipboard.set_contents(content) {
+                log::warn!("Unable to set the clipboard contents: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use ruffle_core::context::UpdateContext;
+use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider, Value};
+use std::collections::HashMap;
+use std::rc::Rc;
+
+type HostMethod = Rc<dyn Fn(&[Value]) -> Value>;
+
+/// An `ExternalInterfaceProvider` that lets the desktop host register native
+/// callbacks for `ExternalInterface.call`.
+///
+/// Calls to methods that the host did not register are logged and return
+/// `null`, so that movies calling out to a missing page script keep running.
+#[derive(Default)]
+pub struct DesktopExternalInterfaceProvider {
+    methods: HashMap<String, HostMethod>,
+}
+
+impl DesktopExternalInterfaceProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a host method that ActionScript can invoke with `ExternalInterface.call(name, ...)`.
+    pub fn register_method<F>(&mut self, name: impl Into<String>, method: F)
+    where
+        F: 'static + Fn(&[Value]) -> Value,
+    {
+        self.methods.insert(name.into(), Rc::new(method));
+    }
+
+    /// Registers the host methods given on the command line.
+    ///
+    /// Each one is a `name=value` pair, and `ExternalInterface.call(name, ...)`
+    /// logs its arguments and returns `value`. Values are read as booleans,
+    /// `null` or numbers where possible, and as strings otherwise; a name
+    /// without a value returns `null`.
+    pub fn register_calls(&mut self, calls: &[String]) {
+        for call in calls {
+            let mut split = call.splitn(2, '=');
+            let name = split.next().unwrap_or_default().to_string();
+            let value = split.next().map(parse_value).unwrap_or(Value::Null);
+            let method_name = name.clone();
+            self.register_method(name, move |args| {
+                log::info!(
+                    "ExternalInterface.call({:?}, {:?}) returned {:?}",
+                    method_name,
+                    args,
+                    value
+                );
+                value.clone()
+            });
+        }
+    }
+}
+
+/// Read a value given on the command line.
+fn parse_value(value: &str) -> Value {
+    match value {
+        "null" => Value::Null,
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => value
+            .parse()
+            .map(Value::Number)
+            .unwrap_or_else(|_| Value::String(value.to_string())),
+    }
+}
+
+struct DesktopHostMethod {
+    name: String,
+    method: Option<HostMethod>,
+}
+
+impl ExternalInterfaceMethod for DesktopHostMethod {
+    fn call(&self, _context: &mut UpdateContext<'_, '_, '_>, args: &[Value]) -> Value {
+        if let Some(method) = &self.method {
+            method(args)
+        } else {
+            log::info!("ExternalInterface.call({:?}, {:?})", self.name, args);
+            Value::Null
+        }
+    }
+}
+
+impl ExternalInterfaceProvider for DesktopExternalInterfaceProvider {
+    fn get_method(&self, name: &str) -> Option<Box<dyn ExternalInterfaceMethod>> {
+        Some(Box::new(DesktopHostMethod {
+            name: name.to_string(),
+            method: self.methods.get(name).cloned(),
+        }))
+    }
+
+    fn on_callback_available(&self, name: &str) {
+        log::info!("ExternalInterface callback available: {}", name);
+    }
+
+    fn on_fs_command(&self, _command: &str, _args: &str) -> bool {
+        false
+    }
+}
@@ -0,0 +1,266 @@
+//! `LocalConnection`s between Ruffle processes
+//!
+//! On Unix, each connection that a movie listens on is a Unix domain socket
+//! in a shared directory, so that movies running in different windows can
+//! talk to each other. Other platforms only connect the movie to itself for
+//! now.
+//!
+//! Receivers trust the domain that a message says it was sent from, so the
+//! directory is private to the user, and only their own processes can send
+//! messages.
+
+use ruffle_core::backend::local_connection::LocalConnectionBackend;
+
+/// The local connection backend for this platform.
+pub fn new_backend() -> Box<dyn LocalConnectionBackend> {
+    #[cfg(unix)]
+    {
+        Box::new(unix::UnixLocalConnectionBackend::new())
+    }
+    #[cfg(not(unix))]
+    {
+        Box::new(ruffle_core::backend::local_connection::InProcessLocalConnectionBackend::default())
+    }
+}
+
+#[cfg(unix)]
+mod unix {
+    use ruffle_core::backend::local_connection::{LocalConnectionBackend, LocalConnectionMessage};
+    use std::collections::HashMap;
+    use std::convert::TryInto;
+    use std::io::{self, Read, Write};
+    use std::os::unix::fs::PermissionsExt;
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::mpsc::{channel, Receiver, Sender};
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// How long to wait for the rest of a message once its sender connected.
+    const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+    /// The largest message that is read, which leaves room for the names
+    /// that come with the 40 KB of arguments that Flash Player allows.
+    const MAX_MESSAGE_SIZE: u64 = 64 * 1024;
+
+    /// A connection that this process listens on.
+    struct Listener {
+        path: PathBuf,
+
+        /// Tells the thread accepting the connection's messages to stop.
+        stopped: Arc<AtomicBool>,
+    }
+
+    impl Listener {
+        fn stop(&self) {
+            self.stopped.store(true, Ordering::SeqCst);
+            // Wake the thread up from waiting for a message.
+            let _ = UnixStream::connect(&self.path);
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+
+    pub struct UnixLocalConnectionBackend {
+        directory: PathBuf,
+
+        listeners: HashMap<String, Listener>,
+
+        /// The messages received by the threads of every listener.
+        sender: Sender<LocalConnectionMessage>,
+        receiver: Receiver<LocalConnectionMessage>,
+    }
+
+    impl UnixLocalConnectionBackend {
+        pub fn new() -> Self {
+            let (sender, receiver) = channel();
+            let runtime_directory = std::env::var_os("XDG_RUNTIME_DIR")
+                .map(PathBuf::from)
+                .unwrap_or_else(std::env::temp_dir);
+            Self {
+                directory: runtime_directory.join("ruffle-local-connections"),
+                listeners: HashMap::new(),
+                sender,
+                receiver,
+            }
+        }
+
+        /// The path of the socket for a connection name. Anything but letters,
+        /// digits, dots and dashes is escaped, so that each name has its own
+        /// file.
+        fn path(&self, name: &str) -> PathBuf {
+            let mut file_name = String::new();
+            for byte in name.bytes() {
+                match byte {
+                    b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'.' | b'-' => {
+                        file_name.push(byte as char)
+                    }
+                    _ => file_name.push_str(&format!("_{:02x}", byte)),
+                }
+            }
+            self.directory.join(file_name)
+        }
+
+        fn listen(&self, path: &Path) -> io::Result<Option<UnixListener>> {
+            std::fs::create_dir_all(&self.directory)?;
+            // This fails unless the directory belongs to the user, so another
+            // user can't have made it for us.
+            std::fs::set_permissions(&self.directory, std::fs::Permissions::from_mode(0o700))?;
+
+            match UnixListener::bind(path) {
+                Err(e) if e.kind() == io::ErrorKind::AddrInUse => {
+                    if UnixStream::connect(path).is_ok() {
+                        // Another process is listening.
+                        return Ok(None);
+                    }
+                    // A process that listened on it before exited without
+                    // cleaning up.
+                    std::fs::remove_file(path)?;
+                    UnixListener::bind(path).map(Some)
+                }
+                result => result.map(Some),
+            }
+        }
+    }
+
+    impl LocalConnectionBackend for UnixLocalConnectionBackend {
+        fn connect(&mut self, name: &str) -> bool {
+            if self.listeners.contains_key(name) {
+                return false;
+            }
+
+            let path = self.path(name);
+            match self.listen(&path) {
+                Ok(Some(listener)) => {
+                    let stopped = Arc::new(AtomicBool::new(false));
+                    let connection = name.to_string();
+                    let sender = self.sender.clone();
+                    let thread_stopped = stopped.clone();
+                    thread::spawn(move || receive(connection, listener, thread_stopped, sender));
+
+                    self.listeners
+                        .insert(name.to_string(), Listener { path, stopped });
+                    true
+                }
+                Ok(None) => false,
+                Err(e) => {
+                    log::error!("Couldn't listen on LocalConnection {}: {}", name, e);
+                    false
+                }
+            }
+        }
+
+        fn close(&mut self, name: &str) {
+            if let Some(listener) = self.listeners.remove(name) {
+                listener.stop();
+            }
+        }
+
+        fn send(&mut self, message: LocalConnectionMessage) -> bool {
+            let mut stream = match UnixStream::connect(self.path(&message.connection)) {
+                Ok(stream) => stream,
+                Err(_) => return false,
+            };
+
+            // The receiver knows which connection the message is for from the
+            // socket it arrives on.
+            let mut data = vec![];
+            for field in &[&message.sender_domain, &message.method] {
+                data.extend_from_slice(&(field.len() as u32).to_be_bytes());
+                data.extend_from_slice(field.as_bytes());
+            }
+            data.extend_from_slice(&message.args);
+
+            match stream.write_all(&data) {
+                Ok(()) => true,
+                Err(e) => {
+                    log::warn!("Couldn't send LocalConnection message: {}", e);
+                    false
+                }
+            }
+        }
+
+        fn poll_messages(&mut self) -> Vec<LocalConnectionMessage> {
+            let listeners = &self.listeners;
+            self.receiver
+                .try_iter()
+                // Messages that arrived just before a connection was closed
+                // are dropped with it.
+                .filter(|message| listeners.contains_key(&message.connection))
+                .collect()
+        }
+    }
+
+    impl Drop for UnixLocalConnectionBackend {
+        fn drop(&mut self) {
+            for listener in self.listeners.values() {
+                listener.stop();
+            }
+        }
+    }
+
+    /// Read the messages sent to `connection` until its listener is stopped.
+    fn receive(
+        connection: String,
+        listener: UnixListener,
+        stopped: Arc<AtomicBool>,
+        sender: Sender<LocalConnectionMessage>,
+    ) {
+        for stream in listener.incoming() {
+            if stopped.load(Ordering::SeqCst) {
+                return;
+            }
+
+            let message = match stream {
+                Ok(stream) => read_message(&connection, stream),
+                Err(e) => Err(e.to_string()),
+            };
+            match message {
+                Ok(message) => {
+                    if sender.send(message).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => log::warn!("Received a broken LocalConnection message: {}", e),
+            }
+        }
+    }
+
+    /// Read a message written by `send`. Senders write their message all at
+    /// once and hang up.
+    fn read_message(
+        connection: &str,
+        mut stream: UnixStream,
+    ) -> Result<LocalConnectionMessage, String> {
+        let mut data = vec![];
+        stream
+            .set_read_timeout(Some(READ_TIMEOUT))
+            .and_then(|_| (&mut stream).take(MAX_MESSAGE_SIZE + 1).read_to_end(&mut data))
+            .map_err(|e| e.to_string())?;
+        if data.len() as u64 > MAX_MESSAGE_SIZE {
+            return Err("the message is too large".to_string());
+        }
+
+        let mut data = &data[..];
+        let mut fields = vec![];
+        for _ in 0..2 {
+            let field = data
+                .get(..4)
+                .map(|len| u32::from_be_bytes(len.try_into().unwrap()) as usize)
+                .and_then(|len| data.get(4..).and_then(|rest| rest.get(..len)))
+                .ok_or("the message is cut short")?;
+            fields.push(String::from_utf8(field.to_vec()).map_err(|e| e.to_string())?);
+            data = &data[4 + field.len()..];
+        }
+        let method = fields.pop().unwrap();
+        let sender_domain = fields.pop().unwrap();
+
+        Ok(LocalConnectionMessage {
+            connection: connection.to_string(),
+            sender_domain,
+            method,
+            args: data.to_vec(),
+        })
+    }
+}
@@ -6,10 +6,14 @@
 #![windows_subsystem = "windows"]
 
 mod audio;
+mod clipboard;
 mod custom_event;
 mod executor;
+mod external_interface;
+mod local_connection;
 mod locale;
 mod navigator;
+mod socket;
 mod storage;
 mod task;
 mod ui;
@@ -19,7 +23,13 @@ use crate::executor::GlutinAsyncExecutor;
 use clap::Clap;
 use isahc::{config::RedirectPolicy, prelude::*, HttpClient};
 use ruffle_core::{
-    backend::audio::AudioBackend, backend::video::NullVideoBackend, config::Letterbox, Player,
+    backend::audio::AudioBackend,
+    backend::gamepad::NullGamepadBackend,
+    backend::local_connection::InProcessLocalConnectionBackend,
+    backend::socket::NullSocketBackend,
+    backend::video::NullVideoBackend,
+    config::{Letterbox, TextHinting, TextRendering},
+    Player,
 };
 use ruffle_render_wgpu::WgpuRenderBackend;
 use std::path::{Path, PathBuf};
@@ -56,6 +66,11 @@ struct Opt {
     #[clap(short = 'P', number_of_values = 1)]
     parameters: Vec<String>,
 
+    /// A host method for the movie to call with ExternalInterface, and the value it returns.
+    /// This can be repeated multiple times, for example --external-call scoreSubmit=true
+    #[clap(long = "external-call", number_of_values = 1)]
+    external_calls: Vec<String>,
+
     /// Type of graphics backend to use. Not all options may be supported by your current system.
     /// Default will attempt to pick the most supported graphics backend.
     #[clap(
@@ -87,6 +102,23 @@ struct Opt {
 
     #[clap(long, case_insensitive = true, takes_value = false)]
     timedemo: bool,
+
+    /// Throw an error in the movie whenever it uses an unimplemented builtin.
+    /// AVM2 scripts can't catch these errors, so the script stops.
+    #[clap(long, case_insensitive = true, takes_value = false)]
+    strict: bool,
+
+    /// Lay text out with whole pixel advances and draw it on whole pixels, as Flash Player does
+    #[clap(long, case_insensitive = true, takes_value = false)]
+    pixel_aligned_text: bool,
+
+    /// Move the focus with Tab, and press the focused button with Enter or Space
+    #[clap(long, case_insensitive = true, takes_value = false)]
+    keyboard_navigation: bool,
+
+    /// Let the movie load from anywhere, ignoring its security sandbox and the policy files of other sites
+    #[clap(long, case_insensitive = true, takes_value = false)]
+    permissive_security: bool,
 }
 
 #[cfg(feature = "render_trace")]
@@ -247,13 +279,44 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
     let video = Box::new(video::SoftwareVideoBackend::new());
     let log = Box::new(ruffle_core::backend::log::NullLogBackend::new());
     let ui = Box::new(ui::DesktopUiBackend::new(window.clone()));
-    let player = Player::new(renderer, audio, navigator, storage, locale, video, log, ui)?;
+    let clipboard = Box::new(clipboard::DesktopClipboardBackend::new());
+    // TODO: Read gamepads on desktop.
+    let gamepad = Box::new(NullGamepadBackend::new());
+    let socket = Box::new(socket::DesktopSocketBackend::new());
+    let local_connection = local_connection::new_backend();
+    let player = Player::new(
+        renderer,
+        audio,
+        navigator,
+        storage,
+        locale,
+        video,
+        log,
+        ui,
+        clipboard,
+        gamepad,
+        socket,
+        local_connection,
+    )?;
     {
         let mut player = player.lock().unwrap();
         player.set_root_movie(Arc::new(movie));
         player.set_is_playing(true); // Desktop player will auto-play.
         player.set_letterbox(Letterbox::On);
+        player.set_strict_mode(opt.strict);
+        player.set_keyboard_navigation(opt.keyboard_navigation);
+        player.set_permissive_security(opt.permissive_security);
+        if opt.pixel_aligned_text {
+            player.set_text_rendering(TextRendering {
+                subpixel_positioning: false,
+                hinting: TextHinting::Full,
+            });
+        }
         player.set_viewport_dimensions(viewport_size.width, viewport_size.height);
+
+        let mut external_interface = external_interface::DesktopExternalInterfaceProvider::new();
+        external_interface.register_calls(&opt.external_calls);
+        player.add_external_interface(Box::new(external_interface));
     }
 
     let mut mouse_pos = PhysicalPosition::new(0.0, 0.0);
@@ -280,6 +343,14 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
                         time = new_time;
                         let mut player_lock = player.lock().unwrap();
                         player_lock.tick(dt as f64 / 1000.0);
+                        let file_dialog_result = player_lock
+                            .ui_mut()
+                            .downcast_mut::<ui::DesktopUiBackend>()
+                            .unwrap()
+                            .take_file_dialog_result();
+                        if let Some(file) = file_dialog_result {
+                            player_lock.finish_file_dialog(file);
+                        }
                         next_frame_time = new_time + player_lock.time_til_next_frame();
                         if player_lock.needs_render() {
                             window.request_redraw();
@@ -469,7 +540,24 @@ fn run_timedemo(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
     let video = Box::new(NullVideoBackend::new());
     let log = Box::new(ruffle_core::backend::log::NullLogBackend::new());
     let ui = Box::new(ruffle_core::backend::ui::NullUiBackend::new());
-    let player = Player::new(renderer, audio, navigator, storage, locale, video, log, ui)?;
+    let clipboard = Box::new(ruffle_core::backend::clipboard::MemoryClipboardBackend::new());
+    let gamepad = Box::new(NullGamepadBackend::new());
+    let socket = Box::new(NullSocketBackend::new());
+    let local_connection = Box::new(InProcessLocalConnectionBackend::default());
+    let player = Player::new(
+        renderer,
+        audio,
+        navigator,
+        storage,
+        locale,
+        video,
+        log,
+        ui,
+        clipboard,
+        gamepad,
+        socket,
+        local_connection,
+    )?;
     player.lock().unwrap().set_root_movie(Arc::new(movie));
     player.lock().unwrap().set_is_playing(true);
 
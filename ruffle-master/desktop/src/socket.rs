@@ -0,0 +1,145 @@
+//! TCP sockets, with a thread per connection
+
+use ruffle_core::backend::socket::{SocketBackend, SocketEvent, SocketHandle};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+/// How long to wait for a connection, which is Flash Player's default.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(20);
+
+pub struct DesktopSocketBackend {
+    sender: Sender<SocketEvent>,
+    receiver: Receiver<SocketEvent>,
+
+    /// The data to write on each socket that hasn't been closed, which its
+    /// connection's thread writes once the connection is made.
+    ///
+    /// Dropping a socket's sender makes its thread close the connection.
+    writers: HashMap<SocketHandle, Sender<Vec<u8>>>,
+}
+
+impl DesktopSocketBackend {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        Self {
+            sender,
+            receiver,
+            writers: HashMap::new(),
+        }
+    }
+}
+
+impl SocketBackend for DesktopSocketBackend {
+    fn connect(&mut self, handle: SocketHandle, host: &str, port: u16) {
+        let sender = self.sender.clone();
+        let host = host.to_string();
+        let (writer, writes) = channel();
+        self.writers.insert(handle, writer);
+
+        thread::spawn(move || {
+            let mut stream = match open(&host, port) {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let _ = sender.send(SocketEvent::Error(handle, e));
+                    return;
+                }
+            };
+            let reader = match stream.try_clone() {
+                Ok(reader) => reader,
+                Err(e) => {
+                    let _ = sender.send(SocketEvent::Error(handle, e.to_string()));
+                    return;
+                }
+            };
+            if sender.send(SocketEvent::Connected(handle)).is_err() {
+                return;
+            }
+
+            let read_sender = sender.clone();
+            thread::spawn(move || read(handle, reader, read_sender));
+
+            // Write until the socket is closed, which drops the other end of
+            // the channel, or a write fails.
+            for data in writes {
+                if let Err(e) = stream.write_all(&data) {
+                    let _ = sender.send(SocketEvent::Error(handle, e.to_string()));
+                    break;
+                }
+            }
+
+            // This also stops the reading thread.
+            let _ = stream.shutdown(Shutdown::Both);
+        });
+    }
+
+    fn send(&mut self, handle: SocketHandle, data: &[u8]) {
+        if let Some(writer) = self.writers.get(&handle) {
+            let _ = writer.send(data.to_vec());
+        }
+    }
+
+    fn close(&mut self, handle: SocketHandle) {
+        self.writers.remove(&handle);
+    }
+
+    fn poll_events(&mut self) -> Vec<SocketEvent> {
+        let mut events = Vec::new();
+
+        for event in self.receiver.try_iter() {
+            let handle = match &event {
+                SocketEvent::Connected(handle)
+                | SocketEvent::Data(handle, _)
+                | SocketEvent::Closed(handle)
+                | SocketEvent::Error(handle, _) => *handle,
+            };
+
+            // Sockets that were closed report nothing more.
+            if !self.writers.contains_key(&handle) {
+                continue;
+            }
+            if let SocketEvent::Closed(_) | SocketEvent::Error(..) = event {
+                self.writers.remove(&handle);
+            }
+            events.push(event);
+        }
+
+        events
+    }
+}
+
+/// Report the data that arrives on `stream` until it's closed.
+fn read(handle: SocketHandle, mut stream: TcpStream, sender: Sender<SocketEvent>) {
+    let mut buffer = [0; 4096];
+    loop {
+        let event = match stream.read(&mut buffer) {
+            Ok(0) => SocketEvent::Closed(handle),
+            Ok(len) => SocketEvent::Data(handle, buffer[..len].to_vec()),
+            Err(e) => SocketEvent::Error(handle, e.to_string()),
+        };
+        let is_data = matches!(event, SocketEvent::Data(..));
+        if sender.send(event).is_err() || !is_data {
+            return;
+        }
+    }
+}
+
+/// Connect to the first address of `host` that accepts the connection.
+fn open(host: &str, port: u16) -> Result<TcpStream, String> {
+    let addresses = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("Couldn't resolve {}: {}", host, e))?;
+
+    let mut error = format!("No addresses found for {}", host);
+    for address in addresses {
+        match TcpStream::connect_timeout(&address, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => error = format!("Couldn't connect to {}: {}", address, e),
+        }
+    }
+
+    Err(error)
+}
@@ -1,11 +1,16 @@
-use clipboard::{ClipboardContext, ClipboardProvider};
-use ruffle_core::backend::ui::{MouseCursor, UiBackend};
+use ruffle_core::backend::ui::{
+    DialogFile, FileFilter, MouseCursor, ScriptError, ScriptErrorResponse, UiBackend,
+};
 use ruffle_core::events::{KeyCode, PlayerEvent};
+use ruffle_core::i18n::{Message, MessageCatalog};
 use std::collections::HashSet;
+use std::path::Path;
 use std::rc::Rc;
-use tinyfiledialogs::{message_box_ok, MessageBoxIcon};
+use tinyfiledialogs::{
+    message_box_ok, message_box_yes_no, open_file_dialog, save_file_dialog, MessageBoxIcon, YesNo,
+};
 use winit::event::{ElementState, ModifiersState, VirtualKeyCode, WindowEvent};
-use winit::window::Window;
+use winit::window::{Fullscreen, Window};
 
 pub struct DesktopUiBackend {
     window: Rc<Window>,
@@ -13,7 +18,10 @@ pub struct DesktopUiBackend {
     cursor_visible: bool,
     last_key: KeyCode,
     last_char: Option<char>,
-    clipboard: ClipboardContext,
+
+    /// The file picked in the last file dialog, which is passed to the player
+    /// once it has finished the frame that showed the dialog.
+    file_dialog_result: Option<Option<DialogFile>>,
 }
 
 impl DesktopUiBackend {
@@ -24,10 +32,16 @@ impl DesktopUiBackend {
             cursor_visible: true,
             last_key: KeyCode::Unknown,
             last_char: None,
-            clipboard: ClipboardProvider::new().unwrap(),
+            file_dialog_result: None,
         }
     }
 
+    /// Take the result of the last file dialog, if it hasn't been passed to
+    /// the player yet.
+    pub fn take_file_dialog_result(&mut self) -> Option<Option<DialogFile>> {
+        self.file_dialog_result.take()
+    }
+
     /// Process an input event, and return an event that should be forward to the player, if any.
     pub fn handle_event(&mut self, event: WindowEvent) -> Option<PlayerEvent> {
         // Allow KeyboardInput.modifiers (ModifiersChanged event not functional yet).
@@ -70,13 +84,6 @@ impl DesktopUiBackend {
     }
 }
 
-// TODO: Move link to https://ruffle.rs/faq or similar
-const UNSUPPORTED_CONTENT_MESSAGE: &str = "\
-This content is not yet supported by Ruffle and will likely not run as intended.
-
-See the following link for more info:
-https://github.com/ruffle-rs/ruffle/wiki/Frequently-Asked-Questions-For-Users";
-
 impl UiBackend for DesktopUiBackend {
     fn is_key_down(&self, key: KeyCode) -> bool {
         match key {
@@ -216,22 +223,102 @@ impl UiBackend for DesktopUiBackend {
         self.window.set_cursor_icon(icon);
     }
 
-    fn set_clipboard_content(&mut self, content: String) {
-        self.clipboard.set_contents(content).unwrap();
-    }
-
     fn is_fullscreen(&self) -> bool {
         self.window.fullscreen().is_some()
     }
 
-    fn display_unsupported_message(&self) {
+    fn set_fullscreen(&mut self, is_full: bool) -> bool {
+        self.window.set_fullscreen(if is_full {
+            Some(Fullscreen::Borderless(None))
+        } else {
+            None
+        });
+        true
+    }
+
+    fn display_unsupported_message(&self, messages: &dyn MessageCatalog) {
         message_box_ok(
-            "Ruffle - Unsupported content",
-            UNSUPPORTED_CONTENT_MESSAGE,
+            &messages.text(Message::UnsupportedContentTitle),
+            &messages.text(Message::UnsupportedContent),
             MessageBoxIcon::Warning,
         );
     }
 
+    fn display_script_error(
+        &mut self,
+        error: &ScriptError,
+        messages: &dyn MessageCatalog,
+    ) -> ScriptErrorResponse {
+        let mut message = format!("{}\n", error);
+        if let Some(url) = &error.movie_url {
+            let movie = messages.format(Message::ScriptErrorMovie, &[("url", url.as_str())]);
+            message.push_str(&format!("\n{}\n", movie));
+        }
+        message.push_str(&format!(
+            "\n{}",
+            messages.text(Message::ScriptErrorContinue)
+        ));
+
+        match message_box_yes_no(
+            &messages.text(Message::ScriptErrorTitle),
+            &message,
+            MessageBoxIcon::Error,
+            YesNo::Yes,
+        ) {
+            YesNo::Yes => ScriptErrorResponse::Continue,
+            YesNo::No => ScriptErrorResponse::SuppressFurtherErrors,
+        }
+    }
+
+    fn display_open_file_dialog(&mut self, filters: &[FileFilter]) -> bool {
+        // The dialog can only offer one filter, so offer all of them at once.
+        let patterns: Vec<&str> = filters
+            .iter()
+            .flat_map(|filter| filter.patterns.iter().map(String::as_str))
+            .collect();
+        let description = filters
+            .iter()
+            .map(|filter| filter.description.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let filter = if patterns.is_empty() {
+            None
+        } else {
+            Some((&patterns[..], description.as_str()))
+        };
+
+        let file = open_file_dialog("Open", "", filter).and_then(|path| {
+            let name = Path::new(&path).file_name()?.to_string_lossy().into_owned();
+            match std::fs::read(&path) {
+                Ok(data) => Some(DialogFile { name, data }),
+                Err(e) => {
+                    log::error!("Couldn't read {}: {}", path, e);
+                    None
+                }
+            }
+        });
+        self.file_dialog_result = Some(file);
+        true
+    }
+
+    fn display_save_file_dialog(&mut self, default_name: &str, data: &[u8]) -> bool {
+        let file = save_file_dialog("Save", default_name).and_then(|path| {
+            let name = Path::new(&path).file_name()?.to_string_lossy().into_owned();
+            match std::fs::write(&path, data) {
+                Ok(()) => Some(DialogFile {
+                    name,
+                    data: data.to_vec(),
+                }),
+                Err(e) => {
+                    log::error!("Couldn't write {}: {}", path, e);
+                    None
+                }
+            }
+        });
+        self.file_dialog_result = Some(file);
+        true
+    }
+
     fn message(&self, message: &str) {
         message_box_ok("Ruffle", message, MessageBoxIcon::Info)
     }
@@ -2,14 +2,20 @@ use clap::Clap;
 use image::RgbaImage;
 use indicatif::{ProgressBar, ProgressStyle};
 use ruffle_core::backend::audio::NullAudioBackend;
+use ruffle_core::backend::clipboard::MemoryClipboardBackend;
+use ruffle_core::backend::gamepad::NullGamepadBackend;
+use ruffle_core::backend::local_connection::InProcessLocalConnectionBackend;
 use ruffle_core::backend::locale::NullLocaleBackend;
 use ruffle_core::backend::log::NullLogBackend;
 use ruffle_core::backend::navigator::NullNavigatorBackend;
+use ruffle_core::backend::render::{BitmapFormat, RenderBackend};
+use ruffle_core::backend::socket::NullSocketBackend;
 use ruffle_core::backend::storage::MemoryStorageBackend;
 use ruffle_core::backend::ui::NullUiBackend;
 use ruffle_core::backend::video::SoftwareVideoBackend;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::Player;
+use ruffle_render_software::SoftwareRenderBackend;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use ruffle_render_wgpu::target::TextureTarget;
 use ruffle_render_wgpu::{wgpu, Descriptors, WgpuRenderBackend};
@@ -80,20 +86,31 @@ struct Opt {
     #[clap(long, short, case_insensitive = true, default_value = "high", arg_enum)]
     power: PowerPreference,
 
+    /// Draw on the CPU instead of the graphics device. This works without graphics drivers,
+    /// and draws frames the same way as the regression tests.
+    #[clap(long)]
+    software: bool,
+
     /// Location to store a wgpu trace output
     #[clap(long, parse(from_os_str))]
     #[cfg(feature = "render_trace")]
     trace_path: Option<PathBuf>,
 }
 
+/// The renderer to draw frames with, which is kept between files.
+enum Renderer {
+    Wgpu(Descriptors),
+    Software,
+}
+
 fn take_screenshot(
-    descriptors: Descriptors,
+    renderer: Renderer,
     swf_path: &Path,
     frames: u32,
     skipframes: u32,
     progress: &Option<ProgressBar>,
     size: SizeOpt,
-) -> Result<(Descriptors, Vec<RgbaImage>), Box<dyn std::error::Error>> {
+) -> Result<(Renderer, Vec<RgbaImage>), Box<dyn std::error::Error>> {
     let movie = SwfMovie::from_path(&swf_path)?;
 
     let width = size.width.unwrap_or_else(|| movie.width());
@@ -102,9 +119,15 @@ fn take_screenshot(
     let height = size.height.unwrap_or_else(|| movie.height());
     let height = (height as f32 * size.scale).round() as u32;
 
-    let target = TextureTarget::new(&descriptors.device, (width, height));
+    let renderer: Box<dyn RenderBackend> = match renderer {
+        Renderer::Wgpu(descriptors) => {
+            let target = TextureTarget::new(&descriptors.device, (width, height));
+            Box::new(WgpuRenderBackend::new(descriptors, target)?)
+        }
+        Renderer::Software => Box::new(SoftwareRenderBackend::new(width, height)),
+    };
     let player = Player::new(
-        Box::new(WgpuRenderBackend::new(descriptors, target)?),
+        renderer,
         Box::new(NullAudioBackend::new()),
         Box::new(NullNavigatorBackend::new()),
         Box::new(MemoryStorageBackend::default()),
@@ -112,6 +135,10 @@ fn take_screenshot(
         Box::new(SoftwareVideoBackend::new()),
         Box::new(NullLogBackend::new()),
         Box::new(NullUiBackend::new()),
+        Box::new(MemoryClipboardBackend::new()),
+        Box::new(NullGamepadBackend::new()),
+        Box::new(NullSocketBackend::new()),
+        Box::new(InProcessLocalConnectionBackend::default()),
     )?;
 
     player
@@ -135,12 +162,7 @@ fn take_screenshot(
         if i >= skipframes {
             player.lock().unwrap().render();
             let mut player = player.lock().unwrap();
-            let renderer = player
-                .renderer_mut()
-                .downcast_mut::<WgpuRenderBackend<TextureTarget>>()
-                .unwrap();
-            let target = renderer.target();
-            if let Some(image) = target.capture(renderer.device()) {
+            if let Some(image) = capture(player.renderer_mut().as_mut()) {
                 result.push(image);
             } else {
                 return Err(format!("Unable to capture frame {} of {:?}", i, swf_path).into());
@@ -152,16 +174,31 @@ fn take_screenshot(
         }
     }
 
-    let descriptors = Arc::try_unwrap(player)
+    let renderer = Arc::try_unwrap(player)
         .ok()
         .unwrap()
         .into_inner()?
-        .destroy()
-        .downcast::<WgpuRenderBackend<TextureTarget>>()
-        .ok()
-        .unwrap()
-        .descriptors();
-    Ok((descriptors, result))
+        .destroy();
+    let renderer = match renderer.downcast::<WgpuRenderBackend<TextureTarget>>() {
+        Ok(renderer) => Renderer::Wgpu(renderer.descriptors()),
+        Err(_) => Renderer::Software,
+    };
+    Ok((renderer, result))
+}
+
+/// Read back the frame that `renderer` last drew.
+fn capture(renderer: &mut dyn RenderBackend) -> Option<RgbaImage> {
+    if let Some(renderer) = renderer.downcast_mut::<WgpuRenderBackend<TextureTarget>>() {
+        return renderer.target().capture(renderer.device());
+    }
+
+    let frame = renderer
+        .downcast_mut::<SoftwareRenderBackend>()?
+        .capture_frame();
+    match frame.data {
+        BitmapFormat::Rgba(rgba) => RgbaImage::from_raw(frame.width, frame.height, rgba),
+        BitmapFormat::Rgb(_) => None,
+    }
 }
 
 fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
@@ -194,7 +231,7 @@ fn find_files(root: &Path, with_progress: bool) -> Vec<DirEntry> {
     results
 }
 
-fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn Error>> {
+fn capture_single_swf(renderer: Renderer, opt: &Opt) -> Result<(), Box<dyn Error>> {
     let output = opt.output_path.clone().unwrap_or_else(|| {
         let mut result = PathBuf::new();
         if opt.frames == 1 {
@@ -225,7 +262,7 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
     };
 
     let (_, frames) = take_screenshot(
-        descriptors,
+        renderer,
         &opt.swf,
         opt.frames,
         opt.skipframes,
@@ -271,7 +308,7 @@ fn capture_single_swf(descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn
     Ok(())
 }
 
-fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(), Box<dyn Error>> {
+fn capture_multiple_swfs(mut renderer: Renderer, opt: &Opt) -> Result<(), Box<dyn Error>> {
     let output = opt.output_path.clone().unwrap();
     let files = find_files(&opt.swf, !opt.silent);
 
@@ -290,15 +327,15 @@ fn capture_multiple_swfs(mut descriptors: Descriptors, opt: &Opt) -> Result<(),
     };
 
     for file in &files {
-        let (new_descriptors, frames) = take_screenshot(
-            descriptors,
+        let (new_renderer, frames) = take_screenshot(
+            renderer,
             &file.path(),
             opt.frames,
             opt.skipframes,
             &progress,
             opt.size,
         )?;
-        descriptors = new_descriptors;
+        renderer = new_renderer;
 
         if let Some(progress) = &progress {
             progress.set_message(&file.path().file_stem().unwrap().to_string_lossy());
@@ -372,19 +409,23 @@ fn trace_path(_opt: &Opt) -> Option<&Path> {
 
 fn main() -> Result<(), Box<dyn Error>> {
     let opt: Opt = Opt::parse();
-    let instance = wgpu::Instance::new(opt.graphics.into());
-    let descriptors = WgpuRenderBackend::<TextureTarget>::build_descriptors(
-        opt.graphics.into(),
-        instance,
-        None,
-        opt.power.into(),
-        trace_path(&opt),
-    )?;
+    let renderer = if opt.software {
+        Renderer::Software
+    } else {
+        let instance = wgpu::Instance::new(opt.graphics.into());
+        Renderer::Wgpu(WgpuRenderBackend::<TextureTarget>::build_descriptors(
+            opt.graphics.into(),
+            instance,
+            None,
+            opt.power.into(),
+            trace_path(&opt),
+        )?)
+    };
 
     if opt.swf.is_file() {
-        capture_single_swf(descriptors, &opt)?;
+        capture_single_swf(renderer, &opt)?;
     } else if opt.output_path.is_some() {
-        capture_multiple_swfs(descriptors, &opt)?;
+        capture_multiple_swfs(renderer, &opt)?;
     } else {
         return Err("Output directory is required when exporting multiple files.".into());
     }
@@ -1,7 +1,7 @@
 use ruffle_core::backend::render::{
     swf::{self, CharacterId, GradientInterpolation, GradientSpread},
-    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, JpegTagFormat, MovieLibrary,
-    RenderBackend, ShapeHandle, Transform,
+    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, CommandHandler, CommandList,
+    JpegTagFormat, MovieLibrary, RenderBackend, RenderCapabilities, ShapeHandle, Transform,
 };
 use ruffle_core::color_transform::ColorTransform;
 use ruffle_core::shape_utils::{DistilledShape, DrawCommand};
@@ -10,8 +10,8 @@ use ruffle_web_common::JsResult;
 use std::convert::TryInto;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
-    CanvasGradient, CanvasPattern, CanvasRenderingContext2d, Element, HtmlCanvasElement,
-    HtmlImageElement, Path2d, SvgsvgElement,
+    CanvasGradient, CanvasPattern, CanvasRenderingContext2d, CanvasWindingRule, Element,
+    HtmlCanvasElement, HtmlImageElement, Path2d, SvgsvgElement,
 };
 
 type Error = Box<dyn std::error::Error>;
@@ -30,6 +30,14 @@ pub struct WebCanvasRenderBackend {
     use_color_transform_hack: bool,
     pixelated_property_value: &'static str,
     deactivating_mask: bool,
+    blend_modes: Vec<swf::BlendMode>,
+
+    /// The area of the viewport that the current frame is restricted to, if
+    /// only part of the previous frame is drawn again.
+    scissor: Option<swf::Rectangle>,
+
+    /// The features that the canvas supports.
+    capabilities: RenderCapabilities,
 }
 
 /// Canvas-drawable shape data extracted from an SWF file.
@@ -37,25 +45,11 @@ struct ShapeData(Vec<CanvasDrawCommand>);
 
 struct CanvasColor(String, u8, u8, u8, u8);
 
-/// Convert an f32 to a u8, clamping all out-of-range values to the `u8` range.
-fn clamped_u8_color(v: f32) -> u8 {
-    if v < 0.0 {
-        0
-    } else if v > 255.0 {
-        255
-    } else {
-        v as u8
-    }
-}
-
 impl CanvasColor {
     /// Apply a color transformation to this color.
     fn color_transform(&self, cxform: &ColorTransform) -> CanvasColor {
-        let CanvasColor(_, r, g, b, a) = self;
-        let r = clamped_u8_color(*r as f32 * cxform.r_mult + (cxform.r_add * 255.0));
-        let g = clamped_u8_color(*g as f32 * cxform.g_mult + (cxform.g_add * 255.0));
-        let b = clamped_u8_color(*b as f32 * cxform.b_mult + (cxform.b_add * 255.0));
-        let a = clamped_u8_color(*a as f32 * cxform.a_mult + (cxform.a_add * 255.0));
+        let CanvasColor(_, r, g, b, a) = *self;
+        let [r, g, b, a] = cxform.transform_rgba([r, g, b, a]);
         let colstring = format!("rgba({},{},{},{})", r, g, b, f32::from(a) / 255.0);
         CanvasColor(colstring, r, g, b, a)
     }
@@ -77,6 +71,7 @@ enum CanvasDrawCommand {
     Fill {
         path: Path2d,
         fill_style: CanvasFillStyle,
+        winding: CanvasWindingRule,
     },
 
     /// A command to draw a particular image (such as an SVG)
@@ -212,6 +207,17 @@ impl WebCanvasRenderBackend {
             viewport_height: 0,
             use_color_transform_hack: is_firefox,
             deactivating_mask: false,
+            blend_modes: vec![],
+            scissor: None,
+            capabilities: RenderCapabilities {
+                // TODO: Invert has no composite operation.
+                blend_modes: RenderCapabilities::default()
+                    .blend_modes
+                    .into_iter()
+                    .filter(|&blend_mode| blend_mode != swf::BlendMode::Invert)
+                    .collect(),
+                ..Default::default()
+            },
 
             // For rendering non-smoothed bitmaps.
             // crisp-edges works in Firefox, pixelated works in Chrome (and others)?
@@ -303,6 +309,30 @@ impl WebCanvasRenderBackend {
         }
     }
 
+    /// Set the canvas composite operation for the current blend mode.
+    fn set_blend_mode(&mut self) {
+        let operation = match self.blend_modes.last() {
+            Some(swf::BlendMode::Multiply) => "multiply",
+            Some(swf::BlendMode::Screen) => "screen",
+            Some(swf::BlendMode::Lighten) => "lighten",
+            Some(swf::BlendMode::Darken) => "darken",
+            Some(swf::BlendMode::Difference) => "difference",
+            Some(swf::BlendMode::Add) => "lighter",
+            // Canvas has no subtraction, but a difference only differs where
+            // the result of a subtraction would be clamped.
+            Some(swf::BlendMode::Subtract) => "difference",
+            Some(swf::BlendMode::Alpha) => "destination-in",
+            Some(swf::BlendMode::Erase) => "destination-out",
+            Some(swf::BlendMode::Overlay) => "overlay",
+            Some(swf::BlendMode::HardLight) => "hard-light",
+            // TODO: Invert has no canvas equivalent.
+            _ => "source-over",
+        };
+        self.context
+            .set_global_composite_operation(operation)
+            .warn_on_error();
+    }
+
     #[allow(clippy::float_cmp)]
     #[inline]
     fn set_transform(&mut self, matrix: &Matrix) {
@@ -411,9 +441,57 @@ impl WebCanvasRenderBackend {
             height: height.try_into().expect("JPEG dimensions too large"),
         })
     }
+
+    fn begin_frame(&mut self, clear: Color) {
+        // Reset canvas transform in case it was left in a dirty state.
+        self.context.reset_transform().unwrap();
+
+        let (x, y, width, height) = match &self.scissor {
+            Some(area) => (
+                area.x_min.to_pixels(),
+                area.y_min.to_pixels(),
+                (area.x_max - area.x_min).to_pixels(),
+                (area.y_max - area.y_min).to_pixels(),
+            ),
+            None => (
+                0.0,
+                0.0,
+                self.canvas.width().into(),
+                self.canvas.height().into(),
+            ),
+        };
+
+        // The rest of the canvas still holds the previous frame, so clip
+        // everything drawn in this frame to the area that changed.
+        if self.scissor.is_some() {
+            self.context.save();
+            self.context.begin_path();
+            self.context.rect(x, y, width, height);
+            self.context.clip();
+        }
+
+        let color = format!("rgb({}, {}, {})", clear.r, clear.g, clear.b);
+        self.context.set_fill_style(&color.into());
+        self.context.fill_rect(x, y, width, height);
+
+        self.deactivating_mask = false;
+        self.blend_modes.clear();
+    }
+
+    fn end_frame(&mut self) {
+        if self.scissor.is_some() {
+            self.context.restore();
+        }
+    }
 }
 
 impl RenderBackend for WebCanvasRenderBackend {
+    fn submit_frame(&mut self, clear: Color, commands: CommandList) {
+        self.begin_frame(clear);
+        commands.execute(self);
+        self.end_frame();
+    }
+
     fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
         self.viewport_width = width;
         self.viewport_height = height;
@@ -519,36 +597,127 @@ impl RenderBackend for WebCanvasRenderBackend {
         })
     }
 
-    fn begin_frame(&mut self, clear: Color) {
-        // Reset canvas transform in case it was left in a dirty state.
-        self.context.reset_transform().unwrap();
+    fn supports_partial_redraw(&self) -> bool {
+        true
+    }
 
-        let width = self.canvas.width();
-        let height = self.canvas.height();
+    fn set_scissor(&mut self, area: Option<swf::Rectangle>) {
+        self.scissor = area;
+    }
 
-        let color = format!("rgb({}, {}, {})", clear.r, clear.g, clear.b);
-        self.context.set_fill_style(&color.into());
-        self.context
-            .fill_rect(0.0, 0.0, width.into(), height.into());
+    fn capabilities(&self) -> &RenderCapabilities {
+        &self.capabilities
+    }
 
-        self.deactivating_mask = false;
+    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        let window = web_sys::window().unwrap();
+        let document = window.document().unwrap();
+
+        let canvas: HtmlCanvasElement = document
+            .create_element("canvas")
+            .unwrap()
+            .dyn_into()
+            .unwrap();
+
+        let context: CanvasRenderingContext2d = canvas
+            .get_context("2d")
+            .unwrap()
+            .unwrap()
+            .dyn_into()
+            .unwrap();
+
+        let bitmap = &self.bitmaps[bitmap.0];
+
+        canvas.set_width(bitmap.width);
+        canvas.set_height(bitmap.height);
+
+        context
+            .draw_image_with_html_image_element(&bitmap.image, 0.0, 0.0)
+            .unwrap();
+
+        if let Ok(bitmap_pixels) =
+            context.get_image_data(0.0, 0.0, bitmap.width as f64, bitmap.height as f64)
+        {
+            Some(Bitmap {
+                width: bitmap.width,
+                height: bitmap.height,
+                data: BitmapFormat::Rgba(bitmap_pixels.data().to_vec()),
+            })
+        } else {
+            None
+        }
     }
 
-    fn end_frame(&mut self) {
-        // Noop
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap_raw(Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            })?
+            .handle)
     }
 
-    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, _smoothing: bool) {
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let png = Self::bitmap_to_png_data_uri(Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        })?;
+
+        let image = HtmlImageElement::new().unwrap();
+        image.set_src(&png);
+
+        self.bitmaps.insert(
+            handle.0,
+            BitmapData {
+                image,
+                width,
+                height,
+                data: png,
+            },
+        );
+
+        Ok(handle)
+    }
+
+    fn unregister_bitmap(&mut self, handle: BitmapHandle) {
+        // Handles index the list, so the slot stays behind, empty.
+        if let Some(bitmap) = self.bitmaps.get_mut(handle.0) {
+            bitmap.image = HtmlImageElement::new().unwrap();
+            bitmap.width = 0;
+            bitmap.height = 0;
+            bitmap.data = String::new();
+        }
+    }
+}
+
+impl CommandHandler for WebCanvasRenderBackend {
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
         if self.deactivating_mask {
             return;
         }
 
+        self.set_blend_mode();
         self.set_transform(&transform.matrix);
         self.set_color_filter(transform);
         if let Some(bitmap) = self.bitmaps.get(bitmap.0) {
+            self.context.set_image_smoothing_enabled(smoothing);
             let _ = self
                 .context
                 .draw_image_with_html_image_element(&bitmap.image, 0.0, 0.0);
+            self.context.set_image_smoothing_enabled(true);
         }
         self.clear_color_filter();
     }
@@ -558,11 +727,16 @@ impl RenderBackend for WebCanvasRenderBackend {
             return;
         }
 
+        self.set_blend_mode();
         self.set_transform(&transform.matrix);
         if let Some(shape) = self.shapes.get(shape.0) {
             for command in shape.0.iter() {
                 match command {
-                    CanvasDrawCommand::Fill { path, fill_style } => {
+                    CanvasDrawCommand::Fill {
+                        path,
+                        fill_style,
+                        winding,
+                    } => {
                         let xformed_fill_style =
                             fill_style.color_transform(&transform.color_transform);
                         if xformed_fill_style.is_none() {
@@ -577,7 +751,7 @@ impl RenderBackend for WebCanvasRenderBackend {
                             CanvasFillStyle::Pattern(patt) => self.context.set_fill_style(patt),
                         };
 
-                        self.context.fill_with_path_2d(&path);
+                        self.context.fill_with_path_2d_and_winding(&path, *winding);
 
                         if xformed_fill_style.is_none() {
                             self.clear_color_filter();
@@ -622,6 +796,7 @@ impl RenderBackend for WebCanvasRenderBackend {
             return;
         }
 
+        self.set_blend_mode();
         self.set_transform(matrix);
         self.clear_color_filter();
 
@@ -645,13 +820,16 @@ impl RenderBackend for WebCanvasRenderBackend {
         // We render the masker clips to the first render target.
         self.push_render_target();
     }
+
     fn activate_mask(&mut self) {
         // We render the maskee clips to the second render target.
         self.push_render_target();
     }
+
     fn deactivate_mask(&mut self) {
         self.deactivating_mask = true;
     }
+
     fn pop_mask(&mut self) {
         self.deactivating_mask = false;
 
@@ -689,93 +867,19 @@ impl RenderBackend for WebCanvasRenderBackend {
         maskee_context.set_filter("none");
 
         // Finally, we draw the finalized masked onto the main canvas.
+        self.set_blend_mode();
         self.context.reset_transform().warn_on_error();
         self.context
             .draw_image_with_html_canvas_element(&maskee_canvas, 0.0, 0.0)
             .unwrap();
     }
 
-    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
-        let window = web_sys::window().unwrap();
-        let document = window.document().unwrap();
-
-        let canvas: HtmlCanvasElement = document
-            .create_element("canvas")
-            .unwrap()
-            .dyn_into()
-            .unwrap();
-
-        let context: CanvasRenderingContext2d = canvas
-            .get_context("2d")
-            .unwrap()
-            .unwrap()
-            .dyn_into()
-            .unwrap();
-
-        let bitmap = &self.bitmaps[bitmap.0];
-
-        canvas.set_width(bitmap.width);
-        canvas.set_height(bitmap.height);
-
-        context
-            .draw_image_with_html_image_element(&bitmap.image, 0.0, 0.0)
-            .unwrap();
-
-        if let Ok(bitmap_pixels) =
-            context.get_image_data(0.0, 0.0, bitmap.width as f64, bitmap.height as f64)
-        {
-            Some(Bitmap {
-                width: bitmap.width,
-                height: bitmap.height,
-                data: BitmapFormat::Rgba(bitmap_pixels.data().to_vec()),
-            })
-        } else {
-            None
-        }
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        self.blend_modes.push(blend_mode);
     }
 
-    fn register_bitmap_raw(
-        &mut self,
-        width: u32,
-        height: u32,
-        rgba: Vec<u8>,
-    ) -> Result<BitmapHandle, Error> {
-        Ok(self
-            .register_bitmap_raw(Bitmap {
-                width,
-                height,
-                data: BitmapFormat::Rgba(rgba),
-            })?
-            .handle)
-    }
-
-    fn update_texture(
-        &mut self,
-        handle: BitmapHandle,
-        width: u32,
-        height: u32,
-        rgba: Vec<u8>,
-    ) -> Result<BitmapHandle, Error> {
-        let png = Self::bitmap_to_png_data_uri(Bitmap {
-            width,
-            height,
-            data: BitmapFormat::Rgba(rgba),
-        })?;
-
-        let image = HtmlImageElement::new().unwrap();
-        image.set_src(&png);
-
-        self.bitmaps.insert(
-            handle.0,
-            BitmapData {
-                image,
-                width,
-                height,
-                data: png,
-            },
-        );
-
-        Ok(handle)
+    fn pop_blend_mode(&mut self) {
+        self.blend_modes.pop();
     }
 }
 
@@ -787,7 +891,7 @@ fn swf_shape_to_svg(
     pixelated_property_value: &str,
 ) -> ShapeData {
     use fnv::FnvHashSet;
-    use ruffle_core::shape_utils::DrawPath;
+    use ruffle_core::shape_utils::{DrawPath, WindingRule};
     use svg::node::element::{
         path::Data, Definitions, Filter, Image, LinearGradient, Path as SvgPath, Pattern,
         RadialGradient, Stop,
@@ -836,8 +940,15 @@ fn swf_shape_to_svg(
     let mut svg_paths = vec![];
     for path in shape.paths {
         match path {
-            DrawPath::Fill { style, commands } => {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding,
+            } => {
                 let mut svg_path = SvgPath::new();
+                if winding == WindingRule::EvenOdd {
+                    svg_path = svg_path.set("fill-rule", "evenodd");
+                }
 
                 let fill = match style {
                     FillStyle::Color(Color { r, g, b, a }) => {
@@ -1268,7 +1379,7 @@ fn swf_shape_to_canvas_commands(
     _pixelated_property_value: &str,
     context: &CanvasRenderingContext2d,
 ) -> Option<ShapeData> {
-    use ruffle_core::shape_utils::DrawPath;
+    use ruffle_core::shape_utils::{DrawPath, WindingRule};
     use swf::{FillStyle, LineCapStyle, LineJoinStyle};
 
     // Some browsers will vomit if you try to load/draw an image with 0 width/height.
@@ -1302,7 +1413,11 @@ fn swf_shape_to_canvas_commands(
 
     for path in &shape.paths {
         match path {
-            DrawPath::Fill { style, commands } => {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding,
+            } => {
                 let fill_style = match style {
                     FillStyle::Color(Color { r, g, b, a }) => CanvasFillStyle::Color(CanvasColor(
                         format!("rgba({},{},{},{})", r, g, b, f32::from(*a) / 255.0),
@@ -1381,9 +1496,15 @@ fn swf_shape_to_canvas_commands(
                     &bounds_viewbox_matrix,
                 );
 
-                canvas_data
-                    .0
-                    .push(CanvasDrawCommand::Fill { path, fill_style });
+                let winding = match winding {
+                    WindingRule::EvenOdd => CanvasWindingRule::Evenodd,
+                    WindingRule::NonZero => CanvasWindingRule::Nonzero,
+                };
+                canvas_data.0.push(CanvasDrawCommand::Fill {
+                    path,
+                    fill_style,
+                    winding,
+                });
             }
             DrawPath::Stroke {
                 style,
@@ -6,7 +6,7 @@ use lyon::tessellation::{
 };
 use lyon::tessellation::{FillOptions, StrokeOptions};
 use ruffle_core::backend::render::{srgb_to_linear, swf, BitmapHandle};
-use ruffle_core::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use ruffle_core::shape_utils::{DistilledShape, DrawCommand, DrawPath, WindingRule};
 
 pub struct ShapeTessellator {
     fill_tess: FillTessellator,
@@ -44,7 +44,11 @@ impl ShapeTessellator {
 
         for path in shape.paths {
             match path {
-                DrawPath::Fill { style, commands } => match style {
+                DrawPath::Fill {
+                    style,
+                    commands,
+                    winding,
+                } => match style {
                     swf::FillStyle::Color(color) => {
                         let mut buffers_builder = BuffersBuilder::new(
                             &mut lyon_mesh,
@@ -55,7 +59,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(winding),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -75,7 +79,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(winding),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -105,7 +109,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(winding),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -138,7 +142,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(winding),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -173,7 +177,7 @@ impl ShapeTessellator {
 
                         if let Err(e) = self.fill_tess.tessellate_path(
                             &ruffle_path_to_lyon_path(commands, true),
-                            &FillOptions::even_odd(),
+                            &fill_options(winding),
                             &mut buffers_builder,
                         ) {
                             // This may just be a degenerate path; skip it.
@@ -480,3 +484,11 @@ pub enum GradientType {
     Radial,
     Focal,
 }
+
+/// The tessellator options that fill a path with the given winding rule.
+fn fill_options(winding: WindingRule) -> FillOptions {
+    match winding {
+        WindingRule::EvenOdd => FillOptions::even_odd(),
+        WindingRule::NonZero => FillOptions::non_zero(),
+    }
+}
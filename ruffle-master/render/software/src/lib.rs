@@ -0,0 +1,530 @@
+//! A render backend that draws on the CPU, without a GPU or a browser.
+//!
+//! Each frame is rasterized into a bitmap in memory with the same software
+//! rasterizer that backs `BitmapData.draw`, so it draws anything that can do,
+//! and is just as slow. Nothing is shown on screen; the finished frame is read
+//! back with `capture_frame`, which makes this backend useful for comparing
+//! frames against reference images in tests, and for running content on
+//! servers or devices without graphics drivers.
+
+use ruffle_core::backend::render::{
+    decode_define_bits_jpeg, decode_define_bits_lossless, glue_tables_to_jpeg,
+    unmultiply_alpha_rgba, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, MovieLibrary,
+    RenderBackend, RenderCapabilities, ShapeHandle, Transform,
+};
+use ruffle_core::bitmap::bitmap_data::{BitmapData, Color as Pixel};
+use ruffle_core::bitmap::rasterizer::Rasterizer;
+use ruffle_core::commands::{CommandHandler, CommandList};
+use ruffle_core::shape_utils::{DistilledShape, DrawCommand, DrawPath, WindingRule};
+use ruffle_core::swf::{self, BlendMode, FillStyle, LineStyle, Matrix, Twips};
+
+type Error = Box<dyn std::error::Error>;
+
+/// A path of a registered shape, which owns its style.
+enum ShapePath {
+    Fill {
+        style: FillStyle,
+        commands: Vec<DrawCommand>,
+        winding: WindingRule,
+
+        /// The bitmap drawn by a bitmap fill, if it could be found.
+        bitmap: Option<BitmapHandle>,
+    },
+    Stroke {
+        style: LineStyle,
+        commands: Vec<DrawCommand>,
+    },
+}
+
+/// How far a mask has been drawn, between `push_mask` and `pop_mask`.
+///
+/// Like the canvas backend, masks are drawn with extra render targets: the
+/// mask is drawn into one, and the content it masks into another, which is
+/// then copied onto the target below wherever the mask was drawn.
+enum MaskState {
+    /// The mask itself is being drawn into this bitmap.
+    DrawingMask(BitmapData),
+
+    /// The masked content is being drawn into `content`.
+    DrawingMaskee {
+        mask: BitmapData,
+        content: BitmapData,
+    },
+
+    /// The mask is being drawn again to remove it, which is ignored.
+    ClearingMask,
+}
+
+pub struct SoftwareRenderBackend {
+    viewport_width: u32,
+    viewport_height: u32,
+
+    /// The frame that is being, or was last, drawn.
+    frame: BitmapData,
+
+    shapes: Vec<Vec<ShapePath>>,
+    bitmaps: Vec<BitmapData>,
+    mask_stack: Vec<MaskState>,
+    blend_modes: Vec<BlendMode>,
+    capabilities: RenderCapabilities,
+}
+
+impl SoftwareRenderBackend {
+    pub fn new(viewport_width: u32, viewport_height: u32) -> Self {
+        let mut frame = BitmapData::default();
+        frame.init_pixels(viewport_width, viewport_height, 0, true);
+
+        Self {
+            viewport_width,
+            viewport_height,
+            frame,
+            shapes: Vec::new(),
+            bitmaps: Vec::new(),
+            mask_stack: Vec::new(),
+            blend_modes: Vec::new(),
+            capabilities: Default::default(),
+        }
+    }
+
+    /// The last frame that was drawn, as unmultiplied RGBA.
+    pub fn capture_frame(&self) -> Bitmap {
+        let mut rgba = self.frame.pixels_rgba();
+        unmultiply_alpha_rgba(&mut rgba);
+
+        Bitmap {
+            width: self.frame.width(),
+            height: self.frame.height(),
+            data: BitmapFormat::Rgba(rgba),
+        }
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> BitmapInfo {
+        let info = BitmapInfo {
+            handle: BitmapHandle(self.bitmaps.len()),
+            width: bitmap.width as u16,
+            height: bitmap.height as u16,
+        };
+        self.bitmaps.push(to_bitmap_data(bitmap));
+        info
+    }
+
+    /// A transparent bitmap the size of the viewport, to draw a mask or the
+    /// content it masks into.
+    fn new_layer(&self) -> BitmapData {
+        let mut layer = BitmapData::default();
+        layer.init_pixels(self.frame.width(), self.frame.height(), 0, true);
+        layer
+    }
+
+    /// Draw into the current render target, if drawing isn't being ignored.
+    fn draw(&mut self, transform: &Transform, draw: impl FnOnce(&mut Rasterizer<'_>)) {
+        let blend_mode = self
+            .blend_modes
+            .last()
+            .copied()
+            .unwrap_or(BlendMode::Normal);
+        if let Some(target) = current_target(&mut self.frame, &mut self.mask_stack) {
+            let mut rasterizer = Rasterizer::new(target, transform, None, blend_mode);
+            draw(&mut rasterizer);
+        }
+    }
+}
+
+impl RenderBackend for SoftwareRenderBackend {
+    fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        self.viewport_width = width;
+        self.viewport_height = height;
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        library: Option<&MovieLibrary<'_>>,
+    ) -> ShapeHandle {
+        let handle = ShapeHandle(self.shapes.len());
+        self.shapes.push(to_shape_paths(shape, library));
+        handle
+    }
+
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        library: Option<&MovieLibrary<'_>>,
+        handle: ShapeHandle,
+    ) {
+        self.shapes[handle.0] = to_shape_paths(shape, library);
+    }
+
+    fn register_glyph_shape(&mut self, glyph: &swf::Glyph) -> ShapeHandle {
+        let shape = ruffle_core::shape_utils::swf_glyph_to_shape(glyph);
+        self.register_shape((&shape).into(), None)
+    }
+
+    fn register_bitmap_jpeg(
+        &mut self,
+        data: &[u8],
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Error> {
+        let data = glue_tables_to_jpeg(data, jpeg_tables);
+        self.register_bitmap_jpeg_2(&data[..])
+    }
+
+    fn register_bitmap_jpeg_2(&mut self, data: &[u8]) -> Result<BitmapInfo, Error> {
+        let bitmap = decode_define_bits_jpeg(data, None)?;
+        Ok(self.register_bitmap(bitmap))
+    }
+
+    fn register_bitmap_jpeg_3(
+        &mut self,
+        jpeg_data: &[u8],
+        alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
+        Ok(self.register_bitmap(bitmap))
+    }
+
+    fn register_bitmap_png(
+        &mut self,
+        swf_tag: &swf::DefineBitsLossless,
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = decode_define_bits_lossless(swf_tag)?;
+        Ok(self.register_bitmap(bitmap))
+    }
+
+    fn submit_frame(&mut self, clear: Color, commands: CommandList) {
+        let clear = Pixel::argb(clear.a, clear.r, clear.g, clear.b);
+        self.frame.init_pixels(
+            self.viewport_width,
+            self.viewport_height,
+            clear.into(),
+            true,
+        );
+        self.mask_stack.clear();
+        self.blend_modes.clear();
+
+        commands.execute(self);
+    }
+
+    fn capabilities(&self) -> &RenderCapabilities {
+        &self.capabilities
+    }
+
+    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        let bitmap = self.bitmaps.get(bitmap.0)?;
+        Some(Bitmap {
+            width: bitmap.width(),
+            height: bitmap.height(),
+            data: BitmapFormat::Rgba(bitmap.pixels_rgba()),
+        })
+    }
+
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap(Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            })
+            .handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let bitmap = to_bitmap_data(Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        });
+        match self.bitmaps.get_mut(handle.0) {
+            Some(existing) => *existing = bitmap,
+            None => return Err("Unknown bitmap handle".into()),
+        }
+
+        Ok(handle)
+    }
+
+    fn unregister_bitmap(&mut self, handle: BitmapHandle) {
+        // Handles index the list, so the slot stays behind, empty.
+        if let Some(bitmap) = self.bitmaps.get_mut(handle.0) {
+            *bitmap = BitmapData::default();
+        }
+    }
+}
+
+impl CommandHandler for SoftwareRenderBackend {
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
+        // Take the bitmap out while it is drawn, as the target is borrowed
+        // from `self` too.
+        let source = match self.bitmaps.get_mut(bitmap.0) {
+            Some(source) => std::mem::take(source),
+            None => return,
+        };
+        self.draw(transform, |rasterizer| {
+            rasterizer.draw_bitmap(&source, smoothing)
+        });
+        self.bitmaps[bitmap.0] = source;
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        // Take the shape and the bitmaps of its fills out while it is drawn,
+        // as the target is borrowed from `self` too.
+        let paths = match self.shapes.get_mut(shape.0) {
+            Some(paths) => std::mem::take(paths),
+            None => return,
+        };
+        let bitmaps = std::mem::take(&mut self.bitmaps);
+        self.draw(transform, |rasterizer| {
+            for path in &paths {
+                match path {
+                    ShapePath::Fill {
+                        style,
+                        commands,
+                        winding,
+                        bitmap,
+                    } => {
+                        let bitmap = bitmap.and_then(|bitmap| bitmaps.get(bitmap.0));
+                        rasterizer.draw_fill(style, commands, *winding, bitmap)
+                    }
+                    ShapePath::Stroke { style, commands } => {
+                        rasterizer.draw_stroke(style, commands)
+                    }
+                }
+            }
+        });
+        self.bitmaps = bitmaps;
+        self.shapes[shape.0] = paths;
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+        // `matrix` maps a single pixel onto the rectangle.
+        let style = FillStyle::Color(color);
+        let transform = Transform {
+            matrix: *matrix,
+            ..Default::default()
+        };
+        self.draw(&transform, |rasterizer| {
+            rasterizer.draw_fill(&style, &unit_square(), WindingRule::EvenOdd, None)
+        });
+    }
+
+    fn push_mask(&mut self) {
+        let mask = self.new_layer();
+        self.mask_stack.push(MaskState::DrawingMask(mask));
+    }
+
+    fn activate_mask(&mut self) {
+        if let Some(MaskState::DrawingMask(mask)) = self.mask_stack.pop() {
+            let content = self.new_layer();
+            self.mask_stack
+                .push(MaskState::DrawingMaskee { mask, content });
+        }
+    }
+
+    fn deactivate_mask(&mut self) {
+        if let Some(MaskState::DrawingMaskee { mask, content }) = self.mask_stack.pop() {
+            if let Some(target) = current_target(&mut self.frame, &mut self.mask_stack) {
+                composite_masked(target, &content, &mask);
+            }
+            self.mask_stack.push(MaskState::ClearingMask);
+        }
+    }
+
+    fn pop_mask(&mut self) {
+        self.mask_stack.pop();
+    }
+
+    fn push_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_modes.push(blend_mode);
+    }
+
+    fn pop_blend_mode(&mut self) {
+        self.blend_modes.pop();
+    }
+}
+
+/// The bitmap that drawing currently goes into, or `None` while a mask is
+/// being cleared.
+fn current_target<'a>(
+    frame: &'a mut BitmapData,
+    mask_stack: &'a mut [MaskState],
+) -> Option<&'a mut BitmapData> {
+    match mask_stack.last_mut() {
+        None => Some(frame),
+        Some(MaskState::DrawingMask(mask)) => Some(mask),
+        Some(MaskState::DrawingMaskee { content, .. }) => Some(content),
+        Some(MaskState::ClearingMask) => None,
+    }
+}
+
+/// Draw masked content over `target`, wherever the mask covers.
+///
+/// Flash ignores the alpha of masks, so any pixel that the mask drew to at
+/// all shows the content in full.
+fn composite_masked(target: &mut BitmapData, content: &BitmapData, mask: &BitmapData) {
+    let pixels = target
+        .pixels()
+        .iter()
+        .zip(content.pixels())
+        .zip(mask.pixels())
+        .map(|((dest, content), mask)| {
+            if mask.alpha() > 0 {
+                dest.blend_over(content)
+            } else {
+                *dest
+            }
+        })
+        .collect();
+    target.set_pixels(pixels);
+    target.set_dirty(true);
+}
+
+/// Convert a decoded bitmap, which has premultiplied alpha, to bitmap data.
+fn to_bitmap_data(bitmap: Bitmap) -> BitmapData {
+    let mut bitmap_data = BitmapData::default();
+    bitmap_data.init_pixels(bitmap.width, bitmap.height, 0, true);
+    let pixels: Vec<i32> = bitmap.data.into();
+    bitmap_data.set_pixels(pixels.into_iter().map(Pixel::from).collect());
+    bitmap_data
+}
+
+fn to_shape_paths(shape: DistilledShape, library: Option<&MovieLibrary<'_>>) -> Vec<ShapePath> {
+    shape
+        .paths
+        .into_iter()
+        .map(|path| match path {
+            DrawPath::Fill {
+                style,
+                commands,
+                winding,
+            } => {
+                let bitmap = match style {
+                    FillStyle::Bitmap { id, .. } => {
+                        let bitmap = library
+                            .and_then(|library| library.get_bitmap(*id))
+                            .and_then(|bitmap| bitmap.bitmap_handle());
+                        if bitmap.is_none() {
+                            log::error!("Couldn't fill shape with unknown bitmap {}", id);
+                        }
+                        bitmap
+                    }
+                    _ => None,
+                };
+                ShapePath::Fill {
+                    style: style.clone(),
+                    commands,
+                    winding,
+                    bitmap,
+                }
+            }
+            DrawPath::Stroke {
+                style, commands, ..
+            } => ShapePath::Stroke {
+                style: style.clone(),
+                commands,
+            },
+        })
+        .collect()
+}
+
+/// A square one pixel wide, from the origin.
+fn unit_square() -> Vec<DrawCommand> {
+    let size = Twips::from_pixels(1.0);
+    vec![
+        DrawCommand::MoveTo {
+            x: Twips::zero(),
+            y: Twips::zero(),
+        },
+        DrawCommand::LineTo {
+            x: size,
+            y: Twips::zero(),
+        },
+        DrawCommand::LineTo { x: size, y: size },
+        DrawCommand::LineTo {
+            x: Twips::zero(),
+            y: size,
+        },
+        DrawCommand::LineTo {
+            x: Twips::zero(),
+            y: Twips::zero(),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Color = Color {
+        r: 255,
+        g: 255,
+        b: 255,
+        a: 255,
+    };
+    const RED: Color = Color {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+
+    /// A matrix that maps a single pixel onto a rectangle.
+    fn rect(x: f64, y: f64, width: f32, height: f32) -> Matrix {
+        Matrix {
+            tx: Twips::from_pixels(x),
+            ty: Twips::from_pixels(y),
+            ..Matrix::scale(width, height)
+        }
+    }
+
+    fn pixel(frame: &Bitmap, x: u32, y: u32) -> [u8; 4] {
+        let i = ((x + y * frame.width) * 4) as usize;
+        match &frame.data {
+            BitmapFormat::Rgba(rgba) => [rgba[i], rgba[i + 1], rgba[i + 2], rgba[i + 3]],
+            BitmapFormat::Rgb(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn rect_is_drawn_over_clear_color() {
+        let mut renderer = SoftwareRenderBackend::new(4, 4);
+        let mut commands = CommandList::new();
+        commands.draw_rect(RED, &rect(1.0, 1.0, 2.0, 2.0));
+        renderer.submit_frame(WHITE, commands);
+
+        let frame = renderer.capture_frame();
+        assert_eq!(pixel(&frame, 0, 0), [255, 255, 255, 255]);
+        assert_eq!(pixel(&frame, 1, 1), [255, 0, 0, 255]);
+        assert_eq!(pixel(&frame, 2, 2), [255, 0, 0, 255]);
+        assert_eq!(pixel(&frame, 3, 3), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn content_is_clipped_to_mask() {
+        let mut renderer = SoftwareRenderBackend::new(4, 4);
+        let mask = rect(0.0, 0.0, 2.0, 2.0);
+        let mut commands = CommandList::new();
+        commands.push_mask();
+        commands.draw_rect(WHITE, &mask);
+        commands.activate_mask();
+        commands.draw_rect(RED, &rect(0.0, 0.0, 4.0, 4.0));
+        commands.deactivate_mask();
+        commands.draw_rect(WHITE, &mask);
+        commands.pop_mask();
+        renderer.submit_frame(WHITE, commands);
+
+        let frame = renderer.capture_frame();
+        assert_eq!(pixel(&frame, 1, 1), [255, 0, 0, 255]);
+        assert_eq!(pixel(&frame, 2, 2), [255, 255, 255, 255]);
+        assert_eq!(pixel(&frame, 0, 3), [255, 255, 255, 255]);
+    }
+}
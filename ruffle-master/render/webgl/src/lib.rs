@@ -1,6 +1,7 @@
 use bytemuck::{Pod, Zeroable};
 use ruffle_core::backend::render::{
-    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, MovieLibrary, RenderBackend,
+    fit_bitmap_to_size, split_bitmap_into_tiles, Bitmap, BitmapFormat, BitmapHandle, BitmapInfo,
+    Color, CommandHandler, CommandList, MovieLibrary, RenderBackend, RenderCapabilities,
     ShapeHandle, Transform,
 };
 use ruffle_core::shape_utils::DistilledShape;
@@ -84,7 +85,15 @@ pub struct WebGlRenderBackend {
     mask_state_dirty: bool,
 
     active_program: *const ShaderProgram,
-    blend_func: (u32, u32),
+    blend_func: (u32, u32, u32),
+    blend_modes: Vec<swf::BlendMode>,
+
+    /// Whether the `MIN` and `MAX` blend equations are available, which are
+    /// needed for the darken and lighten blend modes.
+    has_blend_minmax: bool,
+
+    /// The features that this WebGL context supports.
+    capabilities: RenderCapabilities,
     mult_color: Option<[f32; 4]>,
     add_color: Option<[f32; 4]>,
 
@@ -179,6 +188,10 @@ impl WebGlRenderBackend {
             }
         };
 
+        // WebGL1 needs an extension for the `MIN` and `MAX` blend equations.
+        let has_blend_minmax =
+            gl2.is_some() || matches!(gl.get_extension("EXT_blend_minmax"), Ok(Some(_)));
+
         // Get WebGL driver info.
         let driver_info = if gl.get_extension("WEBGL_debug_renderer_info").is_ok() {
             gl.get_parameter(WebglDebugRendererInfo::UNMASKED_RENDERER_WEBGL)
@@ -191,6 +204,28 @@ impl WebGlRenderBackend {
 
         log::info!("WebGL graphics driver: {}", driver_info);
 
+        let max_texture_size = gl
+            .get_parameter(Gl::MAX_TEXTURE_SIZE)
+            .ok()
+            .and_then(|val| val.as_f64())
+            .map_or(2048, |size| size as u32);
+        let mut blend_modes = vec![
+            swf::BlendMode::Multiply,
+            swf::BlendMode::Screen,
+            swf::BlendMode::Add,
+            swf::BlendMode::Subtract,
+            swf::BlendMode::Alpha,
+            swf::BlendMode::Erase,
+        ];
+        if has_blend_minmax {
+            blend_modes.extend_from_slice(&[swf::BlendMode::Lighten, swf::BlendMode::Darken]);
+        }
+        let capabilities = RenderCapabilities {
+            max_texture_size,
+            supports_filters: true,
+            blend_modes,
+        };
+
         let color_vertex = Self::compile_shader(&gl, Gl::VERTEX_SHADER, COLOR_VERTEX_GLSL)?;
         let texture_vertex = Self::compile_shader(&gl, Gl::VERTEX_SHADER, TEXTURE_VERTEX_GLSL)?;
         let color_fragment = Self::compile_shader(&gl, Gl::FRAGMENT_SHADER, COLOR_FRAGMENT_GLSL)?;
@@ -237,7 +272,10 @@ impl WebGlRenderBackend {
             mask_state_dirty: true,
 
             active_program: std::ptr::null(),
-            blend_func: (Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA),
+            blend_func: (Gl::FUNC_ADD, Gl::SRC_ALPHA, Gl::ONE_MINUS_SRC_ALPHA),
+            blend_modes: vec![],
+            has_blend_minmax,
+            capabilities,
             mult_color: None,
             add_color: None,
             bitmap_registry: HashMap::new(),
@@ -626,6 +664,27 @@ impl WebGlRenderBackend {
         };
     }
 
+    /// The blend equation and factors used to draw with the current blend
+    /// mode, given those used to draw normally.
+    ///
+    /// Only programs that output premultiplied alpha blend exactly; others are
+    /// exact where they are opaque.
+    fn blend_func(&self, src_blend: u32, dst_blend: u32) -> (u32, u32, u32) {
+        match self.blend_modes.last() {
+            Some(swf::BlendMode::Add) => (Gl::FUNC_ADD, src_blend, Gl::ONE),
+            Some(swf::BlendMode::Subtract) => (Gl::FUNC_REVERSE_SUBTRACT, src_blend, Gl::ONE),
+            Some(swf::BlendMode::Multiply) => {
+                (Gl::FUNC_ADD, Gl::DST_COLOR, Gl::ONE_MINUS_SRC_ALPHA)
+            }
+            Some(swf::BlendMode::Screen) => (Gl::FUNC_ADD, src_blend, Gl::ONE_MINUS_SRC_COLOR),
+            Some(swf::BlendMode::Lighten) if self.has_blend_minmax => (Gl2::MAX, Gl::ONE, Gl::ONE),
+            Some(swf::BlendMode::Darken) if self.has_blend_minmax => (Gl2::MIN, Gl::ONE, Gl::ONE),
+            Some(swf::BlendMode::Alpha) => (Gl::FUNC_ADD, Gl::ZERO, Gl::SRC_ALPHA),
+            Some(swf::BlendMode::Erase) => (Gl::FUNC_ADD, Gl::ZERO, Gl::ONE_MINUS_SRC_ALPHA),
+            _ => (Gl::FUNC_ADD, src_blend, dst_blend),
+        }
+    }
+
     fn set_stencil_state(&mut self) {
         // Set stencil state for masking, if necessary.
         if self.mask_state_dirty {
@@ -658,49 +717,27 @@ impl WebGlRenderBackend {
     }
 
     fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapInfo, Error> {
-        let texture = self.gl.create_texture().unwrap();
-        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
-        match &bitmap.data {
-            BitmapFormat::Rgb(data) => self
-                .gl
-                .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
-                    Gl::TEXTURE_2D,
-                    0,
-                    Gl::RGB as i32,
-                    bitmap.width as i32,
-                    bitmap.height as i32,
-                    0,
-                    Gl::RGB,
-                    Gl::UNSIGNED_BYTE,
-                    Some(data),
-                )
-                .into_js_result()?,
-            BitmapFormat::Rgba(data) => self
-                .gl
-                .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
-                    Gl::TEXTURE_2D,
-                    0,
-                    Gl::RGBA as i32,
-                    bitmap.width as i32,
-                    bitmap.height as i32,
-                    0,
-                    Gl::RGBA,
-                    Gl::UNSIGNED_BYTE,
-                    Some(data),
-                )
-                .into_js_result()?,
+        let max_size = self.capabilities.max_texture_size;
+
+        // Bitmap fills are drawn with normalized texture coordinates, so a
+        // bitmap that is too large can be uploaded at a lower resolution for
+        // them. Drawing the bitmap itself uses full resolution tiles instead.
+        let scaled = fit_bitmap_to_size(&bitmap, max_size);
+        let (texture, has_mipmaps) = self.create_texture(scaled.as_ref().unwrap_or(&bitmap))?;
+
+        let mut tiles = Vec::new();
+        for tile in split_bitmap_into_tiles(&bitmap, max_size).unwrap_or_default() {
+            let (texture, has_mipmaps) = self.create_texture(&tile.bitmap)?;
+            tiles.push(TextureTile {
+                x: tile.x,
+                y: tile.y,
+                width: tile.bitmap.width,
+                height: tile.bitmap.height,
+                has_mipmaps,
+                texture,
+            });
         }
 
-        // You must set the texture parameters for non-power-of-2 textures to function in WebGL1.
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, Gl::CLAMP_TO_EDGE as i32);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, Gl::CLAMP_TO_EDGE as i32);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, Gl::LINEAR as i32);
-        self.gl
-            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, Gl::LINEAR as i32);
-
         let handle = BitmapHandle(self.textures.len());
         let width = bitmap.width;
         let height = bitmap.height;
@@ -709,7 +746,9 @@ impl WebGlRenderBackend {
         self.textures.push(Texture {
             width,
             height,
+            has_mipmaps,
             texture,
+            tiles,
         });
 
         Ok(BitmapInfo {
@@ -718,85 +757,64 @@ impl WebGlRenderBackend {
             height: height as u16,
         })
     }
-}
 
-impl RenderBackend for WebGlRenderBackend {
-    fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
-        self.view_width = width as i32;
-        self.view_height = height as i32;
-
-        // Build view matrix based on canvas size.
-        self.build_matrices();
-
-        // Setup GL viewport and renderbuffers clamped to reasonable sizes.
-        self.renderbuffer_width = self.view_width.max(1).min(self.gl.drawing_buffer_width());
-        self.renderbuffer_height = self.view_height.max(1).min(self.gl.drawing_buffer_height());
+    /// Upload a bitmap to a new texture. Returns the texture, and whether it
+    /// has a mip chain.
+    fn create_texture(&self, bitmap: &Bitmap) -> Result<(WebGlTexture, bool), Error> {
+        let texture = self.gl.create_texture().unwrap();
+        let has_mipmaps = self.upload_texture(&texture, bitmap)?;
 
-        // Recreate framebuffers with the new size.
-        let _ = self.build_msaa_buffers(self.renderbuffer_width, self.renderbuffer_height);
+        // You must set the texture parameters for non-power-of-2 textures to function in WebGL1.
         self.gl
-            .viewport(0, 0, self.renderbuffer_width, self.renderbuffer_height);
-    }
-
-    fn register_shape(
-        &mut self,
-        shape: DistilledShape,
-        library: Option<&MovieLibrary<'_>>,
-    ) -> ShapeHandle {
-        let handle = ShapeHandle(self.meshes.len());
-        let mesh = self.register_shape_internal(shape, library);
-        self.meshes.push(mesh);
-        handle
-    }
-
-    fn replace_shape(
-        &mut self,
-        shape: DistilledShape,
-        library: Option<&MovieLibrary<'_>>,
-        handle: ShapeHandle,
-    ) {
-        let mesh = self.register_shape_internal(shape, library);
-        self.meshes[handle.0] = mesh;
-    }
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, Gl::CLAMP_TO_EDGE as i32);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, Gl::CLAMP_TO_EDGE as i32);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, Gl::LINEAR as i32);
+        self.gl
+            .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, Gl::LINEAR as i32);
 
-    fn register_glyph_shape(&mut self, glyph: &swf::Glyph) -> ShapeHandle {
-        let shape = ruffle_core::shape_utils::swf_glyph_to_shape(glyph);
-        let handle = ShapeHandle(self.meshes.len());
-        let mesh = self.register_shape_internal((&shape).into(), None);
-        self.meshes.push(mesh);
-        handle
+        Ok((texture, has_mipmaps))
     }
 
-    fn register_bitmap_jpeg(
-        &mut self,
-        data: &[u8],
-        jpeg_tables: Option<&[u8]>,
-    ) -> Result<BitmapInfo, Error> {
-        let data = ruffle_core::backend::render::glue_tables_to_jpeg(data, jpeg_tables);
-        self.register_bitmap_jpeg_2(&data[..])
-    }
+    /// Bind a texture and replace its contents with a bitmap. Returns whether
+    /// the texture has a mip chain.
+    fn upload_texture(&self, texture: &WebGlTexture, bitmap: &Bitmap) -> Result<bool, Error> {
+        self.gl.bind_texture(Gl::TEXTURE_2D, Some(texture));
 
-    fn register_bitmap_jpeg_2(&mut self, data: &[u8]) -> Result<BitmapInfo, Error> {
-        let bitmap = ruffle_core::backend::render::decode_define_bits_jpeg(data, None)?;
-        self.register_bitmap(bitmap)
-    }
+        let (format, data) = match &bitmap.data {
+            BitmapFormat::Rgb(data) => (Gl::RGB, data),
+            BitmapFormat::Rgba(data) => (Gl::RGBA, data),
+        };
+        self.gl
+            .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+                Gl::TEXTURE_2D,
+                0,
+                format as i32,
+                bitmap.width as i32,
+                bitmap.height as i32,
+                0,
+                format,
+                Gl::UNSIGNED_BYTE,
+                Some(data),
+            )
+            .into_js_result()?;
 
-    fn register_bitmap_jpeg_3(
-        &mut self,
-        jpeg_data: &[u8],
-        alpha_data: &[u8],
-    ) -> Result<BitmapInfo, Error> {
-        let bitmap =
-            ruffle_core::backend::render::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
-        self.register_bitmap(bitmap)
+        Ok(self.generate_mipmaps(bitmap.width, bitmap.height))
     }
 
-    fn register_bitmap_png(
-        &mut self,
-        swf_tag: &swf::DefineBitsLossless,
-    ) -> Result<BitmapInfo, Error> {
-        let bitmap = ruffle_core::backend::render::decode_define_bits_lossless(swf_tag)?;
-        self.register_bitmap(bitmap)
+    /// Build the mip chain of the texture that is bound, if the context can,
+    /// so that it can be sampled smoothly below its size.
+    ///
+    /// WebGL1 can only build mip chains for textures whose sides are powers of
+    /// two. Returns whether the texture has a mip chain.
+    fn generate_mipmaps(&self, width: u32, height: u32) -> bool {
+        let has_mipmaps =
+            self.gl2.is_some() || (width.is_power_of_two() && height.is_power_of_two());
+        if has_mipmaps {
+            self.gl.generate_mipmap(Gl::TEXTURE_2D);
+        }
+        has_mipmaps
     }
 
     fn begin_frame(&mut self, clear: Color) {
@@ -807,6 +825,7 @@ impl RenderBackend for WebGlRenderBackend {
 
         self.mult_color = None;
         self.add_color = None;
+        self.blend_modes.clear();
 
         // Bind to MSAA render buffer if using MSAA.
         if let Some(msaa_buffers) = &self.msaa_buffers {
@@ -864,6 +883,14 @@ impl RenderBackend for WebGlRenderBackend {
                 self.gl.drawing_buffer_height(),
             );
 
+            // The last draw may have left a blend mode other than normal active.
+            let blend_func = (Gl::FUNC_ADD, Gl::ONE, Gl::ONE_MINUS_SRC_ALPHA);
+            if blend_func != self.blend_func {
+                self.gl.blend_equation(blend_func.0);
+                self.gl.blend_func(blend_func.1, blend_func.2);
+                self.blend_func = blend_func;
+            }
+
             let program = &self.bitmap_program;
             self.gl.use_program(Some(&program.program));
 
@@ -914,16 +941,174 @@ impl RenderBackend for WebGlRenderBackend {
             );
         }
     }
+}
+
+impl RenderBackend for WebGlRenderBackend {
+    fn submit_frame(&mut self, clear: Color, commands: CommandList) {
+        self.begin_frame(clear);
+        commands.execute(self);
+        self.end_frame();
+    }
+
+    fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        self.view_width = width as i32;
+        self.view_height = height as i32;
+
+        // Build view matrix based on canvas size.
+        self.build_matrices();
+
+        // Setup GL viewport and renderbuffers clamped to reasonable sizes.
+        self.renderbuffer_width = self.view_width.max(1).min(self.gl.drawing_buffer_width());
+        self.renderbuffer_height = self.view_height.max(1).min(self.gl.drawing_buffer_height());
+
+        // Recreate framebuffers with the new size.
+        let _ = self.build_msaa_buffers(self.renderbuffer_width, self.renderbuffer_height);
+        self.gl
+            .viewport(0, 0, self.renderbuffer_width, self.renderbuffer_height);
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        library: Option<&MovieLibrary<'_>>,
+    ) -> ShapeHandle {
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal(shape, library);
+        self.meshes.push(mesh);
+        handle
+    }
 
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        library: Option<&MovieLibrary<'_>>,
+        handle: ShapeHandle,
+    ) {
+        let mesh = self.register_shape_internal(shape, library);
+        self.meshes[handle.0] = mesh;
+    }
+
+    fn register_glyph_shape(&mut self, glyph: &swf::Glyph) -> ShapeHandle {
+        let shape = ruffle_core::shape_utils::swf_glyph_to_shape(glyph);
+        let handle = ShapeHandle(self.meshes.len());
+        let mesh = self.register_shape_internal((&shape).into(), None);
+        self.meshes.push(mesh);
+        handle
+    }
+
+    fn register_bitmap_jpeg(
+        &mut self,
+        data: &[u8],
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Error> {
+        let data = ruffle_core::backend::render::glue_tables_to_jpeg(data, jpeg_tables);
+        self.register_bitmap_jpeg_2(&data[..])
+    }
+
+    fn register_bitmap_jpeg_2(&mut self, data: &[u8]) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_jpeg(data, None)?;
+        self.register_bitmap(bitmap)
+    }
+
+    fn register_bitmap_jpeg_3(
+        &mut self,
+        jpeg_data: &[u8],
+        alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap =
+            ruffle_core::backend::render::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
+        self.register_bitmap(bitmap)
+    }
+
+    fn register_bitmap_png(
+        &mut self,
+        swf_tag: &swf::DefineBitsLossless,
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_lossless(swf_tag)?;
+        self.register_bitmap(bitmap)
+    }
+
+    fn capabilities(&self) -> &RenderCapabilities {
+        &self.capabilities
+    }
+
+    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        self.bitmap_registry.get(&bitmap).cloned()
+    }
+
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap(Bitmap {
+                data: BitmapFormat::Rgba(rgba),
+                width,
+                height,
+            })?
+            .handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let texture = if let Some(texture) = self.textures.get(handle.0) {
+            texture
+        } else {
+            return Err("update_texture: Bitmap is not regsitered".into());
+        };
+
+        let bitmap = Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        };
+        let max_size = self.capabilities.max_texture_size;
+        let scaled = fit_bitmap_to_size(&bitmap, max_size);
+        let has_mipmaps =
+            self.upload_texture(&texture.texture, scaled.as_ref().unwrap_or(&bitmap))?;
+
+        let tiles = split_bitmap_into_tiles(&bitmap, max_size).unwrap_or_default();
+        let mut tiles_have_mipmaps = Vec::with_capacity(tiles.len());
+        for (texture_tile, tile) in texture.tiles.iter().zip(tiles) {
+            tiles_have_mipmaps.push(self.upload_texture(&texture_tile.texture, &tile.bitmap)?);
+        }
+
+        let texture = &mut self.textures[handle.0];
+        texture.has_mipmaps = has_mipmaps;
+        for (tile, has_mipmaps) in texture.tiles.iter_mut().zip(tiles_have_mipmaps) {
+            tile.has_mipmaps = has_mipmaps;
+        }
+
+        Ok(handle)
+    }
+
+    fn unregister_bitmap(&mut self, handle: BitmapHandle) {
+        // Handles index the list, so the slot stays behind, empty.
+        if let Some(texture) = self.textures.get_mut(handle.0) {
+            self.gl.delete_texture(Some(&texture.texture));
+            for tile in texture.tiles.drain(..) {
+                self.gl.delete_texture(Some(&tile.texture));
+            }
+            texture.width = 0;
+            texture.height = 0;
+        }
+    }
+}
+
+impl CommandHandler for WebGlRenderBackend {
     fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
         self.set_stencil_state();
         if let Some(bitmap) = self.textures.get(bitmap.0) {
-            let texture = &bitmap.texture;
             // Adjust the quad draw to use the target bitmap.
             let mesh = &self.meshes[self.bitmap_quad_shape.0];
             let draw = &mesh.draws[0];
-            let width = bitmap.width as f32;
-            let height = bitmap.height as f32;
             let bitmap_matrix = if let DrawType::Bitmap(BitmapDraw { matrix, .. }) = &draw.draw_type
             {
                 matrix
@@ -931,25 +1116,32 @@ impl RenderBackend for WebGlRenderBackend {
                 unreachable!()
             };
 
-            // Scale the quad to the bitmap's dimensions.
-            let matrix = transform.matrix
-                * swf::Matrix {
-                    a: width,
-                    d: height,
-                    ..Default::default()
-                };
-
-            let world_matrix = [
-                [matrix.a, matrix.b, 0.0, 0.0],
-                [matrix.c, matrix.d, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [
-                    matrix.tx.to_pixels() as f32,
-                    matrix.ty.to_pixels() as f32,
-                    0.0,
-                    1.0,
-                ],
-            ];
+            // A bitmap too large for one texture is drawn as a quad per tile.
+            let quads: Vec<_> = if bitmap.tiles.is_empty() {
+                vec![(
+                    0,
+                    0,
+                    bitmap.width,
+                    bitmap.height,
+                    &bitmap.texture,
+                    bitmap.has_mipmaps,
+                )]
+            } else {
+                bitmap
+                    .tiles
+                    .iter()
+                    .map(|tile| {
+                        (
+                            tile.x,
+                            tile.y,
+                            tile.width,
+                            tile.height,
+                            &tile.texture,
+                            tile.has_mipmaps,
+                        )
+                    })
+                    .collect()
+            };
 
             let mult_color = [
                 transform.color_transform.r_mult,
@@ -981,14 +1173,15 @@ impl RenderBackend for WebGlRenderBackend {
 
                 self.mult_color = None;
                 self.add_color = None;
+            }
 
-                if (src_blend, dst_blend) != self.blend_func {
-                    self.gl.blend_func(src_blend, dst_blend);
-                    self.blend_func = (src_blend, dst_blend);
-                }
+            let blend_func = self.blend_func(src_blend, dst_blend);
+            if blend_func != self.blend_func {
+                self.gl.blend_equation(blend_func.0);
+                self.gl.blend_func(blend_func.1, blend_func.2);
+                self.blend_func = blend_func;
             }
 
-            program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
             if Some(mult_color) != self.mult_color {
                 program.uniform4fv(&self.gl, ShaderUniform::MultColor, &mult_color);
                 self.mult_color = Some(mult_color);
@@ -1000,31 +1193,57 @@ impl RenderBackend for WebGlRenderBackend {
 
             program.uniform_matrix3fv(&self.gl, ShaderUniform::TextureMatrix, &bitmap_matrix);
 
-            // Bind texture.
             self.gl.active_texture(Gl::TEXTURE0);
-            self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture));
             program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
 
-            // Set texture parameters.
-            let filter = if smoothing {
-                Gl::LINEAR as i32
-            } else {
-                Gl::NEAREST as i32
-            };
-            self.gl
-                .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
-            self.gl
-                .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
+            for (x, y, width, height, texture, has_mipmaps) in quads {
+                // Scale the quad to the tile's dimensions.
+                let matrix = transform.matrix
+                    * swf::Matrix {
+                        a: width as f32,
+                        d: height as f32,
+                        tx: swf::Twips::from_pixels(x.into()),
+                        ty: swf::Twips::from_pixels(y.into()),
+                        ..Default::default()
+                    };
 
-            let wrap = Gl::CLAMP_TO_EDGE as i32;
-            self.gl
-                .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, wrap);
-            self.gl
-                .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, wrap);
+                let world_matrix = [
+                    [matrix.a, matrix.b, 0.0, 0.0],
+                    [matrix.c, matrix.d, 0.0, 0.0],
+                    [0.0, 0.0, 1.0, 0.0],
+                    [
+                        matrix.tx.to_pixels() as f32,
+                        matrix.ty.to_pixels() as f32,
+                        0.0,
+                        1.0,
+                    ],
+                ];
+                program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
+
+                // Bind texture.
+                self.gl.bind_texture(Gl::TEXTURE_2D, Some(texture));
+
+                // Set texture parameters.
+                let (mag_filter, min_filter) = texture_filters(smoothing, has_mipmaps);
+                self.gl
+                    .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, mag_filter);
+                self.gl
+                    .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, min_filter);
 
-            // Draw the triangles.
-            self.gl
-                .draw_elements_with_i32(Gl::TRIANGLES, draw.num_indices, Gl::UNSIGNED_INT, 0);
+                let wrap = Gl::CLAMP_TO_EDGE as i32;
+                self.gl
+                    .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_S, wrap);
+                self.gl
+                    .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_WRAP_T, wrap);
+
+                // Draw the triangles.
+                self.gl.draw_elements_with_i32(
+                    Gl::TRIANGLES,
+                    draw.num_indices,
+                    Gl::UNSIGNED_INT,
+                    0,
+                );
+            }
         }
     }
 
@@ -1083,11 +1302,13 @@ impl RenderBackend for WebGlRenderBackend {
 
                 self.mult_color = None;
                 self.add_color = None;
+            }
 
-                if (src_blend, dst_blend) != self.blend_func {
-                    self.gl.blend_func(src_blend, dst_blend);
-                    self.blend_func = (src_blend, dst_blend);
-                }
+            let blend_func = self.blend_func(src_blend, dst_blend);
+            if blend_func != self.blend_func {
+                self.gl.blend_equation(blend_func.0);
+                self.gl.blend_func(blend_func.1, blend_func.2);
+                self.blend_func = blend_func;
             }
 
             program.uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
@@ -1161,15 +1382,12 @@ impl RenderBackend for WebGlRenderBackend {
                     program.uniform1i(&self.gl, ShaderUniform::BitmapTexture, 0);
 
                     // Set texture parameters.
-                    let filter = if bitmap.is_smoothed {
-                        Gl::LINEAR as i32
-                    } else {
-                        Gl::NEAREST as i32
-                    };
+                    let (mag_filter, min_filter) =
+                        texture_filters(bitmap.is_smoothed, texture.has_mipmaps);
                     self.gl
-                        .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, filter);
+                        .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MAG_FILTER, mag_filter);
                     self.gl
-                        .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, filter);
+                        .tex_parameteri(Gl::TEXTURE_2D, Gl::TEXTURE_MIN_FILTER, min_filter);
                     // On WebGL1, you are unable to change the wrapping parameter of non-power-of-2 textures.
                     let wrap = if self.gl2.is_some() && bitmap.is_repeating {
                         Gl::REPEAT as i32
@@ -1227,13 +1445,15 @@ impl RenderBackend for WebGlRenderBackend {
 
             self.mult_color = None;
             self.add_color = None;
-
-            if (src_blend, dst_blend) != self.blend_func {
-                self.gl.blend_func(src_blend, dst_blend);
-                self.blend_func = (src_blend, dst_blend);
-            }
         };
 
+        let blend_func = self.blend_func(src_blend, dst_blend);
+        if blend_func != self.blend_func {
+            self.gl.blend_equation(blend_func.0);
+            self.gl.blend_func(blend_func.1, blend_func.2);
+            self.blend_func = blend_func;
+        }
+
         self.color_program
             .uniform_matrix4fv(&self.gl, ShaderUniform::WorldMatrix, &world_matrix);
         if Some(mult_color) != self.mult_color {
@@ -1290,64 +1510,49 @@ impl RenderBackend for WebGlRenderBackend {
         self.mask_state_dirty = true;
     }
 
-    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
-        self.bitmap_registry.get(&bitmap).cloned()
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        self.blend_modes.push(blend_mode);
     }
 
-    fn register_bitmap_raw(
-        &mut self,
-        width: u32,
-        height: u32,
-        rgba: Vec<u8>,
-    ) -> Result<BitmapHandle, Error> {
-        Ok(self
-            .register_bitmap(Bitmap {
-                data: BitmapFormat::Rgba(rgba),
-                width,
-                height,
-            })?
-            .handle)
+    fn pop_blend_mode(&mut self) {
+        self.blend_modes.pop();
     }
+}
 
-    fn update_texture(
-        &mut self,
-        handle: BitmapHandle,
-        width: u32,
-        height: u32,
-        rgba: Vec<u8>,
-    ) -> Result<BitmapHandle, Error> {
-        let texture = if let Some(texture) = self.textures.get(handle.0) {
-            texture
-        } else {
-            return Err("update_texture: Bitmap is not regsitered".into());
-        };
-
-        self.gl.bind_texture(Gl::TEXTURE_2D, Some(&texture.texture));
-
-        self.gl
-            .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
-                Gl::TEXTURE_2D,
-                0,
-                Gl::RGBA as i32,
-                width as i32,
-                height as i32,
-                0,
-                Gl::RGBA,
-                Gl::UNSIGNED_BYTE,
-                Some(&rgba),
-            )
-            .into_js_result()?;
+struct Texture {
+    width: u32,
+    height: u32,
+    has_mipmaps: bool,
+    texture: WebGlTexture,
 
-        Ok(handle)
-    }
+    /// The full resolution pieces of a bitmap too large for one texture, in
+    /// which case `texture` holds a scaled down copy.
+    tiles: Vec<TextureTile>,
 }
 
-struct Texture {
+/// A piece of a bitmap that is too large for one texture, `x` and `y` pixels
+/// from its top left corner.
+struct TextureTile {
+    x: u32,
+    y: u32,
     width: u32,
     height: u32,
+    has_mipmaps: bool,
     texture: WebGlTexture,
 }
 
+/// The magnification and minification filters to sample a texture with.
+///
+/// Smoothed textures with a mip chain are sampled trilinearly when drawn
+/// smaller than their size.
+fn texture_filters(smoothing: bool, has_mipmaps: bool) -> (i32, i32) {
+    match (smoothing, has_mipmaps) {
+        (true, true) => (Gl::LINEAR as i32, Gl::LINEAR_MIPMAP_LINEAR as i32),
+        (true, false) => (Gl::LINEAR as i32, Gl::LINEAR as i32),
+        (false, _) => (Gl::NEAREST as i32, Gl::NEAREST as i32),
+    }
+}
+
 #[derive(Clone, Debug)]
 struct Gradient {
     matrix: [[f32; 3]; 3],
@@ -24,7 +24,12 @@ fn create_sampler(
         min_filter: filter,
         mipmap_filter: filter,
         lod_min_clamp: 0.0,
-        lod_max_clamp: 100.0,
+        // Smoothed bitmaps are blended trilinearly from their mip chain, but
+        // unsmoothed ones always show the pixels of the full-size bitmap.
+        lod_max_clamp: match filter {
+            wgpu::FilterMode::Linear => 100.0,
+            wgpu::FilterMode::Nearest => 0.0,
+        },
         compare: None,
         anisotropy_clamp: None,
         border_color: None,
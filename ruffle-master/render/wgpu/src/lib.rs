@@ -1,6 +1,7 @@
 use ruffle_core::backend::render::{
-    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, MovieLibrary, RenderBackend,
-    ShapeHandle, Transform,
+    fit_bitmap_to_size, generate_mipmaps, mip_level_count, split_bitmap_into_tiles, Bitmap,
+    BitmapFormat, BitmapHandle, BitmapInfo, Color, CommandHandler, CommandList, MovieLibrary,
+    RenderBackend, RenderCapabilities, ShapeHandle, Transform,
 };
 use ruffle_core::shape_utils::DistilledShape;
 use ruffle_core::swf;
@@ -82,6 +83,8 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     current_frame: Option<Frame<'static, T>>,
     meshes: Vec<Mesh>,
     mask_state: MaskState,
+    blend_modes: Vec<swf::BlendMode>,
+    blend_type: BlendType,
     shape_tessellator: ShapeTessellator,
     textures: Vec<Texture>,
     num_masks: u32,
@@ -89,8 +92,12 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     quad_ibo: wgpu::Buffer,
     quad_tex_transforms: wgpu::Buffer,
     bitmap_registry: HashMap<BitmapHandle, Bitmap>,
+    capabilities: RenderCapabilities,
 }
 
+/// The largest texture that every wgpu backend is guaranteed to support.
+const MAX_TEXTURE_SIZE: u32 = 8192;
+
 #[allow(dead_code)]
 struct Frame<'a, T: RenderTarget> {
     frame_data: Box<(wgpu::CommandEncoder, T::Frame)>,
@@ -119,6 +126,39 @@ pub enum MaskState {
     ClearMaskStencil,
 }
 
+/// How drawn pixels are combined with those already in the frame, as the
+/// blend modes that the pipelines support.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub enum BlendType {
+    Normal,
+    Add,
+    Subtract,
+    Multiply,
+    Screen,
+    Lighten,
+    Darken,
+    Alpha,
+    Erase,
+}
+
+impl From<swf::BlendMode> for BlendType {
+    fn from(blend_mode: swf::BlendMode) -> Self {
+        match blend_mode {
+            swf::BlendMode::Add => BlendType::Add,
+            swf::BlendMode::Subtract => BlendType::Subtract,
+            swf::BlendMode::Multiply => BlendType::Multiply,
+            swf::BlendMode::Screen => BlendType::Screen,
+            swf::BlendMode::Lighten => BlendType::Lighten,
+            swf::BlendMode::Darken => BlendType::Darken,
+            swf::BlendMode::Alpha => BlendType::Alpha,
+            swf::BlendMode::Erase => BlendType::Erase,
+            // TODO: The remaining blend modes can't be done with fixed
+            // function blending.
+            _ => BlendType::Normal,
+        }
+    }
+}
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 struct Transforms {
@@ -347,11 +387,28 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
 
             num_masks: 0,
             mask_state: MaskState::NoMask,
+            blend_modes: Vec::new(),
+            blend_type: BlendType::Normal,
 
             quad_vbo,
             quad_ibo,
             quad_tex_transforms,
             bitmap_registry: HashMap::new(),
+            capabilities: RenderCapabilities {
+                max_texture_size: MAX_TEXTURE_SIZE,
+                supports_filters: true,
+                // The blend modes that `BlendType` can draw.
+                blend_modes: vec![
+                    swf::BlendMode::Multiply,
+                    swf::BlendMode::Screen,
+                    swf::BlendMode::Lighten,
+                    swf::BlendMode::Darken,
+                    swf::BlendMode::Add,
+                    swf::BlendMode::Subtract,
+                    swf::BlendMode::Alpha,
+                    swf::BlendMode::Erase,
+                ],
+            },
         })
     }
 
@@ -591,27 +648,74 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
     }
 
     fn register_bitmap(&mut self, bitmap: Bitmap, debug_str: &str) -> BitmapInfo {
+        let handle = BitmapHandle(self.textures.len());
+        let max_size = self.capabilities.max_texture_size;
+
+        // Bitmap fills are drawn with normalized texture coordinates, so a
+        // bitmap that is too large can be uploaded at a lower resolution for
+        // them. Drawing the bitmap itself uses full resolution tiles instead.
+        let scaled = fit_bitmap_to_size(&bitmap, max_size);
+        let texture_bitmap = scaled.as_ref().unwrap_or(&bitmap);
+        let (texture, mip_level_count, bind_group) =
+            self.create_texture(texture_bitmap, debug_str, handle);
+
+        let tiles = split_bitmap_into_tiles(&bitmap, max_size)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tile| {
+                let (texture, mip_level_count, bind_group) = self.create_texture(
+                    &tile.bitmap,
+                    &format!("{} tile {},{}", debug_str, tile.x, tile.y),
+                    handle,
+                );
+                TextureTile {
+                    x: tile.x,
+                    y: tile.y,
+                    width: tile.bitmap.width,
+                    height: tile.bitmap.height,
+                    mip_level_count,
+                    texture,
+                    bind_group,
+                }
+            })
+            .collect();
+
+        let width = bitmap.width;
+        let height = bitmap.height;
+        self.bitmap_registry.insert(handle, bitmap);
+        self.textures.push(Texture {
+            width,
+            height,
+            mip_level_count,
+            texture,
+            bind_group,
+            tiles,
+        });
+
+        BitmapInfo {
+            handle,
+            width: width as u16,
+            height: height as u16,
+        }
+    }
+
+    /// Upload a bitmap to a new texture, and make the bind group that draws
+    /// it as a quad.
+    fn create_texture(
+        &self,
+        bitmap: &Bitmap,
+        debug_str: &str,
+        handle: BitmapHandle,
+    ) -> (wgpu::Texture, u32, wgpu::BindGroup) {
         let extent = wgpu::Extent3d {
             width: bitmap.width,
             height: bitmap.height,
             depth: 1,
         };
 
-        let data: Cow<[u8]> = match &bitmap.data {
-            BitmapFormat::Rgba(data) => Cow::Borrowed(data),
-            BitmapFormat::Rgb(data) => {
-                // Expand to RGBA.
-                let mut as_rgba =
-                    Vec::with_capacity(extent.width as usize * extent.height as usize * 4);
-                for i in (0..data.len()).step_by(3) {
-                    as_rgba.push(data[i]);
-                    as_rgba.push(data[i + 1]);
-                    as_rgba.push(data[i + 2]);
-                    as_rgba.push(255);
-                }
-                Cow::Owned(as_rgba)
-            }
-        };
+        // Smoothed bitmaps are sampled from the mip chain when drawn smaller
+        // than their size, so that they don't shimmer.
+        let mip_level_count = mip_level_count(extent.width, extent.height);
 
         let texture_label = create_debug_label!("{} Texture", debug_str);
         let texture = self
@@ -620,31 +724,14 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             .create_texture(&wgpu::TextureDescriptor {
                 label: texture_label.as_deref(),
                 size: extent,
-                mip_level_count: 1,
+                mip_level_count,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
                 format: wgpu::TextureFormat::Rgba8Unorm,
                 usage: wgpu::TextureUsage::SAMPLED | wgpu::TextureUsage::COPY_DST,
             });
 
-        self.descriptors.queue.write_texture(
-            wgpu::TextureCopyView {
-                texture: &texture,
-                mip_level: 0,
-                origin: Default::default(),
-            },
-            &data,
-            wgpu::TextureDataLayout {
-                offset: 0,
-                bytes_per_row: 4 * extent.width,
-                rows_per_image: 0,
-            },
-            extent,
-        );
-
-        let handle = BitmapHandle(self.textures.len());
-        let width = bitmap.width;
-        let height = bitmap.height;
+        write_texture_levels(&self.descriptors.queue, &texture, bitmap, mip_level_count);
 
         // Make bind group for bitmap quad.
         let texture_view = texture.create_view(&Default::default());
@@ -672,19 +759,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
                 label: create_debug_label!("Bitmap {} bind group", handle.0).as_deref(),
             });
 
-        self.bitmap_registry.insert(handle, bitmap);
-        self.textures.push(Texture {
-            width,
-            height,
-            texture,
-            bind_group,
-        });
-
-        BitmapInfo {
-            handle,
-            width: width as u16,
-            height: height as u16,
-        }
+        (texture, mip_level_count, bind_group)
     }
 
     pub fn target(&self) -> &T {
@@ -694,9 +769,107 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
     pub fn device(&self) -> &wgpu::Device {
         &self.descriptors.device
     }
+
+    fn begin_frame(&mut self, clear: Color) {
+        self.mask_state = MaskState::NoMask;
+        self.num_masks = 0;
+        self.blend_modes.clear();
+        self.blend_type = BlendType::Normal;
+
+        let frame_output = match self.target.get_next_texture() {
+            Ok(frame) => frame,
+            Err(e) => {
+                log::warn!("Couldn't begin new render frame: {}", e);
+                // Attemp to recreate the swap chain in this case.
+                self.target.resize(
+                    &self.descriptors.device,
+                    self.target.width(),
+                    self.target.height(),
+                );
+                return;
+            }
+        };
+
+        let label = create_debug_label!("Draw encoder");
+        let draw_encoder =
+            self.descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: label.as_deref(),
+                });
+        let mut frame_data = Box::new((draw_encoder, frame_output));
+
+        self.descriptors
+            .globals
+            .update_uniform(&self.descriptors.device, &mut frame_data.0);
+
+        let (color_attachment, resolve_target) = if self.descriptors.msaa_sample_count >= 2 {
+            (&self.frame_buffer_view, Some(frame_data.1.view()))
+        } else {
+            (frame_data.1.view(), None)
+        };
+
+        let render_pass = frame_data.0.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
+                attachment: color_attachment,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color {
+                        r: f64::from(clear.r) / 255.0,
+                        g: f64::from(clear.g) / 255.0,
+                        b: f64::from(clear.b) / 255.0,
+                        a: f64::from(clear.a) / 255.0,
+                    }),
+                    store: true,
+                },
+                resolve_target,
+            }],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
+                attachment: &self.depth_texture_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0.0),
+                    store: true,
+                }),
+                stencil_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(0),
+                    store: true,
+                }),
+            }),
+            label: None,
+        });
+
+        // Since RenderPass holds a reference to the CommandEncoder, we cast the lifetime
+        // away to allow for the self-referencing struct. draw_encoder is boxed so its
+        // address should remain stable.
+        self.current_frame = Some(Frame {
+            render_pass: unsafe {
+                std::mem::transmute::<_, wgpu::RenderPass<'static>>(render_pass)
+            },
+            frame_data,
+        });
+    }
+
+    fn end_frame(&mut self) {
+        if let Some(frame) = self.current_frame.take() {
+            // Finalize render pass.
+            drop(frame.render_pass);
+
+            let draw_encoder = frame.frame_data.0;
+            self.target.submit(
+                &self.descriptors.device,
+                &self.descriptors.queue,
+                vec![draw_encoder.finish()],
+            );
+        }
+    }
 }
 
 impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
+    fn submit_frame(&mut self, clear: Color, commands: CommandList) {
+        self.begin_frame(clear);
+        commands.execute(self);
+        self.end_frame();
+    }
+
     fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
         // Avoid panics from creating 0-sized framebuffers.
         let width = std::cmp::max(width, 1);
@@ -805,82 +978,86 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         Ok(self.register_bitmap(bitmap, "PNG"))
     }
 
-    fn begin_frame(&mut self, clear: Color) {
-        self.mask_state = MaskState::NoMask;
-        self.num_masks = 0;
-
-        let frame_output = match self.target.get_next_texture() {
-            Ok(frame) => frame,
-            Err(e) => {
-                log::warn!("Couldn't begin new render frame: {}", e);
-                // Attemp to recreate the swap chain in this case.
-                self.target.resize(
-                    &self.descriptors.device,
-                    self.target.width(),
-                    self.target.height(),
-                );
-                return;
-            }
-        };
+    fn capabilities(&self) -> &RenderCapabilities {
+        &self.capabilities
+    }
 
-        let label = create_debug_label!("Draw encoder");
-        let draw_encoder =
-            self.descriptors
-                .device
-                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
-                    label: label.as_deref(),
-                });
-        let mut frame_data = Box::new((draw_encoder, frame_output));
+    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        self.bitmap_registry.get(&bitmap).cloned()
+    }
 
-        self.descriptors
-            .globals
-            .update_uniform(&self.descriptors.device, &mut frame_data.0);
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap(
+                Bitmap {
+                    height,
+                    width,
+                    data: BitmapFormat::Rgba(rgba),
+                },
+                "RAW",
+            )
+            .handle)
+    }
 
-        let (color_attachment, resolve_target) = if self.descriptors.msaa_sample_count >= 2 {
-            (&self.frame_buffer_view, Some(frame_data.1.view()))
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let texture = if let Some(texture) = self.textures.get(handle.0) {
+            texture
         } else {
-            (frame_data.1.view(), None)
+            return Err("update_texture: Bitmap not registered".into());
         };
 
-        let render_pass = frame_data.0.begin_render_pass(&wgpu::RenderPassDescriptor {
-            color_attachments: &[wgpu::RenderPassColorAttachmentDescriptor {
-                attachment: color_attachment,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: f64::from(clear.r) / 255.0,
-                        g: f64::from(clear.g) / 255.0,
-                        b: f64::from(clear.b) / 255.0,
-                        a: f64::from(clear.a) / 255.0,
-                    }),
-                    store: true,
-                },
-                resolve_target,
-            }],
-            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachmentDescriptor {
-                attachment: &self.depth_texture_view,
-                depth_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(0.0),
-                    store: true,
-                }),
-                stencil_ops: Some(wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(0),
-                    store: true,
-                }),
-            }),
-            label: None,
-        });
+        let bitmap = Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        };
+        let max_size = self.capabilities.max_texture_size;
+        let scaled = fit_bitmap_to_size(&bitmap, max_size);
+        write_texture_levels(
+            &self.descriptors.queue,
+            &texture.texture,
+            scaled.as_ref().unwrap_or(&bitmap),
+            texture.mip_level_count,
+        );
 
-        // Since RenderPass holds a reference to the CommandEncoder, we cast the lifetime
-        // away to allow for the self-referencing struct. draw_encoder is boxed so its
-        // address should remain stable.
-        self.current_frame = Some(Frame {
-            render_pass: unsafe {
-                std::mem::transmute::<_, wgpu::RenderPass<'static>>(render_pass)
-            },
-            frame_data,
-        });
+        let tiles = split_bitmap_into_tiles(&bitmap, max_size).unwrap_or_default();
+        for (texture_tile, tile) in texture.tiles.iter().zip(tiles) {
+            write_texture_levels(
+                &self.descriptors.queue,
+                &texture_tile.texture,
+                &tile.bitmap,
+                texture_tile.mip_level_count,
+            );
+        }
+
+        Ok(handle)
+    }
+
+    fn unregister_bitmap(&mut self, handle: BitmapHandle) {
+        // Handles index the list, so the slot stays behind, empty.
+        if let Some(texture) = self.textures.get_mut(handle.0) {
+            texture.texture.destroy();
+            for tile in texture.tiles.drain(..) {
+                tile.texture.destroy();
+            }
+            texture.width = 0;
+            texture.height = 0;
+        }
     }
+}
 
+impl<T: RenderTarget + 'static> CommandHandler for WgpuRenderBackend<T> {
     fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
         if let Some(texture) = self.textures.get(bitmap.0) {
             let frame = if let Some(frame) = &mut self.current_frame {
@@ -889,38 +1066,22 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 return;
             };
 
-            let transform = Transform {
-                matrix: transform.matrix
-                    * swf::Matrix {
-                        a: texture.width as f32,
-                        d: texture.height as f32,
-                        ..Default::default()
-                    },
-                ..*transform
+            // A bitmap too large for one texture is drawn as a quad per tile.
+            let quads: Vec<_> = if texture.tiles.is_empty() {
+                vec![(0, 0, texture.width, texture.height, &texture.bind_group)]
+            } else {
+                texture
+                    .tiles
+                    .iter()
+                    .map(|tile| (tile.x, tile.y, tile.width, tile.height, &tile.bind_group))
+                    .collect()
             };
 
-            let world_matrix = [
-                [transform.matrix.a, transform.matrix.b, 0.0, 0.0],
-                [transform.matrix.c, transform.matrix.d, 0.0, 0.0],
-                [0.0, 0.0, 1.0, 0.0],
-                [
-                    transform.matrix.tx.to_pixels() as f32,
-                    transform.matrix.ty.to_pixels() as f32,
-                    0.0,
-                    1.0,
-                ],
-            ];
-
             frame.render_pass.set_pipeline(
                 self.descriptors
                     .pipelines
                     .bitmap_pipelines
-                    .pipeline_for(self.mask_state),
-            );
-            frame.render_pass.set_push_constants(
-                wgpu::ShaderStage::VERTEX,
-                0,
-                bytemuck::cast_slice(&[Transforms { world_matrix }]),
+                    .pipeline_for(self.mask_state, self.blend_type),
             );
             frame.render_pass.set_push_constants(
                 wgpu::ShaderStage::FRAGMENT,
@@ -930,9 +1091,6 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
             frame
                 .render_pass
                 .set_bind_group(0, self.descriptors.globals.bind_group(), &[]);
-            frame
-                .render_pass
-                .set_bind_group(1, &texture.bind_group, &[]);
             frame.render_pass.set_bind_group(
                 2,
                 self.descriptors
@@ -959,7 +1117,36 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 }
             };
 
-            frame.render_pass.draw_indexed(0..6, 0, 0..1);
+            for (x, y, width, height, bind_group) in quads {
+                let matrix = transform.matrix
+                    * swf::Matrix {
+                        a: width as f32,
+                        d: height as f32,
+                        tx: swf::Twips::from_pixels(x.into()),
+                        ty: swf::Twips::from_pixels(y.into()),
+                        ..Default::default()
+                    };
+
+                let world_matrix = [
+                    [matrix.a, matrix.b, 0.0, 0.0],
+                    [matrix.c, matrix.d, 0.0, 0.0],
+                    [0.0, 0.0, 1.0, 0.0],
+                    [
+                        matrix.tx.to_pixels() as f32,
+                        matrix.ty.to_pixels() as f32,
+                        0.0,
+                        1.0,
+                    ],
+                ];
+
+                frame.render_pass.set_push_constants(
+                    wgpu::ShaderStage::VERTEX,
+                    0,
+                    bytemuck::cast_slice(&[Transforms { world_matrix }]),
+                );
+                frame.render_pass.set_bind_group(1, bind_group, &[]);
+                frame.render_pass.draw_indexed(0..6, 0, 0..1);
+            }
         }
     }
 
@@ -996,7 +1183,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                             .descriptors
                             .pipelines
                             .color_pipelines
-                            .pipeline_for(self.mask_state),
+                            .pipeline_for(self.mask_state, self.blend_type),
                     );
                 }
                 DrawType::Gradient { bind_group, .. } => {
@@ -1005,7 +1192,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                             .descriptors
                             .pipelines
                             .gradient_pipelines
-                            .pipeline_for(self.mask_state),
+                            .pipeline_for(self.mask_state, self.blend_type),
                     );
                     frame.render_pass.set_bind_group(1, bind_group, &[]);
                 }
@@ -1020,7 +1207,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                             .descriptors
                             .pipelines
                             .bitmap_pipelines
-                            .pipeline_for(self.mask_state),
+                            .pipeline_for(self.mask_state, self.blend_type),
                     );
                     frame.render_pass.set_bind_group(1, bind_group, &[]);
                     frame.render_pass.set_bind_group(
@@ -1098,7 +1285,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
                 .descriptors
                 .pipelines
                 .color_pipelines
-                .pipeline_for(self.mask_state),
+                .pipeline_for(self.mask_state, self.blend_type),
         );
 
         frame.render_pass.set_push_constants(
@@ -1140,20 +1327,6 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         frame.render_pass.draw_indexed(0..6, 0, 0..1);
     }
 
-    fn end_frame(&mut self) {
-        if let Some(frame) = self.current_frame.take() {
-            // Finalize render pass.
-            drop(frame.render_pass);
-
-            let draw_encoder = frame.frame_data.0;
-            self.target.submit(
-                &self.descriptors.device,
-                &self.descriptors.queue,
-                vec![draw_encoder.finish()],
-            );
-        }
-    }
-
     fn push_mask(&mut self) {
         debug_assert!(
             self.mask_state == MaskState::NoMask || self.mask_state == MaskState::DrawMaskedContent
@@ -1182,63 +1355,17 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         };
     }
 
-    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
-        self.bitmap_registry.get(&bitmap).cloned()
+    fn push_blend_mode(&mut self, blend_mode: swf::BlendMode) {
+        self.blend_modes.push(blend_mode);
+        self.blend_type = blend_mode.into();
     }
 
-    fn register_bitmap_raw(
-        &mut self,
-        width: u32,
-        height: u32,
-        rgba: Vec<u8>,
-    ) -> Result<BitmapHandle, Error> {
-        Ok(self
-            .register_bitmap(
-                Bitmap {
-                    height,
-                    width,
-                    data: BitmapFormat::Rgba(rgba),
-                },
-                "RAW",
-            )
-            .handle)
-    }
-
-    fn update_texture(
-        &mut self,
-        handle: BitmapHandle,
-        width: u32,
-        height: u32,
-        rgba: Vec<u8>,
-    ) -> Result<BitmapHandle, Error> {
-        let texture = if let Some(texture) = self.textures.get(handle.0) {
-            &texture.texture
-        } else {
-            return Err("update_texture: Bitmap not registered".into());
-        };
-
-        let extent = wgpu::Extent3d {
-            width,
-            height,
-            depth: 1,
-        };
-
-        self.descriptors.queue.write_texture(
-            wgpu::TextureCopyView {
-                texture: &texture,
-                mip_level: 0,
-                origin: Default::default(),
-            },
-            &rgba,
-            wgpu::TextureDataLayout {
-                offset: 0,
-                bytes_per_row: 4 * extent.width,
-                rows_per_image: 0,
-            },
-            extent,
-        );
-
-        Ok(handle)
+    fn pop_blend_mode(&mut self) {
+        self.blend_modes.pop();
+        self.blend_type = self
+            .blend_modes
+            .last()
+            .map_or(BlendType::Normal, |&blend_mode| blend_mode.into());
     }
 }
 
@@ -1298,6 +1425,79 @@ fn create_quad_buffers(device: &wgpu::Device) -> (wgpu::Buffer, wgpu::Buffer, wg
 struct Texture {
     width: u32,
     height: u32,
+    mip_level_count: u32,
+    texture: wgpu::Texture,
+    bind_group: wgpu::BindGroup,
+
+    /// The full resolution pieces of a bitmap too large for one texture, in
+    /// which case `texture` holds a scaled down copy.
+    tiles: Vec<TextureTile>,
+}
+
+/// A piece of a bitmap that is too large for one texture, `x` and `y` pixels
+/// from its top left corner.
+struct TextureTile {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    mip_level_count: u32,
     texture: wgpu::Texture,
     bind_group: wgpu::BindGroup,
 }
+
+/// Upload a bitmap to a texture, along with as many levels of its mip chain as
+/// the texture has.
+fn write_texture_levels(
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    bitmap: &Bitmap,
+    mip_level_count: u32,
+) {
+    let mips = if mip_level_count > 1 {
+        generate_mipmaps(bitmap)
+    } else {
+        Vec::new()
+    };
+
+    for (mip_level, level) in std::iter::once(bitmap)
+        .chain(mips.iter())
+        .take(mip_level_count as usize)
+        .enumerate()
+    {
+        let data: Cow<[u8]> = match &level.data {
+            BitmapFormat::Rgba(data) => Cow::Borrowed(data),
+            BitmapFormat::Rgb(data) => {
+                // Expand to RGBA.
+                let mut as_rgba =
+                    Vec::with_capacity(level.width as usize * level.height as usize * 4);
+                for i in (0..data.len()).step_by(3) {
+                    as_rgba.push(data[i]);
+                    as_rgba.push(data[i + 1]);
+                    as_rgba.push(data[i + 2]);
+                    as_rgba.push(255);
+                }
+                Cow::Owned(as_rgba)
+            }
+        };
+
+        queue.write_texture(
+            wgpu::TextureCopyView {
+                texture,
+                mip_level: mip_level as u32,
+                origin: Default::default(),
+            },
+            &data,
+            wgpu::TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 4 * level.width,
+                rows_per_image: 0,
+            },
+            wgpu::Extent3d {
+                width: level.width,
+                height: level.height,
+                depth: 1,
+            },
+        );
+    }
+}
@@ -1,10 +1,14 @@
-use crate::{Error, MaskState, Vertex};
+use crate::{BlendType, Error, MaskState, Vertex};
 use enum_map::{enum_map, EnumMap};
 use wgpu::vertex_attr_array;
 
 #[derive(Debug)]
 pub struct ShapePipeline {
     pub mask_pipelines: EnumMap<MaskState, wgpu::RenderPipeline>,
+
+    /// Pipelines for drawing with each blend mode, for the mask states that
+    /// draw content.
+    pub blend_pipelines: EnumMap<BlendType, EnumMap<MaskState, Option<wgpu::RenderPipeline>>>,
 }
 
 #[derive(Debug)]
@@ -19,8 +23,14 @@ pub struct Pipelines {
 }
 
 impl ShapePipeline {
-    pub fn pipeline_for(&self, mask_state: MaskState) -> &wgpu::RenderPipeline {
-        &self.mask_pipelines[mask_state]
+    pub fn pipeline_for(
+        &self,
+        mask_state: MaskState,
+        blend_type: BlendType,
+    ) -> &wgpu::RenderPipeline {
+        self.blend_pipelines[blend_type][mask_state]
+            .as_ref()
+            .unwrap_or(&self.mask_pipelines[mask_state])
     }
 }
 
@@ -225,145 +235,16 @@ fn create_color_pipelines(
         ],
     });
 
-    let mask_pipelines = enum_map! {
-        MaskState::NoMask => {
-            let (stencil, write_mask) = mask_render_state(MaskState::NoMask);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Color pipeline no mask").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::DrawMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Color pipeline draw mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::DrawMaskedContent => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskedContent);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Color pipeline draw masked content").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::ClearMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::ClearMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Color pipeline clear mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_description,
-                msaa_sample_count,
-            ))
-        },
-    };
-
-    ShapePipeline { mask_pipelines }
+    create_shape_pipeline(
+        device,
+        "Color",
+        vertex_shader,
+        fragment_shader,
+        &pipeline_layout,
+        msaa_sample_count,
+        vertex_buffers_description,
+        wgpu::BlendFactor::SrcAlpha,
+    )
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -393,148 +274,19 @@ fn create_bitmap_pipeline(
         ],
     });
 
-    let mask_pipelines = enum_map! {
-        MaskState::NoMask => {
-            let (stencil, write_mask) = mask_render_state(MaskState::NoMask);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Bitmap pipeline no mask").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::One,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_layout,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::DrawMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Bitmap pipeline draw mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_layout,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::DrawMaskedContent => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskedContent);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Bitmap pipeline draw masked content").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Equal,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::One,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_layout,
-                msaa_sample_count,
-            ))
-        },
-
-        MaskState::ClearMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::ClearMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Bitmap pipeline clear mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_layout,
-                msaa_sample_count,
-            ))
-        }
-    };
-
-    ShapePipeline { mask_pipelines }
+    // Bitmaps use pre-multiplied alpha.
+    create_shape_pipeline(
+        device,
+        "Bitmap",
+        vertex_shader,
+        fragment_shader,
+        &pipeline_layout,
+        msaa_sample_count,
+        vertex_buffers_layout,
+        wgpu::BlendFactor::One,
+    )
 }
 
-#[allow(clippy::too_many_arguments)]
 fn create_gradient_pipeline(
     device: &wgpu::Device,
     vertex_shader: &wgpu::ShaderModule,
@@ -560,146 +312,175 @@ fn create_gradient_pipeline(
         ],
     });
 
-    let mask_pipelines = enum_map! {
-        MaskState::NoMask => {
-            let (stencil, write_mask) = mask_render_state(MaskState::NoMask);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Gradient pipeline no mask").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_layout,
-                msaa_sample_count,
-            ))
-        },
+    create_shape_pipeline(
+        device,
+        "Gradient",
+        vertex_shader,
+        fragment_shader,
+        &pipeline_layout,
+        msaa_sample_count,
+        vertex_buffers_layout,
+        wgpu::BlendFactor::SrcAlpha,
+    )
+}
 
-        MaskState::DrawMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Gradient pipeline draw mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_layout,
-                msaa_sample_count,
-            ))
-        },
+/// Create the pipelines for drawing one kind of shape in every mask state and
+/// blend mode.
+///
+/// `src_factor` is the factor that the output color is multiplied by when
+/// blending normally, which depends on whether the fragment shader outputs
+/// pre-multiplied alpha.
+#[allow(clippy::too_many_arguments)]
+fn create_shape_pipeline(
+    device: &wgpu::Device,
+    name: &str,
+    vertex_shader: &wgpu::ShaderModule,
+    fragment_shader: &wgpu::ShaderModule,
+    pipeline_layout: &wgpu::PipelineLayout,
+    msaa_sample_count: u32,
+    vertex_buffers_layout: &[wgpu::VertexBufferLayout<'_>],
+    src_factor: wgpu::BlendFactor,
+) -> ShapePipeline {
+    let create_pipeline = |mask_state: MaskState, blend_type: BlendType| {
+        let (stencil, write_mask) = mask_render_state(mask_state);
+        let (color_blend, alpha_blend) = blend_state(blend_type, src_factor);
+        device.create_render_pipeline(&create_pipeline_descriptor(
+            create_debug_label!("{} pipeline {:?} {:?}", name, mask_state, blend_type).as_deref(),
+            vertex_shader,
+            fragment_shader,
+            pipeline_layout,
+            Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth24PlusStencil8,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Always,
+                stencil,
+                bias: Default::default(),
+                clamp_depth: false,
+            }),
+            &[wgpu::ColorTargetState {
+                format: wgpu::TextureFormat::Bgra8Unorm,
+                color_blend,
+                alpha_blend,
+                write_mask,
+            }],
+            vertex_buffers_layout,
+            msaa_sample_count,
+        ))
+    };
 
+    let mask_pipelines = enum_map! {
+        mask_state => create_pipeline(mask_state, BlendType::Normal),
+    };
 
-        MaskState::DrawMaskedContent => {
-            let (stencil, write_mask) = mask_render_state(MaskState::DrawMaskedContent);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Gradient pipeline draw masked content").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Equal,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_layout,
-                msaa_sample_count,
-            ))
+    // Drawing to the stencil buffer doesn't write any color, so only the
+    // states that draw content need a pipeline for each blend mode.
+    let blend_pipelines = enum_map! {
+        blend_type => enum_map! {
+            MaskState::NoMask => Some(create_pipeline(MaskState::NoMask, blend_type)),
+            MaskState::DrawMaskedContent => {
+                Some(create_pipeline(MaskState::DrawMaskedContent, blend_type))
+            },
+            _ => None,
         },
-
-        MaskState::ClearMaskStencil => {
-            let (stencil, write_mask) = mask_render_state(MaskState::ClearMaskStencil);
-            device.create_render_pipeline(&create_pipeline_descriptor(
-                create_debug_label!("Gradient pipeline clear mask stencil").as_deref(),
-                vertex_shader,
-                fragment_shader,
-                &pipeline_layout,
-                Some(wgpu::DepthStencilState {
-                    format: wgpu::TextureFormat::Depth24PlusStencil8,
-                    depth_write_enabled: true,
-                    depth_compare: wgpu::CompareFunction::Always,
-                    stencil,
-                    bias: Default::default(),
-                    clamp_depth: false,
-                }),
-                &[wgpu::ColorTargetState {
-                    format: wgpu::TextureFormat::Bgra8Unorm,
-                    color_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    alpha_blend: wgpu::BlendState {
-                        src_factor: wgpu::BlendFactor::SrcAlpha,
-                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                        operation: wgpu::BlendOperation::Add,
-                    },
-                    write_mask,
-                }],
-                vertex_buffers_layout,
-                msaa_sample_count,
-            ))
-        }
     };
 
-    ShapePipeline { mask_pipelines }
+    ShapePipeline {
+        mask_pipelines,
+        blend_pipelines,
+    }
+}
+
+/// The color and alpha blend states used to draw with a blend mode.
+fn blend_state(
+    blend_type: BlendType,
+    src_factor: wgpu::BlendFactor,
+) -> (wgpu::BlendState, wgpu::BlendState) {
+    let blend = |src_factor, dst_factor, operation| wgpu::BlendState {
+        src_factor,
+        dst_factor,
+        operation,
+    };
+    let normal_alpha = blend(
+        wgpu::BlendFactor::SrcAlpha,
+        wgpu::BlendFactor::OneMinusSrcAlpha,
+        wgpu::BlendOperation::Add,
+    );
+
+    match blend_type {
+        BlendType::Normal => (
+            blend(
+                src_factor,
+                wgpu::BlendFactor::OneMinusSrcAlpha,
+                wgpu::BlendOperation::Add,
+            ),
+            normal_alpha,
+        ),
+        BlendType::Add => (
+            blend(
+                src_factor,
+                wgpu::BlendFactor::One,
+                wgpu::BlendOperation::Add,
+            ),
+            normal_alpha,
+        ),
+        BlendType::Subtract => (
+            blend(
+                src_factor,
+                wgpu::BlendFactor::One,
+                wgpu::BlendOperation::ReverseSubtract,
+            ),
+            normal_alpha,
+        ),
+        // This is exact where the drawn shape is opaque.
+        BlendType::Multiply => (
+            blend(
+                wgpu::BlendFactor::DstColor,
+                wgpu::BlendFactor::OneMinusSrcAlpha,
+                wgpu::BlendOperation::Add,
+            ),
+            normal_alpha,
+        ),
+        BlendType::Screen => (
+            blend(
+                src_factor,
+                wgpu::BlendFactor::OneMinusSrcColor,
+                wgpu::BlendOperation::Add,
+            ),
+            normal_alpha,
+        ),
+        BlendType::Lighten => (
+            blend(
+                wgpu::BlendFactor::One,
+                wgpu::BlendFactor::One,
+                wgpu::BlendOperation::Max,
+            ),
+            normal_alpha,
+        ),
+        BlendType::Darken => (
+            blend(
+                wgpu::BlendFactor::One,
+                wgpu::BlendFactor::One,
+                wgpu::BlendOperation::Min,
+            ),
+            normal_alpha,
+        ),
+        BlendType::Alpha => {
+            let state = blend(
+                wgpu::BlendFactor::Zero,
+                wgpu::BlendFactor::SrcAlpha,
+                wgpu::BlendOperation::Add,
+            );
+            (state.clone(), state)
+        }
+        BlendType::Erase => {
+            let state = blend(
+                wgpu::BlendFactor::Zero,
+                wgpu::BlendFactor::OneMinusSrcAlpha,
+                wgpu::BlendOperation::Add,
+            );
+            (state.clone(), state)
+        }
+    }
 }
 
 fn mask_render_state(state: MaskState) -> (wgpu::StencilState, wgpu::ColorWrite) {
@@ -1,6 +1,7 @@
 use clap::Clap;
 use indicatif::{ProgressBar, ProgressStyle};
 use path_slash::PathExt;
+use ruffle_core::swf::dependencies::{find_dependencies, Dependency};
 use ruffle_core::swf::{decompress_swf, parse_swf};
 
 use serde::Serialize;
@@ -15,6 +16,13 @@ struct FileResults {
     error: Option<String>,
 }
 
+#[derive(Serialize, Debug)]
+struct DependencyRecord<'a> {
+    name: &'a str,
+    kind: &'static str,
+    url: &'a str,
+}
+
 #[derive(Clap, Debug)]
 #[clap(version, about, author)]
 struct Opt {
@@ -29,6 +37,10 @@ struct Opt {
     /// Filenames to ignore
     #[clap(short = 'i', long = "ignore")]
     ignore: Vec<String>,
+
+    /// The file to store the external dependencies of each SWF in CSV format
+    #[clap(short = 'd', long = "dependencies", parse(from_os_str))]
+    dependencies_path: Option<PathBuf>,
 }
 
 fn find_files(root: &Path, ignore: &[String]) -> Vec<DirEntry> {
@@ -52,37 +64,49 @@ fn find_files(root: &Path, ignore: &[String]) -> Vec<DirEntry> {
     results
 }
 
-fn scan_file(file: DirEntry, name: String) -> FileResults {
+fn scan_file(file: DirEntry, name: String) -> (FileResults, Vec<Dependency>) {
     let data = match std::fs::read(file.path()) {
         Ok(data) => data,
         Err(e) => {
             return {
-                FileResults {
-                    name,
-                    error: Some(format!("File error: {}", e.to_string())),
-                }
+                (
+                    FileResults {
+                        name,
+                        error: Some(format!("File error: {}", e.to_string())),
+                    },
+                    Vec::new(),
+                )
             }
         }
     };
 
     let swf_buf = decompress_swf(&data[..]).unwrap();
-    match catch_unwind(|| parse_swf(&swf_buf)) {
+    match catch_unwind(|| parse_swf(&swf_buf).map(|swf| find_dependencies(&swf))) {
         Ok(swf) => match swf {
-            Ok(_swf) => FileResults { name, error: None },
-            Err(e) => FileResults {
-                name,
-                error: Some(format!("Parse error: {}", e.to_string())),
-            },
+            Ok(dependencies) => (FileResults { name, error: None }, dependencies),
+            Err(e) => (
+                FileResults {
+                    name,
+                    error: Some(format!("Parse error: {}", e.to_string())),
+                },
+                Vec::new(),
+            ),
         },
         Err(e) => match e.downcast::<String>() {
-            Ok(e) => FileResults {
-                name,
-                error: Some(format!("PANIC: {}", e.to_string())),
-            },
-            Err(_) => FileResults {
-                name,
-                error: Some("PANIC".to_string()),
-            },
+            Ok(e) => (
+                FileResults {
+                    name,
+                    error: Some(format!("PANIC: {}", e.to_string())),
+                },
+                Vec::new(),
+            ),
+            Err(_) => (
+                FileResults {
+                    name,
+                    error: Some("PANIC".to_string()),
+                },
+                Vec::new(),
+            ),
         },
     }
 }
@@ -108,6 +132,15 @@ fn main() -> Result<(), std::io::Error> {
 
     writer.write_record(&["Filename", "Error"])?;
 
+    let mut dependencies_writer = match &opt.dependencies_path {
+        Some(path) => {
+            let mut writer = csv::Writer::from_path(path)?;
+            writer.write_record(&["Filename", "Kind", "Url"])?;
+            Some(writer)
+        }
+        None => None,
+    };
+
     for file in to_scan {
         let name = file
             .path()
@@ -116,7 +149,7 @@ fn main() -> Result<(), std::io::Error> {
             .to_slash_lossy();
         progress.inc(1);
         progress.set_message(&name);
-        let result = scan_file(file, name);
+        let (result, dependencies) = scan_file(file, name);
 
         if result.error.is_none() {
             good += 1;
@@ -124,6 +157,16 @@ fn main() -> Result<(), std::io::Error> {
             bad += 1;
         }
 
+        if let Some(dependencies_writer) = &mut dependencies_writer {
+            for dependency in &dependencies {
+                dependencies_writer.serialize(DependencyRecord {
+                    name: &result.name,
+                    kind: dependency.kind.as_str(),
+                    url: &dependency.url,
+                })?;
+            }
+        }
+
         writer.serialize(result)?;
     }
 
@@ -327,11 +327,17 @@ impl<'a> Reader<'a> {
 
     fn read_metadata(&mut self) -> Result<Metadata> {
         let name = self.read_index()?;
-        let mut items = vec![];
-        let num_items = self.read_u30()?;
+        let num_items = self.read_u30()? as usize;
+
+        // All of the keys come first, followed by all of the values.
+        let mut keys = Vec::with_capacity(num_items);
         for _ in 0..num_items {
+            keys.push(self.read_index()?);
+        }
+        let mut items = Vec::with_capacity(num_items);
+        for key in keys {
             items.push(MetadataItem {
-                key: self.read_index()?,
+                key,
                 value: self.read_index()?,
             })
         }
@@ -897,6 +903,28 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn read_metadata() {
+        // `[Event(name="change", type="flash.events.Event")]`
+        let metadata = Reader::new(&[1, 2, 3, 4, 5, 6]).read_metadata().unwrap();
+        assert_eq!(
+            metadata,
+            Metadata {
+                name: Index::new(1),
+                items: vec![
+                    MetadataItem {
+                        key: Index::new(3),
+                        value: Index::new(5),
+                    },
+                    MetadataItem {
+                        key: Index::new(4),
+                        value: Index::new(6),
+                    },
+                ],
+            }
+        );
+    }
+
     #[test]
     fn read_i32() {
         let read = |data: &[u8]| Reader::new(data).read_i32().unwrap();
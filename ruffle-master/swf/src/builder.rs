@@ -0,0 +1,589 @@
+//! A builder for small SWF files that are put together in code, rather than
+//! authored in Flash.
+//!
+//! This is useful for tests, and for placeholder movies such as splash
+//! screens. Everything added to the builder is placed on the first and only
+//! frame, in the order it was added.
+//!
+//! Individual shapes can be drawn with `ShapeBuilder`, and the other common
+//! tags have constructors on their types, such as `PlaceObject::place` and
+//! `Sprite::new`.
+//!
+//! # Example
+//! ```
+//! use swf::builder::SwfBuilder;
+//! use swf::Color;
+//!
+//! let data = SwfBuilder::new(10)
+//!     .with_stage_size(320.0, 240.0)
+//!     .with_background_color(Color::from_rgb(0x000000, 255))
+//!     .with_rectangle(10.0, 10.0, 100.0, 50.0, Color::from_rgb(0xFF0000, 255))
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use crate::error::{Error, Result};
+use crate::types::*;
+use crate::write::{write_swf, write_zlib_swf};
+use std::io::Write;
+
+/// Something drawn on the frame of a movie made by `SwfBuilder`.
+enum Item {
+    Rectangle {
+        x: Twips,
+        y: Twips,
+        width: Twips,
+        height: Twips,
+        color: Color,
+    },
+    Bitmap {
+        x: Twips,
+        y: Twips,
+        width: u16,
+        height: u16,
+        /// Premultiplied ARGB pixels, compressed with zlib.
+        data: Vec<u8>,
+    },
+}
+
+/// Builds a single-frame SWF file.
+pub struct SwfBuilder {
+    version: u8,
+    compression: Compression,
+    stage_size: Rectangle,
+    frame_rate: f32,
+    background_color: Option<Color>,
+    items: Vec<Item>,
+}
+
+impl SwfBuilder {
+    /// Start building a movie of the given SWF version, with a 550x400 stage
+    /// running at 24 frames per second.
+    pub fn new(version: u8) -> Self {
+        Self {
+            version,
+            compression: Compression::None,
+            stage_size: Rectangle {
+                x_min: Twips::zero(),
+                x_max: Twips::from_pixels(550.0),
+                y_min: Twips::zero(),
+                y_max: Twips::from_pixels(400.0),
+            },
+            frame_rate: 24.0,
+            background_color: None,
+            items: Vec::new(),
+        }
+    }
+
+    pub fn with_stage_size(mut self, width: f64, height: f64) -> Self {
+        self.stage_size = Rectangle {
+            x_min: Twips::zero(),
+            x_max: Twips::from_pixels(width),
+            y_min: Twips::zero(),
+            y_max: Twips::from_pixels(height),
+        };
+        self
+    }
+
+    pub fn with_frame_rate(mut self, frame_rate: f32) -> Self {
+        self.frame_rate = frame_rate;
+        self
+    }
+
+    pub fn with_background_color(mut self, color: Color) -> Self {
+        self.background_color = Some(color);
+        self
+    }
+
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Draw a solid rectangle, given in pixels.
+    pub fn with_rectangle(mut self, x: f64, y: f64, width: f64, height: f64, color: Color) -> Self {
+        self.items.push(Item::Rectangle {
+            x: Twips::from_pixels(x),
+            y: Twips::from_pixels(y),
+            width: Twips::from_pixels(width),
+            height: Twips::from_pixels(height),
+            color,
+        });
+        self
+    }
+
+    /// Draw a bitmap with its top-left corner at the given position, in
+    /// pixels.
+    ///
+    /// `rgba` holds the pixels of the bitmap row by row, four bytes each.
+    pub fn with_bitmap(
+        mut self,
+        x: f64,
+        y: f64,
+        width: u16,
+        height: u16,
+        rgba: &[u8],
+    ) -> Result<Self> {
+        if rgba.len() != usize::from(width) * usize::from(height) * 4 {
+            return Err(Error::invalid_data(
+                "Bitmap data does not match its dimensions.",
+            ));
+        }
+
+        // `DefineBitsLossless2` stores premultiplied ARGB.
+        let mut argb = Vec::with_capacity(rgba.len());
+        for pixel in rgba.chunks_exact(4) {
+            let alpha = u16::from(pixel[3]);
+            argb.push(pixel[3]);
+            for &component in &pixel[..3] {
+                argb.push((u16::from(component) * alpha / 255) as u8);
+            }
+        }
+
+        let mut data = Vec::new();
+        write_zlib_swf(&mut data, &argb)?;
+
+        self.items.push(Item::Bitmap {
+            x: Twips::from_pixels(x),
+            y: Twips::from_pixels(y),
+            width,
+            height,
+            data,
+        });
+        Ok(self)
+    }
+
+    /// Write the movie as an SWF file.
+    pub fn write<W: Write>(&self, output: W) -> Result<()> {
+        let mut tags = Vec::new();
+
+        if self.version >= 8 {
+            tags.push(Tag::FileAttributes(FileAttributes {
+                use_direct_blit: false,
+                use_gpu: false,
+                has_metadata: false,
+                is_action_script_3: false,
+                use_network_sandbox: false,
+            }));
+        }
+
+        if let Some(color) = &self.background_color {
+            tags.push(Tag::SetBackgroundColor(color.clone()));
+        }
+
+        let mut next_id: CharacterId = 1;
+        for (index, item) in self.items.iter().enumerate() {
+            let (x, y, width, height, fill_style) = match item {
+                Item::Rectangle {
+                    x,
+                    y,
+                    width,
+                    height,
+                    color,
+                } => (*x, *y, *width, *height, FillStyle::Color(color.clone())),
+                Item::Bitmap {
+                    x,
+                    y,
+                    width,
+                    height,
+                    data,
+                } => {
+                    let bitmap_id = next_id;
+                    next_id += 1;
+                    tags.push(Tag::DefineBitsLossless(DefineBitsLossless {
+                        version: 2,
+                        id: bitmap_id,
+                        format: BitmapFormat::Rgb32,
+                        width: *width,
+                        height: *height,
+                        num_colors: 0,
+                        data,
+                    }));
+
+                    let fill_style = FillStyle::Bitmap {
+                        id: bitmap_id,
+                        // Bitmap fills are measured in twips.
+                        matrix: Matrix::scale(20.0, 20.0),
+                        is_smoothed: false,
+                        is_repeating: false,
+                    };
+                    let width = Twips::from_pixels(f64::from(*width));
+                    let height = Twips::from_pixels(f64::from(*height));
+                    (*x, *y, width, height, fill_style)
+                }
+            };
+
+            let shape_id = next_id;
+            next_id += 1;
+            tags.push(Tag::DefineShape(rectangle_shape(
+                shape_id, width, height, fill_style,
+            )));
+            tags.push(Tag::PlaceObject(Box::new(
+                PlaceObject::place(index as Depth + 1, shape_id)
+                    .with_matrix(Matrix::translate(x, y)),
+            )));
+        }
+
+        tags.push(Tag::ShowFrame);
+
+        let swf = Swf {
+            header: Header {
+                compression: self.compression.clone(),
+                version: self.version,
+                // Filled in when the file is written.
+                uncompressed_length: 0,
+                stage_size: self.stage_size.clone(),
+                frame_rate: self.frame_rate,
+                num_frames: 1,
+            },
+            tags,
+        };
+
+        write_swf(&swf, output)
+    }
+
+    /// Build the movie into the bytes of an SWF file.
+    pub fn build(&self) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.write(&mut data)?;
+        Ok(data)
+    }
+}
+
+/// A shape of a single rectangle, with its top-left corner at the origin.
+fn rectangle_shape(id: CharacterId, width: Twips, height: Twips, fill_style: FillStyle) -> Shape {
+    ShapeBuilder::new()
+        .fill(fill_style)
+        .line_to(width, Twips::zero())
+        .line_to(width, height)
+        .line_to(Twips::zero(), height)
+        .line_to(Twips::zero(), Twips::zero())
+        .build(id)
+}
+
+/// Builds a `DefineShape` tag by drawing a path, with coordinates in twips.
+///
+/// The pen starts at the origin. A fill or line style applies to the edges
+/// drawn after it is set, until it is replaced or cleared.
+///
+/// # Example
+/// ```
+/// use swf::builder::ShapeBuilder;
+/// use swf::{Color, FillStyle, Twips};
+///
+/// let triangle = ShapeBuilder::new()
+///     .fill(FillStyle::Color(Color::from_rgb(0x00FF00, 255)))
+///     .move_to(Twips::new(0), Twips::new(200))
+///     .line_to(Twips::new(100), Twips::new(0))
+///     .line_to(Twips::new(200), Twips::new(200))
+///     .line_to(Twips::new(0), Twips::new(200))
+///     .build(1);
+/// assert_eq!(triangle.shape_bounds.x_max, Twips::new(200));
+/// ```
+#[derive(Debug, Default)]
+pub struct ShapeBuilder {
+    styles: ShapeStyles,
+    records: Vec<ShapeRecord>,
+    pen: (Twips, Twips),
+    edge_bounds: Option<Rectangle>,
+}
+
+impl ShapeBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fill the area enclosed by the following edges.
+    pub fn fill(mut self, fill_style: FillStyle) -> Self {
+        self.styles.fill_styles.push(fill_style);
+        let index = self.styles.fill_styles.len() as u32;
+        self.style_change().fill_style_1 = Some(index);
+        self
+    }
+
+    /// Leave the area enclosed by the following edges unfilled.
+    pub fn no_fill(mut self) -> Self {
+        self.style_change().fill_style_1 = Some(0);
+        self
+    }
+
+    /// Stroke the following edges.
+    pub fn line(mut self, line_style: LineStyle) -> Self {
+        self.styles.line_styles.push(line_style);
+        let index = self.styles.line_styles.len() as u32;
+        self.style_change().line_style = Some(index);
+        self
+    }
+
+    /// Leave the following edges unstroked.
+    pub fn no_line(mut self) -> Self {
+        self.style_change().line_style = Some(0);
+        self
+    }
+
+    /// Move the pen without drawing an edge.
+    pub fn move_to(mut self, x: Twips, y: Twips) -> Self {
+        self.style_change().move_to = Some((x, y));
+        self.pen = (x, y);
+        self
+    }
+
+    /// Draw a straight edge from the pen.
+    pub fn line_to(mut self, x: Twips, y: Twips) -> Self {
+        self.include(self.pen);
+        self.include((x, y));
+        self.records.push(ShapeRecord::StraightEdge {
+            delta_x: x - self.pen.0,
+            delta_y: y - self.pen.1,
+        });
+        self.pen = (x, y);
+        self
+    }
+
+    /// Draw a quadratic Bézier curve from the pen.
+    ///
+    /// The bounds of the shape include the control point, so they may be
+    /// larger than the curve itself.
+    pub fn curve_to(mut self, control_x: Twips, control_y: Twips, x: Twips, y: Twips) -> Self {
+        self.include(self.pen);
+        self.include((control_x, control_y));
+        self.include((x, y));
+        self.records.push(ShapeRecord::CurvedEdge {
+            control_delta_x: control_x - self.pen.0,
+            control_delta_y: control_y - self.pen.1,
+            anchor_delta_x: x - control_x,
+            anchor_delta_y: y - control_y,
+        });
+        self.pen = (x, y);
+        self
+    }
+
+    /// Finish the shape as the character with the given ID.
+    ///
+    /// This is a `DefineShape3` tag, or `DefineShape4` if any line style
+    /// uses features that older versions cannot store.
+    pub fn build(self, id: CharacterId) -> Shape {
+        let edge_bounds = self.edge_bounds.unwrap_or_default();
+        let half_width = self
+            .styles
+            .line_styles
+            .iter()
+            .map(|line_style| line_style.width / 2)
+            .max()
+            .unwrap_or_default();
+        let shape_bounds = Rectangle {
+            x_min: edge_bounds.x_min - half_width,
+            x_max: edge_bounds.x_max + half_width,
+            y_min: edge_bounds.y_min - half_width,
+            y_max: edge_bounds.y_max + half_width,
+        };
+
+        let line_styles = &self.styles.line_styles;
+        let needs_line_style_2 = line_styles
+            .iter()
+            .any(|style| *style != LineStyle::new_v1(style.width, style.color.clone()));
+        let is_scaling = |style: &LineStyle| style.allow_scale_x && style.allow_scale_y;
+
+        Shape {
+            version: if needs_line_style_2 { 4 } else { 3 },
+            id,
+            shape_bounds,
+            edge_bounds,
+            has_fill_winding_rule: false,
+            has_non_scaling_strokes: needs_line_style_2
+                && line_styles.iter().any(|style| !is_scaling(style)),
+            has_scaling_strokes: needs_line_style_2 && line_styles.iter().any(is_scaling),
+            styles: self.styles,
+            shape: self.records,
+        }
+    }
+
+    /// The style change record that the next change should be merged into.
+    fn style_change(&mut self) -> &mut StyleChangeData {
+        if !matches!(self.records.last(), Some(ShapeRecord::StyleChange(_))) {
+            self.records.push(ShapeRecord::StyleChange(StyleChangeData {
+                move_to: None,
+                fill_style_0: None,
+                fill_style_1: None,
+                line_style: None,
+                new_styles: None,
+            }));
+        }
+
+        match self.records.last_mut() {
+            Some(ShapeRecord::StyleChange(style_change)) => style_change,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Grow the edge bounds to contain a point.
+    fn include(&mut self, (x, y): (Twips, Twips)) {
+        let bounds = self.edge_bounds.get_or_insert(Rectangle {
+            x_min: x,
+            x_max: x,
+            y_min: y,
+            y_max: y,
+        });
+        bounds.x_min = bounds.x_min.min(x);
+        bounds.x_max = bounds.x_max.max(x);
+        bounds.y_min = bounds.y_min.min(y);
+        bounds.y_max = bounds.y_max.max(y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::read::{decompress_swf, parse_swf};
+    use crate::string::SwfStr;
+
+    #[test]
+    fn build_rectangle() {
+        let data = SwfBuilder::new(10)
+            .with_stage_size(320.0, 240.0)
+            .with_frame_rate(30.0)
+            .with_background_color(Color::from_rgb(0x336699, 255))
+            .with_rectangle(10.0, 20.0, 100.0, 50.0, Color::from_rgb(0xFF0000, 255))
+            .build()
+            .unwrap();
+
+        let swf_buf = decompress_swf(&data[..]).unwrap();
+        assert_eq!(swf_buf.header.version, 10);
+        assert_eq!(swf_buf.header.frame_rate, 30.0);
+        assert_eq!(swf_buf.header.num_frames, 1);
+        assert_eq!(swf_buf.header.stage_size.x_max, Twips::from_pixels(320.0));
+        assert_eq!(swf_buf.header.stage_size.y_max, Twips::from_pixels(240.0));
+
+        let swf = parse_swf(&swf_buf).unwrap();
+        assert_eq!(
+            swf.tags[1],
+            Tag::SetBackgroundColor(Color::from_rgb(0x336699, 255))
+        );
+        match &swf.tags[2] {
+            Tag::DefineShape(shape) => {
+                assert_eq!(shape.id, 1);
+                assert_eq!(shape.shape_bounds.x_max, Twips::from_pixels(100.0));
+                assert_eq!(shape.shape_bounds.y_max, Twips::from_pixels(50.0));
+            }
+            tag => panic!("Expected DefineShape, got {:?}", tag),
+        }
+        match &swf.tags[3] {
+            Tag::PlaceObject(place_object) => {
+                assert_eq!(place_object.action, PlaceObjectAction::Place(1));
+                assert_eq!(place_object.depth, 1);
+                assert_eq!(
+                    place_object.matrix,
+                    Some(Matrix::translate(
+                        Twips::from_pixels(10.0),
+                        Twips::from_pixels(20.0)
+                    ))
+                );
+            }
+            tag => panic!("Expected PlaceObject, got {:?}", tag),
+        }
+        assert_eq!(swf.tags[4], Tag::ShowFrame);
+    }
+
+    #[test]
+    fn build_bitmap() {
+        let pixels = [255, 0, 0, 255, 0, 255, 0, 128];
+        let data = SwfBuilder::new(10)
+            .with_bitmap(0.0, 0.0, 2, 1, &pixels)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let swf_buf = decompress_swf(&data[..]).unwrap();
+        let swf = parse_swf(&swf_buf).unwrap();
+        match &swf.tags[1] {
+            Tag::DefineBitsLossless(bitmap) => {
+                assert_eq!(bitmap.id, 1);
+                assert_eq!(bitmap.format, BitmapFormat::Rgb32);
+                assert_eq!((bitmap.width, bitmap.height), (2, 1));
+            }
+            tag => panic!("Expected DefineBitsLossless, got {:?}", tag),
+        }
+        match &swf.tags[2] {
+            Tag::DefineShape(shape) => assert_eq!(shape.id, 2),
+            tag => panic!("Expected DefineShape, got {:?}", tag),
+        }
+    }
+
+    #[test]
+    fn shape_builder() {
+        let shape = ShapeBuilder::new()
+            .fill(FillStyle::Color(Color::from_rgb(0xFF0000, 255)))
+            .line(LineStyle::new_v1(Twips::new(40), Color::from_rgb(0, 255)))
+            .move_to(Twips::new(100), Twips::new(100))
+            .line_to(Twips::new(300), Twips::new(100))
+            .curve_to(
+                Twips::new(300),
+                Twips::new(300),
+                Twips::new(100),
+                Twips::new(300),
+            )
+            .line_to(Twips::new(100), Twips::new(100))
+            .build(5);
+
+        assert_eq!(shape.id, 5);
+        assert_eq!(shape.version, 3);
+        assert_eq!(shape.edge_bounds.x_min, Twips::new(100));
+        assert_eq!(shape.edge_bounds.y_max, Twips::new(300));
+        assert_eq!(shape.shape_bounds.x_min, Twips::new(80));
+        assert_eq!(shape.shape_bounds.y_max, Twips::new(320));
+        assert_eq!(
+            shape.shape[0],
+            ShapeRecord::StyleChange(StyleChangeData {
+                move_to: Some((Twips::new(100), Twips::new(100))),
+                fill_style_0: None,
+                fill_style_1: Some(1),
+                line_style: Some(1),
+                new_styles: None,
+            })
+        );
+        assert_eq!(
+            shape.shape[2],
+            ShapeRecord::CurvedEdge {
+                control_delta_x: Twips::zero(),
+                control_delta_y: Twips::new(200),
+                anchor_delta_x: Twips::new(-200),
+                anchor_delta_y: Twips::zero(),
+            }
+        );
+        assert_eq!(shape.shape.len(), 4);
+    }
+
+    #[test]
+    fn tag_helpers() {
+        let name = SwfStr::from_utf8_str("clip");
+        let place_object = PlaceObject::place(3, 7)
+            .with_name(name)
+            .with_blend_mode(BlendMode::Multiply);
+        assert_eq!(place_object.version, 3);
+        assert_eq!(place_object.action, PlaceObjectAction::Place(7));
+        assert_eq!(place_object.depth, 3);
+        assert_eq!(place_object.name, Some(name));
+        assert_eq!(place_object.matrix, None);
+
+        let sprite = Sprite::new(
+            2,
+            vec![
+                Tag::PlaceObject(Box::new(place_object)),
+                Tag::ShowFrame,
+                Tag::RemoveObject(RemoveObject {
+                    depth: 3,
+                    character_id: None,
+                }),
+                Tag::ShowFrame,
+            ],
+        );
+        assert_eq!(sprite.num_frames, 2);
+    }
+
+    #[test]
+    fn bitmap_size_mismatch() {
+        assert!(SwfBuilder::new(10)
+            .with_bitmap(0.0, 0.0, 2, 2, &[0; 4])
+            .is_err());
+    }
+}
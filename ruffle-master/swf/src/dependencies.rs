@@ -0,0 +1,554 @@
+//! Static analysis of the external resources a SWF refers to.
+//!
+//! `find_dependencies` walks a parsed movie without running it and lists every
+//! outbound URL it can find, so that archivists can mirror a movie's
+//! dependencies ahead of time.
+//!
+//! Script analysis only follows string constants that are pushed directly
+//! before the action or call that consumes them. URLs that are built at
+//! runtime (by concatenation, from variables, from `FlashVars`...) are not
+//! found.
+
+use crate::avm1::types::{Action, Value};
+use crate::avm2::types::{AbcFile, Index, Multiname, Op};
+use crate::string::{Encoding, SwfStr};
+use crate::types::{Swf, Tag};
+use crate::{avm1, avm2};
+
+/// The way in which a SWF refers to an external resource.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    /// The URL of an `ImportAssets` tag.
+    ImportAssets,
+
+    /// A page opened by AVM1 `getURL`.
+    GetUrl,
+
+    /// A movie loaded by AVM1 `loadMovie`, `loadMovieNum` or
+    /// `MovieClipLoader.loadClip`.
+    LoadMovie,
+
+    /// Data loaded by AVM1 `loadVariables`, `LoadVars` or `XML`.
+    LoadVariables,
+
+    /// The URL of an AVM2 `URLRequest`.
+    UrlRequest,
+
+    /// The host of a `Socket` or `XMLSocket`.
+    Socket,
+}
+
+impl DependencyKind {
+    /// A short, stable name for this kind, for use in manifests.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DependencyKind::ImportAssets => "import_assets",
+            DependencyKind::GetUrl => "get_url",
+            DependencyKind::LoadMovie => "load_movie",
+            DependencyKind::LoadVariables => "load_variables",
+            DependencyKind::UrlRequest => "url_request",
+            DependencyKind::Socket => "socket",
+        }
+    }
+}
+
+/// An external resource referred to by a SWF.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Dependency {
+    pub kind: DependencyKind,
+    pub url: String,
+}
+
+/// Lists the external resources referred to by `swf`.
+///
+/// Each dependency is listed once, in the order it was first found. Tags are
+/// searched in file order, including the timelines of sprites, button actions
+/// and clip actions.
+pub fn find_dependencies(swf: &Swf<'_>) -> Vec<Dependency> {
+    let mut finder = DependencyFinder {
+        version: swf.header.version,
+        encoding: SwfStr::encoding_for_version(swf.header.version),
+        dependencies: Vec::new(),
+    };
+    finder.scan_tags(&swf.tags);
+    finder.dependencies
+}
+
+/// A value on the simulated operand stack of a script.
+#[derive(Clone, Debug)]
+enum Operand {
+    Str(String),
+    Number(f64),
+    Unknown,
+}
+
+impl Operand {
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Operand::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// A stack of operands whose values may or may not be known.
+///
+/// Popping past the bottom of the stack yields `Operand::Unknown`, so that a
+/// stack which was cleared by an unsupported action stays usable.
+#[derive(Default)]
+struct OperandStack(Vec<Operand>);
+
+impl OperandStack {
+    fn push(&mut self, operand: Operand) {
+        self.0.push(operand);
+    }
+
+    fn pop(&mut self) -> Operand {
+        self.0.pop().unwrap_or(Operand::Unknown)
+    }
+
+    /// Pops `count` operands, returning them in the order they were pushed.
+    fn pop_args(&mut self, count: usize) -> Vec<Operand> {
+        let mut args: Vec<_> = (0..count).map(|_| self.pop()).collect();
+        args.reverse();
+        args
+    }
+
+    /// Pops an AVM1 argument count followed by that many arguments, returning
+    /// the arguments in the order they were passed.
+    fn pop_avm1_args(&mut self) -> Vec<Operand> {
+        let count = match self.pop() {
+            Operand::Number(n) if (0.0..=255.0).contains(&n) => n as usize,
+            _ => 0,
+        };
+        (0..count).map(|_| self.pop()).collect()
+    }
+
+    fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+struct DependencyFinder {
+    version: u8,
+    encoding: &'static Encoding,
+    dependencies: Vec<Dependency>,
+}
+
+impl DependencyFinder {
+    fn add(&mut self, kind: DependencyKind, url: &str) {
+        if url.is_empty() {
+            return;
+        }
+        let dependency = Dependency {
+            kind,
+            url: url.to_string(),
+        };
+        if !self.dependencies.contains(&dependency) {
+            self.dependencies.push(dependency);
+        }
+    }
+
+    fn scan_tags(&mut self, tags: &[Tag<'_>]) {
+        for tag in tags {
+            match tag {
+                Tag::ImportAssets { url, .. } => self.add(
+                    DependencyKind::ImportAssets,
+                    &url.to_str_lossy(self.encoding),
+                ),
+                Tag::DefineSprite(sprite) => self.scan_tags(&sprite.tags),
+                Tag::DoAction(action_data) | Tag::DoInitAction { action_data, .. } => {
+                    self.scan_avm1(action_data, &[])
+                }
+                Tag::DefineButton(button) | Tag::DefineButton2(button) => {
+                    for action in &button.actions {
+                        self.scan_avm1(action.action_data, &[]);
+                    }
+                }
+                Tag::PlaceObject(place_object) => {
+                    for action in place_object.clip_actions.iter().flatten() {
+                        self.scan_avm1(action.action_data, &[]);
+                    }
+                }
+                Tag::DoAbc(do_abc) => {
+                    if let Ok(abc) = avm2::read::Reader::new(do_abc.data).read() {
+                        self.scan_abc(&abc);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    /// Scans a block of AVM1 actions, and any blocks nested inside of it.
+    ///
+    /// Nested blocks inherit the constant pool that was active when they were
+    /// defined.
+    fn scan_avm1<'a>(&mut self, action_data: &'a [u8], constant_pool: &[&'a SwfStr]) {
+        let mut reader = avm1::read::Reader::new(action_data, self.version);
+        let mut constant_pool = constant_pool.to_vec();
+        let mut stack = OperandStack::default();
+
+        while let Ok(Some(action)) = reader.read_action() {
+            match action {
+                Action::ConstantPool(pool) => constant_pool = pool,
+                Action::Push(values) => {
+                    for value in values {
+                        let operand = match value {
+                            Value::Str(s) => Operand::Str(s.to_string_lossy(self.encoding)),
+                            Value::ConstantPool(i) => match constant_pool.get(usize::from(i)) {
+                                Some(s) => Operand::Str(s.to_string_lossy(self.encoding)),
+                                None => Operand::Unknown,
+                            },
+                            Value::Int(n) => Operand::Number(n.into()),
+                            Value::Float(n) => Operand::Number(n.into()),
+                            Value::Double(n) => Operand::Number(n),
+                            _ => Operand::Unknown,
+                        };
+                        stack.push(operand);
+                    }
+                }
+                Action::PushDuplicate => {
+                    let top = stack.pop();
+                    stack.push(top.clone());
+                    stack.push(top);
+                }
+                Action::Pop => {
+                    stack.pop();
+                }
+                Action::StoreRegister(_) => (),
+                Action::GetVariable => {
+                    stack.pop();
+                    stack.push(Operand::Unknown);
+                }
+                Action::GetMember => {
+                    stack.pop();
+                    stack.pop();
+                    stack.push(Operand::Unknown);
+                }
+                Action::GetUrl { url, target } => {
+                    let url = url.to_str_lossy(self.encoding);
+                    let target = target.to_str_lossy(self.encoding);
+                    self.add_avm1_get_url(&url, Some(&target), false, false);
+                }
+                Action::GetUrl2 {
+                    is_target_sprite,
+                    is_load_vars,
+                    ..
+                } => {
+                    let target = stack.pop();
+                    let url = stack.pop();
+                    if let Some(url) = url.as_str() {
+                        self.add_avm1_get_url(url, target.as_str(), is_target_sprite, is_load_vars);
+                    }
+                }
+                Action::CallMethod => {
+                    let name = stack.pop();
+                    stack.pop();
+                    let args = stack.pop_avm1_args();
+                    if let (Some(name), Some(url)) = (name.as_str(), args.first()) {
+                        if let Some(url) = url.as_str() {
+                            self.add_avm1_method_call(name, url, args.len());
+                        }
+                    }
+                    stack.push(Operand::Unknown);
+                }
+                Action::CallFunction | Action::NewObject => {
+                    stack.pop();
+                    stack.pop_avm1_args();
+                    stack.push(Operand::Unknown);
+                }
+                Action::NewMethod => {
+                    stack.pop();
+                    stack.pop();
+                    stack.pop_avm1_args();
+                    stack.push(Operand::Unknown);
+                }
+                Action::DefineFunction { actions, .. } => {
+                    self.scan_avm1(actions, &constant_pool);
+                    stack.clear();
+                }
+                Action::DefineFunction2(function) => {
+                    self.scan_avm1(function.actions, &constant_pool);
+                    stack.clear();
+                }
+                Action::Try(try_block) => {
+                    self.scan_avm1(try_block.try_actions, &constant_pool);
+                    if let Some((_, catch_actions)) = try_block.catch {
+                        self.scan_avm1(catch_actions, &constant_pool);
+                    }
+                    if let Some(finally_actions) = try_block.finally {
+                        self.scan_avm1(finally_actions, &constant_pool);
+                    }
+                    stack.clear();
+                }
+                Action::With { actions } => {
+                    stack.pop();
+                    self.scan_avm1(actions, &constant_pool);
+                }
+                _ => stack.clear(),
+            }
+        }
+    }
+
+    fn add_avm1_get_url(
+        &mut self,
+        url: &str,
+        target: Option<&str>,
+        is_target_sprite: bool,
+        is_load_vars: bool,
+    ) {
+        // `fscommand` is compiled to a `getURL` of an `FSCommand:` URL.
+        if url.len() >= 10 && url[..10].eq_ignore_ascii_case("fscommand:") {
+            return;
+        }
+
+        // `loadMovieNum` targets a level by name rather than a sprite.
+        let targets_level = target.map_or(false, |target| target.starts_with("_level"));
+        let kind = if is_load_vars {
+            DependencyKind::LoadVariables
+        } else if is_target_sprite || targets_level {
+            DependencyKind::LoadMovie
+        } else {
+            DependencyKind::GetUrl
+        };
+        self.add(kind, url);
+    }
+
+    fn add_avm1_method_call(&mut self, name: &str, url: &str, num_args: usize) {
+        let kind = match name {
+            "loadMovie" | "loadMovieNum" | "loadClip" => DependencyKind::LoadMovie,
+            "loadVariables" | "loadVariablesNum" | "load" | "send" | "sendAndLoad" => {
+                DependencyKind::LoadVariables
+            }
+            "getURL" => DependencyKind::GetUrl,
+            "connect" if num_args == 2 => DependencyKind::Socket,
+            _ => return,
+        };
+        self.add(kind, url);
+    }
+
+    /// Scans every method body of an ABC file for `URLRequest`s and sockets.
+    fn scan_abc(&mut self, abc: &AbcFile) {
+        for body in &abc.method_bodies {
+            let mut reader = avm2::read::Reader::new(&body.code);
+            let mut stack = OperandStack::default();
+
+            while let Ok(Some(op)) = reader.read_op() {
+                match op {
+                    Op::PushString { value } => {
+                        let operand = match pool_string(abc, &value) {
+                            Some(s) => Operand::Str(s.to_string()),
+                            None => Operand::Unknown,
+                        };
+                        stack.push(operand);
+                    }
+                    Op::ConstructProp { index, num_args } => {
+                        let args = stack.pop_args(num_args as usize);
+                        stack.pop();
+                        let kind = match multiname_name(abc, &index) {
+                            Some("URLRequest") => Some(DependencyKind::UrlRequest),
+                            Some("Socket") | Some("XMLSocket") => Some(DependencyKind::Socket),
+                            _ => None,
+                        };
+                        if let (Some(kind), Some(url)) =
+                            (kind, args.first().and_then(Operand::as_str))
+                        {
+                            self.add(kind, url);
+                        }
+                        stack.push(Operand::Unknown);
+                    }
+                    Op::CallProperty { index, num_args } | Op::CallPropLex { index, num_args } => {
+                        self.scan_abc_call(abc, &mut stack, &index, num_args);
+                        stack.push(Operand::Unknown);
+                    }
+                    Op::CallPropVoid { index, num_args } => {
+                        self.scan_abc_call(abc, &mut stack, &index, num_args);
+                    }
+                    _ => stack.clear(),
+                }
+            }
+        }
+    }
+
+    /// Pops the receiver and arguments of a method call, recording the host of
+    /// a `Socket.connect` or `XMLSocket.connect`.
+    fn scan_abc_call(
+        &mut self,
+        abc: &AbcFile,
+        stack: &mut OperandStack,
+        index: &Index<Multiname>,
+        num_args: u32,
+    ) {
+        let args = stack.pop_args(num_args as usize);
+        stack.pop();
+        if multiname_name(abc, index) == Some("connect") && args.len() == 2 {
+            if let Some(host) = args[0].as_str() {
+                self.add(DependencyKind::Socket, host);
+            }
+        }
+    }
+}
+
+fn pool_string<'a>(abc: &'a AbcFile, index: &Index<String>) -> Option<&'a str> {
+    let i = (index.0 as usize).checked_sub(1)?;
+    abc.constant_pool.strings.get(i).map(String::as_str)
+}
+
+fn multiname_name<'a>(abc: &'a AbcFile, index: &Index<Multiname>) -> Option<&'a str> {
+    let i = (index.0 as usize).checked_sub(1)?;
+    match abc.constant_pool.multinames.get(i)? {
+        Multiname::QName { name, .. }
+        | Multiname::QNameA { name, .. }
+        | Multiname::RTQName { name }
+        | Multiname::RTQNameA { name }
+        | Multiname::Multiname { name, .. }
+        | Multiname::MultinameA { name, .. } => pool_string(abc, name),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::types::SendVarsMethod;
+    use crate::avm2::types::ConstantPool;
+    use crate::types::{Compression, DoAbc, Header, Rectangle, Sprite};
+    use std::marker::PhantomData;
+
+    fn avm1_bytes(actions: &[Action<'_>]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut writer = avm1::write::Writer::new(&mut data, 8);
+        for action in actions {
+            writer.write_action(action).unwrap();
+        }
+        data.push(0);
+        data
+    }
+
+    fn swf<'a>(version: u8, tags: Vec<Tag<'a>>) -> Swf<'a> {
+        Swf {
+            header: Header {
+                compression: Compression::None,
+                version,
+                uncompressed_length: 0,
+                stage_size: Rectangle::default(),
+                frame_rate: 24.0,
+                num_frames: 1,
+            },
+            tags,
+        }
+    }
+
+    fn dependency(kind: DependencyKind, url: &str) -> Dependency {
+        Dependency {
+            kind,
+            url: url.to_string(),
+        }
+    }
+
+    #[test]
+    fn find_avm1_dependencies() {
+        let s = SwfStr::from_utf8_str;
+        let main = avm1_bytes(&[
+            Action::GetUrl {
+                url: s("http://example.com/"),
+                target: s("_blank"),
+            },
+            Action::GetUrl {
+                url: s("FSCommand:quit"),
+                target: s(""),
+            },
+            Action::ConstantPool(vec![s("level.swf"), s("_root")]),
+            Action::Push(vec![Value::ConstantPool(0), Value::ConstantPool(1)]),
+            Action::GetUrl2 {
+                send_vars_method: SendVarsMethod::None,
+                is_target_sprite: true,
+                is_load_vars: false,
+            },
+        ]);
+        let sprite = avm1_bytes(&[
+            Action::Push(vec![
+                Value::Int(1024),
+                Value::Str(s("chat.example.com")),
+                Value::Int(2),
+                Value::Str(s("socket")),
+            ]),
+            Action::GetVariable,
+            Action::Push(vec![Value::Str(s("connect"))]),
+            Action::CallMethod,
+        ]);
+        let swf = swf(
+            8,
+            vec![
+                Tag::ImportAssets {
+                    url: s("library.swf"),
+                    imports: vec![],
+                },
+                Tag::DoAction(&main),
+                Tag::DefineSprite(Sprite::new(1, vec![Tag::DoAction(&sprite)])),
+            ],
+        );
+
+        assert_eq!(
+            find_dependencies(&swf),
+            vec![
+                dependency(DependencyKind::ImportAssets, "library.swf"),
+                dependency(DependencyKind::GetUrl, "http://example.com/"),
+                dependency(DependencyKind::LoadMovie, "level.swf"),
+                dependency(DependencyKind::Socket, "chat.example.com"),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_avm2_dependencies() {
+        // findpropstrict URLRequest; pushstring "data.xml"; constructprop URLRequest 1
+        let code = vec![0x5d, 1, 0x2c, 2, 0x4a, 1, 1, 0x47];
+        let abc = AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec!["URLRequest".to_string(), "data.xml".to_string()],
+                namespaces: vec![],
+                namespace_sets: vec![],
+                multinames: vec![Multiname::RTQName {
+                    name: Index(1, PhantomData),
+                }],
+            },
+            methods: vec![],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![avm2::types::MethodBody {
+                method: Index(0, PhantomData),
+                max_stack: 2,
+                num_locals: 1,
+                init_scope_depth: 0,
+                max_scope_depth: 1,
+                code,
+                exceptions: vec![],
+                traits: vec![],
+            }],
+        };
+        let mut data = Vec::new();
+        avm2::write::Writer::new(&mut data).write(abc).unwrap();
+        let swf = swf(
+            10,
+            vec![Tag::DoAbc(DoAbc {
+                name: SwfStr::from_utf8_str(""),
+                is_lazy_initialize: false,
+                data: &data,
+            })],
+        );
+
+        assert_eq!(
+            find_dependencies(&swf),
+            vec![dependency(DependencyKind::UrlRequest, "data.xml")]
+        );
+    }
+}
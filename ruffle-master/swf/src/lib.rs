@@ -24,6 +24,8 @@ extern crate num_traits;
 
 pub mod avm1;
 pub mod avm2;
+pub mod builder;
+pub mod dependencies;
 pub mod error;
 // TODO: Make this private?
 pub mod extensions;
@@ -33,6 +35,8 @@ mod tag_code;
 mod types;
 pub mod write;
 
+#[cfg(test)]
+mod round_trip;
 #[cfg(test)]
 mod test_data;
 
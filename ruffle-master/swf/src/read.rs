@@ -1863,9 +1863,7 @@ impl<'a> Reader<'a> {
         // TODO: What's a best way to know if the tag has a color transform?
         // You only know if there is still data remaining after the matrix.
         // This sucks.
-        let mut vector = [0; 128];
-        self.get_mut().read_exact(&mut vector[..tag_length])?;
-        let mut reader = Reader::new(&vector[..], self.version);
+        let mut reader = Reader::new(self.read_slice(tag_length)?, self.version);
         Ok(PlaceObject {
             version: 1,
             action: PlaceObjectAction::Place(reader.read_u16()?),
@@ -0,0 +1,542 @@
+//! Round-trip tests for the tag writer.
+//!
+//! Random tags are generated, written into an SWF, read back, and compared against the
+//! originals. Any difference means the reader and writer disagree about the format.
+//!
+//! Generated values are kept within what each tag can actually represent (e.g. RGB colors
+//! always have an alpha of 255, and fixed-point values are exact multiples of their step),
+//! so that any mismatch is a real asymmetry rather than a lossy encoding.
+//!
+//! Failing tags are shrunk by `proptest` before being reported, and their seeds are saved
+//! under `proptest-regressions` so that later runs try them first.
+
+use crate::read::{decompress_swf, parse_swf};
+use crate::string::SwfStr;
+use crate::types::*;
+use crate::write::write_swf;
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::sample::select;
+
+/// A short string of ASCII letters. Strings are leaked so that tags can borrow them.
+fn string() -> impl Strategy<Value = &'static SwfStr> {
+    "[a-zA-Z]{0,12}".prop_map(|s| SwfStr::from_bytes(Box::leak(s.into_bytes().into_boxed_slice())))
+}
+
+fn bytes() -> impl Strategy<Value = &'static [u8]> {
+    vec(any::<u8>(), 0..=32).prop_map(|bytes| &*Box::leak(bytes.into_boxed_slice()))
+}
+
+fn twips(limit: i32) -> impl Strategy<Value = Twips> {
+    // Zero is special-cased by several encodings, so make sure it comes up often.
+    prop_oneof![
+        1 => Just(Twips::zero()),
+        3 => (-limit..=limit).prop_map(Twips::new),
+    ]
+}
+
+fn rectangle() -> impl Strategy<Value = Rectangle> {
+    (
+        twips(1 << 20),
+        twips(1 << 20),
+        twips(1 << 20),
+        twips(1 << 20),
+    )
+        .prop_map(|(x_min, x_max, y_min, y_max)| Rectangle {
+            x_min,
+            x_max,
+            y_min,
+            y_max,
+        })
+}
+
+fn color(has_alpha: bool) -> impl Strategy<Value = Color> {
+    any::<[u8; 4]>().prop_map(move |[r, g, b, a]| Color {
+        r,
+        g,
+        b,
+        a: if has_alpha { a } else { 255 },
+    })
+}
+
+fn fixed8() -> impl Strategy<Value = f32> {
+    (-0x8000..=0x7fff).prop_map(|n: i32| n as f32 / 256.0)
+}
+
+fn fixed16() -> impl Strategy<Value = f32> {
+    (-(1 << 20)..=1 << 20).prop_map(|n: i32| n as f32 / 65536.0)
+}
+
+fn matrix() -> impl Strategy<Value = Matrix> {
+    (
+        option::of((fixed16(), fixed16())),
+        option::of((fixed16(), fixed16())),
+        twips(1 << 20),
+        twips(1 << 20),
+    )
+        .prop_map(|(scale, rotate_skew, tx, ty)| {
+            let mut matrix = Matrix::identity();
+            if let Some((a, d)) = scale {
+                matrix.a = a;
+                matrix.d = d;
+            }
+            if let Some((b, c)) = rotate_skew {
+                matrix.b = b;
+                matrix.c = c;
+            }
+            matrix.tx = tx;
+            matrix.ty = ty;
+            matrix
+        })
+}
+
+fn color_transform(has_alpha: bool) -> impl Strategy<Value = ColorTransform> {
+    let multiply = || (-512..=512).prop_map(|n: i32| n as f32 / 256.0);
+    let add = || -255..=255i16;
+
+    (
+        option::of((multiply(), multiply(), multiply(), multiply())),
+        option::of((add(), add(), add(), add())),
+    )
+        .prop_map(move |(multiply, add)| {
+            let mut color_transform = ColorTransform::new();
+            if let Some((r, g, b, a)) = multiply {
+                color_transform.r_multiply = r;
+                color_transform.g_multiply = g;
+                color_transform.b_multiply = b;
+                if has_alpha {
+                    color_transform.a_multiply = a;
+                }
+            }
+            if let Some((r, g, b, a)) = add {
+                color_transform.r_add = r;
+                color_transform.g_add = g;
+                color_transform.b_add = b;
+                if has_alpha {
+                    color_transform.a_add = a;
+                }
+            }
+            color_transform
+        })
+}
+
+fn place_object() -> impl Strategy<Value = PlaceObject<'static>> {
+    prop_oneof![place_object_1(), place_object_2_or_3()]
+}
+
+fn place_object_1() -> impl Strategy<Value = PlaceObject<'static>> {
+    // PlaceObject can only place a character, and always has a matrix.
+    (
+        any::<u16>(),
+        any::<u16>(),
+        matrix(),
+        option::of(color_transform(false)),
+    )
+        .prop_map(|(depth, id, matrix, color_transform)| {
+            let mut place_object = PlaceObject::place(depth, id).with_matrix(matrix);
+            place_object.version = 1;
+            place_object.color_transform = color_transform;
+            place_object
+        })
+}
+
+fn place_object_2_or_3() -> impl Strategy<Value = PlaceObject<'static>> {
+    let blend_mode = select(vec![
+        BlendMode::Normal,
+        BlendMode::Layer,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Lighten,
+        BlendMode::Darken,
+        BlendMode::Difference,
+        BlendMode::Add,
+        BlendMode::Subtract,
+        BlendMode::Invert,
+        BlendMode::Alpha,
+        BlendMode::Erase,
+        BlendMode::Overlay,
+        BlendMode::HardLight,
+    ]);
+    let place_object_3 = (
+        option::of(string()),
+        option::of(blend_mode),
+        option::of(any::<bool>()),
+        option::of(any::<bool>()),
+        option::of(color(true)),
+    );
+
+    (
+        (any::<u16>(), 0..=2u8, any::<u16>()),
+        option::of(matrix()),
+        option::of(color_transform(true)),
+        option::of(any::<u16>()),
+        option::of(string()),
+        option::of(any::<u16>()),
+        option::of(place_object_3),
+    )
+        .prop_map(
+            |((depth, action, id), matrix, color_transform, ratio, name, clip_depth, v3)| {
+                let mut place_object = match action {
+                    0 => PlaceObject::place(depth, id),
+                    1 => PlaceObject::modify(depth),
+                    _ => PlaceObject::replace(depth, id),
+                };
+                place_object.matrix = matrix;
+                place_object.color_transform = color_transform;
+                place_object.ratio = ratio;
+                place_object.name = name;
+                place_object.clip_depth = clip_depth;
+                if let Some((class_name, blend_mode, is_bitmap_cached, is_visible, background)) = v3
+                {
+                    place_object.version = 3;
+                    place_object.class_name = class_name;
+                    place_object.blend_mode = blend_mode;
+                    place_object.is_bitmap_cached = is_bitmap_cached;
+                    place_object.is_visible = is_visible;
+                    place_object.background_color = background;
+                }
+                place_object
+            },
+        )
+}
+
+fn gradient(shape_version: u8) -> impl Strategy<Value = Gradient> {
+    (
+        matrix(),
+        select(vec![
+            GradientSpread::Pad,
+            GradientSpread::Reflect,
+            GradientSpread::Repeat,
+        ]),
+        select(vec![
+            GradientInterpolation::Rgb,
+            GradientInterpolation::LinearRgb,
+        ]),
+        vec((any::<u8>(), color(shape_version >= 3)), 0..=15),
+    )
+        .prop_map(|(matrix, spread, interpolation, records)| Gradient {
+            matrix,
+            spread,
+            interpolation,
+            records: records
+                .into_iter()
+                .map(|(ratio, color)| GradientRecord { ratio, color })
+                .collect(),
+        })
+}
+
+fn fill_style(shape_version: u8) -> impl Strategy<Value = FillStyle> {
+    prop_oneof![
+        color(shape_version >= 3).prop_map(FillStyle::Color),
+        gradient(shape_version).prop_map(FillStyle::LinearGradient),
+        gradient(shape_version).prop_map(FillStyle::RadialGradient),
+        (gradient(shape_version), fixed8()).prop_map(|(gradient, focal_point)| {
+            FillStyle::FocalGradient {
+                gradient,
+                focal_point,
+            }
+        }),
+        (any::<u16>(), matrix(), any::<bool>(), any::<bool>()).prop_map(
+            |(id, matrix, is_smoothed, is_repeating)| FillStyle::Bitmap {
+                id,
+                matrix,
+                is_smoothed,
+                is_repeating,
+            }
+        ),
+    ]
+}
+
+fn line_style(shape_version: u8) -> BoxedStrategy<LineStyle> {
+    let width = any::<u16>().prop_map(Twips::new);
+    if shape_version < 4 {
+        return (width, color(shape_version >= 3))
+            .prop_map(|(width, color)| LineStyle::new_v1(width, color))
+            .boxed();
+    }
+
+    let cap = || {
+        select(vec![
+            LineCapStyle::Round,
+            LineCapStyle::None,
+            LineCapStyle::Square,
+        ])
+    };
+    // A line with a fill style has no color of its own.
+    let fill = prop_oneof![
+        fill_style(shape_version).prop_map(|fill_style| (Some(fill_style), Color::from_rgb(0, 0))),
+        color(true).prop_map(|color| (None, color)),
+    ];
+    let join_style = prop_oneof![
+        Just(LineJoinStyle::Round),
+        Just(LineJoinStyle::Bevel),
+        fixed8().prop_map(LineJoinStyle::Miter),
+    ];
+
+    (width, fill, cap(), cap(), join_style, any::<[bool; 4]>())
+        .prop_map(
+            |(
+                width,
+                (fill_style, color),
+                start_cap,
+                end_cap,
+                join_style,
+                [allow_scale_x, allow_scale_y, is_pixel_hinted, allow_close],
+            )| LineStyle {
+                width,
+                color,
+                start_cap,
+                end_cap,
+                join_style,
+                fill_style,
+                allow_scale_x,
+                allow_scale_y,
+                is_pixel_hinted,
+                allow_close,
+            },
+        )
+        .boxed()
+}
+
+fn shape_styles(shape_version: u8) -> impl Strategy<Value = ShapeStyles> {
+    (
+        vec(fill_style(shape_version), 0..=3),
+        vec(line_style(shape_version), 0..=3),
+    )
+        .prop_map(|(fill_styles, line_styles)| ShapeStyles {
+            fill_styles,
+            line_styles,
+        })
+}
+
+/// Generates a style change record. Its style indices are arbitrary until `fix_style_indices`
+/// brings them into range.
+fn style_change(shape_version: u8) -> impl Strategy<Value = StyleChangeData> {
+    let new_styles = if shape_version >= 2 {
+        option::of(shape_styles(shape_version)).boxed()
+    } else {
+        Just(None).boxed()
+    };
+
+    (
+        option::of((twips(1 << 20), twips(1 << 20))),
+        option::of(any::<u32>()),
+        option::of(any::<u32>()),
+        option::of(any::<u32>()),
+        new_styles,
+    )
+        .prop_map(
+            |(move_to, fill_style_0, fill_style_1, line_style, new_styles)| {
+                let mut style_change = StyleChangeData {
+                    move_to,
+                    fill_style_0,
+                    fill_style_1,
+                    line_style,
+                    new_styles,
+                };
+                // A style change with no fields set would be read back as the end of the shape.
+                if style_change.move_to.is_none()
+                    && style_change.fill_style_0.is_none()
+                    && style_change.fill_style_1.is_none()
+                    && style_change.line_style.is_none()
+                    && style_change.new_styles.is_none()
+                {
+                    style_change.move_to = Some((Twips::zero(), Twips::zero()));
+                }
+                style_change
+            },
+        )
+}
+
+/// Wraps the style indices of each style change record into the styles in effect before that
+/// record, which may then replace them with `new_styles`.
+fn fix_style_indices(styles: &ShapeStyles, records: &mut [ShapeRecord]) {
+    fn wrap(index: &mut Option<u32>, num_styles: usize) {
+        // Index 0 means no style.
+        if let Some(index) = index {
+            *index %= num_styles as u32 + 1;
+        }
+    }
+
+    let mut num_fill_styles = styles.fill_styles.len();
+    let mut num_line_styles = styles.line_styles.len();
+    for record in records {
+        if let ShapeRecord::StyleChange(style_change) = record {
+            wrap(&mut style_change.fill_style_0, num_fill_styles);
+            wrap(&mut style_change.fill_style_1, num_fill_styles);
+            wrap(&mut style_change.line_style, num_line_styles);
+            if let Some(new_styles) = &style_change.new_styles {
+                num_fill_styles = new_styles.fill_styles.len();
+                num_line_styles = new_styles.line_styles.len();
+            }
+        }
+    }
+}
+
+fn shape_record(shape_version: u8) -> impl Strategy<Value = ShapeRecord> {
+    prop_oneof![
+        (twips(0xffff), twips(0xffff))
+            .prop_map(|(delta_x, delta_y)| ShapeRecord::StraightEdge { delta_x, delta_y }),
+        (twips(0xffff), twips(0xffff), twips(0xffff), twips(0xffff)).prop_map(
+            |(control_delta_x, control_delta_y, anchor_delta_x, anchor_delta_y)| {
+                ShapeRecord::CurvedEdge {
+                    control_delta_x,
+                    control_delta_y,
+                    anchor_delta_x,
+                    anchor_delta_y,
+                }
+            }
+        ),
+        style_change(shape_version).prop_map(ShapeRecord::StyleChange),
+    ]
+}
+
+fn shape() -> impl Strategy<Value = Shape> {
+    (1..=4u8)
+        .prop_flat_map(|version| {
+            (
+                Just(version),
+                any::<u16>(),
+                rectangle(),
+                (rectangle(), any::<[bool; 3]>()),
+                shape_styles(version),
+                vec(shape_record(version), 0..=12),
+            )
+        })
+        .prop_map(
+            |(version, id, shape_bounds, (edge_bounds, flags), styles, mut shape)| {
+                let (
+                    edge_bounds,
+                    [has_fill_winding_rule, has_non_scaling_strokes, has_scaling_strokes],
+                ) = if version >= 4 {
+                    (edge_bounds, flags)
+                } else {
+                    // Older shapes don't store these, and are read with fixed values.
+                    (shape_bounds.clone(), [false, true, false])
+                };
+                fix_style_indices(&styles, &mut shape);
+                Shape {
+                    version,
+                    id,
+                    shape_bounds,
+                    edge_bounds,
+                    has_fill_winding_rule,
+                    has_non_scaling_strokes,
+                    has_scaling_strokes,
+                    styles,
+                    shape,
+                }
+            },
+        )
+}
+
+/// A tag that may appear on a sprite's timeline.
+fn control_tag() -> impl Strategy<Value = Tag<'static>> {
+    prop_oneof![
+        Just(()).prop_map(|_| Tag::ShowFrame),
+        place_object().prop_map(|place_object| Tag::PlaceObject(Box::new(place_object))),
+        (any::<u16>(), option::of(any::<u16>())).prop_map(|(depth, character_id)| {
+            Tag::RemoveObject(RemoveObject {
+                depth,
+                character_id,
+            })
+        }),
+        (string(), any::<bool>())
+            .prop_map(|(label, is_anchor)| Tag::FrameLabel(FrameLabel { label, is_anchor })),
+    ]
+}
+
+fn frame_label_data() -> impl Strategy<Value = FrameLabelData<'static>> {
+    (any::<u32>(), string()).prop_map(|(frame_num, label)| FrameLabelData { frame_num, label })
+}
+
+fn tag() -> impl Strategy<Value = Tag<'static>> {
+    prop_oneof![
+        4 => control_tag(),
+        1 => color(false).prop_map(Tag::SetBackgroundColor),
+        1 => (any::<u16>(), any::<u16>()).prop_map(|(max_recursion_depth, timeout_in_seconds)| {
+            Tag::ScriptLimits {
+                max_recursion_depth,
+                timeout_in_seconds,
+            }
+        }),
+        1 => (any::<u16>(), any::<u16>())
+            .prop_map(|(depth, tab_index)| Tag::SetTabIndex { depth, tab_index }),
+        1 => option::of(string()).prop_map(Tag::Protect),
+        1 => string().prop_map(Tag::EnableDebugger),
+        1 => string().prop_map(Tag::Metadata),
+        1 => vec((any::<u16>(), string()), 0..=4).prop_map(|assets| {
+            Tag::ExportAssets(
+                assets
+                    .into_iter()
+                    .map(|(id, name)| ExportedAsset { id, name })
+                    .collect(),
+            )
+        }),
+        1 => vec((any::<u16>(), string()), 0..=4).prop_map(|links| {
+            Tag::SymbolClass(
+                links
+                    .into_iter()
+                    .map(|(id, class_name)| SymbolClassLink { id, class_name })
+                    .collect(),
+            )
+        }),
+        1 => (any::<u16>(), bytes()).prop_map(|(id, data)| Tag::DefineBinaryData { id, data }),
+        1 => (vec(frame_label_data(), 0..=4), vec(frame_label_data(), 0..=4)).prop_map(
+            |(scenes, frame_labels)| {
+                Tag::DefineSceneAndFrameLabelData(DefineSceneAndFrameLabelData {
+                    scenes,
+                    frame_labels,
+                })
+            }
+        ),
+        1 => any::<[bool; 5]>().prop_map(
+            |[use_direct_blit, use_gpu, has_metadata, is_action_script_3, use_network_sandbox]| {
+                Tag::FileAttributes(FileAttributes {
+                    use_direct_blit,
+                    use_gpu,
+                    has_metadata,
+                    is_action_script_3,
+                    use_network_sandbox,
+                })
+            }
+        ),
+        2 => shape().prop_map(Tag::DefineShape),
+        1 => (any::<u16>(), vec(control_tag(), 0..=8))
+            .prop_map(|(id, tags)| Tag::DefineSprite(Sprite::new(id, tags))),
+    ]
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn round_trip_tags(tag in tag(), version in 8..=15u8) {
+        // Bitmap smoothing is only stored in SWF version 8 and later.
+        let swf = Swf {
+            header: Header {
+                compression: Compression::None,
+                version,
+                uncompressed_length: 0,
+                stage_size: Rectangle {
+                    x_min: Twips::zero(),
+                    x_max: Twips::from_pixels(550.0),
+                    y_min: Twips::zero(),
+                    y_max: Twips::from_pixels(400.0),
+                },
+                frame_rate: 24.0,
+                num_frames: 1,
+            },
+            tags: vec![tag],
+        };
+
+        let mut data = Vec::new();
+        write_swf(&swf, &mut data)
+            .map_err(|e| TestCaseError::fail(format!("Error writing tag: {:?}", e)))?;
+        let swf_buf = decompress_swf(&data[..]).unwrap();
+        let read_swf = parse_swf(&swf_buf)
+            .map_err(|e| TestCaseError::fail(format!("Error reading tag: {:?}", e)))?;
+        prop_assert_eq!(read_swf.tags, swf.tags, "Tag changed after round trip");
+    }
+}
@@ -417,6 +417,88 @@ pub struct PlaceObject<'a> {
     pub amf_data: Option<&'a [u8]>,
 }
 
+impl<'a> PlaceObject<'a> {
+    /// A `PlaceObject2` tag that places a new instance of a character at a
+    /// depth, with every optional field left unset.
+    pub const fn place(depth: Depth, id: CharacterId) -> Self {
+        Self::new(depth, PlaceObjectAction::Place(id))
+    }
+
+    /// A `PlaceObject2` tag that modifies the object already at a depth.
+    pub const fn modify(depth: Depth) -> Self {
+        Self::new(depth, PlaceObjectAction::Modify)
+    }
+
+    /// A `PlaceObject2` tag that replaces the object at a depth with a new
+    /// instance of a character.
+    pub const fn replace(depth: Depth, id: CharacterId) -> Self {
+        Self::new(depth, PlaceObjectAction::Replace(id))
+    }
+
+    const fn new(depth: Depth, action: PlaceObjectAction) -> Self {
+        Self {
+            version: 2,
+            action,
+            depth,
+            matrix: None,
+            color_transform: None,
+            ratio: None,
+            name: None,
+            clip_depth: None,
+            class_name: None,
+            filters: None,
+            background_color: None,
+            blend_mode: None,
+            clip_actions: None,
+            is_image: false,
+            is_bitmap_cached: None,
+            is_visible: None,
+            amf_data: None,
+        }
+    }
+
+    pub fn with_matrix(mut self, matrix: Matrix) -> Self {
+        self.matrix = Some(matrix);
+        self
+    }
+
+    pub fn with_color_transform(mut self, color_transform: ColorTransform) -> Self {
+        self.color_transform = Some(color_transform);
+        self
+    }
+
+    pub fn with_ratio(mut self, ratio: u16) -> Self {
+        self.ratio = Some(ratio);
+        self
+    }
+
+    pub fn with_name(mut self, name: &'a SwfStr) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Make the placed object a mask for the objects above it, up to and
+    /// including `clip_depth`.
+    pub fn with_clip_depth(mut self, clip_depth: Depth) -> Self {
+        self.clip_depth = Some(clip_depth);
+        self
+    }
+
+    /// Set the blend mode, upgrading the tag to `PlaceObject3`.
+    pub fn with_blend_mode(mut self, blend_mode: BlendMode) -> Self {
+        self.version = self.version.max(3);
+        self.blend_mode = Some(blend_mode);
+        self
+    }
+
+    /// Set the filters, upgrading the tag to `PlaceObject3`.
+    pub fn with_filters(mut self, filters: Vec<Filter>) -> Self {
+        self.version = self.version.max(3);
+        self.filters = Some(filters);
+        self
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum PlaceObjectAction {
     Place(CharacterId),
@@ -785,7 +867,25 @@ pub struct Sprite<'a> {
     pub tags: Vec<Tag<'a>>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+impl<'a> Sprite<'a> {
+    /// A `DefineSprite` tag with the given timeline.
+    ///
+    /// Each `ShowFrame` in `tags` ends a frame, so `num_frames` is the number
+    /// of `ShowFrame` tags.
+    pub fn new(id: CharacterId, tags: Vec<Tag<'a>>) -> Self {
+        let num_frames = tags
+            .iter()
+            .filter(|tag| matches!(tag, Tag::ShowFrame))
+            .count() as u16;
+        Self {
+            id,
+            num_frames,
+            tags,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
 pub struct ShapeStyles {
     pub fill_styles: Vec<FillStyle>,
     pub line_styles: Vec<LineStyle>,
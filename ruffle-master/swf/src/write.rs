@@ -87,7 +87,7 @@ pub fn write_swf<W: Write>(swf: &Swf, mut output: W) -> Result<()> {
 }
 
 #[cfg(feature = "flate2")]
-fn write_zlib_swf<W: Write>(mut output: W, swf_body: &[u8]) -> Result<()> {
+pub(crate) fn write_zlib_swf<W: Write>(mut output: W, swf_body: &[u8]) -> Result<()> {
     use flate2::write::ZlibEncoder;
     use flate2::Compression;
     let mut encoder = ZlibEncoder::new(&mut output, Compression::best());
@@ -97,7 +97,7 @@ fn write_zlib_swf<W: Write>(mut output: W, swf_body: &[u8]) -> Result<()> {
 }
 
 #[cfg(all(feature = "libflate", not(feature = "flate2")))]
-fn write_zlib_swf<W: Write>(mut output: W, swf_body: &[u8]) -> Result<()> {
+pub(crate) fn write_zlib_swf<W: Write>(mut output: W, swf_body: &[u8]) -> Result<()> {
     use libflate::zlib::Encoder;
     let mut encoder = Encoder::new(&mut output)?;
     encoder.write_all(&swf_body)?;
@@ -106,7 +106,7 @@ fn write_zlib_swf<W: Write>(mut output: W, swf_body: &[u8]) -> Result<()> {
 }
 
 #[cfg(not(any(feature = "flate2", feature = "libflate")))]
-fn write_zlib_swf<W: Write>(_output: W, _swf_body: &[u8]) -> Result<()> {
+pub(crate) fn write_zlib_swf<W: Write>(_output: W, _swf_body: &[u8]) -> Result<()> {
     Err(Error::unsupported(
         "Support for Zlib compressed SWFs is not enabled.",
     ))
@@ -355,14 +355,13 @@ impl<W: Write> Writer<W> {
             color_transform.b_multiply,
         ];
         let add = [
-            color_transform.a_add,
+            color_transform.r_add,
             color_transform.g_add,
             color_transform.b_add,
-            color_transform.a_add,
         ];
         let mut bits = self.bits();
-        bits.write_bit(has_mult)?;
         bits.write_bit(has_add)?;
+        bits.write_bit(has_mult)?;
         let mut num_bits = if has_mult {
             multiply
                 .iter()
@@ -1575,15 +1574,17 @@ impl<W: Write> Writer<W> {
                 let mut num_bits = max(count_sbits_twips(delta_x), count_sbits_twips(delta_y));
                 num_bits = max(2, num_bits);
                 let is_axis_aligned = delta_x.get() == 0 || delta_y.get() == 0;
+                let is_vertical = is_axis_aligned && delta_x.get() == 0;
                 bits.write_ubits(4, num_bits - 2)?;
                 bits.write_bit(!is_axis_aligned)?;
                 if is_axis_aligned {
-                    bits.write_bit(delta_x.get() == 0)?;
+                    bits.write_bit(is_vertical)?;
                 }
-                if delta_x.get() != 0 {
+                // An axis-aligned edge stores exactly one delta, even if both are zero.
+                if !is_axis_aligned || !is_vertical {
                     bits.write_sbits_twips(num_bits, delta_x)?;
                 }
-                if delta_y.get() != 0 {
+                if !is_axis_aligned || is_vertical {
                     bits.write_sbits_twips(num_bits, delta_y)?;
                 }
             }
@@ -1603,7 +1604,8 @@ impl<W: Write> Writer<W> {
                 .iter()
                 .map(|x| count_sbits_twips(*x))
                 .max()
-                .unwrap();
+                .unwrap()
+                .max(2);
                 bits.write_ubits(4, num_bits - 2)?;
                 bits.write_sbits_twips(num_bits, control_delta_x)?;
                 bits.write_sbits_twips(num_bits, control_delta_y)?;
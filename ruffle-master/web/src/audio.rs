@@ -1,7 +1,7 @@
 use fnv::FnvHashMap;
 use generational_arena::Arena;
 use ruffle_core::backend::audio::{
-    decoders::{AdpcmDecoder, Mp3Decoder, NellymoserDecoder},
+    decoders::{AdpcmDecoder, Mp3Decoder, Mp3FrameIndex, NellymoserDecoder},
     swf::{self, AudioCompression},
     AudioBackend, PreloadStreamHandle, SoundHandle, SoundInstanceHandle, SoundTransform,
 };
@@ -16,7 +16,6 @@ pub struct WebAudioBackend {
     sounds: Arena<Sound>,
     left_samples: Vec<f32>,
     right_samples: Vec<f32>,
-    frame_rate: f64,
     min_sample_rate: u16,
     preload_stream_data: FnvHashMap<PreloadStreamHandle, StreamData>,
     next_stream_id: u32,
@@ -321,7 +320,6 @@ impl WebAudioBackend {
             next_stream_id: 0,
             left_samples: vec![],
             right_samples: vec![],
-            frame_rate: 1.0,
             min_sample_rate,
         })
     }
@@ -793,10 +791,6 @@ impl WebAudioBackend {
 }
 
 impl AudioBackend for WebAudioBackend {
-    fn set_frame_rate(&mut self, frame_rate: f64) {
-        self.frame_rate = frame_rate
-    }
-
     fn register_sound(&mut self, sound: &swf::Sound) -> Result<SoundHandle, Error> {
         // Slice off latency seek for MP3 data.
         let (skip_sample_frames, data) = if sound.format.compression == AudioCompression::Mp3 {
@@ -806,21 +800,32 @@ impl AudioBackend for WebAudioBackend {
             (0, sound.data)
         };
 
+        // The sample count in the SWF header can't be trusted for VBR MP3s.
+        let num_sample_frames = if sound.format.compression == AudioCompression::Mp3 {
+            Mp3FrameIndex::new(data).sound_sample_frames(sound.num_samples, skip_sample_frames)
+        } else {
+            sound.num_samples
+        };
+
         let sound = Sound {
             format: sound.format.clone(),
             source: SoundSource::AudioBuffer(self.decompress_to_audio_buffer(
                 &sound.format,
                 data,
-                sound.num_samples,
+                num_sample_frames,
                 None,
             )?),
-            num_sample_frames: sound.num_samples,
+            num_sample_frames,
             skip_sample_frames,
             stream_segments: vec![],
         };
         Ok(self.sounds.insert(sound))
     }
 
+    fn unregister_sound(&mut self, sound: SoundHandle) {
+        self.sounds.remove(sound);
+    }
+
     fn preload_sound_stream_head(
         &mut self,
         stream_info: &swf::SoundStreamHead,
@@ -834,7 +839,8 @@ impl AudioBackend for WebAudioBackend {
                 audio_data: vec![],
                 num_sample_frames: 0,
                 samples_per_block: stream_info.num_samples_per_block.into(),
-                skip_sample_frames: stream_info.latency_seek as u16,
+                // Negative latencies can't be skipped, so they are ignored.
+                skip_sample_frames: stream_info.latency_seek.max(0) as u16,
                 adpcm_block_offsets: vec![],
                 stream_segments: vec![],
                 last_clip_frame: 0,
@@ -941,7 +947,7 @@ impl AudioBackend for WebAudioBackend {
         &mut self,
         stream_handle: Option<SoundHandle>,
         clip_frame: u16,
-        _clip_data: ruffle_core::tag_utils::SwfSlice,
+        clip_data: ruffle_core::tag_utils::SwfSlice,
         _stream_info: &swf::SoundStreamHead,
     ) -> Result<SoundInstanceHandle, Error> {
         if let Some(stream) = stream_handle {
@@ -958,9 +964,21 @@ impl AudioBackend for WebAudioBackend {
                             if i > 0 {
                                 let (segment_frame, segment_sample) = sound.stream_segments[i - 1];
                                 let frames_skipped = clip_frame.saturating_sub(segment_frame);
-                                let samples_per_frame = 44100.0 / self.frame_rate;
-                                segment_sample
-                                    + u32::from(frames_skipped) * (samples_per_frame as u32)
+                                // Stream blocks are timed to the frame rate of the movie
+                                // they are in, even if it's running at the rate of a
+                                // parent movie with a different frame rate.
+                                // A movie with a frame rate of zero never advances, so
+                                // the stream starts where the segment does.
+                                let frame_rate = clip_data.movie.header().frame_rate;
+                                let samples_per_frame = if frame_rate > 0.0 {
+                                    44100.0 / f64::from(frame_rate)
+                                } else {
+                                    0.0
+                                };
+                                segment_sample.saturating_add(
+                                    u32::from(frames_skipped)
+                                        .saturating_mul(samples_per_frame as u32),
+                                )
                             } else {
                                 0
                             }
@@ -975,7 +993,15 @@ impl AudioBackend for WebAudioBackend {
                     });
                 }
             }
-            let instance = self.start_sound_internal(stream, sound_info.as_ref())?;
+            // Streams played from the start still skip the latency of their encoder.
+            let sound_info = sound_info.unwrap_or(swf::SoundInfo {
+                event: swf::SoundEvent::Event,
+                in_sample: None,
+                out_sample: None,
+                num_loops: 1,
+                envelope: None,
+            });
+            let instance = self.start_sound_internal(stream, Some(&sound_info))?;
             Ok(instance)
         } else {
             let msg = format!("Missing stream for sound ID {:?}", stream_handle);
@@ -0,0 +1,46 @@
+use ruffle_core::backend::gamepad::{GamepadBackend, GamepadState};
+use wasm_bindgen::JsCast;
+use web_sys::{Gamepad, GamepadButton};
+
+/// Reads gamepads through the browser's Gamepad API.
+///
+/// Browsers only report a gamepad once a button on it has been pressed while
+/// the page is open, so a gamepad may show up a little after it's plugged in.
+pub struct WebGamepadBackend();
+
+impl WebGamepadBackend {
+    pub fn new() -> Self {
+        Self()
+    }
+}
+
+impl GamepadBackend for WebGamepadBackend {
+    fn gamepads(&mut self) -> Vec<GamepadState> {
+        let gamepads = match web_sys::window().and_then(|w| w.navigator().get_gamepads().ok()) {
+            Some(gamepads) => gamepads,
+            None => return vec![],
+        };
+
+        gamepads
+            .iter()
+            .filter_map(|gamepad| gamepad.dyn_into::<Gamepad>().ok())
+            .filter(|gamepad| gamepad.connected())
+            .map(|gamepad| GamepadState {
+                // The index of a gamepad is kept until it is disconnected.
+                id: gamepad.index().to_string(),
+                name: gamepad.id(),
+                axes: gamepad
+                    .axes()
+                    .iter()
+                    .map(|axis| axis.as_f64().unwrap_or(0.0))
+                    .collect(),
+                buttons: gamepad
+                    .buttons()
+                    .iter()
+                    .filter_map(|button| button.dyn_into::<GamepadButton>().ok())
+                    .map(|button| button.value())
+                    .collect(),
+            })
+            .collect()
+    }
+}
@@ -6,6 +6,7 @@
 
 //! Ruffle web frontend.
 mod audio;
+mod gamepad;
 mod locale;
 mod log_adapter;
 mod navigator;
@@ -16,12 +17,17 @@ use generational_arena::{Arena, Index};
 use js_sys::{Array, Function, Object, Uint8Array};
 use ruffle_core::backend::{
     audio::{AudioBackend, NullAudioBackend},
+    clipboard::MemoryClipboardBackend,
+    local_connection::{InProcessLocalConnectionBackend, LocalConnectionBus},
     render::RenderBackend,
+    socket::NullSocketBackend,
     storage::{MemoryStorageBackend, StorageBackend},
-    ui::UiBackend,
+    ui::{StageOrientation, UiBackend},
     video::SoftwareVideoBackend,
 };
-use ruffle_core::config::Letterbox;
+use ruffle_core::config::{
+    GamepadKeyMapping, Letterbox, LoaderLimits, StageQuality, TextRendering,
+};
 use ruffle_core::context::UpdateContext;
 use ruffle_core::events::{KeyCode, MouseWheelDelta};
 use ruffle_core::external::{
@@ -39,8 +45,8 @@ use std::time::Duration;
 use std::{cell::RefCell, error::Error, num::NonZeroI32};
 use wasm_bindgen::{prelude::*, JsCast, JsValue};
 use web_sys::{
-    AddEventListenerOptions, Element, Event, EventTarget, HtmlCanvasElement, HtmlElement,
-    KeyboardEvent, PointerEvent, WheelEvent,
+    AddEventListenerOptions, CompositionEvent, Element, Event, EventTarget, HtmlCanvasElement,
+    HtmlElement, KeyboardEvent, PointerEvent, WheelEvent,
 };
 
 static RUFFLE_GLOBAL_PANIC: Once = Once::new();
@@ -52,6 +58,9 @@ thread_local! {
     static INSTANCES: RefCell<Arena<RefCell<RuffleInstance>>> = RefCell::new(Arena::new());
 
     static CURRENT_CONTEXT: RefCell<Option<*mut UpdateContext<'static, 'static, 'static>>> = RefCell::new(None);
+
+    /// Carries `LocalConnection` messages between the instances on the page.
+    static LOCAL_CONNECTION_BUS: LocalConnectionBus = LocalConnectionBus::new();
 }
 
 type AnimationHandler = Closure<dyn FnMut(f64)>;
@@ -73,9 +82,13 @@ struct RuffleInstance {
     window_mouse_down_callback: Option<Closure<dyn FnMut(PointerEvent)>>,
     mouse_up_callback: Option<Closure<dyn FnMut(PointerEvent)>>,
     mouse_wheel_callback: Option<Closure<dyn FnMut(WheelEvent)>>,
+    context_menu_callback: Option<Closure<dyn FnMut(Event)>>,
     key_down_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
     key_up_callback: Option<Closure<dyn FnMut(KeyboardEvent)>>,
+    composition_update_callback: Option<Closure<dyn FnMut(CompositionEvent)>>,
+    composition_end_callback: Option<Closure<dyn FnMut(CompositionEvent)>>,
     unload_callback: Option<Closure<dyn FnMut(Event)>>,
+    orientation_change_callback: Option<Closure<dyn FnMut(Event)>>,
     has_focus: bool,
     trace_observer: Arc<RefCell<JsValue>>,
 }
@@ -110,8 +123,14 @@ extern "C" {
     #[wasm_bindgen(method, js_name = "displayMessage")]
     fn display_message(this: &JavascriptPlayer, message: &str);
 
+    #[wasm_bindgen(method, js_name = "showContextMenu")]
+    fn show_context_menu(this: &JavascriptPlayer, items: &JsValue);
+
     #[wasm_bindgen(method, getter, js_name = "isFullscreen")]
     fn is_fullscreen(this: &JavascriptPlayer) -> bool;
+
+    #[wasm_bindgen(method, js_name = "setFullscreen")]
+    fn set_fullscreen(this: &JavascriptPlayer, is_full: bool);
 }
 
 struct JavascriptInterface {
@@ -129,6 +148,14 @@ pub struct Config {
 
     letterbox: Letterbox,
 
+    quality: StageQuality,
+
+    #[serde(rename = "textRendering")]
+    text_rendering: TextRendering,
+
+    #[serde(rename = "gamepadKeyMapping")]
+    gamepad_key_mapping: GamepadKeyMapping,
+
     #[serde(rename = "upgradeToHttps")]
     upgrade_to_https: bool,
 
@@ -140,6 +167,18 @@ pub struct Config {
 
     #[serde(rename = "maxExecutionDuration")]
     max_execution_duration: Duration,
+
+    #[serde(rename = "loaderLimits")]
+    loader_limits: LoaderLimits,
+
+    #[serde(rename = "strictMode")]
+    strict_mode: bool,
+
+    #[serde(rename = "keyboardNavigation")]
+    keyboard_navigation: bool,
+
+    #[serde(rename = "permissiveSecurity")]
+    permissive_security: bool,
 }
 
 impl Default for Config {
@@ -148,10 +187,17 @@ impl Default for Config {
             allow_script_access: false,
             background_color: Default::default(),
             letterbox: Default::default(),
+            quality: Default::default(),
+            text_rendering: Default::default(),
+            gamepad_key_mapping: Default::default(),
             upgrade_to_https: true,
             warn_on_unsupported_content: true,
             log_level: log::Level::Error,
             max_execution_duration: Duration::from_secs(15),
+            loader_limits: Default::default(),
+            strict_mode: false,
+            keyboard_navigation: false,
+            permissive_security: false,
         }
     }
 }
@@ -248,6 +294,32 @@ impl Ruffle {
         })
     }
 
+    /// Run the item at `index` of the context menu that was last passed to
+    /// `showContextMenu`.
+    pub fn run_context_menu_item(&mut self, index: usize) {
+        INSTANCES.with(|instances| {
+            let instances = instances.borrow();
+            let instance = instances.get(self.0).unwrap();
+            instance
+                .borrow()
+                .core
+                .lock()
+                .unwrap()
+                .run_context_menu_item(index);
+        });
+    }
+
+    /// Describe the objects that Tab moves the focus between, in order, for
+    /// auditing how accessible the movie is.
+    pub fn tab_stops(&mut self) -> JsValue {
+        INSTANCES.with(|instances| {
+            let instances = instances.borrow();
+            let instance = instances.get(self.0).unwrap();
+            let tab_stops = instance.borrow().core.lock().unwrap().tab_stops();
+            JsValue::from_serde(&tab_stops).unwrap_or(JsValue::NULL)
+        })
+    }
+
     pub fn destroy(&mut self) {
         // Remove instance from the active list.
         if let Some(instance) = INSTANCES.with(|instances| {
@@ -329,6 +401,16 @@ impl Ruffle {
                         .unwrap();
                     instance.mouse_wheel_callback = None;
                 }
+                if let Some(context_menu_callback) = &instance.context_menu_callback {
+                    let canvas_events: &EventTarget = instance.canvas.as_ref();
+                    canvas_events
+                        .remove_event_listener_with_callback(
+                            "contextmenu",
+                            context_menu_callback.as_ref().unchecked_ref(),
+                        )
+                        .unwrap();
+                    instance.context_menu_callback = None;
+                }
                 if let Some(key_down_callback) = &instance.key_down_callback {
                     window
                         .remove_event_listener_with_callback(
@@ -347,6 +429,24 @@ impl Ruffle {
                         .unwrap();
                     instance.key_up_callback = None;
                 }
+                if let Some(composition_update_callback) = &instance.composition_update_callback {
+                    window
+                        .remove_event_listener_with_callback(
+                            "compositionupdate",
+                            composition_update_callback.as_ref().unchecked_ref(),
+                        )
+                        .unwrap();
+                    instance.composition_update_callback = None;
+                }
+                if let Some(composition_end_callback) = &instance.composition_end_callback {
+                    window
+                        .remove_event_listener_with_callback(
+                            "compositionend",
+                            composition_end_callback.as_ref().unchecked_ref(),
+                        )
+                        .unwrap();
+                    instance.composition_end_callback = None;
+                }
                 if let Some(unload_callback) = &instance.unload_callback {
                     window
                         .remove_event_listener_with_callback(
@@ -356,6 +456,15 @@ impl Ruffle {
                         .unwrap();
                     instance.unload_callback = None;
                 }
+                if let Some(orientation_change_callback) = &instance.orientation_change_callback {
+                    window
+                        .remove_event_listener_with_callback(
+                            "orientationchange",
+                            orientation_change_callback.as_ref().unchecked_ref(),
+                        )
+                        .unwrap();
+                    instance.orientation_change_callback = None;
+                }
             }
 
             // Cancel the animation handler, if it's still active.
@@ -466,16 +575,45 @@ impl Ruffle {
         let video = Box::new(SoftwareVideoBackend::new());
         let log = Box::new(log_adapter::WebLogBackend::new(trace_observer.clone()));
         let ui = Box::new(ui::WebUiBackend::new(js_player.clone(), &canvas));
-        let core =
-            ruffle_core::Player::new(renderer, audio, navigator, storage, locale, video, log, ui)?;
+        // Browsers only allow reading the clipboard asynchronously, so text is
+        // copied and pasted within the player.
+        let clipboard = Box::new(MemoryClipboardBackend::new());
+        let gamepad = Box::new(gamepad::WebGamepadBackend::new());
+        // Browsers can't open raw TCP connections, so sockets always fail to
+        // connect.
+        let socket = Box::new(NullSocketBackend::new());
+        let local_connection = Box::new(InProcessLocalConnectionBackend::new(
+            LOCAL_CONNECTION_BUS.with(LocalConnectionBus::clone),
+        ));
+        let core = ruffle_core::Player::new(
+            renderer,
+            audio,
+            navigator,
+            storage,
+            locale,
+            video,
+            log,
+            ui,
+            clipboard,
+            gamepad,
+            socket,
+            local_connection,
+        )?;
         {
             let mut core = core.lock().unwrap();
             if let Some(color) = config.background_color.and_then(parse_html_color) {
                 core.set_background_color(Some(color));
             }
             core.set_letterbox(config.letterbox);
+            core.set_quality(config.quality);
+            core.set_text_rendering(config.text_rendering);
+            core.set_gamepad_key_mapping(config.gamepad_key_mapping);
+            core.set_keyboard_navigation(config.keyboard_navigation);
             core.set_warn_on_unsupported_content(config.warn_on_unsupported_content);
             core.set_max_execution_duration(config.max_execution_duration);
+            core.set_loader_limits(config.loader_limits);
+            core.set_strict_mode(config.strict_mode);
+            core.set_permissive_security(config.permissive_security);
         }
 
         // Create instance.
@@ -494,9 +632,13 @@ impl Ruffle {
             window_mouse_down_callback: None,
             mouse_up_callback: None,
             mouse_wheel_callback: None,
+            context_menu_callback: None,
             key_down_callback: None,
             key_up_callback: None,
+            composition_update_callback: None,
+            composition_end_callback: None,
             unload_callback: None,
+            orientation_change_callback: None,
             timestamp: None,
             has_focus: false,
             trace_observer,
@@ -735,13 +877,41 @@ impl Ruffle {
                 instance.borrow_mut().mouse_wheel_callback = Some(mouse_wheel_callback);
             }
 
+            // Create context menu handler.
+            {
+                let context_menu_callback = Closure::wrap(Box::new(move |js_event: Event| {
+                    INSTANCES.with(move |instances| {
+                        let instances = instances.borrow();
+                        if let Some(instance) = instances.get(index) {
+                            // Only replace the browser's menu if the movie has items to show.
+                            let core = &instance.borrow().core;
+                            if core.lock().unwrap().show_context_menu() {
+                                js_event.prevent_default();
+                            }
+                        }
+                    });
+                })
+                    as Box<dyn FnMut(Event)>);
+
+                let canvas_events: &EventTarget = canvas.as_ref();
+                canvas_events
+                    .add_event_listener_with_callback(
+                        "contextmenu",
+                        context_menu_callback.as_ref().unchecked_ref(),
+                    )
+                    .unwrap();
+                let instance = instances.get(index).unwrap();
+                instance.borrow_mut().context_menu_callback = Some(context_menu_callback);
+            }
+
             // Create keydown event handler.
             {
                 let key_down_callback = Closure::wrap(Box::new(move |js_event: KeyboardEvent| {
                     INSTANCES.with(|instances| {
                         if let Some(instance) = instances.borrow().get(index) {
                             let instance = instance.borrow();
-                            if instance.has_focus {
+                            // Keys typed into an input method editor arrive as composition events.
+                            if instance.has_focus && !js_event.is_composing() {
                                 let mut core = instance.core.lock().unwrap();
                                 let ui = core.ui_mut().downcast_mut::<ui::WebUiBackend>().unwrap();
                                 ui.keydown(&js_event);
@@ -808,6 +978,55 @@ impl Ruffle {
                 instance.key_up_callback = Some(key_up_callback);
             }
 
+            // Create input method editor composition handlers.
+            {
+                let composition_update_callback =
+                    Closure::wrap(Box::new(move |js_event: CompositionEvent| {
+                        INSTANCES.with(|instances| {
+                            if let Some(instance) = instances.borrow().get(index) {
+                                let instance = instance.borrow();
+                                if instance.has_focus {
+                                    let text = js_event.data().unwrap_or_default();
+                                    let mut core = instance.core.lock().unwrap();
+                                    core.handle_event(PlayerEvent::TextComposition { text });
+                                }
+                            }
+                        });
+                    }) as Box<dyn FnMut(CompositionEvent)>);
+
+                window
+                    .add_event_listener_with_callback(
+                        "compositionupdate",
+                        composition_update_callback.as_ref().unchecked_ref(),
+                    )
+                    .unwrap();
+
+                let composition_end_callback =
+                    Closure::wrap(Box::new(move |js_event: CompositionEvent| {
+                        INSTANCES.with(|instances| {
+                            if let Some(instance) = instances.borrow().get(index) {
+                                let instance = instance.borrow();
+                                if instance.has_focus {
+                                    let text = js_event.data().unwrap_or_default();
+                                    let mut core = instance.core.lock().unwrap();
+                                    core.handle_event(PlayerEvent::TextCompositionEnd { text });
+                                }
+                            }
+                        });
+                    }) as Box<dyn FnMut(CompositionEvent)>);
+
+                window
+                    .add_event_listener_with_callback(
+                        "compositionend",
+                        composition_end_callback.as_ref().unchecked_ref(),
+                    )
+                    .unwrap();
+
+                let mut instance = instances.get(index).unwrap().borrow_mut();
+                instance.composition_update_callback = Some(composition_update_callback);
+                instance.composition_end_callback = Some(composition_end_callback);
+            }
+
             {
                 let unload_callback = Closure::wrap(Box::new(move |_| {
                     INSTANCES.with(|instances| {
@@ -829,6 +1048,34 @@ impl Ruffle {
                 instance.unload_callback = Some(unload_callback);
             }
 
+            // Create orientation change handler.
+            {
+                let orientation_change_callback = Closure::wrap(Box::new(move |_| {
+                    INSTANCES.with(|instances| {
+                        if let Some(instance) = instances.borrow().get(index) {
+                            let instance = instance.borrow();
+                            let orientation = window_orientation();
+                            instance.core.lock().unwrap().set_orientation(orientation);
+                        }
+                    });
+                })
+                    as Box<dyn FnMut(Event)>);
+
+                window
+                    .add_event_listener_with_callback(
+                        "orientationchange",
+                        orientation_change_callback.as_ref().unchecked_ref(),
+                    )
+                    .unwrap();
+                let mut instance = instances.get(index).unwrap().borrow_mut();
+                instance.orientation_change_callback = Some(orientation_change_callback);
+                instance
+                    .core
+                    .lock()
+                    .unwrap()
+                    .set_orientation(window_orientation());
+            }
+
             ruffle
         });
 
@@ -1134,6 +1381,24 @@ pub fn set_panic_handler() {
     });
 }
 
+/// The orientation of the device, from the angle the browser reports it is
+/// rotated by.
+fn window_orientation() -> StageOrientation {
+    let angle = web_sys::window()
+        .and_then(|window| js_sys::Reflect::get(&window, &JsValue::from_str("orientation")).ok())
+        .and_then(|angle| angle.as_f64());
+
+    match angle {
+        Some(angle) if angle == 0.0 => StageOrientation::Default,
+        Some(angle) if angle == 90.0 => StageOrientation::RotatedLeft,
+        Some(angle) if angle == -90.0 || angle == 270.0 => StageOrientation::RotatedRight,
+        Some(angle) if angle == 180.0 => StageOrientation::UpsideDown,
+        // Desktop browsers don't report an orientation at all.
+        None => StageOrientation::Default,
+        Some(_) => StageOrientation::Unknown,
+    }
+}
+
 fn populate_movie_parameters(input: &JsValue, output: &mut PropertyMap<String>) {
     if let Ok(keys) = js_sys::Reflect::own_keys(input) {
         for key in keys.values().into_iter().flatten() {
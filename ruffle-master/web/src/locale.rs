@@ -1,4 +1,4 @@
-use chrono::{DateTime, FixedOffset, Local, Offset, Utc};
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
 use ruffle_core::backend::locale::LocaleBackend;
 
 pub struct WebLocaleBackend();
@@ -17,4 +17,8 @@ impl LocaleBackend for WebLocaleBackend {
     fn get_timezone(&self) -> FixedOffset {
         Local::now().offset().fix()
     }
+
+    fn get_timezone_at(&self, time: DateTime<Utc>) -> FixedOffset {
+        Local.offset_from_utc_datetime(&time.naive_utc()).fix()
+    }
 }
@@ -1,8 +1,10 @@
 use super::JavascriptPlayer;
-use ruffle_core::backend::ui::{MouseCursor, UiBackend};
+use ruffle_core::backend::ui::{ContextMenuItem, MouseCursor, UiBackend};
 use ruffle_core::events::KeyCode;
+use ruffle_core::i18n::MessageCatalog;
 use ruffle_web_common::JsResult;
 use std::collections::HashSet;
+use wasm_bindgen::JsValue;
 use web_sys::{HtmlCanvasElement, KeyboardEvent};
 
 /// An implementation of `UiBackend` utilizing `web_sys` bindings to input
@@ -194,18 +196,26 @@ impl UiBackend for WebUiBackend {
         self.update_mouse_cursor();
     }
 
-    fn set_clipboard_content(&mut self, _content: String) {
-        log::warn!("set clipboard not implemented");
-    }
-
     fn is_fullscreen(&self) -> bool {
         self.js_player.is_fullscreen()
     }
 
-    fn display_unsupported_message(&self) {
+    fn set_fullscreen(&mut self, is_full: bool) -> bool {
+        self.js_player.set_fullscreen(is_full);
+        true
+    }
+
+    fn display_unsupported_message(&self, _messages: &dyn MessageCatalog) {
         self.js_player.display_unsupported_message()
     }
 
+    fn display_context_menu(&mut self, items: &[ContextMenuItem]) {
+        match JsValue::from_serde(items) {
+            Ok(items) => self.js_player.show_context_menu(&items),
+            Err(e) => log::error!("Unable to pass context menu items to JavaScript: {}", e),
+        }
+    }
+
     fn message(&self, message: &str) {
         self.js_player.display_message(message);
     }